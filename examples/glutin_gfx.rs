@@ -218,11 +218,19 @@ mod feature {
                     match kind {
                         render::PrimitiveKind::Rectangle { color } => {
                         },
+                        render::PrimitiveKind::RoundedRect { color, radius } => {
+                        },
+                        render::PrimitiveKind::Border { color, thickness } => {
+                        },
+                        render::PrimitiveKind::Shadow { color, blur, offset } => {
+                        },
                         render::PrimitiveKind::Polygon { color, points } => {
                         },
+                        render::PrimitiveKind::Path { color, fill_rule, segments } => {
+                        },
                         render::PrimitiveKind::Lines { color, cap, thickness, points } => {
                         },
-                        render::PrimitiveKind::Image { color, source_rect } => {
+                        render::PrimitiveKind::Image { image_id, color, source_rect } => {
                         },
                         render::PrimitiveKind::Text { color, text, font_id } => {
                             let positioned_glyphs = text.positioned_glyphs(dpi_factor);
@@ -114,10 +114,9 @@ mod feature {
                 }
             }
 
-            // If some input event has been received, update the GUI.
-            if ui.global_input.events().next().is_some() {
+            // Only update the GUI if some input has been received or a widget is animating.
+            if let Some(mut ui) = ui.set_widgets_if_needed() {
                 // Instantiate a GUI demonstrating every widget type provided by conrod.
-                let mut ui = ui.set_widgets();
                 support::gui(&mut ui, &ids, &mut app);
             }
 
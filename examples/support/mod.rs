@@ -54,6 +54,7 @@ pub fn theme() -> conrod::Theme {
         border_color: conrod::color::BLACK,
         border_width: 0.0,
         label_color: conrod::color::WHITE,
+        disabled_color: conrod::color::GREY,
         font_id: None,
         font_size_large: 26,
         font_size_medium: 18,
@@ -61,6 +62,8 @@ pub fn theme() -> conrod::Theme {
         widget_styling: std::collections::HashMap::new(),
         mouse_drag_threshold: 0.0,
         double_click_threshold: std::time::Duration::from_millis(500),
+        tooltip_delay: std::time::Duration::from_millis(500),
+        gamma_correct_blending: false,
     }
 }
 
@@ -0,0 +1,276 @@
+//! Types and functions for animating values over time, for use within widget transitions.
+
+use Scalar;
+use std::time::Duration;
+use utils::clamp;
+
+
+/// A curve describing how an animated value progresses from its start to its end.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Easing {
+    /// Progresses at a constant rate.
+    Linear,
+    /// Starts slowly and accelerates towards the end (quadratic).
+    EaseIn,
+    /// Starts quickly and decelerates towards the end (quadratic).
+    EaseOut,
+    /// Accelerates through the first half and decelerates through the second (quadratic).
+    EaseInOut,
+    /// Starts slowly and accelerates towards the end (cubic).
+    CubicIn,
+    /// Starts quickly and decelerates towards the end (cubic).
+    CubicOut,
+    /// Accelerates through the first half and decelerates through the second (cubic).
+    CubicInOut,
+}
+
+/// A transition from one value to another over some duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Animation {
+    /// The value at the start of the animation.
+    pub from: Scalar,
+    /// The value at the end of the animation.
+    pub to: Scalar,
+    /// The duration of the animation.
+    pub duration: Duration,
+    /// The curve along which the value progresses from `from` to `to`.
+    pub easing: Easing,
+}
+
+/// A damped spring that pulls a value towards some target, for natural-looking motion such as the
+/// settling of a released drag.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Spring {
+    /// The strength with which the spring pulls the value towards the target.
+    pub stiffness: Scalar,
+    /// The resistance opposing the velocity of the value.
+    pub damping: Scalar,
+    /// The mass being moved by the spring. Must be greater than `0.0`.
+    pub mass: Scalar,
+    /// The current value.
+    pub value: Scalar,
+    /// The current velocity of the value in units per second.
+    pub velocity: Scalar,
+    settled: bool,
+}
+
+/// A `Spring` is considered settled once both its displacement from the target and its velocity
+/// are below this threshold.
+pub const SPRING_EPSILON: Scalar = 1e-3;
+
+/// The largest interval in seconds over which a `Spring` is integrated in a single step.
+///
+/// Longer intervals are subdivided so that stiff springs remain stable at low frame rates.
+pub const MAX_SPRING_STEP: Scalar = 1.0 / 240.0;
+
+
+impl Easing {
+
+    /// Map the given linear progress `t` (from `0.0` to `1.0`) to eased progress.
+    ///
+    /// `t` is clamped to the range `0.0...1.0`.
+    pub fn ease(self, t: Scalar) -> Scalar {
+        let t = clamp(t, 0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut =>
+                if t < 0.5 { 2.0 * t * t } else { -1.0 + (4.0 - 2.0 * t) * t },
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => {
+                let u = t - 1.0;
+                u * u * u + 1.0
+            },
+            Easing::CubicInOut =>
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 * t - 2.0;
+                    0.5 * u * u * u + 1.0
+                },
+        }
+    }
+
+}
+
+impl Animation {
+
+    /// An animation from `from` to `to` over the given `duration` with `Linear` easing.
+    pub fn new(from: Scalar, to: Scalar, duration: Duration) -> Self {
+        Animation {
+            from: from,
+            to: to,
+            duration: duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Use the given `Easing` curve.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// The linear progress of the animation from `0.0` to `1.0` after the given `elapsed` time.
+    ///
+    /// Once `elapsed` reaches the `duration`, progress is `1.0`.
+    pub fn progress(&self, elapsed: Duration) -> Scalar {
+        if elapsed >= self.duration {
+            return 1.0;
+        }
+        secs(elapsed) / secs(self.duration)
+    }
+
+    /// The value of the animation after the given `elapsed` time.
+    ///
+    /// Once `elapsed` reaches the `duration`, this is exactly the `to` value.
+    pub fn value(&self, elapsed: Duration) -> Scalar {
+        if self.is_finished(elapsed) {
+            return self.to;
+        }
+        let t = self.easing.ease(self.progress(elapsed));
+        self.from + (self.to - self.from) * t
+    }
+
+    /// Whether or not the animation has finished after the given `elapsed` time.
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+
+}
+
+impl Spring {
+
+    /// A settled `Spring` at rest at `0.0` with the given parameters.
+    pub fn new(stiffness: Scalar, damping: Scalar, mass: Scalar) -> Self {
+        Spring {
+            stiffness: stiffness,
+            damping: damping,
+            mass: mass,
+            value: 0.0,
+            velocity: 0.0,
+            settled: true,
+        }
+    }
+
+    /// Start the `Spring` at rest at the given value.
+    pub fn at(mut self, value: Scalar) -> Self {
+        self.value = value;
+        self.velocity = 0.0;
+        self
+    }
+
+    /// Integrate the motion of the value towards the `target` over the interval `dt`, returning
+    /// the new value.
+    ///
+    /// Once both the displacement from the `target` and the velocity fall below `SPRING_EPSILON`,
+    /// the value snaps to the `target` and the `Spring` is settled.
+    pub fn step(&mut self, target: Scalar, dt: Duration) -> Scalar {
+        let mut remaining = secs(dt);
+        while remaining > 0.0 {
+            let h = remaining.min(MAX_SPRING_STEP);
+            let force = -self.stiffness * (self.value - target) - self.damping * self.velocity;
+            self.velocity += force / self.mass * h;
+            self.value += self.velocity * h;
+            remaining -= h;
+        }
+
+        let displacement = self.value - target;
+        self.settled = displacement.abs() < SPRING_EPSILON && self.velocity.abs() < SPRING_EPSILON;
+        if self.settled {
+            self.value = target;
+            self.velocity = 0.0;
+        }
+        self.value
+    }
+
+    /// Whether or not the `Spring` came to rest at its target during the last `step`, in which
+    /// case there is no need to continue animating.
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+}
+
+
+/// Convert the given `Duration` to seconds.
+pub fn secs(duration: Duration) -> Scalar {
+    duration.as_secs() as Scalar + duration.subsec_nanos() as Scalar * 1e-9
+}
+
+
+#[test]
+fn easing_should_start_at_zero_and_end_at_one() {
+    let easings = [
+        Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut,
+        Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut,
+    ];
+    for &easing in easings.iter() {
+        assert_eq!(0.0, easing.ease(0.0));
+        assert_eq!(1.0, easing.ease(1.0));
+        assert_eq!(0.0, easing.ease(-1.0));
+        assert_eq!(1.0, easing.ease(2.0));
+    }
+}
+
+#[test]
+fn easing_should_sample_the_expected_midpoints() {
+    assert_eq!(0.5, Easing::Linear.ease(0.5));
+    assert_eq!(0.25, Easing::EaseIn.ease(0.5));
+    assert_eq!(0.75, Easing::EaseOut.ease(0.5));
+    assert_eq!(0.5, Easing::EaseInOut.ease(0.5));
+    assert_eq!(0.125, Easing::CubicIn.ease(0.5));
+    assert_eq!(0.875, Easing::CubicOut.ease(0.5));
+    assert_eq!(0.5, Easing::CubicInOut.ease(0.5));
+}
+
+#[test]
+fn animation_value_should_follow_its_easing_and_clamp_to_the_end() {
+    let duration = Duration::from_millis(200);
+    let animation = Animation::new(10.0, 20.0, duration).easing(Easing::EaseIn);
+    assert_eq!(10.0, animation.value(Duration::from_millis(0)));
+    assert_eq!(12.5, animation.value(Duration::from_millis(100)));
+    assert_eq!(20.0, animation.value(duration));
+    assert_eq!(20.0, animation.value(Duration::from_secs(5)));
+    assert!(!animation.is_finished(Duration::from_millis(100)));
+    assert!(animation.is_finished(Duration::from_secs(5)));
+
+    // A zero duration animation immediately yields the end value.
+    let instant = Animation::new(10.0, 20.0, Duration::from_millis(0));
+    assert_eq!(20.0, instant.value(Duration::from_millis(0)));
+}
+
+#[test]
+fn spring_should_converge_to_the_target_and_settle() {
+    let frame = Duration::from_millis(16);
+    let mut spring = Spring::new(170.0, 26.0, 1.0).at(0.0);
+    assert!(spring.is_settled());
+
+    spring.step(100.0, frame);
+    assert!(!spring.is_settled());
+    assert!(spring.value > 0.0 && spring.value < 100.0);
+
+    let mut frames = 0;
+    while !spring.is_settled() {
+        spring.step(100.0, frame);
+        frames += 1;
+        assert!(frames < 1_000, "the spring should settle within a reasonable number of frames");
+    }
+    assert_eq!(100.0, spring.value);
+    assert_eq!(0.0, spring.velocity);
+
+    // Remains settled for as long as the target is unchanged.
+    assert_eq!(100.0, spring.step(100.0, frame));
+    assert!(spring.is_settled());
+    spring.step(0.0, frame);
+    assert!(!spring.is_settled());
+}
+
+#[test]
+fn spring_should_remain_stable_over_long_intervals() {
+    let mut spring = Spring::new(1_000.0, 10.0, 1.0).at(0.0);
+    spring.step(1.0, Duration::from_secs(30));
+    assert!(spring.is_settled());
+    assert_eq!(1.0, spring.value);
+}
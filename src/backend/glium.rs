@@ -22,19 +22,20 @@ pub enum Command<'a> {
 
 /// A `Command` for drawing to the target.
 ///
-/// Each variant describes how to draw the contents of the vertex buffer.
+/// Each variant describes how to draw a range of the vertex buffer along with the indices of the
+/// triangles within that range. Each index is relative to the start of the range.
 #[derive(Clone, Debug)]
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
-    Image(widget::Id, &'a [Vertex]),
+    Image(widget::Id, &'a [Vertex], &'a [u32]),
     /// A range of vertices representing plain triangles.
-    Plain(&'a [Vertex]),
+    Plain(&'a [Vertex], &'a [u32]),
 }
 
 enum PreparedCommand {
-    Image(widget::Id, std::ops::Range<usize>),
-    Plain(std::ops::Range<usize>),
+    Image(widget::Id, render::IndexedRange),
+    Plain(render::IndexedRange),
     Scizzor(glium::Rect),
 }
 
@@ -50,13 +51,13 @@ pub struct Renderer {
     program: glium::Program,
     glyph_cache: GlyphCache,
     commands: Vec<PreparedCommand>,
-    vertices: Vec<Vertex>,
+    vertices: render::IndexedVertices<Vertex>,
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
 pub struct Commands<'a> {
     commands: std::slice::Iter<'a, PreparedCommand>,
-    vertices: &'a [Vertex],
+    vertices: &'a render::IndexedVertices<Vertex>,
 }
 
 /// Possible errors that may occur during a call to `Renderer::new`.
@@ -73,6 +74,8 @@ pub enum RendererCreationError {
 pub enum DrawError {
     /// Errors that might occur upon construction of a `glium::VertexBuffer`.
     Buffer(glium::vertex::BufferCreationError),
+    /// Errors that might occur upon construction of a `glium::IndexBuffer`.
+    IndexBuffer(glium::index::BufferCreationError),
     /// Errors that might occur when drawing to the `glium::Surface`.
     Draw(glium::DrawError),
 }
@@ -196,6 +199,9 @@ pub fn draw_parameters() -> glium::DrawParameters<'static> {
 ///
 /// sRGB is the default color space for image editors, pictures, internet etc.
 /// Linear gamma yields better results when doing math with colors.
+///
+/// `render::Primitives` always yield sRGB colors, so this is applied to every color drawn by the
+/// `Renderer`, which assumes an sRGB framebuffer.
 pub fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
     fn component(f: f32) -> f32 {
         // Taken from https://github.com/PistonDevelopers/graphics/src/color.rs#L42
@@ -262,7 +268,7 @@ impl Renderer {
             program: program,
             glyph_cache: glyph_cache,
             commands: Vec::new(),
-            vertices: Vec::new(),
+            vertices: render::IndexedVertices::new(),
         })
     }
 
@@ -288,33 +294,36 @@ impl Renderer {
         commands.clear();
         vertices.clear();
 
-        enum State {
-            Image { id: widget::Id, start: usize },
-            Plain { start: usize },
-        }
-
-        let mut current_state = State::Plain { start: 0 };
-
-        // Switches to the `Plain` state and completes the previous `Command` if not already in the
-        // `Plain` state.
-        macro_rules! switch_to_plain_state {
-            () => {
-                match current_state {
-                    State::Plain { .. } => (),
-                    State::Image { id, start } => {
-                        commands.push(PreparedCommand::Image(id, start..vertices.len()));
-                        current_state = State::Plain { start: vertices.len() };
-                    },
+        // Each `Batch` of primitives is drawn via a single command.
+        let mut batcher = render::Batcher::new();
+
+        // Completes the vertices of the given `Batch` with the command that draws them.
+        macro_rules! finish_batch {
+            ($batch:expr) => {
+                match $batch.kind {
+                    render::BatchKind::Plain =>
+                        commands.push(PreparedCommand::Plain(vertices.finish_batch())),
+                    render::BatchKind::Image(id) =>
+                        commands.push(PreparedCommand::Image(id, vertices.finish_batch())),
+                    // `Other` primitives produce no vertices.
+                    render::BatchKind::Other(_) => (),
                 }
             };
         }
 
         // Framebuffer dimensions and the "dots per inch" factor.
+        //
+        // The `Ui`'s scale factor is used where one was set. Otherwise (i.e. at the default of
+        // `1.0`) the window's hidpi factor is used, so that apps that never set a scale factor
+        // still render at the window's DPI.
         let (screen_w, screen_h) = display.get_framebuffer_dimensions();
         let (win_w, win_h) = (screen_w as Scalar, screen_h as Scalar);
         let half_win_w = win_w / 2.0;
         let half_win_h = win_h / 2.0;
-        let dpi_factor = display.get_window().map(|w| w.hidpi_factor()).unwrap_or(1.0) as Scalar;
+        let dpi_factor = match primitives.scale_factor() {
+            scale_factor if scale_factor != 1.0 => scale_factor,
+            _ => display.get_window().map(|w| w.hidpi_factor()).unwrap_or(1.0) as Scalar,
+        };
 
         // Functions for converting for conrod scalar coords to GL vertex coords (-1.0 to 1.0).
         let vx = |x: Scalar| (x * dpi_factor / half_win_w) as f32;
@@ -343,32 +352,24 @@ impl Renderer {
 
         // Draw each primitive in order of depth.
         while let Some(primitive) = primitives.next_primitive() {
-            let render::Primitive { id, kind, scizzor, rect } = primitive;
+
+            // Finish the current command if the primitive begins a new batch.
+            if let Some(batch) = batcher.push(primitive.batch_kind(), primitive.scizzor) {
+                finish_batch!(batch);
+            }
+
+            let render::Primitive { kind, scizzor, rect, .. } = primitive;
 
             // Check for a `Scizzor` command.
             let new_scizzor = rect_to_glium_rect(scizzor);
             if new_scizzor != current_scizzor {
-                // Finish the current command.
-                match current_state {
-                    State::Plain { start } =>
-                        commands.push(PreparedCommand::Plain(start..vertices.len())),
-                    State::Image { id, start } =>
-                        commands.push(PreparedCommand::Image(id, start..vertices.len())),
-                }
-
-                // Update the scizzor and produce a command.
                 current_scizzor = new_scizzor;
                 commands.push(PreparedCommand::Scizzor(new_scizzor));
-
-                // Set the state back to plain drawing.
-                current_state = State::Plain { start: vertices.len() };
             }
 
             match kind {
 
                 render::PrimitiveKind::Rectangle { color } => {
-                    switch_to_plain_state!();
-
                     let color = gamma_srgb_to_linear(color.to_fsa());
                     let (l, r, b, t) = rect.l_r_b_t();
 
@@ -382,17 +383,68 @@ impl Renderer {
                         }
                     };
 
-                    let mut push_v = |x, y| vertices.push(v(x, y));
+                    vertices.push_quad([v(l, t), v(l, b), v(r, b), v(r, t)]);
+                },
+
+                render::PrimitiveKind::RoundedRect { color, radius } => {
+                    let color = gamma_srgb_to_linear(color.to_fsa());
+
+                    let v = |p: [Scalar; 2]| {
+                        Vertex {
+                            position: [vx(p[0]), vy(p[1])],
+                            tex_coords: [0.0, 0.0],
+                            color: color,
+                            mode: MODE_GEOMETRY,
+                        }
+                    };
+
+                    // The rounded rectangle is convex, so we can triangulate its outline as a fan
+                    // in the same manner as the `Polygon`.
+                    let points = render::rounded_rect_points(rect, radius);
+                    let first_v = v(points[0]);
+                    let mut prev_v = v(points[1]);
+                    for &p in &points[2..] {
+                        let v = v(p);
+                        vertices.push_triangle([first_v, prev_v, v]);
+                        prev_v = v;
+                    }
+                },
+
+                render::PrimitiveKind::Border { color, thickness } => {
+                    let color = gamma_srgb_to_linear(color.to_fsa());
+
+                    let v = |x, y| {
+                        Vertex {
+                            position: [vx(x), vy(y)],
+                            tex_coords: [0.0, 0.0],
+                            color: color,
+                            mode: MODE_GEOMETRY,
+                        }
+                    };
+
+                    // Draw each edge of the border as a rectangle.
+                    for edge in &render::border_rects(rect, thickness) {
+                        let (l, r, b, t) = edge.l_r_b_t();
+                        vertices.push_quad([v(l, t), v(l, b), v(r, b), v(r, t)]);
+                    }
+                },
 
-                    // Bottom left triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(l, b);
+                render::PrimitiveKind::Shadow { color, blur, offset } => {
+                    let color = gamma_srgb_to_linear(color.to_fsa());
 
-                    // Top right triangle.
-                    push_v(l, t);
-                    push_v(r, b);
-                    push_v(r, t);
+                    // Fade the shadow out by interpolating the alpha of each vertex.
+                    let v = |(p, opacity): ([Scalar; 2], f32)| {
+                        Vertex {
+                            position: [vx(p[0]), vy(p[1])],
+                            tex_coords: [0.0, 0.0],
+                            color: [color[0], color[1], color[2], color[3] * opacity],
+                            mode: MODE_GEOMETRY,
+                        }
+                    };
+
+                    for triangle in &render::shadow_triangles(rect, blur, offset) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
+                    }
                 },
 
                 render::PrimitiveKind::Polygon { color, points } => {
@@ -401,8 +453,6 @@ impl Renderer {
                         continue;
                     }
 
-                    switch_to_plain_state!();
-
                     let color = gamma_srgb_to_linear(color.to_fsa());
 
                     let v = |p: [Scalar; 2]| {
@@ -426,79 +476,62 @@ impl Renderer {
                     let mut prev_v = v(points[1]);
                     for &p in &points[2..] {
                         let v = v(p);
-                        vertices.push(first_v);
-                        vertices.push(prev_v);
-                        vertices.push(v);
+                        vertices.push_triangle([first_v, prev_v, v]);
                         prev_v = v;
                     }
                 },
 
-                render::PrimitiveKind::Lines { color, cap, thickness, points } => {
+                render::PrimitiveKind::Path { color, fill_rule, segments } => {
+                    let color = gamma_srgb_to_linear(color.to_fsa());
+
+                    let v = |p: [Scalar; 2]| {
+                        Vertex {
+                            position: [vx(p[0]), vy(p[1])],
+                            tex_coords: [0.0, 0.0],
+                            color: color,
+                            mode: MODE_GEOMETRY,
+                        }
+                    };
+
+                    for triangle in &render::tessellate_path(segments, fill_rule) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
+                    }
+                },
+
+                render::PrimitiveKind::Lines { color, thickness, points, .. } => {
 
                     // We need at least two points to draw any lines.
                     if points.len() < 2 {
                         continue;
                     }
 
-                    switch_to_plain_state!();
-
                     let color = gamma_srgb_to_linear(color.to_fsa());
 
-                    let v = |p: [Scalar; 2]| {
+                    // Feather the edges of the lines by interpolating the alpha of each vertex.
+                    let v = |(p, opacity): ([Scalar; 2], f32)| {
                         Vertex {
                             position: [vx(p[0]), vy(p[1])],
                             tex_coords: [0.0, 0.0],
-                            color: color,
+                            color: [color[0], color[1], color[2], color[3] * opacity],
                             mode: MODE_GEOMETRY,
                         }
                     };
 
-                    // Convert each line to a rectangle for triangulation.
-                    //
-                    // TODO: handle `cap` and properly join consecutive lines considering
-                    // the miter. Discussion here:
-                    // https://forum.libcinder.org/topic/smooth-thick-lines-using-geometry-shader#23286000001269127
-                    let mut a = points[0];
-                    for &b in &points[1..] {
-
-                        let direction = [b[0] - a[0], b[1] - a[1]];
-                        let mag = (direction[0].powi(2) + direction[1].powi(2)).sqrt();
-                        let unit = [direction[0] / mag, direction[1] / mag];
-                        let normal = [-unit[1], unit[0]];
-                        let half_thickness = thickness / 2.0;
-
-                        // A perpendicular line with length half the thickness.
-                        let n = [normal[0] * half_thickness, normal[1] * half_thickness];
-
-                        // The corners of the rectangle as GL vertices.
-                        let (r1, r2, r3, r4);
-                        r1 = v([a[0] + n[0], a[1] + n[1]]);
-                        r2 = v([a[0] - n[0], a[1] - n[1]]);
-                        r3 = v([b[0] + n[0], b[1] + n[1]]);
-                        r4 = v([b[0] - n[0], b[1] - n[1]]);
-
-                        // Push the rectangle's vertices.
-                        let mut push_v = |v| vertices.push(v);
-                        push_v(r1);
-                        push_v(r4);
-                        push_v(r2);
-                        push_v(r1);
-                        push_v(r4);
-                        push_v(r3);
-
-                        a = b;
+                    // TODO: handle `cap`.
+                    for triangle in &render::tessellate_lines(points, thickness) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
                     }
                 },
 
-                render::PrimitiveKind::Text { color, text, font_id } => {
-                    switch_to_plain_state!();
-
-                    let positioned_glyphs = text.positioned_glyphs(dpi_factor as f32);
+                render::PrimitiveKind::Text { color, text, .. } => {
+                    // Glyphs drawn from a fallback font are cached under that font's `Id`.
+                    let (positioned_glyphs, font_ids) =
+                        text.positioned_glyphs_and_font_ids(dpi_factor as f32);
 
                     let GlyphCache { ref mut cache, ref mut texture } = *glyph_cache;
 
                     // Queue the glyphs to be cached.
-                    for glyph in positioned_glyphs.iter() {
+                    for (glyph, font_id) in positioned_glyphs.iter().zip(font_ids) {
                         cache.queue_glyph(font_id.index(), glyph.clone());
                     }
 
@@ -521,8 +554,6 @@ impl Renderer {
 
                     let color = gamma_srgb_to_linear(color.to_fsa());
 
-                    let cache_id = font_id.index();
-
                     let origin = text::rt::point(0.0, 0.0);
                     let to_gl_rect = |screen_rect: text::rt::Rect<i32>| text::rt::Rect {
                         min: origin
@@ -533,7 +564,8 @@ impl Renderer {
                                           1.0 - screen_rect.max.y as f32 / screen_h as f32 - 0.5)) * 2.0
                     };
 
-                    for g in positioned_glyphs {
+                    for (g, font_id) in positioned_glyphs.iter().zip(font_ids) {
+                        let cache_id = font_id.index();
                         if let Ok(Some((uv_rect, screen_rect))) = cache.rect_for(cache_id, g) {
                             let gl_rect = to_gl_rect(screen_rect);
                             let v = |p, t| Vertex {
@@ -542,35 +574,20 @@ impl Renderer {
                                 color: color,
                                 mode: MODE_TEXT,
                             };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
+                            vertices.push_quad([
+                                v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]),
+                                v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]),
+                                v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]),
+                                v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]),
+                            ]);
                         }
                     }
                 },
 
-                render::PrimitiveKind::Image { color, source_rect } => {
-                    // Switch to the `Textured` state if we're not in it already.
-                    let widget_id = id;
-                    match current_state {
-                        State::Image { id, .. } if id == widget_id => (),
-                        State::Plain { start } => {
-                            commands.push(PreparedCommand::Plain(start..vertices.len()));
-                            current_state = State::Image { id: id, start: vertices.len() };
-                        },
-                        State::Image { id, start } => {
-                            commands.push(PreparedCommand::Image(id, start..vertices.len()));
-                            current_state = State::Image { id: id, start: vertices.len() };
-                        },
-                    }
-
+                render::PrimitiveKind::Image { image_id, color, source_rect } => {
                     let color = gamma_srgb_to_linear(color.unwrap_or(color::WHITE).to_fsa());
 
-                    let (image_w, image_h) = image_map.get(&id).unwrap().dimensions();
+                    let (image_w, image_h) = image_map.get(&image_id).unwrap().dimensions();
                     let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
                     // Get the sides of the source rectangle as uv coordinates.
@@ -601,19 +618,13 @@ impl Renderer {
                         }
                     };
 
-                    let mut push_v = |x, y, t| vertices.push(v(x, y, t));
-
                     let (l, r, b, t) = rect.l_r_b_t();
-
-                    // Bottom left triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(l, b, [uv_l, uv_b]);
-
-                    // Top right triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(r, t, [uv_r, uv_t]);
+                    vertices.push_quad([
+                        v(l, t, [uv_l, uv_t]),
+                        v(l, b, [uv_l, uv_b]),
+                        v(r, b, [uv_r, uv_b]),
+                        v(r, t, [uv_r, uv_t]),
+                    ]);
                 },
 
                 // We have no special case widgets to handle.
@@ -623,11 +634,8 @@ impl Renderer {
         }
 
         // Enter the final command.
-        match current_state {
-            State::Plain { start } =>
-                commands.push(PreparedCommand::Plain(start..vertices.len())),
-            State::Image { id, start } =>
-                commands.push(PreparedCommand::Image(id, start..vertices.len())),
+        if let Some(batch) = batcher.finish() {
+            finish_batch!(batch);
         }
     }
 
@@ -644,7 +652,7 @@ impl Renderer {
               for<'a> glium::uniforms::Sampler<'a, T>: glium::uniforms::AsUniformValue,
     {
         let mut draw_params = draw_parameters();
-        let no_indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        let triangles = glium::index::PrimitiveType::TrianglesList;
         let uniforms = uniform! {
             tex: self.glyph_cache.texture()
                 .sampled()
@@ -661,22 +669,24 @@ impl Renderer {
                 Command::Draw(draw) => match draw {
 
                     // Draw text and plain 2D geometry.
-                    Draw::Plain(slice) => {
+                    Draw::Plain(slice, indices) => {
                         let vertex_buffer = try!(glium::VertexBuffer::new(facade, slice));
-                        surface.draw(&vertex_buffer, no_indices, &self.program, &uniforms, &draw_params).unwrap();
+                        let index_buffer = try!(glium::IndexBuffer::new(facade, triangles, indices));
+                        surface.draw(&vertex_buffer, &index_buffer, &self.program, &uniforms, &draw_params).unwrap();
                     },
 
                     // Draw an image whose texture data lies within the `image_map` at the
                     // given `id`.
-                    Draw::Image(id, slice) => {
+                    Draw::Image(id, slice, indices) => {
                         let vertex_buffer = glium::VertexBuffer::new(facade, slice).unwrap();
+                        let index_buffer = try!(glium::IndexBuffer::new(facade, triangles, indices));
                         let image = image_map.get(&id).unwrap();
                         let image_uniforms = uniform! {
                             tex: glium::uniforms::Sampler::new(image)
                                 .wrap_function(glium::uniforms::SamplerWrapFunction::Clamp)
                                 .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest),
                         };
-                        surface.draw(&vertex_buffer, no_indices, &self.program, &image_uniforms, &draw_params).unwrap();
+                        surface.draw(&vertex_buffer, &index_buffer, &self.program, &image_uniforms, &draw_params).unwrap();
                     },
 
                 }
@@ -691,13 +701,20 @@ impl Renderer {
 impl<'a> Iterator for Commands<'a> {
     type Item = Command<'a>;
     fn next(&mut self) -> Option<Self::Item> {
-        let Commands { ref mut commands, ref vertices } = *self;
+        let Commands { ref mut commands, vertices } = *self;
+        let slices = |range: &render::IndexedRange| {
+            (&vertices.vertices()[range.vertices.clone()], &vertices.indices()[range.indices.clone()])
+        };
         commands.next().map(|command| match *command {
             PreparedCommand::Scizzor(scizzor) => Command::Scizzor(scizzor),
-            PreparedCommand::Plain(ref range) =>
-                Command::Draw(Draw::Plain(&vertices[range.clone()])),
-            PreparedCommand::Image(id, ref range) =>
-                Command::Draw(Draw::Image(id, &vertices[range.clone()])),
+            PreparedCommand::Plain(ref range) => {
+                let (vertices, indices) = slices(range);
+                Command::Draw(Draw::Plain(vertices, indices))
+            },
+            PreparedCommand::Image(id, ref range) => {
+                let (vertices, indices) = slices(range);
+                Command::Draw(Draw::Image(id, vertices, indices))
+            },
         })
     }
 }
@@ -720,6 +737,12 @@ impl From<glium::vertex::BufferCreationError> for DrawError {
     }
 }
 
+impl From<glium::index::BufferCreationError> for DrawError {
+    fn from(err: glium::index::BufferCreationError) -> Self {
+        DrawError::IndexBuffer(err)
+    }
+}
+
 impl From<glium::DrawError> for DrawError {
     fn from(err: glium::DrawError) -> Self {
         DrawError::Draw(err)
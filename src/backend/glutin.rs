@@ -4,7 +4,7 @@
 
 extern crate glutin;
 
-use Scalar;
+use {MouseCursor, Scalar};
 use event::{Input, Motion};
 use input;
 use std;
@@ -242,3 +242,25 @@ pub fn map_mouse(mouse_button: glutin::MouseButton) -> input::MouseButton {
         _ => MouseButton::Unknown
     }
 }
+
+/// Maps conrod's `MouseCursor` to the equivalent glutin `MouseCursor`.
+///
+/// The result may be passed to `glutin::Window::set_cursor`.
+pub fn map_mouse_cursor(cursor: MouseCursor) -> glutin::MouseCursor {
+    match cursor {
+        MouseCursor::Arrow => glutin::MouseCursor::Default,
+        MouseCursor::Text => glutin::MouseCursor::Text,
+        MouseCursor::VerticalText => glutin::MouseCursor::VerticalText,
+        MouseCursor::Hand => glutin::MouseCursor::Hand,
+        MouseCursor::Crosshair => glutin::MouseCursor::Crosshair,
+        MouseCursor::Grab => glutin::MouseCursor::Grab,
+        MouseCursor::Grabbing => glutin::MouseCursor::Grabbing,
+        MouseCursor::Move => glutin::MouseCursor::Move,
+        MouseCursor::NotAllowed => glutin::MouseCursor::NotAllowed,
+        MouseCursor::Wait => glutin::MouseCursor::Wait,
+        MouseCursor::ResizeHorizontal => glutin::MouseCursor::EwResize,
+        MouseCursor::ResizeVertical => glutin::MouseCursor::NsResize,
+        MouseCursor::ResizeTopLeftBottomRight => glutin::MouseCursor::NwseResize,
+        MouseCursor::ResizeTopRightBottomLeft => glutin::MouseCursor::NeswResize,
+    }
+}
@@ -4,9 +4,15 @@
 //! is `conrod::render::Primitives`. The following feature-gated backend modules provide helper
 //! functionality for converting events and rendering primitives in a way that is suitable to each.
 //!
+//! The `software` module has no additional dependencies and so is always available.
+//!
 //! If there is a popular backend that you would like to see support for that is currently missing
 //! from this module, feel free to open an issue or pull request at the conrod repository.
 
 #[cfg(feature="glium")] pub mod glium;
 #[cfg(feature="glutin")] pub mod glutin;
 #[cfg(feature="piston")] pub mod piston;
+#[cfg(feature="sdl2")] pub mod sdl2;
+pub mod software;
+#[cfg(feature="wgpu")] pub mod wgpu;
+#[cfg(feature="winit")] pub mod winit;
@@ -2,7 +2,7 @@
 
 extern crate graphics as piston_graphics;
 
-use Rect;
+use {Rect, Scalar};
 use image;
 use render;
 use text;
@@ -88,7 +88,7 @@ pub fn primitive<'a, Img, G, T, C, F>(
           C: FnMut(&mut G, &mut T, text::rt::Rect<u32>, &[u8]),
           F: FnMut(&Img) -> &T,
 {
-    let render::Primitive { id, kind, scizzor, rect } = primitive;
+    let render::Primitive { kind, scizzor, rect, .. } = primitive;
     let view_size = context.get_view_size();
     // Translate the `context` to suit conrod's orientation (middle (0, 0), y pointing upwards).
     let context = context.trans(view_size[0] / 2.0, view_size[1] / 2.0).scale(1.0, -1.0);
@@ -103,12 +103,51 @@ pub fn primitive<'a, Img, G, T, C, F>(
             rectangle.draw(lbwh, &context.draw_state, context.transform, graphics);
         },
 
+        render::PrimitiveKind::RoundedRect { color, radius } => {
+            let points = render::rounded_rect_points(rect, radius);
+            let polygon = piston_graphics::Polygon::new(color.to_fsa());
+            polygon.draw(&points, &context.draw_state, context.transform, graphics);
+        },
+
+        render::PrimitiveKind::Border { color, thickness } => {
+            let rectangle = piston_graphics::Rectangle::new(color.to_fsa());
+            for edge in &render::border_rects(rect, thickness) {
+                let (l, b, w, h) = edge.l_b_w_h();
+                let lbwh = [l, b, w, h];
+                rectangle.draw(lbwh, &context.draw_state, context.transform, graphics);
+            }
+        },
+
+        render::PrimitiveKind::Shadow { color, blur, offset } => {
+            // Approximate the blurred falloff with layers of increasingly expanded, translucent
+            // rectangles, as the piston graphics `Rectangle` does not support per-vertex colors.
+            const LAYERS: usize = 4;
+            let inner = rect.shift(offset);
+            let outer = render::shadow_rect(rect, blur, offset);
+            let step = (inner.left() - outer.left()) / LAYERS as Scalar;
+            let mut color = color.to_fsa();
+            color[3] /= LAYERS as f32;
+            let rectangle = piston_graphics::Rectangle::new(color);
+            for i in 0..LAYERS {
+                let (l, b, w, h) = inner.pad(-step * (i + 1) as Scalar).l_b_w_h();
+                let lbwh = [l, b, w, h];
+                rectangle.draw(lbwh, &context.draw_state, context.transform, graphics);
+            }
+        },
+
         render::PrimitiveKind::Polygon { color, points } => {
             let color = color.to_fsa();
             let polygon = piston_graphics::Polygon::new(color);
             polygon.draw(points, &context.draw_state, context.transform, graphics);
         },
 
+        render::PrimitiveKind::Path { color, fill_rule, segments } => {
+            let polygon = piston_graphics::Polygon::new(color.to_fsa());
+            for triangle in &render::tessellate_path(segments, fill_rule) {
+                polygon.draw(triangle, &context.draw_state, context.transform, graphics);
+            }
+        },
+
         render::PrimitiveKind::Lines { color, cap, thickness, points } => {
             use widget::primitive::line::Cap;
             let color = color.to_fsa();
@@ -128,7 +167,7 @@ pub fn primitive<'a, Img, G, T, C, F>(
             }
         },
 
-        render::PrimitiveKind::Text { color, text, font_id } => {
+        render::PrimitiveKind::Text { color, text, .. } => {
 
             // Retrieve the "dots per inch" factor by dividing the window width by the view.
             //
@@ -136,13 +175,13 @@ pub fn primitive<'a, Img, G, T, C, F>(
             let dpi_factor = context.viewport
                 .map(|v| v.window_size[0] as f32 / view_size[0] as f32)
                 .unwrap_or(1.0);
-            let positioned_glyphs = text.positioned_glyphs(dpi_factor);
+            let (positioned_glyphs, font_ids) = text.positioned_glyphs_and_font_ids(dpi_factor);
             // Re-orient the context to top-left origin with *y* facing downwards, as the
             // `positioned_glyphs` yield pixel positioning.
             let context = context.scale(1.0, -1.0).trans(-view_size[0] / 2.0, -view_size[1] / 2.0);
 
             // Queue the glyphs to be cached.
-            for glyph in positioned_glyphs.iter() {
+            for (glyph, font_id) in positioned_glyphs.iter().zip(font_ids) {
                 glyph_cache.queue_glyph(font_id.index(), glyph.clone());
             }
 
@@ -151,12 +190,12 @@ pub fn primitive<'a, Img, G, T, C, F>(
                 cache_queued_glyphs(graphics, text_texture_cache, rect, data)
             }).unwrap();
 
-            let cache_id = font_id.index();
             let (tex_w, tex_h) = text_texture_cache.get_size();
             let color = color.to_fsa();
 
             let rectangles = positioned_glyphs.into_iter()
-                .filter_map(|g| glyph_cache.rect_for(cache_id, g).ok().unwrap_or(None))
+                .zip(font_ids)
+                .filter_map(|(g, id)| glyph_cache.rect_for(id.index(), g).ok().unwrap_or(None))
                 .map(|(uv_rect, screen_rect)| {
                     let rectangle = {
                         let div_dpi_factor = |s| (s as f32 / dpi_factor as f32) as f64;
@@ -187,8 +226,8 @@ pub fn primitive<'a, Img, G, T, C, F>(
                                               graphics);
         },
 
-        render::PrimitiveKind::Image { color, source_rect } => {
-            if let Some(img) = image_map.get(&id) {
+        render::PrimitiveKind::Image { image_id, color, source_rect } => {
+            if let Some(img) = image_map.get(&image_id) {
                 let mut image = piston_graphics::image::Image::new();
                 image.color = color.map(|c| c.to_fsa());
                 if let Some(source_rect) = source_rect {
@@ -0,0 +1,258 @@
+//! Functions for converting `sdl2::event::Event`s to `conrod::event::Input`s.
+
+extern crate sdl2;
+
+use Scalar;
+use event::{Input, Motion};
+use input;
+
+use self::sdl2::event::{Event, WindowEvent};
+use self::sdl2::keyboard::{Keycode, Scancode};
+use self::sdl2::mouse::MouseWheelDirection;
+
+/// The number of points scrolled per step of the mouse wheel.
+///
+/// This should be configurable (we should provide a LineDelta event to allow for this).
+pub const POINTS_PER_WHEEL_STEP: Scalar = 10.0;
+
+
+/// A function for converting an `sdl2::event::Event` to a `conrod::event::Input`.
+///
+/// `win_w` and `win_h` are the dimensions of the window in points, as returned by
+/// `sdl2::video::Window::size`.
+///
+/// Keys are mapped via their `Keycode` so that shortcuts follow the user's keyboard layout. Events
+/// without a `Keycode` fall back to mapping their `Scancode`, i.e. the physical position of the
+/// key on a US keyboard.
+pub fn convert_event(e: Event, win_w: Scalar, win_h: Scalar) -> Option<Input> {
+
+    // Translate the coordinates from top-left-origin-with-y-down to centre-origin-with-y-up.
+    let tx = |x: i32| x as Scalar - win_w / 2.0;
+    let ty = |y: i32| -(y as Scalar - win_h / 2.0);
+
+    match e {
+
+        Event::Window { win_event, .. } => match win_event {
+            WindowEvent::Resized(w, h) => Some(Input::Resize(w as u32, h as u32)),
+            WindowEvent::FocusGained => Some(Input::Focus(true)),
+            WindowEvent::FocusLost => Some(Input::Focus(false)),
+            WindowEvent::Enter => Some(Input::Cursor(true)),
+            WindowEvent::Leave => Some(Input::Cursor(false)),
+            _ => None,
+        },
+
+        Event::TextInput { text, .. } =>
+            Some(Input::Text(text)),
+
+        Event::KeyDown { keycode, scancode, .. } =>
+            map_key_event(keycode, scancode).map(|key| Input::Press(input::Button::Keyboard(key))),
+
+        Event::KeyUp { keycode, scancode, .. } =>
+            map_key_event(keycode, scancode).map(|key| Input::Release(input::Button::Keyboard(key))),
+
+        Event::MouseMotion { x, y, .. } =>
+            Some(Input::Move(Motion::MouseCursor(tx(x), ty(y)))),
+
+        Event::MouseWheel { x, y, direction, .. } => {
+            let scroll = normalize_wheel(x, y, direction);
+            Some(Input::Move(Motion::MouseScroll(scroll[0], scroll[1])))
+        },
+
+        Event::MouseButtonDown { mouse_btn, .. } =>
+            Some(Input::Press(input::Button::Mouse(map_mouse(mouse_btn)))),
+
+        Event::MouseButtonUp { mouse_btn, .. } =>
+            Some(Input::Release(input::Button::Mouse(map_mouse(mouse_btn)))),
+
+        _ => None,
+    }
+}
+
+/// Convert the given mouse wheel steps to a scroll in points with conrod's orientation.
+///
+/// Some platforms report the wheel as `Flipped` (for example when "natural" scrolling is enabled
+/// on macOS), in which case the steps are negated so that the same physical motion of the wheel
+/// always scrolls in the same direction.
+pub fn normalize_wheel(x: i32, y: i32, direction: MouseWheelDirection) -> [Scalar; 2] {
+    let (x, y) = match direction {
+        MouseWheelDirection::Flipped => (-x, -y),
+        _ => (x, y),
+    };
+    [POINTS_PER_WHEEL_STEP * x as Scalar, POINTS_PER_WHEEL_STEP * -y as Scalar]
+}
+
+/// Map the `Keycode` of a key event to a conrod `Key`, falling back to the `Scancode`.
+fn map_key_event(keycode: Option<Keycode>, scancode: Option<Scancode>) -> Option<input::Key> {
+    keycode.map(map_keycode).or_else(|| scancode.map(map_scancode))
+}
+
+/// Maps SDL2's `Keycode` to a conrod `Key`.
+///
+/// Conrod's `Key`s share the values of SDL2's keycodes, so no lookup is necessary.
+pub fn map_keycode(keycode: Keycode) -> input::Key {
+    input::Key::from(keycode as i32 as u32)
+}
+
+/// Maps SDL2's `Scancode` (the physical position of a key) to a conrod `Key`, assuming a US
+/// keyboard layout.
+pub fn map_scancode(scancode: Scancode) -> input::Key {
+    use input::Key;
+
+    match scancode {
+        Scancode::A => Key::A,
+        Scancode::B => Key::B,
+        Scancode::C => Key::C,
+        Scancode::D => Key::D,
+        Scancode::E => Key::E,
+        Scancode::F => Key::F,
+        Scancode::G => Key::G,
+        Scancode::H => Key::H,
+        Scancode::I => Key::I,
+        Scancode::J => Key::J,
+        Scancode::K => Key::K,
+        Scancode::L => Key::L,
+        Scancode::M => Key::M,
+        Scancode::N => Key::N,
+        Scancode::O => Key::O,
+        Scancode::P => Key::P,
+        Scancode::Q => Key::Q,
+        Scancode::R => Key::R,
+        Scancode::S => Key::S,
+        Scancode::T => Key::T,
+        Scancode::U => Key::U,
+        Scancode::V => Key::V,
+        Scancode::W => Key::W,
+        Scancode::X => Key::X,
+        Scancode::Y => Key::Y,
+        Scancode::Z => Key::Z,
+        Scancode::Num0 => Key::D0,
+        Scancode::Num1 => Key::D1,
+        Scancode::Num2 => Key::D2,
+        Scancode::Num3 => Key::D3,
+        Scancode::Num4 => Key::D4,
+        Scancode::Num5 => Key::D5,
+        Scancode::Num6 => Key::D6,
+        Scancode::Num7 => Key::D7,
+        Scancode::Num8 => Key::D8,
+        Scancode::Num9 => Key::D9,
+        Scancode::Return => Key::Return,
+        Scancode::Escape => Key::Escape,
+        Scancode::Backspace => Key::Backspace,
+        Scancode::Tab => Key::Tab,
+        Scancode::Space => Key::Space,
+        Scancode::Minus => Key::Minus,
+        Scancode::Equals => Key::Equals,
+        Scancode::LeftBracket => Key::LeftBracket,
+        Scancode::RightBracket => Key::RightBracket,
+        Scancode::Backslash => Key::Backslash,
+        Scancode::Semicolon => Key::Semicolon,
+        Scancode::Apostrophe => Key::Quote,
+        Scancode::Grave => Key::Backquote,
+        Scancode::Comma => Key::Comma,
+        Scancode::Period => Key::Period,
+        Scancode::Slash => Key::Slash,
+        Scancode::CapsLock => Key::CapsLock,
+        Scancode::F1 => Key::F1,
+        Scancode::F2 => Key::F2,
+        Scancode::F3 => Key::F3,
+        Scancode::F4 => Key::F4,
+        Scancode::F5 => Key::F5,
+        Scancode::F6 => Key::F6,
+        Scancode::F7 => Key::F7,
+        Scancode::F8 => Key::F8,
+        Scancode::F9 => Key::F9,
+        Scancode::F10 => Key::F10,
+        Scancode::F11 => Key::F11,
+        Scancode::F12 => Key::F12,
+        Scancode::PrintScreen => Key::PrintScreen,
+        Scancode::ScrollLock => Key::ScrollLock,
+        Scancode::Pause => Key::Pause,
+        Scancode::Insert => Key::Insert,
+        Scancode::Home => Key::Home,
+        Scancode::PageUp => Key::PageUp,
+        Scancode::Delete => Key::Delete,
+        Scancode::End => Key::End,
+        Scancode::PageDown => Key::PageDown,
+        Scancode::Right => Key::Right,
+        Scancode::Left => Key::Left,
+        Scancode::Down => Key::Down,
+        Scancode::Up => Key::Up,
+        Scancode::NumLockClear => Key::NumLockClear,
+        Scancode::KpDivide => Key::NumPadDivide,
+        Scancode::KpMultiply => Key::NumPadMultiply,
+        Scancode::KpMinus => Key::NumPadMinus,
+        Scancode::KpPlus => Key::NumPadPlus,
+        Scancode::KpEnter => Key::NumPadEnter,
+        Scancode::Kp0 => Key::NumPad0,
+        Scancode::Kp1 => Key::NumPad1,
+        Scancode::Kp2 => Key::NumPad2,
+        Scancode::Kp3 => Key::NumPad3,
+        Scancode::Kp4 => Key::NumPad4,
+        Scancode::Kp5 => Key::NumPad5,
+        Scancode::Kp6 => Key::NumPad6,
+        Scancode::Kp7 => Key::NumPad7,
+        Scancode::Kp8 => Key::NumPad8,
+        Scancode::Kp9 => Key::NumPad9,
+        Scancode::KpPeriod => Key::NumPadPeriod,
+        Scancode::KpEquals => Key::NumPadEquals,
+        Scancode::LCtrl => Key::LCtrl,
+        Scancode::LShift => Key::LShift,
+        Scancode::LAlt => Key::LAlt,
+        Scancode::LGui => Key::LGui,
+        Scancode::RCtrl => Key::RCtrl,
+        Scancode::RShift => Key::RShift,
+        Scancode::RAlt => Key::RAlt,
+        Scancode::RGui => Key::RGui,
+        Scancode::Application => Key::Application,
+        _ => Key::Unknown,
+    }
+}
+
+/// Maps SDL2's mouse button to Piston's mouse button.
+pub fn map_mouse(mouse_button: sdl2::mouse::MouseButton) -> input::MouseButton {
+    use input::MouseButton;
+    match mouse_button {
+        sdl2::mouse::MouseButton::Left => MouseButton::Left,
+        sdl2::mouse::MouseButton::Right => MouseButton::Right,
+        sdl2::mouse::MouseButton::Middle => MouseButton::Middle,
+        sdl2::mouse::MouseButton::X1 => MouseButton::X1,
+        sdl2::mouse::MouseButton::X2 => MouseButton::X2,
+        _ => MouseButton::Unknown,
+    }
+}
+
+
+#[test]
+fn keys_should_map_by_keycode_and_fall_back_to_scancode() {
+    use input::Key;
+    assert_eq!(Key::A, map_keycode(Keycode::A));
+    assert_eq!(Key::Return, map_keycode(Keycode::Return));
+    assert_eq!(Key::LShift, map_keycode(Keycode::LShift));
+    assert_eq!(Key::NumPadEnter, map_scancode(Scancode::KpEnter));
+    assert_eq!(Key::Quote, map_scancode(Scancode::Apostrophe));
+
+    // On an AZERTY keyboard, the key in the position of Q produces an A.
+    assert_eq!(Some(Key::A), map_key_event(Some(Keycode::A), Some(Scancode::Q)));
+    assert_eq!(Some(Key::Q), map_key_event(None, Some(Scancode::Q)));
+    assert_eq!(None, map_key_event(None, None));
+}
+
+#[test]
+fn flipped_wheels_should_scroll_in_the_same_direction_as_normal_wheels() {
+    assert_eq!([0.0, -20.0], normalize_wheel(0, 2, MouseWheelDirection::Normal));
+    assert_eq!([0.0, -20.0], normalize_wheel(0, -2, MouseWheelDirection::Flipped));
+    assert_eq!([10.0, 0.0], normalize_wheel(1, 0, MouseWheelDirection::Normal));
+    assert_eq!([10.0, 0.0], normalize_wheel(-1, 0, MouseWheelDirection::Flipped));
+    assert_eq!([0.0, 10.0], normalize_wheel(0, -1, MouseWheelDirection::Unknown(0)));
+
+    let scrolled = Event::MouseWheel {
+        timestamp: 0,
+        window_id: 0,
+        which: 0,
+        x: 0,
+        y: -1,
+        direction: MouseWheelDirection::Flipped,
+    };
+    assert_eq!(Some(Input::Move(Motion::MouseScroll(0.0, -10.0))),
+               convert_event(scrolled, 800.0, 600.0));
+}
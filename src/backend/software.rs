@@ -0,0 +1,943 @@
+//! A software rasterizer for rendering conrod primitives to an in-memory RGBA image.
+//!
+//! This backend requires neither a window nor a GPU, making it suitable for headless rendering
+//! such as screenshot and pixel-regression tests.
+
+use {Color, Point, Rect, Scalar};
+use color;
+use render;
+use std;
+use std::collections::HashMap;
+use text;
+
+
+/// The number of samples taken along each axis of a pixel when estimating how much of it is
+/// covered by the edge of a polygon.
+pub const SAMPLES_PER_AXIS: usize = 4;
+
+/// The default number of bytes that may be occupied by the rasterized glyphs of a `GlyphCache`.
+pub const DEFAULT_GLYPH_CACHE_BYTES: usize = 1024 * 1024;
+
+
+/// An 8-bit RGBA image stored row by row from the top-left pixel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// Four bytes (red, green, blue and alpha) for every pixel.
+    pub data: Vec<u8>,
+}
+
+
+impl Image {
+
+    /// A fully transparent image with the given dimensions.
+    pub fn new(width: u32, height: u32) -> Self {
+        Image {
+            width: width,
+            height: height,
+            data: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Fill every pixel of the image with the given `color`.
+    pub fn clear(&mut self, color: Color) {
+        let rgba = color.to_byte_fsa();
+        for pixel in self.data.chunks_mut(4) {
+            pixel.copy_from_slice(&rgba);
+        }
+    }
+
+    /// The RGBA value of the pixel at the given position from the top-left of the image.
+    ///
+    /// Returns `None` if the position lies outside of the image.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let d = &self.data;
+        Some([d[i], d[i + 1], d[i + 2], d[i + 3]])
+    }
+
+    /// Blend the given sRGB color onto the pixel at the given position using "source over" alpha
+    /// compositing, where `coverage` scales the alpha of the color.
+    ///
+    /// If `linear` is `true`, the color channels are composited in linear RGB space and the result
+    /// is encoded back to sRGB.
+    fn blend(&mut self, x: u32, y: u32, color: [f32; 4], coverage: f32, linear: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        let src_a = color[3] * coverage;
+        let dst_a = self.data[i + 3] as f32 / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        if out_a <= 0.0 {
+            return;
+        }
+        let decode = |c: f32| if linear { color::srgb_to_linear(c) } else { c };
+        let encode = |c: f32| if linear { color::linear_to_srgb(c) } else { c };
+        for c in 0..3 {
+            let src = decode(color[c]);
+            let dst = decode(self.data[i + c] as f32 / 255.0);
+            let out = (src * src_a + dst * dst_a * (1.0 - src_a)) / out_a;
+            self.data[i + c] = to_byte(encode(out));
+        }
+        self.data[i + 3] = to_byte(out_a);
+    }
+
+}
+
+
+/// Renders primitives onto `Image`s while retaining work between frames.
+///
+/// Rather than rasterizing every glyph each time that it is drawn, the `Renderer` keeps the
+/// coverage of each rasterized glyph within its `GlyphCache`. Similarly, the pixel coverage of
+/// every other shape is kept within a `render::GeometryCache` and reused for as long as the
+/// primitive that produced it remains unchanged.
+pub struct Renderer {
+    glyph_cache: GlyphCache,
+    /// The rasterized geometry of each primitive, or `None` for those drawn without geometry.
+    geometry_cache: render::GeometryCache<Option<Geometry>>,
+    /// The dimensions of the image last drawn to, as the cached geometry is in pixel coordinates.
+    dimensions: (u32, u32),
+}
+
+/// The rasterized shape of a single primitive, along with the color in which it is blended.
+struct Geometry {
+    color: [f32; 4],
+    masks: Vec<Mask>,
+}
+
+/// The coverage of each pixel within a rectangular region of an image, row by row.
+struct Mask {
+    left: u32,
+    top: u32,
+    width: u32,
+    coverage: Vec<f32>,
+}
+
+/// The rasterized coverage of each glyph drawn by a `Renderer`, stored in CPU memory.
+///
+/// The total size of the stored glyphs is bounded by a `text::cache::Lru`, which evicts the least
+/// recently drawn glyphs once its budget would be exceeded.
+pub struct GlyphCache {
+    lru: text::cache::Lru<GlyphKey>,
+    glyphs: HashMap<GlyphKey, Vec<f32>>,
+}
+
+/// Uniquely identifies the rasterization of a glyph.
+///
+/// Glyphs are rasterized differently depending on their position within a pixel, so the
+/// fractional part of the position is a part of the key.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    font_id: text::font::Id,
+    glyph_id: text::GlyphId,
+    scale: (u32, u32),
+    offset: (u32, u32),
+}
+
+
+impl GlyphCache {
+
+    /// An empty cache that may store up to `max_bytes` of rasterized glyphs.
+    pub fn new(max_bytes: usize) -> Self {
+        GlyphCache {
+            lru: text::cache::Lru::new(max_bytes),
+            glyphs: HashMap::new(),
+        }
+    }
+
+    /// The number of glyphs currently stored.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Whether or not no glyphs are currently stored.
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// The number of bytes occupied by all stored glyphs.
+    pub fn total_bytes(&self) -> usize {
+        self.lru.total_bytes()
+    }
+
+    /// Change the maximum number of bytes occupied by all stored glyphs, discarding the least
+    /// recently drawn glyphs if necessary.
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        for key in self.lru.set_max_bytes(max_bytes) {
+            self.glyphs.remove(&key);
+        }
+    }
+
+    /// The coverage of each pixel within the glyph's `pixel_bounding_box`, row by row, rasterizing
+    /// and storing the glyph if it is not already stored.
+    fn coverage(&mut self, font_id: text::font::Id, glyph: &text::PositionedGlyph) -> &[f32] {
+        let position = glyph.position();
+        let scale = glyph.unpositioned().scale();
+        let key = GlyphKey {
+            font_id: font_id,
+            glyph_id: glyph.id(),
+            scale: (scale.x.to_bits(), scale.y.to_bits()),
+            offset: ((position.x - position.x.floor()).to_bits(),
+                     (position.y - position.y.floor()).to_bits()),
+        };
+        if !self.lru.touch(&key) {
+            let width = glyph.pixel_bounding_box().map_or(0, |bb| bb.width() as usize);
+            let height = glyph.pixel_bounding_box().map_or(0, |bb| bb.height() as usize);
+            let mut coverage = vec![0.0; width * height];
+            glyph.draw(|x, y, c| coverage[y as usize * width + x as usize] = c);
+            for evicted in self.lru.insert(key, coverage.len() * 4) {
+                self.glyphs.remove(&evicted);
+            }
+            // A glyph too large for the budget is evicted immediately, but must still be drawn.
+            self.glyphs.insert(key, coverage);
+        }
+        &self.glyphs[&key]
+    }
+
+    /// Discard any glyph that is stored but no longer tracked by the `Lru`.
+    fn discard_untracked(&mut self) {
+        let lru = &self.lru;
+        self.glyphs.retain(|key, _| lru.contains(key));
+    }
+
+}
+
+
+impl Renderer {
+
+    /// A `Renderer` whose `GlyphCache` may store up to `DEFAULT_GLYPH_CACHE_BYTES`.
+    pub fn new() -> Self {
+        Self::with_glyph_cache_bytes(DEFAULT_GLYPH_CACHE_BYTES)
+    }
+
+    /// A `Renderer` whose `GlyphCache` may store up to the given number of bytes.
+    pub fn with_glyph_cache_bytes(max_bytes: usize) -> Self {
+        Renderer {
+            glyph_cache: GlyphCache::new(max_bytes),
+            geometry_cache: render::GeometryCache::new(),
+            dimensions: (0, 0),
+        }
+    }
+
+    /// The cache of rasterized glyphs.
+    pub fn glyph_cache(&self) -> &GlyphCache {
+        &self.glyph_cache
+    }
+
+    /// Mutable access to the cache of rasterized glyphs.
+    pub fn glyph_cache_mut(&mut self) -> &mut GlyphCache {
+        &mut self.glyph_cache
+    }
+
+    /// Draw each of the given primitives onto the given `image` in order.
+    ///
+    /// See the `draw` function for details on how primitives are rasterized. The geometry of any
+    /// primitive that is not drawn is discarded.
+    pub fn draw<P>(&mut self, image: &mut Image, mut primitives: P)
+        where P: render::PrimitiveWalker,
+    {
+        self.sync_dimensions(image);
+        let linear = primitives.gamma_correct_blending();
+        let mut indices = render::PrimitiveIndices::new();
+        let mut drawn = std::collections::HashSet::new();
+        while let Some(primitive) = primitives.next_primitive() {
+            let index = indices.next(primitive.id);
+            drawn.insert((primitive.id, index));
+            let scizzor = primitive.scizzor;
+            self.draw_cached(image, primitive, index, scizzor, linear);
+        }
+        self.geometry_cache.retain(|id, index| drawn.contains(&(id, index)));
+        self.glyph_cache.discard_untracked();
+    }
+
+    /// Redraw only the pixels of the given `image` that lie within the `damage`.
+    ///
+    /// See the `redraw` function for details. The geometry of the primitives within the `damage`
+    /// is reused from previous calls to `draw` and `redraw` wherever it remains unchanged.
+    pub fn redraw<P>(&mut self, image: &mut Image, primitives: P, damage: Rect)
+        where P: render::PrimitiveWalker,
+    {
+        self.sync_dimensions(image);
+        let dimensions = self.dimensions;
+        let linear = primitives.gamma_correct_blending();
+        let (w, h) = (image.width as Scalar, image.height as Scalar);
+        let (left, right, top, bottom) = covered_pixel_bounds(dimensions, damage);
+        let damage = Rect::from_corners([left as Scalar - w / 2.0, h / 2.0 - bottom as Scalar],
+                                        [right as Scalar - w / 2.0, h / 2.0 - top as Scalar]);
+        for y in top..bottom {
+            let start = (y as usize * image.width as usize + left as usize) * 4;
+            let end = (y as usize * image.width as usize + right as usize) * 4;
+            for byte in &mut image.data[start..end] {
+                *byte = 0;
+            }
+        }
+        let Renderer { ref mut glyph_cache, ref mut geometry_cache, .. } = *self;
+        let tessellate = |primitive: &render::Primitive| rasterize(dimensions, primitive);
+        let draw = |primitive: render::Primitive, geometry: &Option<Geometry>| {
+            if let Some(scizzor) = primitive.scizzor.intersect(damage) {
+                draw_rasterized(image, glyph_cache, primitive, geometry, scizzor, linear);
+            }
+        };
+        render::draw_damaged(primitives, damage, geometry_cache, tessellate, draw);
+        glyph_cache.discard_untracked();
+    }
+
+    /// Discard all cached geometry if the given `image` differs in size from the last.
+    fn sync_dimensions(&mut self, image: &Image) {
+        let dimensions = (image.width, image.height);
+        if self.dimensions != dimensions {
+            self.dimensions = dimensions;
+            self.geometry_cache.clear();
+        }
+    }
+
+    /// Draw the given primitive, the `index`th produced by its widget, cropped to the `scizzor`.
+    ///
+    /// The geometry of the primitive is rasterized only if it is missing from the cache.
+    fn draw_cached(&mut self,
+                   image: &mut Image,
+                   primitive: render::Primitive,
+                   index: usize,
+                   scizzor: Rect,
+                   linear: bool)
+    {
+        if self.geometry_cache.get(&primitive, index).is_none() {
+            let geometry = rasterize(self.dimensions, &primitive);
+            self.geometry_cache.insert(&primitive, index, geometry);
+        }
+        let Renderer { ref mut glyph_cache, ref geometry_cache, .. } = *self;
+        match geometry_cache.get(&primitive, index) {
+            Some(geometry) =>
+                draw_rasterized(image, glyph_cache, primitive, geometry, scizzor, linear),
+            // Primitives that can't be cached (i.e. `Other`) are never drawn.
+            None => (),
+        }
+    }
+
+}
+
+
+/// Draw each of the given primitives onto the given `image` in order.
+///
+/// The image is assumed to cover the entire window, with one pixel per unit of conrod's
+/// coordinate space. Its pixels are always stored as gamma-encoded sRGB. Colors are composited in
+/// sRGB space unless the primitives report the `Theme`'s `gamma_correct_blending`, in which case
+/// each pixel is converted to linear RGB, composited, and encoded back to sRGB.
+///
+/// Every primitive is cropped to its `scizzor`, which includes the pixels whose centres lie within
+/// it. The edges of shapes are anti-aliased by the fraction of each pixel that they cover, so that
+/// shapes at sub-pixel positions are drawn with partially transparent edges rather than snapping to
+/// whole pixels. `Image` and `Other` primitives are skipped, as the rasterizer has no access to
+/// textures or to user drawing code.
+///
+/// Nothing is retained between calls. Use a `Renderer` to avoid rasterizing the same glyphs every
+/// frame.
+pub fn draw<P>(image: &mut Image, primitives: P)
+    where P: render::PrimitiveWalker,
+{
+    Renderer::new().draw(image, primitives)
+}
+
+/// Redraw only the pixels of the given `image` that lie within the `damage`, e.g. as produced by a
+/// `render::DamageTracker`, leaving all other pixels as they were drawn during the previous frame.
+///
+/// The damage is first expanded to whole pixels so that anti-aliased edges are redrawn entirely.
+/// Its pixels are then cleared, and only the primitives that intersect it are drawn, cropped to it.
+pub fn redraw<P>(image: &mut Image, primitives: P, damage: Rect)
+    where P: render::PrimitiveWalker,
+{
+    Renderer::new().redraw(image, primitives, damage)
+}
+
+/// Draw a single primitive, whose shape has been rasterized into the given `geometry`, onto the
+/// `image` cropped to the `scizzor`.
+///
+/// Primitives without any `geometry` are drawn as `Text` from the `glyph_cache` instead.
+fn draw_rasterized(image: &mut Image,
+                   glyph_cache: &mut GlyphCache,
+                   primitive: render::Primitive,
+                   geometry: &Option<Geometry>,
+                   scizzor: Rect,
+                   linear: bool)
+{
+    match *geometry {
+        Some(ref geometry) => draw_geometry(image, geometry, scizzor, linear),
+        None => {
+            let primitive = render::Primitive { scizzor: scizzor, ..primitive };
+            draw_text(image, glyph_cache, primitive, linear)
+        },
+    }
+}
+
+/// Rasterize the pixel coverage of the given primitive, uncropped by its `scizzor`.
+///
+/// Returns `None` for `Text`, whose glyphs are rasterized by a `GlyphCache`, as well as for the
+/// `Image` and `Other` primitives that are not drawn at all.
+///
+/// The geometry is positioned within an image of the given `dimensions` in pixels.
+fn rasterize(dimensions: (u32, u32), primitive: &render::Primitive) -> Option<Geometry> {
+    let rect = primitive.rect;
+    let (color, masks) = match primitive.kind {
+
+        render::PrimitiveKind::Rectangle { color } => {
+            (color, vec![mask(dimensions, rect, |pixel| area_coverage(rect, pixel))])
+        },
+
+        render::PrimitiveKind::RoundedRect { color, radius } => {
+            let points = render::rounded_rect_points(rect, radius);
+            let coverage = |pixel| supersample(pixel, |p| contains(&points, p));
+            (color, vec![mask(dimensions, rect, coverage)])
+        },
+
+        render::PrimitiveKind::Border { color, thickness } => {
+            let masks = render::border_rects(rect, thickness).iter()
+                .map(|&edge| mask(dimensions, edge, |pixel| area_coverage(edge, pixel)))
+                .collect();
+            (color, masks)
+        },
+
+        render::PrimitiveKind::Shadow { color, blur, offset } => {
+            let inner = rect.shift(offset);
+            let bounds = render::shadow_rect(rect, blur, offset);
+            let blur = render::clamp_shadow_blur(blur);
+            let mask = mask(dimensions, bounds, |pixel| {
+                let p = pixel.xy();
+                // Fade out linearly along each axis beyond the edges of the shifted `rect`.
+                let falloff = |distance: Scalar| match blur > 0.0 {
+                    true => (1.0 - distance.max(0.0) / blur).max(0.0) as f32,
+                    false => if distance > 0.0 { 0.0 } else { 1.0 },
+                };
+                let dx = (inner.left() - p[0]).max(p[0] - inner.right());
+                let dy = (inner.bottom() - p[1]).max(p[1] - inner.top());
+                falloff(dx) * falloff(dy)
+            });
+            (color, vec![mask])
+        },
+
+        render::PrimitiveKind::Polygon { color, points } => {
+            let bounds = bounding_rect(points);
+            let coverage = |pixel| supersample(pixel, |p| contains(points, p));
+            (color, vec![mask(dimensions, bounds, coverage)])
+        },
+
+        render::PrimitiveKind::Path { color, fill_rule, segments } => {
+            // Sample all triangles at once so that no seams appear along their shared edges.
+            let triangles = render::tessellate_path(segments, fill_rule);
+            let bounds = triangles.iter()
+                .map(|triangle| bounding_rect(triangle))
+                .fold(None, |acc: Option<Rect>, r| Some(acc.map_or(r, |acc| acc.max(r))));
+            let masks = bounds.into_iter()
+                .map(|bounds| mask(dimensions, bounds, |pixel| supersample(pixel, |p| {
+                    triangles.iter().any(|triangle| contains(triangle, p))
+                })))
+                .collect();
+            (color, masks)
+        },
+
+        render::PrimitiveKind::Lines { color, thickness, points, .. } => {
+            let half_thickness = thickness / 2.0;
+            let feather = render::LINE_FEATHER;
+            let bounds = bounding_rect(points).pad(-(half_thickness + feather));
+            let mask = mask(dimensions, bounds, |pixel| {
+                let p = pixel.xy();
+                // Fade out over the feather width beyond the edge of the line, in the same
+                // manner as the geometry produced by `render::tessellate_lines`.
+                let distance = points.windows(2)
+                    .map(|w| distance_to_segment(p, w[0], w[1]))
+                    .fold(std::f64::MAX, f64::min);
+                (1.0 - (distance - half_thickness) / feather).max(0.0).min(1.0) as f32
+            });
+            (color, vec![mask])
+        },
+
+        render::PrimitiveKind::Text { .. } |
+        render::PrimitiveKind::Image { .. } |
+        render::PrimitiveKind::Other(_) => return None,
+    };
+    Some(Geometry { color: color.to_fsa(), masks: masks })
+}
+
+/// Blend the given rasterized `geometry` onto the `image`, cropped to the `scizzor`.
+fn draw_geometry(image: &mut Image, geometry: &Geometry, scizzor: Rect, linear: bool) {
+    for mask in &geometry.masks {
+        blend_mask(image, mask, scizzor, geometry.color, linear);
+    }
+}
+
+/// Draw the glyphs of a `Text` primitive, rasterizing them via the given `glyph_cache`.
+///
+/// All other primitives are ignored.
+fn draw_text(image: &mut Image,
+             glyph_cache: &mut GlyphCache,
+             primitive: render::Primitive,
+             linear: bool)
+{
+    if let render::PrimitiveKind::Text { color, text, .. } = primitive.kind {
+        let color = color.to_fsa();
+        let bounds = pixel_bounds(image, primitive.scizzor);
+        let (glyphs, font_ids) = text.positioned_glyphs_and_font_ids(1.0);
+        for (glyph, &font_id) in glyphs.iter().zip(font_ids) {
+            let coverage = glyph_cache.coverage(font_id, glyph);
+            draw_glyph(image, glyph, coverage, color, bounds, linear);
+        }
+    }
+}
+
+
+/// Convert a color channel to its byte representation.
+fn to_byte(channel: f32) -> u8 {
+    (channel.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+/// The range of pixel columns and rows, as `(left, right, top, bottom)` with exclusive ends, that
+/// lie within the given `rect`.
+///
+/// A pixel lies within the `rect` if its centre does.
+fn pixel_bounds(image: &Image, rect: Rect) -> (u32, u32, u32, u32) {
+    let (w, h) = (image.width as Scalar, image.height as Scalar);
+    let to_px = |v: Scalar, max: Scalar| (v - 0.5).ceil().max(0.0).min(max) as u32;
+    let left = to_px(rect.left() + w / 2.0, w);
+    let right = to_px(rect.right() + w / 2.0, w);
+    let top = to_px(h / 2.0 - rect.top(), h);
+    let bottom = to_px(h / 2.0 - rect.bottom(), h);
+    (left, right, top, bottom)
+}
+
+/// The range of pixel columns and rows, as `(left, right, top, bottom)` with exclusive ends, that
+/// are at least partially covered by the given `rect` within an image of the given `dimensions`.
+fn covered_pixel_bounds(dimensions: (u32, u32), rect: Rect) -> (u32, u32, u32, u32) {
+    let (w, h) = (dimensions.0 as Scalar, dimensions.1 as Scalar);
+    let clamp = |v: Scalar, max: Scalar| v.max(0.0).min(max) as u32;
+    let left = clamp((rect.left() + w / 2.0).floor(), w);
+    let right = clamp((rect.right() + w / 2.0).ceil(), w);
+    let top = clamp((h / 2.0 - rect.top()).floor(), h);
+    let bottom = clamp((h / 2.0 - rect.bottom()).ceil(), h);
+    (left, right, top, bottom)
+}
+
+/// The coverage of every pixel touched by the `rect` within an image of the given `dimensions`,
+/// where `coverage` is given the area of each pixel in conrod coordinates.
+fn mask<F>(dimensions: (u32, u32), rect: Rect, mut coverage: F) -> Mask
+    where F: FnMut(Rect) -> f32,
+{
+    let (w, h) = (dimensions.0 as Scalar, dimensions.1 as Scalar);
+    let (left, right, top, bottom) = covered_pixel_bounds(dimensions, rect);
+    let mut mask = Mask {
+        left: left,
+        top: top,
+        width: right - left,
+        coverage: Vec::with_capacity((right - left) as usize * (bottom - top) as usize),
+    };
+    for y in top..bottom {
+        for x in left..right {
+            let (px, py) = (x as Scalar - w / 2.0, h / 2.0 - y as Scalar);
+            let pixel = Rect::from_corners([px, py - 1.0], [px + 1.0, py]);
+            mask.coverage.push(coverage(pixel));
+        }
+    }
+    mask
+}
+
+/// Blend the given `color` onto every pixel of the `mask` whose centre lies within the `scizzor`.
+fn blend_mask(image: &mut Image, mask: &Mask, scizzor: Rect, color: [f32; 4], linear: bool) {
+    let (s_left, s_right, s_top, s_bottom) = pixel_bounds(image, scizzor);
+    if mask.width == 0 {
+        return;
+    }
+    for (i, &c) in mask.coverage.iter().enumerate() {
+        let x = mask.left + i as u32 % mask.width;
+        let y = mask.top + i as u32 / mask.width;
+        if c > 0.0 && x >= s_left && x < s_right && y >= s_top && y < s_bottom {
+            image.blend(x, y, color, c.min(1.0), linear);
+        }
+    }
+}
+
+/// The fraction of the `pixel` covered by the `rect`.
+fn area_coverage(rect: Rect, pixel: Rect) -> f32 {
+    rect.overlap(pixel).map_or(0.0, |overlap| (overlap.w() * overlap.h()) as f32)
+}
+
+/// The fraction of a regular grid of sample points within the `pixel` for which `is_inside`
+/// returns `true`.
+fn supersample<F>(pixel: Rect, mut is_inside: F) -> f32
+    where F: FnMut(Point) -> bool,
+{
+    let n = SAMPLES_PER_AXIS;
+    let offset = |i: usize| (i as Scalar + 0.5) / n as Scalar;
+    let mut inside = 0;
+    for j in 0..n {
+        for i in 0..n {
+            let p = [pixel.left() + offset(i), pixel.bottom() + offset(j)];
+            if is_inside(p) {
+                inside += 1;
+            }
+        }
+    }
+    inside as f32 / (n * n) as f32
+}
+
+/// Blend a single glyph with the given rasterized `coverage` onto the image, cropped to the given
+/// pixel bounds.
+fn draw_glyph(image: &mut Image,
+              glyph: &text::PositionedGlyph,
+              coverage: &[f32],
+              color: [f32; 4],
+              bounds: (u32, u32, u32, u32),
+              linear: bool)
+{
+    let (left, right, top, bottom) = bounds;
+    let bb = match glyph.pixel_bounding_box() {
+        Some(bb) => bb,
+        None => return,
+    };
+    let width = bb.width() as usize;
+    for (i, &c) in coverage.iter().enumerate() {
+        let x = bb.min.x + (i % width) as i32;
+        let y = bb.min.y + (i / width) as i32;
+        if x < left as i32 || x >= right as i32 || y < top as i32 || y >= bottom as i32 {
+            continue;
+        }
+        image.blend(x as u32, y as u32, color, c, linear);
+    }
+}
+
+/// The smallest `Rect` containing all of the given points.
+fn bounding_rect(points: &[Point]) -> Rect {
+    let mut iter = points.iter();
+    let first = match iter.next() {
+        Some(p) => *p,
+        None => return Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]),
+    };
+    iter.fold(Rect::from_corners(first, first), |rect, p| rect.stretch_to_point(*p))
+}
+
+/// Whether or not the given point lies within the polygon described by the given `points`,
+/// according to the even-odd rule.
+fn contains(points: &[Point], p: Point) -> bool {
+    let mut is_inside = false;
+    let mut j = match points.len() {
+        0 => return false,
+        n => n - 1,
+    };
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[j]);
+        if (a[1] > p[1]) != (b[1] > p[1])
+        && p[0] < (b[0] - a[0]) * (p[1] - a[1]) / (b[1] - a[1]) + a[0] {
+            is_inside = !is_inside;
+        }
+        j = i;
+    }
+    is_inside
+}
+
+/// The shortest distance from the point `p` to the line segment between `a` and `b`.
+fn distance_to_segment(p: Point, a: Point, b: Point) -> Scalar {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq == 0.0 {
+        0.0
+    } else {
+        (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / len_sq).max(0.0).min(1.0)
+    };
+    let (x, y) = (a[0] + t * dx - p[0], a[1] + t * dy - p[1]);
+    (x * x + y * y).sqrt()
+}
+
+
+#[test]
+fn draw_should_blend_and_crop_rectangles() {
+    use {color, Colorable, Positionable, Widget};
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let (red, blue, crop, cropped) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        // Covers the pixels 30..70 along both axes.
+        widget::Rectangle::fill([40.0, 40.0])
+            .x_y(0.0, 0.0)
+            .color(color::rgb(1.0, 0.0, 0.0))
+            .set(red, ui);
+        // Covers the pixel columns 50..90 and overlaps the right half of the red rectangle.
+        widget::Rectangle::fill([40.0, 40.0])
+            .x_y(20.0, 0.0)
+            .color(color::rgba(0.0, 0.0, 1.0, 0.5))
+            .set(blue, ui);
+        // Covers the pixel columns 0..10 and rows 0..10, cropping its bigger child.
+        widget::Rectangle::fill([10.0, 10.0])
+            .x_y(-45.0, 45.0)
+            .color(color::rgba(0.0, 0.0, 0.0, 0.0))
+            .crop_kids()
+            .set(crop, ui);
+        widget::Rectangle::fill([30.0, 30.0])
+            .x_y(-45.0, 45.0)
+            .color(color::rgb(0.0, 1.0, 0.0))
+            .parent(crop)
+            .set(cropped, ui);
+    }
+
+    let mut image = Image::new(100, 100);
+    draw(&mut image, ui.draw());
+
+    assert_eq!(Some([255, 0, 0, 255]), image.pixel(35, 50));
+    assert_eq!(Some([128, 0, 128, 255]), image.pixel(60, 50));
+    assert_eq!(Some([0, 0, 255, 128]), image.pixel(80, 50));
+    assert_eq!(Some([0, 0, 0, 0]), image.pixel(50, 95));
+    assert_eq!(Some([0, 255, 0, 255]), image.pixel(5, 5));
+    // The green child extends beyond its parent, but is cropped.
+    assert_eq!(Some([0, 0, 0, 0]), image.pixel(15, 5));
+    assert_eq!(None, image.pixel(100, 0));
+}
+
+#[test]
+fn draw_should_antialias_edges_at_sub_pixel_positions() {
+    use {color, Colorable, Positionable, Widget};
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let (rectangle, polygon) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        // Covers the pixels 40..60 along both axes along with half of the pixels around them.
+        widget::Rectangle::fill([21.0, 21.0])
+            .x_y(-25.0, 0.0)
+            .color(color::rgb(1.0, 0.0, 0.0))
+            .set(rectangle, ui);
+        // Covers the pixel columns 65..85 along with half of the columns on either side.
+        let points = vec![[-10.5, -10.0], [10.5, -10.0], [10.5, 10.0], [-10.5, 10.0]];
+        widget::Polygon::centred_fill(points)
+            .x_y(25.0, 0.0)
+            .color(color::rgb(0.0, 0.0, 1.0))
+            .set(polygon, ui);
+    }
+
+    let mut image = Image::new(100, 100);
+    draw(&mut image, ui.draw());
+
+    // The rectangle's edges are blended by the area that they cover.
+    assert_eq!(Some([255, 0, 0, 255]), image.pixel(25, 50));
+    assert_eq!(Some([255, 0, 0, 128]), image.pixel(14, 50));
+    assert_eq!(Some([255, 0, 0, 128]), image.pixel(35, 50));
+    assert_eq!(Some([255, 0, 0, 128]), image.pixel(25, 39));
+    assert_eq!(Some([255, 0, 0, 64]), image.pixel(14, 39));
+    assert_eq!(Some([0, 0, 0, 0]), image.pixel(13, 50));
+
+    // The polygon's edges are blended by the fraction of samples that they cover.
+    assert_eq!(Some([0, 0, 255, 255]), image.pixel(75, 50));
+    assert_eq!(Some([0, 0, 255, 128]), image.pixel(64, 50));
+    assert_eq!(Some([0, 0, 255, 128]), image.pixel(85, 50));
+    assert_eq!(Some([0, 0, 255, 255]), image.pixel(75, 40));
+    assert_eq!(Some([0, 0, 0, 0]), image.pixel(75, 39));
+}
+
+#[test]
+fn gamma_correct_blending_should_composite_in_linear_rgb_and_store_srgb() {
+    use {color, Colorable, Widget};
+    use widget;
+
+    fn draw_grey_over_black(gamma_correct_blending: bool) -> [u8; 4] {
+        let theme = ::Theme::builder().gamma_correct_blending(gamma_correct_blending).build();
+        let mut ui = ::UiBuilder::new([10.0, 10.0]).theme(theme).build();
+        let rectangle = ui.widget_id_generator().next();
+        widget::Rectangle::fill([10.0, 10.0])
+            .color(color::rgba(1.0, 1.0, 1.0, 0.5))
+            .set(rectangle, &mut ui.set_widgets());
+        let mut image = Image::new(10, 10);
+        image.clear(color::BLACK);
+        draw(&mut image, ui.draw());
+        image.pixel(5, 5).unwrap()
+    }
+
+    // Half of white over black is half of the sRGB range, or half of the light in linear RGB.
+    assert_eq!([128, 128, 128, 255], draw_grey_over_black(false));
+    assert_eq!([188, 188, 188, 255], draw_grey_over_black(true));
+}
+
+#[test]
+fn redraw_should_match_a_full_draw_after_a_localized_change() {
+    use {color, Color, Colorable, Positionable, Widget};
+    use render::DamageTracker;
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let (moved, recolored) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let set = |ui: &mut ::Ui, x: Scalar, color: Color| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([20.5, 20.5]).x_y(x, 20.0).set(moved, ui);
+        widget::Rectangle::fill([20.0, 20.0])
+            .x_y(0.0, -25.0)
+            .color(color::rgba(color.red(), color.green(), color.blue(), 0.5))
+            .set(recolored, ui);
+    };
+
+    let mut damage = DamageTracker::new();
+    let mut image = Image::new(100, 100);
+    let mut renderer = Renderer::new();
+    let mut renderer_image = Image::new(100, 100);
+    set(&mut ui, -20.0, color::RED);
+    damage.update(ui.draw());
+    draw(&mut image, ui.draw());
+    renderer.draw(&mut renderer_image, ui.draw());
+    let len = renderer.geometry_cache.len();
+
+    set(&mut ui, 10.25, color::BLUE);
+    let area = damage.update(ui.draw()).unwrap();
+    redraw(&mut image, ui.draw(), area);
+    renderer.redraw(&mut renderer_image, ui.draw(), area);
+
+    let mut expected = Image::new(100, 100);
+    draw(&mut expected, ui.draw());
+    assert!(image == expected);
+
+    // The `Renderer` replaces the geometry of the changed primitives within its cache.
+    assert!(renderer_image == expected);
+    assert_eq!(len, renderer.geometry_cache.len());
+}
+
+#[test]
+fn renderer_should_reuse_rasterized_glyphs_within_its_budget() {
+    use {Positionable, Widget};
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    let text = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("abab").x_y(0.0, 0.0).font_size(24).set(text, ui);
+    }
+
+    let mut expected = Image::new(100, 100);
+    draw(&mut expected, ui.draw());
+
+    // Each distinct glyph is rasterized once and then drawn from the cache.
+    let mut renderer = Renderer::new();
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    let (len, bytes) = (renderer.glyph_cache().len(), renderer.glyph_cache().total_bytes());
+    assert!(len > 0 && len <= 4);
+    assert!(image == expected);
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    assert_eq!(len, renderer.glyph_cache().len());
+    assert_eq!(bytes, renderer.glyph_cache().total_bytes());
+    assert!(image == expected);
+
+    // Once the budget is exceeded, glyphs are evicted but still drawn.
+    renderer.glyph_cache_mut().set_max_bytes(0);
+    assert!(renderer.glyph_cache().is_empty());
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    assert!(renderer.glyph_cache().is_empty());
+    assert!(image == expected);
+}
+
+#[test]
+fn renderer_should_reuse_the_geometry_of_unchanged_primitives() {
+    use {color, Color, Colorable, Positionable, Widget};
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let set = |ui: &mut ::Ui, b_color: Option<Color>| {
+        let ui = &mut ui.set_widgets();
+        widget::Circle::fill(10.5).x_y(-20.0, 0.0).set(a, ui);
+        if let Some(color) = b_color {
+            widget::Rectangle::fill([20.5, 20.5]).x_y(20.0, 0.0).color(color).set(b, ui);
+        }
+    };
+    let expected = |ui: &::Ui| {
+        let mut image = Image::new(100, 100);
+        draw(&mut image, ui.draw_primitives());
+        image
+    };
+
+    let mut renderer = Renderer::new();
+    set(&mut ui, Some(color::RED));
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    let len = renderer.geometry_cache.len();
+    assert!(len >= 2);
+    assert!(image == expected(&ui));
+
+    // Unchanged and changed primitives alike are drawn exactly as when rasterized from scratch.
+    set(&mut ui, Some(color::BLUE));
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    assert_eq!(len, renderer.geometry_cache.len());
+    assert!(image == expected(&ui));
+
+    // The geometry of removed widgets is discarded.
+    set(&mut ui, None);
+    let mut image = Image::new(100, 100);
+    renderer.draw(&mut image, ui.draw());
+    assert_eq!(len - 1, renderer.geometry_cache.len());
+    assert!(image == expected(&ui));
+}
+
+#[test]
+fn draw_should_draw_characters_missing_from_every_font_as_the_placeholder() {
+    use {Positionable, Widget};
+    use text::font::PLACEHOLDER;
+    use widget;
+
+    let mut ui = ::UiBuilder::new([100.0, 100.0]).build();
+    let font_path = |name| format!("{}/assets/fonts/NotoSans/NotoSans-{}.ttf",
+                                   env!("CARGO_MANIFEST_DIR"), name);
+    let regular = ui.fonts.insert_from_file(font_path("Regular")).unwrap();
+    let bold = ui.fonts.insert_from_file(font_path("Bold")).unwrap();
+    ui.fonts.set_fallbacks(regular, vec![bold]);
+    let text = ui.widget_id_generator().next();
+    let draw_text = |ui: &mut ::Ui, string: &str| {
+        {
+            let ui = &mut ui.set_widgets();
+            widget::Text::new(string).x_y(0.0, 0.0).font_size(32).font_id(regular).set(text, ui);
+        }
+        let mut image = Image::new(100, 100);
+        draw(&mut image, ui.draw());
+        let width = ui.widget_graph().widget(text)
+            .and_then(|container| container.unique_widget_state::<widget::Text>())
+            .map(|text| text.state.line_infos[0].width)
+            .unwrap();
+        (image, width)
+    };
+
+    // No font contains '漢', so it is measured and drawn as the placeholder from the first font
+    // within the chain that contains one, rather than as the font's "missing glyph".
+    let (placeholder, placeholder_w) = draw_text(&mut ui, &PLACEHOLDER.to_string());
+    let (missing, missing_w) = draw_text(&mut ui, "漢");
+    assert!(placeholder != Image::new(100, 100));
+    assert!(missing == placeholder);
+    assert_eq!(placeholder_w, missing_w);
+
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let ::render::PrimitiveKind::Text { text, .. } = primitive.kind {
+            let (glyphs, font_ids) = text.positioned_glyphs_and_font_ids(1.0);
+            assert_eq!(1, glyphs.len());
+            assert_eq!(&[regular], font_ids);
+        }
+    }
+}
@@ -0,0 +1,894 @@
+//! A wgpu backend for rendering conrod primitives.
+//!
+//! The `Renderer` translates `render::Primitives` into indexed vertices and draw commands (see
+//! `Renderer::fill`) and records them into a render pass targeting any `wgpu::TextureView`
+//! (see `Renderer::draw`), whether it belongs to a swap chain or an offscreen texture.
+//!
+//! Conrod's colors are gamma-encoded sRGB. When the target has an sRGB format, the GPU encodes
+//! the output of the fragment shader to sRGB, so colors are converted to linear space beforehand,
+//! matching the glium backend. When the target has a linear format, colors are passed through
+//! unchanged so that they are displayed the same either way. As `render::Primitives` always yield
+//! sRGB colors, this is the only place in which they are converted.
+
+extern crate wgpu;
+
+use {Color, Rect, Scalar};
+use color;
+use image;
+use render;
+use std;
+use text;
+use widget;
+
+use self::wgpu::util::DeviceExt;
+
+
+/// The `Vertex` type passed to the vertex shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Vertex {
+    /// The position of the vertex within normalized device coordinates.
+    ///
+    /// [-1.0, -1.0] is the leftmost, bottom position of the target.
+    /// [1.0, 1.0] is the rightmost, top position of the target.
+    pub position: [f32; 2],
+    /// The coordinates of the texture used by this `Vertex`.
+    ///
+    /// [0.0, 0.0] is the leftmost, top position of the texture.
+    /// [1.0, 1.0] is the rightmost, bottom position of the texture.
+    pub tex_coords: [f32; 2],
+    /// A color associated with the `Vertex`, already converted for the target's format.
+    pub color: [f32; 4],
+    /// The mode with which the `Vertex` will be drawn within the fragment shader.
+    ///
+    /// One of `MODE_TEXT`, `MODE_IMAGE` or `MODE_GEOMETRY`.
+    pub mode: u32,
+}
+
+/// An image that may be drawn by the `Renderer`, stored within an `image::Map`.
+pub struct Image {
+    /// A view of the image's texture, which must have been created with the `SAMPLED` usage.
+    pub view: wgpu::TextureView,
+    /// The width and height of the texture in pixels.
+    pub dimensions: [u32; 2],
+}
+
+/// A rusttype `GlyphCache` along with a `wgpu::Texture` for caching text on the GPU.
+pub struct GlyphCache {
+    cache: text::GlyphCache,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+/// A type used for translating `render::Primitives` into indexed vertices and draw commands, and
+/// for drawing them with `wgpu`.
+pub struct Renderer {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    glyph_cache: GlyphCache,
+    is_srgb: bool,
+    commands: Vec<Command>,
+    vertices: render::IndexedVertices<Vertex>,
+}
+
+/// A step in the drawing process produced by `Renderer::fill`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Draw the given range of plain geometry and text.
+    Plain(render::IndexedRange),
+    /// Draw the given range textured with the image in the `image::Map` at the given `widget::Id`.
+    Image(widget::Id, render::IndexedRange),
+    /// Restrict all following drawing to the given `[x, y, w, h]` of the target in pixels, where
+    /// `[x, y]` is the top left corner.
+    Scizzor([u32; 4]),
+}
+
+/// The width and height of the glyph cache texture in pixels.
+pub const GLYPH_CACHE_DIMENSIONS: [u32; 2] = [1024, 1024];
+
+/// Draw text from the glyph cache texture in the fragment shader.
+pub const MODE_TEXT: u32 = 0;
+/// Draw an image from its texture in the fragment shader.
+pub const MODE_IMAGE: u32 = 1;
+/// Ignore the texture and draw simple, colored 2D geometry.
+pub const MODE_GEOMETRY: u32 = 2;
+
+/// The WGSL source of the vertex and fragment shaders used by the `Renderer`.
+pub const SHADER: &'static str = "
+    struct VertexOutput {
+        [[builtin(position)]] position: vec4<f32>;
+        [[location(0)]] tex_coords: vec2<f32>;
+        [[location(1)]] color: vec4<f32>;
+        [[location(2), interpolate(flat)]] mode: u32;
+    };
+
+    [[stage(vertex)]]
+    fn vs_main(
+        [[location(0)]] position: vec2<f32>,
+        [[location(1)]] tex_coords: vec2<f32>,
+        [[location(2)]] color: vec4<f32>,
+        [[location(3)]] mode: u32,
+    ) -> VertexOutput {
+        var out: VertexOutput;
+        out.position = vec4<f32>(position, 0.0, 1.0);
+        out.tex_coords = tex_coords;
+        out.color = color;
+        out.mode = mode;
+        return out;
+    }
+
+    [[group(0), binding(0)]] var tex: texture_2d<f32>;
+    [[group(0), binding(1)]] var tex_sampler: sampler;
+
+    [[stage(fragment)]]
+    fn fs_main(v: VertexOutput) -> [[location(0)]] vec4<f32> {
+        let sampled = textureSample(tex, tex_sampler, v.tex_coords);
+        // Text
+        if (v.mode == 0u) {
+            return v.color * vec4<f32>(1.0, 1.0, 1.0, sampled.r);
+        }
+        // Image
+        if (v.mode == 1u) {
+            return sampled;
+        }
+        // 2D Geometry
+        return v.color;
+    }
+";
+
+
+/// Whether or not the given texture format is sRGB-encoded, in which case the GPU converts the
+/// linear output of the fragment shader to sRGB when writing to it.
+pub fn is_srgb(format: wgpu::TextureFormat) -> bool {
+    match format {
+        wgpu::TextureFormat::Rgba8UnormSrgb |
+        wgpu::TextureFormat::Bgra8UnormSrgb => true,
+        _ => false,
+    }
+}
+
+/// Convert the given color to the vertex color for a target that is (or is not) sRGB-encoded.
+pub fn vertex_color(color: Color, is_srgb_target: bool) -> [f32; 4] {
+    if is_srgb_target { color.to_linear().to_fsa() } else { color.to_fsa() }
+}
+
+
+impl GlyphCache {
+
+    /// Construct a `GlyphCache` of `GLYPH_CACHE_DIMENSIONS`.
+    pub fn new(device: &wgpu::Device) -> Self {
+        const SCALE_TOLERANCE: f32 = 0.1;
+        const POSITION_TOLERANCE: f32 = 0.1;
+        let (w, h) = (GLYPH_CACHE_DIMENSIONS[0], GLYPH_CACHE_DIMENSIONS[1]);
+        let cache = text::GlyphCache::new(w, h, SCALE_TOLERANCE, POSITION_TOLERANCE);
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("conrod_glyph_cache"),
+            size: wgpu::Extent3d { width: w, height: h, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        GlyphCache {
+            cache: cache,
+            texture: texture,
+            view: view,
+        }
+    }
+
+    /// The texture used to cache the glyphs on the GPU.
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+}
+
+
+impl Renderer {
+
+    /// Construct a new empty `Renderer` for drawing to targets of the given `format`.
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(&wgpu::ShaderModuleDescriptor {
+            label: Some("conrod_shader"),
+            source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(SHADER)),
+            flags: wgpu::ShaderFlags::all(),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("conrod_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStage::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler { filtering: true, comparison: false },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("conrod_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        const ATTRIBUTES: [wgpu::VertexAttribute; 4] =
+            wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4, 3 => Uint32];
+        let blend = wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::SrcAlpha,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+        };
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("conrod_pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::InputStepMode::Vertex,
+                    attributes: &ATTRIBUTES,
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[wgpu::ColorTargetState {
+                    format: format,
+                    blend: Some(blend),
+                    write_mask: wgpu::ColorWrite::ALL,
+                }],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("conrod_sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Renderer {
+            pipeline: pipeline,
+            bind_group_layout: bind_group_layout,
+            sampler: sampler,
+            glyph_cache: GlyphCache::new(device),
+            is_srgb: is_srgb(format),
+            commands: Vec::new(),
+            vertices: render::IndexedVertices::new(),
+        }
+    }
+
+    /// The `Command`s produced by the most recent call to `fill`.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// The vertices and indices produced by the most recent call to `fill`.
+    pub fn vertices(&self) -> &render::IndexedVertices<Vertex> {
+        &self.vertices
+    }
+
+    /// Fill the inner vertex and command buffers by translating the given `primitives`, uploading
+    /// any newly required glyphs to the glyph cache texture via the given `queue`.
+    ///
+    /// `target_dimensions` are the width and height of the target in pixels. Each conrod point
+    /// covers the number of pixels given by the `primitives`' `scale_factor`.
+    pub fn fill<P>(&mut self,
+                   queue: &wgpu::Queue,
+                   primitives: P,
+                   target_dimensions: [u32; 2],
+                   image_map: &image::Map<Image>)
+        where P: render::PrimitiveWalker,
+    {
+        self.fill_primitives(queue, primitives, None, target_dimensions, image_map)
+    }
+
+    /// The same as `fill`, but the glyphs of each `Text` primitive are taken from the given
+    /// `text_layouts` rather than being positioned while filling the buffers.
+    ///
+    /// The `text_layouts` should be produced by `render::layout_texts` from the same primitives,
+    /// using their `scale_factor` as the DPI factor. Any text missing from the `text_layouts` is
+    /// positioned as usual.
+    pub fn fill_with_text_layouts<P>(&mut self,
+                                     queue: &wgpu::Queue,
+                                     primitives: P,
+                                     text_layouts: &render::TextLayouts,
+                                     target_dimensions: [u32; 2],
+                                     image_map: &image::Map<Image>)
+        where P: render::PrimitiveWalker,
+    {
+        self.fill_primitives(queue, primitives, Some(text_layouts), target_dimensions, image_map)
+    }
+
+    fn fill_primitives<P>(&mut self,
+                          queue: &wgpu::Queue,
+                          mut primitives: P,
+                          text_layouts: Option<&render::TextLayouts>,
+                          target_dimensions: [u32; 2],
+                          image_map: &image::Map<Image>)
+        where P: render::PrimitiveWalker,
+    {
+        let Renderer { ref mut commands, ref mut vertices, ref mut glyph_cache, is_srgb, .. } =
+            *self;
+        let dpi_factor = primitives.scale_factor();
+
+        commands.clear();
+        vertices.clear();
+
+        let (screen_w, screen_h) = (target_dimensions[0], target_dimensions[1]);
+        let half_win_w = screen_w as Scalar / 2.0;
+        let half_win_h = screen_h as Scalar / 2.0;
+
+        // Functions for converting conrod scalar coords to normalized device coords.
+        let vx = |x: Scalar| (x * dpi_factor / half_win_w) as f32;
+        let vy = |y: Scalar| (y * dpi_factor / half_win_h) as f32;
+        let geometry = |p: [Scalar; 2], color: [f32; 4]| Vertex {
+            position: [vx(p[0]), vy(p[1])],
+            tex_coords: [0.0, 0.0],
+            color: color,
+            mode: MODE_GEOMETRY,
+        };
+
+        // Convert a `Rect` to the `[x, y, w, h]` pixel region of the target, with `[x, y]` at
+        // the top left and clamped to the target.
+        let rect_to_scizzor = |rect: Rect| {
+            let clamp_x = |x: Scalar| x.max(0.0).min(screen_w as Scalar) as u32;
+            let clamp_y = |y: Scalar| y.max(0.0).min(screen_h as Scalar) as u32;
+            let left = clamp_x(rect.left() * dpi_factor + half_win_w);
+            let right = clamp_x(rect.right() * dpi_factor + half_win_w);
+            let top = clamp_y(half_win_h - rect.top() * dpi_factor);
+            let bottom = clamp_y(half_win_h - rect.bottom() * dpi_factor);
+            [left, top, right - left, bottom - top]
+        };
+
+        // Each `Batch` of primitives is drawn via a single command.
+        let mut batcher = render::Batcher::new();
+        let mut current_scizzor = [0, 0, screen_w, screen_h];
+
+        // Completes the vertices of the given `Batch` with the command that draws them.
+        macro_rules! finish_batch {
+            ($batch:expr) => {
+                match $batch.kind {
+                    render::BatchKind::Plain =>
+                        commands.push(Command::Plain(vertices.finish_batch())),
+                    render::BatchKind::Image(id) =>
+                        commands.push(Command::Image(id, vertices.finish_batch())),
+                    // `Other` primitives produce no vertices.
+                    render::BatchKind::Other(_) => (),
+                }
+            };
+        }
+
+        // Draw each primitive in order of depth.
+        while let Some(primitive) = primitives.next_primitive() {
+
+            // Finish the current command if the primitive begins a new batch.
+            if let Some(batch) = batcher.push(primitive.batch_kind(), primitive.scizzor) {
+                finish_batch!(batch);
+            }
+
+            let render::Primitive { id, kind, scizzor, rect } = primitive;
+
+            // Check for a `Scizzor` command.
+            let new_scizzor = rect_to_scizzor(scizzor);
+            if new_scizzor != current_scizzor {
+                current_scizzor = new_scizzor;
+                commands.push(Command::Scizzor(new_scizzor));
+            }
+
+            match kind {
+
+                render::PrimitiveKind::Rectangle { color } => {
+                    let color = vertex_color(color, is_srgb);
+                    let (l, r, b, t) = rect.l_r_b_t();
+                    let v = |x, y| geometry([x, y], color);
+                    vertices.push_quad([v(l, t), v(l, b), v(r, b), v(r, t)]);
+                },
+
+                render::PrimitiveKind::RoundedRect { color, radius } => {
+                    let color = vertex_color(color, is_srgb);
+                    let points = render::rounded_rect_points(rect, radius);
+                    push_fan(vertices, &points, |p| geometry(p, color));
+                },
+
+                render::PrimitiveKind::Border { color, thickness } => {
+                    let color = vertex_color(color, is_srgb);
+                    let v = |x, y| geometry([x, y], color);
+                    for edge in &render::border_rects(rect, thickness) {
+                        let (l, r, b, t) = edge.l_r_b_t();
+                        vertices.push_quad([v(l, t), v(l, b), v(r, b), v(r, t)]);
+                    }
+                },
+
+                render::PrimitiveKind::Shadow { color, blur, offset } => {
+                    let color = vertex_color(color, is_srgb);
+                    // Fade the shadow out by interpolating the alpha of each vertex.
+                    let v = |(p, opacity): ([Scalar; 2], f32)| {
+                        geometry(p, [color[0], color[1], color[2], color[3] * opacity])
+                    };
+                    for triangle in &render::shadow_triangles(rect, blur, offset) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
+                    }
+                },
+
+                render::PrimitiveKind::Polygon { color, points } => {
+                    // If we don't at least have a triangle, keep looping.
+                    if points.len() < 3 {
+                        continue;
+                    }
+                    let color = vertex_color(color, is_srgb);
+                    push_fan(vertices, points, |p| geometry(p, color));
+                },
+
+                render::PrimitiveKind::Path { color, fill_rule, segments } => {
+                    let color = vertex_color(color, is_srgb);
+                    let v = |p| geometry(p, color);
+                    for triangle in &render::tessellate_path(segments, fill_rule) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
+                    }
+                },
+
+                render::PrimitiveKind::Lines { color, thickness, points, .. } => {
+                    // We need at least two points to draw any lines.
+                    if points.len() < 2 {
+                        continue;
+                    }
+                    let color = vertex_color(color, is_srgb);
+                    // Feather the edges of the lines by interpolating the alpha of each vertex.
+                    let v = |(p, opacity): ([Scalar; 2], f32)| {
+                        geometry(p, [color[0], color[1], color[2], color[3] * opacity])
+                    };
+                    for triangle in &render::tessellate_lines(points, thickness) {
+                        vertices.push_triangle([v(triangle[0]), v(triangle[1]), v(triangle[2])]);
+                    }
+                },
+
+                render::PrimitiveKind::Text { color, text, .. } => {
+                    // Glyphs drawn from a fallback font are cached under that font's `Id`.
+                    let laid_out = text_layouts.and_then(|layouts| {
+                        layouts.get(id).and_then(|glyphs| layouts.font_ids(id).map(|f| (glyphs, f)))
+                    });
+                    let (positioned_glyphs, font_ids) = match laid_out {
+                        Some(laid_out) => laid_out,
+                        None => text.positioned_glyphs_and_font_ids(dpi_factor as f32),
+                    };
+                    let GlyphCache { ref mut cache, ref texture, .. } = *glyph_cache;
+
+                    // Queue the glyphs to be cached.
+                    for (glyph, font_id) in positioned_glyphs.iter().zip(font_ids) {
+                        cache.queue_glyph(font_id.index(), glyph.clone());
+                    }
+
+                    // Upload the newly cached glyphs to the texture.
+                    cache.cache_queued(|rect, data| {
+                        let size = wgpu::Extent3d {
+                            width: rect.width(),
+                            height: rect.height(),
+                            depth_or_array_layers: 1,
+                        };
+                        let copy = wgpu::ImageCopyTexture {
+                            texture: texture,
+                            mip_level: 0,
+                            origin: wgpu::Origin3d { x: rect.min.x, y: rect.min.y, z: 0 },
+                        };
+                        let layout = wgpu::ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: std::num::NonZeroU32::new(rect.width()),
+                            rows_per_image: None,
+                        };
+                        queue.write_texture(copy, data, layout, size);
+                    }).unwrap();
+
+                    let color = vertex_color(color, is_srgb);
+
+                    // Convert from pixels with the origin at the top left to normalized device
+                    // coordinates.
+                    let nx = |x: i32| (x as f32 / screen_w as f32) * 2.0 - 1.0;
+                    let ny = |y: i32| 1.0 - (y as f32 / screen_h as f32) * 2.0;
+
+                    for (g, font_id) in positioned_glyphs.iter().zip(font_ids) {
+                        if let Ok(Some((uv, screen))) = cache.rect_for(font_id.index(), g) {
+                            let v = |x, y, u, w| Vertex {
+                                position: [nx(x), ny(y)],
+                                tex_coords: [u, w],
+                                color: color,
+                                mode: MODE_TEXT,
+                            };
+                            vertices.push_quad([
+                                v(screen.min.x, screen.min.y, uv.min.x, uv.min.y),
+                                v(screen.min.x, screen.max.y, uv.min.x, uv.max.y),
+                                v(screen.max.x, screen.max.y, uv.max.x, uv.max.y),
+                                v(screen.max.x, screen.min.y, uv.max.x, uv.min.y),
+                            ]);
+                        }
+                    }
+                },
+
+                render::PrimitiveKind::Image { image_id, color, source_rect } => {
+                    let image = match image_map.get(&image_id) {
+                        Some(image) => image,
+                        None => continue,
+                    };
+
+                    let color = vertex_color(color.unwrap_or(color::WHITE), is_srgb);
+                    let (image_w, image_h) = (image.dimensions[0] as Scalar,
+                                              image.dimensions[1] as Scalar);
+
+                    // Get the sides of the source rectangle as texture coordinates, where the
+                    // source rectangle's *y* axis points upwards but the texture's points down.
+                    let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
+                        Some(src_rect) => {
+                            let (l, r, b, t) = src_rect.l_r_b_t();
+                            ((l / image_w) as f32,
+                             (r / image_w) as f32,
+                             (1.0 - b / image_h) as f32,
+                             (1.0 - t / image_h) as f32)
+                        },
+                        None => (0.0, 1.0, 1.0, 0.0),
+                    };
+
+                    let v = |x, y, t| Vertex {
+                        position: [vx(x), vy(y)],
+                        tex_coords: t,
+                        color: color,
+                        mode: MODE_IMAGE,
+                    };
+                    let (l, r, b, t) = rect.l_r_b_t();
+                    vertices.push_quad([
+                        v(l, t, [uv_l, uv_t]),
+                        v(l, b, [uv_l, uv_b]),
+                        v(r, b, [uv_r, uv_b]),
+                        v(r, t, [uv_r, uv_t]),
+                    ]);
+                },
+
+                // We have no special case widgets to handle.
+                render::PrimitiveKind::Other(_) => (),
+            }
+        }
+
+        // Enter the final command.
+        if let Some(batch) = batcher.finish() {
+            finish_batch!(batch);
+        }
+    }
+
+    /// Record the commands produced by the most recent call to `fill` into a render pass drawing
+    /// over the existing contents of the `target`.
+    pub fn draw(&self,
+                device: &wgpu::Device,
+                encoder: &mut wgpu::CommandEncoder,
+                target: &wgpu::TextureView,
+                image_map: &image::Map<Image>)
+    {
+        // Skip commands without geometry as wgpu does not allow empty buffers.
+        let has_geometry = |range: &render::IndexedRange| range.indices.start < range.indices.end;
+
+        // Create the buffers and bind groups up front, as they must outlive the render pass.
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("conrod_vertices"),
+            contents: as_bytes(self.vertices.vertices()),
+            usage: wgpu::BufferUsage::VERTEX,
+        });
+        let index_buffers: Vec<Option<wgpu::Buffer>> = self.commands.iter().map(|command| {
+            let range = match *command {
+                Command::Plain(ref range) | Command::Image(_, ref range) => range,
+                Command::Scizzor(_) => return None,
+            };
+            if !has_geometry(range) {
+                return None;
+            }
+            let indices = &self.vertices.indices()[range.indices.clone()];
+            Some(device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("conrod_indices"),
+                contents: as_bytes(indices),
+                usage: wgpu::BufferUsage::INDEX,
+            }))
+        }).collect();
+        let bind_group = |view: &wgpu::TextureView| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("conrod_bind_group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            })
+        };
+        let glyph_bind_group = bind_group(&self.glyph_cache.view);
+        let image_bind_groups: Vec<Option<wgpu::BindGroup>> = self.commands.iter()
+            .map(|command| match *command {
+                Command::Image(id, _) => image_map.get(&id).map(|image| bind_group(&image.view)),
+                _ => None,
+            })
+            .collect();
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("conrod_render_pass"),
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: true },
+            }],
+            depth_stencil_attachment: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+
+        let vertex_size = std::mem::size_of::<Vertex>() as wgpu::BufferAddress;
+        for (i, command) in self.commands.iter().enumerate() {
+            let (range, bind_group) = match *command {
+                Command::Scizzor(rect) => {
+                    pass.set_scissor_rect(rect[0], rect[1], rect[2], rect[3]);
+                    continue;
+                },
+                Command::Plain(ref range) => (range, &glyph_bind_group),
+                Command::Image(_, ref range) => match image_bind_groups[i] {
+                    Some(ref bind_group) => (range, bind_group),
+                    None => continue,
+                },
+            };
+            let index_buffer = match index_buffers[i] {
+                Some(ref buffer) => buffer,
+                None => continue,
+            };
+            let start = range.vertices.start as wgpu::BufferAddress * vertex_size;
+            let end = range.vertices.end as wgpu::BufferAddress * vertex_size;
+            let num_indices = (range.indices.end - range.indices.start) as u32;
+            pass.set_bind_group(0, bind_group, &[]);
+            pass.set_vertex_buffer(0, vertex_buffer.slice(start..end));
+            pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            pass.draw_indexed(0..num_indices, 0, 0..1);
+        }
+    }
+
+}
+
+
+/// Push the triangles of a fan between the first point and every following pair of points.
+fn push_fan<F>(vertices: &mut render::IndexedVertices<Vertex>, points: &[[Scalar; 2]], v: F)
+    where F: Fn([Scalar; 2]) -> Vertex,
+{
+    let first_v = v(points[0]);
+    let mut prev_v = v(points[1]);
+    for &p in &points[2..] {
+        let v = v(p);
+        vertices.push_triangle([first_v, prev_v, v]);
+        prev_v = v;
+    }
+}
+
+/// View the given slice of plain data as bytes for uploading to a buffer.
+fn as_bytes<T: Copy>(slice: &[T]) -> &[u8] {
+    let len = slice.len() * std::mem::size_of::<T>();
+    unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const u8, len) }
+}
+
+
+#[test]
+fn vertex_colors_should_be_linearized_for_srgb_targets_only() {
+    let grey = color::rgb(0.5, 0.5, 0.5);
+    assert!(is_srgb(wgpu::TextureFormat::Bgra8UnormSrgb));
+    assert!(!is_srgb(wgpu::TextureFormat::Rgba8Unorm));
+    assert_eq!(grey.to_fsa(), vertex_color(grey, false));
+    let linear = vertex_color(grey, true);
+    assert!((linear[0] - 0.214).abs() < 0.001);
+    assert_eq!(1.0, linear[3]);
+}
+
+#[test]
+fn rendering_to_an_offscreen_texture_should_produce_the_expected_pixels() {
+    extern crate pollster;
+    use {Colorable, Positionable, Sizeable, UiBuilder, Widget};
+
+    const W: u32 = 64;
+    const H: u32 = 64;
+    let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    // Skip the test on machines without any GPU adapter (including a software one).
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let adapter = match pollster::block_on(instance.request_adapter(&Default::default())) {
+        Some(adapter) => adapter,
+        None => return,
+    };
+    let (device, queue) = pollster::block_on(adapter.request_device(&Default::default(), None))
+        .unwrap();
+
+    // A grey rectangle covering the left half of the window.
+    let mut ui = UiBuilder::new([W as f64, H as f64]).build();
+    let rectangle = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        let window = ui.window;
+        widget::Rectangle::fill([W as f64 / 2.0, H as f64])
+            .mid_left_of(window)
+            .color(color::rgb(0.5, 0.5, 0.5))
+            .set(rectangle, ui);
+    }
+
+    let mut renderer = Renderer::new(&device, format);
+    let image_map = image::Map::new();
+    renderer.fill(&queue, ui.draw(), [W, H], &image_map);
+
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d { width: W, height: H, depth_or_array_layers: 1 },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: format,
+        usage: wgpu::TextureUsage::RENDER_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
+    });
+    let view = texture.create_view(&Default::default());
+    let bytes_per_row = W * 4;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (bytes_per_row * H) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&Default::default());
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[wgpu::RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color::BLACK), store: true },
+        }],
+        depth_stencil_attachment: None,
+    });
+    renderer.draw(&device, &mut encoder, &view, &image_map);
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: std::num::NonZeroU32::new(bytes_per_row),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d { width: W, height: H, depth_or_array_layers: 1 },
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = buffer.slice(..);
+    let mapping = slice.map_async(wgpu::MapMode::Read);
+    device.poll(wgpu::Maintain::Wait);
+    pollster::block_on(mapping).unwrap();
+    let pixels = slice.get_mapped_range();
+    let pixel = |x: u32, y: u32| {
+        let i = ((y * W + x) * 4) as usize;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    };
+
+    // The grey is written to the sRGB target unchanged, as it would be by the glium backend.
+    let grey = pixel(W / 4, H / 2);
+    assert!((grey[0] as i32 - 128).abs() <= 1, "found {:?}", grey);
+    assert!(grey[0] == grey[1] && grey[1] == grey[2], "found {:?}", grey);
+    assert_eq!(255, grey[3]);
+    assert_eq!([0, 0, 0, 255], pixel(W * 3 / 4, H / 2));
+}
+
+#[test]
+fn fill_should_scale_vertex_positions_by_the_scale_factor_of_the_ui() {
+    extern crate pollster;
+    use {Colorable, Positionable, UiBuilder, Widget};
+
+    // Skip the test on machines without any GPU adapter (including a software one).
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let adapter = match pollster::block_on(instance.request_adapter(&Default::default())) {
+        Some(adapter) => adapter,
+        None => return,
+    };
+    let (device, queue) = pollster::block_on(adapter.request_device(&Default::default(), None))
+        .unwrap();
+
+    // A 16x16 point rectangle in the top right quarter of a 64x64 point window, drawn to a
+    // 128x128 pixel target.
+    let mut ui = UiBuilder::new([64.0, 64.0]).scale_factor(2.0).build();
+    let rectangle = ui.widget_id_generator().next();
+    let red = color::rgb(1.0, 0.0, 0.0);
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([16.0, 16.0]).x_y(8.0, 8.0).color(red).set(rectangle, ui);
+    }
+
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let mut renderer = Renderer::new(&device, format);
+    renderer.fill(&queue, ui.draw(), [128, 128], &image::Map::new());
+
+    // At twice the scale, the rectangle spans 32 of the target's 128 pixels from its centre.
+    let red = vertex_color(red, false);
+    let positions: Vec<_> = renderer.vertices().vertices().iter()
+        .filter(|v| v.color == red)
+        .map(|v| v.position)
+        .collect();
+    assert!(!positions.is_empty());
+    for p in positions {
+        for &c in &p {
+            assert!(c.abs() < 1e-6 || (c - 0.5).abs() < 1e-6, "found {:?}", p);
+        }
+    }
+}
+
+#[test]
+fn fill_with_text_layouts_should_produce_the_same_vertices_as_fill() {
+    extern crate pollster;
+    use {Positionable, UiBuilder, Widget};
+
+    // Skip the test on machines without any GPU adapter (including a software one).
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let adapter = match pollster::block_on(instance.request_adapter(&Default::default())) {
+        Some(adapter) => adapter,
+        None => return,
+    };
+    let (device, queue) = pollster::block_on(adapter.request_device(&Default::default(), None))
+        .unwrap();
+
+    let mut ui = UiBuilder::new([200.0, 200.0]).scale_factor(1.5).build();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Laid out ahead of time").x_y(0.0, 40.0).set(a, ui);
+        widget::Text::new("on a pool of threads").x_y(0.0, -40.0).set(b, ui);
+    }
+
+    let format = wgpu::TextureFormat::Rgba8Unorm;
+    let image_map = image::Map::new();
+    let mut expected = Renderer::new(&device, format);
+    expected.fill(&queue, ui.draw(), [300, 300], &image_map);
+
+    let pool = render::TextLayoutPool::new(2);
+    let text_layouts = render::layout_texts(ui.draw(), 1.5, &pool);
+    assert_eq!(2, text_layouts.len());
+    let mut renderer = Renderer::new(&device, format);
+    renderer.fill_with_text_layouts(&queue, ui.draw(), &text_layouts, [300, 300], &image_map);
+
+    let positions = |renderer: &Renderer| -> Vec<[f32; 2]> {
+        renderer.vertices().vertices().iter().map(|v| v.position).collect()
+    };
+    assert!(!positions(&renderer).is_empty());
+    assert_eq!(positions(&expected), positions(&renderer));
+    assert_eq!(expected.vertices().indices(), renderer.vertices().indices());
+}
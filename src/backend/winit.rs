@@ -0,0 +1,386 @@
+//! Functions for converting `winit` events to `conrod::event::Input`s.
+//!
+//! `winit` describes the positions and sizes within `WindowEvent`s in logical pixels, which are
+//! equivalent to conrod's DPI agnostic `Scalar` points. The deltas of raw `DeviceEvent`s, however,
+//! are given in physical pixels and must be divided by the window's hidpi factor.
+
+extern crate winit;
+
+use {MouseCursor, Scalar};
+use event::{Input, Motion};
+use input;
+
+/// The number of points scrolled per line, for scroll events that are given in lines.
+///
+/// This should be configurable (we should provide a LineDelta event to allow for this).
+pub const POINTS_PER_LINE: Scalar = 10.0;
+
+
+/// A function for converting a `winit::WindowEvent` to a `conrod::event::Input`.
+///
+/// The current logical size and hidpi factor of the window are queried from the given `window`.
+pub fn convert_window_event(e: winit::WindowEvent, window: &winit::Window) -> Option<Input> {
+    let (win_w, win_h) = match window.get_inner_size() {
+        Some(size) => (size.width as Scalar, size.height as Scalar),
+        None => return None,
+    };
+    convert_window_event_for_size(e, win_w, win_h)
+}
+
+/// Convert a `winit::WindowEvent` to a `conrod::event::Input` for a window with the given logical
+/// width and height.
+///
+/// This is useful for converting events without access to the `winit::Window`, for example when
+/// the window is owned by another thread.
+pub fn convert_window_event_for_size(e: winit::WindowEvent,
+                                     win_w: Scalar,
+                                     win_h: Scalar) -> Option<Input>
+{
+    // Translate the coordinates from top-left-origin-with-y-down to centre-origin-with-y-up.
+    let tx = |x: Scalar| x - win_w / 2.0;
+    let ty = |y: Scalar| -(y - win_h / 2.0);
+
+    match e {
+
+        winit::WindowEvent::Resized(size) =>
+            Some(Input::Resize(size.width.round() as u32, size.height.round() as u32)),
+
+        winit::WindowEvent::ReceivedCharacter(ch) => {
+            let string = match ch {
+                // Ignore control characters and return ascii for Text event (like sdl2).
+                '\u{7f}' | // Delete
+                '\u{1b}' | // Escape
+                '\u{8}'  | // Backspace
+                '\r' | '\n' | '\t' => "".to_string(),
+                _ => ch.to_string()
+            };
+            Some(Input::Text(string))
+        },
+
+        winit::WindowEvent::Focused(focused) =>
+            Some(Input::Focus(focused)),
+
+        winit::WindowEvent::CursorEntered { .. } =>
+            Some(Input::Cursor(true)),
+
+        winit::WindowEvent::CursorLeft { .. } =>
+            Some(Input::Cursor(false)),
+
+        winit::WindowEvent::KeyboardInput { input, .. } => input.virtual_keycode.map(|key| {
+            let button = input::Button::Keyboard(map_key(key));
+            match input.state {
+                winit::ElementState::Pressed => Input::Press(button),
+                winit::ElementState::Released => Input::Release(button),
+            }
+        }),
+
+        winit::WindowEvent::Touch(winit::Touch { phase, location, id, .. }) => {
+            let phase = match phase {
+                winit::TouchPhase::Started => input::Touch::Start,
+                winit::TouchPhase::Moved => input::Touch::Move,
+                winit::TouchPhase::Ended => input::Touch::End,
+                winit::TouchPhase::Cancelled => input::Touch::Cancel
+            };
+            let xy = [tx(location.x), ty(location.y)];
+            let args = input::TouchArgs::new(0, id as i64, xy, 1.0, phase);
+            Some(Input::Move(Motion::Touch(args)))
+        },
+
+        winit::WindowEvent::CursorMoved { position, .. } =>
+            Some(Input::Move(Motion::MouseCursor(tx(position.x), ty(position.y)))),
+
+        winit::WindowEvent::MouseWheel { delta, .. } => Some(convert_scroll_delta(delta, 1.0)),
+
+        winit::WindowEvent::MouseInput { state, button, .. } => {
+            let button = input::Button::Mouse(map_mouse(button));
+            match state {
+                winit::ElementState::Pressed => Some(Input::Press(button)),
+                winit::ElementState::Released => Some(Input::Release(button)),
+            }
+        },
+
+        _ => None,
+    }
+}
+
+/// A function for converting a raw `winit::DeviceEvent` to a `conrod::event::Input`.
+///
+/// Unlike `WindowEvent`s, the motion of `DeviceEvent`s is given in physical pixels, so the
+/// window's `hidpi_factor` is required to convert it to points.
+pub fn convert_device_event(e: winit::DeviceEvent, hidpi_factor: f64) -> Option<Input> {
+    let hidpi_factor = hidpi_factor as Scalar;
+    match e {
+
+        // The *y* axis is inverted so that positive motion is upwards.
+        winit::DeviceEvent::MouseMotion { delta: (x, y) } =>
+            Some(Input::Move(Motion::MouseRelative(x / hidpi_factor, -y / hidpi_factor))),
+
+        winit::DeviceEvent::MouseWheel { delta } =>
+            Some(convert_scroll_delta(delta, hidpi_factor)),
+
+        _ => None,
+    }
+}
+
+/// Convert the given scroll `delta` to a `MouseScroll`, where pixel deltas are divided by the
+/// given `hidpi_factor`.
+fn convert_scroll_delta(delta: winit::MouseScrollDelta, hidpi_factor: Scalar) -> Input {
+    let (x, y) = match delta {
+        winit::MouseScrollDelta::PixelDelta(position) =>
+            (position.x / hidpi_factor, -position.y / hidpi_factor),
+        winit::MouseScrollDelta::LineDelta(x, y) =>
+            (POINTS_PER_LINE * x as Scalar, POINTS_PER_LINE * -y as Scalar),
+    };
+    Input::Move(Motion::MouseScroll(x, y))
+}
+
+/// Maps winit's modifier state to conrod's `ModifierKey` flags.
+///
+/// The `Ui` tracks modifiers via the presses and releases of the modifier keys themselves, however
+/// this may be used to recover the modifier state after the window regains focus.
+pub fn map_modifiers(modifiers: winit::ModifiersState) -> input::keyboard::ModifierKey {
+    use input::keyboard::{ALT, CTRL, GUI, NO_MODIFIER, SHIFT};
+    let mut key = NO_MODIFIER;
+    if modifiers.shift { key.insert(SHIFT); }
+    if modifiers.ctrl { key.insert(CTRL); }
+    if modifiers.alt { key.insert(ALT); }
+    if modifiers.logo { key.insert(GUI); }
+    key
+}
+
+/// Maps winit's key to a conrod `Key`.
+pub fn map_key(keycode: winit::VirtualKeyCode) -> input::keyboard::Key {
+    use input::keyboard::Key;
+
+    match keycode {
+        winit::VirtualKeyCode::Key0 => Key::D0,
+        winit::VirtualKeyCode::Key1 => Key::D1,
+        winit::VirtualKeyCode::Key2 => Key::D2,
+        winit::VirtualKeyCode::Key3 => Key::D3,
+        winit::VirtualKeyCode::Key4 => Key::D4,
+        winit::VirtualKeyCode::Key5 => Key::D5,
+        winit::VirtualKeyCode::Key6 => Key::D6,
+        winit::VirtualKeyCode::Key7 => Key::D7,
+        winit::VirtualKeyCode::Key8 => Key::D8,
+        winit::VirtualKeyCode::Key9 => Key::D9,
+        winit::VirtualKeyCode::A => Key::A,
+        winit::VirtualKeyCode::B => Key::B,
+        winit::VirtualKeyCode::C => Key::C,
+        winit::VirtualKeyCode::D => Key::D,
+        winit::VirtualKeyCode::E => Key::E,
+        winit::VirtualKeyCode::F => Key::F,
+        winit::VirtualKeyCode::G => Key::G,
+        winit::VirtualKeyCode::H => Key::H,
+        winit::VirtualKeyCode::I => Key::I,
+        winit::VirtualKeyCode::J => Key::J,
+        winit::VirtualKeyCode::K => Key::K,
+        winit::VirtualKeyCode::L => Key::L,
+        winit::VirtualKeyCode::M => Key::M,
+        winit::VirtualKeyCode::N => Key::N,
+        winit::VirtualKeyCode::O => Key::O,
+        winit::VirtualKeyCode::P => Key::P,
+        winit::VirtualKeyCode::Q => Key::Q,
+        winit::VirtualKeyCode::R => Key::R,
+        winit::VirtualKeyCode::S => Key::S,
+        winit::VirtualKeyCode::T => Key::T,
+        winit::VirtualKeyCode::U => Key::U,
+        winit::VirtualKeyCode::V => Key::V,
+        winit::VirtualKeyCode::W => Key::W,
+        winit::VirtualKeyCode::X => Key::X,
+        winit::VirtualKeyCode::Y => Key::Y,
+        winit::VirtualKeyCode::Z => Key::Z,
+        winit::VirtualKeyCode::Apostrophe => Key::Quote,
+        winit::VirtualKeyCode::Backslash => Key::Backslash,
+        winit::VirtualKeyCode::Back => Key::Backspace,
+        winit::VirtualKeyCode::Capital => Key::CapsLock,
+        winit::VirtualKeyCode::Delete => Key::Delete,
+        winit::VirtualKeyCode::Comma => Key::Comma,
+        winit::VirtualKeyCode::Down => Key::Down,
+        winit::VirtualKeyCode::End => Key::End,
+        winit::VirtualKeyCode::Return => Key::Return,
+        winit::VirtualKeyCode::Equals => Key::Equals,
+        winit::VirtualKeyCode::Escape => Key::Escape,
+        winit::VirtualKeyCode::F1 => Key::F1,
+        winit::VirtualKeyCode::F2 => Key::F2,
+        winit::VirtualKeyCode::F3 => Key::F3,
+        winit::VirtualKeyCode::F4 => Key::F4,
+        winit::VirtualKeyCode::F5 => Key::F5,
+        winit::VirtualKeyCode::F6 => Key::F6,
+        winit::VirtualKeyCode::F7 => Key::F7,
+        winit::VirtualKeyCode::F8 => Key::F8,
+        winit::VirtualKeyCode::F9 => Key::F9,
+        winit::VirtualKeyCode::F10 => Key::F10,
+        winit::VirtualKeyCode::F11 => Key::F11,
+        winit::VirtualKeyCode::F12 => Key::F12,
+        winit::VirtualKeyCode::F13 => Key::F13,
+        winit::VirtualKeyCode::F14 => Key::F14,
+        winit::VirtualKeyCode::F15 => Key::F15,
+        winit::VirtualKeyCode::F16 => Key::F16,
+        winit::VirtualKeyCode::F17 => Key::F17,
+        winit::VirtualKeyCode::F18 => Key::F18,
+        winit::VirtualKeyCode::F19 => Key::F19,
+        winit::VirtualKeyCode::F20 => Key::F20,
+        winit::VirtualKeyCode::F21 => Key::F21,
+        winit::VirtualKeyCode::F22 => Key::F22,
+        winit::VirtualKeyCode::F23 => Key::F23,
+        winit::VirtualKeyCode::F24 => Key::F24,
+        winit::VirtualKeyCode::Numpad0 => Key::NumPad0,
+        winit::VirtualKeyCode::Numpad1 => Key::NumPad1,
+        winit::VirtualKeyCode::Numpad2 => Key::NumPad2,
+        winit::VirtualKeyCode::Numpad3 => Key::NumPad3,
+        winit::VirtualKeyCode::Numpad4 => Key::NumPad4,
+        winit::VirtualKeyCode::Numpad5 => Key::NumPad5,
+        winit::VirtualKeyCode::Numpad6 => Key::NumPad6,
+        winit::VirtualKeyCode::Numpad7 => Key::NumPad7,
+        winit::VirtualKeyCode::Numpad8 => Key::NumPad8,
+        winit::VirtualKeyCode::Numpad9 => Key::NumPad9,
+        winit::VirtualKeyCode::NumpadComma => Key::NumPadDecimal,
+        winit::VirtualKeyCode::Decimal => Key::NumPadDecimal,
+        winit::VirtualKeyCode::Divide => Key::NumPadDivide,
+        winit::VirtualKeyCode::Multiply => Key::NumPadMultiply,
+        winit::VirtualKeyCode::Subtract => Key::NumPadMinus,
+        winit::VirtualKeyCode::Add => Key::NumPadPlus,
+        winit::VirtualKeyCode::NumpadEnter => Key::NumPadEnter,
+        winit::VirtualKeyCode::NumpadEquals => Key::NumPadEquals,
+        winit::VirtualKeyCode::LShift => Key::LShift,
+        winit::VirtualKeyCode::LControl => Key::LCtrl,
+        winit::VirtualKeyCode::LAlt => Key::LAlt,
+        winit::VirtualKeyCode::LWin => Key::LGui,
+        winit::VirtualKeyCode::RShift => Key::RShift,
+        winit::VirtualKeyCode::RControl => Key::RCtrl,
+        winit::VirtualKeyCode::RAlt => Key::RAlt,
+        winit::VirtualKeyCode::RWin => Key::RGui,
+        winit::VirtualKeyCode::Grave => Key::Backquote,
+        winit::VirtualKeyCode::Home => Key::Home,
+        winit::VirtualKeyCode::Insert => Key::Insert,
+        winit::VirtualKeyCode::Left => Key::Left,
+        winit::VirtualKeyCode::LBracket => Key::LeftBracket,
+        winit::VirtualKeyCode::Minus => Key::Minus,
+        winit::VirtualKeyCode::Numlock => Key::NumLockClear,
+        winit::VirtualKeyCode::PageDown => Key::PageDown,
+        winit::VirtualKeyCode::PageUp => Key::PageUp,
+        winit::VirtualKeyCode::Pause => Key::Pause,
+        winit::VirtualKeyCode::Period => Key::Period,
+        winit::VirtualKeyCode::Snapshot => Key::PrintScreen,
+        winit::VirtualKeyCode::Right => Key::Right,
+        winit::VirtualKeyCode::RBracket => Key::RightBracket,
+        winit::VirtualKeyCode::Scroll => Key::ScrollLock,
+        winit::VirtualKeyCode::Semicolon => Key::Semicolon,
+        winit::VirtualKeyCode::Slash => Key::Slash,
+        winit::VirtualKeyCode::Space => Key::Space,
+        winit::VirtualKeyCode::Tab => Key::Tab,
+        winit::VirtualKeyCode::Up => Key::Up,
+        _ => Key::Unknown,
+    }
+}
+
+/// Maps winit's mouse button to Piston's mouse button.
+pub fn map_mouse(mouse_button: winit::MouseButton) -> input::MouseButton {
+    use input::MouseButton;
+    match mouse_button {
+        winit::MouseButton::Left => MouseButton::Left,
+        winit::MouseButton::Right => MouseButton::Right,
+        winit::MouseButton::Middle => MouseButton::Middle,
+        winit::MouseButton::Other(0) => MouseButton::X1,
+        winit::MouseButton::Other(1) => MouseButton::X2,
+        winit::MouseButton::Other(2) => MouseButton::Button6,
+        winit::MouseButton::Other(3) => MouseButton::Button7,
+        winit::MouseButton::Other(4) => MouseButton::Button8,
+        _ => MouseButton::Unknown
+    }
+}
+
+/// Maps conrod's `MouseCursor` to the equivalent winit `MouseCursor`.
+///
+/// The result may be passed to `winit::Window::set_cursor`.
+pub fn map_mouse_cursor(cursor: MouseCursor) -> winit::MouseCursor {
+    match cursor {
+        MouseCursor::Arrow => winit::MouseCursor::Default,
+        MouseCursor::Text => winit::MouseCursor::Text,
+        MouseCursor::VerticalText => winit::MouseCursor::VerticalText,
+        MouseCursor::Hand => winit::MouseCursor::Hand,
+        MouseCursor::Crosshair => winit::MouseCursor::Crosshair,
+        MouseCursor::Grab => winit::MouseCursor::Grab,
+        MouseCursor::Grabbing => winit::MouseCursor::Grabbing,
+        MouseCursor::Move => winit::MouseCursor::Move,
+        MouseCursor::NotAllowed => winit::MouseCursor::NotAllowed,
+        MouseCursor::Wait => winit::MouseCursor::Wait,
+        MouseCursor::ResizeHorizontal => winit::MouseCursor::EwResize,
+        MouseCursor::ResizeVertical => winit::MouseCursor::NsResize,
+        MouseCursor::ResizeTopLeftBottomRight => winit::MouseCursor::NwseResize,
+        MouseCursor::ResizeTopRightBottomLeft => winit::MouseCursor::NeswResize,
+    }
+}
+
+
+#[test]
+fn window_events_should_map_to_centred_inputs() {
+    use input::{Button, Key, MouseButton};
+    let device_id = unsafe { winit::DeviceId::dummy() };
+    let modifiers = winit::ModifiersState::default();
+    let convert = |e| convert_window_event_for_size(e, 800.0, 600.0);
+
+    let moved = winit::WindowEvent::CursorMoved {
+        device_id: device_id,
+        position: winit::dpi::LogicalPosition::new(500.0, 200.0),
+        modifiers: modifiers,
+    };
+    assert_eq!(Some(Input::Move(Motion::MouseCursor(100.0, 100.0))), convert(moved));
+
+    let pressed = winit::WindowEvent::MouseInput {
+        device_id: device_id,
+        state: winit::ElementState::Pressed,
+        button: winit::MouseButton::Left,
+        modifiers: modifiers,
+    };
+    assert_eq!(Some(Input::Press(Button::Mouse(MouseButton::Left))), convert(pressed));
+
+    let released = winit::WindowEvent::KeyboardInput {
+        device_id: device_id,
+        input: winit::KeyboardInput {
+            scancode: 0,
+            state: winit::ElementState::Released,
+            virtual_keycode: Some(winit::VirtualKeyCode::LShift),
+            modifiers: modifiers,
+        },
+    };
+    assert_eq!(Some(Input::Release(Button::Keyboard(Key::LShift))), convert(released));
+
+    let scrolled = winit::WindowEvent::MouseWheel {
+        device_id: device_id,
+        delta: winit::MouseScrollDelta::LineDelta(0.0, 2.0),
+        phase: winit::TouchPhase::Moved,
+        modifiers: modifiers,
+    };
+    assert_eq!(Some(Input::Move(Motion::MouseScroll(0.0, -20.0))), convert(scrolled));
+
+    let text = winit::WindowEvent::ReceivedCharacter('a');
+    assert_eq!(Some(Input::Text("a".to_string())), convert(text));
+
+    let resized = winit::WindowEvent::Resized(winit::dpi::LogicalSize::new(1024.0, 768.0));
+    assert_eq!(Some(Input::Resize(1024, 768)), convert(resized));
+}
+
+#[test]
+fn device_events_should_be_scaled_from_physical_pixels() {
+    let motion = winit::DeviceEvent::MouseMotion { delta: (20.0, 10.0) };
+    assert_eq!(Some(Input::Move(Motion::MouseRelative(10.0, -5.0))),
+               convert_device_event(motion, 2.0));
+
+    let position = winit::dpi::LogicalPosition::new(0.0, 30.0);
+    let scroll = winit::DeviceEvent::MouseWheel {
+        delta: winit::MouseScrollDelta::PixelDelta(position),
+    };
+    assert_eq!(Some(Input::Move(Motion::MouseScroll(0.0, -15.0))),
+               convert_device_event(scroll, 2.0));
+}
+
+#[test]
+fn modifiers_should_map_to_modifier_keys() {
+    use input::keyboard::{CTRL, NO_MODIFIER, SHIFT};
+    let mut modifiers = winit::ModifiersState::default();
+    assert_eq!(NO_MODIFIER, map_modifiers(modifiers));
+    modifiers.shift = true;
+    modifiers.ctrl = true;
+    assert_eq!(SHIFT | CTRL, map_modifiers(modifiers));
+}
@@ -0,0 +1,86 @@
+//! Types for reading and writing the clipboard used by text widgets for cut, copy and paste.
+//!
+//! The `Ui` owns a single `Clipboard` which may be specified via `UiBuilder::clipboard`. By
+//! default this is an in-memory `clipboard::Memory`, which is shared by all widgets within the
+//! `Ui` but not with other applications. Enable the `clipboard` feature for a `clipboard::System`
+//! that uses the operating system's clipboard.
+
+#[cfg(feature = "clipboard")]
+pub use self::system::System;
+
+
+/// Some storage from which text may be copied and to which text may be pasted.
+pub trait Clipboard {
+    /// The text currently stored within the clipboard, if any.
+    fn contents(&mut self) -> Option<String>;
+    /// Replace the contents of the clipboard with the given text.
+    fn set_contents(&mut self, contents: String);
+}
+
+/// A `Clipboard` that stores its contents in memory.
+///
+/// This is the default clipboard used by the `Ui`. It is also useful for testing cut, copy and
+/// paste without touching the system clipboard.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    contents: Option<String>,
+}
+
+
+impl Memory {
+    /// An empty clipboard.
+    pub fn new() -> Self {
+        Memory { contents: None }
+    }
+}
+
+impl Clipboard for Memory {
+    fn contents(&mut self) -> Option<String> {
+        self.contents.clone()
+    }
+    fn set_contents(&mut self, contents: String) {
+        self.contents = Some(contents);
+    }
+}
+
+
+#[cfg(feature = "clipboard")]
+mod system {
+    extern crate clipboard;
+
+    use self::clipboard::ClipboardProvider;
+    use std;
+
+    /// A `Clipboard` that reads from and writes to the operating system's clipboard.
+    pub struct System {
+        context: clipboard::ClipboardContext,
+    }
+
+    impl System {
+        /// Connect to the operating system's clipboard.
+        pub fn new() -> Result<Self, Box<std::error::Error>> {
+            let context = try!(clipboard::ClipboardContext::new());
+            Ok(System { context: context })
+        }
+    }
+
+    impl super::Clipboard for System {
+        fn contents(&mut self) -> Option<String> {
+            self.context.get_contents().ok()
+        }
+        fn set_contents(&mut self, contents: String) {
+            // Failing to reach the system clipboard leaves its contents unchanged.
+            let _ = self.context.set_contents(contents);
+        }
+    }
+}
+
+
+#[test]
+fn memory_clipboard_should_return_the_most_recently_set_contents() {
+    let mut clipboard = Memory::new();
+    assert_eq!(None, clipboard.contents());
+    clipboard.set_contents("foo".to_string());
+    clipboard.set_contents("bar".to_string());
+    assert_eq!(Some("bar".to_string()), clipboard.contents());
+}
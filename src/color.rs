@@ -11,6 +11,7 @@
 //! and [HSL](http://en.wikipedia.org/wiki/HSL_and_HSV) creation, gradients and built-in names.
 //!
 
+use std;
 use std::f32::consts::PI;
 use utils::{degrees, fmod, turns};
 
@@ -61,7 +62,13 @@ pub fn rgb_bytes(r: u8, g: u8, b: u8) -> Color {
 /// transparency.
 #[inline]
 pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Color {
-    Color::Hsla(hue - turns((hue / (2.0 * PI)).floor()), saturation, lightness, alpha)
+    Color::Hsla(wrap_hue(hue), saturation, lightness, alpha)
+}
+
+
+/// Wrap the given hue (in radians) to the range `0.0..2.0*PI`.
+fn wrap_hue(hue: f32) -> f32 {
+    hue - turns((hue / (2.0 * PI)).floor())
 }
 
 
@@ -148,6 +155,34 @@ impl Color {
         }
     }
 
+    /// Extract the hue, saturation and value components of the color in the
+    /// [HSV](http://en.wikipedia.org/wiki/HSL_and_HSV) format.
+    ///
+    /// The hue is given in radians within the range `0.0..2.0*PI`. Grayscale colors (where the
+    /// saturation is `0.0`) always have a hue of `0.0`.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let Rgba(r, g, b, _) = self.to_rgb();
+        rgb_to_hsv(r, g, b)
+    }
+
+    /// Create an opaque RGB color from the given [HSV](http://en.wikipedia.org/wiki/HSL_and_HSV)
+    /// components, where the hue is given in radians.
+    ///
+    /// Hues outside of the range `0.0..2.0*PI` wrap around the color wheel.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let (r, g, b) = hsv_to_rgb(wrap_hue(hue), saturation, value);
+        rgb(r, g, b)
+    }
+
+    /// Create an opaque RGB color from the given [HSL](http://en.wikipedia.org/wiki/HSL_and_HSV)
+    /// components, where the hue is given in radians.
+    ///
+    /// Unlike the `hsl` function, which produces a `Color::Hsla`, this produces a `Color::Rgba`.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+        let (r, g, b) = hsl_to_rgb(wrap_hue(hue), saturation, lightness);
+        rgb(r, g, b)
+    }
+
     /// Extract the components of a color in the RGB format.
     pub fn to_rgb(self) -> Rgba {
         match self {
@@ -171,13 +206,69 @@ impl Color {
         [f32_to_byte(r), f32_to_byte(g), f32_to_byte(b), f32_to_byte(a)]
     }
 
-    // /// Return the hex representation of this color in the format #RRGGBBAA
-    // /// e.g. `Color(1.0, 0.0, 5.0, 1.0) == "#FF0080FF"`
-    // pub fn to_hex(self) -> String {
-    //     let vals = self.to_byte_fsa();
-    //     let hex = vals.to_hex().to_ascii_uppercase();
-    //     format!("#{}", &hex)
-    // }
+    /// Convert the gamma-encoded sRGB channels of the color to linear RGB.
+    ///
+    /// The alpha channel remains unchanged.
+    pub fn to_linear(&self) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a)
+    }
+
+    /// Convert the linear RGB channels of the color to gamma-encoded sRGB.
+    ///
+    /// This is the inverse of `to_linear`.
+    pub fn to_srgb(&self) -> Color {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        rgba(linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a)
+    }
+
+    /// The same as `overlay`, but the compositing is performed in linear RGB space rather than
+    /// directly upon the gamma-encoded sRGB values, avoiding the darkened edges that occur when
+    /// blending in sRGB space.
+    ///
+    /// Fully opaque and fully transparent `top` colors produce exactly the same result as
+    /// `overlay`.
+    pub fn overlay_linear(&self, top: Color) -> Color {
+        let Rgba(_, _, _, a_t) = top.to_rgb();
+        if a_t <= 0.0 || a_t >= 1.0 {
+            return self.overlay(top);
+        }
+        self.to_linear().overlay(top.to_linear()).to_srgb()
+    }
+
+    /// Return the hex representation of this color in the format `#RRGGBBAA`.
+    ///
+    /// e.g. `rgba(1.0, 0.0, 0.5, 1.0).to_hex() == "#FF0080FF"`
+    pub fn to_hex(&self) -> String {
+        let Rgba(r, g, b, a) = self.to_rgb();
+        let byte = |c: f32| (clampf32(c) * 255.0).round() as u8;
+        format!("#{:02X}{:02X}{:02X}{:02X}", byte(r), byte(g), byte(b), byte(a))
+    }
+
+    /// Parse a color from a hex string in one of the following formats:
+    ///
+    /// - `#RGB`, where each digit is repeated, e.g. `#F80 == #FF8800`.
+    /// - `#RRGGBB`.
+    /// - `#RRGGBBAA`.
+    ///
+    /// The leading `#` is optional and digits are case-insensitive. Colors without an alpha
+    /// component are fully opaque.
+    pub fn from_hex(s: &str) -> Result<Color, ColorParseError> {
+        let digits = if s.starts_with('#') { &s[1..] } else { s };
+
+        if let Some(ch) = digits.chars().find(|ch| !ch.is_digit(16)) {
+            return Err(ColorParseError::InvalidCharacter(ch));
+        }
+
+        // All digits are now known to be ASCII, so byte indexing is safe.
+        let byte = |i: usize, len: usize| u8::from_str_radix(&digits[i..i+len], 16).unwrap();
+        match digits.len() {
+            3 => Ok(rgb_bytes(byte(0, 1) * 17, byte(1, 1) * 17, byte(2, 1) * 17)),
+            6 => Ok(rgb_bytes(byte(0, 2), byte(2, 2), byte(4, 2))),
+            8 => Ok(rgba_bytes(byte(0, 2), byte(2, 2), byte(4, 2), byte(6, 2) as f32 / 255.0)),
+            len => Err(ColorParseError::InvalidLength(len)),
+        }
+    }
 
     /// Return the same color but with the given luminance.
     pub fn with_luminance(self, l: f32) -> Color {
@@ -277,6 +368,39 @@ impl Color {
         *self = rgba(r, g, b, a);
     }
 
+    /// Linearly interpolate between `self` and `other` by `t`, where `t == 0.0` returns `self`
+    /// and `t == 1.0` returns `other`. `t` is clamped to the range `0.0...1.0`.
+    ///
+    /// The interpolation of the red, green and blue channels is performed in linear RGB space
+    /// rather than directly upon the gamma-encoded sRGB values so that the result is perceptually
+    /// even. The alpha channel is interpolated directly.
+    pub fn blend(&self, other: Color, t: f32) -> Color {
+        let t = clampf32(t);
+        let Rgba(r_a, g_a, b_a, a_a) = self.to_rgb();
+        let Rgba(r_b, g_b, b_b, a_b) = other.to_rgb();
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let lerp_linear = |a: f32, b: f32| {
+            linear_to_srgb(lerp(srgb_to_linear(a), srgb_to_linear(b)))
+        };
+        rgba(lerp_linear(r_a, r_b),
+             lerp_linear(g_a, g_b),
+             lerp_linear(b_a, b_b),
+             lerp(a_a, a_b))
+    }
+
+    /// Composite the `top` color over `self` using straight (non-premultiplied) alpha, i.e. the
+    /// porter-duff "over" operator.
+    pub fn overlay(&self, top: Color) -> Color {
+        let Rgba(r_b, g_b, b_b, a_b) = self.to_rgb();
+        let Rgba(r_t, g_t, b_t, a_t) = top.to_rgb();
+        let a = a_t + a_b * (1.0 - a_t);
+        if a == 0.0 {
+            return rgba(0.0, 0.0, 0.0, 0.0);
+        }
+        let over = |t: f32, b: f32| (t * a_t + b * a_b * (1.0 - a_t)) / a;
+        rgba(over(r_t, r_b), over(g_t, g_b), over(b_t, b_b), a)
+    }
+
 }
 
 
@@ -290,11 +414,55 @@ pub struct Hsla(pub f32, pub f32, pub f32, pub f32);
 pub struct Rgba(pub f32, pub f32, pub f32, pub f32);
 
 
+/// The error returned by `Color::from_hex` when a string could not be parsed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColorParseError {
+    /// The number of hex digits (excluding the optional leading `#`) was not 3, 6 or 8.
+    InvalidLength(usize),
+    /// The string contained a character that is not a hex digit.
+    InvalidCharacter(char),
+}
+
+impl std::error::Error for ColorParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ColorParseError::InvalidLength(_) =>
+                "A hex color must have 3 (#RGB), 6 (#RRGGBB) or 8 (#RRGGBBAA) digits.",
+            ColorParseError::InvalidCharacter(_) =>
+                "A hex color may only contain the digits 0-9, a-f and A-F.",
+        }
+    }
+}
+
+impl std::fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            ColorParseError::InvalidLength(len) =>
+                write!(f, "{} Found {} digits.", std::error::Error::description(self), len),
+            ColorParseError::InvalidCharacter(ch) =>
+                write!(f, "{} Found {:?}.", std::error::Error::description(self), ch),
+        }
+    }
+}
+
+
 /// Convert an f32 color to a byte.
 #[inline]
 pub fn f32_to_byte(c: f32) -> u8 { (c * 255.0) as u8 }
 
 
+/// Convert a gamma-encoded sRGB channel value to linear RGB.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+
+/// Convert a linear RGB channel value to gamma-encoded sRGB.
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+
 /// Pure function for converting rgb to hsl.
 pub fn rgb_to_hsl(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
     let c_max = r.max(g).max(b);
@@ -337,6 +505,46 @@ pub fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (f32, f32, f32)
 }
 
 
+/// Pure function for converting rgb to hsv.
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let c_max = r.max(g).max(b);
+    let c_min = r.min(g).min(b);
+    let c = c_max - c_min;
+
+    let hue = if c == 0.0 {
+        // If there's no difference in the channels we have grayscale, so the hue is undefined.
+        0.0
+    } else {
+        degrees(60.0) * if      c_max == r { fmod(((g - b) / c), 6) }
+                        else if c_max == g { ((b - r) / c) + 2.0 }
+                        else               { ((r - g) / c) + 4.0 }
+    };
+
+    let saturation = if c_max == 0.0 { 0.0 } else { c / c_max };
+    (hue, saturation, c_max)
+}
+
+
+/// Pure function for converting hsv to rgb.
+pub fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> (f32, f32, f32) {
+    let chroma = value * saturation;
+    let hue = hue / degrees(60.0);
+    let x = chroma * (1.0 - (fmod(hue, 2) - 1.0).abs());
+    let (r, g, b) = match hue {
+        hue if hue < 0.0 => (0.0, 0.0, 0.0),
+        hue if hue < 1.0 => (chroma, x, 0.0),
+        hue if hue < 2.0 => (x, chroma, 0.0),
+        hue if hue < 3.0 => (0.0, chroma, x),
+        hue if hue < 4.0 => (0.0, x, chroma),
+        hue if hue < 5.0 => (x, 0.0, chroma),
+        hue if hue < 6.0 => (chroma, 0.0, x),
+        _ => (0.0, 0.0, 0.0),
+    };
+    let m = value - chroma;
+    (r + m, g + m, b + m)
+}
+
+
 /// Linear or Radial Gradient.
 #[derive(Clone, Debug)]
 pub enum Gradient {
@@ -520,3 +728,129 @@ fn plain_contrast_should_weight_colors() {
     assert_eq!(b, 1.0);
 
 }
+
+#[test]
+fn blend_should_interpolate_in_linear_space() {
+    fn assert_gray(color: Color, expected: f32) {
+        let Rgba(r, g, b, a) = color.to_rgb();
+        for &c in &[r, g, b] {
+            assert!((c - expected).abs() < 0.001, "expected {}, found {}", expected, c);
+        }
+        assert_eq!(1.0, a);
+    }
+
+    let black = rgb(0.0, 0.0, 0.0);
+    let white = rgb(1.0, 1.0, 1.0);
+
+    assert_gray(black.blend(white, 0.0), 0.0);
+    assert_gray(black.blend(white, 1.0), 1.0);
+
+    // `t` should be clamped to the range 0.0...1.0.
+    assert_gray(black.blend(white, -1.0), 0.0);
+    assert_gray(black.blend(white, 2.0), 1.0);
+
+    // Half way between black and white in linear space is ~0.735 in sRGB.
+    assert_gray(black.blend(white, 0.5), 0.7354);
+}
+
+#[test]
+fn overlay_should_alpha_composite() {
+    let blue = rgb(0.0, 0.0, 1.0);
+    let half_red = rgba(1.0, 0.0, 0.0, 0.5);
+
+    let Rgba(r, g, b, a) = blue.overlay(half_red).to_rgb();
+    assert_eq!((0.5, 0.0, 0.5, 1.0), (r, g, b, a));
+
+    // Compositing over a fully transparent color should yield the top color.
+    let Rgba(r, g, b, a) = rgba(0.0, 1.0, 0.0, 0.0).overlay(half_red).to_rgb();
+    assert_eq!((1.0, 0.0, 0.0, 0.5), (r, g, b, a));
+
+    // An opaque top color should completely cover the bottom.
+    assert_eq!(blue, half_red.overlay(blue));
+}
+
+#[cfg(test)]
+fn assert_rgb_approx_eq(a: Color, b: Color) {
+    let (Rgba(r_a, g_a, b_a, _), Rgba(r_b, g_b, b_b, _)) = (a.to_rgb(), b.to_rgb());
+    for &(x, y) in &[(r_a, r_b), (g_a, g_b), (b_a, b_b)] {
+        assert!((x - y).abs() < 0.0001, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn hsv_and_hsl_should_round_trip() {
+    let colors = [
+        rgb(1.0, 0.0, 0.0),
+        rgb(0.0, 1.0, 0.0),
+        rgb(0.0, 0.0, 1.0),
+        rgb(0.2, 0.4, 0.6),
+        rgb(0.9, 0.1, 0.5),
+        rgb(0.75, 0.75, 0.1),
+    ];
+    for &color in colors.iter() {
+        let (h, s, v) = color.to_hsv();
+        assert_rgb_approx_eq(color, Color::from_hsv(h, s, v));
+        let Hsla(h, s, l, _) = color.to_hsl();
+        assert_rgb_approx_eq(color, Color::from_hsl(h, s, l));
+    }
+
+    let (h, s, v) = rgb(0.0, 0.0, 1.0).to_hsv();
+    assert!((h - degrees(240.0)).abs() < 0.0001);
+    assert_eq!((1.0, 1.0), (s, v));
+}
+
+#[test]
+fn hsv_hue_should_wrap_around() {
+    assert_rgb_approx_eq(rgb(0.0, 1.0, 0.0), Color::from_hsv(degrees(480.0), 1.0, 1.0));
+    assert_rgb_approx_eq(rgb(0.0, 0.0, 1.0), Color::from_hsv(degrees(-120.0), 1.0, 1.0));
+    assert_rgb_approx_eq(rgb(1.0, 0.0, 0.0), Color::from_hsv(degrees(360.0), 1.0, 1.0));
+    assert_rgb_approx_eq(rgb(0.0, 1.0, 0.0), Color::from_hsl(degrees(480.0), 1.0, 0.5));
+}
+
+#[test]
+fn grayscale_should_have_a_hue_of_zero() {
+    assert_eq!((0.0, 0.0, 0.5), rgb(0.5, 0.5, 0.5).to_hsv());
+    assert_eq!((0.0, 0.0, 0.0), rgb(0.0, 0.0, 0.0).to_hsv());
+    let Hsla(h, s, _, _) = rgb(0.5, 0.5, 0.5).to_hsl();
+    assert_eq!((0.0, 0.0), (h, s));
+    assert_rgb_approx_eq(rgb(0.5, 0.5, 0.5), Color::from_hsv(0.0, 0.0, 0.5));
+}
+
+#[test]
+fn hex_should_round_trip() {
+    assert_eq!("#FF0080FF", rgba(1.0, 0.0, 0.5, 1.0).to_hex());
+    for hex in &["#FF0080FF", "#00000000", "#3465A4FF", "#EEEEEC80"] {
+        assert_eq!(*hex, Color::from_hex(hex).unwrap().to_hex());
+    }
+    assert_eq!("#FF8800FF", Color::from_hex("#F80").unwrap().to_hex());
+    assert_eq!("#3465A4FF", Color::from_hex("3465a4").unwrap().to_hex());
+    assert_eq!(rgb_bytes(52, 101, 164), Color::from_hex("#3465A4").unwrap());
+}
+
+#[test]
+fn invalid_hex_should_produce_an_error() {
+    assert_eq!(Err(ColorParseError::InvalidLength(0)), Color::from_hex("#"));
+    assert_eq!(Err(ColorParseError::InvalidLength(4)), Color::from_hex("#FFFF"));
+    assert_eq!(Err(ColorParseError::InvalidLength(9)), Color::from_hex("FF0080FF0"));
+    assert_eq!(Err(ColorParseError::InvalidCharacter('G')), Color::from_hex("#GG0000"));
+    assert_eq!(Err(ColorParseError::InvalidCharacter('#')), Color::from_hex("##FFF"));
+    assert_eq!(Err(ColorParseError::InvalidCharacter('é')), Color::from_hex("#éFF"));
+}
+
+#[test]
+fn linear_overlay_should_differ_from_srgb_overlay() {
+    let black = rgb(0.0, 0.0, 0.0);
+    let half_white = rgba(1.0, 1.0, 1.0, 0.5);
+
+    let Rgba(srgb_r, _, _, srgb_a) = black.overlay(half_white).to_rgb();
+    let Rgba(linear_r, _, _, linear_a) = black.overlay_linear(half_white).to_rgb();
+    assert_eq!(0.5, srgb_r);
+    assert!((linear_r - 0.7354).abs() < 0.001);
+    assert_eq!(srgb_a, linear_a);
+
+    // Opaque colors should be unaffected by the blending space.
+    let opaque = rgb(0.2, 0.4, 0.6);
+    assert_eq!(black.overlay(opaque), black.overlay_linear(opaque));
+    assert_eq!(opaque.overlay(rgba(0.9, 0.1, 0.1, 0.0)),
+               opaque.overlay_linear(rgba(0.9, 0.1, 0.1, 0.0)));
+}
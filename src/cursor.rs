@@ -0,0 +1,45 @@
+//! Types for describing the shape of the mouse cursor.
+//!
+//! Widgets may request a particular cursor shape via the `Widget::mouse_cursor` builder method or
+//! by overriding `Widget::cursor_hint`. The `Ui` resolves the hint that applies to the current
+//! mouse position via `Ui::mouse_cursor`, which may then be applied to the window by the backend.
+
+
+/// The shape of the mouse cursor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseCursor {
+    /// The default arrow-shaped cursor.
+    Arrow,
+    /// A text-editing "I-beam" cursor.
+    Text,
+    /// A text-editing "I-beam" cursor for vertical text.
+    VerticalText,
+    /// A pointing hand, commonly used for hyperlinks.
+    Hand,
+    /// A crosshair, commonly used for precise selection.
+    Crosshair,
+    /// An open hand, indicating that something may be grabbed.
+    Grab,
+    /// A closed hand, indicating that something is being grabbed.
+    Grabbing,
+    /// Indicates that something may be moved in any direction.
+    Move,
+    /// Indicates that the interaction is not allowed.
+    NotAllowed,
+    /// Indicates that the program is busy.
+    Wait,
+    /// Indicates that something may be resized horizontally.
+    ResizeHorizontal,
+    /// Indicates that something may be resized vertically.
+    ResizeVertical,
+    /// Indicates that something may be resized from its top-left or bottom-right corner.
+    ResizeTopLeftBottomRight,
+    /// Indicates that something may be resized from its top-right or bottom-left corner.
+    ResizeTopRightBottomLeft,
+}
+
+impl Default for MouseCursor {
+    fn default() -> Self {
+        MouseCursor::Arrow
+    }
+}
@@ -94,6 +94,10 @@ pub enum Ui {
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(Option<widget::Id>, DoubleClick),
+    /// A context menu was requested by right-clicking the given widget.
+    ///
+    /// This immediately follows the right button's `Click`.
+    ContextMenu(Option<widget::Id>, ContextMenu),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Option<widget::Id>, Drag),
     /// A generic scroll event.
@@ -113,6 +117,27 @@ pub enum Ui {
     WidgetCapturesKeyboard(widget::Id),
     /// Indicates that the given widget has released the keyboard from capturing.
     WidgetUncapturesKeyboard(widget::Id),
+    /// Indicates that the given widget was instantiated during the previous update, but not during
+    /// the update before it.
+    ///
+    /// This event is always delivered one update late: whether a widget is newly instantiated is
+    /// only known once the update that instantiates it has finished, by which time it has already
+    /// received that update's events. The widget receives this event during its second update.
+    ///
+    /// A widget that re-appears after having not been instantiated for some updates is mounted
+    /// again.
+    WidgetMounted(widget::Id),
+    /// Indicates that the given widget was instantiated during the update before the previous
+    /// update, but not during the previous update.
+    ///
+    /// Like `WidgetMounted`, this event is delivered one update late, i.e. during the update
+    /// following the first update in which the widget was not instantiated.
+    WidgetUnmounted(widget::Id),
+    /// The given `Accelerator` registered by the given widget was pressed.
+    Accelerator(widget::Id, input::Accelerator),
+    /// Text is being composed via an input method editor (IME), along with the widget that was
+    /// capturing the keyboard at the time.
+    Composition(Option<widget::Id>, Composition),
 }
 
 /// Events that apply to a specific widget.
@@ -138,6 +163,8 @@ pub enum Widget {
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(DoubleClick),
+    /// A context menu was requested by right-clicking the widget.
+    ContextMenu(ContextMenu),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Drag),
     /// Represents the amount of scroll that has been applied to this widget.
@@ -152,6 +179,10 @@ pub enum Widget {
     CapturesKeyboard,
     /// Indicates that the given widget has released the keyboard from capturing.
     UncapturesKeyboard,
+    /// The given `Accelerator` registered by the widget was pressed.
+    Accelerator(input::Accelerator),
+    /// Text is being composed via an input method editor (IME).
+    Composition(Composition),
 }
 
 /// Contains all relevant information for a Text event.
@@ -163,6 +194,20 @@ pub struct Text {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// The stages of composing text via an input method editor (IME), e.g. for CJK input.
+///
+/// While composing, the in-progress "pre-edit" text should be displayed at the cursor (typically
+/// underlined) without yet being inserted into the text.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Composition {
+    /// The in-progress text, replacing any previous pre-edit text.
+    PreEdit(String),
+    /// The composition has ended, replacing the pre-edit text with the given text.
+    ///
+    /// The committed text is empty if the composition was cancelled.
+    Commit(String),
+}
+
 /// Contains all relevant information for a Motion event.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Move {
@@ -271,6 +316,12 @@ pub struct Click {
     pub xy: Point,
     /// Which modifier keys, if any, that were being held down when the user clicked
     pub modifiers: input::keyboard::ModifierKey,
+    /// The number of consecutive clicks made with the same button at the same position, each
+    /// within the `theme.double_click_threshold` of the last, including this one.
+    ///
+    /// For example, this is `1` for a single click, `2` for the second click of a double click and
+    /// `3` for a triple click.
+    pub count: u32,
 }
 
 /// Contains all the relevant information for a double click.
@@ -286,6 +337,15 @@ pub struct DoubleClick {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a request to open a context menu.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ContextMenu {
+    /// The position at which the context menu was requested, i.e. that of the right-click.
+    pub xy: Point,
+    /// Which modifier keys, if any, that were being held down when the menu was requested.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// Holds all the relevant information about a scroll event
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Scroll {
@@ -415,6 +475,16 @@ impl DoubleClick {
     }
 }
 
+impl ContextMenu {
+    /// Returns a copy of the ContextMenu relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> ContextMenu {
+        ContextMenu {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 impl Drag {
     /// Returns a copy of the Drag relative to the given `xy`
     pub fn relative_to(&self, xy: Point) -> Drag {
@@ -446,6 +516,12 @@ impl From<Text> for Widget {
     }
 }
 
+impl From<Composition> for Widget {
+    fn from(composition: Composition) -> Self {
+        Widget::Composition(composition)
+    }
+}
+
 impl From<Move> for Widget {
     fn from(move_: Move) -> Self {
         Widget::Move(move_)
@@ -476,6 +552,12 @@ impl From<DoubleClick> for Widget {
     }
 }
 
+impl From<ContextMenu> for Widget {
+    fn from(context_menu: ContextMenu) -> Self {
+        Widget::ContextMenu(context_menu)
+    }
+}
+
 impl From<Scroll> for Widget {
     fn from(scroll: Scroll) -> Self {
         Widget::Scroll(scroll)
@@ -64,15 +64,22 @@ impl PickWidgets {
     ///
     /// If the next widget is some graphic element of another widget, the graphic parent will be
     /// returned.
+    ///
+    /// Widgets that do not consume events (see `Widget::consume_events`) are skipped so that
+    /// events may pass through them to the widgets beneath.
     pub fn next(&mut self, graph: &Graph, depth_order: &[widget::Id]) -> Option<widget::Id> {
-        self.next_including_graphics_children(graph, depth_order)
-            .map(|idx| {
-                // Ensure that if we've picked some widget that is a **Graphic** child of some
-                // other widget, we return the **Graphic** parent.
-                graph.graphic_parent_recursion(idx)
-                    .last_node(graph)
-                    .unwrap_or(idx)
-            })
+        while let Some(idx) = self.next_including_graphics_children(graph, depth_order) {
+            // Ensure that if we've picked some widget that is a **Graphic** child of some
+            // other widget, we return the **Graphic** parent.
+            let idx = graph.graphic_parent_recursion(idx)
+                .last_node(graph)
+                .unwrap_or(idx);
+            let consumes_events = graph.widget(idx).map(|c| c.consumes_events).unwrap_or(true);
+            if consumes_events {
+                return Some(idx);
+            }
+        }
+        None
     }
 
 }
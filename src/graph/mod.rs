@@ -3,6 +3,7 @@
 //!
 //! The primary type of interest in this module is the [**Graph**](./struct.Graph) type.
 
+use cursor::MouseCursor;
 use daggy;
 use position::{Axis, Depth, Rect};
 use std;
@@ -85,6 +86,17 @@ pub struct Container {
     pub maybe_floating: Option<widget::Floating>,
     /// Whether or not children widgets should be cropped to the `kid_area`.
     pub crop_kids: bool,
+    /// The shape that the mouse cursor should take while over or captured by the widget.
+    pub maybe_cursor_hint: Option<MouseCursor>,
+    /// The text displayed within a `Tooltip` while the mouse hovers over the widget.
+    ///
+    /// This is the text given via `Widget::tooltip`, unless it was replaced by some owned text via
+    /// `UiCell::set_widget_tooltip` during the latest update.
+    pub maybe_tooltip: Option<std::borrow::Cow<'static, str>>,
+    /// Whether or not the widget consumes the mouse events that occur over it.
+    ///
+    /// Widgets that do not consume events are skipped when picking the widget under the mouse.
+    pub consumes_events: bool,
     /// Scroll related state (is only `Some` if this axis is scrollable).
     pub maybe_x_scroll_state: Option<widget::scroll::StateX>,
     /// Scroll related state (is only `Some` if this axis is scrollable).
@@ -610,13 +622,17 @@ impl Graph {
         let widget::PreUpdateCache {
             type_id, id, maybe_parent_id, maybe_x_positioned_relatively_id,
             maybe_y_positioned_relatively_id, rect, depth, kid_area, maybe_floating,
-            crop_kids, maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for,
+            crop_kids, maybe_cursor_hint, maybe_tooltip, consumes_events, maybe_x_scroll_state,
+            maybe_y_scroll_state, maybe_graphics_for,
         } = widget;
 
         assert!(self.node(id).is_some(), "No node found for the given widget::Id {:?}", id);
 
+        // Any owned text given via `UiCell::set_widget_tooltip` is replaced upon each update.
+        let maybe_tooltip = maybe_tooltip.map(std::borrow::Cow::Borrowed);
+
         // Construct a new `Container` to place in the `Graph`.
-        let new_container = || Container {
+        let new_container = |maybe_tooltip| Container {
             maybe_state: None,
             type_id: type_id,
             rect: rect,
@@ -624,6 +640,9 @@ impl Graph {
             kid_area: kid_area,
             maybe_floating: maybe_floating,
             crop_kids: crop_kids,
+            maybe_cursor_hint: maybe_cursor_hint,
+            maybe_tooltip: maybe_tooltip,
+            consumes_events: consumes_events,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_y_scroll_state: maybe_y_scroll_state,
             instantiation_order_idx: instantiation_order_idx,
@@ -653,7 +672,7 @@ impl Graph {
 
             // If the node is currently a `Placeholder`, construct a new container and use this
             // to set it as the `Widget` variant.
-            node @ &mut Node::Placeholder => *node = Node::Widget(new_container()),
+            node @ &mut Node::Placeholder => *node = Node::Widget(new_container(maybe_tooltip)),
 
             // Otherwise, update the data in the container that already exists.
             &mut Node::Widget(ref mut container) => {
@@ -675,6 +694,9 @@ impl Graph {
                 container.kid_area = kid_area;
                 container.maybe_floating = maybe_floating;
                 container.crop_kids = crop_kids;
+                container.maybe_cursor_hint = maybe_cursor_hint;
+                container.maybe_tooltip = maybe_tooltip;
+                container.consumes_events = consumes_events;
                 container.maybe_x_scroll_state = maybe_x_scroll_state;
                 container.maybe_y_scroll_state = maybe_y_scroll_state;
                 container.instantiation_order_idx = instantiation_order_idx;
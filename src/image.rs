@@ -1,7 +1,9 @@
 //! A type used to manage a user's image data and map them to `Image` widgets:
 //!
 //! - [Map](./struct.Map.html)
+//! - [Atlas](./struct.Atlas.html)
 
+use {Rect, Scalar};
 use std;
 use widget;
 
@@ -84,6 +86,207 @@ impl<Img> std::iter::Extend<(widget::Id, Img)> for Map<Img> {
 }
 
 
+/// An allocator that packs many small images into a few large textures, or "pages".
+///
+/// Drawing many small images (such as icons) from a single texture allows backends to avoid
+/// binding a new texture for each one. The `Atlas` only decides where each image should be placed;
+/// copying the image data into the page textures is left to the user. Each `Image` widget may
+/// then display its region via `Image::atlas_region`, given the key at which the texture of the
+/// region's page is stored within the `image::Map`. Consecutive `Image`s on the same page are then
+/// drawn together.
+///
+/// Images are packed into horizontal "shelves" from the top of each page downwards. An image that
+/// is larger than the page dimensions along either axis is given a page of its own.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Atlas {
+    page_dimensions: [u32; 2],
+    pages: Vec<AtlasPage>,
+}
+
+/// A page of an `Atlas` along with the shelves that have been allocated within it.
+#[derive(Clone, Debug, PartialEq)]
+struct AtlasPage {
+    dimensions: [u32; 2],
+    shelves: Vec<Shelf>,
+    /// Whether or not the page is dedicated to a single oversized image.
+    is_dedicated: bool,
+}
+
+/// A row of images within an `AtlasPage`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Shelf {
+    /// The distance from the top of the page to the top of the shelf.
+    y: u32,
+    height: u32,
+    /// The distance from the left of the page to the first unallocated pixel of the shelf.
+    x: u32,
+}
+
+/// The area of an `Atlas` page allocated to a single image.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasRegion {
+    /// The index of the page on which the image was placed.
+    pub page: usize,
+    /// The `[width, height]` of the page in pixels.
+    pub page_dimensions: [u32; 2],
+    /// The position of the image's top left corner from the top left of the page, in pixels.
+    pub x: u32,
+    /// The position of the image's top edge from the top of the page, in pixels.
+    pub y: u32,
+    /// The width of the image in pixels.
+    pub w: u32,
+    /// The height of the image in pixels.
+    pub h: u32,
+}
+
+
+impl Atlas {
+
+    /// Construct an empty `Atlas` whose pages have the given `[width, height]` in pixels.
+    pub fn new(page_dimensions: [u32; 2]) -> Self {
+        Atlas {
+            page_dimensions: page_dimensions,
+            pages: Vec::new(),
+        }
+    }
+
+    /// The number of pages allocated so far.
+    pub fn num_pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// The `[width, height]` in pixels of the page at the given index.
+    ///
+    /// This is the `page_dimensions` given to `Atlas::new`, unless the page is dedicated to an
+    /// oversized image, in which case it is the size of that image.
+    pub fn page_dimensions(&self, page: usize) -> Option<[u32; 2]> {
+        self.pages.get(page).map(|page| page.dimensions)
+    }
+
+    /// Allocate a region for an image with the given dimensions in pixels.
+    ///
+    /// The image is placed on the existing shelf that fits it most tightly. If no shelf fits, a
+    /// new shelf is started beneath the others and, failing that, a new page is allocated.
+    pub fn allocate(&mut self, w: u32, h: u32) -> AtlasRegion {
+        let (page_w, page_h) = (self.page_dimensions[0], self.page_dimensions[1]);
+
+        // Images that could never fit within a page get a page of their own.
+        if w > page_w || h > page_h {
+            self.pages.push(AtlasPage {
+                dimensions: [w, h],
+                shelves: Vec::new(),
+                is_dedicated: true,
+            });
+            return AtlasRegion {
+                page: self.pages.len() - 1,
+                page_dimensions: [w, h],
+                x: 0,
+                y: 0,
+                w: w,
+                h: h,
+            };
+        }
+
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            if page.is_dedicated {
+                continue;
+            }
+            if let Some((x, y)) = page.allocate(w, h) {
+                return AtlasRegion {
+                    page: i,
+                    page_dimensions: page.dimensions,
+                    x: x,
+                    y: y,
+                    w: w,
+                    h: h,
+                };
+            }
+        }
+
+        let mut page = AtlasPage {
+            dimensions: self.page_dimensions,
+            shelves: Vec::new(),
+            is_dedicated: false,
+        };
+        let (x, y) = page.allocate(w, h).expect("an image within the page dimensions must fit");
+        self.pages.push(page);
+        AtlasRegion {
+            page: self.pages.len() - 1,
+            page_dimensions: self.page_dimensions,
+            x: x,
+            y: y,
+            w: w,
+            h: h,
+        }
+    }
+
+    /// Remove all allocations and pages.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+}
+
+impl AtlasPage {
+
+    /// Find the position of the top left corner of a new image with the given dimensions.
+    fn allocate(&mut self, w: u32, h: u32) -> Option<(u32, u32)> {
+        let (page_w, page_h) = (self.dimensions[0], self.dimensions[1]);
+
+        // Find the shelf that wastes the least height.
+        let best_shelf = self.shelves.iter_mut()
+            .filter(|shelf| shelf.height >= h && shelf.x + w <= page_w)
+            .min_by_key(|shelf| shelf.height - h);
+        if let Some(shelf) = best_shelf {
+            let x = shelf.x;
+            shelf.x += w;
+            return Some((x, shelf.y));
+        }
+
+        // Otherwise start a new shelf beneath the last.
+        let y = self.shelves.last().map_or(0, |shelf| shelf.y + shelf.height);
+        if y + h > page_h {
+            return None;
+        }
+        self.shelves.push(Shelf { y: y, height: h, x: w });
+        Some((0, y))
+    }
+
+}
+
+impl AtlasRegion {
+
+    /// The region as a `Rect` in pixels, suitable for `Image::source_rectangle`.
+    ///
+    /// Like all source rectangles, the *y* axis points upwards from the bottom of the page.
+    pub fn source_rect(&self) -> Rect {
+        let page_h = self.page_dimensions[1] as Scalar;
+        let (x, y, w, h) = (self.x as Scalar, self.y as Scalar, self.w as Scalar, self.h as Scalar);
+        Rect::from_corners([x, page_h - y - h], [x + w, page_h - y])
+    }
+
+    /// The region as normalised texture coordinates within the page, where `0.0` and `1.0` are
+    /// the edges of the page.
+    ///
+    /// Like the `source_rect`, the *y* axis points upwards from the bottom of the page.
+    pub fn uv_rect(&self) -> Rect {
+        let (page_w, page_h) = (self.page_dimensions[0] as Scalar,
+                                self.page_dimensions[1] as Scalar);
+        let src = self.source_rect();
+        Rect::from_corners([src.left() / page_w, src.bottom() / page_h],
+                           [src.right() / page_w, src.top() / page_h])
+    }
+
+    /// Whether or not the region shares any pixels with the `other` region.
+    pub fn overlaps(&self, other: &AtlasRegion) -> bool {
+        self.page == other.page
+            && self.x < other.x + other.w && other.x < self.x + self.w
+            && self.y < other.y + other.h && other.y < self.y + self.h
+    }
+
+}
+
+
 /// A macro for simplifying the instantiation of an `image::Map`.
 ///
 /// See the [**Map**](./image/struct.Map.html) documentation for an example.
@@ -97,3 +300,49 @@ macro_rules! image_map {
         map
     }};
 }
+
+
+#[test]
+fn atlas_should_pack_images_without_overlapping() {
+    let mut atlas = Atlas::new([64, 64]);
+    let sizes = [[16, 16], [32, 8], [8, 8], [40, 20], [16, 16], [24, 30], [10, 4], [64, 10]];
+    let regions: Vec<AtlasRegion> = sizes.iter().map(|wh| atlas.allocate(wh[0], wh[1])).collect();
+
+    for (i, region) in regions.iter().enumerate() {
+        assert_eq!([sizes[i][0], sizes[i][1]], [region.w, region.h]);
+        assert!(region.x + region.w <= 64 && region.y + region.h <= 64, "{:?}", region);
+        for other in &regions[i + 1..] {
+            assert!(!region.overlaps(other), "{:?} overlaps {:?}", region, other);
+        }
+    }
+
+    // The small images share the first shelf and the second page is only used once full.
+    assert_eq!((0, 0, 0), (regions[0].page, regions[0].x, regions[0].y));
+    assert_eq!((0, 16, 0), (regions[1].page, regions[1].x, regions[1].y));
+    assert_eq!((0, 48, 0), (regions[2].page, regions[2].x, regions[2].y));
+    assert_eq!(2, atlas.num_pages());
+}
+
+#[test]
+fn atlas_should_give_oversized_images_their_own_page() {
+    let mut atlas = Atlas::new([64, 64]);
+    let small = atlas.allocate(8, 8);
+    let big = atlas.allocate(100, 20);
+    let next = atlas.allocate(8, 8);
+    assert_eq!(1, big.page);
+    assert_eq!(Some([100, 20]), atlas.page_dimensions(1));
+    assert_eq!((0, 0), (big.x, big.y));
+    // Later images continue to be packed into the shared page.
+    assert_eq!(0, next.page);
+    assert!(!small.overlaps(&next));
+    assert_eq!(2, atlas.num_pages());
+}
+
+#[test]
+fn atlas_regions_should_describe_their_source_and_uv_rects() {
+    let region = AtlasRegion { page: 0, page_dimensions: [64, 32], x: 16, y: 8, w: 16, h: 8 };
+    let src = region.source_rect();
+    assert_eq!((16.0, 32.0, 16.0, 24.0), src.l_r_b_t());
+    let uv = region.uv_rect();
+    assert_eq!((0.25, 0.5, 0.5, 0.75), uv.l_r_b_t());
+}
@@ -0,0 +1,194 @@
+//! Keyboard accelerators (or "mnemonics") that activate a widget when some key chord is pressed.
+//!
+//! Widgets register an `Accelerator` with the `Ui` during each update via
+//! `UiCell::register_accelerator`. When the accelerator's key is pressed while exactly its
+//! modifiers are held down, the `Ui` delivers an `event::Widget::Accelerator` to the registered
+//! widget during the next update, regardless of which widget is capturing the keyboard.
+
+use super::keyboard::{ALT, CTRL, GUI, ModifierKey, SHIFT};
+use super::Key;
+use std;
+use std::collections::HashMap;
+use widget;
+
+
+/// A chord of modifiers and a single non-modifier key that activates some widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    /// The modifiers that must be held down.
+    pub modifiers: ModifierKey,
+    /// The key that must be pressed while the `modifiers` are held down.
+    pub key: Key,
+}
+
+/// Maps each registered `Accelerator` to the widget that it activates.
+///
+/// Registrations only last for a single update, so widgets must register their accelerators each
+/// time they are instantiated.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Registry {
+    widgets: HashMap<Accelerator, widget::Id>,
+    descriptions: HashMap<Accelerator, String>,
+    conflicts: Vec<Conflict>,
+}
+
+/// The error returned when an `Accelerator` is registered by a widget while some other widget has
+/// already registered it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    /// The accelerator registered by both widgets.
+    pub accelerator: Accelerator,
+    /// The widget that registered the accelerator first and remains its target.
+    pub registered: widget::Id,
+    /// The widget whose registration was rejected.
+    pub rejected: widget::Id,
+}
+
+
+impl Accelerator {
+
+    /// An accelerator for the given key pressed while exactly the given modifiers are held down.
+    pub fn new(modifiers: ModifierKey, key: Key) -> Self {
+        Accelerator {
+            modifiers: modifiers,
+            key: key,
+        }
+    }
+
+    /// A mnemonic, activated by pressing the given key while holding down `Alt`.
+    pub fn alt(key: Key) -> Self {
+        Accelerator::new(ALT, key)
+    }
+
+}
+
+impl Registry {
+
+    /// An empty registry.
+    pub fn new() -> Self {
+        Registry {
+            widgets: HashMap::new(),
+            descriptions: HashMap::new(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Register the given accelerator as activating the widget with the given `id`.
+    ///
+    /// Registering the same accelerator more than once for the same widget has no effect.
+    ///
+    /// Returns a `Conflict` if some other widget has already registered the accelerator, in which
+    /// case the accelerator continues to activate that widget. The `Conflict` is also retained so
+    /// that it may be reported later via `Registry::conflicts`.
+    pub fn register(&mut self, accelerator: Accelerator, id: widget::Id) -> Result<(), Conflict> {
+        match self.widgets.get(&accelerator) {
+            Some(&registered) if registered != id => {
+                let conflict = Conflict {
+                    accelerator: accelerator,
+                    registered: registered,
+                    rejected: id,
+                };
+                self.conflicts.push(conflict);
+                return Err(conflict);
+            },
+            Some(_) => return Ok(()),
+            None => (),
+        }
+        self.widgets.insert(accelerator, id);
+        Ok(())
+    }
+
+    /// Register the given accelerator as activating the widget with the given `id`, along with a
+    /// description of what the accelerator does, e.g. for display within a list of shortcuts.
+    ///
+    /// The description is only retained if the registration succeeds. See `Registry::register`.
+    pub fn register_with_description(&mut self,
+                                     accelerator: Accelerator,
+                                     id: widget::Id,
+                                     description: String) -> Result<(), Conflict>
+    {
+        try!(self.register(accelerator, id));
+        self.descriptions.insert(accelerator, description);
+        Ok(())
+    }
+
+    /// The widget activated by the given accelerator, if any.
+    pub fn get(&self, accelerator: &Accelerator) -> Option<widget::Id> {
+        self.widgets.get(accelerator).cloned()
+    }
+
+    /// The description given when the accelerator was registered, if any.
+    pub fn description(&self, accelerator: &Accelerator) -> Option<&str> {
+        self.descriptions.get(accelerator).map(|description| &description[..])
+    }
+
+    /// An iterator yielding each registered accelerator along with the widget that it activates,
+    /// in no particular order.
+    pub fn iter(&self) -> std::collections::hash_map::Iter<Accelerator, widget::Id> {
+        self.widgets.iter()
+    }
+
+    /// All conflicting registrations that have occurred since the registry was last cleared.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// Remove all registered accelerators and conflicts.
+    pub fn clear(&mut self) {
+        self.widgets.clear();
+        self.descriptions.clear();
+        self.conflicts.clear();
+    }
+
+}
+
+impl std::fmt::Display for Accelerator {
+    /// Formats the chord as it is commonly displayed within menus, e.g. `Ctrl+Shift+S`.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let modifiers = [(CTRL, "Ctrl"), (SHIFT, "Shift"), (ALT, "Alt"), (GUI, "Gui")];
+        for &(modifier, name) in modifiers.iter() {
+            if self.modifiers.contains(modifier) {
+                try!(write!(f, "{}+", name));
+            }
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "The accelerator {} registered by {:?} is already registered by {:?}.",
+               self.accelerator, self.rejected, self.registered)
+    }
+}
+
+
+#[test]
+fn registering_the_same_accelerator_twice_should_only_conflict_between_widgets() {
+    let (a, b) = (widget::Id::new(0), widget::Id::new(1));
+    let accelerator = Accelerator::alt(Key::F);
+    let mut registry = Registry::new();
+    assert_eq!(Ok(()), registry.register(accelerator, a));
+    assert_eq!(Ok(()), registry.register(accelerator, a));
+    assert!(registry.conflicts().is_empty());
+
+    let conflict = Conflict { accelerator: accelerator, registered: a, rejected: b };
+    assert_eq!(Err(conflict), registry.register(accelerator, b));
+    assert_eq!(Some(a), registry.get(&accelerator));
+    assert_eq!(&[conflict][..], registry.conflicts());
+    assert!(conflict.to_string().starts_with("The accelerator Alt+F registered by"));
+
+    registry.clear();
+    assert_eq!(None, registry.get(&accelerator));
+    assert!(registry.conflicts().is_empty());
+}
+
+#[test]
+fn accelerators_should_display_as_modifiers_followed_by_the_key() {
+    use super::keyboard::NO_MODIFIER;
+    assert_eq!("Alt+F", Accelerator::alt(Key::F).to_string());
+    assert_eq!("Ctrl+Shift+S", Accelerator::new(SHIFT | CTRL, Key::S).to_string());
+    assert_eq!("F5", Accelerator::new(NO_MODIFIER, Key::F5).to_string());
+}
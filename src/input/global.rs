@@ -5,6 +5,8 @@
 use event;
 use input;
 use std;
+use std::any::Any;
+use widget;
 
 /// Global input event handler that also implements `input::Provider`. The `Ui` passes all events
 /// to it's `Global` instance, which aggregates and interprets the events to provide so-called
@@ -17,9 +19,62 @@ pub struct Global {
     pub current: input::State,
     /// The events that have occurred between two consecutive updates.
     events: Vec<event::Event>,
+    /// Whether or not the moment at which each event arrives is recorded in order to measure
+    /// input latency (the default is `false`).
+    ///
+    /// See `Global::latencies` for the results.
+    pub timestamp_events: bool,
+    /// The arrival of each event in `events` and the moment at which it was handled (if recorded).
+    timestamps: Vec<Option<(std::time::Instant, Option<std::time::Instant>)>>,
+    /// The latencies of the events that occurred before the last update.
+    latencies: Vec<EventLatency>,
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(std::time::Instant, event::Click)>,
+    /// The payload of the drag-and-drop operation that is currently in progress (if any).
+    drag_payload: Option<DragPayload>,
+}
+
+/// The time taken for some event to be handled after arriving at the `Global`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventLatency {
+    /// The event that was measured.
+    pub event: event::Event,
+    /// Whether or not the widget to which the event was routed was updated.
+    ///
+    /// If `false`, the `latency` is the age of the event at the end of the update.
+    pub was_handled: bool,
+    /// The duration between the arrival of the event and the moment at which it was handled.
+    pub latency: std::time::Duration,
+}
+
+/// The data carried by a drag-and-drop operation.
+///
+/// A source widget begins a drag via `Global::begin_drag`. While the drag is in progress, target
+/// widgets may check whether a compatible payload is hovering over them with
+/// `Global::is_drag_payload_over`. Once the left mouse button is released, the widget under the
+/// mouse may claim the payload via `Global::take_dropped_payload`.
+///
+/// If no target claims the payload before the end of the update in which it was dropped, it is
+/// returned to the source, which may reclaim it via `Global::take_returned_payload` during the
+/// following update.
+pub struct DragPayload {
+    /// The widget from which the drag originated.
+    pub source: widget::Id,
+    /// The dragged data.
+    pub payload: Box<Any + Send>,
+    stage: DragStage,
+}
+
+/// The stages through which a `DragPayload` passes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum DragStage {
+    /// The left mouse button is still down and the payload is being dragged.
+    Dragging,
+    /// The payload was dropped over the given widget (if any).
+    Dropped(Option<widget::Id>),
+    /// No target claimed the payload and it is waiting to be reclaimed by its source.
+    Returned,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -42,9 +97,13 @@ impl Global {
     pub fn new() -> Global {
         Global{
             events: Vec::new(),
+            timestamp_events: false,
+            timestamps: Vec::new(),
+            latencies: Vec::new(),
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            drag_payload: None,
         }
     }
 
@@ -57,17 +116,171 @@ impl Global {
     /// Add the new event to the stack.
     pub fn push_event(&mut self, event: event::Event) {
         self.events.push(event);
+        let timestamp = if self.timestamp_events {
+            Some((std::time::Instant::now(), None))
+        } else {
+            None
+        };
+        self.timestamps.push(timestamp);
+    }
+
+    /// Mark all timestamped events routed to the given widget that have not yet been handled as
+    /// handled now.
+    ///
+    /// This is called by the `Ui` after each widget is updated.
+    pub fn handle_events_for(&mut self, widget: widget::Id) {
+        let now = std::time::Instant::now();
+        for (event, timestamp) in self.events.iter().zip(self.timestamps.iter_mut()) {
+            if let Some((_, ref mut handled)) = *timestamp {
+                if handled.is_none() && routed_widget(event) == Some(widget) {
+                    *handled = Some(now);
+                }
+            }
+        }
+    }
+
+    /// The latency of each timestamped event that occurred before the last update, in the order
+    /// in which the events occurred.
+    ///
+    /// Only events that arrived while `timestamp_events` was enabled are included.
+    pub fn latencies(&self) -> &[EventLatency] {
+        &self.latencies
+    }
+
+    /// Begin a drag-and-drop operation from the `source` widget carrying the given `payload`.
+    ///
+    /// Any payload from a previous operation that has not yet been claimed is discarded.
+    pub fn begin_drag(&mut self, source: widget::Id, payload: Box<Any + Send>) {
+        self.drag_payload = Some(DragPayload {
+            source: source,
+            payload: payload,
+            stage: DragStage::Dragging,
+        });
+    }
+
+    /// The payload that is currently being dragged.
+    ///
+    /// Returns `None` if there is no drag in progress or if the payload has already been dropped.
+    pub fn drag_payload(&self) -> Option<&DragPayload> {
+        self.drag_payload.as_ref().and_then(|drag| match drag.stage {
+            DragStage::Dragging => Some(drag),
+            _ => None,
+        })
+    }
+
+    /// Whether or not a payload of type `T` is currently being dragged over the `target` widget.
+    ///
+    /// Always returns `false` for the widget from which the drag originated.
+    pub fn is_drag_payload_over<T: Any>(&self, target: widget::Id) -> bool {
+        self.current.widget_under_mouse == Some(target) && match self.drag_payload() {
+            Some(drag) => drag.source != target && drag.payload.is::<T>(),
+            None => false,
+        }
+    }
+
+    /// Drop the payload that is currently being dragged (if any) over the given `target`.
+    ///
+    /// This is called by the `Ui` when the left mouse button is released.
+    pub fn drop_drag_payload(&mut self, target: Option<widget::Id>) {
+        if let Some(ref mut drag) = self.drag_payload {
+            if let DragStage::Dragging = drag.stage {
+                drag.stage = DragStage::Dropped(target);
+            }
+        }
+    }
+
+    /// Claim the payload that was dropped over the `target` widget during this update.
+    ///
+    /// Returns `None` if no payload was dropped over the `target` or if the payload is not of type
+    /// `T`. In the latter case, the payload remains in place and will be returned to its source.
+    pub fn take_dropped_payload<T: Any>(&mut self, target: widget::Id) -> Option<Box<T>> {
+        let is_compatible = match self.drag_payload {
+            Some(ref drag) => drag.stage == DragStage::Dropped(Some(target))
+                && drag.source != target
+                && drag.payload.is::<T>(),
+            None => false,
+        };
+        if !is_compatible {
+            return None;
+        }
+        self.drag_payload.take().and_then(|drag| drag.payload.downcast().ok())
+    }
+
+    /// Reclaim a payload that was dropped without being claimed by any valid target.
+    ///
+    /// Returns `None` if no payload originating from the `source` widget is awaiting return.
+    pub fn take_returned_payload(&mut self, source: widget::Id) -> Option<Box<Any + Send>> {
+        let is_returned = match self.drag_payload {
+            Some(ref drag) => drag.stage == DragStage::Returned && drag.source == source,
+            None => false,
+        };
+        if !is_returned {
+            return None;
+        }
+        self.drag_payload.take().map(|drag| drag.payload)
     }
 
     /// Called at the end of every update cycle in order to prepare the `Global` to
     /// handle events for the next one.
     pub fn clear_events_and_update_start_state(&mut self) {
-        self.events.clear();
+        // Events that were never handled report their age at the end of the update.
+        let now = std::time::Instant::now();
+        self.latencies.clear();
+        for (event, timestamp) in self.events.drain(..).zip(self.timestamps.drain(..)) {
+            if let Some((arrival, maybe_handled)) = timestamp {
+                self.latencies.push(EventLatency {
+                    event: event,
+                    was_handled: maybe_handled.is_some(),
+                    latency: maybe_handled.unwrap_or(now).duration_since(arrival),
+                });
+            }
+        }
         self.start = self.current.clone();
+
+        // A dropped payload that was not claimed by a target is returned to its source. A returned
+        // payload that was not reclaimed by its source during the following update is discarded.
+        self.drag_payload = match self.drag_payload.take() {
+            Some(mut drag) => match drag.stage {
+                DragStage::Dragging => Some(drag),
+                DragStage::Dropped(_) => {
+                    drag.stage = DragStage::Returned;
+                    Some(drag)
+                },
+                DragStage::Returned => None,
+            },
+            None => None,
+        };
     }
 
 }
 
+/// The widget to which the given event was routed, if any.
+fn routed_widget(event: &event::Event) -> Option<widget::Id> {
+    match *event {
+        event::Event::Ui(ref ui_event) => match *ui_event {
+            event::Ui::Text(idx, _) |
+            event::Ui::Composition(idx, _) |
+            event::Ui::Press(idx, _) |
+            event::Ui::Release(idx, _) |
+            event::Ui::Move(idx, _) |
+            event::Ui::Click(idx, _) |
+            event::Ui::DoubleClick(idx, _) |
+            event::Ui::ContextMenu(idx, _) |
+            event::Ui::Drag(idx, _) |
+            event::Ui::Scroll(idx, _) => idx,
+            event::Ui::WidgetCapturesMouse(idx) |
+            event::Ui::WidgetUncapturesMouse(idx) |
+            event::Ui::WidgetCapturesKeyboard(idx) |
+            event::Ui::WidgetUncapturesKeyboard(idx) |
+            event::Ui::WidgetMounted(idx) |
+            event::Ui::WidgetUnmounted(idx) |
+            event::Ui::Accelerator(idx, _) => Some(idx),
+            event::Ui::WindowResized(_) => None,
+        },
+        event::Event::Raw(_) => None,
+    }
+}
+
 impl<'a> Events<'a> {
     /// Converts the `Events` into a `UiEvents`, yielding only the `event::Ui`s that have occurred
     /// since the last time `Ui::set_widgets` was called.
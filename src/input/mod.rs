@@ -15,13 +15,18 @@
 //! middle of the widget's bounding `Rect`. `GlobalInput`, on the other hand, will never filter out
 //! any events, and will always provide them with coordinates relative to the window.
 
+pub mod accelerator;
 pub mod state;
 pub mod widget;
 pub mod global;
+pub mod record;
+pub mod sticky;
 
+pub use self::accelerator::Accelerator;
 pub use self::state::State;
-pub use self::global::Global;
+pub use self::global::{DragPayload, EventLatency, Global};
 pub use self::widget::Widget;
+pub use self::sticky::StickyModifiers;
 
 #[doc(inline)]
 pub use piston_input::keyboard::ModifierKey;
@@ -0,0 +1,418 @@
+//! Recording a session of `Input` events to a file and loading it for replay, allowing a user's
+//! session to be reproduced exactly.
+//!
+//! Recordings are stored in a simple, versioned, line-based text format. The first line is a
+//! header containing the format `VERSION`. Each following line describes a single `Input`,
+//! prefixed by the number of microseconds between the start of the recording and its arrival:
+//!
+//! ```txt
+//! conrod-input-recording 1
+//! 0 move cursor -120.5 40
+//! 8000 press mouse 1
+//! 90000 release mouse 1
+//! 150000 text hello\nworld
+//! ```
+
+use event::{Input, Motion};
+use std;
+use std::io::{BufRead, Write};
+use std::time::{Duration, Instant};
+use super::{Button, ControllerAxisArgs, ControllerButton, Key, MouseButton};
+
+
+/// The version of the format written by `Recording::write_to`.
+///
+/// Recordings of any version up to and including this one may be read.
+pub const VERSION: u32 = 1;
+
+/// The first word of the header line of every recording.
+pub const MAGIC: &'static str = "conrod-input-recording";
+
+/// Records each `Input` given to it along with the moment at which it arrived.
+///
+/// The `Recorder` should be given every `Input` that is given to `Ui::handle_event`.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    maybe_start: Option<Instant>,
+    recording: Recording,
+}
+
+/// A session of `Input` events in the order in which they arrived.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    /// Each recorded `Input` along with the time since the start of the recording.
+    pub events: Vec<(Duration, Input)>,
+}
+
+/// The errors that may occur while writing or reading a `Recording`.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occurred while reading from or writing to the file.
+    Io(std::io::Error),
+    /// The recording was written in a newer version of the format than this version of conrod
+    /// supports.
+    UnsupportedVersion(u32),
+    /// The recording's header or the event on the given line could not be parsed.
+    Malformed(usize),
+}
+
+
+impl Recorder {
+
+    /// A `Recorder` whose recording starts upon receiving its first `Input`.
+    pub fn new() -> Self {
+        Recorder {
+            maybe_start: None,
+            recording: Recording { events: Vec::new() },
+        }
+    }
+
+    /// Record the given `Input`.
+    ///
+    /// `Input`s that do not affect the `Ui` (such as `Render` or `Update` events) and touch events
+    /// are not recorded. Returns whether or not the `Input` was recorded.
+    pub fn record(&mut self, input: &Input) -> bool {
+        if encode(input).is_none() {
+            return false;
+        }
+        let now = Instant::now();
+        let start = match self.maybe_start {
+            Some(start) => start,
+            None => {
+                self.maybe_start = Some(now);
+                now
+            },
+        };
+        self.recording.events.push((now.duration_since(start), input.clone()));
+        true
+    }
+
+    /// The events recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Finish recording, producing the `Recording`.
+    pub fn into_recording(self) -> Recording {
+        self.recording
+    }
+
+    /// Write the events recorded so far to the file at the given path, replacing it if it exists.
+    pub fn record_to<P>(&self, path: P) -> Result<(), Error>
+        where P: AsRef<std::path::Path>,
+    {
+        let file = try!(std::fs::File::create(path));
+        self.recording.write_to(std::io::BufWriter::new(file))
+    }
+
+}
+
+impl Recording {
+
+    /// Write the recording in the current `VERSION` of the format.
+    pub fn write_to<W>(&self, mut writer: W) -> Result<(), Error>
+        where W: Write,
+    {
+        try!(writeln!(writer, "{} {}", MAGIC, VERSION));
+        for &(time, ref input) in &self.events {
+            if let Some(line) = encode(input) {
+                let micros = time.as_secs() * 1_000_000 + time.subsec_nanos() as u64 / 1_000;
+                try!(writeln!(writer, "{} {}", micros, line));
+            }
+        }
+        try!(writer.flush());
+        Ok(())
+    }
+
+    /// Read a recording written by `Recording::write_to`.
+    pub fn read_from<R>(reader: R) -> Result<Self, Error>
+        where R: BufRead,
+    {
+        let mut lines = reader.lines();
+
+        // Check the version before attempting to parse any events.
+        let header = match lines.next() {
+            Some(line) => try!(line),
+            None => return Err(Error::Malformed(1)),
+        };
+        let mut words = header.split(' ');
+        if words.next() != Some(MAGIC) {
+            return Err(Error::Malformed(1));
+        }
+        let version = match words.next().and_then(|v| v.parse::<u32>().ok()) {
+            Some(version) => version,
+            None => return Err(Error::Malformed(1)),
+        };
+        if version > VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let mut events = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line = try!(line);
+            if line.is_empty() {
+                continue;
+            }
+            match decode_line(&line) {
+                Some(event) => events.push(event),
+                None => return Err(Error::Malformed(i + 2)),
+            }
+        }
+        Ok(Recording { events: events })
+    }
+
+}
+
+/// Load the recording from the file at the given path for replay.
+///
+/// Replay the session by giving each of the recording's `events` to `Ui::handle_event` in order,
+/// calling `Ui::set_widgets` at the recorded times if timing is relevant to the bug being
+/// reproduced.
+pub fn replay_from<P>(path: P) -> Result<Recording, Error>
+    where P: AsRef<std::path::Path>,
+{
+    let file = try!(std::fs::File::open(path));
+    Recording::read_from(std::io::BufReader::new(file))
+}
+
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(std::error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::UnsupportedVersion(_) | Error::Malformed(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            Error::Io(ref err) => std::fmt::Display::fmt(err, f),
+            Error::UnsupportedVersion(version) =>
+                write!(f, "The input recording was written by a newer version of conrod. Found \
+                           version {}, but only versions up to {} are supported.",
+                       version, VERSION),
+            Error::Malformed(line) =>
+                write!(f, "The input recording could not be parsed. Found an error on line {}.",
+                       line),
+        }
+    }
+}
+
+
+/// Describe the given `Input` as a single line, if it is of a kind that may be recorded.
+fn encode(input: &Input) -> Option<String> {
+    fn encode_button(button: &Button) -> String {
+        match *button {
+            Button::Keyboard(key) => format!("key {}", key as u32),
+            Button::Mouse(button) => format!("mouse {}", button as u32),
+            Button::Controller(button) => format!("controller {} {}", button.id, button.button),
+        }
+    }
+
+    let line = match *input {
+        Input::Press(ref button) => format!("press {}", encode_button(button)),
+        Input::Release(ref button) => format!("release {}", encode_button(button)),
+        Input::Move(Motion::MouseCursor(x, y)) => format!("move cursor {} {}", x, y),
+        Input::Move(Motion::MouseRelative(x, y)) => format!("move relative {} {}", x, y),
+        Input::Move(Motion::MouseScroll(x, y)) => format!("move scroll {} {}", x, y),
+        Input::Move(Motion::ControllerAxis(args)) =>
+            format!("move axis {} {} {}", args.id, args.axis, args.position),
+        Input::Text(ref text) => format!("text {}", escape(text)),
+        Input::Resize(w, h) => format!("resize {} {}", w, h),
+        Input::Focus(focused) => format!("focus {}", focused),
+        Input::Cursor(over) => format!("cursor {}", over),
+        _ => return None,
+    };
+    Some(line)
+}
+
+/// Parse a line written by `Recording::write_to` that follows the header.
+fn decode_line(line: &str) -> Option<(Duration, Input)> {
+    fn parse<T: std::str::FromStr>(word: Option<&str>) -> Option<T> {
+        word.and_then(|word| word.parse().ok())
+    }
+
+    fn decode_button<'a, I>(words: &mut I) -> Option<Button>
+        where I: Iterator<Item=&'a str>,
+    {
+        match words.next() {
+            Some("key") => parse::<u32>(words.next()).map(|code| Button::Keyboard(Key::from(code))),
+            Some("mouse") =>
+                parse::<u32>(words.next()).map(|code| Button::Mouse(MouseButton::from(code))),
+            Some("controller") => {
+                let id = parse(words.next());
+                let button = parse(words.next());
+                match (id, button) {
+                    (Some(id), Some(button)) =>
+                        Some(Button::Controller(ControllerButton { id: id, button: button })),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+
+    let (micros, rest) = match line.find(' ') {
+        Some(i) => (&line[..i], &line[i+1..]),
+        None => return None,
+    };
+    let micros = match micros.parse::<u64>() {
+        Ok(micros) => micros,
+        Err(_) => return None,
+    };
+    let time = Duration::new(micros / 1_000_000, (micros % 1_000_000) as u32 * 1_000);
+
+    // The text is the remainder of the line, so it must be split off before the other words.
+    if rest.starts_with("text ") {
+        return unescape(&rest["text ".len()..]).map(|text| (time, Input::Text(text)));
+    }
+
+    let mut words = rest.split(' ');
+    let input = match words.next() {
+        Some("press") => decode_button(&mut words).map(Input::Press),
+        Some("release") => decode_button(&mut words).map(Input::Release),
+        Some("move") => {
+            let kind = words.next();
+            let a = words.next();
+            let b = words.next();
+            match (kind, parse(a), parse(b)) {
+                (Some("cursor"), Some(x), Some(y)) => Some(Motion::MouseCursor(x, y)),
+                (Some("relative"), Some(x), Some(y)) => Some(Motion::MouseRelative(x, y)),
+                (Some("scroll"), Some(x), Some(y)) => Some(Motion::MouseScroll(x, y)),
+                (Some("axis"), _, _) => match (parse(a), parse(b), parse(words.next())) {
+                    (Some(id), Some(axis), Some(position)) => {
+                        let args = ControllerAxisArgs { id: id, axis: axis, position: position };
+                        Some(Motion::ControllerAxis(args))
+                    },
+                    _ => None,
+                },
+                _ => None,
+            }.map(Input::Move)
+        },
+        Some("resize") => match (parse(words.next()), parse(words.next())) {
+            (Some(w), Some(h)) => Some(Input::Resize(w, h)),
+            _ => None,
+        },
+        Some("focus") => parse(words.next()).map(Input::Focus),
+        Some("cursor") => parse(words.next()).map(Input::Cursor),
+        _ => None,
+    };
+
+    // Trailing words indicate a malformed line.
+    match words.next() {
+        None => input.map(|input| (time, input)),
+        Some(_) => None,
+    }
+}
+
+/// Escape backslashes and line breaks so that the text fits on a single line.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// The inverse of `escape`, returning `None` upon an unknown escape sequence.
+fn unescape(escaped: &str) -> Option<String> {
+    let mut text = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => text.push('\\'),
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(text)
+}
+
+
+#[test]
+fn text_should_survive_escaping() {
+    let text = "line one\\\nline two\r";
+    assert_eq!(Some(text.to_string()), unescape(&escape(text)));
+    assert_eq!(None, unescape("trailing\\"));
+}
+
+#[test]
+fn a_recorded_session_should_round_trip_through_a_file() {
+    let mut recorder = Recorder::new();
+    let inputs = vec![
+        Input::Move(Motion::MouseCursor(-120.5, 40.0)),
+        Input::Press(Button::Mouse(MouseButton::Left)),
+        Input::Release(Button::Mouse(MouseButton::Left)),
+        Input::Move(Motion::MouseScroll(0.0, -1.0 / 3.0)),
+        Input::Press(Button::Keyboard(Key::Return)),
+        Input::Text("hello\nworld".to_string()),
+        Input::Resize(640, 480),
+        Input::Focus(false),
+    ];
+    for input in &inputs {
+        assert!(recorder.record(input));
+    }
+
+    // A unique path, so that concurrent test runs do not write to the same file.
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    let file_name = format!("conrod_input_recording_round_trip_{}_{}.txt",
+                            std::process::id(), nanos);
+    let path = std::env::temp_dir().join(file_name);
+    recorder.record_to(&path).unwrap();
+    let replayed = replay_from(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let replayed_inputs: Vec<Input> = replayed.events.iter().map(|e| e.1.clone()).collect();
+    assert_eq!(inputs, replayed_inputs);
+    // Times are stored to the microsecond and remain in order.
+    let times: Vec<Duration> = replayed.events.iter().map(|e| e.0).collect();
+    assert_eq!(Duration::from_secs(0), times[0]);
+    assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+    for (&(recorded, _), &replayed) in recorder.recording().events.iter().zip(&times) {
+        assert!(recorded - replayed < Duration::new(0, 1_000));
+    }
+}
+
+#[test]
+fn reading_a_newer_version_should_fail_clearly() {
+    let newer = format!("{} {}\n0 focus true\n", MAGIC, VERSION + 1);
+    match Recording::read_from(newer.as_bytes()) {
+        Err(err @ Error::UnsupportedVersion(_)) => {
+            let expected = format!("The input recording was written by a newer version of \
+                                    conrod. Found version {}, but only versions up to {} are \
+                                    supported.", VERSION + 1, VERSION);
+            assert_eq!(expected, err.to_string());
+        },
+        other => panic!("expected an unsupported version error, found {:?}", other),
+    }
+
+    let current = format!("{} {}\n0 focus true\n12 move bogus 1 2\n", MAGIC, VERSION);
+    match Recording::read_from(current.as_bytes()) {
+        Err(err @ Error::Malformed(_)) => {
+            let expected = "The input recording could not be parsed. Found an error on line 3.";
+            assert_eq!(expected, err.to_string());
+        },
+        other => panic!("expected a malformed line error, found {:?}", other),
+    }
+}
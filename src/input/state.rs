@@ -7,7 +7,7 @@
 //!
 //! This module exists mostly to support the `input::Provider` trait.
 
-use position::Point;
+use position::{Point, Scalar};
 use self::mouse::Mouse;
 use super::keyboard::{NO_MODIFIER, ModifierKey};
 use utils;
@@ -35,6 +35,14 @@ pub struct State {
     pub widget_under_mouse: Option<widget::Id>,
     /// Which modifier keys are being held down.
     pub modifiers: ModifierKey,
+    /// The factor by which scrolling is scaled along each axis.
+    ///
+    /// See `State::set_scroll_config`.
+    pub scroll_scale: Point,
+    /// Whether or not the direction of scrolling is inverted along both axes.
+    ///
+    /// See `State::set_scroll_config`.
+    pub invert_scroll: bool,
 }
 
 impl State {
@@ -47,9 +55,27 @@ impl State {
             widget_capturing_mouse: None,
             widget_under_mouse: None,
             modifiers: NO_MODIFIER,
+            scroll_scale: [1.0, 1.0],
+            invert_scroll: false,
         }
     }
 
+    /// Configure how raw scroll input is interpreted.
+    ///
+    /// Each axis of every scroll delta is multiplied by the respective axis of `scale`. If `invert`
+    /// is `true`, the direction of scrolling is reversed along both axes (sometimes referred to as
+    /// "natural" scrolling).
+    pub fn set_scroll_config(&mut self, scale: Point, invert: bool) {
+        self.scroll_scale = scale;
+        self.invert_scroll = invert;
+    }
+
+    /// Apply the scroll configuration to the given raw scroll delta.
+    pub fn scroll_delta(&self, delta: [Scalar; 2]) -> [Scalar; 2] {
+        let direction = if self.invert_scroll { -1.0 } else { 1.0 };
+        [delta[0] * self.scroll_scale[0] * direction, delta[1] * self.scroll_scale[1] * direction]
+    }
+
     /// Returns a copy of the input::State relative to the given `position::Point`
     pub fn relative_to(mut self, xy: Point) -> State {
         self.mouse.xy = utils::vec2_sub(self.mouse.xy, xy);
@@ -284,3 +310,16 @@ fn input_state_should_be_made_relative_to_a_given_point() {
     assert_eq!([30.0, -30.0], relative_state.mouse.xy);
     assert_eq!(Some([-40.0, -30.0]), relative_state.mouse.buttons[mouse::Button::Middle].xy_if_down());
 }
+
+#[test]
+fn inverted_and_scaled_scroll_should_accumulate_the_expected_delta() {
+    let mut state = State::new();
+    assert_eq!([3.0, -4.0], state.scroll_delta([3.0, -4.0]));
+
+    state.set_scroll_config([2.0, 0.5], true);
+    let deltas = [[3.0, -4.0], [1.0, 10.0]];
+    let total = deltas.iter().fold([0.0, 0.0], |total, &delta| {
+        utils::vec2_add(total, state.scroll_delta(delta))
+    });
+    assert_eq!([-8.0, -3.0], total);
+}
@@ -0,0 +1,154 @@
+//! An input-remapping layer providing "sticky" modifier keys for accessibility.
+//!
+//! With sticky modifiers, modifier keys need not be held down while pressing the key that they
+//! modify. Instead, pressing a modifier *latches* it until the next non-modifier key press.
+//! Pressing the same modifier twice in a row *locks* it until it is pressed a third time.
+
+use super::keyboard::{ALT, CTRL, GUI, NO_MODIFIER, SHIFT, ModifierKey};
+use super::{Key, State};
+
+
+/// A wrapper around an `input::State` that intercepts modifier key presses and applies them as
+/// sticky modifiers.
+///
+/// The `modifiers` of the wrapped `State` always reflect the currently latched and locked
+/// modifiers.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StickyModifiers {
+    state: State,
+    /// Modifiers that apply to the next non-modifier key press only.
+    latched: ModifierKey,
+    /// Modifiers that apply to all key presses until pressed again.
+    locked: ModifierKey,
+}
+
+
+impl StickyModifiers {
+
+    /// Wrap the given `input::State`.
+    ///
+    /// Any modifiers already set within the `state` are discarded.
+    pub fn new(mut state: State) -> Self {
+        state.modifiers = NO_MODIFIER;
+        StickyModifiers {
+            state: state,
+            latched: NO_MODIFIER,
+            locked: NO_MODIFIER,
+        }
+    }
+
+    /// The wrapped `input::State`.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Unwrap the `input::State`.
+    pub fn into_state(self) -> State {
+        self.state
+    }
+
+    /// The modifiers that are currently latched for the next key press.
+    pub fn latched(&self) -> ModifierKey {
+        self.latched
+    }
+
+    /// The modifiers that are currently locked.
+    pub fn locked(&self) -> ModifierKey {
+        self.locked
+    }
+
+    /// All modifiers that currently apply, whether latched or locked.
+    pub fn modifiers(&self) -> ModifierKey {
+        self.latched | self.locked
+    }
+
+    /// Handle the press of the given `key`, returning the modifiers that apply to the press.
+    ///
+    /// - Pressing an inactive modifier latches it.
+    /// - Pressing a latched modifier locks it.
+    /// - Pressing a locked modifier releases it.
+    /// - Pressing any other key applies all active modifiers and then releases those that were
+    ///   only latched.
+    pub fn press(&mut self, key: Key) -> ModifierKey {
+        match modifier_from_key(key) {
+            Some(modifier) => {
+                if self.locked.contains(modifier) {
+                    self.locked.remove(modifier);
+                } else if self.latched.contains(modifier) {
+                    self.latched.remove(modifier);
+                    self.locked.insert(modifier);
+                } else {
+                    self.latched.insert(modifier);
+                }
+                self.state.modifiers = self.modifiers();
+                self.state.modifiers
+            },
+            None => {
+                let modifiers = self.modifiers();
+                self.latched = NO_MODIFIER;
+                self.state.modifiers = self.modifiers();
+                modifiers
+            },
+        }
+    }
+
+    /// Handle the release of the given `key`, returning the modifiers that apply to the release.
+    ///
+    /// Releasing a key never changes the sticky state, as modifiers remain active until the next
+    /// key press.
+    pub fn release(&mut self, _key: Key) -> ModifierKey {
+        self.state.modifiers
+    }
+
+}
+
+/// Maps the given `Key` to its respective `ModifierKey` (if it is a modifier).
+fn modifier_from_key(key: Key) -> Option<ModifierKey> {
+    match key {
+        Key::LCtrl | Key::RCtrl => Some(CTRL),
+        Key::LShift | Key::RShift => Some(SHIFT),
+        Key::LAlt | Key::RAlt => Some(ALT),
+        Key::LGui | Key::RGui => Some(GUI),
+        _ => None,
+    }
+}
+
+
+#[test]
+fn latched_modifier_should_apply_to_exactly_one_key_press() {
+    let mut sticky = StickyModifiers::new(State::new());
+
+    assert_eq!(CTRL, sticky.press(Key::LCtrl));
+    assert_eq!(CTRL, sticky.release(Key::LCtrl));
+    assert_eq!(CTRL, sticky.latched());
+
+    assert_eq!(CTRL, sticky.press(Key::C));
+    assert_eq!(NO_MODIFIER, sticky.state().modifiers);
+    assert_eq!(NO_MODIFIER, sticky.press(Key::V));
+}
+
+#[test]
+fn multiple_latched_modifiers_should_combine() {
+    let mut sticky = StickyModifiers::new(State::new());
+    sticky.press(Key::LCtrl);
+    sticky.press(Key::RShift);
+    assert_eq!(CTRL | SHIFT, sticky.press(Key::Z));
+    assert_eq!(NO_MODIFIER, sticky.modifiers());
+}
+
+#[test]
+fn double_pressed_modifier_should_lock_until_pressed_a_third_time() {
+    let mut sticky = StickyModifiers::new(State::new());
+
+    sticky.press(Key::LShift);
+    sticky.press(Key::LShift);
+    assert_eq!(SHIFT, sticky.locked());
+    assert_eq!(NO_MODIFIER, sticky.latched());
+
+    assert_eq!(SHIFT, sticky.press(Key::A));
+    assert_eq!(SHIFT, sticky.press(Key::B));
+    assert_eq!(SHIFT, sticky.state().modifiers);
+
+    assert_eq!(NO_MODIFIER, sticky.press(Key::LShift));
+    assert_eq!(NO_MODIFIER, sticky.press(Key::C));
+}
@@ -22,6 +22,7 @@ pub struct Widget<'a> {
     global: &'a input::Global,
     rect: Rect,
     idx: widget::Id,
+    is_observer: bool,
 }
 
 /// A view of the `input::state::Mouse` that is specific to a single widget.
@@ -47,6 +48,7 @@ pub struct Events<'a> {
     capturing_mouse: Option<widget::Id>,
     rect: Rect,
     idx: widget::Id,
+    is_observer: bool,
 }
 
 /// An `Iterator` yielding all button presses occuring within the given sequence of
@@ -120,6 +122,12 @@ pub struct ButtonClicks<'a> {
     button: input::MouseButton,
 }
 
+/// An iterator that yields all `event::ContextMenu` events yielded by the `Events` iterator.
+#[derive(Clone)]
+pub struct ContextMenus<'a> {
+    events: Events<'a>,
+}
+
 /// An iterator that yields all `event::Drag` events yielded by the `Events` iterator.
 ///
 /// Only events that occurred while the widget was capturing the device that did the dragging will
@@ -151,6 +159,12 @@ pub struct Scrolls<'a> {
     events: Events<'a>,
 }
 
+/// An iterator that yields each `Accelerator` registered by the widget that was pressed.
+#[derive(Clone)]
+pub struct Accelerators<'a> {
+    events: Events<'a>,
+}
+
 
 impl<'a> Widget<'a> {
 
@@ -164,14 +178,28 @@ impl<'a> Widget<'a> {
             global: global,
             rect: rect,
             idx: idx,
+            is_observer: false,
+        }
+    }
+
+    /// Returns a `Widget` with events for a widget that does not consume events.
+    ///
+    /// Rather than only yielding the events routed to the widget, an observer yields all events
+    /// regardless of the widget to which they were routed, and always has access to the mouse.
+    ///
+    /// All events will also be made relative to the widget's own (0, 0) origin.
+    pub fn for_observer(idx: widget::Id, rect: Rect, global: &'a input::Global) -> Self {
+        Widget {
+            is_observer: true,
+            ..Widget::for_widget(idx, rect, global)
         }
     }
 
     /// If the widget is currently capturing the mouse, this returns the state of the mouse.
     ///
-    /// Returns `None` if the widget is not capturing the mouse.
+    /// Returns `None` if the widget is not capturing the mouse, unless the widget is an observer.
     pub fn mouse(&self) -> Option<Mouse<'a>> {
-        if self.global.current.widget_capturing_mouse == Some(self.idx) {
+        if self.is_observer || self.global.current.widget_capturing_mouse == Some(self.idx) {
             let mouse = Mouse {
                 buttons: &self.global.current.mouse.buttons,
                 mouse_abs_xy: self.global.current.mouse.xy,
@@ -196,6 +224,7 @@ impl<'a> Widget<'a> {
             capturing_mouse: self.global.start.widget_capturing_mouse,
             rect: self.rect,
             idx: self.idx,
+            is_observer: self.is_observer,
         }
     }
 
@@ -217,6 +246,12 @@ impl<'a> Widget<'a> {
         Clicks { events: self.events() }
     }
 
+    /// Produces an iterator that yields each `event::ContextMenu` requested by right-clicking the
+    /// widget, e.g. for opening a `widget::ContextMenu`.
+    pub fn context_menus(&self) -> ContextMenus<'a> {
+        ContextMenus { events: self.events() }
+    }
+
     /// Produces an iterator that yields all `event::Drag` events yielded by the `Events` iterator.
     ///
     /// Only events that occurred while the widget was capturing the device that did the dragging
@@ -238,6 +273,17 @@ impl<'a> Widget<'a> {
         Scrolls { events: self.events() }
     }
 
+    /// Produce an iterator that yields each `Accelerator` registered by the widget that has been
+    /// pressed since the last time `Ui::set_widgets` was called.
+    pub fn accelerators(&self) -> Accelerators<'a> {
+        Accelerators { events: self.events() }
+    }
+
+    /// Whether or not a drag-and-drop payload of type `T` is currently hovering over the widget.
+    pub fn is_drag_payload_over<T: ::std::any::Any>(&self) -> bool {
+        self.global.is_drag_payload_over::<T>(self.idx)
+    }
+
 }
 
 impl<'a> Mouse<'a> {
@@ -407,6 +453,15 @@ impl<'a> Drags<'a> {
 }
 
 
+impl<'a> Events<'a> {
+
+    /// Whether or not an event routed to the given widget should be yielded.
+    fn is_routed(&self, idx: Option<widget::Id>) -> bool {
+        self.is_observer || idx == Some(self.idx)
+    }
+
+}
+
 impl<'a> Iterator for Events<'a> {
     type Item = event::Widget;
 
@@ -451,30 +506,39 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::WindowResized(dim) =>
                     return Some(event::Widget::WindowResized(dim)),
 
-                event::Ui::Text(idx, ref text) if idx == Some(self.idx) =>
+                event::Ui::Text(idx, ref text) if self.is_routed(idx) =>
                     return Some(text.clone().into()),
 
-                event::Ui::Move(idx, ref move_) if idx == Some(self.idx) =>
+                event::Ui::Composition(idx, ref composition) if self.is_routed(idx) =>
+                    return Some(composition.clone().into()),
+
+                event::Ui::Move(idx, ref move_) if self.is_routed(idx) =>
                     return Some(move_.clone().into()),
 
-                event::Ui::Press(idx, ref press) if idx == Some(self.idx) =>
+                event::Ui::Press(idx, ref press) if self.is_routed(idx) =>
                     return Some(press.clone().relative_to(self.rect.xy()).into()),
                 
-                event::Ui::Release(idx, ref release) if idx == Some(self.idx) =>
+                event::Ui::Release(idx, ref release) if self.is_routed(idx) =>
                     return Some(release.clone().relative_to(self.rect.xy()).into()),
 
-                event::Ui::Click(idx, ref click) if idx == Some(self.idx) =>
+                event::Ui::Click(idx, ref click) if self.is_routed(idx) =>
                     return Some(click.clone().relative_to(self.rect.xy()).into()),
 
-                event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) =>
+                event::Ui::DoubleClick(idx, ref double_click) if self.is_routed(idx) =>
                     return Some(double_click.clone().relative_to(self.rect.xy()).into()),
 
-                event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
+                event::Ui::ContextMenu(idx, ref context_menu) if self.is_routed(idx) =>
+                    return Some(context_menu.clone().relative_to(self.rect.xy()).into()),
+
+                event::Ui::Drag(idx, ref drag) if self.is_routed(idx) =>
                     return Some(drag.clone().relative_to(self.rect.xy()).into()),
 
-                event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) =>
+                event::Ui::Scroll(idx, ref scroll) if self.is_routed(idx) =>
                     return Some(scroll.clone().into()),
 
+                event::Ui::Accelerator(idx, accelerator) if self.is_routed(Some(idx)) =>
+                    return Some(event::Widget::Accelerator(accelerator)),
+
                 _ => (),
                 
             }
@@ -605,6 +669,18 @@ impl<'a> Iterator for ButtonClicks<'a> {
     }
 }
 
+impl<'a> Iterator for ContextMenus<'a> {
+    type Item = event::ContextMenu;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::ContextMenu(context_menu) = event {
+                return Some(context_menu);
+            }
+        }
+        None
+    }
+}
+
 impl<'a> Iterator for Drags<'a> {
     type Item = event::Drag;
     fn next(&mut self) -> Option<Self::Item> {
@@ -652,3 +728,15 @@ impl<'a> Iterator for Scrolls<'a> {
         None
     }
 }
+
+impl<'a> Iterator for Accelerators<'a> {
+    type Item = input::Accelerator;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Accelerator(accelerator) = event {
+                return Some(accelerator);
+            }
+        }
+        None
+    }
+}
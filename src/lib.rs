@@ -11,21 +11,29 @@ extern crate daggy;
 extern crate num;
 extern crate input as piston_input;
 extern crate rusttype;
+extern crate unicode_bidi;
+extern crate unicode_segmentation;
 
 #[cfg(feature="glium")] #[macro_use] pub extern crate glium;
+#[cfg(feature="serialize")] extern crate serde;
+#[cfg(feature="serialize")] #[macro_use] extern crate serde_derive;
 
 pub use color::{Color, Colorable};
+pub use cursor::MouseCursor;
 pub use border::{Bordering, Borderable};
 pub use label::{FontSize, Labelable};
 pub use position::{Align, Axis, Corner, Depth, Direction, Dimension, Dimensions, Edge, Margin,
                    Padding, Place, Point, Position, Positionable, Range, Rect, Scalar, Sizeable};
-pub use theme::Theme;
-pub use ui::{Ui, UiCell, UiBuilder};
+pub use theme::{Theme, ThemeBuilder};
+pub use ui::{FrameStats, Ui, UiCell, UiBuilder, WidgetInfo};
 pub use widget::{scroll, Widget};
 
+pub mod anim;
 pub mod backend;
 mod border;
+pub mod clipboard;
 pub mod color;
+pub mod cursor;
 pub mod event;
 pub mod graph;
 pub mod guide;
@@ -34,9 +42,11 @@ pub mod input;
 mod label;
 mod position;
 pub mod render;
+pub mod snapshot;
 pub mod text;
 pub mod theme;
 mod ui;
+pub mod undo;
 pub mod utils;
 pub mod widget;
 
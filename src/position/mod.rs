@@ -1,5 +1,6 @@
 
 use Ui;
+use input;
 use widget;
 
 pub use self::range::{Edge, Range};
@@ -65,6 +66,12 @@ pub enum Position {
     Direction(Direction, Scalar, Option<widget::Id>),
     /// A position at a place on some other Widget.
     Place(Place, Option<widget::Id>),
+    /// A position at which the widget's edge meets the given anchor and the widget extends from
+    /// the anchor in the given **Direction**.
+    ///
+    /// If the `bool` is `true` and the widget would extend beyond the edge of the window, the
+    /// widget is flipped across the anchor so that it extends in the opposite **Direction**.
+    Anchor(Scalar, Direction, bool),
 }
 
 /// Directionally positioned, normally relative to some other widget.
@@ -120,6 +127,10 @@ pub enum Dimension {
     /// The `Option<Scalar>` is an optional padding argument which when `Some`, will subtract the
     /// scalar from both ends of the other widget's dimension.
     KidAreaOf(widget::Id, Option<Scalar>),
+    /// The dimension should be the given fraction of that of the widget at the given index.
+    ///
+    /// The fraction is clamped to the range `0.0...1.0` when the dimension is resolved.
+    FractionOf(widget::Id, Scalar),
 }
 
 /// Widgets that are positionable.
@@ -268,6 +279,25 @@ pub trait Positionable: Sized {
         self.x_direction_from(other, Direction::Forwards, x)
     }
 
+    // Mouse positioning.
+
+    /// Place the top left corner of the widget at the current position of the mouse.
+    ///
+    /// This is useful for instantiating context menus and other popups.
+    fn at_mouse(self, input: &input::State) -> Self {
+        let xy = input.mouse.xy;
+        self.x_position(Position::Anchor(xy[0], Direction::Forwards, false))
+            .y_position(Position::Anchor(xy[1], Direction::Backwards, false))
+    }
+
+    /// The same as `at_mouse`, but if the widget would extend beyond the right or bottom edge of
+    /// the window, it is flipped across the mouse position so that it remains visible.
+    fn at_mouse_within_window(self, input: &input::State) -> Self {
+        let xy = input.mouse.xy;
+        self.x_position(Position::Anchor(xy[0], Direction::Forwards, true))
+            .y_position(Position::Anchor(xy[1], Direction::Backwards, true))
+    }
+
     // Alignment positioning.
 
     /// Align the **Position** of the widget along the *x* axis.
@@ -320,32 +350,38 @@ pub trait Positionable: Sized {
         self.y_position(Position::Align(align, Some(other.into())))
     }
 
-    /// Align the position to the left (only effective for Up or Down `Direction`s).
+    // Note that the `align_*_of` methods below resolve the position using the **Rect** of the
+    // `other` widget. If the `other` widget has not yet been set during the current update, the
+    // **Rect** from its previous update is used instead. If the `other` widget has never been set,
+    // the widget falls back to the origin until the `other` widget's **Rect** becomes known. Thus,
+    // the `other` widget should be set *before* any widgets that align to it.
+
+    /// Align the left edge of the widget with the left edge of the given widget.
     fn align_left_of(self, other: widget::Id) -> Self {
         self.x_align_to(other, Align::Start)
     }
 
-    /// Align the position to the middle (only effective for Up or Down `Direction`s).
+    /// Align the middle of the widget with the middle of the given widget along the *x* axis.
     fn align_middle_x_of(self, other: widget::Id) -> Self {
         self.x_align_to(other, Align::Middle)
     }
 
-    /// Align the position to the right (only effective for Up or Down `Direction`s).
+    /// Align the right edge of the widget with the right edge of the given widget.
     fn align_right_of(self, other: widget::Id) -> Self {
         self.x_align_to(other, Align::End)
     }
 
-    /// Align the position to the top (only effective for Left or Right `Direction`s).
+    /// Align the top edge of the widget with the top edge of the given widget.
     fn align_top_of(self, other: widget::Id) -> Self {
         self.y_align_to(other, Align::End)
     }
 
-    /// Align the position to the middle (only effective for Left or Right `Direction`s).
+    /// Align the middle of the widget with the middle of the given widget along the *y* axis.
     fn align_middle_y_of(self, other: widget::Id) -> Self {
         self.y_align_to(other, Align::Middle)
     }
 
-    /// Align the position to the bottom (only effective for Left or Right `Direction`s).
+    /// Align the bottom edge of the widget with the bottom edge of the given widget.
     fn align_bottom_of(self, other: widget::Id) -> Self {
         self.y_align_to(other, Align::Start)
     }
@@ -609,11 +645,26 @@ pub trait Positionable: Sized {
     ///// Rendering Depth (aka Z axis) /////
 
     /// The depth at which the widget should be rendered relatively to its sibling widgets.
+    ///
+    /// Siblings with a greater depth are rendered first, so a negative depth may be used to draw
+    /// a widget (i.e. a popup or tooltip) above its siblings. Siblings with equal depths are
+    /// rendered in the order in which they were instantiated.
     fn depth(self, depth: Depth) -> Self;
 
     /// Return the depth.
     fn get_depth(&self) -> Depth;
 
+    ///// Grid Snapping /////
+
+    /// Snap the resolved position of the widget to the nearest multiple of the given `step` along
+    /// both axes.
+    ///
+    /// A `step` that is not greater than `0.0` disables snapping.
+    fn snap_to_grid(self, step: Scalar) -> Self;
+
+    /// Return the step of the grid to which the widget's position is snapped, if any.
+    fn get_grid_step(&self) -> Option<Scalar>;
+
 }
 
 /// Widgets that support different dimensions.
@@ -688,6 +739,24 @@ pub trait Sizeable: Sized {
         self.padded_w_of(idx, pad).padded_h_of(idx, pad)
     }
 
+    /// Set the width as the given fraction of the width of the widget at the given index.
+    ///
+    /// For example, a `fraction` of `0.5` results in half the width of the other widget.
+    fn w_fraction_of(self, idx: widget::Id, fraction: Scalar) -> Self {
+        self.x_dimension(Dimension::FractionOf(idx.into(), fraction))
+    }
+
+    /// Set the height as the given fraction of the height of the widget at the given index.
+    fn h_fraction_of(self, idx: widget::Id, fraction: Scalar) -> Self {
+        self.y_dimension(Dimension::FractionOf(idx.into(), fraction))
+    }
+
+    /// Set the dimensions as the given fraction of the dimensions of the widget at the given
+    /// index.
+    fn wh_fraction_of(self, idx: widget::Id, fraction: Scalar) -> Self {
+        self.w_fraction_of(idx, fraction).h_fraction_of(idx, fraction)
+    }
+
     /// Set the width as the width of the padded area of the widget at the given index.
     fn kid_area_w_of(self, idx: widget::Id) -> Self {
         self.x_dimension(Dimension::KidAreaOf(idx.into(), None))
@@ -729,6 +798,7 @@ pub trait Sizeable: Sized {
             Dimension::Of(idx, Some(pad)) => ui.w_of(idx).map(|w| w - pad * 2.0),
             Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.w()),
             Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.w() - pad * 2.0),
+            Dimension::FractionOf(idx, fraction) => ui.w_of(idx).map(|w| fraction_of(w, fraction)),
         }
     }
 
@@ -740,6 +810,7 @@ pub trait Sizeable: Sized {
             Dimension::Of(idx, Some(pad)) => ui.h_of(idx).map(|w| w - pad * 2.0),
             Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.h()),
             Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.h() - pad * 2.0),
+            Dimension::FractionOf(idx, fraction) => ui.h_of(idx).map(|h| fraction_of(h, fraction)),
         }
     }
 
@@ -750,6 +821,85 @@ pub trait Sizeable: Sized {
 
 }
 
+/// Round the given `value` to the nearest multiple of `step`.
+///
+/// Values lying exactly half way between two multiples are always rounded towards positive
+/// infinity, so that negative values snap consistently with positive ones. A `step` that is not
+/// greater than `0.0` returns the `value` unchanged.
+pub fn snap_to_grid(value: Scalar, step: Scalar) -> Scalar {
+    if !(step > 0.0) {
+        return value;
+    }
+    (value / step + 0.5).floor() * step
+}
+
+/// Determine the centre of a widget with the given length whose edge meets the given `anchor`,
+/// extending from the `anchor` in the given `direction`.
+///
+/// When `bounds` is `Some` and the widget would exceed the bounds, it is flipped across the
+/// anchor. If the widget would still exceed the bounds, it is shifted back within them as far as
+/// its length allows.
+pub fn anchored(anchor: Scalar,
+                len: Scalar,
+                direction: Direction,
+                bounds: Option<Range>) -> Scalar
+{
+    let forwards = anchor + len / 2.0;
+    let backwards = anchor - len / 2.0;
+    let bounds = match bounds {
+        Some(bounds) => bounds.undirected(),
+        None => return match direction {
+            Direction::Forwards => forwards,
+            Direction::Backwards => backwards,
+        },
+    };
+    let fits_forwards = anchor + len <= bounds.end;
+    let fits_backwards = anchor - len >= bounds.start;
+    let middle = match (direction, fits_forwards, fits_backwards) {
+        (Direction::Forwards, false, true) => backwards,
+        (Direction::Backwards, true, false) => forwards,
+        (Direction::Forwards, _, _) => forwards,
+        (Direction::Backwards, _, _) => backwards,
+    };
+    let half_len = len / 2.0;
+    if middle + half_len > bounds.end {
+        bounds.end - half_len
+    } else if middle - half_len < bounds.start {
+        bounds.start + half_len
+    } else {
+        middle
+    }
+}
+
+/// Convert the given point from conrod's logical coordinates (relative to the centre of a window
+/// with the given logical dimensions, with *y* pointing upwards) to the physical coordinates of the
+/// device (relative to the top left of the framebuffer in pixels, with *y* pointing downwards).
+///
+/// `scale_factor` is the number of physical pixels per logical unit, e.g. `2.0` on most high-DPI
+/// displays. No rounding occurs, so fractional scale factors convert without loss.
+pub fn logical_to_physical(point: Point, window_dim: Dimensions, scale_factor: Scalar) -> Point {
+    let x = (point[0] + window_dim[0] / 2.0) * scale_factor;
+    let y = (window_dim[1] / 2.0 - point[1]) * scale_factor;
+    [x, y]
+}
+
+/// Convert the given point from the physical coordinates of the device to conrod's logical
+/// coordinates.
+///
+/// This is the inverse of `logical_to_physical`.
+pub fn physical_to_logical(point: Point, window_dim: Dimensions, scale_factor: Scalar) -> Point {
+    let x = point[0] / scale_factor - window_dim[0] / 2.0;
+    let y = window_dim[1] / 2.0 - point[1] / scale_factor;
+    [x, y]
+}
+
+/// Scale the given length by the given fraction, clamped to the range `0.0...1.0`.
+///
+/// A `NaN` fraction results in a length of `0.0`.
+fn fraction_of(length: Scalar, fraction: Scalar) -> Scalar {
+    length * fraction.max(0.0).min(1.0)
+}
+
 /// The distance between the inner edge of a border and the outer edge of the inner content.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Padding {
@@ -768,3 +918,21 @@ impl Padding {
         }
     }
 }
+
+
+#[test]
+fn snap_to_grid_should_round_to_the_nearest_multiple() {
+    assert_eq!(10.0, snap_to_grid(12.0, 10.0));
+    assert_eq!(20.0, snap_to_grid(16.0, 10.0));
+    assert_eq!(-10.0, snap_to_grid(-12.0, 10.0));
+    assert_eq!(-20.0, snap_to_grid(-16.0, 10.0));
+    assert_eq!(0.0, snap_to_grid(-4.0, 10.0));
+
+    // Ties always round towards positive infinity, regardless of sign.
+    assert_eq!(20.0, snap_to_grid(15.0, 10.0));
+    assert_eq!(-10.0, snap_to_grid(-15.0, 10.0));
+
+    // Non-positive steps disable snapping.
+    assert_eq!(12.5, snap_to_grid(12.5, 0.0));
+    assert_eq!(12.5, snap_to_grid(12.5, -10.0));
+}
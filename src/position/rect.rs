@@ -60,6 +60,25 @@ impl Rect {
         }
     }
 
+    /// The Rect representing the area in which two Rects intersect.
+    ///
+    /// Unlike `overlap`, Rects that only share an edge or corner are not considered to intersect,
+    /// so `None` is returned unless the intersection has some area. This is useful for computing
+    /// clipping regions, where a zero-sized region would result in nothing being drawn.
+    pub fn intersect(&self, other: Rect) -> Option<Rect> {
+        self.overlap(other).and_then(|rect| match rect.w() > 0.0 && rect.h() > 0.0 {
+            true => Some(rect),
+            false => None,
+        })
+    }
+
+    /// The smallest Rect that contains both `self` and the `other` Rect.
+    ///
+    /// This is the same as `max`.
+    pub fn union(&self, other: Rect) -> Rect {
+        self.max(other)
+    }
+
     /// The position in the middle of the x bounds.
     pub fn x(&self) -> Scalar {
         self.x.middle()
@@ -234,6 +253,18 @@ impl Rect {
         Rect { x: x.pad(pad), y: y.pad(pad) }
     }
 
+    /// The Rect with independent amounts of padding applied to each edge.
+    ///
+    /// If the padding along an axis exceeds the length of the Rect along that axis, that axis is
+    /// clamped to a length of `0.0`. The remaining point is positioned between the two edges in
+    /// proportion to the amount of padding applied to each.
+    pub fn pad_each(&self, left: Scalar, right: Scalar, top: Scalar, bottom: Scalar) -> Rect {
+        Rect {
+            x: pad_range_clamped(self.x, left, right),
+            y: pad_range_clamped(self.y, bottom, top),
+        }
+    }
+
     /// The Rect with some padding applied.
     pub fn padding(self, padding: Padding) -> Self {
         Rect {
@@ -406,3 +437,63 @@ impl Rect {
     }
 
 }
+
+/// Pad the start and end of the given undirected `Range`, clamping the resulting length to `0.0`.
+fn pad_range_clamped(range: Range, start: Scalar, end: Scalar) -> Range {
+    let range = range.undirected();
+    let len = range.len();
+    if start + end <= len {
+        return range.pad_ends(start, end);
+    }
+    let pos = match start + end > 0.0 {
+        true => range.start + len * (start / (start + end)),
+        false => range.middle(),
+    };
+    Range::new(pos, pos)
+}
+
+
+#[test]
+fn intersect_should_return_the_overlapping_area() {
+    let a = Rect::from_corners([0.0, 0.0], [20.0, 10.0]);
+    let b = Rect::from_corners([10.0, 5.0], [30.0, 30.0]);
+    assert_eq!(Some(Rect::from_corners([10.0, 5.0], [20.0, 10.0])), a.intersect(b));
+    assert_eq!(a.intersect(b), b.intersect(a));
+    assert_eq!(Rect::from_corners([0.0, 0.0], [30.0, 30.0]), a.union(b));
+}
+
+#[test]
+fn intersect_should_return_none_for_separate_rects() {
+    let a = Rect::from_corners([0.0, 0.0], [10.0, 10.0]);
+    let b = Rect::from_corners([20.0, -5.0], [30.0, 5.0]);
+    assert_eq!(None, a.intersect(b));
+    assert_eq!(Rect::from_corners([0.0, -5.0], [30.0, 10.0]), a.union(b));
+}
+
+#[test]
+fn intersect_should_return_none_for_rects_sharing_an_edge() {
+    let a = Rect::from_corners([0.0, 0.0], [10.0, 10.0]);
+    let right = Rect::from_corners([10.0, 0.0], [20.0, 10.0]);
+    let corner = Rect::from_corners([10.0, 10.0], [20.0, 20.0]);
+    assert_eq!(None, a.intersect(right));
+    assert_eq!(None, a.intersect(corner));
+    assert!(a.overlap(right).is_some());
+}
+
+#[test]
+fn pad_each_should_pad_each_edge_independently() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let padded = rect.pad_each(10.0, 20.0, 5.0, 15.0);
+    assert_eq!(Rect::from_corners([10.0, 15.0], [80.0, 45.0]), padded);
+}
+
+#[test]
+fn pad_each_should_clamp_excess_padding_to_zero_size() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let padded = rect.pad_each(150.0, 50.0, 10.0, 10.0);
+    assert_eq!([0.0, 30.0], padded.dim());
+    assert_eq!(75.0, padded.x());
+    let padded = rect.pad_each(0.0, 0.0, 40.0, 40.0);
+    assert_eq!([100.0, 0.0], padded.dim());
+    assert_eq!(25.0, padded.y());
+}
@@ -36,6 +36,20 @@ pub struct Primitives<'a> {
     points: Vec<Point>,
     /// The slice of rusttype `PositionedGlyph`s to re-use for the `Text` primitive.
     positioned_glyphs: Vec<text::PositionedGlyph>,
+    /// The `font::Id` of each of the `positioned_glyphs`.
+    glyph_font_ids: Vec<text::font::Id>,
+    /// The highlight `Rect`s remaining for the current highlighted `Text`, in reverse order.
+    highlight_rects: Vec<Rect>,
+    /// The `Text` whose highlight is currently being yielded, along with the highlight color.
+    highlighted_text: Option<(widget::Id, Rect, &'a graph::Container, Color)>,
+    /// The `(destination, source)` `Rect`s remaining for the current nine-patch `Image`, in
+    /// reverse order.
+    nine_patch_quads: Vec<(Rect, Rect)>,
+    /// The nine-patch `Image` whose patches are currently being yielded, along with its image
+    /// key and color.
+    nine_patch_image: Option<(widget::Id, Rect, widget::Id, Option<Color>)>,
+    /// The number of physical pixels per logical unit of the window.
+    scale_factor: Scalar,
 }
 
 /// An owned alternative to the `Primitives` type.
@@ -47,9 +61,12 @@ pub struct Primitives<'a> {
 pub struct OwnedPrimitives {
     primitives: Vec<OwnedPrimitive>,
     points: Vec<Point>,
+    path_segments: Vec<PathSegment>,
     max_glyphs: usize,
     line_infos: Vec<text::line::Info>,
     texts_string: String,
+    scale_factor: Scalar,
+    gamma_correct_blending: bool,
 }
 
 
@@ -59,18 +76,51 @@ pub struct OwnedPrimitives {
 pub trait PrimitiveWalker {
     /// Yield the next `Primitive` in order of depth, bottom to top.
     fn next_primitive(&mut self) -> Option<Primitive>;
+
+    /// The number of physical pixels per logical unit of the window (see `Ui::scale_factor`).
+    ///
+    /// All `Primitive`s are described in logical units, so backends multiply their coordinates by
+    /// this factor to find the positions of their vertices in pixels.
+    fn scale_factor(&self) -> Scalar {
+        1.0
+    }
+
+    /// Whether or not colors should be blended in linear RGB space (see the `Theme`'s
+    /// `gamma_correct_blending` field).
+    ///
+    /// Colors are always yielded in sRGB, so backends that honour this convert them to linear RGB
+    /// themselves before blending.
+    fn gamma_correct_blending(&self) -> bool {
+        false
+    }
 }
 
 impl<'a> PrimitiveWalker for Primitives<'a> {
     fn next_primitive(&mut self) -> Option<Primitive> {
         self.next()
     }
+
+    fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
+    fn gamma_correct_blending(&self) -> bool {
+        self.theme.gamma_correct_blending
+    }
 }
 
 impl<'a> PrimitiveWalker for WalkOwnedPrimitives<'a> {
     fn next_primitive(&mut self) -> Option<Primitive> {
         self.next()
     }
+
+    fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
+    fn gamma_correct_blending(&self) -> bool {
+        self.gamma_correct_blending
+    }
 }
 
 
@@ -88,6 +138,26 @@ pub struct Primitive<'a> {
     pub rect: Rect,
 }
 
+impl<'a> Primitive<'a> {
+
+    /// The kind of `Batch` to which the `Primitive` belongs.
+    pub fn batch_kind(&self) -> BatchKind {
+        match self.kind {
+            PrimitiveKind::Rectangle { .. } |
+            PrimitiveKind::RoundedRect { .. } |
+            PrimitiveKind::Border { .. } |
+            PrimitiveKind::Shadow { .. } |
+            PrimitiveKind::Polygon { .. } |
+            PrimitiveKind::Path { .. } |
+            PrimitiveKind::Lines { .. } |
+            PrimitiveKind::Text { .. } => BatchKind::Plain,
+            PrimitiveKind::Image { image_id, .. } => BatchKind::Image(image_id),
+            PrimitiveKind::Other(_) => BatchKind::Other(self.id),
+        }
+    }
+
+}
+
 /// The unique kind for each primitive element in the Ui.
 pub enum PrimitiveKind<'a> {
 
@@ -101,6 +171,48 @@ pub enum PrimitiveKind<'a> {
         color: Color
     },
 
+    /// A filled `Rectangle` whose corners are rounded with the given `radius`.
+    ///
+    /// Backends may tessellate the outline of the rounded rectangle via `rounded_rect_points`.
+    RoundedRect {
+        /// The fill colour for the rectangle.
+        color: Color,
+        /// The radius of each corner arc.
+        ///
+        /// This is clamped to half of the rectangle's shorter side, in which case the rectangle
+        /// is drawn as a capsule (or a circle if it is square).
+        radius: Scalar,
+    },
+
+    /// A border of the given `thickness` drawn along the inside edges of the `Primitive`'s `rect`.
+    ///
+    /// Backends may draw the border as the four `Rect`s produced by `border_rects`.
+    Border {
+        /// The colour of the border.
+        color: Color,
+        /// The thickness of each edge of the border.
+        ///
+        /// This is clamped to half of the rectangle's shorter side, in which case the rectangle
+        /// is entirely filled.
+        thickness: Scalar,
+    },
+
+    /// A soft shadow cast by the `Primitive`'s `rect`, to be drawn behind some widget.
+    ///
+    /// The shadow is fully opaque within the `rect` shifted by the `offset` and fades out to
+    /// transparent over the `blur` distance beyond it. Backends may draw the shadow using the
+    /// triangles produced by `shadow_triangles`.
+    Shadow {
+        /// The colour of the shadow at its most opaque.
+        color: Color,
+        /// The distance over which the shadow fades out beyond its edges.
+        ///
+        /// This is clamped to `MAX_SHADOW_BLUR` to avoid excessive overdraw.
+        blur: Scalar,
+        /// The offset of the shadow from the `rect` that casts it.
+        offset: Point,
+    },
+
     /// A filled `Polygon`.
     ///
     /// These are produced by the `Oval` and `Polygon` primitive widgets.
@@ -113,10 +225,25 @@ pub enum PrimitiveKind<'a> {
         points: &'a [Point],
     },
 
+    /// A filled vector `Path` made up of straight lines and quadratic bezier curves.
+    ///
+    /// Backends may triangulate the path via `tessellate_path`.
+    Path {
+        /// The fill colour for the inner part of the path.
+        color: Color,
+        /// Determines which areas of a self-intersecting path are considered to be inside it.
+        fill_rule: FillRule,
+        /// The ordered segments describing each closed sub-path.
+        segments: &'a [PathSegment],
+    },
+
     /// A series of consecutive `Line`s.
     ///
     /// These are produces via the `Line` and `PointPath` primitive widgets, or the `shape`
     /// primitives if they are instantiated with an `Outline` style.
+    ///
+    /// Backends may triangulate the lines, including their joins and anti-aliased edges, via
+    /// `tessellate_lines`.
     Lines {
         /// The colour of each `Line`.
         color: Color,
@@ -131,6 +258,10 @@ pub enum PrimitiveKind<'a> {
 
     /// A single `Image`, produced by the primitive `Image` widget.
     Image {
+        /// The key of the image's data within the `image::Map`.
+        ///
+        /// This is the id of the `Image` widget unless another was given via `Image::image_id`.
+        image_id: widget::Id,
         /// When `Some`, colours the `Image`. When `None`, the `Image` uses its regular colours.
         color: Option<Color>,
         /// The area of the texture that will be drawn to the `Image`'s `Rect`.
@@ -169,15 +300,57 @@ pub enum PrimitiveKind<'a> {
 /// handle "HiDPI" scales when caching glyphs.
 pub struct Text<'a> {
     positioned_glyphs: &'a mut Vec<text::PositionedGlyph>,
+    glyph_font_ids: &'a mut Vec<text::font::Id>,
+    window_dim: Dimensions,
+    text: &'a str,
+    line_infos: &'a [text::line::Info],
+    font_id: text::font::Id,
+    fonts: &'a text::font::Map,
+    font_size: FontSize,
+    rect: Rect,
+    x_align: Align,
+    y_align: Align,
+    line_spacing: Scalar,
+    justify: bool,
+    monospace: Option<Scalar>,
+}
+
+/// An owned copy of everything required to position the glyphs of a `Text` primitive.
+///
+/// Unlike `Text`, a `TextLayout` does not borrow from the `Ui` and so may be sent to other threads
+/// in order to lay out many texts in parallel (see `layout_texts`).
+#[derive(Clone)]
+pub struct TextLayout {
+    window_dim: Dimensions,
+    text: String,
+    line_infos: Vec<text::line::Info>,
+    font_id: text::font::Id,
+    /// The text's font along with its fallback chain.
+    fonts: text::font::Map,
+    font_size: FontSize,
+    rect: Rect,
+    x_align: Align,
+    y_align: Align,
+    line_spacing: Scalar,
+    justify: bool,
+    monospace: Option<Scalar>,
+}
+
+/// The parameters shared by `Text` and `TextLayout` for positioning glyphs.
+#[derive(Copy, Clone)]
+struct GlyphLayout<'a> {
     window_dim: Dimensions,
     text: &'a str,
     line_infos: &'a [text::line::Info],
-    font: &'a text::Font,
+    font_id: text::font::Id,
+    fonts: &'a text::font::Map,
     font_size: FontSize,
     rect: Rect,
     x_align: Align,
     y_align: Align,
     line_spacing: Scalar,
+    justify: bool,
+    monospace: Option<Scalar>,
 }
 
 
@@ -194,10 +367,28 @@ enum OwnedPrimitiveKind {
     Rectangle {
         color: Color,
     },
+    RoundedRect {
+        color: Color,
+        radius: Scalar,
+    },
+    Border {
+        color: Color,
+        thickness: Scalar,
+    },
+    Shadow {
+        color: Color,
+        blur: Scalar,
+        offset: Point,
+    },
     Polygon {
         color: Color,
         point_range: std::ops::Range<usize>,
     },
+    Path {
+        color: Color,
+        fill_rule: FillRule,
+        segment_range: std::ops::Range<usize>,
+    },
     Lines {
         color: Color,
         cap: primitive::line::Cap,
@@ -205,6 +396,7 @@ enum OwnedPrimitiveKind {
         point_range: std::ops::Range<usize>,
     },
     Image {
+        image_id: widget::Id,
         color: Option<Color>,
         source_rect: Option<Rect>,
     },
@@ -220,21 +412,28 @@ struct OwnedText {
     str_byte_range: std::ops::Range<usize>,
     line_infos_range: std::ops::Range<usize>,
     window_dim: Dimensions,
-    font: text::Font,
+    /// The text's font along with its fallback chain.
+    fonts: text::font::Map,
     font_size: FontSize,
     rect: Rect,
     x_align: Align,
     y_align: Align,
     line_spacing: Scalar,
+    justify: bool,
+    monospace: Option<Scalar>,
 }
 
 /// An iterator-like type for yielding `Primitive`s from an `OwnedPrimitives`.
 pub struct WalkOwnedPrimitives<'a> {
     primitives: std::slice::Iter<'a, OwnedPrimitive>,
     points: &'a [Point],
+    path_segments: &'a [PathSegment],
     line_infos: &'a [text::line::Info],
     texts_str: &'a str,
     positioned_glyphs: Vec<text::PositionedGlyph>,
+    glyph_font_ids: Vec<text::font::Id>,
+    scale_factor: Scalar,
+    gamma_correct_blending: bool,
 }
 
 
@@ -251,18 +450,139 @@ impl<'a> Text<'a> {
     /// out text. This is because conrod positioning uses a "pixel-agnostic" `Scalar` value
     /// representing *perceived* distances for its positioning and layout, rather than pixel
     /// values. During rendering however, the pixel density must be known
+    ///
+    /// Each character that is missing from the `Text`'s font is drawn from the first font within
+    /// its fallback chain that contains it (see `font::Map::set_fallbacks`). Use
+    /// `positioned_glyphs_and_font_ids` to determine the font of each glyph.
     pub fn positioned_glyphs(self, dpi_factor: f32) -> &'a [text::PositionedGlyph] {
+        self.positioned_glyphs_and_font_ids(dpi_factor).0
+    }
+
+    /// Produces the same list of `PositionedGlyph`s as `positioned_glyphs`, along with the
+    /// `font::Id` of the font from which each glyph was produced.
+    ///
+    /// Backends should use the `font::Id` of each glyph, rather than the `font_id` of the `Text`
+    /// primitive, when caching glyphs as glyphs may be drawn from a fallback font.
+    pub fn positioned_glyphs_and_font_ids(self, dpi_factor: f32)
+        -> (&'a [text::PositionedGlyph], &'a [text::font::Id])
+    {
         let Text {
             positioned_glyphs,
+            glyph_font_ids,
+            window_dim,
+            text,
+            line_infos,
+            font_id,
+            fonts,
+            font_size,
+            rect,
+            x_align,
+            y_align,
+            line_spacing,
+            justify,
+            monospace,
+        } = self;
+
+        let layout = GlyphLayout {
+            window_dim: window_dim,
+            text: text,
+            line_infos: line_infos,
+            font_id: font_id,
+            fonts: fonts,
+            font_size: font_size,
+            rect: rect,
+            x_align: x_align,
+            y_align: y_align,
+            line_spacing: line_spacing,
+            justify: justify,
+            monospace: monospace,
+        };
+
+        // Clear the existing glyphs and fill the buffer with glyphs for this Text.
+        positioned_glyphs.clear();
+        glyph_font_ids.clear();
+        layout.position_glyphs(dpi_factor, positioned_glyphs, glyph_font_ids);
+        (positioned_glyphs, glyph_font_ids)
+    }
+
+    /// An owned copy of everything required to position the glyphs of this `Text`, which may be
+    /// sent to another thread (see `layout_texts`).
+    pub fn layout(&self) -> TextLayout {
+        TextLayout {
+            window_dim: self.window_dim,
+            text: self.text.to_owned(),
+            line_infos: self.line_infos.to_vec(),
+            font_id: self.font_id,
+            fonts: self.fonts.fallback_chain(self.font_id),
+            font_size: self.font_size,
+            rect: self.rect,
+            x_align: self.x_align,
+            y_align: self.y_align,
+            line_spacing: self.line_spacing,
+            justify: self.justify,
+            monospace: self.monospace,
+        }
+    }
+
+}
+
+impl TextLayout {
+
+    /// Produces the list of `PositionedGlyph`s for the text, exactly as `Text::positioned_glyphs`
+    /// would for the `Text` from which this layout was produced.
+    pub fn positioned_glyphs(&self, dpi_factor: f32) -> Vec<text::PositionedGlyph> {
+        self.positioned_glyphs_and_font_ids(dpi_factor).0
+    }
+
+    /// Produces the list of `PositionedGlyph`s for the text along with the `font::Id` of each,
+    /// exactly as `Text::positioned_glyphs_and_font_ids` would.
+    pub fn positioned_glyphs_and_font_ids(&self, dpi_factor: f32)
+        -> (Vec<text::PositionedGlyph>, Vec<text::font::Id>)
+    {
+        let layout = GlyphLayout {
+            window_dim: self.window_dim,
+            text: &self.text,
+            line_infos: &self.line_infos,
+            font_id: self.font_id,
+            fonts: &self.fonts,
+            font_size: self.font_size,
+            rect: self.rect,
+            x_align: self.x_align,
+            y_align: self.y_align,
+            line_spacing: self.line_spacing,
+            justify: self.justify,
+            monospace: self.monospace,
+        };
+        let mut positioned_glyphs = Vec::new();
+        let mut glyph_font_ids = Vec::new();
+        layout.position_glyphs(dpi_factor, &mut positioned_glyphs, &mut glyph_font_ids);
+        (positioned_glyphs, glyph_font_ids)
+    }
+
+}
+
+impl<'a> GlyphLayout<'a> {
+
+    /// Push the positioned glyphs of the text onto the end of the given buffer, along with the
+    /// `font::Id` of each glyph.
+    fn position_glyphs(self,
+                       dpi_factor: f32,
+                       positioned_glyphs: &mut Vec<text::PositionedGlyph>,
+                       glyph_font_ids: &mut Vec<text::font::Id>)
+    {
+        let GlyphLayout {
             window_dim,
             text,
             line_infos,
-            font,
+            font_id,
+            fonts,
             font_size,
             rect,
             x_align,
             y_align,
             line_spacing,
+            justify,
+            monospace,
         } = self;
 
         // Convert conrod coordinates to pixel coordinates.
@@ -271,24 +591,100 @@ impl<'a> Text<'a> {
 
         // Produce the text layout iterators.
         let line_infos = line_infos.iter().cloned();
-        let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+        let lines = line_infos.clone().map(|info| (&text[info.byte_range()], info));
         let line_rects = text::line::rects(line_infos, font_size, rect,
                                            x_align, y_align, line_spacing);
 
-        // Clear the existing glyphs and fill the buffer with glyphs for this Text.
-        positioned_glyphs.clear();
+        // Fill the buffer with glyphs for this text.
         let scale = text::pt_to_scale((font_size as f32 * dpi_factor) as FontSize);
-        for (line, line_rect) in lines.zip(line_rects) {
-            let (x, y) = (trans_x(line_rect.left()) as f32, trans_y(line_rect.bottom()) as f32);
+        for ((line, info), line_rect) in lines.zip(line_rects) {
+
+            // Lines containing right-to-left text are laid out in visual order.
+            let line = text::bidi::reorder(line);
+            let line = &line[..];
+
+            // Monospace glyphs begin at the start of each of their cells.
+            if let Some(cell_w) = monospace {
+                let y = trans_y(line_rect.bottom()) as f32;
+                let mut x = line_rect.left();
+                let point = text::rt::Point { x: trans_x(x) as f32, y: y };
+                for (id, g, ch) in layout_line(fonts, font_id, line, scale, point) {
+                    let position = text::rt::Point { x: trans_x(x) as f32, y: y };
+                    positioned_glyphs.push(g.into_unpositioned().positioned(position).standalone());
+                    glyph_font_ids.push(id);
+                    x += text::line::monospace_advance(ch, cell_w);
+                }
+                continue;
+            }
+
+            let spacing = match justify {
+                true => text::line::justify_spacing(line, &info, rect.w()),
+                false => 0.0,
+            };
+
+            // Justified lines always begin at the left edge of the bounding `Rect`.
+            let left = if spacing > 0.0 { rect.left() } else { line_rect.left() };
+            let (x, y) = (trans_x(left) as f32, trans_y(line_rect.bottom()) as f32);
             let point = text::rt::Point { x: x, y: y };
-            positioned_glyphs.extend(font.layout(line, scale, point).map(|g| g.standalone()));
-        }
 
-        positioned_glyphs
+            // Shift each glyph by the spacing inserted after each preceding whitespace.
+            let spacing = (spacing * dpi_factor as Scalar) as f32;
+            let mut offset = 0.0;
+            for (id, g, ch) in layout_line(fonts, font_id, line, scale, point) {
+                let position = g.position();
+                let position = text::rt::Point { x: position.x + offset, y: position.y };
+                positioned_glyphs.push(g.into_unpositioned().positioned(position).standalone());
+                glyph_font_ids.push(id);
+                if ch.is_whitespace() {
+                    offset += spacing;
+                }
+            }
+        }
     }
 
 }
 
+/// Lay out a single line of text from the given `point`, yielding the `font::Id` and glyph of
+/// each character along with the character itself.
+///
+/// Each character is drawn from the first font within the fallback chain of the font with the
+/// given `font_id` that contains it (see `font::Map::resolve_glyph`), falling back to the font's
+/// own placeholder glyph (`GlyphId(0)`) if no font in the chain has even the `PLACEHOLDER`.
+/// Consecutive glyphs from the same font are kerned exactly as they are by rusttype's `LayoutIter`.
+fn layout_line<'a>(fonts: &'a text::font::Map,
+                   font_id: text::font::Id,
+                   line: &'a str,
+                   scale: text::Scale,
+                   point: text::rt::Point<f32>)
+    -> Vec<(text::font::Id, text::PositionedGlyph, char)>
+{
+    let mut caret = 0.0;
+    let mut last: Option<(text::font::Id, text::GlyphId)> = None;
+    let mut glyphs = Vec::with_capacity(line.len());
+    for ch in line.chars() {
+        let (id, glyph_ch) = fonts.resolve_glyph(font_id, ch);
+        let font = match fonts.get(id) {
+            Some(font) => font,
+            None => continue,
+        };
+        // Fonts lacking even the `PLACEHOLDER` are drawn with their own placeholder glyph.
+        let g = match font.glyph(glyph_ch).or_else(|| font.glyph(text::GlyphId(0))) {
+            Some(g) => g.scaled(scale),
+            None => continue,
+        };
+        if let Some((last_id, last_glyph)) = last {
+            if last_id == id {
+                caret += font.pair_kerning(scale, last_glyph, g.id());
+            }
+        }
+        let g = g.positioned(text::rt::Point { x: point.x + caret, y: point.y });
+        caret += g.unpositioned().h_metrics().advance_width;
+        last = Some((id, g.id()));
+        glyphs.push((id, g.standalone(), ch));
+    }
+    glyphs
+}
+
 const CIRCLE_RESOLUTION: usize = 50;
 const NUM_POINTS: usize = CIRCLE_RESOLUTION + 1;
 
@@ -296,11 +692,15 @@ const NUM_POINTS: usize = CIRCLE_RESOLUTION + 1;
 impl<'a> Primitives<'a> {
 
     /// Constructor for the `Primitives` iterator.
+    ///
+    /// The `scale_factor` is the number of physical pixels per logical unit of the window (see
+    /// `PrimitiveWalker::scale_factor`).
     pub fn new(graph: &'a Graph,
                depth_order: &'a [widget::Id],
                theme: &'a Theme,
                fonts: &'a text::font::Map,
-               window_dim: Dimensions) -> Self
+               window_dim: Dimensions,
+               scale_factor: Scalar) -> Self
     {
         Primitives {
             crop_stack: Vec::new(),
@@ -314,6 +714,12 @@ impl<'a> Primitives<'a> {
             // before writing points for an `Oval` or `Rectangle`.
             points: vec![[0.0, 0.0]; NUM_POINTS],
             positioned_glyphs: Vec::new(),
+            glyph_font_ids: Vec::new(),
+            highlight_rects: Vec::new(),
+            highlighted_text: None,
+            nine_patch_quads: Vec::new(),
+            nine_patch_image: None,
+            scale_factor: scale_factor,
         }
     }
 
@@ -324,17 +730,54 @@ impl<'a> Primitives<'a> {
             ref mut depth_order,
             ref mut points,
             ref mut positioned_glyphs,
+            ref mut glyph_font_ids,
+            ref mut highlight_rects,
+            ref mut highlighted_text,
+            ref mut nine_patch_quads,
+            ref mut nine_patch_image,
             graph,
             theme,
             fonts,
             window_rect,
         } = *self;
 
-        while let Some(widget) = next_widget(depth_order, graph, crop_stack, window_rect) {
+        loop {
             use widget::primitive::point_path::{State as PointPathState, Style as PointPathStyle};
             use widget::primitive::shape::polygon::{State as PolygonState};
             use widget::primitive::shape::Style as ShapeStyle;
 
+            // The highlight of a `Text` is yielded before the `Text` itself so that it is drawn
+            // behind the glyphs.
+            if let Some((id, scizzor, _, color)) = *highlighted_text {
+                if let Some(highlight_rect) = highlight_rects.pop() {
+                    let kind = PrimitiveKind::Rectangle { color: color };
+                    return Some(new_primitive(id, kind, scizzor, highlight_rect));
+                }
+            }
+
+            // Each patch of a nine-patch `Image` is yielded as a separate `Image` primitive.
+            if let Some((id, scizzor, image_id, color)) = *nine_patch_image {
+                match nine_patch_quads.pop() {
+                    Some((dest, src)) => {
+                        let kind = PrimitiveKind::Image {
+                            image_id: image_id,
+                            color: color,
+                            source_rect: Some(src),
+                        };
+                        return Some(new_primitive(id, kind, scizzor, dest));
+                    },
+                    None => *nine_patch_image = None,
+                }
+            }
+
+            let (widget, is_highlighted) = match highlighted_text.take() {
+                Some((id, scizzor, container, _)) => ((id, scizzor, container), true),
+                None => match next_widget(depth_order, graph, crop_stack, window_rect) {
+                    Some(widget) => (widget, false),
+                    None => return None,
+                },
+            };
+
             let (id, scizzor, container) = widget;
             let rect = container.rect;
 
@@ -487,18 +930,47 @@ impl<'a> Primitives<'a> {
                     let line_spacing = style.line_spacing(theme);
                     let x_align = style.text_align(theme);
                     let y_align = Align::End;
+                    let justify = style.justify(theme);
+                    let monospace = style.maybe_monospace(theme);
+
+                    // Collect the highlight `Rect`s so that they are yielded before the text.
+                    if let (false, Some(chars)) = (is_highlighted, state.highlight.clone()) {
+                        let line_infos = state.line_infos.iter().cloned();
+                        let lines = line_infos.clone()
+                            .map(|info| (&state.string[info.byte_range()], info));
+                        let line_rects = text::line::rects(line_infos, font_size, rect,
+                                                           x_align, y_align, line_spacing);
+                        let lines = lines.zip(line_rects)
+                            .map(|((line, info), line_rect)| (line, info, line_rect));
+                        let rects = match monospace {
+                            Some(cell_w) => {
+                                let advance = |ch, _| text::line::monospace_advance(ch, cell_w);
+                                text::line::highlight_rects_with(lines, chars, advance)
+                            },
+                            None => text::line::highlight_rects(lines, chars, font, font_size),
+                        };
+                        highlight_rects.extend(rects.into_iter().rev());
+                        let highlight_color = style.highlight_color(theme)
+                            .unwrap_or_else(|| color.highlighted().alpha(0.25));
+                        *highlighted_text = Some((id, scizzor, container, highlight_color));
+                        continue;
+                    }
 
                     let text = Text {
                         positioned_glyphs: positioned_glyphs,
+                        glyph_font_ids: glyph_font_ids,
                         window_dim: window_rect.dim(),
                         text: &state.string,
                         line_infos: &state.line_infos,
-                        font: font,
+                        font_id: font_id,
+                        fonts: fonts,
                         font_size: font_size,
                         rect: rect,
                         x_align: x_align,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        justify: justify,
+                        monospace: monospace,
                     };
 
                     let kind = PrimitiveKind::Text {
@@ -514,7 +986,15 @@ impl<'a> Primitives<'a> {
                 if let Some(image) = container.state_and_style::<State, Style>() {
                     let graph::UniqueWidgetState { ref state, ref style } = *image;
                     let color = style.maybe_color(theme);
+                    let image_id = state.image_id.unwrap_or(id);
+                    if let (Some(nine_patch), Some(src_rect)) = (state.nine_patch, state.src_rect) {
+                        let quads = nine_patch.quads(src_rect, rect);
+                        nine_patch_quads.extend(quads.iter().rev().cloned());
+                        *nine_patch_image = Some((id, scizzor, image_id, color));
+                        continue;
+                    }
                     let kind = PrimitiveKind::Image {
+                        image_id: image_id,
                         color: color,
                         source_rect: state.src_rect,
                     };
@@ -527,16 +1007,17 @@ impl<'a> Primitives<'a> {
                 return Some(new_primitive(id, kind, scizzor, rect));
             }
         }
-
-        None
     }
 
     /// Collect the `Primitives` list into an owned collection.
     ///
     /// This is useful for sending `Ui` rendering data across threads in an efficient manner.
     pub fn owned(mut self) -> OwnedPrimitives {
+        let scale_factor = self.scale_factor;
+        let gamma_correct_blending = self.theme.gamma_correct_blending;
         let mut primitives = Vec::with_capacity(self.depth_order.len());
         let mut primitive_points = Vec::new();
+        let mut primitive_path_segments = Vec::new();
         let mut primitive_line_infos = Vec::new();
         let mut texts_string = String::new();
         let mut max_glyphs = 0;
@@ -556,6 +1037,25 @@ impl<'a> Primitives<'a> {
                     primitives.push(new(kind));
                 },
 
+                PrimitiveKind::RoundedRect { color, radius } => {
+                    let kind = OwnedPrimitiveKind::RoundedRect { color: color, radius: radius };
+                    primitives.push(new(kind));
+                },
+
+                PrimitiveKind::Border { color, thickness } => {
+                    let kind = OwnedPrimitiveKind::Border { color: color, thickness: thickness };
+                    primitives.push(new(kind));
+                },
+
+                PrimitiveKind::Shadow { color, blur, offset } => {
+                    let kind = OwnedPrimitiveKind::Shadow {
+                        color: color,
+                        blur: blur,
+                        offset: offset,
+                    };
+                    primitives.push(new(kind));
+                },
+
                 PrimitiveKind::Polygon { color, points } => {
                     let start = primitive_points.len();
                     primitive_points.extend(points.iter().cloned());
@@ -567,6 +1067,18 @@ impl<'a> Primitives<'a> {
                     primitives.push(new(kind));
                 },
 
+                PrimitiveKind::Path { color, fill_rule, segments } => {
+                    let start = primitive_path_segments.len();
+                    primitive_path_segments.extend(segments.iter().cloned());
+                    let end = primitive_path_segments.len();
+                    let kind = OwnedPrimitiveKind::Path {
+                        color: color,
+                        fill_rule: fill_rule,
+                        segment_range: start..end,
+                    };
+                    primitives.push(new(kind));
+                },
+
                 PrimitiveKind::Lines { color, cap, thickness, points } => {
                     let start = primitive_points.len();
                     primitive_points.extend(points.iter().cloned());
@@ -580,8 +1092,9 @@ impl<'a> Primitives<'a> {
                     primitives.push(new(kind));
                 },
 
-                PrimitiveKind::Image { color, source_rect } => {
+                PrimitiveKind::Image { image_id, color, source_rect } => {
                     let kind = OwnedPrimitiveKind::Image {
+                        image_id: image_id,
                         color: color,
                         source_rect: source_rect,
                     };
@@ -593,12 +1106,14 @@ impl<'a> Primitives<'a> {
                         window_dim,
                         text,
                         line_infos,
-                        font,
+                        fonts,
                         font_size,
                         rect,
                         x_align,
                         y_align,
                         line_spacing,
+                        justify,
+                        monospace,
                         ..
                     } = text;
 
@@ -620,12 +1135,14 @@ impl<'a> Primitives<'a> {
                         str_byte_range: start_str_byte..end_str_byte,
                         line_infos_range: start_line_info_idx..end_line_info_idx,
                         window_dim: window_dim,
-                        font: font.clone(),
+                        fonts: fonts.fallback_chain(font_id),
                         font_size: font_size,
                         rect: rect,
                         x_align: x_align,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        justify: justify,
+                        monospace: monospace,
                     };
 
                     let kind = OwnedPrimitiveKind::Text {
@@ -645,9 +1162,12 @@ impl<'a> Primitives<'a> {
         OwnedPrimitives {
             primitives: primitives,
             points: primitive_points,
+            path_segments: primitive_path_segments,
             max_glyphs: max_glyphs,
             line_infos: primitive_line_infos,
             texts_string: texts_string,
+            scale_factor: scale_factor,
+            gamma_correct_blending: gamma_correct_blending,
         }
     }
 
@@ -661,16 +1181,23 @@ impl OwnedPrimitives {
         let OwnedPrimitives {
             ref primitives,
             ref points,
+            ref path_segments,
             ref line_infos,
             ref texts_string,
             max_glyphs,
+            scale_factor,
+            gamma_correct_blending,
         } = *self;
         WalkOwnedPrimitives {
             primitives: primitives.iter(),
             points: points,
+            path_segments: path_segments,
             line_infos: line_infos,
             texts_str: texts_string,
             positioned_glyphs: Vec::with_capacity(max_glyphs),
+            glyph_font_ids: Vec::with_capacity(max_glyphs),
+            scale_factor: scale_factor,
+            gamma_correct_blending: gamma_correct_blending,
         }
     }
 
@@ -683,7 +1210,9 @@ impl<'a> WalkOwnedPrimitives<'a> {
         let WalkOwnedPrimitives {
             ref mut primitives,
             ref mut positioned_glyphs,
+            ref mut glyph_font_ids,
             points,
+            path_segments,
             line_infos,
             texts_str,
         } = *self;
@@ -703,6 +1232,21 @@ impl<'a> WalkOwnedPrimitives<'a> {
                     new(kind)
                 },
 
+                OwnedPrimitiveKind::RoundedRect { color, radius } => {
+                    let kind = PrimitiveKind::RoundedRect { color: color, radius: radius };
+                    new(kind)
+                },
+
+                OwnedPrimitiveKind::Border { color, thickness } => {
+                    let kind = PrimitiveKind::Border { color: color, thickness: thickness };
+                    new(kind)
+                },
+
+                OwnedPrimitiveKind::Shadow { color, blur, offset } => {
+                    let kind = PrimitiveKind::Shadow { color: color, blur: blur, offset: offset };
+                    new(kind)
+                },
+
                 OwnedPrimitiveKind::Polygon { color, ref point_range } => {
                     let kind = PrimitiveKind::Polygon {
                         color: color,
@@ -711,6 +1255,15 @@ impl<'a> WalkOwnedPrimitives<'a> {
                     new(kind)
                 },
 
+                OwnedPrimitiveKind::Path { color, fill_rule, ref segment_range } => {
+                    let kind = PrimitiveKind::Path {
+                        color: color,
+                        fill_rule: fill_rule,
+                        segments: &path_segments[segment_range.clone()],
+                    };
+                    new(kind)
+                },
+
                 OwnedPrimitiveKind::Lines { color, cap, thickness, ref point_range } => {
                     let kind = PrimitiveKind::Lines {
                         color: color,
@@ -725,13 +1278,15 @@ impl<'a> WalkOwnedPrimitives<'a> {
                     let OwnedText {
                         ref str_byte_range,
                         ref line_infos_range,
-                        ref font,
+                        ref fonts,
                         window_dim,
                         font_size,
                         rect,
                         x_align,
                         y_align,
                         line_spacing,
+                        justify,
+                        monospace,
                     } = *text;
 
                     let text_str = &texts_str[str_byte_range.clone()];
@@ -739,15 +1294,19 @@ impl<'a> WalkOwnedPrimitives<'a> {
 
                     let text = Text {
                         positioned_glyphs: positioned_glyphs,
+                        glyph_font_ids: glyph_font_ids,
                         window_dim: window_dim,
                         text: text_str,
                         line_infos: line_infos,
-                        font: font,
+                        font_id: font_id,
+                        fonts: fonts,
                         font_size: font_size,
                         rect: rect,
                         x_align: x_align,
                         y_align: y_align,
                         line_spacing: line_spacing,
+                        justify: justify,
+                        monospace: monospace,
                     };
 
                     let kind = PrimitiveKind::Text {
@@ -758,8 +1317,9 @@ impl<'a> WalkOwnedPrimitives<'a> {
                     new(kind)
                 },
 
-                OwnedPrimitiveKind::Image { color, source_rect } => {
+                OwnedPrimitiveKind::Image { image_id, color, source_rect } => {
                     let kind = PrimitiveKind::Image {
+                        image_id: image_id,
                         color: color,
                         source_rect: source_rect,
                     };
@@ -773,60 +1333,1735 @@ impl<'a> WalkOwnedPrimitives<'a> {
 
 
 
-/// Simplify the constructor for a `Primitive`.
-fn new_primitive(id: widget::Id, kind: PrimitiveKind, scizzor: Rect, rect: Rect) -> Primitive {
-    Primitive {
-        id: id,
-        kind: kind,
-        scizzor: scizzor,
-        rect: rect,
-    }
+/// The texture and shader state required for drawing a `Primitive`.
+///
+/// Consecutive primitives of the same `BatchKind` may be merged into a single vertex buffer.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BatchKind {
+    /// Colored geometry along with glyphs from the glyph cache, which the provided backends draw
+    /// using a single shader.
+    Plain,
+    /// An `Image`, whose texture is associated with the given key within the `image::Map`.
+    Image(widget::Id),
+    /// A non-primitive widget that must be drawn by the user. These are never merged.
+    Other(widget::Id),
 }
 
-/// Retrieves the next visible widget from the `depth_order`, updating the `crop_stack` as
-/// necessary.
-fn next_widget<'a>(depth_order: &mut std::slice::Iter<widget::Id>,
-                   graph: &'a Graph,
-                   crop_stack: &mut Vec<(widget::Id, Rect)>,
-                   window_rect: Rect) -> Option<(widget::Id, Rect, &'a graph::Container)>
-{
-    while let Some(&id) = depth_order.next() {
-        let container = match graph.widget(id) {
-            Some(container) => container,
-            None => continue,
-        };
+/// A contiguous sequence of primitives that may be drawn with a single draw call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Batch {
+    /// The kind shared by every primitive within the batch.
+    pub kind: BatchKind,
+    /// The `Rect` to which every primitive within the batch is cropped.
+    pub scizzor: Rect,
+    /// The range of indices of the batched primitives within the original sequence.
+    pub primitives: std::ops::Range<usize>,
+}
 
-        // If we're currently using a cropped context and the current `crop_parent_idx` is
-        // *not* a depth-wise parent of the widget at the current `idx`, we should pop that
-        // cropped context from the stack as we are done with it.
-        while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
-            if graph.does_recursive_depth_edge_exist(crop_parent_idx, id) {
-                break;
-            } else {
-                crop_stack.pop();
+/// Groups a sequence of primitives into `Batch`es one primitive at a time.
+///
+/// This allows backends to determine where each draw call begins while filling their vertex
+/// buffers in a single pass over the primitives. See the `batches` function for the rules by
+/// which primitives are grouped.
+#[derive(Clone, Debug, Default)]
+pub struct Batcher {
+    current: Option<Batch>,
+    next_index: usize,
+}
+
+impl Batcher {
+
+    /// A `Batcher` that has not yet been given any primitives.
+    pub fn new() -> Self {
+        Batcher {
+            current: None,
+            next_index: 0,
+        }
+    }
+
+    /// Add the next primitive, described by its `BatchKind` and scizzor `Rect`.
+    ///
+    /// If the primitive cannot be merged with the current batch, the current batch is complete
+    /// and is returned, while the primitive begins a new batch.
+    pub fn push(&mut self, kind: BatchKind, scizzor: Rect) -> Option<Batch> {
+        let i = self.next_index;
+        self.next_index += 1;
+        if let Some(ref mut batch) = self.current {
+            let is_mergeable = match kind {
+                BatchKind::Other(_) => false,
+                _ => batch.kind == kind,
+            };
+            if is_mergeable && batch.scizzor == scizzor {
+                batch.primitives.end = i + 1;
+                return None;
             }
         }
+        let batch = Batch { kind: kind, scizzor: scizzor, primitives: i..i + 1 };
+        std::mem::replace(&mut self.current, Some(batch))
+    }
 
-        // Check the stack for the current Context.
-        let scizzor = crop_stack.last().map(|&(_, scizzor)| scizzor).unwrap_or(window_rect);
+    /// The batch to which the most recently pushed primitive belongs.
+    pub fn current(&self) -> Option<&Batch> {
+        self.current.as_ref()
+    }
 
-        // If the current widget should crop its children, we need to add a rect for it to
-        // the top of the crop stack.
-        if container.crop_kids {
-            let scizzor_rect = container.kid_area.rect.overlap(scizzor)
-                .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]));
-            crop_stack.push((id, scizzor_rect));
-        }
+    /// Complete and return the final batch, if any primitives were pushed since the last call.
+    pub fn finish(&mut self) -> Option<Batch> {
+        self.current.take()
+    }
 
-        // We only want to return primitives that are actually visible.
-        let is_visible = container.rect.overlap(window_rect).is_some()
-            && graph::algo::cropped_area_of_widget(graph, id).is_some();
-        if !is_visible {
-            continue;
+}
+
+/// Group the given sequence of primitives, described by their `BatchKind` and scizzor `Rect`,
+/// into as few `Batch`es as possible while preserving their order.
+///
+/// A new batch begins whenever the scizzor changes, the texture changes (i.e. switching between
+/// plain geometry and an image or between two different images) or an `Other` primitive occurs.
+pub fn batches<I>(primitives: I) -> Vec<Batch>
+    where I: IntoIterator<Item=(BatchKind, Rect)>,
+{
+    let mut batcher = Batcher::new();
+    let mut batches: Vec<Batch> = primitives.into_iter()
+        .filter_map(|(kind, scizzor)| batcher.push(kind, scizzor))
+        .collect();
+    batches.extend(batcher.finish());
+    batches
+}
+
+
+/// A buffer of vertices along with a buffer of indices describing the triangles between them.
+///
+/// Drawing with indices allows the vertices shared between the two triangles of a quad to be
+/// stored once rather than twice, so that each rectangle, image and glyph requires four vertices
+/// rather than six.
+///
+/// The vertices are divided into consecutive batches (one for each draw call) via `finish_batch`.
+/// The indices within each batch are relative to the first vertex of that batch so that the
+/// batch's vertices and indices may be uploaded alone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexedVertices<V> {
+    vertices: Vec<V>,
+    indices: Vec<u32>,
+    batch_start: usize,
+    batch_indices_start: usize,
+}
+
+/// The ranges of the vertices and indices that make up a single batch of `IndexedVertices`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedRange {
+    /// The range of the batch's vertices.
+    pub vertices: std::ops::Range<usize>,
+    /// The range of the batch's indices, each of which is relative to `vertices.start`.
+    pub indices: std::ops::Range<usize>,
+}
+
+impl<V> IndexedVertices<V>
+    where V: Copy,
+{
+
+    /// Empty vertex and index buffers.
+    pub fn new() -> Self {
+        IndexedVertices {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            batch_start: 0,
+            batch_indices_start: 0,
         }
+    }
 
-        return Some((id, scizzor, container));
+    /// Clear both buffers, ready for filling with the next frame's geometry.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.batch_start = 0;
+        self.batch_indices_start = 0;
+    }
+
+    /// All vertices within the buffer.
+    pub fn vertices(&self) -> &[V] {
+        &self.vertices
+    }
+
+    /// All indices within the buffer.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Push a single triangle.
+    pub fn push_triangle(&mut self, triangle: [V; 3]) {
+        let first = self.next_index();
+        self.vertices.extend_from_slice(&triangle);
+        self.indices.extend_from_slice(&[first, first + 1, first + 2]);
+    }
+
+    /// Push a quad whose corners are given in order around its perimeter (e.g. top left, bottom
+    /// left, bottom right, top right).
+    ///
+    /// The quad is split into two triangles along the diagonal between the first and third
+    /// corners, sharing both of the corners on that diagonal.
+    pub fn push_quad(&mut self, corners: [V; 4]) {
+        let first = self.next_index();
+        self.vertices.extend_from_slice(&corners);
+        self.indices.extend_from_slice(&[first, first + 1, first + 2,
+                                         first, first + 2, first + 3]);
+    }
+
+    /// Complete the current batch, returning its ranges. The following vertices begin a new
+    /// batch whose indices are relative to its own first vertex.
+    pub fn finish_batch(&mut self) -> IndexedRange {
+        let range = IndexedRange {
+            vertices: self.batch_start..self.vertices.len(),
+            indices: self.batch_indices_start..self.indices.len(),
+        };
+        self.batch_start = self.vertices.len();
+        self.batch_indices_start = self.indices.len();
+        range
+    }
+
+    /// The index of the next vertex relative to the start of the current batch.
+    fn next_index(&self) -> u32 {
+        (self.vertices.len() - self.batch_start) as u32
+    }
+
+}
+
+impl<V> Default for IndexedVertices<V>
+    where V: Copy,
+{
+    fn default() -> Self {
+        IndexedVertices::new()
+    }
+}
+
+/// Statistics describing the volume of geometry produced when rendering some primitives.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// The number of triangles required to draw the primitives.
+    ///
+    /// Rectangles, images and glyphs each require two triangles, polygons and rounded rectangles
+    /// are drawn as a triangle fan, borders as four quads, shadows as nine quads, while paths and
+    /// lines are drawn as the triangles produced by `tessellate_path` and `tessellate_lines`.
+    pub triangles: usize,
+    /// The number of draw calls required after batching.
+    ///
+    /// This is the number of `Batch`es produced by the `batches` function, excluding those for
+    /// `Other` primitives, which are drawn by the user.
+    pub draw_calls: usize,
+    /// The number of glyphs within all `Text` primitives.
+    pub glyphs: usize,
+}
+
+/// Walk the given primitives and produce `RenderStats` for them as they would be drawn by the
+/// provided backends.
+pub fn stats<P>(mut primitives: P) -> RenderStats
+    where P: PrimitiveWalker,
+{
+    let mut stats = RenderStats::default();
+    let mut kinds = Vec::new();
+    while let Some(primitive) = primitives.next_primitive() {
+        kinds.push((primitive.batch_kind(), primitive.scizzor));
+        match primitive.kind {
+            PrimitiveKind::Rectangle { .. } | PrimitiveKind::Image { .. } => stats.triangles += 2,
+            PrimitiveKind::RoundedRect { radius, .. } =>
+                stats.triangles += rounded_rect_points(primitive.rect, radius).len() - 2,
+            PrimitiveKind::Border { .. } => stats.triangles += 8,
+            PrimitiveKind::Shadow { .. } => stats.triangles += 18,
+            PrimitiveKind::Polygon { points, .. } =>
+                stats.triangles += if points.len() > 2 { points.len() - 2 } else { 0 },
+            PrimitiveKind::Path { fill_rule, segments, .. } =>
+                stats.triangles += tessellate_path(segments, fill_rule).len(),
+            PrimitiveKind::Lines { thickness, points, .. } =>
+                stats.triangles += tessellate_lines(points, thickness).len(),
+            PrimitiveKind::Text { text, .. } => {
+                let glyphs = text.positioned_glyphs(1.0).len();
+                stats.glyphs += glyphs;
+                stats.triangles += glyphs * 2;
+            },
+            PrimitiveKind::Other(_) => (),
+        }
+    }
+    stats.draw_calls = batches(kinds).iter()
+        .filter(|batch| match batch.kind {
+            BatchKind::Other(_) => false,
+            _ => true,
+        })
+        .count();
+    stats
+}
+
+
+/// A retained cache of the geometry (i.e. vertices) produced by a backend for each widget's
+/// `Primitive`, allowing the geometry of unchanged widgets to be reused between frames rather
+/// than being re-tessellated.
+///
+/// Each entry is keyed by the widget's `Id` along with the index of the `Primitive` amongst those
+/// produced by the same widget (see `PrimitiveIndices`), so that widgets producing more than one
+/// `Primitive` may cache the geometry of each. The geometry is only reused while the hash of the
+/// `Primitive` that produced it (see `primitive_hash`) is unchanged.
+///
+/// As a `Primitive`'s colors are already resolved from the `Theme`, most theme changes are
+/// detected by the hash alone. However, as backends may also derive geometry from the `Theme`
+/// directly, the whole cache is invalidated when `sync_theme` observes a new theme.
+pub struct GeometryCache<G> {
+    entries: std::collections::HashMap<(widget::Id, usize), (u64, G)>,
+    theme_generation: usize,
+}
+
+/// Yields the index of each `Primitive` amongst those produced by the same widget so far, as used
+/// to key a `GeometryCache`.
+#[derive(Clone, Debug, Default)]
+pub struct PrimitiveIndices {
+    counts: std::collections::HashMap<widget::Id, usize>,
+}
+
+impl<G> GeometryCache<G> {
+
+    /// An empty `GeometryCache`.
+    pub fn new() -> Self {
+        GeometryCache {
+            entries: std::collections::HashMap::new(),
+            theme_generation: 0,
+        }
+    }
+
+    /// Invalidate all cached geometry if the given `Ui::theme_generation` differs from the one
+    /// last observed.
+    pub fn sync_theme(&mut self, theme_generation: usize) {
+        if self.theme_generation != theme_generation {
+            self.theme_generation = theme_generation;
+            self.entries.clear();
+        }
+    }
+
+    /// The cached geometry for the given `Primitive`, the `index`th produced by its widget, if it
+    /// is unchanged since it was cached.
+    pub fn get(&self, primitive: &Primitive, index: usize) -> Option<&G> {
+        let hash = match primitive_hash(primitive) {
+            Some(hash) => hash,
+            None => return None,
+        };
+        match self.entries.get(&(primitive.id, index)) {
+            Some(&(cached_hash, ref geometry)) if cached_hash == hash => Some(geometry),
+            _ => None,
+        }
+    }
+
+    /// Cache the `geometry` produced for the given `Primitive`, the `index`th produced by its
+    /// widget, replacing any previous geometry cached for the same widget and index.
+    ///
+    /// Returns `false` if the `Primitive` cannot be cached, i.e. it is `PrimitiveKind::Other`.
+    pub fn insert(&mut self, primitive: &Primitive, index: usize, geometry: G) -> bool {
+        match primitive_hash(primitive) {
+            Some(hash) => {
+                self.entries.insert((primitive.id, index), (hash, geometry));
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Remove the geometry cached for every primitive of the widget with the given `id`, i.e. once
+    /// it is removed.
+    pub fn remove(&mut self, id: widget::Id) {
+        self.entries.retain(|&(entry_id, _), _| entry_id != id);
+    }
+
+    /// Retain only the geometry for which `f` returns `true` when given the `Id` of the widget and
+    /// the index of the primitive that produced it.
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(widget::Id, usize) -> bool,
+    {
+        self.entries.retain(|&(id, index), _| f(id, index));
+    }
+
+    /// Remove all cached geometry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The number of primitives with cached geometry.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+}
+
+impl PrimitiveIndices {
+
+    /// No primitives have been counted yet.
+    pub fn new() -> Self {
+        PrimitiveIndices {
+            counts: std::collections::HashMap::new(),
+        }
+    }
+
+    /// The index of the next primitive produced by the widget with the given `id`.
+    pub fn next(&mut self, id: widget::Id) -> usize {
+        let count = self.counts.entry(id).or_insert(0);
+        *count += 1;
+        *count - 1
+    }
+
+}
+
+/// Produce a hash of all state that affects the appearance of the given `Primitive`, including
+/// its `rect`, `scizzor`, colors and text.
+///
+/// `Scalar`s are hashed at a precision of 1/1024th of a unit.
+///
+/// Returns `None` for `PrimitiveKind::Other` primitives as they may be drawn from arbitrary state.
+pub fn primitive_hash(primitive: &Primitive) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+
+    fn hash_scalar<H: Hasher>(scalar: Scalar, state: &mut H) {
+        ((scalar * 1024.0).round() as i64).hash(state);
+    }
+    fn hash_rect<H: Hasher>(rect: Rect, state: &mut H) {
+        for &scalar in &[rect.x.start, rect.x.end, rect.y.start, rect.y.end] {
+            hash_scalar(scalar, state);
+        }
+    }
+    fn hash_points<H: Hasher>(points: &[Point], state: &mut H) {
+        points.len().hash(state);
+        for point in points {
+            hash_scalar(point[0], state);
+            hash_scalar(point[1], state);
+        }
+    }
+    fn hash_align<H: Hasher>(align: Align, state: &mut H) {
+        let discriminant: u8 = match align {
+            Align::Start => 0,
+            Align::Middle => 1,
+            Align::End => 2,
+        };
+        discriminant.hash(state);
+    }
+
+    let mut state = std::collections::hash_map::DefaultHasher::new();
+    hash_rect(primitive.rect, &mut state);
+    hash_rect(primitive.scizzor, &mut state);
+    match primitive.kind {
+        PrimitiveKind::Rectangle { color } => {
+            0u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+        },
+        PrimitiveKind::RoundedRect { color, radius } => {
+            5u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            hash_scalar(radius, &mut state);
+        },
+        PrimitiveKind::Border { color, thickness } => {
+            6u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            hash_scalar(thickness, &mut state);
+        },
+        PrimitiveKind::Shadow { color, blur, offset } => {
+            7u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            hash_scalar(blur, &mut state);
+            hash_points(&[offset], &mut state);
+        },
+        PrimitiveKind::Polygon { color, points } => {
+            1u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            hash_points(points, &mut state);
+        },
+        PrimitiveKind::Path { color, fill_rule, segments } => {
+            8u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            let fill_rule: u8 = match fill_rule {
+                FillRule::NonZero => 0,
+                FillRule::EvenOdd => 1,
+            };
+            fill_rule.hash(&mut state);
+            segments.len().hash(&mut state);
+            for segment in segments {
+                match *segment {
+                    PathSegment::MoveTo(to) => {
+                        0u8.hash(&mut state);
+                        hash_points(&[to], &mut state);
+                    },
+                    PathSegment::LineTo(to) => {
+                        1u8.hash(&mut state);
+                        hash_points(&[to], &mut state);
+                    },
+                    PathSegment::QuadraticTo { ctrl, to } => {
+                        2u8.hash(&mut state);
+                        hash_points(&[ctrl, to], &mut state);
+                    },
+                }
+            }
+        },
+        PrimitiveKind::Lines { color, cap, thickness, points } => {
+            2u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            let cap: u8 = match cap {
+                primitive::line::Cap::Flat => 0,
+                primitive::line::Cap::Round => 1,
+            };
+            cap.hash(&mut state);
+            hash_scalar(thickness, &mut state);
+            hash_points(points, &mut state);
+        },
+        PrimitiveKind::Image { image_id, color, source_rect } => {
+            3u8.hash(&mut state);
+            image_id.hash(&mut state);
+            color.map(|color| color.to_byte_fsa()).hash(&mut state);
+            source_rect.is_some().hash(&mut state);
+            if let Some(source_rect) = source_rect {
+                hash_rect(source_rect, &mut state);
+            }
+        },
+        PrimitiveKind::Text { color, font_id, ref text } => {
+            4u8.hash(&mut state);
+            color.to_byte_fsa().hash(&mut state);
+            font_id.hash(&mut state);
+            text.text.hash(&mut state);
+            text.line_infos.len().hash(&mut state);
+            for info in text.line_infos {
+                info.start_byte.hash(&mut state);
+                info.end_byte().hash(&mut state);
+            }
+            text.font_size.hash(&mut state);
+            hash_rect(text.rect, &mut state);
+            hash_align(text.x_align, &mut state);
+            hash_align(text.y_align, &mut state);
+            hash_scalar(text.line_spacing, &mut state);
+            text.justify.hash(&mut state);
+            text.monospace.is_some().hash(&mut state);
+            if let Some(cell_w) = text.monospace {
+                hash_scalar(cell_w, &mut state);
+            }
+        },
+        PrimitiveKind::Other(_) => return None,
+    }
+    Some(state.finish())
+}
+
+/// The area of the window to which the given `Primitive` may draw, i.e. the bounds of its
+/// geometry cropped to its `scizzor`.
+///
+/// Returns `None` if the `Primitive` is cropped entirely.
+pub fn primitive_bounds(primitive: &Primitive) -> Option<Rect> {
+    use utils::bounding_box_for_points;
+    let Primitive { ref kind, scizzor, rect, .. } = *primitive;
+    let bounds = match *kind {
+        PrimitiveKind::Shadow { blur, offset, .. } => shadow_rect(rect, blur, offset),
+        PrimitiveKind::Polygon { points, .. } => bounding_box_for_points(points.iter().cloned()),
+        PrimitiveKind::Path { segments, .. } => {
+            let contours = flatten_path(segments);
+            bounding_box_for_points(contours.iter().flat_map(|c| c.iter().cloned()))
+        },
+        PrimitiveKind::Lines { points, thickness, .. } => {
+            let bounds = bounding_box_for_points(points.iter().cloned());
+            bounds.pad(-(thickness / 2.0 + LINE_FEATHER))
+        },
+        _ => rect,
+    };
+    bounds.overlap(scizzor)
+}
+
+
+/// Tracks the area of the window that has changed between frames, i.e. the "damage".
+///
+/// Each frame's primitives are compared with those of the previous frame via `primitive_hash`.
+/// The area covered by each changed primitive is damaged both where it was and where it now is,
+/// so that a moved widget damages its old and new positions alike. Widgets that were removed
+/// damage the area that they previously covered.
+///
+/// Backends may then redraw only the primitives intersecting the damage (see `draw_damaged`).
+pub struct DamageTracker {
+    /// The combined hash and bounds of each widget's primitives during the previous frame.
+    prev: std::collections::HashMap<widget::Id, (Option<u64>, Option<Rect>)>,
+}
+
+impl DamageTracker {
+
+    /// A `DamageTracker` with no previous frame, so that the first frame is damaged entirely.
+    pub fn new() -> Self {
+        DamageTracker {
+            prev: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Forget the previous frame, so that all of the next frame is damaged (e.g. after the
+    /// window is resized or the target is lost).
+    pub fn invalidate(&mut self) {
+        self.prev.clear();
+    }
+
+    /// Compare the given primitives to those of the previous call, returning the smallest `Rect`
+    /// containing all damaged areas.
+    ///
+    /// Returns `None` if nothing has changed.
+    pub fn update<P>(&mut self, mut primitives: P) -> Option<Rect>
+        where P: PrimitiveWalker,
+    {
+        use std::hash::{Hash, Hasher};
+
+        // Collect the hash and bounds of each widget, combining those widgets that produce more
+        // than one primitive.
+        let mut current = std::collections::HashMap::new();
+        while let Some(primitive) = primitives.next_primitive() {
+            let hash = primitive_hash(&primitive);
+            let bounds = primitive_bounds(&primitive);
+            let entry = current.entry(primitive.id).or_insert(None);
+            *entry = match *entry {
+                None => Some((hash, bounds)),
+                Some((prev_hash, prev_bounds)) => {
+                    let combined = match (prev_hash, hash) {
+                        (Some(a), Some(b)) => {
+                            let mut state = std::collections::hash_map::DefaultHasher::new();
+                            (a, b).hash(&mut state);
+                            Some(state.finish())
+                        },
+                        _ => None,
+                    };
+                    let combined_bounds = match (prev_bounds, bounds) {
+                        (Some(a), Some(b)) => Some(a.union(b)),
+                        (a, b) => a.or(b),
+                    };
+                    Some((combined, combined_bounds))
+                },
+            };
+        }
+        let current: std::collections::HashMap<_, _> = current.into_iter()
+            .filter_map(|(id, entry)| entry.map(|entry| (id, entry)))
+            .collect();
+
+        let mut damaged = Vec::new();
+        for (id, &(hash, bounds)) in &current {
+            match self.prev.get(id) {
+                // Primitives that can't be hashed are always damaged.
+                Some(&(prev_hash, _)) if hash.is_some() && prev_hash == hash => (),
+                Some(&(_, prev_bounds)) => damaged.extend(prev_bounds.into_iter().chain(bounds)),
+                None => damaged.extend(bounds),
+            }
+        }
+        for (id, &(_, prev_bounds)) in &self.prev {
+            if !current.contains_key(id) {
+                damaged.extend(prev_bounds);
+            }
+        }
+
+        self.prev = current;
+        damaged.into_iter()
+            .fold(None, |damage: Option<Rect>, rect| Some(damage.map_or(rect, |d| d.union(rect))))
+    }
+
+}
+
+/// Draw only those primitives whose bounds intersect the given `damage`, reusing the geometry
+/// within the `cache` for those that are unchanged.
+///
+/// `tessellate` is only called for intersecting primitives whose geometry is missing from the
+/// `cache` (or may not be cached, i.e. `PrimitiveKind::Other`). `draw` is then given each
+/// intersecting primitive along with its geometry in order of depth. Primitives outside of the
+/// `damage` are skipped entirely, as the backend is expected to preserve the pixels outside of the
+/// damage from the previous frame, e.g. by cropping all drawing to the damage.
+pub fn draw_damaged<P, G, T, D>(mut primitives: P,
+                                damage: Rect,
+                                cache: &mut GeometryCache<G>,
+                                mut tessellate: T,
+                                mut draw: D)
+    where P: PrimitiveWalker,
+          T: FnMut(&Primitive) -> G,
+          D: FnMut(Primitive, &G),
+{
+    let mut indices = PrimitiveIndices::new();
+    while let Some(primitive) = primitives.next_primitive() {
+        let index = indices.next(primitive.id);
+        let is_damaged = primitive_bounds(&primitive)
+            .map_or(false, |bounds| bounds.intersect(damage).is_some());
+        if !is_damaged {
+            continue;
+        }
+        // Geometry that can't be cached must be re-tessellated each frame.
+        if primitive_hash(&primitive).is_none() {
+            let geometry = tessellate(&primitive);
+            draw(primitive, &geometry);
+            continue;
+        }
+        if cache.get(&primitive, index).is_none() {
+            let geometry = tessellate(&primitive);
+            cache.insert(&primitive, index, geometry);
+        }
+        if let Some(geometry) = cache.get(&primitive, index) {
+            draw(primitive, geometry);
+        }
+    }
+}
+
+
+/// The glyphs of every `Text` primitive, positioned ahead of rendering via `layout_texts`.
+pub struct TextLayouts {
+    glyphs: std::collections::HashMap<widget::Id, Glyphs>,
+}
+
+/// The positioned glyphs of a single `Text` primitive along with the `font::Id` of each.
+type Glyphs = (Vec<text::PositionedGlyph>, Vec<text::font::Id>);
+
+impl TextLayouts {
+
+    /// The positioned glyphs of the `Text` primitive produced by the widget with the given `id`.
+    pub fn get(&self, id: widget::Id) -> Option<&[text::PositionedGlyph]> {
+        self.glyphs.get(&id).map(|&(ref glyphs, _)| &glyphs[..])
+    }
+
+    /// The `font::Id` of each glyph yielded by `get` for the widget with the given `id`.
+    pub fn font_ids(&self, id: widget::Id) -> Option<&[text::font::Id]> {
+        self.glyphs.get(&id).map(|&(_, ref font_ids)| &font_ids[..])
+    }
+
+    /// The number of `Text` primitives that were laid out.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Queue all positioned glyphs to be cached by the given `GlyphCache`.
+    ///
+    /// The glyph cache is never shared with the threads of the `TextLayoutPool`. Instead, the
+    /// results of all threads are merged before their glyphs are queued here, so that the cache
+    /// may be used exactly as it is when laying out text serially.
+    pub fn queue_glyphs(&self, cache: &mut text::GlyphCache) {
+        for &(ref glyphs, ref font_ids) in self.glyphs.values() {
+            for (glyph, font_id) in glyphs.iter().zip(font_ids) {
+                cache.queue_glyph(font_id.index(), glyph.clone());
+            }
+        }
+    }
+
+}
+
+/// A persistent pool of threads on which `layout_texts` positions glyphs.
+///
+/// The threads are spawned once by `TextLayoutPool::new` and are reused by every call to
+/// `layout_texts`, so that laying out text each frame does not pay for spawning threads. The
+/// threads exit once the pool is dropped.
+pub struct TextLayoutPool {
+    /// Sends each chunk of texts to whichever thread is idle. `None` only while dropping.
+    jobs: Option<std::sync::mpsc::Sender<TextLayoutJob>>,
+    threads: Vec<std::thread::JoinHandle<()>>,
+}
+
+/// A chunk of texts to be laid out by one of the threads of a `TextLayoutPool`, along with the
+/// DPI factor and the channel on which the positioned glyphs are returned.
+type TextLayoutJob = (Vec<(widget::Id, TextLayout)>,
+                      f32,
+                      std::sync::mpsc::Sender<Vec<(widget::Id, Glyphs)>>);
+
+impl TextLayoutPool {
+
+    /// Spawn a pool of the given number of threads.
+    ///
+    /// With one thread or fewer, no threads are spawned and `layout_texts` lays out all texts
+    /// serially on the calling thread.
+    pub fn new(num_threads: usize) -> Self {
+        if num_threads <= 1 {
+            return TextLayoutPool { jobs: None, threads: Vec::new() };
+        }
+        let (jobs_tx, jobs_rx) = std::sync::mpsc::channel::<TextLayoutJob>();
+        let jobs_rx = std::sync::Arc::new(std::sync::Mutex::new(jobs_rx));
+        let threads = (0..num_threads)
+            .map(|_| {
+                let jobs_rx = jobs_rx.clone();
+                std::thread::spawn(move || loop {
+                    // The lock is released as soon as a job is received.
+                    let job = jobs_rx.lock().expect("a text layout thread panicked").recv();
+                    match job {
+                        Ok((chunk, dpi_factor, results)) => {
+                            let _ = results.send(layout_all(chunk, dpi_factor));
+                        },
+                        // The pool has been dropped.
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+        TextLayoutPool { jobs: Some(jobs_tx), threads: threads }
+    }
+
+    /// The number of threads between which texts are distributed.
+    pub fn num_threads(&self) -> usize {
+        self.threads.len()
+    }
+
+}
+
+impl Drop for TextLayoutPool {
+    fn drop(&mut self) {
+        // Closing the channel causes each thread to exit once it has finished its current job.
+        self.jobs.take();
+        for thread in self.threads.drain(..) {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Position the glyphs of each of the given texts.
+fn layout_all<I>(jobs: I, dpi_factor: f32) -> Vec<(widget::Id, Glyphs)>
+    where I: IntoIterator<Item=(widget::Id, TextLayout)>,
+{
+    jobs.into_iter()
+        .map(|(id, layout)| (id, layout.positioned_glyphs_and_font_ids(dpi_factor)))
+        .collect()
+}
+
+/// Position the glyphs of every `Text` primitive, distributing the texts between the threads of
+/// the given `pool`.
+///
+/// The glyphs of each text are independent of all others, so laying out large amounts of text in
+/// parallel avoids blocking the calling thread for the sum of their layout times. Each thread
+/// receives an owned `TextLayout` for each of its texts, and the results of all threads are
+/// collected before returning. If the pool has fewer than two threads, all texts are laid out
+/// serially on the calling thread.
+///
+/// Backends may then look up the glyphs of each `Text` primitive via `TextLayouts::get` rather
+/// than calling `Text::positioned_glyphs` while rendering (e.g. via the wgpu backend's
+/// `Renderer::fill_with_text_layouts`).
+pub fn layout_texts<P>(mut primitives: P, dpi_factor: f32, pool: &TextLayoutPool) -> TextLayouts
+    where P: PrimitiveWalker,
+{
+    let mut jobs = Vec::new();
+    while let Some(primitive) = primitives.next_primitive() {
+        if let PrimitiveKind::Text { ref text, .. } = primitive.kind {
+            jobs.push((primitive.id, text.layout()));
+        }
+    }
+
+    let num_threads = pool.num_threads();
+    let pool_jobs = match pool.jobs {
+        Some(ref pool_jobs) if num_threads > 1 && jobs.len() > 1 => pool_jobs,
+        _ => return TextLayouts { glyphs: layout_all(jobs, dpi_factor).into_iter().collect() },
+    };
+
+    // Divide the texts as evenly as possible between the threads.
+    let jobs_per_thread = (jobs.len() + num_threads - 1) / num_threads;
+    let mut jobs = jobs.into_iter();
+    let (results_tx, results_rx) = std::sync::mpsc::channel();
+    let mut num_chunks = 0;
+    loop {
+        let chunk: Vec<_> = jobs.by_ref().take(jobs_per_thread).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        pool_jobs.send((chunk, dpi_factor, results_tx.clone()))
+            .expect("the text layout threads have exited");
+        num_chunks += 1;
+    }
+    // Only the threads hold senders now, so receiving fails if any of them panics.
+    drop(results_tx);
+
+    let mut glyphs = std::collections::HashMap::new();
+    for _ in 0..num_chunks {
+        glyphs.extend(results_rx.recv().expect("a text layout thread panicked"));
+    }
+    TextLayouts { glyphs: glyphs }
+}
+
+
+/// The number of segments used to tessellate each corner arc of a `RoundedRect`.
+pub const CORNER_RESOLUTION: usize = 8;
+
+/// Clamp the given corner `radius` so that it lies between `0.0` and half of the shorter side of
+/// the given `rect`.
+pub fn clamp_corner_radius(rect: Rect, radius: Scalar) -> Scalar {
+    let (w, h) = rect.w_h();
+    let max = w.min(h) / 2.0;
+    radius.max(0.0).min(max)
+}
+
+/// The outline of the given `rect` with corners rounded by the given `radius`, in
+/// counter-clockwise order starting from the right end of the top edge.
+///
+/// The `radius` is first clamped via `clamp_corner_radius`. Each corner arc is tessellated into
+/// `CORNER_RESOLUTION` segments unless the radius is `0.0`, in which case only the four corners
+/// are produced.
+///
+/// As with the `Polygon` primitive, the first and final points are the same, so that the outline
+/// may be triangulated as a fan of `points.len() - 2` triangles.
+pub fn rounded_rect_points(rect: Rect, radius: Scalar) -> Vec<Point> {
+    use std::f64::consts::PI;
+    let (l, r, b, t) = rect.l_r_b_t();
+    let radius = clamp_corner_radius(rect, radius);
+    let mut points = if radius == 0.0 {
+        vec![[r, t], [l, t], [l, b], [r, b]]
+    } else {
+        // The centre of each corner arc, along with the angle at which the arc begins.
+        let corners = [
+            ([r - radius, t - radius], 0.0),
+            ([l + radius, t - radius], PI / 2.0),
+            ([l + radius, b + radius], PI),
+            ([r - radius, b + radius], PI * 1.5),
+        ];
+        let step = PI / 2.0 / CORNER_RESOLUTION as Scalar;
+        let mut points = Vec::with_capacity(corners.len() * (CORNER_RESOLUTION + 1) + 1);
+        for &([x, y], start) in &corners {
+            for i in 0..CORNER_RESOLUTION + 1 {
+                let angle = start + step * i as Scalar;
+                points.push([x + radius * angle.cos(), y + radius * angle.sin()]);
+            }
+        }
+        points
+    };
+    let first = points[0];
+    points.push(first);
+    points
+}
+
+/// The four `Rect`s that make up a border of the given `thickness` along the inside edges of the
+/// given `rect`, in the order top, bottom, left, right.
+///
+/// The top and bottom edges span the full width of the `rect` while the left and right edges fill
+/// the space between them, so that the corners are not drawn twice. The `thickness` is clamped to
+/// half of the `rect`'s shorter side.
+pub fn border_rects(rect: Rect, thickness: Scalar) -> [Rect; 4] {
+    let (l, r, b, t) = rect.l_r_b_t();
+    let thickness = clamp_corner_radius(rect, thickness);
+    [
+        Rect::from_corners([l, t - thickness], [r, t]),
+        Rect::from_corners([l, b], [r, b + thickness]),
+        Rect::from_corners([l, b + thickness], [l + thickness, t - thickness]),
+        Rect::from_corners([r - thickness, b + thickness], [r, t - thickness]),
+    ]
+}
+
+
+/// The maximum `blur` distance of a `Shadow` primitive.
+///
+/// The area covered by a shadow grows with the square of its blur, so larger values are clamped
+/// to avoid excessive overdraw.
+pub const MAX_SHADOW_BLUR: Scalar = 64.0;
+
+/// Clamp the given shadow `blur` between `0.0` and `MAX_SHADOW_BLUR`.
+pub fn clamp_shadow_blur(blur: Scalar) -> Scalar {
+    blur.max(0.0).min(MAX_SHADOW_BLUR)
+}
+
+/// The bounds of the area covered by a `Shadow` cast by the given `rect`.
+///
+/// This is the `rect` shifted by the `offset` and expanded on each side by the clamped `blur`.
+pub fn shadow_rect(rect: Rect, blur: Scalar, offset: Point) -> Rect {
+    rect.shift(offset).pad(-clamp_shadow_blur(blur))
+}
+
+/// Triangulate a `Shadow` cast by the given `rect` as nine quads, i.e. eighteen triangles.
+///
+/// Each vertex is paired with the opacity of the shadow at that vertex, by which the alpha of the
+/// shadow's color should be multiplied. The opacity is `1.0` within the shifted `rect` and `0.0`
+/// at the edges of the `shadow_rect`, so that interpolating between the vertices produces the
+/// blurred falloff.
+pub fn shadow_triangles(rect: Rect, blur: Scalar, offset: Point) -> Vec<[(Point, f32); 3]> {
+    let inner = rect.shift(offset);
+    let outer = shadow_rect(rect, blur, offset);
+    let (il, ir, ib, it) = inner.l_r_b_t();
+    let (ol, or, ob, ot) = outer.l_r_b_t();
+    let xs = [ol, il, ir, or];
+    let ys = [ob, ib, it, ot];
+    let v = |i: usize, j: usize| {
+        let opacity = if (i == 1 || i == 2) && (j == 1 || j == 2) { 1.0 } else { 0.0 };
+        ([xs[i], ys[j]], opacity)
+    };
+    let mut triangles = Vec::with_capacity(18);
+    for i in 0..3 {
+        for j in 0..3 {
+            triangles.push([v(i, j + 1), v(i + 1, j), v(i, j)]);
+            triangles.push([v(i, j + 1), v(i + 1, j), v(i + 1, j + 1)]);
+        }
+    }
+    triangles
+}
+
+
+/// A single segment of a `Path` primitive.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    /// Begin a new sub-path at the given point, closing the current sub-path.
+    MoveTo(Point),
+    /// A straight line from the current point to the given point.
+    LineTo(Point),
+    /// A quadratic bezier curve from the current point to `to`, bending towards `ctrl`.
+    QuadraticTo {
+        /// The control point of the curve.
+        ctrl: Point,
+        /// The end point of the curve.
+        to: Point,
+    },
+}
+
+/// Determines which areas of a `Path` are considered to be inside it.
+///
+/// The winding number of a point is the number of times the path's outline travels around it,
+/// counting counter-clockwise as positive and clockwise as negative.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Fill every area with a winding number other than zero.
+    ///
+    /// Overlapping sub-paths travelling in the same direction are filled, while a sub-path
+    /// travelling in the opposite direction to the one enclosing it cuts a hole.
+    NonZero,
+    /// Fill every area with an odd winding number.
+    EvenOdd,
+}
+
+/// The number of straight lines into which each `QuadraticTo` segment of a `Path` is flattened.
+pub const CURVE_RESOLUTION: usize = 16;
+
+/// Flatten the given `Path` segments into a list of closed sub-paths, or "contours", made up of
+/// straight lines.
+///
+/// Each contour is implicitly closed, i.e. its last point joins its first. Segments that occur
+/// before the first `MoveTo` begin a contour at their end point.
+pub fn flatten_path(segments: &[PathSegment]) -> Vec<Vec<Point>> {
+    let mut contours: Vec<Vec<Point>> = Vec::new();
+    let mut contour: Vec<Point> = Vec::new();
+    for segment in segments {
+        match *segment {
+            PathSegment::MoveTo(to) => {
+                if !contour.is_empty() {
+                    contours.push(std::mem::replace(&mut contour, Vec::new()));
+                }
+                contour.push(to);
+            },
+            PathSegment::LineTo(to) => contour.push(to),
+            PathSegment::QuadraticTo { ctrl, to } => {
+                let from = match contour.last() {
+                    Some(&from) => from,
+                    None => {
+                        contour.push(to);
+                        continue;
+                    },
+                };
+                for i in 1..CURVE_RESOLUTION + 1 {
+                    let t = i as Scalar / CURVE_RESOLUTION as Scalar;
+                    let u = 1.0 - t;
+                    let p = |d: usize| u * u * from[d] + 2.0 * u * t * ctrl[d] + t * t * to[d];
+                    contour.push([p(0), p(1)]);
+                }
+            },
+        }
+    }
+    if !contour.is_empty() {
+        contours.push(contour);
+    }
+    contours
+}
+
+/// Triangulate the area enclosed by the given `Path` segments according to the `fill_rule`.
+///
+/// The path is first flattened via `flatten_path` and then divided into horizontal bands at each
+/// vertex and at each point where two edges intersect. As no edges cross within a band, each band
+/// is filled with the trapezoids between consecutive edges whose winding number satisfies the
+/// `fill_rule`. Each trapezoid produces two triangles, or one if either of its ends has no width.
+pub fn tessellate_path(segments: &[PathSegment], fill_rule: FillRule) -> Vec<[Point; 3]> {
+    // Each non-horizontal edge as its lower point, its upper point and its winding direction.
+    let mut edges: Vec<(Point, Point, i32)> = Vec::new();
+    for contour in &flatten_path(segments) {
+        for (i, &a) in contour.iter().enumerate() {
+            let b = contour[(i + 1) % contour.len()];
+            if a[1] < b[1] {
+                edges.push((a, b, 1));
+            } else if a[1] > b[1] {
+                edges.push((b, a, -1));
+            }
+        }
+    }
+
+    // The x position of the given edge at the given height.
+    fn x_at(&(lo, hi, _): &(Point, Point, i32), y: Scalar) -> Scalar {
+        lo[0] + (hi[0] - lo[0]) * (y - lo[1]) / (hi[1] - lo[1])
+    }
+
+    // Find the height of every vertex and every intersection between edges.
+    let mut ys: Vec<Scalar> = Vec::with_capacity(edges.len() * 2);
+    for (i, edge) in edges.iter().enumerate() {
+        ys.push(edge.0[1]);
+        ys.push(edge.1[1]);
+        for other in &edges[i + 1..] {
+            let y_start = edge.0[1].max(other.0[1]);
+            let y_end = edge.1[1].min(other.1[1]);
+            if y_start >= y_end {
+                continue;
+            }
+            // As both edges span the range, their difference in x varies linearly along it.
+            let start_diff = x_at(edge, y_start) - x_at(other, y_start);
+            let end_diff = x_at(edge, y_end) - x_at(other, y_end);
+            if start_diff * end_diff < 0.0 {
+                let t = start_diff / (start_diff - end_diff);
+                ys.push(y_start + (y_end - y_start) * t);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    ys.dedup();
+
+    let is_inside = |winding: i32| match fill_rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    };
+
+    let mut triangles = Vec::new();
+    let mut crossings: Vec<(Scalar, Scalar, Scalar, i32)> = Vec::new();
+    for band in ys.windows(2) {
+        let (y0, y1) = (band[0], band[1]);
+        let y_mid = (y0 + y1) / 2.0;
+
+        // The x position of each edge spanning the band at its bottom, middle and top.
+        crossings.clear();
+        crossings.extend(edges.iter()
+            .filter(|edge| edge.0[1] <= y0 && edge.1[1] >= y1)
+            .map(|edge| (x_at(edge, y_mid), x_at(edge, y0), x_at(edge, y1), edge.2)));
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].3;
+            if !is_inside(winding) {
+                continue;
+            }
+            let (bl, br) = ([pair[0].1, y0], [pair[1].1, y0]);
+            let (tl, tr) = ([pair[0].2, y1], [pair[1].2, y1]);
+            if br[0] > bl[0] {
+                triangles.push([bl, br, tr]);
+            }
+            if tr[0] > tl[0] {
+                triangles.push([bl, tr, tl]);
+            }
+        }
+    }
+    triangles
+}
+
+
+/// The width of the anti-aliased fringe along each edge of a line produced by `tessellate_lines`,
+/// over which the line fades from opaque to transparent.
+pub const LINE_FEATHER: Scalar = 1.0;
+
+/// The maximum length of a mitered join relative to half the line's thickness, beyond which the
+/// join is beveled instead.
+pub const MITER_LIMIT: Scalar = 4.0;
+
+/// Triangulate a series of consecutive lines of the given `thickness` joining the given `points`.
+///
+/// Each vertex is paired with the opacity of the line at that vertex, by which the alpha of the
+/// line's color should be multiplied. Each line is drawn as an opaque quad of the given
+/// `thickness`, along with a quad on either side that fades out to transparent over the
+/// `LINE_FEATHER` width in order to anti-alias its edges.
+///
+/// The gap on the outside of each corner between two lines is filled with a mitered join, or a
+/// beveled join if the miter would exceed the `MITER_LIMIT`. Joins are feathered in the same
+/// manner as the lines.
+pub fn tessellate_lines(points: &[Point], thickness: Scalar) -> Vec<[(Point, f32); 3]> {
+    fn add(a: Point, b: Point) -> Point { [a[0] + b[0], a[1] + b[1]] }
+    fn scale(a: Point, s: Scalar) -> Point { [a[0] * s, a[1] * s] }
+    fn normalize(a: Point) -> Option<Point> {
+        let mag = (a[0] * a[0] + a[1] * a[1]).sqrt();
+        if mag > 0.0 { Some(scale(a, 1.0 / mag)) } else { None }
+    }
+
+    // Push the quad `a b c d`, where `a` and `b` are opaque and `c` and `d` are transparent.
+    fn push_quad(triangles: &mut Vec<[(Point, f32); 3]>,
+                 a: Point, b: Point, c: Point, d: Point, opacity: [f32; 2])
+    {
+        let (a, b) = ((a, opacity[0]), (b, opacity[0]));
+        let (c, d) = ((c, opacity[1]), (d, opacity[1]));
+        triangles.push([a, b, c]);
+        triangles.push([a, c, d]);
+    }
+
+    let half = thickness / 2.0;
+    let feathered = half + LINE_FEATHER;
+    let mut triangles = Vec::new();
+
+    // The normal of each segment with any zero-length segments skipped.
+    let mut segments: Vec<(Point, Point, Point)> = Vec::new();
+    for window in points.windows(2) {
+        let (a, b) = (window[0], window[1]);
+        if let Some(direction) = normalize([b[0] - a[0], b[1] - a[1]]) {
+            segments.push((a, b, [-direction[1], direction[0]]));
+        }
+    }
+
+    for &(a, b, n) in &segments {
+        let core = |p: Point, side: Scalar| add(p, scale(n, side * half));
+        let fringe = |p: Point, side: Scalar| add(p, scale(n, side * feathered));
+        push_quad(&mut triangles, core(a, 1.0), core(b, 1.0), core(b, -1.0), core(a, -1.0),
+                  [1.0, 1.0]);
+        push_quad(&mut triangles, core(a, 1.0), core(b, 1.0), fringe(b, 1.0), fringe(a, 1.0),
+                  [1.0, 0.0]);
+        push_quad(&mut triangles, core(a, -1.0), core(b, -1.0), fringe(b, -1.0), fringe(a, -1.0),
+                  [1.0, 0.0]);
+    }
+
+    for window in segments.windows(2) {
+        let (_, p, n1) = window[0];
+        let (_, _, n2) = window[1];
+        // The cross product of the two directions determines which side of the corner is outside.
+        let cross = n1[0] * n2[1] - n1[1] * n2[0];
+        if cross.abs() < 1e-9 {
+            continue;
+        }
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let (n1, n2) = (scale(n1, side), scale(n2, side));
+        let (c1, c2) = (add(p, scale(n1, half)), add(p, scale(n2, half)));
+        let (f1, f2) = (add(p, scale(n1, feathered)), add(p, scale(n2, feathered)));
+
+        // The miter bisects the two normals, extended so as to meet both outer edges.
+        let miter = normalize(add(n1, n2)).and_then(|m| {
+            let cos = m[0] * n1[0] + m[1] * n1[1];
+            if cos > 0.0 && 1.0 / cos <= MITER_LIMIT { Some(scale(m, 1.0 / cos)) } else { None }
+        });
+
+        match miter {
+            Some(m) => {
+                let (tip, tip_fringe) = (add(p, scale(m, half)), add(p, scale(m, feathered)));
+                triangles.push([(p, 1.0), (c1, 1.0), (tip, 1.0)]);
+                triangles.push([(p, 1.0), (tip, 1.0), (c2, 1.0)]);
+                push_quad(&mut triangles, c1, tip, tip_fringe, f1, [1.0, 0.0]);
+                push_quad(&mut triangles, tip, c2, f2, tip_fringe, [1.0, 0.0]);
+            },
+            None => {
+                triangles.push([(p, 1.0), (c1, 1.0), (c2, 1.0)]);
+                push_quad(&mut triangles, c1, c2, f2, f1, [1.0, 0.0]);
+            },
+        }
+    }
+
+    triangles
+}
+
+
+/// Simplify the constructor for a `Primitive`.
+fn new_primitive(id: widget::Id, kind: PrimitiveKind, scizzor: Rect, rect: Rect) -> Primitive {
+    Primitive {
+        id: id,
+        kind: kind,
+        scizzor: scizzor,
+        rect: rect,
+    }
+}
+
+/// Retrieves the next visible widget from the `depth_order`, updating the `crop_stack` as
+/// necessary.
+fn next_widget<'a>(depth_order: &mut std::slice::Iter<widget::Id>,
+                   graph: &'a Graph,
+                   crop_stack: &mut Vec<(widget::Id, Rect)>,
+                   window_rect: Rect) -> Option<(widget::Id, Rect, &'a graph::Container)>
+{
+    while let Some(&id) = depth_order.next() {
+        let container = match graph.widget(id) {
+            Some(container) => container,
+            None => continue,
+        };
+
+        // If we're currently using a cropped context and the current `crop_parent_idx` is
+        // *not* a depth-wise parent of the widget at the current `idx`, we should pop that
+        // cropped context from the stack as we are done with it.
+        while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
+            if graph.does_recursive_depth_edge_exist(crop_parent_idx, id) {
+                break;
+            } else {
+                crop_stack.pop();
+            }
+        }
+
+        // Check the stack for the current Context.
+        let scizzor = crop_stack.last().map(|&(_, scizzor)| scizzor).unwrap_or(window_rect);
+
+        // If the current widget should crop its children, we need to add a rect for it to
+        // the top of the crop stack.
+        if container.crop_kids {
+            let scizzor_rect = container.kid_area.rect.overlap(scizzor)
+                .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]));
+            crop_stack.push((id, scizzor_rect));
+        }
+
+        // We only want to return primitives that are actually visible.
+        let is_visible = container.rect.overlap(window_rect).is_some()
+            && graph::algo::cropped_area_of_widget(graph, id).is_some();
+        if !is_visible {
+            continue;
+        }
+
+        return Some((id, scizzor, container));
     }
 
     None
 }
+
+
+#[test]
+fn batches_should_merge_consecutive_primitives_sharing_a_texture_and_scizzor() {
+    let a = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let b = Rect::from_corners([0.0, 0.0], [50.0, 50.0]);
+    let (image_a, image_b, other) = (widget::Id::new(0), widget::Id::new(1), widget::Id::new(2));
+    let kinds = vec![
+        (BatchKind::Plain, a),
+        (BatchKind::Plain, a),
+        (BatchKind::Image(image_a), a),
+        (BatchKind::Image(image_a), a),
+        (BatchKind::Plain, a),
+        // A change in scizzor forces a new batch.
+        (BatchKind::Plain, b),
+        (BatchKind::Plain, b),
+        // `Other` primitives are never merged.
+        (BatchKind::Other(other), b),
+        (BatchKind::Other(other), b),
+        // Switching textures forces a new batch.
+        (BatchKind::Image(image_a), b),
+        (BatchKind::Image(image_b), b),
+    ];
+    let merged = batches(kinds);
+    let ranges: Vec<_> = merged.iter().map(|batch| batch.primitives.clone()).collect();
+    assert_eq!(vec![0..2, 2..4, 4..5, 5..7, 7..8, 8..9, 9..10, 10..11], ranges);
+    assert_eq!(BatchKind::Image(image_a), merged[1].kind);
+    assert_eq!(b, merged[3].scizzor);
+    assert!(batches(Vec::new()).is_empty());
+}
+
+#[test]
+fn geometry_cache_should_hit_until_the_primitive_or_theme_changes() {
+    use color;
+    let rect = Rect::from_corners([0.0, 0.0], [10.0, 10.0]);
+    let id = widget::Id::new(0);
+    let rectangle = |color| Primitive {
+        id: id,
+        kind: PrimitiveKind::Rectangle { color: color },
+        scizzor: rect,
+        rect: rect,
+    };
+
+    let mut cache = GeometryCache::new();
+    assert!(cache.get(&rectangle(color::RED), 0).is_none());
+    assert!(cache.insert(&rectangle(color::RED), 0, vec![1, 2, 3]));
+
+    // An unchanged primitive hits the cache.
+    assert_eq!(Some(&vec![1, 2, 3]), cache.get(&rectangle(color::RED), 0));
+
+    // A change in color misses.
+    assert!(cache.get(&rectangle(color::BLUE), 0).is_none());
+
+    // Each primitive produced by the same widget is cached separately.
+    assert!(cache.get(&rectangle(color::RED), 1).is_none());
+    assert!(cache.insert(&rectangle(color::BLUE), 1, vec![4, 5]));
+    assert_eq!(Some(&vec![1, 2, 3]), cache.get(&rectangle(color::RED), 0));
+    assert_eq!(Some(&vec![4, 5]), cache.get(&rectangle(color::BLUE), 1));
+    cache.remove(id);
+    assert_eq!(0, cache.len());
+
+    // A change in theme invalidates everything.
+    cache.insert(&rectangle(color::RED), 0, vec![1, 2, 3]);
+    cache.sync_theme(0);
+    assert_eq!(1, cache.len());
+    cache.sync_theme(1);
+    assert_eq!(0, cache.len());
+    assert!(cache.get(&rectangle(color::RED), 0).is_none());
+}
+
+#[test]
+fn draw_damaged_should_only_retessellate_the_changed_widgets() {
+    use {color, Color, Colorable, Positionable, UiBuilder, Widget};
+    let ui = &mut UiBuilder::new([200.0, 200.0]).build();
+    let (a, b, c) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    let set = |ui: &mut ::Ui, a_xy: Point, b_color: Color| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([40.0, 40.0]).xy(a_xy).set(a, ui);
+        widget::Rectangle::fill([40.0, 40.0]).x_y(50.0, 50.0).color(b_color).set(b, ui);
+        widget::Rectangle::fill([40.0, 40.0]).x_y(50.0, -50.0).set(c, ui);
+    };
+
+    let mut damage = DamageTracker::new();
+    let mut cache = GeometryCache::new();
+    let mut frame = |ui: &mut ::Ui| {
+        let mut tessellated = Vec::new();
+        let mut drawn = Vec::new();
+        let area = damage.update(ui.draw());
+        if let Some(area) = area {
+            draw_damaged(ui.draw(), area, &mut cache,
+                         |p| tessellated.push(p.id),
+                         |p, _| drawn.push(p.id));
+        }
+        (area, tessellated, drawn)
+    };
+
+    // The first frame is damaged and tessellated entirely.
+    set(ui, [-50.0, 50.0], color::RED);
+    let (area, tessellated, _) = frame(ui);
+    assert_eq!(Some(Rect::from_xy_dim([0.0, 0.0], [200.0, 200.0])), area);
+    assert!(tessellated.contains(&a) && tessellated.contains(&b) && tessellated.contains(&c));
+
+    // An unchanged frame is not damaged at all.
+    set(ui, [-50.0, 50.0], color::RED);
+    assert_eq!(None, frame(ui).0);
+
+    // Recoloring `b` only damages `b`, so only `b` is re-tessellated, and only `b` along with the
+    // window's border and background rectangles beneath it are redrawn.
+    set(ui, [-50.0, 50.0], color::BLUE);
+    let (area, tessellated, drawn) = frame(ui);
+    assert_eq!(Some(Rect::from_xy_dim([50.0, 50.0], [40.0, 40.0])), area);
+    assert_eq!(vec![b], tessellated);
+    assert_eq!(3, drawn.len());
+    assert!(drawn.contains(&b) && !drawn.contains(&a) && !drawn.contains(&c));
+
+    // Moving `a` damages both its old and new positions.
+    set(ui, [-50.0, -50.0], color::BLUE);
+    let (area, tessellated, drawn) = frame(ui);
+    assert_eq!(Some(Rect::from_corners([-70.0, -70.0], [-30.0, 70.0])), area);
+    assert_eq!(vec![a], tessellated);
+    assert!(!drawn.contains(&b) && !drawn.contains(&c));
+}
+
+#[test]
+fn stats_should_count_triangles_and_batched_draw_calls() {
+    use {Sizeable, UiBuilder};
+    let ui = &mut UiBuilder::new([100.0, 100.0]).build();
+    let (rectangle, triangle, line, crop, cropped) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).set(rectangle, ui);
+        widget::Polygon::abs_fill(vec![[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]]).set(triangle, ui);
+        widget::Line::abs([0.0, 0.0], [10.0, 10.0]).set(line, ui);
+        // The children of `crop` have a different scizzor, requiring a second draw call.
+        widget::Rectangle::fill([20.0, 20.0]).crop_kids().set(crop, ui);
+        widget::Rectangle::fill([10.0, 10.0]).w_h(30.0, 30.0).parent(crop).set(cropped, ui);
+    }
+    let expected = RenderStats { triangles: 2 + 1 + 6 + 2 + 2, draw_calls: 2, glyphs: 0 };
+    assert_eq!(expected, stats(ui.draw()));
+}
+
+#[test]
+fn images_sharing_an_atlas_page_should_be_batched_together() {
+    use image::Atlas;
+    use {Positionable, UiBuilder};
+    let ui = &mut UiBuilder::new([100.0, 100.0]).build();
+    let (page, a, b, c) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    let mut atlas = Atlas::new([64, 64]);
+    let (region_a, region_b) = (atlas.allocate(16, 16), atlas.allocate(8, 8));
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Image::new().atlas_region(page, &region_a).x(-20.0).set(a, ui);
+        widget::Image::new().atlas_region(page, &region_b).x(20.0).set(b, ui);
+    }
+    let image_kinds = |ui: &::Ui| -> Vec<BatchKind> {
+        let mut primitives = ui.draw_primitives();
+        let mut kinds = Vec::new();
+        while let Some(primitive) = primitives.next() {
+            let kind = primitive.batch_kind();
+            if let BatchKind::Image(_) = kind {
+                kinds.push(kind);
+            }
+        }
+        kinds
+    };
+    assert_eq!(vec![BatchKind::Image(page), BatchKind::Image(page)], image_kinds(ui));
+    assert_eq!(1, stats(ui.draw()).draw_calls);
+
+    // An `Image` keyed by its own id requires a draw call of its own.
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Image::new().atlas_region(page, &region_a).x(-20.0).set(a, ui);
+        widget::Image::new().atlas_region(page, &region_b).x(20.0).set(b, ui);
+        widget::Image::new().set(c, ui);
+    }
+    assert_eq!(BatchKind::Image(c), image_kinds(ui)[2]);
+    assert_eq!(2, stats(ui.draw()).draw_calls);
+}
+
+#[test]
+fn primitives_should_carry_the_scale_factor_of_the_ui() {
+    use UiBuilder;
+    let ui = &mut UiBuilder::new([100.0, 100.0]).scale_factor(2.0).build();
+    ui.set_widgets();
+    assert_eq!(2.0, ui.draw().scale_factor());
+    let owned = ui.draw().owned();
+    assert_eq!(2.0, owned.walk().scale_factor());
+}
+
+#[test]
+fn rounded_rect_points_should_tessellate_each_corner_arc() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let points = rounded_rect_points(rect, 10.0);
+    assert_eq!(4 * (CORNER_RESOLUTION + 1) + 1, points.len());
+    assert_eq!(points[0], points[points.len() - 1]);
+    assert_eq!([100.0, 40.0], points[0]);
+    for p in &points {
+        assert!(p[0] >= 0.0 && p[0] <= 100.0 && p[1] >= 0.0 && p[1] <= 50.0);
+    }
+
+    // A zero radius produces a plain rectangle.
+    let points = rounded_rect_points(rect, 0.0);
+    assert_eq!(vec![[100.0, 50.0], [0.0, 50.0], [0.0, 0.0], [100.0, 0.0], [100.0, 50.0]], points);
+}
+
+#[test]
+fn rounded_rect_radius_should_clamp_to_a_circle_or_capsule() {
+    let square = Rect::from_corners([-5.0, -5.0], [5.0, 5.0]);
+    assert_eq!(5.0, clamp_corner_radius(square, 100.0));
+    assert_eq!(0.0, clamp_corner_radius(square, -1.0));
+    for p in &rounded_rect_points(square, 100.0) {
+        let distance = (p[0].powi(2) + p[1].powi(2)).sqrt();
+        assert!((distance - 5.0).abs() < 1e-9);
+    }
+
+    // A wide rectangle becomes a capsule whose height is the diameter of its ends.
+    let wide = Rect::from_corners([0.0, 0.0], [40.0, 10.0]);
+    let points = rounded_rect_points(wide, 100.0);
+    assert_eq!(4 * (CORNER_RESOLUTION + 1) + 1, points.len());
+    let max_y = points.iter().map(|p| p[1]).fold(std::f64::MIN, f64::max);
+    let min_y = points.iter().map(|p| p[1]).fold(std::f64::MAX, f64::min);
+    assert!((max_y - min_y - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn border_rects_should_line_the_inside_edges_without_overlap() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let rects = border_rects(rect, 5.0);
+    assert_eq!(Rect::from_corners([0.0, 45.0], [100.0, 50.0]), rects[0]);
+    assert_eq!(Rect::from_corners([0.0, 0.0], [100.0, 5.0]), rects[1]);
+    assert_eq!(Rect::from_corners([0.0, 5.0], [5.0, 45.0]), rects[2]);
+    assert_eq!(Rect::from_corners([95.0, 5.0], [100.0, 45.0]), rects[3]);
+
+    // An oversized thickness fills the rect with the top and bottom edges alone.
+    let rects = border_rects(rect, 1000.0);
+    assert_eq!(Rect::from_corners([0.0, 25.0], [100.0, 50.0]), rects[0]);
+    assert_eq!(Rect::from_corners([0.0, 0.0], [100.0, 25.0]), rects[1]);
+    assert_eq!(0.0, rects[2].h());
+}
+
+#[test]
+fn shadow_rect_should_expand_by_the_clamped_blur() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let shadow = shadow_rect(rect, 8.0, [2.0, -4.0]);
+    assert_eq!(Rect::from_corners([-6.0, -12.0], [110.0, 54.0]), shadow);
+
+    // Very large blurs are clamped to avoid huge overdraw.
+    let shadow = shadow_rect(rect, 1_000.0, [0.0, 0.0]);
+    assert_eq!(rect.pad(-MAX_SHADOW_BLUR), shadow);
+    assert_eq!(rect, shadow_rect(rect, -1.0, [0.0, 0.0]));
+
+    // The triangles cover exactly the shadow bounds, opaque only within the shifted `rect`.
+    let triangles = shadow_triangles(rect, 8.0, [2.0, -4.0]);
+    assert_eq!(18, triangles.len());
+    let shadow = shadow_rect(rect, 8.0, [2.0, -4.0]);
+    let inner = rect.shift([2.0, -4.0]);
+    for triangle in &triangles {
+        for &(p, opacity) in triangle {
+            assert!(p[0] >= shadow.left() && p[0] <= shadow.right());
+            assert!(p[1] >= shadow.bottom() && p[1] <= shadow.top());
+            let is_inner = p[0] >= inner.left() && p[0] <= inner.right()
+                && p[1] >= inner.bottom() && p[1] <= inner.top();
+            assert_eq!(if is_inner { 1.0 } else { 0.0 }, opacity);
+        }
+    }
+}
+
+#[test]
+fn tessellate_path_should_triangulate_lines_and_curves() {
+    fn area(triangles: &[[Point; 3]]) -> Scalar {
+        triangles.iter()
+            .map(|t| ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1])
+                      - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1])).abs() / 2.0)
+            .fold(0.0, |total, area| total + area)
+    }
+
+    let triangle = [
+        PathSegment::MoveTo([0.0, 0.0]),
+        PathSegment::LineTo([10.0, 0.0]),
+        PathSegment::LineTo([0.0, 10.0]),
+    ];
+    let triangles = tessellate_path(&triangle, FillRule::NonZero);
+    assert_eq!(1, triangles.len());
+    assert_eq!(50.0, area(&triangles));
+
+    // The curve is flattened into lines whose vertices lie at `CURVE_RESOLUTION / 2 + 1` distinct
+    // heights, producing a trapezoid for each band between them but the topmost, a triangle.
+    let curve = [
+        PathSegment::MoveTo([0.0, 0.0]),
+        PathSegment::QuadraticTo { ctrl: [5.0, 10.0], to: [10.0, 0.0] },
+    ];
+    let triangles = tessellate_path(&curve, FillRule::NonZero);
+    assert_eq!(CURVE_RESOLUTION - 1, triangles.len());
+    assert_eq!(CURVE_RESOLUTION + 1, flatten_path(&curve)[0].len());
+}
+
+#[test]
+fn tessellate_path_should_follow_the_fill_rule_for_overlapping_contours() {
+    fn area(triangles: &[[Point; 3]]) -> Scalar {
+        triangles.iter()
+            .map(|t| ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1])
+                      - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1])).abs() / 2.0)
+            .fold(0.0, |total, area| total + area)
+    }
+    fn square(l: Scalar, b: Scalar, size: Scalar, clockwise: bool) -> Vec<PathSegment> {
+        let (r, t) = (l + size, b + size);
+        let mut corners = vec![[l, b], [r, b], [r, t], [l, t]];
+        if clockwise {
+            corners.reverse();
+        }
+        let mut segments = vec![PathSegment::MoveTo(corners[0])];
+        segments.extend(corners[1..].iter().map(|&p| PathSegment::LineTo(p)));
+        segments
+    }
+
+    // Overlapping squares travelling in the same direction fill their union.
+    let mut overlapping = square(0.0, 0.0, 10.0, false);
+    overlapping.extend(square(5.0, 5.0, 10.0, false));
+    assert_eq!(175.0, area(&tessellate_path(&overlapping, FillRule::NonZero)));
+    assert_eq!(150.0, area(&tessellate_path(&overlapping, FillRule::EvenOdd)));
+
+    // An inner square travelling in the opposite direction cuts a hole.
+    let mut hole = square(0.0, 0.0, 10.0, false);
+    hole.extend(square(2.0, 2.0, 6.0, true));
+    assert_eq!(64.0, area(&tessellate_path(&hole, FillRule::NonZero)));
+
+    // A self-intersecting bow-tie is filled on both sides of the crossing.
+    let bow_tie = [
+        PathSegment::MoveTo([0.0, 0.0]),
+        PathSegment::LineTo([10.0, 10.0]),
+        PathSegment::LineTo([10.0, 0.0]),
+        PathSegment::LineTo([0.0, 10.0]),
+    ];
+    assert_eq!(50.0, area(&tessellate_path(&bow_tie, FillRule::NonZero)));
+}
+
+#[test]
+fn tessellate_lines_should_cover_the_line_with_its_thickness_and_feathered_edges() {
+    let triangles = tessellate_lines(&[[0.0, 0.0], [10.0, 0.0]], 4.0);
+    assert_eq!(6, triangles.len());
+    let vertices: Vec<_> = triangles.iter().flat_map(|t| t.iter().cloned()).collect();
+    for &(p, opacity) in &vertices {
+        assert!(p[0] == 0.0 || p[0] == 10.0);
+        let expected = if opacity == 1.0 { 2.0 } else { 2.0 + LINE_FEATHER };
+        assert_eq!(expected, p[1].abs());
+    }
+    // Zero-length segments produce no geometry.
+    assert!(tessellate_lines(&[[5.0, 5.0], [5.0, 5.0]], 4.0).is_empty());
+}
+
+#[test]
+fn tessellate_lines_should_join_corners_without_gaps() {
+    fn is_covered(triangles: &[[(Point, f32); 3]], p: Point) -> bool {
+        triangles.iter().filter(|t| t.iter().all(|&(_, opacity)| opacity == 1.0)).any(|t| {
+            let sign = |a: Point, b: Point| {
+                (p[0] - b[0]) * (a[1] - b[1]) - (a[0] - b[0]) * (p[1] - b[1])
+            };
+            let (a, b, c) = (t[0].0, t[1].0, t[2].0);
+            let (d1, d2, d3) = (sign(a, b), sign(b, c), sign(c, a));
+            !((d1 < 0.0 || d2 < 0.0 || d3 < 0.0) && (d1 > 0.0 || d2 > 0.0 || d3 > 0.0))
+        })
+    }
+
+    // A right angle is mitered, covering the outer corner.
+    let right_angle = tessellate_lines(&[[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]], 4.0);
+    assert_eq!(6 + 6 + 6, right_angle.len());
+    assert!(is_covered(&right_angle, [11.9, -1.5]));
+    assert!(!is_covered(&right_angle, [12.1, -1.9]));
+
+    // A sharp corner exceeds the miter limit and is beveled instead.
+    let sharp = tessellate_lines(&[[0.0, 0.0], [10.0, 0.0], [0.0, 1.0]], 4.0);
+    assert_eq!(6 + 6 + 3, sharp.len());
+    assert!(is_covered(&sharp, [10.02, -1.0]));
+    assert!(!is_covered(&sharp, [13.0, -1.0]));
+}
+
+#[test]
+fn indexed_vertices_should_share_the_diagonal_of_each_quad() {
+    let mut buffer = IndexedVertices::new();
+    buffer.push_quad([0, 1, 2, 3]);
+    buffer.push_quad([4, 5, 6, 7]);
+    buffer.push_triangle([8, 9, 10]);
+    assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10], buffer.vertices());
+    assert_eq!(&[0, 1, 2, 0, 2, 3,
+                 4, 5, 6, 4, 6, 7,
+                 8, 9, 10], buffer.indices());
+    let range = buffer.finish_batch();
+    assert_eq!(0..11, range.vertices);
+    assert_eq!(0..15, range.indices);
+}
+
+#[test]
+fn indexed_vertices_should_restart_indices_for_each_batch() {
+    let mut buffer = IndexedVertices::new();
+    buffer.push_quad([0, 1, 2, 3]);
+    let first = buffer.finish_batch();
+    buffer.push_quad([4, 5, 6, 7]);
+    buffer.push_quad([8, 9, 10, 11]);
+    let second = buffer.finish_batch();
+    assert_eq!(IndexedRange { vertices: 0..4, indices: 0..6 }, first);
+    assert_eq!(IndexedRange { vertices: 4..12, indices: 6..18 }, second);
+    assert_eq!(&[0, 1, 2, 0, 2, 3,
+                 0, 1, 2, 0, 2, 3,
+                 4, 5, 6, 4, 6, 7], buffer.indices());
+
+    // Each batch's indices address only its own vertices.
+    let vertices = &buffer.vertices()[second.vertices.clone()];
+    let quads: Vec<u32> = buffer.indices()[second.indices].iter()
+        .map(|&i| vertices[i as usize])
+        .collect();
+    assert_eq!(vec![4, 5, 6, 4, 6, 7, 8, 9, 10, 8, 10, 11], quads);
+
+    // An empty batch produces empty ranges.
+    assert_eq!(IndexedRange { vertices: 12..12, indices: 18..18 }, buffer.finish_batch());
+}
+
+#[test]
+fn layout_texts_should_position_glyphs_identically_in_parallel_and_serially() {
+    use {Positionable, Sizeable, UiBuilder};
+    let ui = &mut UiBuilder::new([400.0, 400.0]).build();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    let ids: Vec<widget::Id> = {
+        let mut generator = ui.widget_id_generator();
+        (0..6).map(|_| generator.next()).collect()
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        for (i, &id) in ids.iter().enumerate() {
+            let text = widget::Text::new("The quick brown fox jumps over the lazy dog")
+                .w(150.0)
+                .x_y(-100.0 + (i % 2) as Scalar * 200.0, 150.0 - (i / 2) as Scalar * 120.0);
+            match i % 3 {
+                0 => text.set(id, ui),
+                1 => text.justify().set(id, ui),
+                _ => text.monospace(9.0).set(id, ui),
+            }
+        }
+    }
+
+    let dpi_factor = 1.5;
+    let pool = TextLayoutPool::new(4);
+    let serial = layout_texts(ui.draw(), dpi_factor, &TextLayoutPool::new(1));
+    let parallel = layout_texts(ui.draw(), dpi_factor, &pool);
+    // The same threads are reused by later calls.
+    let reused = layout_texts(ui.draw(), dpi_factor, &pool);
+    assert_eq!(4, pool.num_threads());
+    assert_eq!(ids.len(), serial.len());
+    assert_eq!(ids.len(), parallel.len());
+    assert_eq!(ids.len(), reused.len());
+
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        let expected = match primitive.kind {
+            PrimitiveKind::Text { text, .. } => text.positioned_glyphs(dpi_factor),
+            _ => continue,
+        };
+        for layouts in &[&serial, &parallel, &reused] {
+            let glyphs = layouts.get(primitive.id).unwrap();
+            assert_eq!(expected.len(), glyphs.len());
+            for (a, b) in expected.iter().zip(glyphs) {
+                assert_eq!(a.id(), b.id());
+                assert_eq!(a.position(), b.position());
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! A stable, comparable description of the layout of all widgets within a `Ui`, for use within
+//! golden-file and inline snapshot tests.
+//!
+//! A `LayoutSnapshot` is produced by `Ui::dump_layout`. When the `serialize` feature is enabled,
+//! all types within this module implement `serde::Serialize`.
+
+use {Depth, Scalar};
+use std;
+use widget;
+
+
+/// The number of decimal places to which all `Scalar`s within a `LayoutSnapshot` are rounded.
+///
+/// Rounding ensures that snapshots remain stable in the face of floating point noise from the
+/// layout arithmetic.
+pub const PRECISION: i32 = 2;
+
+/// The layout of every widget instantiated during the most recent update, sorted by index.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature="serialize", derive(Serialize))]
+pub struct LayoutSnapshot {
+    /// A description of each widget, ordered by index.
+    pub widgets: Vec<WidgetLayout>,
+}
+
+/// The layout of a single widget within a `LayoutSnapshot`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature="serialize", derive(Serialize))]
+pub struct WidgetLayout {
+    /// The index of the widget within the widget graph.
+    pub index: usize,
+    /// The name of the widget's type if it is one of conrod's own widgets.
+    ///
+    /// Widgets whose kind can't be named (such as user-defined widgets) are `None`.
+    pub kind: Option<&'static str>,
+    /// The index of the widget's depth parent, if it has one.
+    pub parent: Option<usize>,
+    /// The `[left, bottom, right, top]` edges of the widget's `Rect`, rounded to `PRECISION`.
+    pub rect: [Scalar; 4],
+    /// The depth of the widget relative to its siblings, rounded to `PRECISION`.
+    pub depth: Depth,
+    /// Whether or not the widget is floating.
+    pub floating: bool,
+    /// Whether or not the widget crops its children to its kid area.
+    pub crop_kids: bool,
+    /// Whether or not the widget consumes the mouse events that occur over it.
+    pub consumes_events: bool,
+    /// The text displayed by the widget, if there is any.
+    ///
+    /// See `WidgetInfo::text` for the widgets to which this applies.
+    pub text: Option<String>,
+}
+
+
+impl LayoutSnapshot {
+
+    /// A snapshot of the given widgets, sorted by index.
+    pub fn new(mut widgets: Vec<WidgetLayout>) -> Self {
+        widgets.sort_by_key(|widget| widget.index);
+        LayoutSnapshot { widgets: widgets }
+    }
+
+}
+
+/// Each widget is written on its own line, making it simple to compare against an inline expected
+/// string and to read the differences when the comparison fails.
+impl std::fmt::Display for LayoutSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        for widget in &self.widgets {
+            try!(writeln!(f, "{}", widget));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for WidgetLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        try!(write!(f, "{} {}", self.index, self.kind.unwrap_or("?")));
+        if let Some(parent) = self.parent {
+            try!(write!(f, " parent={}", parent));
+        }
+        let r = self.rect;
+        try!(write!(f, " rect=[{}, {}, {}, {}] depth={}", r[0], r[1], r[2], r[3], self.depth));
+        if self.floating {
+            try!(write!(f, " floating"));
+        }
+        if self.crop_kids {
+            try!(write!(f, " crop_kids"));
+        }
+        if !self.consumes_events {
+            try!(write!(f, " passthrough"));
+        }
+        if let Some(ref text) = self.text {
+            try!(write!(f, " text={:?}", text));
+        }
+        Ok(())
+    }
+}
+
+
+/// Round the given value to `PRECISION` decimal places.
+///
+/// Negative zero is normalised to zero so that it is displayed consistently.
+pub fn round(value: Scalar) -> Scalar {
+    let scale = (10.0 as Scalar).powi(PRECISION);
+    let rounded = (value * scale).round() / scale;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+/// The name of the widget whose `Widget::State` has the given `TypeId`, if it is one of conrod's
+/// own widgets.
+///
+/// Widgets defined outside of conrod can't be named and are `None`.
+pub fn kind_name(type_id: std::any::TypeId) -> Option<&'static str> {
+    macro_rules! kinds {
+        ($($State:ty => $name:expr,)*) => {
+            $(
+                if type_id == std::any::TypeId::of::<$State>() {
+                    return Some($name);
+                }
+            )*
+        };
+    }
+
+    kinds! {
+        widget::autocomplete::State => "Autocomplete",
+        widget::bordered_rectangle::State => "BorderedRectangle",
+        widget::breadcrumbs::State => "Breadcrumbs",
+        widget::button::State => "Button",
+        widget::canvas::State => "Canvas",
+        widget::chart::State => "Chart",
+        widget::collapsible::State => "Collapsible",
+        widget::color_picker::State => "ColorPicker",
+        widget::context_menu::State => "ContextMenu",
+        widget::drop_down_list::State => "DropDownList",
+        widget::envelope_editor::State => "EnvelopeEditor",
+        widget::file_navigator::State => "FileNavigator",
+        widget::file_navigator::directory_view::State => "DirectoryView",
+        widget::flow::State => "Flow",
+        widget::image::State => "Image",
+        widget::line::State => "Line",
+        widget::list::State => "List",
+        widget::list_select::State => "ListSelect",
+        widget::matrix::State => "Matrix",
+        widget::menu_bar::State => "MenuBar",
+        widget::modal::State => "Modal",
+        widget::number_dialer::State => "NumberDialer",
+        widget::numeric_field::State => "NumericField",
+        widget::oval::State => "Oval",
+        widget::plot_path::State => "PlotPath",
+        widget::point_path::State => "PointPath",
+        widget::polygon::State => "Polygon",
+        widget::progress_bar::State => "ProgressBar",
+        widget::radio_group::State => "RadioGroup",
+        widget::range_slider::State => "RangeSlider",
+        widget::rectangle::State => "Rectangle",
+        widget::rich_text::State => "RichText",
+        widget::scrollbar::State => "Scrollbar",
+        widget::slider::State => "Slider",
+        widget::spinner::State => "Spinner",
+        widget::split_pane::State => "SplitPane",
+        widget::table::State => "Table",
+        widget::tabs::State => "Tabs",
+        widget::text::State => "Text",
+        widget::text_box::State => "TextBox",
+        widget::text_edit::State => "TextEdit",
+        widget::title_bar::State => "TitleBar",
+        widget::toasts::State => "Toasts",
+        widget::toggle::State => "Toggle",
+        widget::tooltip::State => "Tooltip",
+        widget::tree_view::State => "TreeView",
+        widget::window::State => "Window",
+        widget::xy_pad::State => "XYPad",
+    }
+
+    None
+}
+
+
+#[test]
+fn round_should_remove_floating_point_noise() {
+    assert_eq!(0.3, round(0.1 + 0.2));
+    assert_eq!(-12.35, round(-12.345_01));
+    assert_eq!(100.0, round(99.999_999));
+    assert_eq!("0", format!("{}", round(-0.000_1)));
+}
+
+#[test]
+fn kind_name_should_name_conrod_widgets_only() {
+    assert_eq!(Some("Button"), kind_name(std::any::TypeId::of::<widget::button::State>()));
+    assert_eq!(Some("Rectangle"), kind_name(std::any::TypeId::of::<widget::rectangle::State>()));
+    assert_eq!(None, kind_name(std::any::TypeId::of::<()>()));
+}
+
+#[test]
+fn kind_name_should_name_every_widget_module() {
+    use std::io::Read;
+    use std::path::Path;
+
+    // Collects the name of each module within the `dir` that declares a non-generic `State`.
+    fn widget_modules(dir: &Path, modules: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                widget_modules(&path, modules);
+                continue;
+            }
+            let mut source = String::new();
+            std::fs::File::open(&path).unwrap().read_to_string(&mut source).unwrap();
+            if !source.contains("\npub struct State {") {
+                continue;
+            }
+            let name = match path.file_stem().unwrap().to_str().unwrap() {
+                "mod" => path.parent().unwrap().file_name().unwrap().to_str().unwrap(),
+                stem => stem,
+            };
+            modules.push(name.to_string());
+        }
+    }
+
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut modules = Vec::new();
+    widget_modules(&src.join("widget"), &mut modules);
+    let mut source = String::new();
+    std::fs::File::open(src.join("snapshot.rs")).unwrap().read_to_string(&mut source).unwrap();
+    assert!(modules.len() > 40);
+    for module in &modules {
+        let entry = format!("::{}::State => ", module);
+        assert!(source.contains(&entry), "`kind_name` has no entry for `widget::{}`", module);
+    }
+}
@@ -3,6 +3,7 @@ use input::{self, Key, MouseButton};
 use input::Button::Keyboard;
 use input::Button::Mouse;
 use position::Scalar;
+use widget;
 
 
 // Pushes an event onto the given global input with a default drag threshold.
@@ -64,3 +65,95 @@ fn events_should_return_all_inputs_in_order() {
     assert_eq!(evt1, *results[0]);
     assert_eq!(evt2, *results[1]);
 }
+
+#[test]
+fn dropping_a_compatible_payload_should_deliver_it_to_the_target() {
+    let mut input = input::Global::new();
+    let source = widget::Id::new(1);
+    let target = widget::Id::new(2);
+
+    input.begin_drag(source, Box::new(42u32));
+    input.current.widget_under_mouse = Some(target);
+    assert!(input.is_drag_payload_over::<u32>(target));
+    assert!(!input.is_drag_payload_over::<String>(target));
+    assert!(!input.is_drag_payload_over::<u32>(source));
+
+    input.drop_drag_payload(Some(target));
+    assert!(input.drag_payload().is_none());
+    assert_eq!(Some(Box::new(42u32)), input.take_dropped_payload::<u32>(target));
+
+    // Nothing should be returned to the source once the target has claimed the payload.
+    input.clear_events_and_update_start_state();
+    assert!(input.take_returned_payload(source).is_none());
+}
+
+#[test]
+fn rejected_payload_should_be_returned_to_the_source() {
+    let mut input = input::Global::new();
+    let source = widget::Id::new(1);
+    let target = widget::Id::new(2);
+
+    input.begin_drag(source, Box::new(42u32));
+    input.current.widget_under_mouse = Some(target);
+    input.drop_drag_payload(Some(target));
+
+    // The target only accepts `String`s, so the payload should remain unclaimed.
+    assert!(input.take_dropped_payload::<String>(target).is_none());
+    assert!(input.take_returned_payload(source).is_none());
+
+    input.clear_events_and_update_start_state();
+    let returned = input.take_returned_payload(source).expect("payload was not returned");
+    assert_eq!(Some(&42u32), returned.downcast_ref::<u32>());
+}
+
+#[test]
+fn payload_dropped_over_no_widget_should_be_returned_to_the_source() {
+    let mut input = input::Global::new();
+    let source = widget::Id::new(1);
+
+    input.begin_drag(source, Box::new("payload"));
+    input.drop_drag_payload(None);
+    input.clear_events_and_update_start_state();
+    assert!(input.take_returned_payload(widget::Id::new(2)).is_none());
+    assert!(input.take_returned_payload(source).is_some());
+
+    // An unclaimed returned payload should be discarded after one more update.
+    input.begin_drag(source, Box::new("payload"));
+    input.drop_drag_payload(None);
+    input.clear_events_and_update_start_state();
+    input.clear_events_and_update_start_state();
+    assert!(input.take_returned_payload(source).is_none());
+}
+
+#[test]
+fn latencies_should_measure_events_until_handled_or_the_end_of_the_update() {
+    use std::time::Duration;
+    let mut input = input::Global::new();
+    let widget = widget::Id::new(3);
+    let press = |key| event::Press {
+        button: event::Button::Keyboard(key),
+        modifiers: input::keyboard::NO_MODIFIER,
+    };
+
+    // Events are only timestamped once enabled.
+    push_event(&mut input, event::Ui::Press(Some(widget), press(Key::A)).into());
+    input.timestamp_events = true;
+    push_event(&mut input, event::Ui::Press(Some(widget), press(Key::B)).into());
+    push_event(&mut input, event::Ui::Press(None, press(Key::C)).into());
+    input.handle_events_for(widget);
+    input.clear_events_and_update_start_state();
+
+    let latencies = input.latencies();
+    assert_eq!(2, latencies.len());
+    assert_eq!(event::Event::from(event::Ui::Press(Some(widget), press(Key::B))),
+               latencies[0].event);
+    assert!(latencies[0].was_handled);
+    assert!(!latencies[1].was_handled);
+    for latency in latencies {
+        assert!(latency.latency < Duration::from_secs(1));
+    }
+
+    // The latencies are replaced by those of the following update.
+    input.clear_events_and_update_start_state();
+    assert!(input.latencies().is_empty());
+}
@@ -1,12 +1,16 @@
 use {
     Color,
+    FrameStats,
     Labelable,
+    MouseCursor,
     Positionable,
     Colorable,
     Sizeable,
+    Theme,
     Widget,
     Ui,
-    UiBuilder
+    UiBuilder,
+    WidgetInfo,
 };
 use event::{self, Input, Motion};
 use input::{self, Button, Key, MouseButton};
@@ -68,6 +72,43 @@ fn windowless_ui() -> Ui {
     UiBuilder::new([800.0, 600.0]).build()
 }
 
+fn windowless_ui_with_font() -> Ui {
+    let mut ui = windowless_ui();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    ui
+}
+
+fn press_key(ui: &mut Ui, key: Key) {
+    ui.handle_event(Input::Press(Button::Keyboard(key)));
+    ui.handle_event(Input::Release(Button::Keyboard(key)));
+}
+
+fn press_chord(ui: &mut Ui, modifier: Key, key: Key) {
+    ui.handle_event(Input::Press(Button::Keyboard(modifier)));
+    press_key(ui, key);
+    ui.handle_event(Input::Release(Button::Keyboard(modifier)));
+}
+
+fn press_ctrl(ui: &mut Ui, key: Key) {
+    press_chord(ui, Key::LCtrl, key);
+}
+
+fn is_text_displayed(ui: &Ui, string: &str) -> bool {
+    ui.find_widget(|info| info.is::<widget::Text>() && info.text == Some(string)).is_some()
+}
+
+/// Update the `TextEdit` built by `text_edit` for the given `text`, applying any edits to it.
+fn set_text_edit<F>(ui: &mut Ui, id: widget::Id, text: &mut String, text_edit: F)
+    where F: for<'a> FnOnce(widget::TextEdit<'a>) -> widget::TextEdit<'a>,
+{
+    let ui = &mut ui.set_widgets();
+    if let Some(new_text) = text_edit(widget::TextEdit::new(text)).set(id, ui) {
+        *text = new_text;
+    }
+}
+
 
 ///// Actual tests.
 
@@ -133,3 +174,2631 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+fn set_cursor_hint_widgets(ui: &mut Ui, canvas: widget::Id, text: widget::Id, divider: widget::Id) {
+    let ui = &mut ui.set_widgets();
+    widget::Canvas::new().set(canvas, ui);
+    widget::Rectangle::fill([100.0, 100.0])
+        .top_left_of(canvas)
+        .mouse_cursor(MouseCursor::Text)
+        .set(text, ui);
+    widget::Rectangle::fill([10.0, 100.0])
+        .top_right_of(canvas)
+        .mouse_cursor(MouseCursor::ResizeHorizontal)
+        .set(divider, ui);
+}
+
+#[test]
+fn mouse_cursor_should_resolve_to_the_hint_of_the_widget_under_the_mouse() {
+    let ui = &mut windowless_ui();
+    let (canvas, text, divider) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+    set_cursor_hint_widgets(ui, canvas, text, divider);
+
+    assert_eq!(MouseCursor::Arrow, ui.mouse_cursor());
+
+    let text_xy = ui.xy_of(text).unwrap();
+    move_mouse_to_abs_coordinates(text_xy[0], text_xy[1], ui);
+    assert_eq!(MouseCursor::Text, ui.mouse_cursor());
+
+    let divider_xy = ui.xy_of(divider).unwrap();
+    move_mouse_to_abs_coordinates(divider_xy[0], divider_xy[1], ui);
+    assert_eq!(MouseCursor::ResizeHorizontal, ui.mouse_cursor());
+
+    // The canvas has no hint, so the cursor should fall back to the default.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    assert_eq!(MouseCursor::Arrow, ui.mouse_cursor());
+}
+
+#[test]
+fn mouse_cursor_should_prefer_the_hint_of_the_widget_capturing_the_mouse() {
+    let ui = &mut windowless_ui();
+    let (canvas, text, divider) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+    set_cursor_hint_widgets(ui, canvas, text, divider);
+
+    // Begin dragging the divider and move the mouse over the text widget.
+    let divider_xy = ui.xy_of(divider).unwrap();
+    move_mouse_to_abs_coordinates(divider_xy[0], divider_xy[1], ui);
+    press_mouse_button(MouseButton::Left, ui);
+    let text_xy = ui.xy_of(text).unwrap();
+    move_mouse_to_abs_coordinates(text_xy[0], text_xy[1], ui);
+
+    assert_eq!(Some(text), ui.global_input.current.widget_under_mouse);
+    assert_eq!(MouseCursor::ResizeHorizontal, ui.mouse_cursor());
+
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(MouseCursor::Text, ui.mouse_cursor());
+}
+
+#[test]
+fn scroll_config_should_scale_and_invert_high_level_scroll_events() {
+    let ui = &mut windowless_ui();
+    let canvas = ui.widget_id_generator().next();
+    widget::Canvas::new().set(canvas, &mut ui.set_widgets());
+
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    assert_eq!(Some(canvas), ui.global_input.current.widget_capturing_mouse);
+
+    ui.global_input.current.set_scroll_config([2.0, 3.0], true);
+    ui.handle_event(Input::Move(Motion::MouseScroll(10.0, 5.0)));
+    ui.handle_event(Input::Move(Motion::MouseScroll(-4.0, 1.0)));
+
+    let total = ui.global_input.events().ui().fold([0.0, 0.0], |total, event| match *event {
+        event::Ui::Scroll(Some(id), scroll) if id == canvas =>
+            [total[0] + scroll.x, total[1] + scroll.y],
+        _ => total,
+    });
+    assert_eq!([-12.0, -18.0], total);
+}
+
+#[test]
+fn set_theme_should_replace_the_active_theme() {
+    let ui = &mut UiBuilder::new([800.0, 600.0]).theme(Theme::light()).build();
+    let canvas = ui.widget_id_generator().next();
+    widget::Canvas::new().set(canvas, &mut ui.set_widgets());
+    assert!(!ui.theme_has_changed());
+    let theme_generation = ui.theme_generation();
+
+    let prev = ui.set_theme(Theme::dark());
+    assert!(ui.theme_generation() != theme_generation);
+    assert_eq!(Theme::light().background_color, prev.background_color);
+    assert_eq!(Theme::dark().background_color, ui.theme.background_color);
+    assert_eq!(Theme::dark().label_color, ui.theme.label_color);
+
+    // Widgets should see the change during the next `set_widgets` stage only.
+    {
+        let ui = &mut ui.set_widgets();
+        assert!(ui.theme_has_changed());
+        widget::Canvas::new().set(canvas, ui);
+    }
+    assert!(!ui.theme_has_changed());
+}
+
+#[test]
+fn gamma_correct_blending_should_be_reported_by_primitives_with_srgb_colors() {
+    use color;
+    use render::{PrimitiveKind, PrimitiveWalker};
+
+    fn rectangle_color(gamma_correct_blending: bool) -> Color {
+        let theme = Theme::builder().gamma_correct_blending(gamma_correct_blending).build();
+        let ui = &mut UiBuilder::new([800.0, 600.0]).theme(theme).build();
+        let rect = ui.widget_id_generator().next();
+        widget::Rectangle::fill([100.0, 100.0])
+            .color(color::rgba(0.5, 0.5, 0.5, 0.5))
+            .set(rect, &mut ui.set_widgets());
+
+        assert_eq!(gamma_correct_blending, ui.draw().gamma_correct_blending());
+        assert_eq!(gamma_correct_blending, ui.draw().owned().walk().gamma_correct_blending());
+        let mut primitives = ui.draw();
+        while let Some(primitive) = primitives.next() {
+            if let (true, PrimitiveKind::Rectangle { color }) = (primitive.id == rect, primitive.kind) {
+                return color;
+            }
+        }
+        panic!("no primitive was yielded for the rectangle");
+    }
+
+    // The colors are left for the backend to convert, so that they are converted only once.
+    let srgb = color::rgba(0.5, 0.5, 0.5, 0.5);
+    assert_eq!(srgb, rectangle_color(false));
+    assert_eq!(srgb, rectangle_color(true));
+}
+
+#[test]
+fn middle_of_should_center_nested_widgets_on_their_relatively_offset_parents() {
+    let ui = &mut windowless_ui();
+    let (outer, inner, innermost, child) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([400.0, 300.0])
+            .x_y_relative_to(ui.window, 50.0, -30.0)
+            .set(outer, ui);
+        widget::Rectangle::fill([200.0, 100.0])
+            .middle_of(outer)
+            .set(inner, ui);
+        widget::Rectangle::fill([50.0, 20.0])
+            .middle_of(inner)
+            .set(innermost, ui);
+        widget::Rectangle::fill([30.0, 10.0])
+            .parent(outer)
+            .middle()
+            .set(child, ui);
+    }
+
+    let outer_center = ui.xy_of(outer).unwrap();
+    assert_eq!([50.0, -30.0], outer_center);
+    let sizes = [(inner, 200.0, 100.0), (innermost, 50.0, 20.0), (child, 30.0, 10.0)];
+    for &(id, w, h) in sizes.iter() {
+        let rect = ui.rect_of(id).unwrap();
+        assert_eq!(outer_center, rect.xy());
+        assert_eq!([outer_center[0] - w / 2.0, outer_center[1] - h / 2.0], rect.bottom_left());
+    }
+}
+
+#[test]
+fn fraction_of_sizing_should_scale_and_clamp_the_dimensions_of_the_other_widget() {
+    let ui = &mut windowless_ui();
+    let (parent, empty, half, clamped, from_empty) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([400.0, 300.0]).set(parent, ui);
+        widget::Rectangle::fill([0.0, 0.0]).set(empty, ui);
+        widget::Rectangle::fill([0.0, 0.0])
+            .w_fraction_of(parent, 0.5)
+            .h_fraction_of(parent, 0.25)
+            .set(half, ui);
+        widget::Rectangle::fill([0.0, 0.0])
+            .w_fraction_of(parent, 2.0)
+            .h_fraction_of(parent, -1.0)
+            .set(clamped, ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .wh_fraction_of(empty, 0.5)
+            .set(from_empty, ui);
+    }
+    assert_eq!(Some([200.0, 75.0]), ui.wh_of(half));
+    assert_eq!(Some([400.0, 0.0]), ui.wh_of(clamped));
+    assert_eq!(Some([0.0, 0.0]), ui.wh_of(from_empty));
+}
+
+#[test]
+fn align_of_methods_should_align_edges_with_the_other_widget() {
+    let ui = &mut windowless_ui();
+    let (reference, left, middle_x, right, top, middle_y, bottom) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 100.0]).x_y(100.0, 50.0).set(reference, ui);
+        widget::Rectangle::fill([40.0, 20.0]).align_left_of(reference).y(-200.0).set(left, ui);
+        widget::Rectangle::fill([40.0, 20.0])
+            .align_middle_x_of(reference)
+            .y(-200.0)
+            .set(middle_x, ui);
+        widget::Rectangle::fill([40.0, 20.0]).align_right_of(reference).y(-200.0).set(right, ui);
+        widget::Rectangle::fill([40.0, 20.0]).align_top_of(reference).x(-300.0).set(top, ui);
+        widget::Rectangle::fill([40.0, 20.0])
+            .align_middle_y_of(reference)
+            .x(-300.0)
+            .set(middle_y, ui);
+        widget::Rectangle::fill([40.0, 20.0]).align_bottom_of(reference).x(-300.0).set(bottom, ui);
+    }
+    let rect = |id| ui.rect_of(id).unwrap();
+    assert_eq!(0.0, rect(left).left());
+    assert_eq!(100.0, rect(middle_x).x());
+    assert_eq!(200.0, rect(right).right());
+    assert_eq!(100.0, rect(top).top());
+    assert_eq!(50.0, rect(middle_y).y());
+    assert_eq!(0.0, rect(bottom).bottom());
+}
+
+#[test]
+fn aligning_to_a_sibling_set_later_should_resolve_on_the_following_update() {
+    let ui = &mut windowless_ui();
+    let (aligned, sibling) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    fn set_widgets(ui: &mut Ui, aligned: widget::Id, sibling: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([40.0, 20.0]).align_left_of(sibling).y(-200.0).set(aligned, ui);
+        widget::Rectangle::fill([200.0, 100.0]).x_y(100.0, 50.0).set(sibling, ui);
+    }
+
+    // The sibling's `Rect` is unknown during the first update, so the widget is not yet aligned.
+    set_widgets(ui, aligned, sibling);
+    assert_eq!(0.0, ui.rect_of(aligned).unwrap().x());
+
+    // The sibling's `Rect` from the previous update is used.
+    set_widgets(ui, aligned, sibling);
+    assert_eq!(0.0, ui.rect_of(aligned).unwrap().left());
+}
+
+#[test]
+fn flow_should_position_children_in_wrapping_rows_from_its_top_left() {
+    let ui = &mut windowless_ui();
+    let flow = ui.widget_id_generator().next();
+    let dims = [[100.0, 50.0], [100.0, 50.0], [100.0, 30.0], [400.0, 20.0]];
+    let mut child_ids = Vec::new();
+    {
+        let ui = &mut ui.set_widgets();
+        let mut items = widget::Flow::new(&dims).w_h(300.0, 200.0).gap(10.0).set(flow, ui);
+        while let Some(item) = items.next() {
+            child_ids.push(item.widget_id);
+            item.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+        }
+    }
+
+    let top_left = ui.rect_of(flow).unwrap().top_left();
+    let expected = [[0.0, 0.0], [110.0, 0.0], [0.0, 60.0], [0.0, 100.0]];
+    assert_eq!(expected.len(), child_ids.len());
+    for (&id, offset) in child_ids.iter().zip(expected.iter()) {
+        let rect = ui.rect_of(id).unwrap();
+        assert_eq!([top_left[0] + offset[0], top_left[1] - offset[1]], rect.top_left());
+    }
+}
+
+#[test]
+fn at_mouse_should_place_the_top_left_corner_of_the_widget_at_the_mouse() {
+    let ui = &mut windowless_ui();
+    let popup = ui.widget_id_generator().next();
+    move_mouse_to_abs_coordinates(-100.0, 50.0, ui);
+    let input = ui.global_input.current.clone();
+    widget::Rectangle::fill([100.0, 80.0]).at_mouse(&input).set(popup, &mut ui.set_widgets());
+    assert_eq!([-100.0, 50.0], ui.rect_of(popup).unwrap().top_left());
+}
+
+#[test]
+fn at_mouse_within_window_should_flip_popups_near_the_window_corner() {
+    let ui = &mut windowless_ui();
+    let popup = ui.widget_id_generator().next();
+
+    // Near the bottom right corner of the 800x600 window, the popup flips up and to the left.
+    move_mouse_to_abs_coordinates(390.0, -290.0, ui);
+    let input = ui.global_input.current.clone();
+    widget::Rectangle::fill([100.0, 80.0])
+        .at_mouse_within_window(&input)
+        .set(popup, &mut ui.set_widgets());
+    let rect = ui.rect_of(popup).unwrap();
+    assert_eq!([390.0, -290.0], rect.bottom_right());
+    let window = ui.rect_of(ui.window).unwrap();
+    assert_eq!(Some(rect), window.overlap(rect));
+
+    // The popup is placed normally when there is enough space.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    let input = ui.global_input.current.clone();
+    widget::Rectangle::fill([100.0, 80.0])
+        .at_mouse_within_window(&input)
+        .set(popup, &mut ui.set_widgets());
+    assert_eq!([0.0, 0.0], ui.rect_of(popup).unwrap().top_left());
+}
+
+#[test]
+fn snap_to_grid_should_round_the_resolved_widget_position() {
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).x_y(23.0, -37.0).snap_to_grid(25.0).set(a, ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .right_from(a, 4.0)
+            .snap_to_grid(25.0)
+            .set(b, ui);
+    }
+    assert_eq!(Some([25.0, -25.0]), ui.xy_of(a));
+    // `b` resolves to `[39.0, -25.0]` before snapping.
+    assert_eq!(Some([50.0, -25.0]), ui.xy_of(b));
+}
+
+#[test]
+fn siblings_should_be_rendered_in_order_of_depth_then_instantiation() {
+    let ui = &mut windowless_ui();
+    let (canvas, a, b, c, d, e) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(canvas).set(a, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(canvas).depth(-1.0).set(b, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(canvas).set(c, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(canvas).depth(1.0).set(d, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(canvas).set(e, ui);
+    }
+
+    let mut order = Vec::new();
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if [a, b, c, d, e].contains(&primitive.id) {
+            order.push(primitive.id);
+        }
+    }
+    assert_eq!(vec![d, a, c, e, b], order);
+}
+
+#[test]
+fn nested_crop_kids_should_crop_descendants_to_the_intersection_of_kid_areas() {
+    use position::Rect;
+    let ui = &mut windowless_ui();
+    let (outer, inner, inner_kid, outer_kid) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 200.0]).x_y(0.0, 0.0).crop_kids().set(outer, ui);
+        widget::Rectangle::fill([200.0, 200.0])
+            .x_y(100.0, 100.0)
+            .parent(outer)
+            .crop_kids()
+            .set(inner, ui);
+        widget::Rectangle::fill([50.0, 50.0]).x_y(100.0, 100.0).parent(inner).set(inner_kid, ui);
+        widget::Rectangle::fill([50.0, 50.0]).x_y(-100.0, 0.0).parent(outer).set(outer_kid, ui);
+    }
+
+    let mut scizzors = Vec::new();
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        scizzors.push((primitive.id, primitive.scizzor));
+    }
+    let scizzor_of = |id| scizzors.iter().find(|&&(i, _)| i == id).map(|&(_, s)| s).unwrap();
+
+    let window = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    let outer_area = Rect::from_corners([-100.0, -100.0], [100.0, 100.0]);
+    assert_eq!(window, scizzor_of(outer));
+    assert_eq!(outer_area, scizzor_of(inner));
+    // The inner crop intersects with, rather than replaces, the outer crop.
+    assert_eq!(Rect::from_corners([0.0, 0.0], [100.0, 100.0]), scizzor_of(inner_kid));
+    // The inner crop no longer applies once the inner widget's descendants are done.
+    assert_eq!(outer_area, scizzor_of(outer_kid));
+}
+
+#[test]
+fn tabs_should_be_selectable_by_click_and_wrap_with_ctrl_tab() {
+    let ui = &mut windowless_ui();
+    let (tabs, a, b, c) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    let tab_list = [(a, "A"), (b, "B"), (c, "C")];
+    let set_widgets = |ui: &mut Ui| {
+        widget::Tabs::new(&tab_list).w_h(300.0, 200.0).set(tabs, &mut ui.set_widgets());
+    };
+
+    // The first tab is selected by default.
+    set_widgets(ui);
+    assert!(ui.updated_widgets().contains(&a));
+
+    // Clicking the last tab within the tab bar along the top edge selects it.
+    move_mouse_to_abs_coordinates(100.0, 90.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui);
+    assert!(ui.updated_widgets().contains(&c));
+    assert!(!ui.updated_widgets().contains(&a));
+
+    // The clicked tab now captures the keyboard, so `Ctrl+Tab` wraps around to the first tab.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::Tab)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::LCtrl)));
+    set_widgets(ui);
+    assert!(ui.updated_widgets().contains(&a));
+    assert!(!ui.updated_widgets().contains(&c));
+
+    // Without `Ctrl`, `Tab` leaves the selection unchanged.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    set_widgets(ui);
+    assert!(ui.updated_widgets().contains(&a));
+}
+
+#[test]
+fn modal_should_block_clicks_to_background_widgets_until_dismissed() {
+    use widget::modal::Dismissal;
+    let ui = &mut windowless_ui();
+    let (button, modal, dialog) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    // Returns whether the background button was clicked along with any modal `Dismissal`.
+    let set_widgets = |ui: &mut Ui, maybe_modal: Option<widget::Modal>| {
+        let ui = &mut ui.set_widgets();
+        let clicked = widget::Button::new().w_h(100.0, 50.0).x_y(0.0, -200.0).set(button, ui);
+        let dismissal = maybe_modal.and_then(|modal_widget| {
+            let dismissal = modal_widget.set(modal, ui);
+            widget::Rectangle::fill([200.0, 100.0]).middle_of(modal).set(dialog, ui);
+            dismissal
+        });
+        (clicked.was_clicked(), dismissal)
+    };
+    let click_button = |ui: &mut Ui| {
+        move_mouse_to_abs_coordinates(0.0, -200.0, ui);
+        left_click_mouse(ui);
+    };
+
+    // While the modal is open, clicks over the button land on the scrim instead.
+    set_widgets(ui, Some(widget::Modal::new()));
+    click_button(ui);
+    assert_eq!((false, None), set_widgets(ui, Some(widget::Modal::new())));
+
+    // The modal captures the keyboard, so `Escape` dismisses it.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::Escape)));
+    assert_eq!((false, Some(Dismissal::Escape)), set_widgets(ui, Some(widget::Modal::new())));
+
+    // Clicking the scrim only dismisses the modal when enabled.
+    click_button(ui);
+    let modal_widget = widget::Modal::new().dismiss_on_scrim_click(true);
+    assert_eq!((false, Some(Dismissal::ScrimClick)), set_widgets(ui, Some(modal_widget)));
+
+    // Once the modal is no longer instantiated, the button receives clicks again.
+    set_widgets(ui, None);
+    click_button(ui);
+    assert_eq!((true, None), set_widgets(ui, None));
+}
+
+#[test]
+fn nested_modals_should_block_background_captures_and_restore_focus_once_closed() {
+    let ui = &mut windowless_ui();
+    let (button, outer, inner) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    // Instantiates the given number of nested modals, returning whether the button was clicked.
+    let set_widgets = |ui: &mut Ui, num_modals: usize| -> bool {
+        let ui = &mut ui.set_widgets();
+        let clicked = widget::Button::new().w_h(100.0, 50.0).x_y(0.0, -200.0).set(button, ui);
+        if num_modals > 0 {
+            widget::Modal::new().set(outer, ui);
+        }
+        if num_modals > 1 {
+            widget::Modal::new().set(inner, ui);
+        }
+        clicked.was_clicked()
+    };
+    let click_button = |ui: &mut Ui| {
+        move_mouse_to_abs_coordinates(0.0, -200.0, ui);
+        left_click_mouse(ui);
+    };
+    let keyboard = |ui: &Ui| ui.global_input().current.widget_capturing_keyboard;
+
+    // Clicking the button gives it the keyboard.
+    set_widgets(ui, 0);
+    click_button(ui);
+    assert!(set_widgets(ui, 0));
+    assert_eq!(Some(button), keyboard(ui));
+
+    // The modal takes the keyboard, and the button can capture neither the mouse nor keyboard.
+    set_widgets(ui, 1);
+    assert_eq!(Some(outer), ui.top_modal());
+    assert_eq!(Some(outer), keyboard(ui));
+    assert!(ui.is_blocked_by_modal(button));
+    ui.focus_widget(button);
+    assert_eq!(Some(outer), keyboard(ui));
+    click_button(ui);
+    assert!(!set_widgets(ui, 1));
+    assert!(ui.global_input().current.widget_capturing_mouse != Some(button));
+    assert_eq!(Some(outer), keyboard(ui));
+
+    // A nested modal blocks the outer one in turn.
+    set_widgets(ui, 2);
+    assert_eq!(Some(inner), ui.top_modal());
+    assert_eq!(Some(inner), keyboard(ui));
+    assert!(ui.is_blocked_by_modal(outer));
+
+    // Closing each modal returns the keyboard to the widget that had it before the modal opened.
+    set_widgets(ui, 1);
+    assert_eq!(Some(outer), ui.top_modal());
+    assert_eq!(Some(outer), keyboard(ui));
+    set_widgets(ui, 0);
+    assert_eq!(None, ui.top_modal());
+    assert_eq!(Some(button), keyboard(ui));
+
+    // Once all modals are closed, the button receives clicks again.
+    click_button(ui);
+    assert!(set_widgets(ui, 0));
+}
+
+#[test]
+fn context_menu_should_select_clicked_items_and_dismiss_on_outside_presses() {
+    use widget::context_menu::Event;
+    let ui = &mut windowless_ui();
+    let (target, menu) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let items = ["Cut", "Copy", "Paste"];
+    let mut menu_xy = None;
+    // Opens the menu wherever the target was last right-clicked.
+    let mut set_menu = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([400.0, 400.0]).set(target, ui);
+        let target_xy = ui.xy_of(target).unwrap();
+        if let Some(context_menu) = ui.widget_input(target).context_menus().last() {
+            let xy = context_menu.xy;
+            menu_xy = Some([target_xy[0] + xy[0], target_xy[1] + xy[1]]);
+        }
+        menu_xy.and_then(|xy| widget::ContextMenu::new(&items, xy).set(menu, ui))
+    };
+    assert_eq!(None, set_menu(ui));
+    assert!(ui.rect_of(menu).is_none());
+
+    // The right-click that opens the menu does not dismiss it.
+    move_mouse_to_abs_coordinates(-100.0, 100.0, ui);
+    press_mouse_button(MouseButton::Right, ui);
+    release_mouse_button(MouseButton::Right, ui);
+    assert_eq!(None, set_menu(ui));
+    let rect = ui.rect_of(menu).unwrap();
+    assert_eq!([-100.0, 100.0], rect.top_left());
+    assert_eq!(90.0, rect.h());
+
+    // Clicking the second item selects it.
+    move_mouse_to_abs_coordinates(-25.0, 55.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(Event::Selected(1)), set_menu(ui));
+
+    // Pressing anywhere outside of the menu dismisses it.
+    move_mouse_to_abs_coordinates(300.0, -250.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(Event::Dismissed), set_menu(ui));
+}
+
+#[test]
+fn context_menu_should_flip_to_stay_within_the_window() {
+    let ui = &mut windowless_ui();
+    let menu = ui.widget_id_generator().next();
+    let items = ["Cut", "Copy", "Paste"];
+    widget::ContextMenu::new(&items, [390.0, -290.0]).set(menu, &mut ui.set_widgets());
+    let rect = ui.rect_of(menu).unwrap();
+    assert_eq!([390.0, -290.0], rect.bottom_right());
+    let window = ui.rect_of(ui.window).unwrap();
+    assert_eq!(Some(rect), window.overlap(rect));
+}
+
+#[test]
+fn color_picker_should_map_drags_within_the_square_to_saturation_and_value() {
+    use color;
+    let ui = &mut windowless_ui();
+    let picker = ui.widget_id_generator().next();
+    // The square spans `-62.5..37.5` along *x* and `-50.0..50.0` along *y*.
+    let set_picker = |ui: &mut Ui, s: f32, v: f32| {
+        widget::ColorPicker::new(0.0, s, v).w_h(125.0, 100.0).set(picker, &mut ui.set_widgets())
+    };
+    set_picker(ui, 0.0, 0.0);
+
+    move_mouse_to_abs_coordinates(-12.5, 25.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    let event = set_picker(ui, 0.0, 0.0).unwrap();
+    assert_eq!((0.0, 0.5, 0.75), (event.hue, event.saturation, event.value));
+    assert_eq!(color::rgb(0.75, 0.375, 0.375), event.color);
+
+    // Dragging beyond the bottom right corner clamps to it.
+    move_mouse_to_abs_coordinates(200.0, -200.0, ui);
+    let event = set_picker(ui, 0.5, 0.75).unwrap();
+    assert_eq!((1.0, 0.0), (event.saturation, event.value));
+    assert_eq!(color::rgb(0.0, 0.0, 0.0), event.color);
+}
+
+#[test]
+fn range_slider_handles_should_drag_independently_without_crossing() {
+    use widget::range_slider::Edge;
+    let ui = &mut windowless_ui();
+    let slider = ui.widget_id_generator().next();
+    // With the 1.0 border, the value `v` lies at `x = -100.0 + 2.0 * v`.
+    let set_slider = |ui: &mut Ui, start: f64, end: f64| -> Vec<(Edge, f64)> {
+        widget::RangeSlider::new(start, end, 0.0, 100.0)
+            .w_h(202.0, 20.0)
+            .set(slider, &mut ui.set_widgets())
+            .collect()
+    };
+    let drag = |ui: &mut Ui, from_x: f64, to_x: f64| {
+        move_mouse_to_abs_coordinates(from_x, 0.0, ui);
+        press_mouse_button(MouseButton::Left, ui);
+        move_mouse_to_abs_coordinates(to_x, 0.0, ui);
+    };
+    set_slider(ui, 20.0, 80.0);
+
+    // Dragging the start handle leaves the end untouched.
+    drag(ui, -58.0, -50.0);
+    assert_eq!(vec![(Edge::Start, 25.0)], set_slider(ui, 20.0, 80.0));
+    release_mouse_button(MouseButton::Left, ui);
+    set_slider(ui, 25.0, 80.0);
+
+    // Dragging the end handle leaves the start untouched and clamps to the maximum.
+    drag(ui, 58.0, 150.0);
+    assert_eq!(vec![(Edge::End, 100.0)], set_slider(ui, 25.0, 80.0));
+    release_mouse_button(MouseButton::Left, ui);
+    set_slider(ui, 25.0, 80.0);
+
+    // Dragging the start handle past the end handle clamps at the end's value.
+    drag(ui, -48.0, 90.0);
+    assert_eq!(vec![(Edge::Start, 80.0)], set_slider(ui, 25.0, 80.0));
+}
+
+#[test]
+fn consumed_clicks_should_not_reach_widgets_beneath_unless_passed_through() {
+    let ui = &mut windowless_ui();
+    let (lower, upper) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    // Returns the number of left clicks seen by the lower and upper widgets respectively.
+    let set_widgets = |ui: &mut Ui, upper_consumes_events: bool| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 200.0]).x_y(0.0, 0.0).set(lower, ui);
+        widget::Rectangle::fill([100.0, 100.0])
+            .x_y(0.0, 0.0)
+            .consume_events(upper_consumes_events)
+            .set(upper, ui);
+        let clicks = |id| ui.widget_input(id).clicks().left().count();
+        (clicks(lower), clicks(upper))
+    };
+    let click_center = |ui: &mut Ui| {
+        move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+        left_click_mouse(ui);
+    };
+
+    // The upper widget consumes the click, so the lower widget never sees it.
+    set_widgets(ui, true);
+    click_center(ui);
+    assert_eq!((0, 1), set_widgets(ui, true));
+
+    // Once the upper widget stops consuming events, the click passes through to the lower widget
+    // while the upper widget still observes it.
+    set_widgets(ui, false);
+    click_center(ui);
+    assert_eq!(Some(lower), ui.global_input.current.widget_capturing_mouse);
+    assert_eq!((1, 1), set_widgets(ui, false));
+}
+
+#[test]
+fn widget_under_mouse_should_return_the_topmost_widget_by_depth_then_instantiation() {
+    let ui = &mut windowless_ui();
+    let (a, b, c, cropper, cropped) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        // `b` is above `a` as it was instantiated later, while `c` is above both due to its depth.
+        widget::Rectangle::fill([200.0, 200.0]).x_y(0.0, 0.0).set(a, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(50.0, 0.0).set(b, ui);
+        widget::Rectangle::fill([50.0, 50.0]).x_y(25.0, 0.0).depth(-1.0).set(c, ui);
+        widget::Rectangle::fill([50.0, 50.0]).x_y(200.0, 200.0).crop_kids().set(cropper, ui);
+        widget::Rectangle::fill([100.0, 100.0])
+            .x_y(200.0, 200.0)
+            .parent(cropper)
+            .set(cropped, ui);
+    }
+    let widget_under = |ui: &mut Ui, x, y| {
+        move_mouse_to_abs_coordinates(x, y, ui);
+        ui.widget_under_mouse(&ui.global_input.current)
+    };
+
+    let window = ui.window;
+    assert_eq!(Some(b), widget_under(ui, 75.0, 0.0));
+    assert_eq!(Some(c), widget_under(ui, 25.0, 0.0));
+    assert_eq!(Some(a), widget_under(ui, -50.0, 0.0));
+    assert_eq!(Some(window), widget_under(ui, 0.0, -250.0));
+
+    // The cropped widget is only hit within its parent's area.
+    assert_eq!(Some(cropped), widget_under(ui, 210.0, 210.0));
+    assert_eq!(Some(window), widget_under(ui, 240.0, 240.0));
+    assert_eq!(ui.widget_under_mouse(&ui.global_input.current),
+               ui.global_input.current.widget_under_mouse);
+}
+
+#[test]
+fn mouse_capture_should_go_to_the_topmost_widget_and_stay_pinned_while_pressed() {
+    use event::Widget::{CapturesMouse, UncapturesMouse};
+    let ui = &mut windowless_ui();
+    let (lower, upper) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    // Returns the mouse capturing events seen by the lower and upper widgets respectively.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 200.0]).x_y(0.0, 0.0).set(lower, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(50.0, 0.0).set(upper, ui);
+        let capturing_events = |id| {
+            ui.widget_input(id).events()
+                .filter(|event| *event == CapturesMouse || *event == UncapturesMouse)
+                .collect::<Vec<_>>()
+        };
+        (capturing_events(lower), capturing_events(upper))
+    };
+
+    let none: Vec<event::Widget> = vec![];
+
+    // Both widgets are under the stationary mouse once they appear, but only the topmost
+    // captures it.
+    move_mouse_to_abs_coordinates(50.0, 0.0, ui);
+    assert_eq!((none.clone(), none.clone()), set_widgets(ui));
+    assert_eq!((none.clone(), vec![CapturesMouse]), set_widgets(ui));
+    assert_eq!(Some(upper), ui.global_input.current.widget_capturing_mouse);
+
+    // The lower widget keeps the capture while pressed, even when dragged over the upper widget.
+    move_mouse_to_abs_coordinates(-50.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(50.0, 0.0, ui);
+    assert_eq!((vec![CapturesMouse], vec![UncapturesMouse]), set_widgets(ui));
+    assert_eq!(Some(upper), ui.global_input.current.widget_under_mouse);
+    assert_eq!(Some(lower), ui.global_input.current.widget_capturing_mouse);
+
+    // Upon release, the capture immediately passes to the topmost widget under the mouse.
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!((vec![UncapturesMouse], vec![CapturesMouse]), set_widgets(ui));
+    assert_eq!(Some(upper), ui.global_input.current.widget_capturing_mouse);
+}
+
+#[test]
+fn global_input_should_report_the_mouse_position_relative_to_the_window() {
+    let ui = &mut windowless_ui();
+    let rect = ui.widget_id_generator().next();
+    // Returns the global and widget-relative mouse positions respectively.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 200.0]).x_y(100.0, 100.0).set(rect, ui);
+        let rel_xy = ui.widget_input(rect).mouse().map(|mouse| mouse.rel_xy());
+        (ui.global_input().current.mouse.xy, rel_xy)
+    };
+
+    move_mouse_to_abs_coordinates(50.0, 60.0, ui);
+    set_widgets(ui);
+    assert_eq!(([50.0, 60.0], Some([-50.0, -40.0])), set_widgets(ui));
+    assert_eq!([50.0, 60.0], ui.global_input().current.mouse.xy);
+}
+
+#[test]
+fn widgets_should_be_mounted_on_first_use_and_unmounted_after_disappearing() {
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    // Sets the given widgets, returning the lifecycle events of `a` and `b` delivered to the update.
+    let set_widgets = |ui: &mut Ui, ids: &[widget::Id]| {
+        let ui = &mut ui.set_widgets();
+        for &id in ids {
+            widget::Rectangle::fill([10.0, 10.0]).set(id, ui);
+        }
+        ui.global_input().events().ui()
+            .filter(|event| match **event {
+                event::Ui::WidgetMounted(id) | event::Ui::WidgetUnmounted(id) =>
+                    id == a || id == b,
+                _ => false,
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    // Each event is delivered exactly one update after the change that produced it, so the update
+    // that first instantiates `a` and `b` is not yet told that they were mounted.
+    assert!(set_widgets(ui, &[a, b]).is_empty());
+    let mounted = vec![event::Ui::WidgetMounted(a), event::Ui::WidgetMounted(b)];
+    assert_eq!(mounted, set_widgets(ui, &[a]));
+
+    // Likewise, the update that omits `b` is not told that `b` was unmounted until the next one.
+    assert_eq!(vec![event::Ui::WidgetUnmounted(b)], set_widgets(ui, &[a, b]));
+
+    // A widget that re-appears is mounted again, one update later.
+    assert_eq!(vec![event::Ui::WidgetMounted(b)], set_widgets(ui, &[a, b]));
+    assert!(set_widgets(ui, &[a, b]).is_empty());
+}
+
+#[test]
+fn widget_ids_from_names_should_be_stable_across_updates() {
+    let ui = &mut windowless_ui();
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let ui = &mut ui.set_widgets();
+        let id = ui.widget_id_from_name("ok_button").unwrap();
+        widget::Button::new().set(id, ui);
+        ids.push(id);
+    }
+    assert!(ids.iter().all(|&id| id == ids[0]));
+    let key = widget::id::hash_name("ok_button");
+    assert_eq!(Some(key), ui.widget_names().key_of(ids[0]));
+    assert!(ui.widget_id_from_name("ok_button").is_ok());
+    assert_eq!(Some(ids[0]), ui.widget_names().get("ok_button"));
+}
+
+#[test]
+fn frame_stats_should_average_the_given_frame_durations() {
+    use std::time::Duration;
+    let zero = Duration::from_secs(0);
+    let no_frames = FrameStats { last_frame: zero, avg: zero, fps: 0.0 };
+    assert_eq!(no_frames, FrameStats::from_durations(vec![]));
+
+    let stats = FrameStats::from_durations((1..4).map(|i| Duration::from_millis(i * 10)));
+    assert_eq!(Duration::from_millis(30), stats.last_frame);
+    assert_eq!(Duration::from_millis(20), stats.avg);
+    assert!((stats.fps - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn ui_frame_stats_should_be_zero_until_a_frame_has_been_timed() {
+    let ui = &mut windowless_ui();
+    assert_eq!(0.0, ui.frame_stats().fps);
+    ui.set_widgets();
+    assert_eq!(0.0, ui.frame_stats().fps);
+    ui.set_widgets();
+    let stats = ui.frame_stats();
+    assert_eq!(stats.last_frame, stats.avg);
+}
+
+#[test]
+fn clicks_handled_by_a_widget_should_report_their_latency() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    ui.global_input.timestamp_events = true;
+    let button = ui.widget_id_generator().next();
+    let set_button = |ui: &mut Ui| {
+        widget::Button::new().w_h(100.0, 100.0).set(button, &mut ui.set_widgets());
+    };
+    set_button(ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_button(ui);
+
+    let click = ui.global_input().latencies().iter()
+        .find(|latency| match latency.event {
+            event::Event::Ui(event::Ui::Click(idx, _)) => idx == Some(button),
+            _ => false,
+        })
+        .expect("expected the click to be timestamped");
+    assert!(click.was_handled);
+    assert!(click.latency < Duration::from_secs(1));
+}
+
+#[test]
+fn focus_widget_should_move_keyboard_capture_to_instantiated_widgets_only() {
+    let ui = &mut windowless_ui();
+    let (a, b, never_set) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().set(a, ui);
+        widget::Button::new().set(b, ui);
+    }
+    let keyboard_events = |ui: &Ui| {
+        ui.global_input().events().ui()
+            .filter(|event| match **event {
+                event::Ui::WidgetCapturesKeyboard(_) | event::Ui::WidgetUncapturesKeyboard(_) =>
+                    true,
+                _ => false,
+            })
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    ui.focus_widget(a);
+    assert_eq!(Some(a), ui.global_input.current.widget_capturing_keyboard);
+    ui.focus_widget(b);
+    assert_eq!(Some(b), ui.global_input.current.widget_capturing_keyboard);
+    let expected = vec![
+        event::Ui::WidgetCapturesKeyboard(a),
+        event::Ui::WidgetUncapturesKeyboard(a),
+        event::Ui::WidgetCapturesKeyboard(b),
+    ];
+    assert_eq!(expected, keyboard_events(ui));
+
+    // Focusing unknown widgets leaves the focus unchanged.
+    ui.focus_widget(never_set);
+    ui.focus_widget(widget::Id::new(9999));
+    assert_eq!(Some(b), ui.global_input.current.widget_capturing_keyboard);
+    assert_eq!(3, keyboard_events(ui).len());
+}
+
+#[test]
+fn disabled_button_should_ignore_clicks_and_release_any_capture() {
+    let ui = &mut windowless_ui();
+    let button = ui.widget_id_generator().next();
+    let set_button = |ui: &mut Ui, enabled: bool| {
+        widget::Button::new()
+            .w_h(100.0, 100.0)
+            .enabled(enabled)
+            .set(button, &mut ui.set_widgets())
+            .count()
+    };
+    set_button(ui, true);
+    move_mouse_to_abs_coordinates(400.0, 300.0, ui);
+
+    // Pressing the enabled button captures both the mouse and the keyboard.
+    press_mouse_button(MouseButton::Left, ui);
+    assert_eq!(0, set_button(ui, true));
+    assert_eq!(Some(button), ui.global_input.current.widget_capturing_mouse);
+    assert_eq!(Some(button), ui.global_input.current.widget_capturing_keyboard);
+
+    // Disabling the button drops the pending click along with both captures.
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(0, set_button(ui, false));
+    assert_eq!(None, ui.global_input.current.widget_capturing_mouse);
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+
+    // Clicks upon the disabled button are ignored.
+    left_click_mouse(ui);
+    assert_eq!(0, set_button(ui, false));
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+
+    // Once re-enabled the button responds to clicks again.
+    left_click_mouse(ui);
+    assert_eq!(1, set_button(ui, true));
+}
+
+#[test]
+fn disabled_text_widgets_should_ignore_input_and_release_the_keyboard() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = String::new();
+    let set_widgets = |ui: &mut Ui, text: &mut String, enabled: bool| {
+        set_text_edit(ui, text_edit, text, |text_edit| {
+            text_edit.w_h(400.0, 100.0).enabled(enabled)
+        });
+    };
+    set_widgets(ui, &mut text, true);
+    ui.focus_widget(text_edit);
+    ui.handle_event(Input::Text("a".to_string()));
+    set_widgets(ui, &mut text, true);
+    assert_eq!("a", text);
+
+    // Disabling the `TextEdit` drops the pending keystroke along with the keyboard.
+    ui.handle_event(Input::Text("b".to_string()));
+    set_widgets(ui, &mut text, false);
+    assert_eq!("a", text);
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+
+    // Clicking the disabled `TextEdit` does not give it the keyboard.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut text, false);
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+
+    // Disabling a `NumericField` mid-edit discards the edit.
+    let ui = &mut windowless_ui_with_font();
+    let field = ui.widget_id_generator().next();
+    let set_field = |ui: &mut Ui, enabled: bool| {
+        widget::NumericField::new(1.0)
+            .w_h(200.0, 40.0)
+            .enabled(enabled)
+            .set(field, &mut ui.set_widgets())
+    };
+    set_field(ui, true);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_field(ui, true);
+    assert!(ui.global_input.current.widget_capturing_keyboard.is_some());
+    press_ctrl(ui, Key::A);
+    set_field(ui, true);
+    ui.handle_event(Input::Text("5".to_string()));
+    assert_eq!(None, set_field(ui, false));
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+}
+
+#[test]
+fn disabled_range_slider_and_tabs_should_ignore_presses_and_release_any_capture() {
+    use widget::range_slider::Edge;
+    let ui = &mut windowless_ui();
+    let slider = ui.widget_id_generator().next();
+    let set_slider = |ui: &mut Ui, enabled: bool| -> Vec<(Edge, f64)> {
+        widget::RangeSlider::new(20.0, 80.0, 0.0, 100.0)
+            .w_h(202.0, 20.0)
+            .enabled(enabled)
+            .set(slider, &mut ui.set_widgets())
+            .collect()
+    };
+    set_slider(ui, true);
+
+    // Disabling the slider mid-drag drops the drag along with the mouse capture.
+    move_mouse_to_abs_coordinates(-58.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    set_slider(ui, true);
+    assert_eq!(Some(slider), ui.global_input.current.widget_capturing_mouse);
+    move_mouse_to_abs_coordinates(-50.0, 0.0, ui);
+    assert!(set_slider(ui, false).is_empty());
+    assert_eq!(None, ui.global_input.current.widget_capturing_mouse);
+    release_mouse_button(MouseButton::Left, ui);
+    set_slider(ui, false);
+
+    // Presses upon the disabled slider are ignored.
+    move_mouse_to_abs_coordinates(-90.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    assert!(set_slider(ui, false).is_empty());
+    release_mouse_button(MouseButton::Left, ui);
+
+    // The selected tab of disabled `Tabs` may be changed neither by clicks nor by `Ctrl+Tab`.
+    let ui = &mut windowless_ui();
+    let (tabs, a, b, c) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    let tab_list = [(a, "A"), (b, "B"), (c, "C")];
+    let set_tabs = |ui: &mut Ui, enabled: bool| {
+        widget::Tabs::new(&tab_list)
+            .w_h(300.0, 200.0)
+            .enabled(enabled)
+            .set(tabs, &mut ui.set_widgets());
+    };
+    set_tabs(ui, false);
+    move_mouse_to_abs_coordinates(100.0, 90.0, ui);
+    left_click_mouse(ui);
+    set_tabs(ui, false);
+    assert!(ui.updated_widgets().contains(&a));
+    ui.focus_widget(tabs);
+    press_ctrl(ui, Key::Tab);
+    set_tabs(ui, false);
+    assert!(ui.updated_widgets().contains(&a));
+    assert_eq!(None, ui.global_input.current.widget_capturing_keyboard);
+
+    // Once re-enabled, the tabs respond to clicks again.
+    left_click_mouse(ui);
+    set_tabs(ui, true);
+    assert!(ui.updated_widgets().contains(&c));
+}
+
+#[test]
+fn redraw_needed_should_remain_true_until_input_is_handled_and_animations_settle() {
+    use std::time::Duration;
+    use widget::toasts::Queue;
+    let ui = &mut windowless_ui();
+    let toasts = ui.widget_id_generator().next();
+    let mut queue = Queue::new(1);
+    // Updates and draws the `Ui` once, returning whether or not it needs to do so again.
+    let frame = |ui: &mut Ui, queue: &mut Queue| {
+        widget::Toasts::new(queue).w_h(200.0, 100.0).set(toasts, &mut ui.set_widgets());
+        ui.draw_if_changed();
+        ui.redraw_needed()
+    };
+    let settle = |ui: &mut Ui, queue: &mut Queue| {
+        let mut frames = 0;
+        while frame(ui, queue) {
+            frames += 1;
+            assert!(frames < 100, "the ui should settle within a reasonable number of frames");
+            ui.step(&[], Duration::from_millis(5));
+        }
+    };
+
+    // Drive the `Ui`'s clock via `step` so that animations progress deterministically.
+    ui.step(&[], Duration::from_secs(0));
+    settle(ui, &mut queue);
+    assert!(!ui.redraw_needed());
+
+    // Pending input requires an update.
+    move_mouse_to_abs_coordinates(10.0, 10.0, ui);
+    assert!(ui.redraw_needed());
+    settle(ui, &mut queue);
+
+    // The toast animates until it has been dismissed.
+    queue.push("saved", Duration::from_millis(50));
+    for _ in 0..5 {
+        assert!(frame(ui, &mut queue));
+    }
+    settle(ui, &mut queue);
+    assert!(queue.is_empty());
+    assert!(!ui.redraw_needed());
+}
+
+#[test]
+fn find_widget_should_query_the_most_recently_instantiated_widgets() {
+    use position::Rect;
+    let ui = &mut windowless_ui();
+    let (cancel, ok) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let set_widgets = |ui: &mut Ui, show_ok: bool| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().label("Cancel").w_h(100.0, 40.0).x_y(-60.0, 0.0).set(cancel, ui);
+        if show_ok {
+            widget::Button::new().label("OK").w_h(100.0, 40.0).x_y(60.0, 0.0).set(ok, ui);
+        }
+    };
+    fn is_button_labelled(info: &WidgetInfo, label: &str) -> bool {
+        info.is::<widget::Button<widget::button::Flat>>() && info.text == Some(label)
+    }
+
+    set_widgets(ui, true);
+    assert_eq!(Some(ok), ui.find_widget(|info| is_button_labelled(info, "OK")));
+    assert_eq!(Some(cancel), ui.find_widget(|info| is_button_labelled(info, "Cancel")));
+    let ok_rect = Rect::from_xy_dim([60.0, 0.0], [100.0, 40.0]);
+    assert_eq!(Some(ok), ui.find_widget(|info| info.rect == ok_rect));
+    assert_eq!(None, ui.find_widget(|info| is_button_labelled(info, "Apply")));
+
+    // Widgets that were not instantiated during the last update are not found.
+    set_widgets(ui, false);
+    assert_eq!(None, ui.find_widget(|info| is_button_labelled(info, "OK")));
+}
+
+#[test]
+fn dump_layout_should_describe_every_widget_with_rounded_rects() {
+    let ui = &mut windowless_ui();
+    let (thin, panel, dot) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        let window = ui.window;
+        widget::Rectangle::fill([10.0, 10.0]).x_y(100.0 / 3.0, 0.0).set(thin, ui);
+        widget::Rectangle::fill([200.0, 100.0]).top_left_of(window).crop_kids().set(panel, ui);
+        widget::Oval::fill([50.0, 50.0]).middle_of(panel).consume_events(false).set(dot, ui);
+    };
+
+    set_widgets(ui);
+
+    let expected = "\
+0 BorderedRectangle rect=[-400, -300, 400, 300] depth=0
+1 Rectangle parent=0 rect=[28.33, -5, 38.33, 5] depth=0
+2 Rectangle parent=0 rect=[-400, 200, -200, 300] depth=0 crop_kids
+3 Oval parent=2 rect=[-325, 225, -275, 275] depth=0 passthrough
+";
+    let snapshot = ui.dump_layout();
+    assert_eq!(expected, format!("{}", snapshot));
+
+    // The snapshot is stable across updates of an unchanged layout.
+    set_widgets(ui);
+    assert_eq!(snapshot, ui.dump_layout());
+}
+
+#[test]
+fn step_without_events_should_still_advance_animations() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    let collapsible = ui.widget_id_generator().next();
+    // Returns the height of the collapsible's body, if it is visible.
+    let set_widgets = |ui: &mut Ui, is_open: bool| -> Option<f64> {
+        let event = {
+            let ui = &mut ui.set_widgets();
+            widget::Collapsible::new("Section", is_open, 100.0).w(200.0).set(collapsible, ui)
+        };
+        event.body.and_then(|body| ui.rect_of(body)).map(|rect| rect.h())
+    };
+
+    // Drive the `Ui` via `step` from the start, then begin closing the body.
+    ui.step(&[], Duration::from_secs(0));
+    assert_eq!(Some(100.0), set_widgets(ui, true));
+    assert_eq!(Some(100.0), set_widgets(ui, false));
+    let start = ui.now();
+
+    // A quarter of the animation passes without any input.
+    let dt = Duration::from_millis(50);
+    ui.step(&[], dt);
+    assert_eq!(start + dt, ui.now());
+    let body_h = set_widgets(ui, false).unwrap();
+    assert!((body_h - 75.0).abs() < 1e-6);
+    assert!(ui.redraw_needed());
+
+    // The rest of the animation passes and the body disappears.
+    ui.step(&[], Duration::from_millis(200));
+    assert_eq!(None, set_widgets(ui, false));
+}
+
+#[test]
+fn collapsible_height_should_follow_its_body_while_animating() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    let collapsible = ui.widget_id_generator().next();
+    // Returns the height of the whole collapsible.
+    let set_widgets = |ui: &mut Ui, is_open: bool| -> f64 {
+        {
+            let ui = &mut ui.set_widgets();
+            widget::Collapsible::new("Section", is_open, 100.0)
+                .w(200.0)
+                .header_h(30.0)
+                .set(collapsible, ui);
+        }
+        ui.h_of(collapsible).unwrap()
+    };
+
+    ui.step(&[], Duration::from_secs(0));
+    assert_eq!(130.0, set_widgets(ui, true));
+    assert_eq!(130.0, set_widgets(ui, false));
+
+    // Part way through the animation, only the revealed part of the body is included.
+    ui.step(&[], Duration::from_millis(50));
+    assert!((set_widgets(ui, false) - 105.0).abs() < 1e-6);
+    ui.step(&[], Duration::from_millis(100));
+    assert!((set_widgets(ui, false) - 55.0).abs() < 1e-6);
+
+    // Once the body is hidden, only the header remains.
+    ui.step(&[], Duration::from_millis(100));
+    assert_eq!(30.0, set_widgets(ui, false));
+
+    // Reopening grows the collapsible by the same steps.
+    assert_eq!(30.0, set_widgets(ui, true));
+    ui.step(&[], Duration::from_millis(50));
+    assert!((set_widgets(ui, true) - 55.0).abs() < 1e-6);
+}
+
+#[test]
+fn nested_collapsibles_should_grow_with_their_children() {
+    use std::time::Duration;
+    use widget::collapsible;
+    let ui = &mut windowless_ui();
+    let (outer, inner) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    // The outer body holds a 20 unit gap followed by the inner collapsible. Returns the heights of
+    // both collapsibles along with the distance between their bottom edges.
+    let set_widgets = |ui: &mut Ui, inner_is_open: bool| -> (f64, f64, f64) {
+        {
+            let ui = &mut ui.set_widgets();
+            let inner_h = collapsible::height(30.0, 50.0, inner_is_open);
+            let event = widget::Collapsible::new("Outer", true, 20.0 + inner_h)
+                .w(200.0)
+                .header_h(30.0)
+                .set(outer, ui);
+            if let Some(body) = event.body {
+                widget::Collapsible::new("Inner", inner_is_open, 50.0)
+                    .w(200.0)
+                    .header_h(30.0)
+                    .mid_top_with_margin_on(body, 20.0)
+                    .parent(body)
+                    .set(inner, ui);
+            }
+        }
+        let (outer_rect, inner_rect) = (ui.rect_of(outer).unwrap(), ui.rect_of(inner).unwrap());
+        (outer_rect.h(), inner_rect.h(), inner_rect.bottom() - outer_rect.bottom())
+    };
+
+    ui.step(&[], Duration::from_secs(0));
+    let (outer_h, inner_h, gap) = set_widgets(ui, false);
+    assert_eq!((80.0, 30.0), (outer_h, inner_h));
+    assert!(gap.abs() < 1e-6);
+
+    // Once the inner body has been revealed, the outer collapsible has grown to contain it.
+    set_widgets(ui, true);
+    ui.step(&[], Duration::from_millis(200));
+    let (outer_h, inner_h, gap) = set_widgets(ui, true);
+    assert_eq!((130.0, 80.0), (outer_h, inner_h));
+    assert!(gap.abs() < 1e-6);
+}
+
+#[test]
+fn points_should_round_trip_between_logical_and_physical_coordinates() {
+    for &scale_factor in &[2.0, 1.5] {
+        let ui = UiBuilder::new([800.0, 600.0]).scale_factor(scale_factor).build();
+        for &point in &[[0.0, 0.0], [-400.0, 300.0], [123.25, -57.5], [1.0 / 3.0, 0.1]] {
+            let physical = ui.logical_to_physical(point);
+            let logical = ui.physical_to_logical(physical);
+            assert!((logical[0] - point[0]).abs() < 1e-9, "{:?} became {:?}", point, logical);
+            assert!((logical[1] - point[1]).abs() < 1e-9, "{:?} became {:?}", point, logical);
+        }
+    }
+
+    // The top left of the window is the origin of the framebuffer.
+    let ui = UiBuilder::new([800.0, 600.0]).scale_factor(2.0).build();
+    assert_eq!([0.0, 0.0], ui.logical_to_physical([-400.0, 300.0]));
+    assert_eq!([1600.0, 1200.0], ui.logical_to_physical([400.0, -300.0]));
+    let ui = UiBuilder::new([800.0, 600.0]).scale_factor(1.5).build();
+    assert_eq!([600.0, 450.0], ui.logical_to_physical([0.0, 0.0]));
+    assert_eq!([-200.0, 0.0], ui.physical_to_logical([300.0, 450.0]));
+}
+
+#[test]
+fn physical_cursor_positions_should_hit_test_without_rounding_at_fractional_scales() {
+    let mut ui = UiBuilder::new([800.0, 600.0]).scale_factor(1.5).build();
+    let rect = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        // Spans the logical x range -400..-200, or the physical pixels 0..300.
+        widget::Rectangle::fill([200.0, 600.0])
+            .mid_left_of(ui.window)
+            .set(rect, ui);
+    }
+
+    // Each position lies half a logical unit from the edge, so rounding would misplace them.
+    ui.handle_physical_event(Input::Move(Motion::MouseCursor(299.25, 450.0)));
+    assert_eq!(Some(rect), ui.global_input.current.widget_under_mouse);
+    ui.handle_physical_event(Input::Move(Motion::MouseCursor(300.75, 450.0)));
+    assert_eq!(Some(ui.window), ui.global_input.current.widget_under_mouse);
+
+    // Touch positions are mapped to logical space in the same manner.
+    let touch = |xy| {
+        let args = input::TouchArgs::new(0, 0, xy, 1.0, input::Touch::Start);
+        Input::Move(Motion::Touch(args))
+    };
+    ui.handle_physical_event(touch([300.0, 450.0]));
+    assert_event_was_pushed(&ui, event::Event::Raw(touch([-200.0, 0.0])));
+
+    // Physical framebuffer dimensions resize the window in logical units.
+    ui.handle_physical_event(Input::Resize(1500, 900));
+    assert_eq!([1000.0, 600.0], [ui.win_w, ui.win_h]);
+}
+
+#[test]
+fn draw_primitives_should_yield_each_primitive_in_depth_order_with_its_scizzor() {
+    use position::Rect;
+    use render::PrimitiveKind;
+
+    let mut ui = windowless_ui();
+    let (on_top, cropper, cropped, oval) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next(), ids.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        // Instantiated first, but drawn last due to its depth.
+        widget::Rectangle::fill([50.0, 50.0]).x_y(0.0, 0.0).depth(-1.0).set(on_top, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(0.0, 0.0).crop_kids().set(cropper, ui);
+        widget::Rectangle::fill([200.0, 20.0]).x_y(0.0, 0.0).parent(cropper).set(cropped, ui);
+        widget::Oval::fill([30.0, 30.0]).x_y(200.0, 0.0).set(oval, ui);
+    }
+
+    let mut stream = Vec::new();
+    let mut primitives = ui.draw_primitives();
+    while let Some(primitive) = primitives.next() {
+        let kind = match primitive.kind {
+            PrimitiveKind::Rectangle { .. } => "Rectangle",
+            PrimitiveKind::Polygon { .. } => "Polygon",
+            _ => "Other",
+        };
+        stream.push((primitive.id, kind, primitive.scizzor));
+    }
+
+    // The window's border and background come first.
+    let window = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    let cropper_rect = Rect::from_xy_dim([0.0, 0.0], [100.0, 100.0]);
+    assert_eq!(6, stream.len());
+    assert_eq!(vec![(cropper, "Rectangle", window),
+                    (cropped, "Rectangle", cropper_rect),
+                    (oval, "Polygon", window),
+                    (on_top, "Rectangle", window)],
+               stream[2..].to_vec());
+
+    // Collecting the primitives doesn't count as drawing the `Ui`.
+    for _ in 0..4 {
+        ui.draw_primitives();
+    }
+    assert!(ui.draw_if_changed().is_some());
+}
+
+#[test]
+fn event_driven_ui_should_sleep_without_input_or_animation() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    let collapsible = ui.widget_id_generator().next();
+    // Updates the widgets only if needed, returning whether or not they were updated.
+    let update = |ui: &mut Ui, is_open: bool| -> bool {
+        match ui.set_widgets_if_needed() {
+            Some(mut ui) => {
+                widget::Collapsible::new("Section", is_open, 100.0).set(collapsible, &mut ui);
+                true
+            },
+            None => false,
+        }
+    };
+    let draw_all = |ui: &mut Ui| while ui.draw_if_changed().is_some() {};
+
+    // The first update is always needed.
+    ui.step(&[], Duration::from_secs(0));
+    assert!(update(ui, true));
+    draw_all(ui);
+
+    // Without input, animation or requests, the `Ui` may sleep.
+    assert!(!ui.update_needed());
+    assert!(!ui.redraw_needed());
+    assert!(!update(ui, true));
+
+    // An explicit request wakes the `Ui` for a single update.
+    ui.request_redraw();
+    assert!(ui.redraw_needed());
+    assert!(update(ui, true));
+    assert!(!ui.update_needed());
+
+    // Closing the collapsible keeps the `Ui` awake until the animation settles.
+    ui.request_redraw();
+    assert!(update(ui, false));
+    for _ in 0..3 {
+        ui.step(&[], Duration::from_millis(50));
+        assert!(ui.update_needed());
+        assert!(update(ui, false));
+    }
+    ui.step(&[], Duration::from_millis(200));
+    while update(ui, false) {
+        draw_all(ui);
+    }
+    draw_all(ui);
+    assert!(!ui.redraw_needed());
+}
+
+#[test]
+fn pressed_accelerators_should_be_dispatched_to_the_registered_widget() {
+    use input::keyboard::{ALT, CTRL};
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    // Registers an accelerator for each button, returning those that each button received.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().set(a, ui);
+        widget::Button::new().set(b, ui);
+        ui.register_accelerator(input::Accelerator::alt(Key::F), a).unwrap();
+        ui.register_accelerator(input::Accelerator::new(CTRL, Key::S), b).unwrap();
+        let a_accelerators = ui.widget_input(a).accelerators().collect::<Vec<_>>();
+        let b_accelerators = ui.widget_input(b).accelerators().collect::<Vec<_>>();
+        (a_accelerators, b_accelerators)
+    };
+    set_widgets(ui);
+
+    // Accelerators are dispatched regardless of which widget is capturing the keyboard.
+    ui.focus_widget(b);
+    press_chord(ui, Key::LAlt, Key::F);
+    let expected = vec![input::Accelerator::new(ALT, Key::F)];
+    assert_eq!((expected, vec![]), set_widgets(ui));
+
+    press_chord(ui, Key::RCtrl, Key::S);
+    let expected = vec![input::Accelerator::new(CTRL, Key::S)];
+    assert_eq!((vec![], expected), set_widgets(ui));
+
+    // The key alone, or with the wrong modifiers, activates nothing.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::F)));
+    press_chord(ui, Key::LCtrl, Key::F);
+    assert_eq!((vec![], vec![]), set_widgets(ui));
+}
+
+#[test]
+fn registering_an_accelerator_for_two_widgets_should_report_a_conflict() {
+    use input::accelerator::Conflict;
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let accelerator = input::Accelerator::alt(Key::O);
+    let conflict = Conflict { accelerator: accelerator, registered: a, rejected: b };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().set(a, ui);
+        widget::Button::new().set(b, ui);
+        assert_eq!(Ok(()), ui.register_accelerator(accelerator, a));
+        assert_eq!(Err(conflict), ui.register_accelerator(accelerator, b));
+    }
+    assert_eq!(&[conflict][..], ui.accelerators().conflicts());
+
+    // The first widget to register the accelerator remains its target.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LAlt)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::O)));
+    {
+        let ui = &mut ui.set_widgets();
+        assert_eq!(1, ui.widget_input(a).accelerators().count());
+        assert_eq!(0, ui.widget_input(b).accelerators().count());
+    }
+
+    // Conflicts are forgotten once the widgets stop registering the accelerator.
+    assert!(ui.accelerators().conflicts().is_empty());
+}
+
+#[test]
+fn shortcut_help_should_list_the_accelerators_of_instantiated_widgets_by_chord() {
+    use input::keyboard::{CTRL, SHIFT};
+    let ui = &mut windowless_ui();
+    let (save, open, unmounted) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next(), ids.next())
+    };
+    let set_widgets = |ui: &mut Ui, show_open: bool| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().set(save, ui);
+        ui.register_accelerator_with_description(input::Accelerator::new(CTRL, Key::S), save,
+                                                 "Save").unwrap();
+        ui.register_accelerator_with_description(input::Accelerator::new(CTRL | SHIFT, Key::S),
+                                                 save, "Save As").unwrap();
+        if show_open {
+            widget::Button::new().set(open, ui);
+        }
+        ui.register_accelerator(input::Accelerator::alt(Key::O), open).unwrap();
+        // Never instantiated, so never listed.
+        ui.register_accelerator_with_description(input::Accelerator::alt(Key::Q), unmounted,
+                                                 "Quit").unwrap();
+    };
+    let entry = |chord: &str, description: &str| (chord.to_string(), description.to_string());
+
+    set_widgets(ui, true);
+    let expected = vec![
+        entry("Alt+O", ""),
+        entry("Ctrl+S", "Save"),
+        entry("Ctrl+Shift+S", "Save As"),
+    ];
+    assert_eq!(expected, ui.shortcut_help());
+
+    // Once a widget is unmounted, its accelerators disappear from the list.
+    set_widgets(ui, false);
+    let expected = vec![entry("Ctrl+S", "Save"), entry("Ctrl+Shift+S", "Save As")];
+    assert_eq!(expected, ui.shortcut_help());
+}
+
+#[test]
+fn text_edit_should_copy_cut_and_paste_via_the_clipboard() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = "hello".to_string();
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        set_text_edit(ui, text_edit, text, |text_edit| text_edit.w_h(400.0, 100.0));
+    };
+    // Presses the given key while holding `Ctrl`, then updates the widgets.
+    let press_ctrl_and_set = |ui: &mut Ui, text: &mut String, key: Key| {
+        press_ctrl(ui, key);
+        set_widgets(ui, text);
+    };
+    set_widgets(ui, &mut text);
+    ui.focus_widget(text_edit);
+    set_widgets(ui, &mut text);
+
+    // Copying without a selection leaves the clipboard untouched.
+    press_ctrl_and_set(ui, &mut text, Key::C);
+    assert_eq!(None, ui.clipboard().contents());
+
+    // Copy all text, then paste it at the end.
+    press_ctrl_and_set(ui, &mut text, Key::A);
+    press_ctrl_and_set(ui, &mut text, Key::C);
+    assert_eq!(Some("hello".to_string()), ui.clipboard().contents());
+    press_key(ui, Key::Right);
+    set_widgets(ui, &mut text);
+    press_ctrl_and_set(ui, &mut text, Key::V);
+    assert_eq!("hellohello", text);
+
+    // Pasting replaces the selection and leaves the caret after the pasted text.
+    ui.clipboard().set_contents("bye".to_string());
+    press_ctrl_and_set(ui, &mut text, Key::A);
+    press_ctrl_and_set(ui, &mut text, Key::V);
+    assert_eq!("bye", text);
+    press_ctrl_and_set(ui, &mut text, Key::V);
+    assert_eq!("byebye", text);
+
+    // Cutting copies the selection before removing it.
+    press_ctrl_and_set(ui, &mut text, Key::A);
+    press_ctrl_and_set(ui, &mut text, Key::X);
+    assert_eq!("", text);
+    assert_eq!(Some("byebye".to_string()), ui.clipboard().contents());
+}
+
+#[test]
+fn ui_should_be_send_along_with_its_clipboard() {
+    fn assert_send<T: Send>(_: &T) {}
+    let ui = UiBuilder::new([800.0, 600.0]).clipboard(Box::new(::clipboard::Memory::new())).build();
+    assert_send(&ui);
+}
+
+#[test]
+fn text_edit_should_undo_typed_characters_together_and_truncate_redo_history() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = String::new();
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        set_text_edit(ui, text_edit, text, |text_edit| text_edit.w_h(400.0, 100.0));
+    };
+    let type_chars = |ui: &mut Ui, text: &mut String, chars: &str| {
+        for ch in chars.chars() {
+            ui.handle_event(Input::Text(ch.to_string()));
+            set_widgets(ui, text);
+        }
+    };
+    let press_ctrl_and_set = |ui: &mut Ui, text: &mut String, key: Key| {
+        press_ctrl(ui, key);
+        set_widgets(ui, text);
+    };
+    set_widgets(ui, &mut text);
+    ui.focus_widget(text_edit);
+    set_widgets(ui, &mut text);
+
+    // Consecutively typed characters are undone and redone in a single step.
+    type_chars(ui, &mut text, "abc");
+    assert_eq!("abc", text);
+    press_ctrl_and_set(ui, &mut text, Key::Z);
+    assert_eq!("", text);
+    press_ctrl_and_set(ui, &mut text, Key::Y);
+    assert_eq!("abc", text);
+
+    // Moving the cursor begins a new step.
+    press_key(ui, Key::Left);
+    set_widgets(ui, &mut text);
+    type_chars(ui, &mut text, "x");
+    assert_eq!("abxc", text);
+    press_ctrl_and_set(ui, &mut text, Key::Z);
+    assert_eq!("abc", text);
+
+    // A new edit after undoing discards the undone edit.
+    type_chars(ui, &mut text, "d");
+    assert_eq!("abdc", text);
+    press_ctrl_and_set(ui, &mut text, Key::Y);
+    assert_eq!("abdc", text);
+    press_ctrl_and_set(ui, &mut text, Key::Z);
+    assert_eq!("abc", text);
+    press_ctrl_and_set(ui, &mut text, Key::Z);
+    assert_eq!("", text);
+}
+
+#[test]
+fn text_edit_should_display_the_composition_underlined_until_committed_or_cancelled() {
+    use widget::text_edit::COMPOSITION_UNDERLINE_THICKNESS;
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = "ab".to_string();
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        set_text_edit(ui, text_edit, text, |text_edit| text_edit.w_h(400.0, 100.0));
+    };
+    let is_underlined = |ui: &Ui| {
+        ui.find_widget(|info| {
+            info.is::<widget::Rectangle>() && info.rect.h() == COMPOSITION_UNDERLINE_THICKNESS
+        }).is_some()
+    };
+    set_widgets(ui, &mut text);
+    ui.focus_widget(text_edit);
+    press_key(ui, Key::End);
+    set_widgets(ui, &mut text);
+    assert!(is_text_displayed(ui, "ab"));
+    assert!(!is_underlined(ui));
+
+    // The pre-edit text is displayed underlined at the cursor without changing the text.
+    ui.handle_composition(event::Composition::PreEdit("ni".to_string()));
+    set_widgets(ui, &mut text);
+    assert_eq!("ab", text);
+    assert!(is_text_displayed(ui, "abni"));
+    assert!(is_underlined(ui));
+    ui.handle_composition(event::Composition::PreEdit("nih".to_string()));
+    set_widgets(ui, &mut text);
+    assert!(is_text_displayed(ui, "abnih"));
+
+    // Committing replaces the pre-edit text with the committed text.
+    ui.handle_composition(event::Composition::Commit("NI".to_string()));
+    set_widgets(ui, &mut text);
+    assert_eq!("abNI", text);
+    assert!(is_text_displayed(ui, "abNI"));
+    assert!(!is_underlined(ui));
+
+    // Cancelling removes the pre-edit text.
+    ui.handle_composition(event::Composition::PreEdit("x".to_string()));
+    set_widgets(ui, &mut text);
+    assert!(is_text_displayed(ui, "abNIx"));
+    ui.handle_composition(event::Composition::Commit(String::new()));
+    set_widgets(ui, &mut text);
+    assert_eq!("abNI", text);
+    assert!(is_text_displayed(ui, "abNI"));
+    assert!(!is_underlined(ui));
+}
+
+#[test]
+fn numeric_field_should_reject_invalid_keystrokes_and_produce_parsed_values() {
+    let ui = &mut windowless_ui_with_font();
+    let field = ui.widget_id_generator().next();
+    let mut value = 1234.5;
+    // Updates the `NumericField`, applying any newly entered value.
+    let set_widgets = |ui: &mut Ui, value: &mut f64| {
+        let ui = &mut ui.set_widgets();
+        if let Some(new_value) = widget::NumericField::new(*value).w_h(200.0, 40.0).set(field, ui) {
+            *value = new_value;
+        }
+    };
+    let type_char = |ui: &mut Ui, value: &mut f64, ch: char| {
+        ui.handle_event(Input::Text(ch.to_string()));
+        set_widgets(ui, value);
+    };
+    set_widgets(ui, &mut value);
+    assert!(is_text_displayed(ui, "1,234.50"));
+
+    // Clicking the field begins editing the value without separators.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut value);
+    assert!(is_text_displayed(ui, "1234.50"));
+
+    // Replace the text, keystroke by keystroke.
+    press_ctrl(ui, Key::A);
+    set_widgets(ui, &mut value);
+    type_char(ui, &mut value, '-');
+    assert!(is_text_displayed(ui, "-"));
+    assert_eq!(1234.5, value);
+    // A rejected keystroke is discarded by the following update.
+    type_char(ui, &mut value, 'x');
+    set_widgets(ui, &mut value);
+    assert!(is_text_displayed(ui, "-"));
+    type_char(ui, &mut value, '2');
+    assert_eq!(-2.0, value);
+    type_char(ui, &mut value, '.');
+    assert!(is_text_displayed(ui, "-2."));
+    assert_eq!(-2.0, value);
+    type_char(ui, &mut value, '2');
+    type_char(ui, &mut value, '5');
+    assert_eq!(-2.25, value);
+
+    // Keystrokes exceeding the decimal places are rejected.
+    type_char(ui, &mut value, '9');
+    set_widgets(ui, &mut value);
+    assert!(is_text_displayed(ui, "-2.25"));
+    assert_eq!(-2.25, value);
+
+    // Once the field loses the keyboard, the value is formatted again.
+    move_mouse_to_abs_coordinates(300.0, 250.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut value);
+    assert!(is_text_displayed(ui, "-2.25"));
+    assert_eq!(-2.25, value);
+}
+
+#[test]
+fn numeric_field_should_undo_and_redo_each_edit_of_its_value_in_a_single_step() {
+    let ui = &mut windowless_ui_with_font();
+    let field = ui.widget_id_generator().next();
+    let mut value = 1.0;
+    let set_widgets = |ui: &mut Ui, value: &mut f64| {
+        let ui = &mut ui.set_widgets();
+        if let Some(new_value) = widget::NumericField::new(*value).w_h(200.0, 40.0).set(field, ui) {
+            *value = new_value;
+        }
+    };
+    let type_chars = |ui: &mut Ui, value: &mut f64, chars: &str| {
+        for ch in chars.chars() {
+            ui.handle_event(Input::Text(ch.to_string()));
+            set_widgets(ui, value);
+        }
+    };
+    let press_ctrl_and_set = |ui: &mut Ui, value: &mut f64, key: Key| {
+        press_ctrl(ui, key);
+        set_widgets(ui, value);
+    };
+    let click_at = |ui: &mut Ui, value: &mut f64, x: f64, y: f64| {
+        move_mouse_to_abs_coordinates(x, y, ui);
+        left_click_mouse(ui);
+        set_widgets(ui, value);
+    };
+    set_widgets(ui, &mut value);
+
+    // Each value entered while editing is undone together.
+    click_at(ui, &mut value, 0.0, 0.0);
+    press_ctrl_and_set(ui, &mut value, Key::A);
+    type_chars(ui, &mut value, "25");
+    assert_eq!(25.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(1.0, value);
+    assert!(is_text_displayed(ui, "1.00"));
+    press_ctrl_and_set(ui, &mut value, Key::Y);
+    assert_eq!(25.0, value);
+    assert!(is_text_displayed(ui, "25.00"));
+
+    // Editing the field again begins a new step.
+    click_at(ui, &mut value, 300.0, 250.0);
+    click_at(ui, &mut value, 0.0, 0.0);
+    press_ctrl_and_set(ui, &mut value, Key::A);
+    type_chars(ui, &mut value, "7");
+    assert_eq!(7.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(25.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(1.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(1.0, value);
+}
+
+#[test]
+fn number_dialer_should_undo_and_redo_each_drag_in_a_single_step() {
+    let ui = &mut windowless_ui_with_font();
+    let dialer = ui.widget_id_generator().next();
+    let mut value = 50.0;
+    let set_widgets = |ui: &mut Ui, value: &mut f64| {
+        let ui = &mut ui.set_widgets();
+        if let Some(new_value) = widget::NumberDialer::new(*value, 0.0, 100.0, 0)
+            .w_h(200.0, 40.0)
+            .set(dialer, ui)
+        {
+            *value = new_value;
+        }
+    };
+    let press_ctrl_and_set = |ui: &mut Ui, value: &mut f64, key: Key| {
+        press_ctrl(ui, key);
+        set_widgets(ui, value);
+    };
+    set_widgets(ui, &mut value);
+
+    // Drag the tens digit of "050" upwards over two updates.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui, &mut value);
+    move_mouse_to_abs_coordinates(0.0, 10.0, ui);
+    set_widgets(ui, &mut value);
+    move_mouse_to_abs_coordinates(0.0, 20.0, ui);
+    set_widgets(ui, &mut value);
+    release_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui, &mut value);
+    assert_eq!(70.0, value);
+
+    // The whole drag is undone and redone at once.
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(50.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Z);
+    assert_eq!(50.0, value);
+    press_ctrl_and_set(ui, &mut value, Key::Y);
+    assert_eq!(70.0, value);
+}
+
+#[test]
+fn password_text_edit_should_mask_the_displayed_glyphs_but_edit_the_real_text() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = "päss".to_string();
+    let set_widgets = |ui: &mut Ui, text: &mut String, reveal: bool| {
+        set_text_edit(ui, text_edit, text, |text_edit| {
+            text_edit.password(true).reveal(reveal).w_h(400.0, 100.0)
+        });
+    };
+    set_widgets(ui, &mut text, false);
+    ui.focus_widget(text_edit);
+    press_key(ui, Key::End);
+    set_widgets(ui, &mut text, false);
+    assert!(is_text_displayed(ui, "\u{2022}\u{2022}\u{2022}\u{2022}"));
+
+    // The cursor moves over the real chars behind the mask.
+    press_key(ui, Key::Left);
+    ui.handle_event(Input::Text("X".to_string()));
+    set_widgets(ui, &mut text, false);
+    assert_eq!("päsXs", text);
+    assert!(is_text_displayed(ui, "\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}"));
+
+    // Typing over a selection replaces the selected real chars.
+    press_chord(ui, Key::LShift, Key::Left);
+    ui.handle_event(Input::Text("Y".to_string()));
+    set_widgets(ui, &mut text, false);
+    assert_eq!("päsYs", text);
+
+    // Passwords may not be copied.
+    press_ctrl(ui, Key::A);
+    press_ctrl(ui, Key::C);
+    set_widgets(ui, &mut text, false);
+    assert_eq!(None, ui.clipboard().contents());
+
+    // Revealing the password displays the real text.
+    set_widgets(ui, &mut text, true);
+    assert!(is_text_displayed(ui, "päsYs"));
+}
+
+#[test]
+fn autocomplete_should_filter_navigate_and_complete_suggestions() {
+    let ui = &mut windowless_ui_with_font();
+    let autocomplete = ui.widget_id_generator().next();
+    let candidates = ["apple", "apricot", "banana"];
+    let mut text = String::new();
+    // Updates the `Autocomplete`, applying any edits or completions to the `text`.
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        let ui = &mut ui.set_widgets();
+        let events = widget::Autocomplete::new(text, &candidates)
+            .w_h(200.0, 40.0)
+            .set(autocomplete, ui);
+        for event in events {
+            match event {
+                widget::autocomplete::Event::Update(new_text) => *text = new_text,
+                widget::autocomplete::Event::Complete(i) => *text = candidates[i].to_string(),
+            }
+        }
+    };
+    let type_char = |ui: &mut Ui, text: &mut String, ch: char| {
+        ui.handle_event(Input::Text(ch.to_string()));
+        set_widgets(ui, text);
+    };
+    let press_and_set = |ui: &mut Ui, text: &mut String, key: Key| {
+        press_key(ui, key);
+        set_widgets(ui, text);
+    };
+
+    // Focus the field.
+    set_widgets(ui, &mut text);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut text);
+
+    // Only the candidates beginning with the text are suggested.
+    type_char(ui, &mut text, 'a');
+    type_char(ui, &mut text, 'p');
+    assert!(is_text_displayed(ui, "apple"));
+    assert!(is_text_displayed(ui, "apricot"));
+    assert!(!is_text_displayed(ui, "banana"));
+
+    // The drop down list closes while the text matches no candidates.
+    type_char(ui, &mut text, 'z');
+    assert!(!is_text_displayed(ui, "apple"));
+    assert!(!is_text_displayed(ui, "apricot"));
+    press_and_set(ui, &mut text, Key::Backspace);
+    assert_eq!("ap", text);
+    assert!(is_text_displayed(ui, "apricot"));
+
+    // Navigation is clamped to the suggestions and `Return` completes the highlighted one.
+    press_and_set(ui, &mut text, Key::Down);
+    press_and_set(ui, &mut text, Key::Down);
+    press_and_set(ui, &mut text, Key::Down);
+    press_and_set(ui, &mut text, Key::Return);
+    set_widgets(ui, &mut text);
+    assert_eq!("apricot", text);
+    assert!(!is_text_displayed(ui, "apple"));
+
+    // `Tab` completes the first suggestion when none is highlighted.
+    press_ctrl(ui, Key::A);
+    set_widgets(ui, &mut text);
+    type_char(ui, &mut text, 'B');
+    assert!(is_text_displayed(ui, "banana"));
+    press_and_set(ui, &mut text, Key::Tab);
+    set_widgets(ui, &mut text);
+    assert_eq!("banana", text);
+    assert!(!is_text_displayed(ui, "apple"));
+}
+
+#[test]
+fn text_edit_should_select_words_whitespace_and_lines_on_multiple_clicks() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    let original = "foo          bar\nbaz";
+    let mut text = original.to_string();
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        set_text_edit(ui, text_edit, text, |text_edit| text_edit.font_size(40).w_h(400.0, 200.0));
+    };
+    // Clicks the given number of times at the given position, then replaces the selection.
+    let click_and_type = |ui: &mut Ui, text: &mut String, x: f64, y: f64, clicks: usize| {
+        move_mouse_to_abs_coordinates(x, y, ui);
+        for _ in 0..clicks {
+            left_click_mouse(ui);
+        }
+        set_widgets(ui, text);
+        ui.handle_event(Input::Text("X".to_string()));
+        set_widgets(ui, text);
+    };
+    set_widgets(ui, &mut text);
+
+    // Double clicking a word selects the word.
+    click_and_type(ui, &mut text, -195.0, 80.0, 2);
+    assert_eq!("X          bar\nbaz", text);
+
+    // Double clicking whitespace selects the whole run of whitespace.
+    text = original.to_string();
+    set_widgets(ui, &mut text);
+    click_and_type(ui, &mut text, -90.0, 80.0, 2);
+    assert_eq!("fooXbar\nbaz", text);
+
+    // Triple clicking selects the line, excluding the line break.
+    text = original.to_string();
+    set_widgets(ui, &mut text);
+    click_and_type(ui, &mut text, -185.0, 80.0, 3);
+    assert_eq!("X\nbaz", text);
+}
+
+#[test]
+fn text_edit_should_move_over_and_delete_whole_grapheme_clusters() {
+    let ui = &mut windowless_ui_with_font();
+    let text_edit = ui.widget_id_generator().next();
+    // Thumbs up emoji with a skin-tone modifier.
+    let thumbs_up = "\u{1F44D}\u{1F3FD}";
+    let mut text = format!("a{}b{}", thumbs_up, thumbs_up);
+    let set_widgets = |ui: &mut Ui, text: &mut String| {
+        set_text_edit(ui, text_edit, text, |text_edit| text_edit.w_h(400.0, 100.0));
+    };
+    let press_and_set = |ui: &mut Ui, text: &mut String, key: Key| {
+        press_key(ui, key);
+        set_widgets(ui, text);
+    };
+    set_widgets(ui, &mut text);
+
+    // Place the cursor at the end of the text.
+    move_mouse_to_abs_coordinates(190.0, 40.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut text);
+
+    // Backspace removes the emoji along with its modifier.
+    press_and_set(ui, &mut text, Key::Backspace);
+    assert_eq!(format!("a{}b", thumbs_up), text);
+
+    // Moving left skips over the whole emoji, so that delete removes all of it.
+    press_and_set(ui, &mut text, Key::Left);
+    press_and_set(ui, &mut text, Key::Left);
+    press_and_set(ui, &mut text, Key::Delete);
+    assert_eq!("ab", text);
+}
+
+#[test]
+fn drop_down_list_should_scroll_items_highlighted_via_the_keyboard_into_view() {
+    let ui = &mut windowless_ui_with_font();
+    let drop_down_list = ui.widget_id_generator().next();
+    let items: Vec<String> = (0..20).map(|i| format!("Item {}", i)).collect();
+    let mut selected = None;
+    let set_widgets = |ui: &mut Ui, selected: &mut Option<usize>| {
+        let ui = &mut ui.set_widgets();
+        if let Some(i) = widget::DropDownList::new(&items, *selected)
+            .w_h(200.0, 40.0)
+            .max_visible_items(3)
+            .set(drop_down_list, ui)
+        {
+            *selected = Some(i);
+        }
+    };
+    let press_and_set = |ui: &mut Ui, selected: &mut Option<usize>, key: Key| {
+        press_key(ui, key);
+        set_widgets(ui, selected);
+    };
+    // The vertical scroll offset of the open menu's `List`.
+    let scroll_offset = |ui: &Ui| {
+        let list = ui.find_widget(|info| info.is::<widget::List>()).unwrap();
+        ui.widget_graph().widget(list)
+            .and_then(|container| container.maybe_y_scroll_state.as_ref())
+            .map(|scroll_state| scroll_state.offset)
+            .unwrap()
+    };
+
+    // Open the menu.
+    set_widgets(ui, &mut selected);
+    move_mouse_to_abs_coordinates(400.0, 300.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, &mut selected);
+    set_widgets(ui, &mut selected);
+    assert_eq!(0.0, scroll_offset(ui));
+
+    // Highlighting the fifth item scrolls it to the bottom of the three visible items.
+    for _ in 0..5 {
+        press_and_set(ui, &mut selected, Key::Down);
+    }
+    set_widgets(ui, &mut selected);
+    assert_eq!(80.0, scroll_offset(ui));
+
+    // Items that are already visible do not cause scrolling.
+    press_and_set(ui, &mut selected, Key::Up);
+    press_and_set(ui, &mut selected, Key::Up);
+    set_widgets(ui, &mut selected);
+    assert_eq!(80.0, scroll_offset(ui));
+
+    // Highlighting an item above the visible items scrolls it to the top.
+    press_and_set(ui, &mut selected, Key::Up);
+    set_widgets(ui, &mut selected);
+    assert_eq!(40.0, scroll_offset(ui));
+
+    // `Return` selects the highlighted item and closes the menu.
+    press_and_set(ui, &mut selected, Key::Return);
+    set_widgets(ui, &mut selected);
+    assert_eq!(Some(1), selected);
+    assert!(ui.find_widget(|info| info.is::<widget::List>()).is_none());
+}
+
+#[test]
+fn table_should_sort_rows_stably_by_the_clicked_column_header() {
+    use widget::table::{Order, Sort};
+    let ui = &mut windowless_ui_with_font();
+    let table = ui.widget_id_generator().next();
+    let columns = ["Name", "Age"];
+    let rows = vec![
+        vec!["Carol", "30"],
+        vec!["Alice", "25"],
+        vec!["Bob", "30"],
+        vec!["Dave", "9"],
+    ];
+    // The table is 200x200 with a 40 high header, so the "Age" header is centred at (50, 80).
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Table::new(&columns, &rows)
+            .w_h(200.0, 200.0)
+            .row_h(40.0)
+            .set(table, ui)
+    };
+    // The names in order of display from the top.
+    let names = |ui: &Ui| {
+        let mut names: Vec<(f64, &str)> = ["Alice", "Bob", "Carol", "Dave"].iter()
+            .map(|&name| {
+                let text = ui.find_widget(|info| {
+                    info.is::<widget::Text>() && info.text == Some(name)
+                }).unwrap();
+                (-ui.xy_of(text).unwrap()[1], name)
+            })
+            .collect();
+        names.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        names.into_iter().map(|(_, name)| name).collect::<Vec<_>>()
+    };
+
+    set_widgets(ui);
+    assert_eq!(vec!["Carol", "Alice", "Bob", "Dave"], names(ui));
+
+    // The first click sorts in ascending order, keeping Carol above Bob.
+    move_mouse_to_abs_coordinates(450.0, 220.0, ui);
+    left_click_mouse(ui);
+    let sort = set_widgets(ui);
+    assert_eq!(Some(Sort { column: 1, order: Order::Ascending }), sort);
+    set_widgets(ui);
+    assert_eq!(vec!["Dave", "Alice", "Carol", "Bob"], names(ui));
+
+    // The second click toggles to descending order, still keeping Carol above Bob.
+    left_click_mouse(ui);
+    let sort = set_widgets(ui);
+    assert_eq!(Some(Sort { column: 1, order: Order::Descending }), sort);
+    set_widgets(ui);
+    assert_eq!(vec!["Carol", "Bob", "Alice", "Dave"], names(ui));
+}
+
+#[test]
+fn menu_bar_should_open_submenus_on_hover_and_close_on_activation_or_outside_presses() {
+    use widget::menu_bar::Item;
+    let ui = &mut windowless_ui_with_font();
+    let window = ui.window;
+    let menu_bar = ui.widget_id_generator().next();
+    let menus = vec![
+        Item::new("File")
+            .child(Item::new("New"))
+            .child(Item::new("Open Recent").child(Item::new("a.txt")).child(Item::new("b.txt"))),
+        Item::new("Edit").child(Item::new("Undo")),
+    ];
+    // The bar spans the top 30 of the window, with "File" spanning `-400.0..-320.0` along *x*.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::MenuBar::new(&menus).w_h(800.0, 30.0).mid_top_of(window).set(menu_bar, ui)
+    };
+    let text_rect = |ui: &Ui, label: &str| {
+        ui.find_widget(|info| info.is::<widget::Text>() && info.text == Some(label))
+            .and_then(|text| ui.rect_of(text))
+    };
+
+    // Clicking "File" opens its menu beneath it.
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(40.0, 15.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(None, set_widgets(ui));
+    set_widgets(ui);
+    assert!(text_rect(ui, "New").is_some());
+    assert!(text_rect(ui, "a.txt").is_none());
+
+    // Hovering over "Open Recent" opens its submenu to the right of the menu.
+    move_mouse_to_abs_coordinates(75.0, 75.0, ui);
+    set_widgets(ui);
+    set_widgets(ui);
+    let a_txt = text_rect(ui, "a.txt").unwrap();
+    assert!(a_txt.left() > -250.0 && a_txt.right() < -100.0);
+
+    // Clicking "b.txt" activates it and closes all menus.
+    move_mouse_to_abs_coordinates(225.0, 105.0, ui);
+    set_widgets(ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(vec![0, 1, 1]), set_widgets(ui));
+    set_widgets(ui);
+    assert!(text_rect(ui, "New").is_none());
+    assert!(text_rect(ui, "b.txt").is_none());
+
+    // Hovering over another menu while one is open opens it instead.
+    move_mouse_to_abs_coordinates(40.0, 15.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(120.0, 15.0, ui);
+    set_widgets(ui);
+    set_widgets(ui);
+    assert!(text_rect(ui, "Undo").is_some());
+    assert!(text_rect(ui, "New").is_none());
+
+    // Pressing outside of the bar and its menus closes them.
+    move_mouse_to_abs_coordinates(400.0, 500.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(None, set_widgets(ui));
+    set_widgets(ui);
+    assert!(text_rect(ui, "Undo").is_none());
+}
+
+#[test]
+fn menu_bar_submenus_should_flip_to_the_left_near_the_right_edge_of_the_window() {
+    use widget::menu_bar::Item;
+    let ui = &mut windowless_ui_with_font();
+    let window = ui.window;
+    let menu_bar = ui.widget_id_generator().next();
+    let menus = vec![
+        Item::new("File").child(Item::new("Quit")),
+        Item::new("View")
+            .child(Item::new("Zoom").child(Item::new("Presets").child(Item::new("100%")))),
+    ];
+    // "View" spans `-20.0..360.0`, so its menu spans `-20.0..130.0` and the "Zoom" submenu spans
+    // `130.0..280.0`. The "Presets" submenu would extend beyond the window and so flips left.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::MenuBar::new(&menus)
+            .bar_item_w(380.0)
+            .w_h(800.0, 30.0)
+            .mid_top_of(window)
+            .set(menu_bar, ui)
+    };
+    let press_and_set = |ui: &mut Ui, key: Key| {
+        press_key(ui, key);
+        set_widgets(ui);
+    };
+    let text_rect = |ui: &Ui, label: &str| {
+        ui.find_widget(|info| info.is::<widget::Text>() && info.text == Some(label))
+            .and_then(|text| ui.rect_of(text))
+    };
+
+    // Open "View" and navigate into the nested submenus via the keyboard.
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(570.0, 15.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui);
+    press_and_set(ui, Key::Down);
+    press_and_set(ui, Key::Right);
+    set_widgets(ui);
+    let presets = text_rect(ui, "Presets").unwrap();
+    assert!(presets.left() > 130.0 && presets.right() < 280.0);
+    let percent = text_rect(ui, "100%").unwrap();
+    assert!(percent.left() > -20.0 && percent.right() < 130.0);
+
+    // `Left` closes the innermost submenu, while `Escape` closes the rest.
+    press_and_set(ui, Key::Left);
+    set_widgets(ui);
+    assert!(text_rect(ui, "100%").is_none());
+    assert!(text_rect(ui, "Zoom").is_some());
+    press_and_set(ui, Key::Escape);
+    set_widgets(ui);
+    assert!(text_rect(ui, "Zoom").is_none());
+}
+
+#[test]
+fn split_pane_ratio_should_round_trip_after_dragging_the_divider() {
+    use Axis;
+    // The panes share 400.0 less the 6.0 thick divider.
+    let set_widgets = |ui: &mut Ui, split_pane: widget::Id, ratio: f64| {
+        let ui = &mut ui.set_widgets();
+        widget::SplitPane::new(Axis::X, ratio).w_h(400.0, 200.0).set(split_pane, ui)
+    };
+
+    let ui = &mut windowless_ui();
+    let split_pane = ui.widget_id_generator().next();
+    let event = set_widgets(ui, split_pane, 0.25);
+    assert_eq!(None, event.ratio);
+    assert_eq!(98.5, ui.rect_of(event.first).unwrap().w());
+
+    // Drag the divider 50.0 to the right.
+    move_mouse_to_abs_coordinates(301.5, 300.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(351.5, 300.0, ui);
+    let ratio = set_widgets(ui, split_pane, 0.25).ratio.unwrap();
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(148.5 / 394.0, ratio);
+
+    // Restoring the ratio within a new `Ui` reproduces the same panes.
+    let ui = &mut windowless_ui();
+    let split_pane = ui.widget_id_generator().next();
+    let event = set_widgets(ui, split_pane, ratio);
+    assert_eq!(None, event.ratio);
+    let first = ui.rect_of(event.first).unwrap();
+    let second = ui.rect_of(event.second).unwrap();
+    assert!((first.w() - 148.5).abs() < 1e-9);
+    assert!((second.w() - 245.5).abs() < 1e-9);
+}
+
+#[test]
+fn split_pane_should_clamp_restored_ratios_that_violate_the_minimum_lengths() {
+    use Axis;
+    let ui = &mut windowless_ui();
+    let split_pane = ui.widget_id_generator().next();
+    let set_widgets = |ui: &mut Ui, ratio: f64| {
+        let ui = &mut ui.set_widgets();
+        widget::SplitPane::new(Axis::Y, ratio)
+            .w_h(200.0, 406.0)
+            .min_lengths(50.0, 100.0)
+            .set(split_pane, ui)
+    };
+
+    // The second pane is kept at least 100.0 tall.
+    let event = set_widgets(ui, 0.95);
+    assert_eq!(Some(0.75), event.ratio);
+    assert_eq!(100.0, ui.rect_of(event.second).unwrap().h());
+    assert_eq!(300.0, ui.rect_of(event.first).unwrap().h());
+
+    // The first pane is kept at least 50.0 tall.
+    let event = set_widgets(ui, 0.0);
+    assert_eq!(Some(0.125), event.ratio);
+    assert_eq!(50.0, ui.rect_of(event.first).unwrap().h());
+
+    // Ratios within the limits are left untouched.
+    assert_eq!(None, set_widgets(ui, 0.5).ratio);
+}
+
+#[test]
+fn spinner_should_rotate_with_elapsed_time_and_keep_the_ui_awake_while_visible() {
+    use render::PrimitiveKind;
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    let spinner = ui.widget_id_generator().next();
+    let set_widgets = |ui: &mut Ui, visible: bool| {
+        let ui = &mut ui.set_widgets();
+        if visible {
+            widget::Spinner::new().w_h(54.0, 54.0).x_y(0.0, 0.0).thickness(4.0).set(spinner, ui);
+        }
+    };
+    // Asserts that the leading end of the spinner's arc lies at the given point.
+    let assert_arc_head = |ui: &mut Ui, expected: Point| {
+        let mut primitives = ui.draw_primitives();
+        while let Some(primitive) = primitives.next() {
+            if let PrimitiveKind::Lines { points, .. } = primitive.kind {
+                let head = points[points.len() - 1];
+                assert!((head[0] - expected[0]).abs() < 1e-6, "{:?} != {:?}", head, expected);
+                assert!((head[1] - expected[1]).abs() < 1e-6, "{:?} != {:?}", head, expected);
+                return;
+            }
+        }
+        panic!("expected the spinner's arc");
+    };
+
+    // The arc begins at the top of the spinner.
+    ui.step(&[], Duration::from_secs(0));
+    set_widgets(ui, true);
+    assert_arc_head(ui, [0.0, 25.0]);
+    assert!(ui.update_needed());
+
+    // A quarter of a revolution passes without any input.
+    ui.step(&[], Duration::from_millis(250));
+    set_widgets(ui, true);
+    assert_arc_head(ui, [25.0, 0.0]);
+    assert!(ui.update_needed());
+
+    ui.step(&[], Duration::from_millis(500));
+    set_widgets(ui, true);
+    assert_arc_head(ui, [-25.0, 0.0]);
+
+    // Each update requests another redraw, so the spinner is drawn on every frame for longer than
+    // a single redraw request would last.
+    for _ in 0..::ui::SAFE_REDRAW_COUNT * 2 {
+        ui.step(&[], Duration::from_millis(16));
+        set_widgets(ui, true);
+        assert!(ui.update_needed());
+        assert!(ui.draw_if_changed().is_some());
+    }
+
+    // Once hidden, the spinner no longer keeps the `Ui` awake.
+    set_widgets(ui, false);
+    assert!(!ui.update_needed());
+    while ui.draw_if_changed().is_some() {}
+    assert!(!ui.redraw_needed());
+}
+
+#[test]
+fn radio_group_should_select_exclusively_by_click_and_wrap_select_via_the_arrow_keys() {
+    use color;
+    use render::PrimitiveKind;
+    let ui = &mut windowless_ui();
+    let radio_group = ui.widget_id_generator().next();
+    let options = ["Low", "Medium", "High"];
+    let set_widgets = |ui: &mut Ui, selected: Option<usize>| {
+        widget::RadioGroup::new(&options, selected)
+            .w_h(200.0, 90.0)
+            .x_y(0.0, 0.0)
+            .color(color::WHITE)
+            .dot_color(color::RED)
+            .set(radio_group, &mut ui.set_widgets())
+    };
+    // The *y* coordinate of the centre of each dot marking a selected option.
+    let dot_ys = |ui: &mut Ui| {
+        let mut ys = Vec::new();
+        let mut primitives = ui.draw_primitives();
+        while let Some(primitive) = primitives.next() {
+            if let PrimitiveKind::Polygon { color, points } = primitive.kind {
+                if color == color::RED {
+                    let sum = points.iter().fold(0.0, |sum, point| sum + point[1]);
+                    ys.push((sum / points.len() as f64).round());
+                }
+            }
+        }
+        ys
+    };
+
+    // Nothing is selected until an option is clicked.
+    assert_eq!(None, set_widgets(ui, None));
+    assert!(dot_ys(ui).is_empty());
+    move_mouse_to_abs_coordinates(400.0, 330.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(2), set_widgets(ui, None));
+    assert_eq!(vec![-30.0], dot_ys(ui));
+
+    // Clicking another option deselects the previous one.
+    move_mouse_to_abs_coordinates(400.0, 270.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(0), set_widgets(ui, Some(2)));
+    assert_eq!(vec![30.0], dot_ys(ui));
+    assert_eq!(None, set_widgets(ui, Some(0)));
+
+    // The clicked option captures the keyboard, so the arrow keys wrap and select immediately.
+    press_key(ui, Key::Up);
+    assert_eq!(Some(2), set_widgets(ui, Some(0)));
+    assert_eq!(vec![-30.0], dot_ys(ui));
+    press_key(ui, Key::Down);
+    assert_eq!(Some(0), set_widgets(ui, Some(2)));
+    press_key(ui, Key::Right);
+    assert_eq!(Some(1), set_widgets(ui, Some(0)));
+    assert_eq!(vec![0.0], dot_ys(ui));
+}
+
+#[test]
+fn breadcrumbs_should_collapse_middle_segments_into_a_menu_opened_by_the_ellipsis() {
+    let ui = &mut windowless_ui_with_font();
+    let breadcrumbs = ui.widget_id_generator().next();
+    let segments = ["Home", "Docs", "Rust", "Std", "Collections", "HashMap"];
+    // Four slots of 80 separated by 20 fit within 380, centred at the origin.
+    let set_widgets = |ui: &mut Ui, w: f64| {
+        widget::Breadcrumbs::new(&segments)
+            .w_h(w, 30.0)
+            .x_y(0.0, 0.0)
+            .segment_w(80.0)
+            .separator_w(20.0)
+            .set(breadcrumbs, &mut ui.set_widgets())
+    };
+
+    // Every segment is displayed when there is room and clicking one produces its index.
+    set_widgets(ui, 600.0);
+    assert!(segments.iter().all(|segment| is_text_displayed(ui, segment)));
+    assert!(!is_text_displayed(ui, "..."));
+    move_mouse_to_abs_coordinates(550.0, 300.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(4), set_widgets(ui, 600.0));
+
+    // Otherwise the middle segments collapse, leaving "Home", "...", "Collections", "HashMap".
+    assert_eq!(None, set_widgets(ui, 380.0));
+    for &(label, displayed) in &[("Home", true), ("...", true), ("Docs", false), ("Rust", false),
+                                 ("Std", false), ("Collections", true), ("HashMap", true)] {
+        assert_eq!(displayed, is_text_displayed(ui, label), "{}", label);
+    }
+    move_mouse_to_abs_coordinates(550.0, 300.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(5), set_widgets(ui, 380.0));
+
+    // Clicking the ellipsis lists the collapsed segments beneath it.
+    move_mouse_to_abs_coordinates(350.0, 300.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(None, set_widgets(ui, 380.0));
+    assert!(["Docs", "Rust", "Std"].iter().all(|segment| is_text_displayed(ui, segment)));
+
+    // Clicking a collapsed segment produces its index and closes the menu.
+    move_mouse_to_abs_coordinates(350.0, 360.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(Some(2), set_widgets(ui, 380.0));
+    set_widgets(ui, 380.0);
+    assert!(!is_text_displayed(ui, "Rust"));
+
+    // Pressing outside of the breadcrumbs and the open menu closes it.
+    move_mouse_to_abs_coordinates(350.0, 300.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, 380.0);
+    assert!(is_text_displayed(ui, "Rust"));
+    move_mouse_to_abs_coordinates(700.0, 500.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, 380.0);
+    set_widgets(ui, 380.0);
+    assert!(!is_text_displayed(ui, "Rust"));
+}
+
+#[test]
+fn window_title_bar_drags_should_move_the_window_and_clamp_it_within_its_parent() {
+    use position::Rect;
+    let ui = &mut windowless_ui();
+    let window = ui.widget_id_generator().next();
+    let set_widgets = |ui: &mut Ui, rect: Rect| {
+        widget::Window::new("Inspector")
+            .xy(rect.xy())
+            .wh(rect.dim())
+            .title_bar_h(30.0)
+            .set(window, &mut ui.set_widgets())
+    };
+
+    // The 30 high title bar lies along the top of the window, centred at (0, 60).
+    let rect = Rect::from_xy_dim([0.0, 0.0], [200.0, 150.0]);
+    assert_eq!(None, set_widgets(ui, rect).rect);
+    move_mouse_to_abs_coordinates(400.0, 240.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui, rect);
+
+    // Dragging the title bar moves the window by the same distance.
+    move_mouse_to_abs_coordinates(500.0, 190.0, ui);
+    let event = set_widgets(ui, rect);
+    let rect = event.rect.expect("expected the window to move");
+    assert_eq!(Rect::from_xy_dim([100.0, 50.0], [200.0, 150.0]), rect);
+    assert_eq!(Some(rect.pad_top(30.0)), ui.rect_of(event.body));
+
+    // Dragging past the top right corner of the window stops at the edges.
+    move_mouse_to_abs_coordinates(790.0, 10.0, ui);
+    let rect = set_widgets(ui, rect).rect.expect("expected the window to move");
+    assert_eq!(Rect::from_xy_dim([300.0, 225.0], [200.0, 150.0]), rect);
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(None, set_widgets(ui, rect).rect);
+
+    // A rect restored beyond the edges of the parent is clamped on-screen.
+    let restored = Rect::from_xy_dim([-450.0, -400.0], [200.0, 150.0]);
+    let clamped = set_widgets(ui, restored).rect;
+    assert_eq!(Some(Rect::from_xy_dim([-300.0, -225.0], [200.0, 150.0])), clamped);
+}
+
+#[test]
+fn tooltip_should_appear_for_a_hovered_widget_once_the_delay_has_passed() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui_with_font();
+    ui.theme.tooltip_delay = Duration::from_millis(500);
+    let (save, other) = {
+        let mut ids = ui.widget_id_generator();
+        (ids.next(), ids.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().w_h(100.0, 40.0).x_y(0.0, 0.0).tooltip("Save").set(save, ui);
+        widget::Button::new().w_h(100.0, 40.0).x_y(0.0, 100.0).set(other, ui);
+    };
+    let shown_tooltip = |ui: &Ui| ui.find_widget(|info| info.is::<widget::Tooltip>())
+        .map(|tooltip| ui.rect_of(tooltip).unwrap());
+
+    ui.step(&[], Duration::from_secs(0));
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(400.0, 300.0, ui);
+    set_widgets(ui);
+    assert_eq!(Some((save, "Save")), ui.tooltip_under_mouse());
+    assert!(shown_tooltip(ui).is_none());
+    // The `Ui` keeps updating while waiting to show the tooltip.
+    assert!(ui.update_needed());
+
+    ui.step(&[], Duration::from_millis(250));
+    set_widgets(ui);
+    assert!(shown_tooltip(ui).is_none());
+
+    // Once the mouse has hovered for the delay, the tooltip appears beneath the mouse.
+    ui.step(&[], Duration::from_millis(250));
+    set_widgets(ui);
+    let rect = shown_tooltip(ui).expect("expected the tooltip to be shown");
+    assert!(rect.left().abs() < 1e-6);
+    assert!((rect.top() + widget::tooltip::CURSOR_OFFSET).abs() < 1e-6);
+    assert!(is_text_displayed(ui, "Save"));
+    assert!(!ui.update_needed());
+
+    // Moving onto a widget without a tooltip hides it.
+    move_mouse_to_abs_coordinates(400.0, 200.0, ui);
+    set_widgets(ui);
+    assert_eq!(None, ui.tooltip_under_mouse());
+    assert!(shown_tooltip(ui).is_none());
+    assert!(!is_text_displayed(ui, "Save"));
+
+    // Returning to the widget begins the delay again.
+    move_mouse_to_abs_coordinates(400.0, 300.0, ui);
+    set_widgets(ui);
+    assert!(shown_tooltip(ui).is_none());
+    ui.step(&[], Duration::from_millis(500));
+    set_widgets(ui);
+    assert!(shown_tooltip(ui).is_some());
+}
+
+#[test]
+fn tooltip_should_still_appear_for_a_disabled_widget() {
+    use std::time::Duration;
+    let ui = &mut windowless_ui();
+    ui.theme.tooltip_delay = Duration::from_millis(500);
+    let button = ui.widget_id_generator().next();
+    let reason = "no file is open";
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new()
+            .w_h(100.0, 40.0)
+            .enabled(false)
+            .tooltip("Saving is disabled")
+            .set(button, ui);
+        // Owned text replaces the text given to the builder.
+        ui.set_widget_tooltip(button, format!("Saving is disabled as {}", reason));
+    };
+
+    ui.step(&[], Duration::from_secs(0));
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(400.0, 300.0, ui);
+    set_widgets(ui);
+    let expected = Some((button, "Saving is disabled as no file is open"));
+    assert_eq!(expected, ui.tooltip_under_mouse());
+    assert!(ui.find_widget(|info| info.is::<widget::Tooltip>()).is_none());
+
+    ui.step(&[], Duration::from_millis(500));
+    set_widgets(ui);
+    assert!(ui.find_widget(|info| info.is::<widget::Tooltip>()).is_some());
+}
@@ -1,6 +1,6 @@
 //! Text layout logic.
 
-use {FontSize, Scalar};
+use {Dimensions, FontSize, Scalar};
 use std;
 
 // Re-export all relevant rusttype types here.
@@ -40,6 +40,32 @@ pub fn height(num_lines: usize, font_size: FontSize, line_spacing: Scalar) -> Sc
 }
 
 
+/// Determine the dimensions of the given `text` when rendered with the given `font` and
+/// `font_size`, without any wrapping.
+///
+/// The width is that of the widest line and the height is one `font_size` per line, where each
+/// `\n` begins a new line. An empty `text` has no width but still occupies a single line.
+pub fn dimensions(text: &str, font_size: FontSize, font: &Font) -> Dimensions {
+    dimensions_with(text, font_size, |line| line::width(line, font, font_size))
+}
+
+/// The same as `dimensions` but measures the width of each line with the given `line_width`
+/// function rather than a `Font`.
+pub fn dimensions_with<F>(text: &str, font_size: FontSize, mut line_width: F) -> Dimensions
+    where F: FnMut(&str) -> Scalar,
+{
+    let mut num_lines = 0;
+    let mut max_w = 0.0;
+    for line in text.split('\n') {
+        let line = line.trim_right_matches('\r');
+        let w = line_width(line);
+        max_w = if w > max_w { w } else { max_w };
+        num_lines += 1;
+    }
+    [max_w, height(num_lines, font_size, 0.0)]
+}
+
+
 /// Produce an iterator yielding each line within the given `text` as a new `&str`, where the
 /// start and end indices into each line are provided by the given iterator.
 pub fn lines<I>(text: &str, ranges: I) -> Lines<I>
@@ -62,6 +88,15 @@ pub fn pt_to_scale(font_size_in_points: FontSize) -> Scale {
     Scale::uniform(pt_to_px(font_size_in_points))
 }
 
+/// The kerning adjustment to the advance between the glyphs for the characters `a` and `b`
+/// within the given `font`, i.e. negative for pairs like "AV" that should sit closer together.
+///
+/// Fonts without a kerning table produce `0.0`, in which case text advances by the plain advance
+/// width of each glyph.
+pub fn kerning(font: &Font, scale: Scale, a: char, b: char) -> Scalar {
+    font.pair_kerning(scale, a, b) as Scalar
+}
+
 
 impl<'a, I> Iterator for Lines<'a, I>
     where I: Iterator<Item=std::ops::Range<usize>>,
@@ -88,11 +123,17 @@ pub mod font {
     pub struct Id(usize);
 
     /// A collection of mappings from `font::Id`s to `rusttype::Font`s.
+    #[derive(Clone)]
     pub struct Map {
         next_index: usize,
         map: std::collections::HashMap<Id, super::Font>,
+        /// The fonts to search, in order, for glyphs that are missing from some font.
+        fallbacks: std::collections::HashMap<Id, Vec<Id>>,
     }
 
+    /// The character drawn in place of a character for which no font has a glyph.
+    pub const PLACEHOLDER: char = '\u{FFFD}';
+
     /// An iterator yielding an `Id` for each new `rusttype::Font` inserted into the `Map` via the
     /// `insert_collection` method.
     pub struct NewIds {
@@ -130,6 +171,7 @@ pub mod font {
             Map {
                 next_index: 0,
                 map: std::collections::HashMap::new(),
+                fallbacks: std::collections::HashMap::new(),
             }
         }
 
@@ -177,6 +219,94 @@ pub mod font {
             Ids { keys: self.map.keys() }
         }
 
+        /// Specify the fonts that should be searched, in order, for glyphs that are missing from
+        /// the font with the given `id`, i.e. CJK or emoji fonts.
+        pub fn set_fallbacks(&mut self, id: Id, fallbacks: Vec<Id>) {
+            self.fallbacks.insert(id, fallbacks);
+        }
+
+        /// The fallback chain for the font with the given `id`.
+        pub fn fallbacks(&self, id: Id) -> &[Id] {
+            self.fallbacks.get(&id).map(|ids| &ids[..]).unwrap_or(&[])
+        }
+
+        /// Determine the font that should be used to draw the given `ch` in place of the font with
+        /// the given `id` along with the character that should be drawn.
+        ///
+        /// See the `resolve_glyph_with` function for details.
+        pub fn resolve_glyph(&self, id: Id, ch: char) -> (Id, char) {
+            resolve_glyph_with(id, self.fallbacks(id), ch, |id, ch| {
+                self.get(id).map(|font| has_glyph(font, ch)).unwrap_or(false)
+            })
+        }
+
+        /// The fonts within the fallback chain for the font with the given `id`, in order.
+        ///
+        /// Fallbacks that are not within the `Map` are skipped.
+        pub fn fallback_fonts(&self, id: Id) -> Vec<&super::Font> {
+            self.fallbacks(id).iter().filter_map(|&id| self.get(id)).collect()
+        }
+
+        /// A new `Map` containing only the font with the given `id` and its fallback chain, each
+        /// retaining its `Id`.
+        ///
+        /// This is useful for laying out text on another thread, which requires an owned copy of
+        /// every font from which the text's glyphs may be drawn.
+        pub fn fallback_chain(&self, id: Id) -> Map {
+            let chain = std::iter::once(id).chain(self.fallbacks(id).iter().cloned());
+            let map = chain.filter_map(|id| self.get(id).map(|font| (id, font.clone()))).collect();
+            let mut fallbacks = std::collections::HashMap::new();
+            fallbacks.insert(id, self.fallbacks(id).to_vec());
+            Map {
+                next_index: self.next_index,
+                map: map,
+                fallbacks: fallbacks,
+            }
+        }
+
+    }
+
+
+    /// Whether or not the given `font` contains a glyph for the given `ch`.
+    pub fn has_glyph(font: &super::Font, ch: char) -> bool {
+        font.glyph(ch).map(|g| g.id() != super::GlyphId(0)).unwrap_or(false)
+    }
+
+    /// Determine the font that should be used to draw the given `ch` along with the character
+    /// that should be drawn, where `has_glyph` indicates whether or not a font has some glyph.
+    ///
+    /// The font with the given `id` is searched first, followed by each of its `fallbacks` in
+    /// order. If no font within the chain has a glyph for `ch`, the chain is searched again for the
+    /// `PLACEHOLDER` instead. If even the `PLACEHOLDER` cannot be found, the font with the given
+    /// `id` is returned alongside the `PLACEHOLDER`.
+    pub fn resolve_glyph_with<F>(id: Id, fallbacks: &[Id], ch: char, mut has_glyph: F) -> (Id, char)
+        where F: FnMut(Id, char) -> bool,
+    {
+        let chain = || std::iter::once(id).chain(fallbacks.iter().cloned());
+        if let Some(found) = chain().find(|&id| has_glyph(id, ch)) {
+            return (found, ch);
+        }
+        match chain().find(|&id| has_glyph(id, PLACEHOLDER)) {
+            Some(found) => (found, PLACEHOLDER),
+            None => (id, PLACEHOLDER),
+        }
+    }
+
+
+    /// Determine the index of the font within the given `chain` that should be used to draw the
+    /// given `ch`, along with the character that should be drawn.
+    ///
+    /// This performs the same search as `resolve_glyph_with`, where the `chain` yields the primary
+    /// font followed by each of its fallbacks. If no font has either the `ch` or the `PLACEHOLDER`,
+    /// the primary font (at index `0`) is returned alongside the `PLACEHOLDER`.
+    pub fn resolve_glyph_in<'a, I>(chain: I, ch: char) -> (usize, char)
+        where I: Iterator<Item=&'a super::Font> + Clone,
+    {
+        if let Some(index) = chain.clone().position(|font| has_glyph(font, ch)) {
+            return (index, ch);
+        }
+        let index = chain.clone().position(|font| has_glyph(font, PLACEHOLDER)).unwrap_or(0);
+        (index, PLACEHOLDER)
     }
 
 
@@ -236,9 +366,355 @@ pub mod font {
         }
     }
 
+
+    #[test]
+    fn resolve_glyph_should_select_the_first_fallback_with_the_glyph() {
+        // The primary font only covers ASCII, the first fallback covers CJK.
+        let (primary, cjk, emoji) = (Id(0), Id(1), Id(2));
+        fn has_glyph(id: Id, ch: char) -> bool {
+            match id.index() {
+                0 => (ch as u32) < 128,
+                1 => ch == '漢' || ch == PLACEHOLDER,
+                _ => ch == '🙂',
+            }
+        }
+        let chain = [cjk, emoji];
+        assert_eq!((primary, 'a'), resolve_glyph_with(primary, &chain, 'a', has_glyph));
+        assert_eq!((cjk, '漢'), resolve_glyph_with(primary, &chain, '漢', has_glyph));
+        assert_eq!((emoji, '🙂'), resolve_glyph_with(primary, &chain, '🙂', has_glyph));
+    }
+
+    #[test]
+    fn resolve_glyph_should_fall_back_to_the_placeholder() {
+        let (primary, secondary) = (Id(0), Id(1));
+        fn has_placeholder(id: Id, ch: char) -> bool {
+            id == Id(1) && ch == PLACEHOLDER
+        }
+        assert_eq!((secondary, PLACEHOLDER),
+                   resolve_glyph_with(primary, &[secondary], '漢', has_placeholder));
+        assert_eq!((primary, PLACEHOLDER), resolve_glyph_with(primary, &[], '漢', |_, _| false));
+    }
+
 }
 
 
+/// A least-recently-used eviction policy for caching glyphs within a limited memory budget.
+///
+/// The rusttype `GlyphCache` used by the provided backends is bounded by the size of its texture.
+/// Backends that store rasterized glyphs elsewhere (i.e. within a CPU-side map of glyph images)
+/// may use the `Lru` type to determine which glyphs to discard once the budget is exceeded.
+pub mod cache {
+    use std::collections::{BTreeMap, HashMap};
+    use std::hash::Hash;
+
+    /// Tracks the size and usage of each cached glyph, evicting the least recently used glyphs
+    /// whenever the total size would exceed the maximum number of bytes.
+    #[derive(Clone, Debug)]
+    pub struct Lru<K>
+        where K: Eq + Hash,
+    {
+        max_bytes: usize,
+        total_bytes: usize,
+        /// Incremented every time an entry is used.
+        tick: u64,
+        entries: HashMap<K, Entry>,
+        /// Each key ordered by the tick at which it was last used, starting with the oldest.
+        order: BTreeMap<u64, K>,
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    struct Entry {
+        bytes: usize,
+        last_used: u64,
+    }
+
+    impl<K> Lru<K>
+        where K: Clone + Eq + Hash,
+    {
+
+        /// Construct an empty `Lru` with the given budget in bytes.
+        pub fn new(max_bytes: usize) -> Self {
+            Lru {
+                max_bytes: max_bytes,
+                total_bytes: 0,
+                tick: 0,
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+            }
+        }
+
+        /// The maximum number of bytes that may be occupied by all entries.
+        pub fn max_bytes(&self) -> usize {
+            self.max_bytes
+        }
+
+        /// The number of bytes currently occupied by all entries.
+        pub fn total_bytes(&self) -> usize {
+            self.total_bytes
+        }
+
+        /// The number of entries currently cached.
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        /// Whether or not there are no cached entries.
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        /// Whether or not an entry exists for the given key.
+        pub fn contains(&self, key: &K) -> bool {
+            self.entries.contains_key(key)
+        }
+
+        /// Change the budget, returning the keys of all entries evicted as a result, starting with
+        /// the least recently used.
+        pub fn set_max_bytes(&mut self, max_bytes: usize) -> Vec<K> {
+            self.max_bytes = max_bytes;
+            let mut evicted = Vec::new();
+            self.evict_until_within(0, &mut evicted);
+            evicted
+        }
+
+        /// Mark the entry for the given key as the most recently used.
+        ///
+        /// Returns `false` if there is no entry for the key.
+        pub fn touch(&mut self, key: &K) -> bool {
+            let tick = self.next_tick();
+            match self.entries.get_mut(key) {
+                Some(entry) => {
+                    let key = self.order.remove(&entry.last_used).expect("no order for entry");
+                    entry.last_used = tick;
+                    self.order.insert(tick, key);
+                    true
+                },
+                None => false,
+            }
+        }
+
+        /// Insert an entry of the given size as the most recently used, replacing any existing
+        /// entry for the same key.
+        ///
+        /// Returns the keys of all entries evicted to make room, starting with the least recently
+        /// used. An entry that is larger than the entire budget is never cached, so its own key is
+        /// returned without evicting any other entries.
+        pub fn insert(&mut self, key: K, bytes: usize) -> Vec<K> {
+            self.remove(&key);
+            if bytes > self.max_bytes {
+                return vec![key];
+            }
+            let mut evicted = Vec::new();
+            self.evict_until_within(bytes, &mut evicted);
+            let tick = self.next_tick();
+            self.entries.insert(key.clone(), Entry { bytes: bytes, last_used: tick });
+            self.order.insert(tick, key);
+            self.total_bytes += bytes;
+            evicted
+        }
+
+        /// Remove the entry for the given key, returning its size in bytes.
+        pub fn remove(&mut self, key: &K) -> Option<usize> {
+            self.entries.remove(key).map(|entry| {
+                self.order.remove(&entry.last_used);
+                self.total_bytes -= entry.bytes;
+                entry.bytes
+            })
+        }
+
+        /// Evict the least recently used entries until `additional_bytes` may be inserted without
+        /// exceeding the budget.
+        fn evict_until_within(&mut self, additional_bytes: usize, evicted: &mut Vec<K>) {
+            while self.total_bytes + additional_bytes > self.max_bytes {
+                let oldest = match self.order.keys().next() {
+                    Some(&tick) => tick,
+                    None => return,
+                };
+                let key = self.order.remove(&oldest).expect("no key for tick");
+                let entry = self.entries.remove(&key).expect("no entry for key");
+                self.total_bytes -= entry.bytes;
+                evicted.push(key);
+            }
+        }
+
+        fn next_tick(&mut self) -> u64 {
+            self.tick = self.tick.wrapping_add(1);
+            self.tick
+        }
+
+    }
+
+}
+
+
+/// Reordering of bidirectional text (e.g. Arabic or Hebrew mixed with Latin) for display.
+///
+/// Text is always stored and edited in logical order, i.e. the order in which it is read. Lines
+/// containing right-to-left characters are reordered into visual order via the Unicode
+/// Bidirectional Algorithm before being laid out, while `VisualOrder` maps between the logical
+/// and visual positions of each character and cursor.
+pub mod bidi {
+    use std;
+    use unicode_bidi::BidiInfo;
+
+    /// The visual order of the characters within a single line of text.
+    ///
+    /// The base direction of the line is determined by its first strongly directional character.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct VisualOrder {
+        /// The logical index of the character displayed at each visual position.
+        logical: Vec<usize>,
+        /// The visual position of the character at each logical index.
+        visual: Vec<usize>,
+        /// Whether or not the character at each logical index is displayed right-to-left.
+        is_rtl: Vec<bool>,
+    }
+
+    impl VisualOrder {
+
+        /// Determine the visual order of the characters in the given line.
+        pub fn new(line: &str) -> Self {
+            let char_starts: Vec<usize> = line.char_indices().map(|(i, _)| i).collect();
+            let num_chars = char_starts.len();
+            let info = BidiInfo::new(line, None);
+            if !info.has_rtl() {
+                return VisualOrder {
+                    logical: (0..num_chars).collect(),
+                    visual: (0..num_chars).collect(),
+                    is_rtl: vec![false; num_chars],
+                };
+            }
+
+            let char_idx = |byte_idx: usize| match char_starts.binary_search(&byte_idx) {
+                Ok(i) | Err(i) => i,
+            };
+            let mut logical = Vec::with_capacity(num_chars);
+            let mut is_rtl = vec![false; num_chars];
+            for paragraph in &info.paragraphs {
+                let (levels, runs) = info.visual_runs(paragraph, paragraph.range.clone());
+                for run in runs {
+                    let run_is_rtl = levels[run.start].is_rtl();
+                    let chars = char_idx(run.start)..char_idx(run.end);
+                    for i in chars.clone() {
+                        is_rtl[i] = run_is_rtl;
+                    }
+                    if run_is_rtl {
+                        logical.extend(chars.rev());
+                    } else {
+                        logical.extend(chars);
+                    }
+                }
+            }
+
+            let mut visual = vec![0; num_chars];
+            for (visual_idx, &logical_idx) in logical.iter().enumerate() {
+                visual[logical_idx] = visual_idx;
+            }
+            VisualOrder {
+                logical: logical,
+                visual: visual,
+                is_rtl: is_rtl,
+            }
+        }
+
+        /// Whether or not any of the characters are displayed right-to-left.
+        ///
+        /// If not, the visual order is the same as the logical order.
+        pub fn has_rtl(&self) -> bool {
+            self.is_rtl.iter().any(|&is_rtl| is_rtl)
+        }
+
+        /// The number of characters in the line.
+        pub fn len(&self) -> usize {
+            self.logical.len()
+        }
+
+        /// The logical index of the character displayed at the given visual position.
+        pub fn logical_index(&self, visual_idx: usize) -> Option<usize> {
+            self.logical.get(visual_idx).cloned()
+        }
+
+        /// The visual position of the character at the given logical index.
+        pub fn visual_index(&self, char_idx: usize) -> Option<usize> {
+            self.visual.get(char_idx).cloned()
+        }
+
+        /// Whether or not the character at the given logical index is displayed right-to-left.
+        pub fn is_rtl(&self, char_idx: usize) -> bool {
+            self.is_rtl.get(char_idx).cloned().unwrap_or(false)
+        }
+
+        /// The given line's characters in visual order.
+        ///
+        /// The given `line` must be the line from which the `VisualOrder` was produced.
+        pub fn reorder(&self, line: &str) -> String {
+            let chars: Vec<char> = line.chars().collect();
+            self.logical.iter().map(|&i| chars[i]).collect()
+        }
+
+        /// The visual slot at which the cursor at the given logical char index is displayed,
+        /// where slot `0` is the left edge of the line and slot `n` is the right edge of the `n`th
+        /// character in visual order.
+        ///
+        /// The cursor is displayed at the leading edge of the character that follows it, which is
+        /// the right edge for right-to-left characters. The cursor at the end of the line is
+        /// displayed at the trailing edge of the final character.
+        pub fn cursor_slot(&self, char_idx: usize) -> usize {
+            let num_chars = self.len();
+            if char_idx < num_chars {
+                let visual_idx = self.visual[char_idx];
+                if self.is_rtl[char_idx] { visual_idx + 1 } else { visual_idx }
+            } else if num_chars > 0 {
+                let visual_idx = self.visual[num_chars - 1];
+                if self.is_rtl[num_chars - 1] { visual_idx } else { visual_idx + 1 }
+            } else {
+                0
+            }
+        }
+
+        /// The logical index of the cursor displayed nearest to the left of the cursor at the
+        /// given logical index.
+        ///
+        /// Returns `None` if the cursor is already displayed at the left edge of the line.
+        pub fn cursor_left(&self, char_idx: usize) -> Option<usize> {
+            let slot = self.cursor_slot(char_idx);
+            (0..slot).rev().filter_map(|slot| self.cursor_at_slot(slot, char_idx)).next()
+        }
+
+        /// The logical index of the cursor displayed nearest to the right of the cursor at the
+        /// given logical index.
+        ///
+        /// Returns `None` if the cursor is already displayed at the right edge of the line.
+        pub fn cursor_right(&self, char_idx: usize) -> Option<usize> {
+            let slot = self.cursor_slot(char_idx);
+            (slot + 1..self.len() + 1).filter_map(|slot| self.cursor_at_slot(slot, char_idx)).next()
+        }
+
+        /// The logical index of the cursor displayed at the given slot, preferring the index
+        /// closest to `near` where more than one cursor shares the slot.
+        fn cursor_at_slot(&self, slot: usize, near: usize) -> Option<usize> {
+            let distance = |i: usize| if i > near { i - near } else { near - i };
+            (0..self.len() + 1)
+                .filter(|&i| self.cursor_slot(i) == slot)
+                .min_by_key(|&i| distance(i))
+        }
+
+    }
+
+    /// The given line of text in visual order.
+    ///
+    /// Lines without any right-to-left characters are returned unchanged.
+    pub fn reorder(line: &str) -> std::borrow::Cow<str> {
+        let order = VisualOrder::new(line);
+        if order.has_rtl() {
+            std::borrow::Cow::Owned(order.reorder(line))
+        } else {
+            std::borrow::Cow::Borrowed(line)
+        }
+    }
+
+}
+
 /// Logic and types specific to individual glyph layout.
 pub mod glyph {
     use {FontSize, Range, Rect, Scalar};
@@ -260,6 +736,9 @@ pub mod glyph {
         next_left: Scalar,
         /// `PositionedGlyphs` yielded by the RustType `LayoutIter`.
         layout: super::LayoutIter<'a, 'b>,
+        /// The `Rect`s of a line containing right-to-left text, laid out in visual order and
+        /// yielded in logical order.
+        bidi_rects: Option<std::vec::IntoIter<Rect>>,
     }
 
     /// An iterator that, for every `(line, line_rect)` pair yielded by the given iterator,
@@ -359,10 +838,33 @@ pub mod glyph {
             lines_with_rects.next().map(|(line, line_rect)| {
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
+                let order = super::bidi::VisualOrder::new(line);
+                if !order.has_rtl() {
+                    return Rects {
+                        next_left: line_rect.x.start,
+                        layout: font.layout(line, scale, point),
+                        y: line_rect.y,
+                        bidi_rects: None,
+                    };
+                }
+
+                // Lay out the line in visual order, then yield the `Rect`s in logical order.
+                let visual_line = order.reorder(line);
+                let visual_rects: Vec<Rect> = Rects {
+                    next_left: line_rect.x.start,
+                    layout: font.layout(&visual_line, scale, point),
+                    y: line_rect.y,
+                    bidi_rects: None,
+                }.collect();
+                let logical_rects: Vec<Rect> = (0..order.len())
+                    .filter_map(|i| order.visual_index(i).and_then(|v| visual_rects.get(v)))
+                    .cloned()
+                    .collect();
                 Rects {
                     next_left: line_rect.x.start,
-                    layout: font.layout(line, scale, point),
-                    y: line_rect.y
+                    layout: font.layout("", scale, point),
+                    y: line_rect.y,
+                    bidi_rects: Some(logical_rects.into_iter()),
                 }
             })
         }
@@ -412,7 +914,10 @@ pub mod glyph {
     impl<'a, 'b> Iterator for Rects<'a, 'b> {
         type Item = Rect;
         fn next(&mut self) -> Option<Self::Item> {
-            let Rects { ref mut next_left, ref mut layout, y } = *self;
+            let Rects { ref mut next_left, ref mut layout, y, ref mut bidi_rects } = *self;
+            if let Some(ref mut bidi_rects) = *bidi_rects {
+                return bidi_rects.next();
+            }
             layout.next().map(|g| {
                 let left = *next_left;
                 let right = g.pixel_bounding_box()
@@ -444,6 +949,7 @@ pub mod glyph {
 pub mod cursor {
     use {FontSize, Range, Rect, Scalar, Point, Align};
     use std;
+    use unicode_segmentation::UnicodeSegmentation;
 
     /// Every possible cursor position within each line of text yielded by the given iterator.
     ///
@@ -480,6 +986,9 @@ pub mod cursor {
     pub struct Xs<'a, 'b> {
         next_x: Option<Scalar>,
         layout: super::LayoutIter<'a, 'b>,
+        /// The positions of each cursor within a line containing right-to-left text, laid out in
+        /// visual order and yielded in logical order.
+        bidi_xs: Option<std::vec::IntoIter<Scalar>>,
     }
 
     /// An index representing the position of a cursor within some text.
@@ -630,6 +1139,110 @@ pub mod cursor {
                 })
         }
 
+        /// The cursor index displayed immediately to the left of `self`, skipping any positions
+        /// within grapheme clusters.
+        ///
+        /// Within lines containing right-to-left text, this follows the visual order of the
+        /// characters in the given `text`, so that the cursor moves consistently across direction
+        /// boundaries. Otherwise, this is the same as `previous_grapheme`.
+        ///
+        /// If `self` is displayed at the left edge of a line, this returns the last index of the
+        /// previous line, or `None` if it is the first line.
+        pub fn left<I>(self, text: &str, line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info> + Clone,
+        {
+            let line_info = match line_infos.clone().nth(self.line) {
+                Some(line_info) => line_info,
+                None => return None,
+            };
+            let line = &text[line_info.byte_range()];
+            let order = super::bidi::VisualOrder::new(line);
+            if !order.has_rtl() {
+                return self.previous_grapheme(text, line_infos);
+            }
+            let boundaries = grapheme_boundaries(line);
+            let mut char = self.char;
+            while let Some(left) = order.cursor_left(char) {
+                if boundaries.contains(&left) {
+                    return Some(Index { line: self.line, char: left });
+                }
+                char = left;
+            }
+            Index { line: self.line, char: 0 }.previous(line_infos)
+        }
+
+        /// The cursor index displayed immediately to the right of `self`, skipping any positions
+        /// within grapheme clusters.
+        ///
+        /// Within lines containing right-to-left text, this follows the visual order of the
+        /// characters in the given `text`, so that the cursor moves consistently across direction
+        /// boundaries. Otherwise, this is the same as `next_grapheme`.
+        ///
+        /// If `self` is displayed at the right edge of a line, this returns the first index of the
+        /// next line, or `None` if it is the last line.
+        pub fn right<I>(self, text: &str, line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info> + Clone,
+        {
+            let line_info = match line_infos.clone().nth(self.line) {
+                Some(line_info) => line_info,
+                None => return None,
+            };
+            let line = &text[line_info.byte_range()];
+            let order = super::bidi::VisualOrder::new(line);
+            if !order.has_rtl() {
+                return self.next_grapheme(text, line_infos);
+            }
+            let boundaries = grapheme_boundaries(line);
+            let mut char = self.char;
+            while let Some(right) = order.cursor_right(char) {
+                if boundaries.contains(&right) {
+                    return Some(Index { line: self.line, char: right });
+                }
+                char = right;
+            }
+            let end = line_info.end_char() - line_info.start_char;
+            Index { line: self.line, char: end }.next(line_infos)
+        }
+
+        /// The cursor index at the start of the grapheme cluster before `self`, e.g. before an
+        /// emoji along with all of its modifiers or a letter along with its combining accents.
+        ///
+        /// If `self` is at the beginning of a line other than the first, this returns the last
+        /// index position of the previous line, or `None` if it is the first line.
+        pub fn previous_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            if char == 0 {
+                return self.previous(line_infos);
+            }
+            line_infos.nth(line).map(|line_info| {
+                let boundaries = grapheme_boundaries(&text[line_info.byte_range()]);
+                let new_char = boundaries.into_iter().take_while(|&b| b < char).last().unwrap_or(0);
+                Index { line: line, char: new_char }
+            })
+        }
+
+        /// The cursor index at the end of the grapheme cluster after `self`, e.g. after an emoji
+        /// along with all of its modifiers or a letter along with its combining accents.
+        ///
+        /// If `self` is at the end of a line other than the last, this returns the first index of
+        /// the next line, or `None` if it is the last line.
+        pub fn next_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+            where I: Iterator<Item=super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).and_then(|line_info| {
+                let line_count = line_info.char_range().count();
+                if char >= line_count {
+                    return line_infos.next().map(|_| Index { line: line + 1, char: 0 });
+                }
+                let boundaries = grapheme_boundaries(&text[line_info.byte_range()]);
+                let new_char = boundaries.into_iter().find(|&b| b > char).unwrap_or(line_count);
+                Some(Index { line: line, char: new_char })
+            })
+        }
+
     }
 
 
@@ -678,6 +1291,18 @@ pub mod cursor {
         }
     }
 
+    /// The char index of every cursor position within the given line that does not split a
+    /// grapheme cluster, including the start and end of the line.
+    pub fn grapheme_boundaries(line: &str) -> Vec<usize> {
+        let mut boundaries = vec![0];
+        let mut char_idx = 0;
+        for grapheme in line.graphemes(true) {
+            char_idx += grapheme.chars().count();
+            boundaries.push(char_idx);
+        }
+        boundaries
+    }
+
     /// Convert the given character index into a cursor `Index`.
     pub fn index_before_char<I>(line_infos: I, char_index: usize) -> Option<Index>
         where I: Iterator<Item=super::line::Info>,
@@ -758,6 +1383,9 @@ pub mod cursor {
 
     /// Find the closest cursor index to the given `x` position on the given line along with the
     /// `x` position of that cursor.
+    ///
+    /// Every cursor position is checked, as positions within lines containing right-to-left text
+    /// are not ordered along the *x* axis.
     pub fn closest_cursor_index_on_line<'a>(x_pos: Scalar, line_xs: Xs<'a, 'a>) -> (usize, Scalar) {
         let mut xs_enumerated = line_xs.enumerate();
         // `xs` always yields at least one `x` (the start of the line).
@@ -770,8 +1398,6 @@ pub mod cursor {
             if diff < closest_diff {
                 closest = (i,x);
                 closest_diff = diff;
-            } else {
-                break;
             }
         }
         closest
@@ -792,10 +1418,31 @@ pub mod cursor {
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
                 let y = line_rect.y;
-                let layout = font.layout(line, scale, point);
-                let xs = Xs {
+                let order = super::bidi::VisualOrder::new(line);
+                if !order.has_rtl() {
+                    let xs = Xs {
+                        next_x: Some(line_rect.x.start),
+                        layout: font.layout(line, scale, point),
+                        bidi_xs: None,
+                    };
+                    return (xs, y);
+                }
+
+                // Lay out the line in visual order, then yield each cursor's position in logical
+                // order, as cursors at direction boundaries are no longer ordered along *x*.
+                let visual_line = order.reorder(line);
+                let slot_xs: Vec<Scalar> = Xs {
                     next_x: Some(line_rect.x.start),
-                    layout: layout,
+                    layout: font.layout(&visual_line, scale, point),
+                    bidi_xs: None,
+                }.collect();
+                let logical_xs: Vec<Scalar> = (0..order.len() + 1)
+                    .filter_map(|i| slot_xs.get(order.cursor_slot(i)).cloned())
+                    .collect();
+                let xs = Xs {
+                    next_x: None,
+                    layout: font.layout("", scale, point),
+                    bidi_xs: Some(logical_xs.into_iter()),
                 };
                 (xs, y)
             })
@@ -813,6 +1460,9 @@ pub mod cursor {
         // Each possible cursor position along the *x* axis.
         type Item = Scalar;
         fn next(&mut self) -> Option<Self::Item> {
+            if let Some(ref mut bidi_xs) = self.bidi_xs {
+                return bidi_xs.next();
+            }
             self.next_x.map(|x| {
                 self.next_x = self.layout.next()
                     .map(|g| {
@@ -835,6 +1485,8 @@ pub mod cursor {
 pub mod line {
     use {Align, FontSize, Range, Rect, Scalar};
     use std;
+    use std::borrow::Cow;
+    use widget::text::{Ellipsis, Wrap};
 
     /// The two types of **Break** indices returned by the **WrapIndicesBy** iterators.
     #[derive(Copy, Clone, Debug, PartialEq)]
@@ -897,6 +1549,7 @@ pub mod line {
     pub struct Infos<'a, F> {
         text: &'a str,
         font: &'a super::Font,
+        fallbacks: &'a [&'a super::Font],
         font_size: FontSize,
         max_width: Scalar,
         next_break_fn: F,
@@ -919,16 +1572,21 @@ pub mod line {
 
     /// An iterator yielding a `Rect` for each selected line in a block of text.
     ///
-    /// The yielded `Rect`s represent the selected range within each line of text.
+    /// The yielded `Rect`s represent the selected range within each line of text. Lines containing
+    /// right-to-left text may yield more than one `Rect`, as the selected characters may not be
+    /// contiguous once reordered for display.
     ///
     /// Lines that do not contain any selected text will be skipped.
     pub struct SelectedRects<'a, I> {
         selected_char_rects_per_line: super::glyph::SelectedRectsPerLine<'a, I>,
+        /// The remaining `Rect`s for the current line.
+        line_rects: std::vec::IntoIter<Rect>,
     }
 
     /// An alias for function pointers that are compatible with the `Block`'s required text
     /// wrapping function.
-    pub type NextBreakFnPtr = fn(&str, &super::Font, FontSize, Scalar) -> (Break, Scalar);
+    pub type NextBreakFnPtr =
+        fn(&str, &super::Font, &[&super::Font], FontSize, Scalar) -> (Break, Scalar);
 
 
     impl Break {
@@ -962,6 +1620,7 @@ pub mod line {
             Infos {
                 text: self.text,
                 font: self.font,
+                fallbacks: self.fallbacks,
                 font_size: self.font_size,
                 max_width: self.max_width,
                 next_break_fn: self.next_break_fn.clone(),
@@ -996,6 +1655,19 @@ pub mod line {
 
     }
 
+    impl<'a, F> Infos<'a, F> {
+
+        /// Measure each character that is missing from the `Font` using the first of the given
+        /// `fallbacks` that contains it, as the glyph will be drawn from that font.
+        ///
+        /// See `font::Map::fallback_fonts`.
+        pub fn fallbacks(mut self, fallbacks: &'a [&'a super::Font]) -> Self {
+            self.fallbacks = fallbacks;
+            self
+        }
+
+    }
+
     impl<'a> Infos<'a, NextBreakFnPtr> {
 
         /// Converts `Self` into an `Infos` whose lines are wrapped at the character that first
@@ -1020,22 +1692,37 @@ pub mod line {
     /// A function for finding the advance width between the given character that also considers
     /// the kerning for some previous glyph.
     ///
-    /// This also updates the `last_glyph` with the glyph produced for the given `char`.
+    /// The character is measured using the first font within the `font` and its `fallbacks` that
+    /// contains it, in the same manner as when it is drawn (see `font::resolve_glyph_in`), falling
+    /// back to the font's placeholder glyph (`GlyphId(0)`). Kerning is only applied between
+    /// consecutive glyphs from the same font.
+    ///
+    /// This also updates the `last_glyph` with the index of the font and the glyph produced for
+    /// the given `char`.
     ///
     /// This is primarily for use within the `next_break` functions below.
     ///
     /// The following code is adapted from the rusttype::LayoutIter::next src.
     fn advance_width(ch: char,
                      font: &super::Font,
+                     fallbacks: &[&super::Font],
                      scale: super::Scale,
-                     last_glyph: &mut Option<super::GlyphId>) -> Scalar
+                     last_glyph: &mut Option<(usize, super::GlyphId)>) -> Scalar
     {
-        let g = font.glyph(ch).unwrap().scaled(scale);
-        let kern = last_glyph
-            .map(|last| font.pair_kerning(scale, last, g.id()))
-            .unwrap_or(0.0);
+        let chain = std::iter::once(font).chain(fallbacks.iter().cloned());
+        let (index, ch) = super::font::resolve_glyph_in(chain, ch);
+        let font = if index == 0 { font } else { fallbacks[index - 1] };
+        let g = match font.glyph(ch).or_else(|| font.glyph(super::GlyphId(0))) {
+            Some(g) => g.scaled(scale),
+            None => return 0.0,
+        };
+        let kern = match *last_glyph {
+            Some((last_index, last)) if last_index == index =>
+                font.pair_kerning(scale, last, g.id()),
+            _ => 0.0,
+        };
         let advance_width = g.h_metrics().advance_width;
-        *last_glyph = Some(g.id());
+        *last_glyph = Some((index, g.id()));
         (kern + advance_width) as Scalar
     }
 
@@ -1044,6 +1731,7 @@ pub mod line {
     /// along with the width of the line.
     fn next_break(text: &str,
                   font: &super::Font,
+                  fallbacks: &[&super::Font],
                   font_size: FontSize) -> (Break, Scalar)
     {
         let scale = super::pt_to_scale(font_size);
@@ -1064,7 +1752,7 @@ pub mod line {
             }
 
             // Update the width.
-            width += advance_width(ch, font, scale, &mut last_glyph);
+            width += advance_width(ch, font, fallbacks, scale, &mut last_glyph);
             char_i += 1;
         }
         let break_ = Break::End { byte: text.len(), char: char_i };
@@ -1078,14 +1766,29 @@ pub mod line {
     /// Also returns the width of each line alongside the Break.
     fn next_break_by_character(text: &str,
                                font: &super::Font,
+                               fallbacks: &[&super::Font],
                                font_size: FontSize,
                                max_width: Scalar) -> (Break, Scalar)
     {
         let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        next_break_by_character_with(text, max_width, |ch| {
+            advance_width(ch, font, fallbacks, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as the `Font`-based character wrapping, but determines the advance width of each
+    /// character using the given `char_width` function.
+    ///
+    /// This allows for reasoning about line breaks independently of any particular `Font`.
+    pub fn next_break_by_character_with<F>(text: &str,
+                                           max_width: Scalar,
+                                           mut char_width: F) -> (Break, Scalar)
+        where F: FnMut(char) -> Scalar,
+    {
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
         while let Some((byte_i, ch)) = char_indices.next() {
 
             // Check for a newline.
@@ -1100,10 +1803,11 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + char_width(ch);
 
-            // Check for a line wrap.
-            if new_width > max_width {
+            // Check for a line wrap. The first character of a line is never wrapped, otherwise
+            // characters wider than `max_width` would never be consumed.
+            if new_width > max_width && char_i > 0 {
                 let break_ = Break::Wrap { byte: byte_i, char: char_i, len_bytes: 0 };
                 return (break_, width);
             }
@@ -1126,16 +1830,32 @@ pub mod line {
     /// Also returns the width the line alongside the Break.
     fn next_break_by_whitespace(text: &str,
                                 font: &super::Font,
+                                fallbacks: &[&super::Font],
                                 font_size: FontSize,
                                 max_width: Scalar) -> (Break, Scalar)
     {
-        struct Last { byte: usize, char: usize, width_before: Scalar }
         let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        next_break_by_whitespace_with(text, max_width, |ch| {
+            advance_width(ch, font, fallbacks, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as the `Font`-based whitespace wrapping, but determines the advance width of each
+    /// character using the given `char_width` function.
+    ///
+    /// Words that are wider than the `max_width` are broken at the first character that exceeds
+    /// the `max_width`.
+    pub fn next_break_by_whitespace_with<F>(text: &str,
+                                            max_width: Scalar,
+                                            mut char_width: F) -> (Break, Scalar)
+        where F: FnMut(char) -> Scalar,
+    {
+        struct Last { byte: usize, char: usize, len_bytes: usize, width_before: Scalar }
         let mut last_whitespace_start = None;
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
         while let Some((byte_i, ch)) = char_indices.next() {
 
             // Check for a newline.
@@ -1149,14 +1869,25 @@ pub mod line {
                 return (break_, width);
             }
 
+            // Check for a new whitespace. Whitespace at the start of the line is never used as a
+            // break, as this would only produce an empty line.
+            if ch.is_whitespace() && char_i > 0 {
+                last_whitespace_start = Some(Last {
+                    byte: byte_i,
+                    char: char_i,
+                    len_bytes: ch.len_utf8(),
+                    width_before: width,
+                });
+            }
+
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + char_width(ch);
 
             // Check for a line wrap.
-            if width > max_width {
+            if new_width > max_width && char_i > 0 {
                 match last_whitespace_start {
-                    Some(Last { byte, char, width_before }) => {
-                        let break_ = Break::Wrap { byte: byte, char: char, len_bytes: 1 };
+                    Some(Last { byte, char, len_bytes, width_before }) => {
+                        let break_ = Break::Wrap { byte: byte, char: char, len_bytes: len_bytes };
                         return (break_, width_before);
                     },
                     None => {
@@ -1166,11 +1897,6 @@ pub mod line {
                 }
             }
 
-            // Check for a new whitespace.
-            if ch.is_whitespace() {
-                last_whitespace_start = Some(Last { byte: byte_i, char: char_i, width_before: width });
-            }
-
             width = new_width;
             char_i += 1;
         }
@@ -1180,6 +1906,53 @@ pub mod line {
     }
 
 
+    /// The number of cells occupied by the given character within a monospace layout.
+    ///
+    /// Wide characters such as CJK ideographs, Hangul syllables and fullwidth forms occupy two
+    /// cells, while all other characters occupy one.
+    pub fn char_cells(ch: char) -> usize {
+        match ch as u32 {
+            0x1100...0x115F |
+            0x2E80...0x303E |
+            0x3041...0x33FF |
+            0x3400...0x4DBF |
+            0x4E00...0x9FFF |
+            0xA000...0xA4CF |
+            0xAC00...0xD7A3 |
+            0xF900...0xFAFF |
+            0xFE30...0xFE4F |
+            0xFF00...0xFF60 |
+            0xFFE0...0xFFE6 |
+            0x1F300...0x1F64F |
+            0x1F900...0x1F9FF |
+            0x20000...0x3FFFD => 2,
+            _ => 1,
+        }
+    }
+
+    /// The advance of the given character within a monospace layout with the given `cell_width`.
+    pub fn monospace_advance(ch: char, cell_width: Scalar) -> Scalar {
+        char_cells(ch) as Scalar * cell_width
+    }
+
+    /// Produce the width of the given line of text where each character advances by the width
+    /// given by `advance`, adjusted by the `kerning` between each pair of consecutive characters.
+    pub fn width_with<A, K>(text: &str, mut advance: A, mut kerning: K) -> Scalar
+        where A: FnMut(char) -> Scalar,
+              K: FnMut(char, char) -> Scalar,
+    {
+        let mut width = 0.0;
+        let mut last = None;
+        for ch in text.chars() {
+            if let Some(last) = last {
+                width += kerning(last, ch);
+            }
+            width += advance(ch);
+            last = Some(ch);
+        }
+        width
+    }
+
     /// Produce the width of the given line of text including spaces (i.e. ' ').
     pub fn width(text: &str, font: &super::Font, font_size: FontSize) -> Scalar {
         let scale = super::Scale::uniform(super::pt_to_px(font_size));
@@ -1203,11 +1976,13 @@ pub mod line {
                                    font_size: FontSize,
                                    max_width: Scalar,
                                    next_break_fn: F) -> Infos<'a, F>
-        where F: for<'b> FnMut(&'b str, &'b super::Font, FontSize, Scalar) -> (Break, Scalar)
+        where F: for<'b> FnMut(&'b str, &'b super::Font, &'b [&'b super::Font], FontSize, Scalar)
+                                -> (Break, Scalar)
     {
         Infos {
             text: text,
             font: font,
+            fallbacks: &[],
             font_size: font_size,
             max_width: max_width,
             next_break_fn: next_break_fn,
@@ -1227,15 +2002,132 @@ pub mod line {
     {
         fn no_wrap(text: &str,
                    font: &super::Font,
+                   fallbacks: &[&super::Font],
                    font_size: FontSize,
                    _max_width: Scalar) -> (Break, Scalar)
         {
-            next_break(text, font, font_size)
+            next_break(text, font, fallbacks, font_size)
         }
 
         infos_wrapped_by(text, font, font_size, std::f64::MAX, no_wrap)
     }
 
+    /// Produce an `Info` for every line in the given `text`, wrapped to the given `max_width` in
+    /// the manner described by `wrap`.
+    ///
+    /// Rather than requiring a `Font`, the advance width of each character is determined by the
+    /// given `char_width` function. This is useful for laying out text using custom font metrics.
+    pub fn wrap_with<F>(text: &str, max_width: Scalar, wrap: Wrap, mut char_width: F) -> Vec<Info>
+        where F: FnMut(char) -> Scalar,
+    {
+        let mut infos = Vec::new();
+        let (mut start_byte, mut start_char, mut last_break) = (0, 0, None);
+        loop {
+            let next_break_fn = |line: &str| match wrap {
+                Wrap::Character => next_break_by_character_with(line, max_width, &mut char_width),
+                Wrap::Whitespace => next_break_by_whitespace_with(line, max_width, &mut char_width),
+            };
+            let (start_byte, start_char, last_break) =
+                (&mut start_byte, &mut start_char, &mut last_break);
+            match next_info(text, start_byte, start_char, last_break, next_break_fn) {
+                Some(info) => infos.push(info),
+                None => return infos,
+            }
+        }
+    }
+
+    /// The string appended to text that has been truncated.
+    pub const ELLIPSIS: &'static str = "…";
+
+    /// Truncate the given single line of `text` so that it fits within the given `max_width`,
+    /// ending it with the `ELLIPSIS`.
+    ///
+    /// See the `truncate_with` function for details.
+    pub fn truncate<'a>(text: &'a str,
+                        max_width: Scalar,
+                        ellipsis: Ellipsis,
+                        font: &super::Font,
+                        font_size: FontSize) -> Cow<'a, str>
+    {
+        let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        truncate_with(text, max_width, ellipsis, |ch| {
+            advance_width(ch, font, fallbacks, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as `truncate` but determines the advance width of each character using the given
+    /// `char_width` function rather than a `Font`.
+    ///
+    /// Text that already fits is returned unchanged. Otherwise, trailing characters are dropped
+    /// until the remaining text and the `ELLIPSIS` fit within the `max_width`, at which point any
+    /// trailing whitespace is also dropped. If even the `ELLIPSIS` alone does not fit, the result
+    /// is described by the given `Ellipsis`.
+    pub fn truncate_with<'a, F>(text: &'a str,
+                                max_width: Scalar,
+                                ellipsis: Ellipsis,
+                                mut char_width: F) -> Cow<'a, str>
+        where F: FnMut(char) -> Scalar,
+    {
+        let widths: Vec<(usize, Scalar)> = text.char_indices()
+            .map(|(i, ch)| (i + ch.len_utf8(), char_width(ch)))
+            .collect();
+        let total_w = widths.iter().fold(0.0, |total, &(_, w)| total + w);
+        if total_w <= max_width {
+            return Cow::Borrowed(text);
+        }
+
+        let ellipsis_w = ELLIPSIS.chars().fold(0.0, |total, ch| total + char_width(ch));
+        if ellipsis_w > max_width {
+            return match ellipsis {
+                Ellipsis::Hide => Cow::Borrowed(""),
+                Ellipsis::Partial => {
+                    let period_w = char_width('.');
+                    let mut periods = String::new();
+                    let mut w = period_w;
+                    while periods.len() < 3 && w <= max_width {
+                        periods.push('.');
+                        w += period_w;
+                    }
+                    Cow::Owned(periods)
+                },
+            };
+        }
+
+        // Find the end of the last character that leaves room for the ellipsis.
+        let mut end = 0;
+        let mut w = 0.0;
+        for &(char_end, char_w) in &widths {
+            if w + char_w + ellipsis_w > max_width {
+                break;
+            }
+            w += char_w;
+            end = char_end;
+        }
+        let mut truncated = text[..end].trim_right().to_owned();
+        truncated.push_str(ELLIPSIS);
+        Cow::Owned(truncated)
+    }
+
+    /// Determine the additional spacing that should be inserted after each whitespace character
+    /// within the given `line` so that it is justified to fill the given `max_width`.
+    ///
+    /// Only lines that were wrapped due to exceeding the `max_width` are justified, so `0.0` is
+    /// returned for the last line of a paragraph (i.e. ending with a newline or the end of the
+    /// text). `0.0` is also returned for lines without any whitespace between words, such as a
+    /// single word, in which case the line should fall back to its regular alignment.
+    pub fn justify_spacing(line: &str, info: &Info, max_width: Scalar) -> Scalar {
+        match info.end_break {
+            Break::Wrap { .. } => (),
+            Break::Newline { .. } | Break::End { .. } => return 0.0,
+        }
+        let num_gaps = line.trim_right().chars().filter(|ch| ch.is_whitespace()).count();
+        if num_gaps == 0 || info.width >= max_width {
+            return 0.0;
+        }
+        (max_width - info.width) / num_gaps as Scalar
+    }
+
     /// Produce an iterator yielding the bounding `Rect` for each line in the text.
     ///
     /// This function assumes that `font_size` is the same `FontSize` used to produce the `Info`s
@@ -1295,20 +2187,85 @@ pub mod line {
         where I: Iterator<Item=(&'a str, Rect)>,
     {
         SelectedRects {
-            selected_char_rects_per_line:
-                super::glyph::selected_rects_per_line(lines_with_rects, font, font_size, start, end)
+            selected_char_rects_per_line: super::glyph::selected_rects_per_line(lines_with_rects,
+                                                                                font,
+                                                                                font_size,
+                                                                                start,
+                                                                                end),
+            line_rects: Vec::new().into_iter(),
+        }
+    }
+
+    /// Produce a highlight `Rect` for each line containing characters within the given range of
+    /// `chars`, where each line is described by its text, `Info` and `Rect`.
+    ///
+    /// A selection spanning multiple lines produces one `Rect` per line. Lines that contain none
+    /// of the selected characters are skipped.
+    pub fn highlight_rects<'a, I>(lines: I,
+                                  chars: std::ops::Range<usize>,
+                                  font: &super::Font,
+                                  font_size: FontSize) -> Vec<Rect>
+        where I: Iterator<Item=(&'a str, Info, Rect)>,
+    {
+        let scale = super::pt_to_scale(font_size);
+        let mut last_glyph = None;
+        highlight_rects_with(lines, chars, |ch, is_line_start| {
+            if is_line_start {
+                last_glyph = None;
+            }
+            advance_width(ch, font, fallbacks, scale, &mut last_glyph)
+        })
+    }
+
+    /// The same as `highlight_rects` but determines the advance width of each character using
+    /// the given `char_width` function rather than a `Font`.
+    ///
+    /// `char_width` is also given whether or not the character is the first on its line.
+    pub fn highlight_rects_with<'a, I, F>(lines: I,
+                                          chars: std::ops::Range<usize>,
+                                          mut char_width: F) -> Vec<Rect>
+        where I: Iterator<Item=(&'a str, Info, Rect)>,
+              F: FnMut(char, bool) -> Scalar,
+    {
+        let mut rects = Vec::new();
+        for (line, info, line_rect) in lines {
+            let end_char = info.end_char();
+            if chars.start >= end_char || chars.end <= info.start_char {
+                continue;
+            }
+
+            // Find the offset of the start and end of the selection from the left of the line.
+            let (mut start_x, mut end_x) = (0.0, 0.0);
+            let mut x = 0.0;
+            for (i, ch) in line.chars().enumerate() {
+                let char_i = info.start_char + i;
+                if char_i == chars.start {
+                    start_x = x;
+                }
+                x += char_width(ch, i == 0);
+                if char_i < chars.end {
+                    end_x = x;
+                }
+            }
+
+            let left = line_rect.left();
+            let y = line_rect.y;
+            rects.push(Rect { x: Range::new(left + start_x, left + end_x), y: y });
         }
+        rects
     }
 
 
     impl<'a, F> Iterator for Infos<'a, F>
-        where F: for<'b> FnMut(&'b str, &'b super::Font, FontSize, Scalar) -> (Break, Scalar)
+        where F: for<'b> FnMut(&'b str, &'b super::Font, &'b [&'b super::Font], FontSize, Scalar)
+                                -> (Break, Scalar)
     {
         type Item = Info;
         fn next(&mut self) -> Option<Self::Item> {
             let Infos {
                 text,
                 font,
+                fallbacks,
                 font_size,
                 max_width,
                 ref mut next_break_fn,
@@ -1316,77 +2273,92 @@ pub mod line {
                 ref mut start_char,
                 ref mut last_break,
             } = *self;
+            next_info(text, start_byte, start_char, last_break, |line| {
+                next_break_fn(line, font, fallbacks, font_size, max_width)
+            })
+        }
+    }
 
-            match next_break_fn(&text[*start_byte..], font, font_size, max_width) {
-                (next @ Break::Newline { .. }, width) | (next @ Break::Wrap { .. }, width) => {
-
-                    let next_break = match next {
-                        Break::Newline { byte, char, len_bytes } =>
-                            Break::Newline {
-                                byte: *start_byte + byte,
-                                char: *start_char + char,
-                                len_bytes: len_bytes,
-                            },
-                        Break::Wrap { byte, char, len_bytes } =>
-                            Break::Wrap {
-                                byte: *start_byte + byte,
-                                char: *start_char + char,
-                                len_bytes: len_bytes,
-                            },
-                        _ => unreachable!(),
-                    };
+    /// Produce the `Info` for the line beginning at the given `start_byte` and `start_char`,
+    /// advancing them to the start of the following line.
+    ///
+    /// The given `next_break_fn` is called with the remainder of the `text`.
+    fn next_info<F>(text: &str,
+                    start_byte: &mut usize,
+                    start_char: &mut usize,
+                    last_break: &mut Option<Break>,
+                    next_break_fn: F) -> Option<Info>
+        where F: FnOnce(&str) -> (Break, Scalar),
+    {
+        match next_break_fn(&text[*start_byte..]) {
+            (next @ Break::Newline { .. }, width) | (next @ Break::Wrap { .. }, width) => {
+
+                let next_break = match next {
+                    Break::Newline { byte, char, len_bytes } =>
+                        Break::Newline {
+                            byte: *start_byte + byte,
+                            char: *start_char + char,
+                            len_bytes: len_bytes,
+                        },
+                    Break::Wrap { byte, char, len_bytes } =>
+                        Break::Wrap {
+                            byte: *start_byte + byte,
+                            char: *start_char + char,
+                            len_bytes: len_bytes,
+                        },
+                    _ => unreachable!(),
+                };
+
+                let info = Info {
+                    start_byte: *start_byte,
+                    start_char: *start_char,
+                    end_break: next_break,
+                    width: width,
+                };
 
+                match next {
+                    Break::Newline { byte, char, len_bytes } |
+                    Break::Wrap { byte, char, len_bytes } => {
+                        *start_byte = info.start_byte + byte + len_bytes;
+                        *start_char = info.start_char + char + 1;
+                    },
+                    _ => unreachable!(),
+                };
+                *last_break = Some(next_break);
+                Some(info)
+            },
+
+            (Break::End { char, .. }, width) => {
+                // if the last line ends in a new line, or the entire text is empty, return an empty line Info
+                let empty_line = {
+                    match *last_break {
+                        Some(last_break_) => match last_break_ {
+                            Break::Newline { .. } => true,
+                            _ => false,
+                        }, None => true,
+                    }
+                };
+                if *start_byte < text.len() || empty_line {
+                    let total_bytes = text.len();
+                    let total_chars = *start_char + char;
+                    let end_break = Break::End {
+                        byte: total_bytes,
+                        char: total_chars,
+                    };
                     let info = Info {
                         start_byte: *start_byte,
                         start_char: *start_char,
-                        end_break: next_break,
+                        end_break: end_break,
                         width: width,
                     };
-
-                    match next {
-                        Break::Newline { byte, char, len_bytes } |
-                        Break::Wrap { byte, char, len_bytes } => {
-                            *start_byte = info.start_byte + byte + len_bytes;
-                            *start_char = info.start_char + char + 1;
-                        },
-                        _ => unreachable!(),
-                    };
-                    *last_break = Some(next_break);
+                    *start_byte = total_bytes;
+                    *start_char = total_chars;
+                    *last_break = Some(end_break);
                     Some(info)
-                },
-
-                (Break::End { char, .. }, width) => {
-                    // if the last line ends in a new line, or the entire text is empty, return an empty line Info
-                    let empty_line = {
-                        match *last_break {
-                            Some(last_break_) => match last_break_ {
-                                Break::Newline { .. } => true,
-                                _ => false,
-                            }, None => true,
-                        }
-                    };
-                    if *start_byte < text.len() || empty_line {
-                        let total_bytes = text.len();
-                        let total_chars = *start_char + char;
-                        let end_break = Break::End {
-                            byte: total_bytes,
-                            char: total_chars,
-                        };
-                        let info = Info {
-                            start_byte: *start_byte,
-                            start_char: *start_char,
-                            end_break: end_break,
-                            width: width,
-                        };
-                        *start_byte = total_bytes;
-                        *start_char = total_chars;
-                        *last_break = Some(end_break);
-                        Some(info)
-                    } else {
-                        None
-                    }
-                },
-            }
+                } else {
+                    None
+                }
+            },
         }
     }
 
@@ -1427,13 +2399,29 @@ pub mod line {
     {
         type Item = Rect;
         fn next(&mut self) -> Option<Self::Item> {
-            while let Some(mut rects) = self.selected_char_rects_per_line.next() {
-                if let Some(first_rect) = rects.next() {
-                    let total_selected_rect = rects.fold(first_rect, |mut total, next| {
-                        total.x.end = next.x.end;
-                        total
-                    });
-                    return Some(total_selected_rect);
+            if let Some(rect) = self.line_rects.next() {
+                return Some(rect);
+            }
+            while let Some(rects) = self.selected_char_rects_per_line.next() {
+                // Join the `Rect`s of visually adjacent characters.
+                let mut rects: Vec<Rect> = rects.collect();
+                rects.sort_by(|a, b| {
+                    a.x.start.partial_cmp(&b.x.start).unwrap_or(std::cmp::Ordering::Equal)
+                });
+                let mut joined: Vec<Rect> = Vec::with_capacity(rects.len());
+                for rect in rects {
+                    match joined.last_mut() {
+                        Some(total) if rect.x.start <= total.x.end => {
+                            total.x.end = rect.x.end.max(total.x.end);
+                            continue;
+                        },
+                        _ => (),
+                    }
+                    joined.push(rect);
+                }
+                self.line_rects = joined.into_iter();
+                if let Some(rect) = self.line_rects.next() {
+                    return Some(rect);
                 }
             }
             None
@@ -1441,3 +2429,255 @@ pub mod line {
     }
 
 }
+
+
+#[test]
+fn wrap_with_should_break_at_whitespace_and_within_long_words() {
+    use widget::text::Wrap;
+    // Every character is 10 wide, so lines may hold at most 5 characters.
+    let text = "ab cd efghijklm n";
+    let lines: Vec<&str> = line::wrap_with(text, 50.0, Wrap::Whitespace, |_| 10.0)
+        .into_iter()
+        .map(|info| &text[info.byte_range()])
+        .collect();
+    assert_eq!(vec!["ab cd", "efghi", "jklm", "n"], lines);
+}
+
+#[test]
+fn wrap_with_should_break_at_the_character_exceeding_the_width() {
+    use widget::text::Wrap;
+    let text = "ab cd ef\ngh";
+    let infos = line::wrap_with(text, 35.0, Wrap::Character, |_| 10.0);
+    let lines: Vec<&str> = infos.iter().map(|info| &text[info.byte_range()]).collect();
+    assert_eq!(vec!["ab ", "cd ", "ef", "gh"], lines);
+    assert_eq!(vec![30.0, 30.0, 20.0, 20.0], infos.iter().map(|i| i.width).collect::<Vec<_>>());
+
+    // A character wider than the maximum width still occupies its own line.
+    let lines = line::wrap_with("abc", 5.0, Wrap::Character, |_| 10.0);
+    assert_eq!(3, lines.len());
+}
+
+#[test]
+fn line_rects_should_be_offset_by_alignment() {
+    use widget::text::Wrap;
+    use {Align, Rect};
+    let text = "ab cd efgh";
+    let infos = line::wrap_with(text, 50.0, Wrap::Whitespace, |_| 10.0);
+    let bounds = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let lefts = |x_align| {
+        line::rects(infos.iter().cloned(), 10, bounds, x_align, Align::End, 0.0)
+            .map(|rect| rect.left())
+            .collect::<Vec<_>>()
+    };
+    // The lines are "ab cd" (50 wide) and "efgh" (40 wide).
+    assert_eq!(vec![0.0, 0.0], lefts(Align::Start));
+    assert_eq!(vec![25.0, 30.0], lefts(Align::Middle));
+    assert_eq!(vec![50.0, 60.0], lefts(Align::End));
+}
+
+#[test]
+fn justify_spacing_should_only_stretch_wrapped_lines_with_gaps() {
+    use widget::text::Wrap;
+    let text = "ab c dd efghijk";
+    let infos = line::wrap_with(text, 75.0, Wrap::Whitespace, |_| 10.0);
+    let spacing: Vec<Scalar> = infos.iter()
+        .map(|info| line::justify_spacing(&text[info.byte_range()], info, 75.0))
+        .collect();
+    // "ab c dd" is 70 wide with two gaps, "efghijk" is a single word and the last line.
+    assert_eq!(vec![2.5, 0.0], spacing);
+}
+
+#[test]
+fn lru_should_evict_the_least_recently_used_glyphs_first() {
+    let mut lru = cache::Lru::new(10);
+    assert!(lru.insert('a', 4).is_empty());
+    assert!(lru.insert('b', 4).is_empty());
+    assert!(lru.touch(&'a'));
+
+    // `b` is now the least recently used.
+    assert_eq!(vec!['b'], lru.insert('c', 4));
+    assert_eq!(8, lru.total_bytes());
+
+    // Making room for a large glyph evicts multiple entries, oldest first.
+    assert_eq!(vec!['a', 'c'], lru.insert('d', 9));
+    assert_eq!(vec!['d'], lru.set_max_bytes(5));
+    assert!(lru.is_empty());
+}
+
+#[test]
+fn lru_should_not_cache_glyphs_larger_than_the_budget() {
+    let mut lru = cache::Lru::new(10);
+    lru.insert('a', 3);
+    assert_eq!(vec!['z'], lru.insert('z', 11));
+    assert!(lru.contains(&'a'));
+    assert!(!lru.contains(&'z'));
+    assert_eq!(3, lru.total_bytes());
+}
+
+#[test]
+fn dimensions_should_measure_the_widest_line() {
+    // Each character is half as wide as the font size.
+    let char_w = |line: &str| line.chars().count() as Scalar * 5.0;
+    assert_eq!([25.0, 10.0], dimensions_with("hello", 10, char_w));
+    assert_eq!([30.0, 30.0], dimensions_with("ab\r\nabcdef\nabc", 10, char_w));
+    assert_eq!([10.0, 20.0], dimensions_with("ab\n", 10, char_w));
+}
+
+#[test]
+fn dimensions_of_empty_text_should_be_one_line_high() {
+    let dims = dimensions_with("", 24, |line| line.chars().count() as Scalar * 12.0);
+    assert_eq!([0.0, 24.0], dims);
+}
+
+#[test]
+fn highlight_rects_should_span_the_selection_on_a_single_line() {
+    use widget::text::Wrap;
+    use Rect;
+    let text = "hello world";
+    let infos = line::wrap_with(text, 1000.0, Wrap::Whitespace, |_| 10.0);
+    let line_rect = Rect::from_corners([0.0, 0.0], [110.0, 20.0]);
+    let lines = infos.iter().map(|info| (&text[info.byte_range()], *info, line_rect));
+    let rects = line::highlight_rects_with(lines, 2..7, |_, _| 10.0);
+    assert_eq!(vec![Rect::from_corners([20.0, 0.0], [70.0, 20.0])], rects);
+}
+
+#[test]
+fn highlight_rects_should_produce_a_rect_per_wrapped_line() {
+    use widget::text::Wrap;
+    use {Align, Rect};
+    // Wraps into the lines "aaa", "bbb" and "ccc".
+    let text = "aaa bbb ccc";
+    let infos = line::wrap_with(text, 40.0, Wrap::Whitespace, |_| 10.0);
+    let bounds = Rect::from_corners([0.0, 0.0], [40.0, 30.0]);
+    let line_rects = line::rects(infos.iter().cloned(), 10, bounds, Align::Start, Align::End, 0.0);
+    let lines = infos.iter().zip(line_rects)
+        .map(|(info, rect)| (&text[info.byte_range()], *info, rect));
+    let rects = line::highlight_rects_with(lines, 1..9, |_, _| 10.0);
+    assert_eq!(3, rects.len());
+    assert_eq!((10.0, 30.0), (rects[0].left(), rects[0].right()));
+    assert_eq!((0.0, 30.0), (rects[1].left(), rects[1].right()));
+    assert_eq!((0.0, 10.0), (rects[2].left(), rects[2].right()));
+    assert_eq!((30.0, 20.0), (rects[0].top(), rects[0].bottom()));
+    assert_eq!((10.0, 0.0), (rects[2].top(), rects[2].bottom()));
+}
+
+#[test]
+fn width_with_should_tighten_kerned_pairs() {
+    fn advance(_: char) -> Scalar {
+        10.0
+    }
+    fn kerning(a: char, b: char) -> Scalar {
+        if (a, b) == ('A', 'V') { -2.5 } else { 0.0 }
+    }
+    let av = line::width_with("AV", advance, kerning);
+    assert!(av < advance('A') + advance('V'));
+    assert_eq!(17.5, av);
+    assert_eq!(37.5, line::width_with("AVAB", advance, kerning));
+    // Without a kerning table, text advances by the plain advance of each character.
+    assert_eq!(20.0, line::width_with("AV", advance, |_, _| 0.0));
+}
+
+#[test]
+fn monospace_advances_should_be_uniform_per_cell() {
+    use widget::text::Wrap;
+    let advance = |ch| line::monospace_advance(ch, 8.0);
+    assert_eq!(8.0, advance('i'));
+    assert_eq!(8.0, advance('W'));
+    assert_eq!(32.0, line::width_with("iiWW", &advance, |_, _| 0.0));
+
+    // Wide characters occupy two cells.
+    assert_eq!(2, line::char_cells('漢'));
+    assert_eq!(2, line::char_cells('가'));
+    assert_eq!(1, line::char_cells('a'));
+    assert_eq!(40.0, line::width_with("a漢字b", &advance, |_, _| 0.0));
+
+    // A wide character that does not fit within the remaining cells wraps to the next line.
+    let infos = line::wrap_with("abc漢", 32.0, Wrap::Character, &advance);
+    assert_eq!(vec![24.0, 16.0], infos.iter().map(|info| info.width).collect::<Vec<_>>());
+}
+
+#[test]
+fn truncate_with_should_end_overflowing_text_with_an_ellipsis() {
+    use widget::text::Ellipsis;
+    fn width(_: char) -> Scalar {
+        10.0
+    }
+    // Text that fits is left untouched.
+    assert_eq!("hello", line::truncate_with("hello", 50.0, Ellipsis::Hide, width));
+    // "hello world" is 110 wide, so drop characters until the ellipsis fits.
+    assert_eq!("hello w\u{2026}", line::truncate_with("hello world", 80.0, Ellipsis::Hide, width));
+    // Whitespace preceding the ellipsis is dropped.
+    assert_eq!("hello\u{2026}", line::truncate_with("hello world", 70.0, Ellipsis::Hide, width));
+    assert_eq!("\u{2026}", line::truncate_with("hello world", 10.0, Ellipsis::Hide, width));
+}
+
+#[test]
+fn truncate_with_should_hide_or_shorten_an_ellipsis_that_does_not_fit() {
+    use widget::text::Ellipsis;
+    fn width(ch: char) -> Scalar {
+        if ch == '.' { 3.0 } else { 10.0 }
+    }
+    assert_eq!("", line::truncate_with("hello", 7.0, Ellipsis::Hide, width));
+    assert_eq!("..", line::truncate_with("hello", 7.0, Ellipsis::Partial, width));
+    assert_eq!("", line::truncate_with("hello", 2.0, Ellipsis::Partial, width));
+}
+
+#[test]
+fn bidi_should_reorder_right_to_left_runs_within_mixed_direction_lines() {
+    // A left-to-right line containing a right-to-left word.
+    let order = bidi::VisualOrder::new("abc אבג");
+    assert!(order.has_rtl());
+    assert_eq!("abc גבא", order.reorder("abc אבג"));
+    assert_eq!(Some(6), order.visual_index(4));
+    assert_eq!(Some(6), order.logical_index(4));
+
+    // A right-to-left line containing a left-to-right word.
+    assert_eq!("abc גבא", bidi::reorder("אבג abc"));
+
+    // Lines without right-to-left text are left untouched.
+    assert!(!bidi::VisualOrder::new("abc def").has_rtl());
+    match bidi::reorder("abc def") {
+        std::borrow::Cow::Borrowed(line) => assert_eq!("abc def", line),
+        std::borrow::Cow::Owned(_) => panic!("expected the line to be borrowed"),
+    }
+}
+
+#[test]
+fn bidi_cursor_should_move_visually_across_direction_boundaries() {
+    let order = bidi::VisualOrder::new("abc אבג");
+    // Moving right through `abc ` and then through the reversed Hebrew word, whose logical end is
+    // displayed at its left edge.
+    let mut moves = vec![];
+    let mut char_idx = 0;
+    while let Some(next) = order.cursor_right(char_idx) {
+        moves.push(next);
+        char_idx = next;
+    }
+    assert_eq!(vec![1, 2, 3, 7, 6, 5, 4], moves);
+    assert_eq!(Some(5), order.cursor_left(4));
+    assert_eq!(Some(3), order.cursor_left(7));
+    assert_eq!(None, order.cursor_left(0));
+}
+
+#[test]
+fn cursor_should_move_over_whole_grapheme_clusters() {
+    use widget::text::Wrap;
+    // An emoji with a skin-tone modifier, followed by an `e` with a combining acute accent.
+    let text = "a\u{1F44D}\u{1F3FD}e\u{301}b";
+    assert_eq!(vec![0, 1, 3, 5, 6], cursor::grapheme_boundaries(text));
+    let infos = line::wrap_with(text, 1000.0, Wrap::Whitespace, |_| 10.0);
+    let idx = |char| cursor::Index { line: 0, char: char };
+    let next = |char| idx(char).next_grapheme(text, infos.iter().cloned());
+    let previous = |char| idx(char).previous_grapheme(text, infos.iter().cloned());
+    assert_eq!(Some(idx(3)), next(1));
+    assert_eq!(Some(idx(5)), next(3));
+    assert_eq!(Some(idx(3)), next(2));
+    assert_eq!(None, next(6));
+    assert_eq!(Some(idx(3)), previous(5));
+    assert_eq!(Some(idx(1)), previous(3));
+    assert_eq!(Some(idx(1)), previous(2));
+    assert_eq!(None, previous(0));
+    // Left-to-right lines are traversed by grapheme too.
+    assert_eq!(Some(idx(3)), idx(5).left(text, infos.iter().cloned()));
+    assert_eq!(Some(idx(5)), idx(3).right(text, infos.iter().cloned()));
+}
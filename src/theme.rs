@@ -3,7 +3,7 @@
 //!
 
 use Scalar;
-use color::{Color, BLACK, WHITE};
+use color::{self, Color, BLACK, WHITE};
 use position::{Align, Direction, Padding, Position};
 use std;
 use std::any::Any;
@@ -31,6 +31,8 @@ pub struct Theme {
     pub border_width: Scalar,
     /// A default color for widget labels.
     pub label_color: Color,
+    /// The color with which disabled widgets are drawn in place of their usual color.
+    pub disabled_color: Color,
     /// The `Id` of the default font used for text widgets when one is not specified.
     pub font_id: Option<text::font::Id>,
     /// A default "large" font size.
@@ -47,6 +49,24 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The `Duration` for which the mouse must hover over a widget before the `Ui` displays the
+    /// widget's tooltip.
+    pub tooltip_delay: std::time::Duration,
+    /// Whether or not colors should be blended in linear RGB space rather than sRGB space.
+    ///
+    /// `render::Primitives` always yield sRGB colors and report this flag via
+    /// `PrimitiveWalker::gamma_correct_blending`, leaving the conversion to the backend. The
+    /// software backend honours it by compositing each pixel in linear RGB. The GPU backends
+    /// already blend in linear space whenever they draw to an sRGB framebuffer, converting colors
+    /// themselves, so this flag has no effect on them.
+    pub gamma_correct_blending: bool,
+}
+
+/// A type used for building a **Theme** by overriding only some of the default values.
+///
+/// All values that are not set remain equal to those of `Theme::default`.
+pub struct ThemeBuilder {
+    theme: Theme,
 }
 
 /// The defaults for a specific widget.
@@ -90,6 +110,7 @@ impl Theme {
             border_color: BLACK,
             border_width: 1.0,
             label_color: BLACK,
+            disabled_color: color::GREY,
             font_id: None,
             font_size_large: 26,
             font_size_medium: 18,
@@ -97,9 +118,39 @@ impl Theme {
             widget_styling: std::collections::HashMap::new(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            tooltip_delay: std::time::Duration::from_millis(500),
+            gamma_correct_blending: false,
         }
     }
 
+    /// A preset theme with light-colored text and widgets upon a dark background.
+    pub fn dark() -> Theme {
+        Theme::builder()
+            .name("Dark Theme")
+            .background_color(color::DARK_CHARCOAL)
+            .shape_color(color::CHARCOAL)
+            .border_color(BLACK)
+            .label_color(color::LIGHT_GREY)
+            .disabled_color(color::DARK_GREY)
+            .build()
+    }
+
+    /// A preset theme with dark-colored text and widgets upon a light background.
+    pub fn light() -> Theme {
+        Theme::builder()
+            .name("Light Theme")
+            .background_color(color::LIGHT_GREY)
+            .shape_color(WHITE)
+            .border_color(color::DARK_GREY)
+            .label_color(color::DARK_CHARCOAL)
+            .build()
+    }
+
+    /// Begin building a new **Theme**, starting from `Theme::default`.
+    pub fn builder() -> ThemeBuilder {
+        ThemeBuilder::new()
+    }
+
     /// Retrieve the unique default styling for a widget.
     ///
     /// Attempts to cast the `Box<WidgetStyle>` to the **Widget**'s unique associated style **T**.
@@ -119,3 +170,182 @@ impl Theme {
     }
 
 }
+
+impl ThemeBuilder {
+
+    /// Begin building a new **Theme**, starting from `Theme::default`.
+    pub fn new() -> Self {
+        ThemeBuilder {
+            theme: Theme::default(),
+        }
+    }
+
+    /// A name for the theme used for identification.
+    pub fn name<S>(mut self, value: S) -> Self
+        where S: Into<String>,
+    {
+        self.theme.name = value.into();
+        self
+    }
+
+    /// Padding for Canvas layout and positioning.
+    pub fn padding(mut self, value: Padding) -> Self {
+        self.theme.padding = value;
+        self
+    }
+
+    /// A default widget position along the *x* axis.
+    pub fn x_position(mut self, value: Position) -> Self {
+        self.theme.x_position = value;
+        self
+    }
+
+    /// A default widget position along the *y* axis.
+    pub fn y_position(mut self, value: Position) -> Self {
+        self.theme.y_position = value;
+        self
+    }
+
+    /// A default background for the theme.
+    pub fn background_color(mut self, value: Color) -> Self {
+        self.theme.background_color = value;
+        self
+    }
+
+    /// A default color for widget shapes.
+    pub fn shape_color(mut self, value: Color) -> Self {
+        self.theme.shape_color = value;
+        self
+    }
+
+    /// A default color for widget borders.
+    pub fn border_color(mut self, value: Color) -> Self {
+        self.theme.border_color = value;
+        self
+    }
+
+    /// A default width for widget borders.
+    pub fn border_width(mut self, value: Scalar) -> Self {
+        self.theme.border_width = value;
+        self
+    }
+
+    /// A default color for widget labels.
+    pub fn label_color(mut self, value: Color) -> Self {
+        self.theme.label_color = value;
+        self
+    }
+
+    /// The color with which disabled widgets are drawn in place of their usual color.
+    pub fn disabled_color(mut self, value: Color) -> Self {
+        self.theme.disabled_color = value;
+        self
+    }
+
+    /// The `Id` of the default font used for text widgets when one is not specified.
+    pub fn font_id(mut self, value: text::font::Id) -> Self {
+        self.theme.font_id = Some(value);
+        self
+    }
+
+    /// A default "large" font size.
+    pub fn font_size_large(mut self, value: u32) -> Self {
+        self.theme.font_size_large = value;
+        self
+    }
+
+    /// A default "medium" font size.
+    pub fn font_size_medium(mut self, value: u32) -> Self {
+        self.theme.font_size_medium = value;
+        self
+    }
+
+    /// A default "small" font size.
+    pub fn font_size_small(mut self, value: u32) -> Self {
+        self.theme.font_size_small = value;
+        self
+    }
+
+    /// The minimum distance that the mouse must move from the mouse-down point
+    /// before starting a drag operation.
+    pub fn mouse_drag_threshold(mut self, value: Scalar) -> Self {
+        self.theme.mouse_drag_threshold = value;
+        self
+    }
+
+    /// The maximum `Duration` that may separate two consecutive `Click`s for a
+    /// `DoubleClick` event to be generated.
+    pub fn double_click_threshold(mut self, value: std::time::Duration) -> Self {
+        self.theme.double_click_threshold = value;
+        self
+    }
+
+    /// The `Duration` for which the mouse must hover over a widget before its tooltip is shown.
+    pub fn tooltip_delay(mut self, value: std::time::Duration) -> Self {
+        self.theme.tooltip_delay = value;
+        self
+    }
+
+    /// Whether or not colors should be blended in linear RGB space rather than sRGB space.
+    pub fn gamma_correct_blending(mut self, value: bool) -> Self {
+        self.theme.gamma_correct_blending = value;
+        self
+    }
+
+    /// Unique default styling for the widget whose **Widget::Style** is of type `T`.
+    pub fn widget_default<T>(mut self, value: WidgetDefault) -> Self
+        where T: widget::Style,
+    {
+        self.theme.widget_styling.insert(std::any::TypeId::of::<T>(), value);
+        self
+    }
+
+    /// Build the **Theme**.
+    pub fn build(self) -> Theme {
+        self.theme
+    }
+
+}
+
+
+#[test]
+fn theme_builder_should_only_override_given_fields() {
+    use color::{BLUE, RED};
+    let default = Theme::default();
+    let theme = Theme::builder()
+        .label_color(RED)
+        .font_size_medium(24)
+        .build();
+
+    assert_eq!(RED, theme.label_color);
+    assert_eq!(24, theme.font_size_medium);
+
+    assert_eq!(default.name, theme.name);
+    assert_eq!(default.padding, theme.padding);
+    assert_eq!(default.x_position, theme.x_position);
+    assert_eq!(default.y_position, theme.y_position);
+    assert_eq!(default.background_color, theme.background_color);
+    assert_eq!(default.shape_color, theme.shape_color);
+    assert_eq!(default.border_color, theme.border_color);
+    assert_eq!(default.border_width, theme.border_width);
+    assert_eq!(default.disabled_color, theme.disabled_color);
+    assert_eq!(default.font_id, theme.font_id);
+    assert_eq!(default.font_size_large, theme.font_size_large);
+    assert_eq!(default.font_size_small, theme.font_size_small);
+    assert_eq!(default.widget_styling.len(), theme.widget_styling.len());
+    assert_eq!(default.mouse_drag_threshold, theme.mouse_drag_threshold);
+    assert_eq!(default.double_click_threshold, theme.double_click_threshold);
+    assert_eq!(default.tooltip_delay, theme.tooltip_delay);
+    assert_eq!(default.gamma_correct_blending, theme.gamma_correct_blending);
+    assert!(BLUE != theme.label_color);
+}
+
+#[test]
+fn dark_and_light_presets_should_differ() {
+    let (dark, light) = (Theme::dark(), Theme::light());
+    assert!(dark.name != light.name);
+    assert!(dark.background_color != light.background_color);
+    assert!(dark.label_color != light.label_color);
+    assert!(dark.background_color.luminance() < light.background_color.luminance());
+    assert!(dark.label_color.luminance() > light.label_color.luminance());
+}
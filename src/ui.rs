@@ -1,9 +1,14 @@
+use anim;
+use clipboard::{self, Clipboard};
 use color::Color;
+use cursor::MouseCursor;
 use event;
 use graph::{self, Graph};
 use input;
-use position::{Align, Direction, Dimensions, Padding, Place, Point, Position, Range, Rect, Scalar};
+use position::{self, Align, Depth, Direction, Dimensions, Padding, Place, Point, Position, Range,
+               Rect, Scalar};
 use render;
+use snapshot::{self, LayoutSnapshot, WidgetLayout};
 use std;
 use text;
 use theme::Theme;
@@ -31,7 +36,17 @@ pub struct UiBuilder {
     ///
     /// If this field is `None` when `build` is called, these collections will be initialised with
     /// no pre-reserved size and will instead grow organically as needed.
-    pub maybe_widgets_capacity: Option<usize>
+    pub maybe_widgets_capacity: Option<usize>,
+    /// The number of physical pixels per logical unit of the window, e.g. `2.0` on most high-DPI
+    /// displays.
+    ///
+    /// By default this is `1.0`.
+    pub scale_factor: Scalar,
+    /// The clipboard used by text widgets for cut, copy and paste.
+    ///
+    /// If this field is `None` when `build` is called, an in-memory `clipboard::Memory` will be
+    /// used.
+    pub maybe_clipboard: Option<Box<Clipboard + Send>>,
 }
 
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
@@ -53,6 +68,8 @@ pub struct Ui {
     pub fonts: text::font::Map,
     /// The Widget cache, storing state for all widgets.
     widget_graph: Graph,
+    /// The `widget::Id`s that have been produced from user-provided names.
+    widget_names: widget::id::Names,
     /// The widget::Id of the widget that was last updated/set.
     maybe_prev_widget_id: Option<widget::Id>,
     /// The widget::Id of the last widget used as a parent for another widget.
@@ -80,6 +97,34 @@ pub struct Ui {
     /// the end of the `Ui::set_widgets` method. This ensures that the events are received by the
     /// target widgets during the next call to `Ui::set_widgets`.
     pending_scroll_events: Vec<event::Ui>,
+    /// Whether or not the `theme` has been replaced since the last call to `set_widgets`.
+    theme_has_changed: bool,
+    /// The number of times that the `Theme` has been replaced via `set_theme`.
+    theme_generation: usize,
+    /// The moment at which `set_widgets` was last called.
+    maybe_last_frame_start: Option<std::time::Instant>,
+    /// The durations of up to `FRAME_STATS_WINDOW` of the most recent frames, oldest first.
+    frame_durations: std::collections::VecDeque<std::time::Duration>,
+    /// The current time according to `Ui::step`, if the `Ui` is being driven by it.
+    maybe_stepped_time: Option<std::time::Instant>,
+    /// The number of physical pixels per logical unit of the window.
+    scale_factor: Scalar,
+    /// Whether or not the widgets should be updated again, regardless of input.
+    update_requested: bool,
+    /// The keyboard accelerators registered by widgets during the most recent update.
+    accelerators: input::accelerator::Registry,
+    /// The clipboard used by text widgets for cut, copy and paste.
+    clipboard: Box<Clipboard + Send>,
+    /// The `Tooltip` instantiated by the `Ui` for the widget under the mouse, generated the first
+    /// time that some tooltip is shown.
+    maybe_tooltip_id: Option<widget::Id>,
+    /// The widget with a tooltip that is under the mouse, along with the moment at which the
+    /// mouse began hovering over it.
+    maybe_tooltip_hover: Option<(widget::Id, std::time::Instant)>,
+    /// The stack of open `Modal`s from the bottom-most to the top-most, each paired with the
+    /// widget that was capturing the keyboard when it opened so that focus may be restored once
+    /// it closes.
+    modal_captures: Vec<(widget::Id, Option<widget::Id>)>,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -104,12 +149,94 @@ pub struct UiCell<'a> {
     ui: &'a mut Ui,
 }
 
+/// A description of a widget instantiated during the most recent update, used for querying the
+/// `Ui` via `Ui::find_widget`.
+#[derive(Copy, Clone, Debug)]
+pub struct WidgetInfo<'a> {
+    /// The widget's unique identifier.
+    pub id: widget::Id,
+    /// The `TypeId` of the widget's `Widget::State`, identifying the kind of widget.
+    ///
+    /// See `WidgetInfo::is` for a more convenient way of checking the kind of widget.
+    pub type_id: std::any::TypeId,
+    /// The area occupied by the widget.
+    pub rect: Rect,
+    /// The depth of the widget relative to its siblings.
+    pub depth: Depth,
+    /// The text displayed by the widget if there is any.
+    ///
+    /// For a `Text` widget, this is its string. For any other widget, this is the string of a
+    /// `Text` widget instantiated as one of its graphics (such as a `Button`'s label).
+    pub text: Option<&'a str>,
+}
+
+/// Timings of the most recent frames, where each frame spans from one call to `Ui::set_widgets`
+/// to the next.
+///
+/// Produced by the `Ui::frame_stats` method.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FrameStats {
+    /// The duration of the most recent frame.
+    pub last_frame: std::time::Duration,
+    /// The average duration of the most recent `FRAME_STATS_WINDOW` frames.
+    pub avg: std::time::Duration,
+    /// The number of frames per second according to the `avg` frame duration.
+    pub fps: f64,
+}
+
 
 /// Each time conrod is required to redraw the GUI, it must draw for at least the next three frames
 /// to ensure that, in the case that graphics buffers are being swapped, we have filled each
 /// buffer. Otherwise if we don't draw into each buffer, we will probably be subject to flickering.
 pub const SAFE_REDRAW_COUNT: u8 = 3;
 
+/// The number of most recent frames over which the `FrameStats` average is calculated.
+pub const FRAME_STATS_WINDOW: usize = 60;
+
+impl<'a> WidgetInfo<'a> {
+
+    /// Whether or not the widget is of the given type.
+    ///
+    /// Widgets that share the same `Widget::State` type (such as a `Button` of any `Show` kind)
+    /// are considered to be of the same type.
+    pub fn is<W>(&self) -> bool
+        where W: Widget,
+              W::State: std::any::Any + 'static,
+    {
+        self.type_id == std::any::TypeId::of::<W::State>()
+    }
+
+}
+
+impl FrameStats {
+
+    /// Calculate the `FrameStats` for the given frame durations, ordered from oldest to newest.
+    ///
+    /// If there are no durations, all fields are zero.
+    pub fn from_durations<I>(durations: I) -> Self
+        where I: IntoIterator<Item=std::time::Duration>,
+    {
+        let zero = std::time::Duration::from_secs(0);
+        let mut total = zero;
+        let mut count = 0;
+        let mut last_frame = zero;
+        for duration in durations {
+            total += duration;
+            count += 1;
+            last_frame = duration;
+        }
+        let avg = if count == 0 { zero } else { total / count };
+        let avg_secs = anim::secs(avg);
+        let fps = if avg_secs > 0.0 { 1.0 / avg_secs } else { 0.0 };
+        FrameStats {
+            last_frame: last_frame,
+            avg: avg,
+            fps: fps,
+        }
+    }
+
+}
+
 impl UiBuilder {
 
     /// Begin building a new `Ui` instance.
@@ -120,7 +247,9 @@ impl UiBuilder {
         UiBuilder {
             window_dimensions: window_dimensions,
             maybe_theme: None,
-            maybe_widgets_capacity: None
+            maybe_widgets_capacity: None,
+            scale_factor: 1.0,
+            maybe_clipboard: None,
         }
     }
 
@@ -150,6 +279,24 @@ impl UiBuilder {
         self
     }
 
+    /// The number of physical pixels per logical unit of the window, e.g. `2.0` on most high-DPI
+    /// displays.
+    ///
+    /// By default this is `1.0`.
+    pub fn scale_factor(mut self, value: Scalar) -> Self {
+        self.scale_factor = value;
+        self
+    }
+
+    /// The clipboard used by text widgets for cut, copy and paste.
+    ///
+    /// If this field is `None` when `build` is called, an in-memory `clipboard::Memory` will be
+    /// used.
+    pub fn clipboard(mut self, value: Box<Clipboard + Send>) -> Self {
+        self.maybe_clipboard = Some(value);
+        self
+    }
+
     /// Build **Ui** from the given builder
     pub fn build(self) -> Ui {
         Ui::new(self)
@@ -166,6 +313,8 @@ impl Ui {
             window_dimensions,
             maybe_widgets_capacity,
             maybe_theme,
+            scale_factor,
+            maybe_clipboard,
         } = builder;
 
         let (mut widget_graph, depth_order, updated_widgets) =
@@ -181,6 +330,7 @@ impl Ui {
         let prev_updated_widgets = updated_widgets.clone();
         Ui {
             widget_graph: widget_graph,
+            widget_names: widget::id::Names::new(),
             theme: maybe_theme.unwrap_or_else(|| Theme::default()),
             fonts: text::font::Map::new(),
             window: window,
@@ -196,7 +346,158 @@ impl Ui {
             prev_updated_widgets: prev_updated_widgets,
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
+            theme_has_changed: false,
+            theme_generation: 0,
+            maybe_last_frame_start: None,
+            frame_durations: std::collections::VecDeque::with_capacity(FRAME_STATS_WINDOW),
+            maybe_stepped_time: None,
+            scale_factor: scale_factor,
+            update_requested: true,
+            accelerators: input::accelerator::Registry::new(),
+            clipboard: maybe_clipboard.unwrap_or_else(|| Box::new(clipboard::Memory::new())),
+            maybe_tooltip_id: None,
+            maybe_tooltip_hover: None,
+            modal_captures: Vec::new(),
+        }
+    }
+
+    /// Timings of the most recent frames, where each frame spans from one call to
+    /// `Ui::set_widgets` to the next.
+    ///
+    /// Until `set_widgets` has been called twice, no frame has been timed and all fields are zero.
+    pub fn frame_stats(&self) -> FrameStats {
+        FrameStats::from_durations(self.frame_durations.iter().cloned())
+    }
+
+    /// The first widget that satisfies the given predicate, if any.
+    ///
+    /// Only the widgets that were instantiated during the most recent update are queried, in the
+    /// order in which they are drawn. This is particularly useful for asserting the presence of
+    /// some widget within tests.
+    pub fn find_widget<F>(&self, predicate: F) -> Option<widget::Id>
+        where F: Fn(&WidgetInfo) -> bool,
+    {
+        let graph = &self.widget_graph;
+        let texts = self.widget_texts();
+        self.depth_order.indices.iter().cloned().find(|&id| {
+            graph.widget(id).map_or(false, |container| {
+                let info = WidgetInfo {
+                    id: id,
+                    type_id: container.type_id,
+                    rect: container.rect,
+                    depth: container.depth,
+                    text: texts.get(&id).cloned(),
+                };
+                predicate(&info)
+            })
+        })
+    }
+
+    /// A snapshot of the layout of every widget instantiated during the most recent update,
+    /// sorted by index.
+    ///
+    /// All `Rect`s and depths are rounded to `snapshot::PRECISION` decimal places so that the
+    /// snapshot may be compared against some expected layout, either via `PartialEq`, its
+    /// `Display` implementation or (with the `serialize` feature) a serialized golden file.
+    pub fn dump_layout(&self) -> LayoutSnapshot {
+        let graph = &self.widget_graph;
+        let texts = self.widget_texts();
+        let widgets = self.depth_order.indices.iter().filter_map(|&id| {
+            graph.widget(id).map(|container| {
+                let rect = container.rect;
+                WidgetLayout {
+                    index: id.index(),
+                    kind: snapshot::kind_name(container.type_id),
+                    parent: graph.depth_parent(id).map(|parent| parent.index()),
+                    rect: [
+                        snapshot::round(rect.left()),
+                        snapshot::round(rect.bottom()),
+                        snapshot::round(rect.right()),
+                        snapshot::round(rect.top()),
+                    ],
+                    depth: snapshot::round(container.depth as Scalar) as Depth,
+                    floating: container.maybe_floating.is_some(),
+                    crop_kids: container.crop_kids,
+                    consumes_events: container.consumes_events,
+                    text: texts.get(&id).map(|&text| text.to_string()),
+                }
+            })
+        }).collect();
+        LayoutSnapshot::new(widgets)
+    }
+
+    /// Map each widget instantiated during the most recent update to its own text if it is a
+    /// `Text` widget, or otherwise to the text of its first `Text` graphic.
+    fn widget_texts(&self) -> std::collections::HashMap<widget::Id, &str> {
+        let graph = &self.widget_graph;
+        let text_of = |id: widget::Id| graph.widget(id)
+            .and_then(|container| container.unique_widget_state::<widget::Text>())
+            .map(|unique| &unique.state.string[..]);
+
+        let mut texts = std::collections::HashMap::new();
+        for &id in &self.depth_order.indices {
+            if let Some(text) = text_of(id) {
+                texts.insert(id, text);
+                if let Some(parent) = graph.graphic_parent(id) {
+                    texts.entry(parent).or_insert(text);
+                }
+            }
+        }
+        texts
+    }
+
+    /// The topmost widget under the mouse position of the given input `State`.
+    ///
+    /// Widgets are ordered by depth and then by the order in which they were instantiated, so when
+    /// widgets overlap the one that is rendered last is returned. Widgets are only hit within the
+    /// area to which they are cropped, so a point outside of a widget's visible area will not hit
+    /// it even if it is within the widget's `Rect`.
+    ///
+    /// Widgets that do not consume events are ignored (see `Widget::consume_events`).
+    pub fn widget_under_mouse(&self, input: &input::State) -> Option<widget::Id> {
+        graph::algo::pick_widgets(&self.depth_order.indices, input.mouse.xy)
+            .next(&self.widget_graph, &self.depth_order.indices)
+    }
+
+    /// The shape that the mouse cursor should currently take.
+    ///
+    /// If a widget is capturing the mouse, its cursor hint is used regardless of which widget the
+    /// mouse is over. Otherwise, the hint of the top-most widget under the mouse is used. Falls
+    /// back to `MouseCursor::Arrow` if the relevant widget has no hint.
+    pub fn mouse_cursor(&self) -> MouseCursor {
+        let input = &self.global_input.current;
+        input.widget_capturing_mouse
+            .or(input.widget_under_mouse)
+            .and_then(|id| self.widget_graph.widget(id))
+            .and_then(|container| container.maybe_cursor_hint)
+            .unwrap_or(MouseCursor::Arrow)
+    }
+
+    /// The widget under the mouse that describes itself with a tooltip, along with the text of
+    /// that tooltip.
+    ///
+    /// This is the top-most widget under the mouse if it was given a tooltip via
+    /// `Widget::tooltip`, otherwise its nearest depth parent that was. Widgets are considered
+    /// regardless of whether or not they are enabled.
+    pub fn tooltip_under_mouse(&self) -> Option<(widget::Id, &str)> {
+        let mut maybe_id = self.global_input.current.widget_under_mouse;
+        while let Some(id) = maybe_id {
+            let maybe_text = self.widget_graph.widget(id).and_then(|c| c.maybe_tooltip.as_ref());
+            if let Some(text) = maybe_text {
+                return Some((id, &text[..]));
+            }
+            maybe_id = self.widget_graph.depth_parent(id);
         }
+        None
+    }
+
+    /// Returns an immutable reference to the `input::Global` of the `Ui`.
+    ///
+    /// Unlike the `input::Widget` returned by `Ui::widget_input`, the global input is neither
+    /// filtered by capturing nor made relative to any widget. All coordinates here will be relative
+    /// to the center of the window, making it suitable for screen-space behaviour.
+    pub fn global_input(&self) -> &input::Global {
+        &self.global_input
     }
 
     /// Returns a `input::Widget` for the given widget
@@ -209,7 +510,15 @@ impl Ui {
             let bottom_edge = self.win_h / 2.0;
             Rect::from_xy_dim([right_edge, bottom_edge], [0.0, 0.0])
         });
-        input::Widget::for_widget(widget, rect, &self.global_input)
+        // Widgets that do not consume events observe all events instead.
+        let consumes_events = self.widget_graph.widget(widget)
+            .map(|container| container.consumes_events)
+            .unwrap_or(true);
+        if consumes_events {
+            input::Widget::for_widget(widget, rect, &self.global_input)
+        } else {
+            input::Widget::for_observer(widget, rect, &self.global_input)
+        }
     }
 
     /// The **Rect** for the widget at the given index.
@@ -290,6 +599,123 @@ impl Ui {
         widget::id::Generator::new(&mut self.widget_graph)
     }
 
+    /// The `widget::Id` for the given name, generating a new one the first time the name is used.
+    ///
+    /// Unlike generated `widget::Id`s, a name always refers to the same widget and hashes to a key
+    /// that is stable across runs (see `Ui::widget_names`).
+    ///
+    /// Returns a `NameCollision` if some other name that hashes to the same key is already in use.
+    pub fn widget_id_from_name(&mut self, name: &str)
+        -> Result<widget::Id, widget::id::NameCollision>
+    {
+        let Ui { ref mut widget_names, ref mut widget_graph, .. } = *self;
+        widget_names.get_or_generate(name, &mut widget::id::Generator::new(widget_graph))
+    }
+
+    /// The registry of all `widget::Id`s produced from names via `Ui::widget_id_from_name`.
+    pub fn widget_names(&self) -> &widget::id::Names {
+        &self.widget_names
+    }
+
+    /// The clipboard used by text widgets for cut, copy and paste.
+    pub fn clipboard(&mut self) -> &mut Clipboard {
+        &mut *self.clipboard
+    }
+
+    /// The keyboard accelerators registered by widgets via `UiCell::register_accelerator` during
+    /// the most recent update, along with any conflicts between them.
+    pub fn accelerators(&self) -> &input::accelerator::Registry {
+        &self.accelerators
+    }
+
+    /// A list of the keyboard shortcuts registered during the most recent update, suitable for
+    /// displaying as a cheat-sheet.
+    ///
+    /// Each shortcut is given as its chord (e.g. `"Ctrl+S"`) and the description with which it was
+    /// registered via `UiCell::register_accelerator_with_description`, or an empty description if
+    /// it was registered without one. The list is sorted by chord.
+    ///
+    /// Accelerators registered for widgets that were not instantiated during the most recent
+    /// update are excluded.
+    pub fn shortcut_help(&self) -> Vec<(String, String)> {
+        let mut help: Vec<(String, String)> = self.accelerators.iter()
+            .filter(|&(_, id)| self.updated_widgets.contains(id))
+            .map(|(accelerator, _)| {
+                let description = self.accelerators.description(accelerator).unwrap_or("");
+                (accelerator.to_string(), description.to_string())
+            })
+            .collect();
+        help.sort();
+        help
+    }
+
+    /// Give keyboard focus to the given widget, as though it had been clicked.
+    ///
+    /// If some other widget is capturing the keyboard, a `WidgetUncapturesKeyboard` event is
+    /// emitted for it before the `WidgetCapturesKeyboard` event for the given widget.
+    ///
+    /// If the widget was not instantiated during the current or previous update, a warning is
+    /// printed and focus remains unchanged. Focus also remains unchanged if the widget is blocked
+    /// by an open `Modal` (see `is_blocked_by_modal`).
+    pub fn focus_widget(&mut self, id: widget::Id) {
+        let is_mounted = self.widget_graph.widget(id).is_some()
+            && (self.updated_widgets.contains(&id) || self.prev_updated_widgets.contains(&id));
+        if !is_mounted {
+            use std::io::Write;
+            writeln!(std::io::stderr(),
+                     "Cannot focus the widget at the given WidgetId ({:?}) as it does not exist or \
+                     is not currently instantiated.", id).unwrap();
+            return;
+        }
+        if self.is_blocked_by_modal(id) {
+            return;
+        }
+
+        let input = &mut self.global_input;
+        match input.current.widget_capturing_keyboard {
+            Some(idx) if idx == id => return,
+            Some(idx) => input.push_event(event::Ui::WidgetUncapturesKeyboard(idx).into()),
+            None => (),
+        }
+        input.push_event(event::Ui::WidgetCapturesKeyboard(id).into());
+        input.current.widget_capturing_keyboard = Some(id);
+    }
+
+    /// The top-most `Modal` that is currently open, if any.
+    ///
+    /// A `Modal` opens when it is first instantiated and closes at the end of the first update in
+    /// which it is not instantiated.
+    pub fn top_modal(&self) -> Option<widget::Id> {
+        self.modal_captures.last().map(|&(id, _)| id)
+    }
+
+    /// Whether the given widget lies outside of the top-most open `Modal`.
+    ///
+    /// Such widgets may not capture the mouse or keyboard until the `Modal` is closed.
+    pub fn is_blocked_by_modal(&self, id: widget::Id) -> bool {
+        match self.top_modal() {
+            Some(modal) =>
+                id != modal && !self.widget_graph.does_recursive_depth_edge_exist(modal, id),
+            None => false,
+        }
+    }
+
+    /// Release any capture of the mouse or keyboard held by the given widget.
+    ///
+    /// A `WidgetUncapturesMouse` and/or `WidgetUncapturesKeyboard` event is emitted for each
+    /// capture released. This is used by widgets that become disabled while capturing input.
+    pub fn release_captures(&mut self, id: widget::Id) {
+        let input = &mut self.global_input;
+        if input.current.widget_capturing_mouse == Some(id) {
+            input.push_event(event::Ui::WidgetUncapturesMouse(id).into());
+            input.current.widget_capturing_mouse = None;
+        }
+        if input.current.widget_capturing_keyboard == Some(id) {
+            input.push_event(event::Ui::WidgetUncapturesKeyboard(id).into());
+            input.current.widget_capturing_keyboard = None;
+        }
+    }
+
     /// Scroll the widget at the given index by the given offset amount.
     ///
     /// The produced `Scroll` event will be applied upon the next call to `Ui::set_widgets`.
@@ -306,6 +732,124 @@ impl Ui {
         }
     }
 
+    /// Advance the `Ui` by the given input `events` and by the given duration `dt`.
+    ///
+    /// This is a backend-agnostic alternative to calling `handle_event` for each event, for
+    /// backends that drive the `Ui` with their own clock. Once `step` has been called, the `Ui`'s
+    /// time (see `Ui::now`) only advances by the given `dt`, so animations progress by exactly
+    /// `dt` during the following call to `set_widgets`, even if no `events` are given.
+    ///
+    /// The `events` are handled at the start of the step.
+    pub fn step(&mut self, events: &[event::Input], dt: std::time::Duration) {
+        for event in events {
+            self.handle_event(event.clone());
+        }
+        if !events.is_empty() {
+            self.needs_redraw();
+        }
+        let now = self.now();
+        self.maybe_stepped_time = Some(now + dt);
+    }
+
+    /// The current time according to the `Ui`, used by widgets for timing animations.
+    ///
+    /// This is the system time, unless the `Ui` is being driven by `Ui::step`, in which case it
+    /// is the sum of all durations given to `step` since it was first called.
+    pub fn now(&self) -> std::time::Instant {
+        self.maybe_stepped_time.unwrap_or_else(std::time::Instant::now)
+    }
+
+    /// The number of physical pixels per logical unit of the window.
+    ///
+    /// All widget positions and dimensions, along with the coordinates of all events given to
+    /// `handle_event`, are logical. Renderers multiply by this factor to find device coordinates.
+    pub fn scale_factor(&self) -> Scalar {
+        self.scale_factor
+    }
+
+    /// Set the number of physical pixels per logical unit of the window, e.g. when the window is
+    /// moved to a display with a different DPI.
+    pub fn set_scale_factor(&mut self, scale_factor: Scalar) {
+        if self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.needs_redraw();
+        }
+    }
+
+    /// Convert the given logical point (relative to the centre of the window with *y* pointing
+    /// upwards) to physical device coordinates (relative to the top left of the framebuffer in
+    /// pixels with *y* pointing downwards).
+    pub fn logical_to_physical(&self, point: Point) -> Point {
+        position::logical_to_physical(point, [self.win_w, self.win_h], self.scale_factor)
+    }
+
+    /// Convert the given point in physical device coordinates to a logical point.
+    ///
+    /// This is the inverse of `logical_to_physical`.
+    pub fn physical_to_logical(&self, point: Point) -> Point {
+        position::physical_to_logical(point, [self.win_w, self.win_h], self.scale_factor)
+    }
+
+    /// Handle a stage of composing text via an input method editor (IME).
+    ///
+    /// As `event::Input` has no way of describing text composition, backends that support IME
+    /// should call this upon receiving pre-edit and commit events from the windowing system. The
+    /// composition is delivered to the widget that is capturing the keyboard.
+    pub fn handle_composition(&mut self, composition: event::Composition) {
+        let widget = self.global_input.current.widget_capturing_keyboard;
+        let composition_event = event::Ui::Composition(widget, composition).into();
+        self.global_input.push_event(composition_event);
+    }
+
+    /// Handle an event whose coordinates are given in physical pixels, as reported by most
+    /// windowing systems, rather than logical units.
+    ///
+    /// - `Motion::MouseCursor` and `Motion::Touch` positions are relative to the top left of the
+    ///   framebuffer with *y* pointing downwards and are mapped back to logical space via
+    ///   `physical_to_logical`.
+    /// - `Motion::MouseRelative` and `Motion::MouseScroll` deltas are in conrod's orientation and
+    ///   are divided by the `scale_factor`.
+    /// - `Input::Resize` gives the dimensions of the framebuffer.
+    ///
+    /// Positions are never rounded, so that fractional scale factors can't offset hit-testing.
+    /// All other events are passed to `handle_event` unchanged.
+    pub fn handle_physical_event(&mut self, event: event::Input) {
+        use event::{Input, Motion};
+        let scale = self.scale_factor;
+        let event = match event {
+            Input::Move(Motion::MouseCursor(x, y)) => {
+                let xy = self.physical_to_logical([x, y]);
+                Input::Move(Motion::MouseCursor(xy[0], xy[1]))
+            },
+            Input::Move(Motion::Touch(args)) => {
+                let xy = self.physical_to_logical([args.x, args.y]);
+                Input::Move(Motion::Touch(input::TouchArgs { x: xy[0], y: xy[1], ..args }))
+            },
+            Input::Move(Motion::MouseRelative(x, y)) =>
+                Input::Move(Motion::MouseRelative(x / scale, y / scale)),
+            Input::Move(Motion::MouseScroll(x, y)) =>
+                Input::Move(Motion::MouseScroll(x / scale, y / scale)),
+            Input::Resize(w, h) => {
+                self.resize(w as Scalar / scale, h as Scalar / scale);
+                return;
+            },
+            event => event,
+        };
+        self.handle_event(event);
+    }
+
+    /// Resize the window to the given logical dimensions.
+    fn resize(&mut self, w: Scalar, h: Scalar) {
+        // Create a `WindowResized` event.
+        let window_resized = event::Ui::WindowResized([w, h]).into();
+        self.global_input.push_event(window_resized);
+
+        self.win_w = w;
+        self.win_h = h;
+        self.needs_redraw();
+        track_widget_under_mouse_and_update_capturing(self);
+    }
+
     /// Handle raw window events and update the `Ui` state accordingly.
     ///
     /// This occurs within several stages:
@@ -327,51 +871,6 @@ impl Ui {
         use input::{Button, Key, ModifierKey};
         use input::state::mouse::Button as MouseButton;
 
-        // Determines which widget is currently under the mouse and sets it within the `Ui`'s
-        // `input::Global`'s `input::State`.
-        //
-        // If the `widget_under_mouse` has changed, this function will also update the
-        // `widget_capturing_mouse`.
-        //
-        // If the left mouse button is up, we assume that the widget directly under the
-        // mouse cursor captures all input from the mouse.
-        //
-        // If the left mouse button is down, we assume that the widget that was clicked
-        // remains "pinned" and will continue to capture the mouse until it is
-        // released.
-        //
-        // Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
-        fn track_widget_under_mouse_and_update_capturing(ui: &mut Ui) {
-            ui.global_input.current.widget_under_mouse =
-                graph::algo::pick_widgets(&ui.depth_order.indices,
-                                          ui.global_input.current.mouse.xy)
-                                          .next(&ui.widget_graph, &ui.depth_order.indices);
-
-            // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
-            // under mouse.
-            if ui.global_input.current.mouse.buttons.left().is_up() {
-                let widget_under_mouse = ui.global_input.current.widget_under_mouse;
-
-                // Check to see if we need to uncapture a widget.
-                if let Some(idx) = ui.global_input.current.widget_capturing_mouse {
-                    if widget_under_mouse != Some(idx) {
-                        let event = event::Ui::WidgetUncapturesMouse(idx).into();
-                        ui.global_input.push_event(event);
-                        ui.global_input.current.widget_capturing_mouse = None;
-                    }
-                }
-
-                // Check to see if there is a new widget capturing the mouse.
-                if ui.global_input.current.widget_capturing_mouse.is_none() {
-                    if let Some(idx) = widget_under_mouse {
-                        let event = event::Ui::WidgetCapturesMouse(idx).into();
-                        ui.global_input.push_event(event);
-                        ui.global_input.current.widget_capturing_mouse = Some(idx);
-                    }
-                }
-            }
-        }
-
         // A function for filtering `ModifierKey`s.
         fn filter_modifier(key: Key) -> Option<ModifierKey> {
             use input::keyboard::{CTRL, SHIFT, ALT, GUI};
@@ -421,9 +920,11 @@ impl Ui {
 
                         // Check to see if we need to capture the keyboard.
                         if let Some(idx) = self.global_input.current.widget_under_mouse {
-                            let event = event::Ui::WidgetCapturesKeyboard(idx).into();
-                            self.global_input.push_event(event);
-                            self.global_input.current.widget_capturing_keyboard = Some(idx);
+                            if !self.is_blocked_by_modal(idx) {
+                                let event = event::Ui::WidgetCapturesKeyboard(idx).into();
+                                self.global_input.push_event(event);
+                                self.global_input.current.widget_capturing_keyboard = Some(idx);
+                            }
                         }
                     }
 
@@ -445,8 +946,16 @@ impl Ui {
                     self.global_input.push_event(press_event);
 
                     // If some modifier key was pressed, add it to the current modifiers.
+                    // Otherwise, activate the widget whose accelerator was pressed (if any).
                     if let Some(modifier) = filter_modifier(key) {
                         self.global_input.current.modifiers.insert(modifier);
+                    } else {
+                        let modifiers = self.global_input.current.modifiers;
+                        let accelerator = input::Accelerator::new(modifiers, key);
+                        if let Some(id) = self.accelerators.get(&accelerator) {
+                            let event = event::Ui::Accelerator(id, accelerator).into();
+                            self.global_input.push_event(event);
+                        }
                     }
 
                     // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
@@ -467,7 +976,9 @@ impl Ui {
             // Checks for events in the following order:
             // 1. Click
             // 2. DoubleClick
-            // 2. WidgetUncapturesMouse
+            // 3. ContextMenu
+            // 4. WidgetUncapturesMouse
+            // 5. WidgetCapturesMouse
             Input::Release(button_type) => match button_type {
                 Button::Mouse(mouse_button) => {
 
@@ -491,76 +1002,72 @@ impl Ui {
                                 if pressed == released { Some(released) } else { None }
                             }));
 
+                        let now = self.now();
+                        let xy = self.global_input.current.mouse.xy;
+                        let modifiers = self.global_input.current.modifiers;
+
+                        // Count the consecutive clicks made with the same button, at the same
+                        // position and within the double_click_threshold of one another.
+                        //
+                        // TODO: Work out how to get this threshold from the user's system
+                        // preferences.
+                        let threshold = self.theme.double_click_threshold;
+                        let count = match self.global_input.last_click {
+                            Some((last_time, last_click)) if last_click.button == mouse_button
+                                && last_click.xy == xy
+                                && now.duration_since(last_time) < threshold =>
+                                last_click.count + 1,
+                            _ => 1,
+                        };
+
                         let click = event::Click {
                             button: mouse_button,
-                            xy: self.global_input.current.mouse.xy,
-                            modifiers: self.global_input.current.modifiers,
+                            xy: xy,
+                            modifiers: modifiers,
+                            count: count,
                         };
 
                         let click_event = event::Ui::Click(clicked_widget, click).into();
                         self.global_input.push_event(click_event);
 
-                        let now = std::time::Instant::now();
-                        let double_click = self.global_input.last_click
-                            .and_then(|(last_time, last_click)| {
-
-                                // If the button of this click is different to the button
-                                // of last click, don't create a `DoubleClick`.
-                                if click.button != last_click.button {
-                                    return None;
-                                }
-
-                                // If the mouse has moved since the last click, don't
-                                // create a `DoubleClick`.
-                                if click.xy != last_click.xy {
-                                    return None;
-                                }
-
-                                // If the duration since the last click is longer than the
-                                // double_click_threshold, don't create a `DoubleClick`.
-                                let duration = now.duration_since(last_time);
-                                // TODO: Work out how to get this threshold from the user's
-                                // system preferences.
-                                let threshold = self.theme.double_click_threshold;
-                                if duration >= threshold {
-                                    return None;
-                                }
-
-                                Some(event::DoubleClick {
-                                    button: click.button,
-                                    xy: click.xy,
-                                    modifiers: click.modifiers,
-                                })
-                            });
-
-                        if let Some(double_click) = double_click {
-                            // Reset the `last_click` to `None`, as to not register another
-                            // `DoubleClick` on the next consecutive `Click`.
-                            self.global_input.last_click = None;
+                        // Every second consecutive click completes a `DoubleClick`.
+                        if count % 2 == 0 {
+                            let double_click = event::DoubleClick {
+                                button: click.button,
+                                xy: click.xy,
+                                modifiers: click.modifiers,
+                            };
                             let double_click_event =
                                 event::Ui::DoubleClick(clicked_widget, double_click).into();
                             self.global_input.push_event(double_click_event);
+                        }
 
-                        } else {
-                            // Set the `Click` that we just stored as the `last_click`.
-                            self.global_input.last_click = Some((now, click));
+                        // Right-clicking a widget requests its context menu.
+                        if let MouseButton::Right = mouse_button {
+                            let context_menu = event::ContextMenu { xy: xy, modifiers: modifiers };
+                            let context_menu_event =
+                                event::Ui::ContextMenu(clicked_widget, context_menu).into();
+                            self.global_input.push_event(context_menu_event);
                         }
+
+                        // Store the click that we just made as the `last_click`.
+                        self.global_input.last_click = Some((now, click));
                     }
 
-                    // Uncapture widget capturing mouse if MouseButton::Left is down and
-                    // widget_under_mouse != capturing widget.
+                    // Drop any drag-and-drop payload over the widget under the mouse.
                     if let MouseButton::Left = mouse_button {
-                        if let Some(idx) = self.global_input.current.widget_capturing_mouse {
-                            if Some(idx) != self.global_input.current.widget_under_mouse {
-                                let event = event::Ui::WidgetUncapturesMouse(idx).into();
-                                self.global_input.push_event(event);
-                                self.global_input.current.widget_capturing_mouse = None;
-                            }
-                        }
+                        let target = self.global_input.current.widget_under_mouse;
+                        self.global_input.drop_drag_payload(target);
                     }
 
                     // Release the given mouse_button from the input::State.
                     self.global_input.current.mouse.buttons.release(mouse_button);
+
+                    // Releasing MouseButton::Left unpins the widget capturing the mouse, so
+                    // capture passes to the widget now under the mouse.
+                    if let MouseButton::Left = mouse_button {
+                        track_widget_under_mouse_and_update_capturing(self);
+                    }
                 },
 
                 Button::Keyboard(key) => {
@@ -584,17 +1091,7 @@ impl Ui {
             },
 
             // The window was resized.
-            Input::Resize(w, h) => {
-                // Create a `WindowResized` event.
-                let (w, h) = (w as Scalar, h as Scalar);
-                let window_resized = event::Ui::WindowResized([w, h]).into();
-                self.global_input.push_event(window_resized);
-
-                self.win_w = w;
-                self.win_h = h;
-                self.needs_redraw();
-                track_widget_under_mouse_and_update_capturing(self);
-            },
+            Input::Resize(w, h) => self.resize(w as Scalar, h as Scalar),
 
             // The mouse cursor was moved to a new position.
             //
@@ -650,6 +1147,10 @@ impl Ui {
                     // The mouse was scrolled.
                     Motion::MouseScroll(x, y) => {
 
+                        // Apply the user's scroll sensitivity and direction.
+                        let delta = self.global_input.current.scroll_delta([x, y]);
+                        let (x, y) = (delta[0], delta[1]);
+
                         let mut scrollable_widgets = {
                             let depth_order = &self.depth_order.indices;
                             let mouse_xy = self.global_input.current.mouse.xy;
@@ -855,6 +1356,15 @@ impl Ui {
                         })
                         .unwrap_or(0.0),
 
+                Position::Anchor(anchor, direction, keep_in_window) => {
+                    let window = Rect::from_xy_dim([0.0, 0.0], [ui.win_w, ui.win_h]);
+                    let bounds = match keep_in_window {
+                        true => Some(range_from_rect(window)),
+                        false => None,
+                    };
+                    position::anchored(anchor, dim, direction, bounds)
+                },
+
                 Position::Place(place, maybe_id) => {
                     let parent_id = maybe_id
                         .or(ui.maybe_current_parent_id)
@@ -906,6 +1416,43 @@ impl Ui {
         self.maybe_prev_widget_id = None;
         self.maybe_current_parent_id = None;
 
+        // Animating widgets request another update during this one if they are yet to settle.
+        self.update_requested = false;
+
+        // Widgets re-register their accelerators each time they are instantiated.
+        self.accelerators.clear();
+
+        // Time the frame that is ended by this call.
+        let now = self.now();
+        if let Some(last_frame_start) = self.maybe_last_frame_start {
+            if self.frame_durations.len() == FRAME_STATS_WINDOW {
+                self.frame_durations.pop_front();
+            }
+            self.frame_durations.push_back(now.duration_since(last_frame_start));
+        }
+        self.maybe_last_frame_start = Some(now);
+
+        // Notify this update of all widgets that were mounted or unmounted by the previous one.
+        //
+        // These events are necessarily one update late, as the set of widgets instantiated by an
+        // update is only complete once all of its widgets have received its events.
+        {
+            let Ui { ref updated_widgets, ref prev_updated_widgets, ref mut global_input, .. } =
+                *self;
+            let mut mounted: Vec<_> =
+                updated_widgets.difference(prev_updated_widgets).cloned().collect();
+            let mut unmounted: Vec<_> =
+                prev_updated_widgets.difference(updated_widgets).cloned().collect();
+            mounted.sort();
+            unmounted.sort();
+            for id in mounted {
+                global_input.push_event(event::Ui::WidgetMounted(id).into());
+            }
+            for id in unmounted {
+                global_input.push_event(event::Ui::WidgetUnmounted(id).into());
+            }
+        }
+
         // Move the previous `updated_widgets` to `prev_updated_widgets` and clear
         // `updated_widgets` so that we're ready to store the newly updated widgets.
         {
@@ -914,6 +1461,10 @@ impl Ui {
             updated_widgets.clear();
         }
 
+        // The widgets under the mouse may have changed since the last time capture was
+        // arbitrated, even if the mouse has not moved.
+        track_widget_under_mouse_and_update_capturing(self);
+
         let mut ui_cell = UiCell { ui: self };
 
         // Instantiate the root `Window` `Widget`.
@@ -945,6 +1496,35 @@ impl Ui {
     }
 
 
+    /// Replace the active `Theme`, returning the previous one.
+    ///
+    /// All widgets will be re-drawn using the new `Theme` from the next call to `set_widgets`.
+    /// Widgets that cache values derived from the `Theme` within their `State` may check
+    /// `theme_has_changed` to determine when to invalidate them.
+    pub fn set_theme(&mut self, theme: Theme) -> Theme {
+        self.theme_has_changed = true;
+        self.theme_generation = self.theme_generation.wrapping_add(1);
+        self.needs_redraw();
+        std::mem::replace(&mut self.theme, theme)
+    }
+
+    /// Whether or not the `Theme` has been replaced via `set_theme` since the last time widgets
+    /// were set.
+    ///
+    /// This remains `true` for the duration of the first `set_widgets` stage following the
+    /// change.
+    pub fn theme_has_changed(&self) -> bool {
+        self.theme_has_changed
+    }
+
+    /// A number that changes every time the `Theme` is replaced via `set_theme`.
+    ///
+    /// Unlike `theme_has_changed`, this may be compared between frames, i.e. by a
+    /// `render::GeometryCache`.
+    pub fn theme_generation(&self) -> usize {
+        self.theme_generation
+    }
+
     /// Tells the `Ui` that it needs to be re-draw everything. It does this by setting the redraw
     /// count to `num_redraw_frames`. See the docs for `set_num_redraw_frames`, SAFE_REDRAW_COUNT
     /// or `draw_if_changed` for more info on how/why the redraw count is used.
@@ -952,6 +1532,48 @@ impl Ui {
         self.redraw_count = self.num_redraw_frames;
     }
 
+    /// Request that the widgets are updated and drawn again, even if no input arrives.
+    ///
+    /// Applications should call this when some state displayed by the `Ui` changes outside of
+    /// conrod, e.g. upon receiving a message from another thread. Widgets call this during every
+    /// update in which they are animating, so that the animation continues until it settles.
+    pub fn request_redraw(&mut self) {
+        self.update_requested = true;
+        self.needs_redraw();
+    }
+
+    /// Whether or not the widgets should be updated via `Ui::set_widgets`.
+    ///
+    /// This is `true` if input events have arrived since `Ui::set_widgets` was last called, if some
+    /// widget is animating or if `Ui::request_redraw` has been called since. The widgets must
+    /// always be updated before the first draw.
+    ///
+    /// Applications that only update their widgets when this is `true` are "event-driven": rather
+    /// than updating at a fixed frame rate, they may sleep whenever `redraw_needed` is `false`.
+    pub fn update_needed(&self) -> bool {
+        self.update_requested || self.global_input.events().next().is_some()
+    }
+
+    /// Update the widgets via `Ui::set_widgets`, but only if `Ui::update_needed`.
+    pub fn set_widgets_if_needed(&mut self) -> Option<UiCell> {
+        if self.update_needed() {
+            Some(self.set_widgets())
+        } else {
+            None
+        }
+    }
+
+    /// Whether or not the `Ui` has any reason to be updated and drawn again.
+    ///
+    /// This is `true` if `Ui::update_needed`, or if a redraw was requested and has not yet been
+    /// completed via `Ui::draw_if_changed`. A redraw is requested via `Ui::needs_redraw` whenever
+    /// the state of some widget changes.
+    ///
+    /// When this is `false`, applications may sleep until the next input event arrives.
+    pub fn redraw_needed(&self) -> bool {
+        self.redraw_count > 0 || self.update_needed()
+    }
+
     /// The first of the `Primitivees` yielded by `Ui::draw` or `Ui::draw_if_changed` will always
     /// be a `Rectangle` the size of the window in which conrod is hosted.
     ///
@@ -966,25 +1588,37 @@ impl Ui {
     /// NOTE: If you don't need to redraw your conrod GUI every frame, it is recommended to use the
     /// `Ui::draw_if_changed` method instead.
     pub fn draw(&mut self) -> render::Primitives {
+        // We're about to draw everything, so take one from the redraw count.
+        if self.redraw_count > 0 {
+            self.redraw_count -= 1;
+        }
+
+        self.draw_primitives()
+    }
+
+    /// The primitives describing the `Ui` in its current state, for use by custom backends and
+    /// within tests.
+    ///
+    /// The primitives are yielded bottom to top in the order in which they should be drawn (i.e.
+    /// the same order as `Ui::draw`), each with the `scizzor` to which it must be cropped.
+    ///
+    /// Unlike `Ui::draw`, this neither requires mutable access to the `Ui` nor counts as a redraw,
+    /// so it may be called any number of times without affecting `Ui::draw_if_changed`.
+    pub fn draw_primitives(&self) -> render::Primitives {
         let Ui {
-            ref mut redraw_count,
             ref widget_graph,
             ref depth_order,
             ref theme,
             ref fonts,
             win_w, win_h,
+            scale_factor,
             ..
         } = *self;
 
         // Use the depth_order indices as the order for drawing.
         let indices = &depth_order.indices;
 
-        // We're about to draw everything, so take one from the redraw count.
-        if *redraw_count > 0 {
-            *redraw_count -= 1;
-        }
-
-        render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h])
+        render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h], scale_factor)
     }
 
 
@@ -1047,7 +1681,7 @@ impl<'a> UiCell<'a> {
     ///
     /// All coordinates here will be relative to the center of the window.
     pub fn global_input(&self) -> &input::Global {
-        &self.ui.global_input
+        self.ui.global_input()
     }
 
     /// Returns a `input::Widget` with input events for the widget.
@@ -1065,6 +1699,76 @@ impl<'a> UiCell<'a> {
         self.ui.widget_id_generator()
     }
 
+    /// Give keyboard focus to the given widget.
+    ///
+    /// See the `Ui::focus_widget` docs for details.
+    pub fn focus_widget(&mut self, id: widget::Id) {
+        self.ui.focus_widget(id)
+    }
+
+    /// Release any capture of the mouse or keyboard held by the given widget.
+    ///
+    /// See the `Ui::release_captures` docs for details.
+    pub fn release_captures(&mut self, id: widget::Id) {
+        self.ui.release_captures(id)
+    }
+
+    /// The `widget::Id` for the given name, generating a new one the first time the name is used.
+    ///
+    /// See the `Ui::widget_id_from_name` docs for details.
+    pub fn widget_id_from_name(&mut self, name: &str)
+        -> Result<widget::Id, widget::id::NameCollision>
+    {
+        self.ui.widget_id_from_name(name)
+    }
+
+    /// The clipboard used by text widgets for cut, copy and paste.
+    pub fn clipboard(&mut self) -> &mut Clipboard {
+        self.ui.clipboard()
+    }
+
+    /// Register the given keyboard accelerator for the widget with the given `id`.
+    ///
+    /// When the accelerator is pressed, the widget receives an `event::Widget::Accelerator` during
+    /// the following update. Accelerators must be registered during every update in which they
+    /// should remain active.
+    ///
+    /// Returns a `Conflict` if some other widget has already registered the same accelerator
+    /// during this update, in which case the accelerator continues to activate that widget. All
+    /// conflicts are also reported via `Ui::accelerators`.
+    pub fn register_accelerator(&mut self, accelerator: input::Accelerator, id: widget::Id)
+        -> Result<(), input::accelerator::Conflict>
+    {
+        self.ui.accelerators.register(accelerator, id)
+    }
+
+    /// Register the given keyboard accelerator for the widget with the given `id`, along with a
+    /// description of what it does to be listed by `Ui::shortcut_help`.
+    ///
+    /// See the `UiCell::register_accelerator` docs for details.
+    pub fn register_accelerator_with_description(&mut self,
+                                                 accelerator: input::Accelerator,
+                                                 id: widget::Id,
+                                                 description: &str)
+        -> Result<(), input::accelerator::Conflict>
+    {
+        self.ui.accelerators.register_with_description(accelerator, id, description.to_string())
+    }
+
+    /// Describe the widget with the given `id` with some owned text, e.g. text formatted from the
+    /// widget's current value.
+    ///
+    /// This replaces any text given via `Widget::tooltip` and must be called after the widget is
+    /// set during every update in which the tooltip should remain. Does nothing if no widget
+    /// exists for the given `id`.
+    pub fn set_widget_tooltip<T>(&mut self, id: widget::Id, text: T)
+        where T: Into<std::borrow::Cow<'static, str>>,
+    {
+        if let Some(container) = self.ui.widget_graph.widget_mut(id) {
+            container.maybe_tooltip = Some(text.into());
+        }
+    }
+
     /// The **Rect** that bounds the kids of the widget with the given index.
     ///
     /// Returns `None` if the widget has no children or if there's is no widget for the given index.
@@ -1089,10 +1793,34 @@ impl<'a> UiCell<'a> {
         }
     }
 
+    /// Begin dragging the given `payload` from the `source` widget.
+    ///
+    /// See the [**input::DragPayload**](../input/global/struct.DragPayload.html) docs for details.
+    pub fn begin_drag(&mut self, source: widget::Id, payload: Box<std::any::Any + Send>) {
+        self.ui.global_input.begin_drag(source, payload);
+    }
+
+    /// Claim the payload of type `T` that was dropped over the `target` widget (if any).
+    pub fn take_dropped_payload<T>(&mut self, target: widget::Id) -> Option<Box<T>>
+        where T: std::any::Any,
+    {
+        self.ui.global_input.take_dropped_payload(target)
+    }
+
+    /// Reclaim a payload that originated from the `source` widget but was not claimed by a target.
+    pub fn take_returned_payload(&mut self, source: widget::Id)
+        -> Option<Box<std::any::Any + Send>>
+    {
+        self.ui.global_input.take_returned_payload(source)
+    }
+
 }
 
 impl<'a> Drop for UiCell<'a> {
     fn drop(&mut self) {
+        // Describe the hovered widget before determining whether or not the widgets have changed.
+        set_tooltip(self);
+
         // We'll need to re-draw if we have gained or lost widgets.
         if self.ui.updated_widgets != self.ui.prev_updated_widgets {
             self.ui.needs_redraw();
@@ -1114,6 +1842,13 @@ impl<'a> Drop for UiCell<'a> {
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
+        // Close the `Modal`s that were not instantiated, so that the widgets behind them may
+        // capture input during the next update.
+        close_uninstantiated_modals(self.ui);
+
+        // All widgets have now been updated with the current theme.
+        self.ui.theme_has_changed = false;
+
         // Move all pending `Scroll` events that have been produced since the start of this method
         // into the `global_input` event buffer.
         for scroll_event in self.ui.pending_scroll_events.drain(0..) {
@@ -1135,6 +1870,59 @@ impl<'a> AsRef<Ui> for UiCell<'a> {
     }
 }
 
+/// Instantiate the `Tooltip` of the widget under the mouse once the mouse has hovered over it
+/// for the theme's `tooltip_delay`.
+///
+/// The delay begins again whenever the mouse moves onto another widget. No tooltip is shown while
+/// a mouse button is down.
+fn set_tooltip(ui_cell: &mut UiCell) {
+    use {Positionable, Sizeable, Widget};
+
+    let now = ui_cell.now();
+    let maybe_hovered_id = ui_cell.tooltip_under_mouse().map(|(id, _)| id);
+    let maybe_hover = maybe_hovered_id.map(|id| match ui_cell.maybe_tooltip_hover {
+        Some((hovered_id, start)) if hovered_id == id => (id, start),
+        _ => (id, now),
+    });
+    ui_cell.ui.maybe_tooltip_hover = maybe_hover;
+
+    let (hovered_id, start) = match maybe_hover {
+        Some(hover) => hover,
+        None => return,
+    };
+    if ui_cell.global_input.current.mouse.buttons.pressed().next().is_some() {
+        return;
+    }
+
+    // Keep updating until the mouse has hovered for long enough to show the tooltip.
+    if now.duration_since(start) < ui_cell.theme.tooltip_delay {
+        ui_cell.ui.request_redraw();
+        return;
+    }
+
+    let text = match ui_cell.widget_graph().widget(hovered_id) {
+        Some(container) => container.maybe_tooltip.clone(),
+        None => None,
+    };
+    let tooltip = match text {
+        Some(text) => widget::Tooltip::new(text),
+        None => return,
+    };
+    let dim = tooltip.get_wh(ui_cell).unwrap_or([0.0, 0.0]);
+    let mouse_xy = ui_cell.global_input.current.mouse.xy;
+    let xy = widget::tooltip::position(mouse_xy, dim, [ui_cell.win_w, ui_cell.win_h]);
+    let id = match ui_cell.maybe_tooltip_id {
+        Some(id) => id,
+        None => {
+            let id = ui_cell.widget_id_generator().next();
+            ui_cell.ui.maybe_tooltip_id = Some(id);
+            id
+        },
+    };
+    let window = ui_cell.window;
+    tooltip.wh(dim).xy(xy).floating(true).parent(window).set(id, ui_cell);
+}
+
 /// A function for retrieving the `&mut Ui<B>` from a `UiCell<B>`.
 ///
 /// This function is only for internal use to allow for some `Ui` type acrobatics in order to
@@ -1208,3 +1996,101 @@ pub fn post_update_cache<W>(ui: &mut Ui, widget: widget::PostUpdateCache<W>)
     ui.maybe_current_parent_id = widget.maybe_parent_id;
     ui.widget_graph.post_update_cache(widget);
 }
+
+
+/// Determines which widget is currently under the mouse and arbitrates which widget captures the
+/// mouse.
+///
+/// When several widgets are under the mouse, only the topmost (see `Ui::widget_under_mouse`) may
+/// capture it, and only if it is not blocked by an open `Modal`. If some other widget was
+/// capturing the mouse, a `WidgetUncapturesMouse` event is emitted for it before the
+/// `WidgetCapturesMouse` event for the topmost widget.
+///
+/// If the left mouse button is down, we assume that the widget that was clicked remains "pinned"
+/// and will continue to capture the mouse until it is released, regardless of the widget under the
+/// mouse.
+///
+/// This is called whenever the mouse moves, the window is resized or the left mouse button is
+/// released, as well as at the beginning of each `Ui::set_widgets` so that capture follows widgets
+/// that appear or disappear beneath a stationary mouse.
+///
+/// Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
+fn track_widget_under_mouse_and_update_capturing(ui: &mut Ui) {
+    ui.global_input.current.widget_under_mouse = ui.widget_under_mouse(&ui.global_input.current);
+
+    // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
+    // under mouse.
+    if ui.global_input.current.mouse.buttons.left().is_up() {
+        let widget_under_mouse = ui.global_input.current.widget_under_mouse;
+
+        // Check to see if we need to uncapture a widget.
+        if let Some(idx) = ui.global_input.current.widget_capturing_mouse {
+            if widget_under_mouse != Some(idx) {
+                let event = event::Ui::WidgetUncapturesMouse(idx).into();
+                ui.global_input.push_event(event);
+                ui.global_input.current.widget_capturing_mouse = None;
+            }
+        }
+
+        // Check to see if there is a new widget capturing the mouse.
+        if ui.global_input.current.widget_capturing_mouse.is_none() {
+            if let Some(idx) = widget_under_mouse {
+                if !ui.is_blocked_by_modal(idx) {
+                    let event = event::Ui::WidgetCapturesMouse(idx).into();
+                    ui.global_input.push_event(event);
+                    ui.global_input.current.widget_capturing_mouse = Some(idx);
+                }
+            }
+        }
+    }
+}
+
+/// Open the given `Modal` if it is not already open, pushing it onto the `Ui`'s stack of modal
+/// captures along with the widget that is currently capturing the keyboard.
+///
+/// Any capture of the mouse held by a widget outside of the `Modal` is released.
+pub fn open_modal(ui: &mut Ui, modal: widget::Id) {
+    if ui.modal_captures.iter().any(|&(id, _)| id == modal) {
+        return;
+    }
+    let maybe_focus = ui.global_input.current.widget_capturing_keyboard;
+    ui.modal_captures.push((modal, maybe_focus));
+    if let Some(idx) = ui.global_input.current.widget_capturing_mouse {
+        if ui.is_blocked_by_modal(idx) {
+            ui.global_input.push_event(event::Ui::WidgetUncapturesMouse(idx).into());
+            ui.global_input.current.widget_capturing_mouse = None;
+        }
+    }
+}
+
+/// Close each `Modal` that was not instantiated during the update that is ending, removing it from
+/// the `Ui`'s stack of modal captures.
+///
+/// When the top-most `Modal` closes while it or one of its descendants is capturing the keyboard
+/// (or nothing is), the keyboard is returned to the widget that was capturing it when the `Modal`
+/// opened, as long as that widget is still instantiated.
+fn close_uninstantiated_modals(ui: &mut Ui) {
+    while let Some(&(modal, maybe_focus)) = ui.modal_captures.last() {
+        if ui.updated_widgets.contains(&modal) {
+            break;
+        }
+        ui.modal_captures.pop();
+        let maybe_capturing = ui.global_input.current.widget_capturing_keyboard;
+        let is_within_modal = maybe_capturing.map_or(true, |idx| {
+            idx == modal || ui.widget_graph.does_recursive_depth_edge_exist(modal, idx)
+        });
+        if !is_within_modal {
+            continue;
+        }
+        match maybe_focus {
+            Some(idx) if ui.updated_widgets.contains(&idx) => ui.focus_widget(idx),
+            _ => if let Some(idx) = maybe_capturing {
+                ui.global_input.push_event(event::Ui::WidgetUncapturesKeyboard(idx).into());
+                ui.global_input.current.widget_capturing_keyboard = None;
+            },
+        }
+    }
+    // A `Modal` beneath the top-most one may also have been closed.
+    let updated_widgets = &ui.updated_widgets;
+    ui.modal_captures.retain(|&(modal, _)| updated_widgets.contains(&modal));
+}
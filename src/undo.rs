@@ -0,0 +1,137 @@
+//! A generic history of states for undoing and redoing edits, e.g. to the text of a `TextEdit`.
+
+/// A history of states that may be stepped backwards via `undo` and forwards again via `redo`.
+///
+/// Before applying some edit, the state prior to the edit is `push`ed onto the stack. Undoing
+/// swaps the current state for the most recently pushed one, while redoing reverses an undo.
+///
+/// Consecutive states pushed via `push_coalesced` (e.g. for each character typed in a row) are
+/// merged into a single undo step until the run is broken by `break_coalescing`, a regular
+/// `push`, an `undo` or a `redo`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoStack<T> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    is_coalescing: bool,
+}
+
+
+impl<T> UndoStack<T> {
+
+    /// An empty history.
+    pub fn new() -> Self {
+        UndoStack {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            is_coalescing: false,
+        }
+    }
+
+    /// Record the given state prior to some edit as a new undo step.
+    ///
+    /// As the edit diverges from any previously undone edits, the redo history is discarded.
+    pub fn push(&mut self, state: T) {
+        self.redo.clear();
+        self.undo.push(state);
+        self.is_coalescing = false;
+    }
+
+    /// Record the given state prior to some edit, merging the edit with the previous undo step if
+    /// that step was also pushed via `push_coalesced` and the run has not since been broken.
+    ///
+    /// When merged, the given state is discarded in favour of the state prior to the first edit
+    /// of the run, so that undoing reverts the whole run at once.
+    pub fn push_coalesced(&mut self, state: T) {
+        if self.is_coalescing && !self.undo.is_empty() {
+            self.redo.clear();
+        } else {
+            self.push(state);
+            self.is_coalescing = true;
+        }
+    }
+
+    /// Ensure that the next state pushed via `push_coalesced` begins a new undo step.
+    pub fn break_coalescing(&mut self) {
+        self.is_coalescing = false;
+    }
+
+    /// Step backwards, swapping the given `current` state for the state prior to the most recent
+    /// edit.
+    ///
+    /// Returns `None` if there is nothing to undo, in which case `current` is discarded.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        self.is_coalescing = false;
+        self.undo.pop().map(|previous| {
+            self.redo.push(current);
+            previous
+        })
+    }
+
+    /// Step forwards, swapping the given `current` state for the state prior to the most recent
+    /// undo.
+    ///
+    /// Returns `None` if there is nothing to redo, in which case `current` is discarded.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        self.is_coalescing = false;
+        self.redo.pop().map(|next| {
+            self.undo.push(current);
+            next
+        })
+    }
+
+    /// Whether or not there are any edits that may be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether or not there are any undone edits that may be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Discard the entire history.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.is_coalescing = false;
+    }
+
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        UndoStack::new()
+    }
+}
+
+
+#[test]
+fn coalesced_pushes_should_be_undone_in_a_single_step() {
+    let mut stack = UndoStack::new();
+    stack.push_coalesced("");
+    stack.push_coalesced("a");
+    stack.push_coalesced("ab");
+    stack.break_coalescing();
+    stack.push_coalesced("abc");
+    assert_eq!(Some("abc"), stack.undo("abcd"));
+    assert_eq!(Some(""), stack.undo("abc"));
+    assert_eq!(None, stack.undo(""));
+    assert_eq!(Some("abc"), stack.redo(""));
+    assert_eq!(Some("abcd"), stack.redo("abc"));
+    assert!(!stack.can_redo());
+}
+
+#[test]
+fn pushing_after_undo_should_truncate_the_redo_history() {
+    let mut stack = UndoStack::new();
+    stack.push(1);
+    stack.push(2);
+    assert_eq!(Some(2), stack.undo(3));
+    assert_eq!(Some(1), stack.undo(2));
+    assert!(stack.can_redo());
+    stack.push(1);
+    assert!(!stack.can_redo());
+    assert_eq!(None, stack.redo(10));
+    assert_eq!(Some(1), stack.undo(10));
+    assert!(!stack.can_undo());
+}
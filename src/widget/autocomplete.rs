@@ -0,0 +1,356 @@
+//! A one-line field of text that suggests candidates matching the text entered so far.
+
+use {
+    Align,
+    Color,
+    Colorable,
+    FontSize,
+    Borderable,
+    Labelable,
+    MouseCursor,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use input;
+use std;
+use text;
+use widget;
+
+/// A one-line field of text that, while focused, displays a drop down list of the candidates
+/// beginning with the text entered so far.
+///
+/// The suggestions may be highlighted via the `Up` and `Down` arrow keys. Pressing `Enter` or
+/// `Return` completes the highlighted suggestion, while `Tab` completes the highlighted suggestion
+/// or the first if none is highlighted. Suggestions may also be completed by clicking them.
+///
+/// The drop down list is closed while the text matches none of the candidates.
+pub struct Autocomplete<'a, S: 'a> {
+    common: widget::CommonBuilder,
+    text: &'a str,
+    candidates: &'a [S],
+    max_suggestions: usize,
+    style: Style,
+}
+
+widget_style!{
+    /// Unique graphical styling for the Autocomplete.
+    style Style {
+        /// The length of the gap between the bounding rectangle's border and the edge of the text.
+        - text_padding: Scalar { 5.0 }
+        /// Color of the rectangle behind the text and of each suggestion.
+        - color: Color { theme.shape_color }
+        /// The width of the bounding `BorderedRectangle` border.
+        - border: Scalar { theme.border_width }
+        /// The color of the `BorderedRecangle`'s border.
+        - border_color: Color { theme.border_color }
+        /// The color of the text.
+        - text_color: Color { theme.label_color }
+        /// The font size for the text.
+        - font_size: FontSize { theme.font_size_medium }
+        /// The horizontal alignment of the text.
+        - x_align: Align { Align::Start }
+        /// The font used for the `Text`.
+        - font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        text_edit,
+        rectangle,
+        list,
+        suggestions[],
+    }
+}
+
+/// The `State` of the `Autocomplete` widget that will be cached within the `Ui`.
+pub struct State {
+    ids: Ids,
+    /// The index of the highlighted suggestion within the drop down list, if any.
+    highlighted: Option<usize>,
+}
+
+/// The events produced by the `Autocomplete` widget.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The text was edited.
+    Update(String),
+    /// The candidate at the given index was chosen to complete the text.
+    ///
+    /// The user should replace the text with the candidate.
+    Complete(usize),
+}
+
+/// The maximum number of suggestions displayed by default.
+pub const DEFAULT_MAX_SUGGESTIONS: usize = 8;
+
+
+impl<'a, S> Autocomplete<'a, S>
+    where S: AsRef<str>,
+{
+
+    /// Construct an Autocomplete widget suggesting completions of the given text from the given
+    /// candidates.
+    pub fn new(text: &'a str, candidates: &'a [S]) -> Self {
+        Autocomplete {
+            common: widget::CommonBuilder::new(),
+            text: text,
+            candidates: candidates,
+            max_suggestions: DEFAULT_MAX_SUGGESTIONS,
+            style: Style::new(),
+        }
+    }
+
+    /// The maximum number of suggestions displayed within the drop down list at once.
+    ///
+    /// By default this is `DEFAULT_MAX_SUGGESTIONS`.
+    pub fn max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self
+    }
+
+    /// Specify the font used for displaying the text and suggestions.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub x_align_text { style.x_align = Some(Align) }
+        pub pad_text { style.text_padding = Some(Scalar) }
+    }
+
+}
+
+impl<'a, S> Widget for Autocomplete<'a, S>
+    where S: AsRef<str>,
+{
+    type State = State;
+    type Style = Style;
+    type Event = Vec<Event>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            highlighted: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn cursor_hint(&self) -> Option<MouseCursor> {
+        self.common.maybe_cursor_hint.or(Some(MouseCursor::Text))
+    }
+
+    /// Update the state of the Autocomplete.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Autocomplete { text, candidates, max_suggestions, .. } = self;
+
+        let font_size = style.font_size(ui.theme());
+        let border = style.border(ui.theme());
+        let text_padding = style.text_padding(ui.theme());
+        let x_align = style.x_align(ui.theme());
+
+        let text_rect = {
+            let w = rect.x.pad(border + text_padding).len();
+            let h = font_size as Scalar + 1.0;
+            let x = Range::new(0.0, w).align_middle_of(rect.x);
+            let y = Range::new(0.0, h).align_middle_of(rect.y);
+            Rect { x: x, y: y }
+        };
+
+        let color = style.color(ui.theme());
+        let border_color = style.border_color(ui.theme());
+        widget::BorderedRectangle::new(rect.dim())
+            .xy(rect.xy())
+            .graphics_for(id)
+            .parent(id)
+            .border(border)
+            .color(color)
+            .border_color(border_color)
+            .set(state.ids.rectangle, ui);
+
+        let mut events = Vec::new();
+
+        let text_color = style.text_color(ui.theme());
+        let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
+        let maybe_new_text = widget::TextEdit::new(text)
+            .and_then(font_id, widget::TextEdit::font_id)
+            .wh(text_rect.dim())
+            .xy(text_rect.xy())
+            .font_size(font_size)
+            .color(text_color)
+            .x_align_text(x_align)
+            .parent(id)
+            .set(state.ids.text_edit, ui);
+
+        // Editing the text resets the highlighted suggestion.
+        let mut highlighted = state.highlighted;
+        let text = match maybe_new_text {
+            Some(new_text) => {
+                highlighted = None;
+                events.push(Event::Update(new_text.clone()));
+                new_text
+            },
+            None => text.to_string(),
+        };
+
+        // The suggestions are only shown while the field, or one of the suggestions themselves,
+        // is capturing the keyboard.
+        let is_focused = match ui.global_input().current.widget_capturing_keyboard {
+            Some(capturing) => capturing == state.ids.text_edit
+                || state.ids.suggestions.iter().any(|&suggestion| suggestion == capturing),
+            None => false,
+        };
+
+        let suggestions: Vec<usize> = if is_focused {
+            candidates.iter()
+                .enumerate()
+                .filter(|&(_, candidate)| matches(candidate.as_ref(), &text))
+                .map(|(i, _)| i)
+                .take(max_suggestions)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // The previously highlighted suggestion may no longer exist.
+        highlighted = match highlighted {
+            Some(_) if suggestions.is_empty() => None,
+            Some(i) => Some(std::cmp::min(i, suggestions.len() - 1)),
+            None => None,
+        };
+
+        // Navigate and complete the suggestions via the keyboard.
+        let mut completed = None;
+        if !suggestions.is_empty() {
+            for press in ui.widget_input(state.ids.text_edit).presses().key() {
+                match press.key {
+                    input::Key::Down => highlighted = match highlighted {
+                        None => Some(0),
+                        Some(i) => Some(std::cmp::min(i + 1, suggestions.len() - 1)),
+                    },
+                    input::Key::Up => highlighted = match highlighted {
+                        None | Some(0) => None,
+                        Some(i) => Some(i - 1),
+                    },
+                    input::Key::Tab =>
+                        completed = Some(suggestions[highlighted.unwrap_or(0)]),
+                    input::Key::Return | input::Key::NumPadEnter =>
+                        if let Some(i) = highlighted {
+                            completed = Some(suggestions[i]);
+                        },
+                    _ => (),
+                }
+            }
+        }
+
+        // Display the drop down list of suggestions just below the field.
+        if completed.is_none() && !suggestions.is_empty() {
+            let num_suggestions = suggestions.len();
+            if state.ids.suggestions.len() < num_suggestions {
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.suggestions.resize(num_suggestions, id_gen));
+            }
+
+            let item_h = rect.h();
+            let list_h = item_h * num_suggestions as Scalar;
+            let list_y = rect.bottom() - list_h / 2.0;
+            widget::BorderedRectangle::new([rect.w(), list_h])
+                .x_y(rect.x(), list_y)
+                .parent(id)
+                .floating(true)
+                .border(border)
+                .color(color)
+                .border_color(border_color)
+                .set(state.ids.list, ui);
+
+            let label_font_size = style.font_size(ui.theme());
+            for (i, &candidate_idx) in suggestions.iter().enumerate() {
+                let suggestion_id = state.ids.suggestions[i];
+                let item_y = rect.bottom() - item_h * (i as Scalar + 0.5);
+                let item_color = if highlighted == Some(i) { color.highlighted() } else { color };
+                let mut button = widget::Button::new()
+                    .label(candidates[candidate_idx].as_ref())
+                    .label_color(text_color)
+                    .label_font_size(label_font_size)
+                    .color(item_color)
+                    .border(border)
+                    .border_color(border_color)
+                    .x_y(rect.x(), item_y)
+                    .w_h(rect.w(), item_h)
+                    .parent(state.ids.list);
+                button.style.label_x_align = Some(x_align);
+                button.style.label_font_id = Some(font_id);
+                if button.set(suggestion_id, ui).was_clicked() {
+                    completed = Some(candidate_idx);
+                    // Return the keyboard to the field so that typing may continue.
+                    ui.focus_widget(state.ids.text_edit);
+                }
+            }
+        }
+
+        if let Some(candidate_idx) = completed {
+            highlighted = None;
+            events.push(Event::Complete(candidate_idx));
+        }
+
+        if state.highlighted != highlighted {
+            state.update(|state| state.highlighted = highlighted);
+        }
+
+        events
+    }
+
+}
+
+impl<'a, S> Borderable for Autocomplete<'a, S> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
+
+impl<'a, S> Colorable for Autocomplete<'a, S> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Whether or not the given candidate should be suggested as a completion of the given text.
+///
+/// This is the case when the candidate begins with the non-empty text, ignoring case, but is not
+/// already equal to it.
+pub fn matches(candidate: &str, text: &str) -> bool {
+    !text.is_empty()
+        && candidate != text
+        && candidate.to_lowercase().starts_with(&text.to_lowercase())
+}
+
+
+#[test]
+fn matches_should_suggest_longer_candidates_beginning_with_the_text_ignoring_case() {
+    assert!(matches("apple", "ap"));
+    assert!(matches("Apple", "aP"));
+    assert!(matches("apple", "APPLE"));
+    assert!(!matches("apple", "apple"));
+    assert!(!matches("apple", "pp"));
+    assert!(!matches("apple", "apples"));
+    assert!(!matches("apple", ""));
+}
@@ -0,0 +1,315 @@
+//! A clickable path of segments leading from the root of some hierarchy to the current location.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Labelable,
+    Positionable,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use event;
+use std;
+use text;
+use widget;
+
+
+/// Displays a horizontal path of segments separated by `>`, where clicking a segment produces
+/// its index so that the user may navigate to it.
+///
+/// If the segments do not all fit within the width of the widget, the middle segments are
+/// collapsed into an ellipsis (`...`) following the first segment, leaving as many of the final
+/// segments as fit. Clicking the ellipsis opens a menu beneath it listing the collapsed segments,
+/// which may also be clicked. The menu is closed by clicking the ellipsis again or by pressing a
+/// mouse button outside of the `Breadcrumbs` and its menu.
+pub struct Breadcrumbs<'a, S: 'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    segments: &'a [S],
+}
+
+/// The label of the segment into which the middle segments are collapsed.
+pub const ELLIPSIS: &'static str = "...";
+
+/// The text drawn between each pair of adjacent segments.
+pub const SEPARATOR: &'static str = ">";
+
+widget_style! {
+    /// Unique styling for the `Breadcrumbs`.
+    style Style {
+        /// The width of each segment.
+        - segment_w: Scalar { 80.0 }
+        /// The width of the gap between each segment, within which the separator is drawn.
+        - separator_w: Scalar { 20.0 }
+        /// The color of each segment and of the menu of collapsed segments.
+        - color: Color { theme.shape_color }
+        /// The color of the segment labels and separators.
+        - label_color: Color { theme.label_color }
+        /// The font size of the segment labels and separators.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display the segment labels and separators.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        segments[],
+        separators[],
+        ellipsis,
+        menu,
+        menu_items[],
+    }
+}
+
+/// The state of the `Breadcrumbs`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// Whether or not the menu of collapsed segments is open.
+    menu_open: bool,
+}
+
+/// A single slot along the displayed path.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Slot {
+    /// The segment at the given index.
+    Segment(usize),
+    /// The ellipsis standing in for the collapsed segments.
+    Ellipsis,
+}
+
+
+impl<'a, S> Breadcrumbs<'a, S> {
+
+    /// Begin building `Breadcrumbs` displaying the given segments, from the root to the current
+    /// location.
+    pub fn new(segments: &'a [S]) -> Self {
+        Breadcrumbs {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            segments: segments,
+        }
+    }
+
+    /// Specify the font used for displaying the segment labels and separators.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub segment_w { style.segment_w = Some(Scalar) }
+        pub separator_w { style.separator_w = Some(Scalar) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a, S> Widget for Breadcrumbs<'a, S>
+    where S: AsRef<str>,
+{
+    type State = State;
+    type Style = Style;
+    /// The index of the segment that was clicked, if any.
+    type Event = Option<usize>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            menu_open: false,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Breadcrumbs.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Breadcrumbs { segments, .. } = self;
+        let num_segments = segments.len();
+
+        let segment_w = style.segment_w(&ui.theme);
+        let separator_w = style.separator_w(&ui.theme);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+
+        let collapsed = collapsed_segments(num_segments, segment_w, separator_w, rect.w());
+        let num_collapsed = collapsed.as_ref().map_or(0, |range| range.len());
+        if state.ids.segments.len() < num_segments || state.ids.menu_items.len() < num_collapsed {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.segments.resize(num_segments, id_gen);
+                state.ids.separators.resize(num_segments, id_gen);
+                state.ids.menu_items.resize(num_collapsed, id_gen);
+            });
+        }
+
+        // The slots along the path from left to right.
+        let slots: Vec<Slot> = match collapsed {
+            Some(ref range) => std::iter::once(Slot::Segment(0))
+                .chain(std::iter::once(Slot::Ellipsis))
+                .chain((range.end..num_segments).map(Slot::Segment))
+                .collect(),
+            None => (0..num_segments).map(Slot::Segment).collect(),
+        };
+
+        let mut clicked = None;
+        let mut menu_open = state.menu_open && collapsed.is_some();
+        let mut ellipsis_rect = None;
+        for (i, &slot) in slots.iter().enumerate() {
+            let left = rect.left() + i as Scalar * (segment_w + separator_w);
+
+            // Separate each slot from the previous one.
+            if i > 0 {
+                widget::Text::new(SEPARATOR)
+                    .and_then(font_id, widget::Text::font_id)
+                    .x_y(left - separator_w / 2.0, rect.y())
+                    .color(label_color)
+                    .font_size(font_size)
+                    .no_line_wrap()
+                    .align_text_middle()
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(state.ids.separators[i - 1], ui);
+            }
+
+            let (label, slot_id) = match slot {
+                Slot::Segment(idx) => (segments[idx].as_ref(), state.ids.segments[idx]),
+                Slot::Ellipsis => (ELLIPSIS, state.ids.ellipsis),
+            };
+            let is_highlighted = slot == Slot::Ellipsis && menu_open;
+            let mut button = widget::Button::new()
+                .label(label)
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .color(if is_highlighted { color.highlighted() } else { color })
+                .w_h(segment_w, rect.h())
+                .x_y(left + segment_w / 2.0, rect.y())
+                .parent(id);
+            button.style.label_font_id = Some(font_id);
+            let was_clicked = button.set(slot_id, ui).was_clicked();
+            match slot {
+                Slot::Segment(idx) => if was_clicked {
+                    clicked = Some(idx);
+                    menu_open = false;
+                },
+                Slot::Ellipsis => {
+                    if was_clicked {
+                        menu_open = !menu_open;
+                    }
+                    ellipsis_rect = ui.rect_of(slot_id);
+                },
+            }
+        }
+
+        // List the collapsed segments within a menu beneath the ellipsis.
+        let mut menu_rect = None;
+        if let (true, Some(range), Some(ellipsis)) = (menu_open, collapsed, ellipsis_rect) {
+            let item_h = rect.h();
+            let top = ellipsis.bottom();
+            let bottom = top - range.len() as Scalar * item_h;
+            let menu = Rect::from_corners([ellipsis.left(), bottom], [ellipsis.right(), top]);
+            widget::Rectangle::fill(menu.dim())
+                .xy(menu.xy())
+                .color(color)
+                .floating(true)
+                .parent(id)
+                .set(state.ids.menu, ui);
+
+            for (i, idx) in range.enumerate() {
+                let mut button = widget::Button::new()
+                    .label(segments[idx].as_ref())
+                    .label_color(label_color)
+                    .label_font_size(font_size)
+                    .color(color)
+                    .w_h(menu.w(), item_h)
+                    .x_y(menu.x(), top - (i as Scalar + 0.5) * item_h)
+                    .parent(state.ids.menu);
+                button.style.label_font_id = Some(font_id);
+                if button.set(state.ids.menu_items[i], ui).was_clicked() {
+                    clicked = Some(idx);
+                    menu_open = false;
+                }
+            }
+            menu_rect = Some(menu);
+        }
+
+        // Close the menu if a mouse button is pressed outside of the path and the menu.
+        let was_pressed_outside = state.menu_open
+            && ui.global_input().events().ui().any(|e| match *e {
+                event::Ui::Press(_, event::Press { button: event::Button::Mouse(_, xy), .. }) =>
+                    !rect.is_over(xy) && !menu_rect.map_or(false, |menu| menu.is_over(xy)),
+                _ => false,
+            });
+        if was_pressed_outside {
+            menu_open = false;
+        }
+
+        if state.menu_open != menu_open {
+            state.update(|state| state.menu_open = menu_open);
+        }
+
+        clicked
+    }
+
+}
+
+
+impl<'a, S> Colorable for Breadcrumbs<'a, S> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// The range of segments that must be collapsed into the ellipsis so that the path fits within
+/// the given available width, or `None` if every segment fits.
+///
+/// The first segment and the final segment are always displayed, along with as many of the
+/// segments preceding the final segment as fit. Paths of fewer than three segments are never
+/// collapsed.
+pub fn collapsed_segments(num_segments: usize,
+                          segment_w: Scalar,
+                          separator_w: Scalar,
+                          available_w: Scalar) -> Option<std::ops::Range<usize>>
+{
+    let slot_w = segment_w + separator_w;
+    if num_segments < 3 || slot_w <= 0.0 {
+        return None;
+    }
+    let max_slots = ((available_w + separator_w) / slot_w).floor().max(0.0) as usize;
+    if num_segments <= max_slots {
+        return None;
+    }
+    // One slot is occupied by the first segment and another by the ellipsis.
+    let num_trailing = std::cmp::max(max_slots, 3) - 2;
+    Some(1..num_segments - num_trailing)
+}
+
+
+#[test]
+fn collapsed_segments_should_keep_the_first_and_as_many_final_segments_as_fit() {
+    // Four slots of 80 separated by 20 fit within 380.
+    assert_eq!(None, collapsed_segments(4, 80.0, 20.0, 380.0));
+    assert_eq!(Some(1..4), collapsed_segments(6, 80.0, 20.0, 380.0));
+    assert_eq!(Some(1..5), collapsed_segments(6, 80.0, 20.0, 379.0));
+    // The final segment is always displayed, even if it does not fit.
+    assert_eq!(Some(1..5), collapsed_segments(6, 80.0, 20.0, 0.0));
+    assert_eq!(None, collapsed_segments(2, 80.0, 20.0, 0.0));
+    assert_eq!(None, collapsed_segments(0, 80.0, 20.0, 0.0));
+}
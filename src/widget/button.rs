@@ -245,9 +245,16 @@ impl<'a, S> Widget for Button<'a, S>
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
-        let Button { show, maybe_label, .. } = self;
+        let Button { show, maybe_label, enabled, .. } = self;
 
-        let (color, times_clicked) = {
+        // A disabled button ignores all input and relinquishes any capture that it held.
+        if !enabled {
+            ui.release_captures(id);
+        }
+
+        let (color, times_clicked) = if !enabled {
+            (ui.theme.disabled_color, 0)
+        } else {
             let input = ui.widget_input(id);
             let color = style.color(ui.theme());
             let color = input.mouse().map_or(color, |mouse| {
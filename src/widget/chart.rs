@@ -0,0 +1,286 @@
+//! A widget for plotting a series of data as a line chart with axis ticks.
+
+use {Color, Colorable, Point, Positionable, Range, Rect, Scalar, Sizeable, Widget};
+use widget;
+
+
+/// A widget that plots a series of data as a line within the given *x* and *y* ranges, along with
+/// an axis and evenly spaced ticks along its left and bottom edges.
+///
+/// The series may be given either as a function *x -> y*, which is sampled once per pixel, or as
+/// a slice of points.
+///
+/// Any point whose *x* or *y* is `NaN` or infinite is skipped, breaking the line at that point
+/// rather than connecting its neighbours. Each unbroken run of points is drawn using conrod's
+/// `PointPath` primitive widget.
+pub struct Chart<'a, F> {
+    common: widget::CommonBuilder,
+    style: Style,
+    x: Range,
+    y: Range,
+    series: Series<'a, F>,
+}
+
+/// The data plotted by a `Chart`.
+pub enum Series<'a, F> {
+    /// A function *x -> y*, sampled once per pixel.
+    Function(F),
+    /// A series of points in data coordinates, joined in order.
+    Points(&'a [Point]),
+}
+
+widget_style! {
+    /// Unique styling parameters for the `Chart` widget.
+    style Style {
+        /// The thickness of the plotted line.
+        - thickness: Scalar { 1.0 }
+        /// The color of the plotted line.
+        - color: Color { theme.shape_color }
+        /// The color of the axes and their ticks.
+        - axis_color: Color { theme.border_color }
+        /// The thickness of the axes and their ticks.
+        - axis_thickness: Scalar { 1.0 }
+        /// The length of each tick, measured outwards from its axis.
+        - tick_length: Scalar { 5.0 }
+        /// The number of ticks along the *x* axis, including those at either end.
+        - x_ticks: usize { 5 }
+        /// The number of ticks along the *y* axis, including those at either end.
+        - y_ticks: usize { 5 }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        x_axis,
+        y_axis,
+        lines[],
+        x_ticks[],
+        y_ticks[],
+    }
+}
+
+/// Unique state stored between updates for the `Chart` widget.
+pub struct State {
+    ids: Ids,
+}
+
+
+impl<'a> Chart<'a, fn(Scalar) -> Scalar> {
+
+    /// Begin building a `Chart` that plots the given series of points.
+    pub fn points(min_x: Scalar, max_x: Scalar, min_y: Scalar, max_y: Scalar, points: &'a [Point])
+        -> Self
+    {
+        Chart::new(min_x, max_x, min_y, max_y, Series::Points(points))
+    }
+
+}
+
+impl<'a, F> Chart<'a, F> {
+
+    /// Begin building a `Chart` that plots the given function *x -> y*.
+    pub fn function(min_x: Scalar, max_x: Scalar, min_y: Scalar, max_y: Scalar, f: F) -> Self {
+        Chart::new(min_x, max_x, min_y, max_y, Series::Function(f))
+    }
+
+    fn new(min_x: Scalar, max_x: Scalar, min_y: Scalar, max_y: Scalar, series: Series<'a, F>)
+        -> Self
+    {
+        Chart {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            x: Range::new(min_x, max_x),
+            y: Range::new(min_y, max_y),
+            series: series,
+        }
+    }
+
+    builder_methods!{
+        pub thickness { style.thickness = Some(Scalar) }
+        pub axis_color { style.axis_color = Some(Color) }
+        pub axis_thickness { style.axis_thickness = Some(Scalar) }
+        pub tick_length { style.tick_length = Some(Scalar) }
+        pub x_ticks { style.x_ticks = Some(usize) }
+        pub y_ticks { style.y_ticks = Some(usize) }
+    }
+
+}
+
+
+impl<'a, F> Widget for Chart<'a, F>
+    where F: FnMut(Scalar) -> Scalar,
+{
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Chart.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
+        let Chart { x, y, series, .. } = self;
+
+        // Map the series to screen coordinates, broken into runs of finite points.
+        let runs = match series {
+            Series::Function(mut f) => {
+                let points = (0..rect.w() as usize).map(|i| {
+                    let screen_x = rect.x.start + i as Scalar;
+                    let data_x = rect.x.map_value_to(screen_x, &x);
+                    [data_x, f(data_x)]
+                });
+                finite_runs(points.map(|p| map_point(p, x, y, rect)))
+            },
+            Series::Points(points) =>
+                finite_runs(points.iter().map(|&p| map_point(p, x, y, rect))),
+        };
+
+        let x_ticks = ticks(rect.left(), rect.right(), style.x_ticks(&ui.theme));
+        let y_ticks = ticks(rect.bottom(), rect.top(), style.y_ticks(&ui.theme));
+
+        // Ensure there is a `widget::Id` for every run and tick.
+        if state.ids.lines.len() < runs.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.lines.resize(runs.len(), id_gen));
+        }
+        if state.ids.x_ticks.len() < x_ticks.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.x_ticks.resize(x_ticks.len(), id_gen));
+        }
+        if state.ids.y_ticks.len() < y_ticks.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.y_ticks.resize(y_ticks.len(), id_gen));
+        }
+
+        let color = style.color(&ui.theme);
+        let thickness = style.thickness(&ui.theme);
+        for (i, run) in runs.into_iter().enumerate() {
+            widget::PointPath::new(run)
+                .wh(rect.dim())
+                .xy(rect.xy())
+                .color(color)
+                .thickness(thickness)
+                .parent(id)
+                .graphics_for(id)
+                .set(state.ids.lines[i], ui);
+        }
+
+        // Draw the axes along the left and bottom edges with their ticks pointing outwards.
+        let axis_color = style.axis_color(&ui.theme);
+        let axis_thickness = style.axis_thickness(&ui.theme);
+        let tick_length = style.tick_length(&ui.theme);
+        let (l, r, b, t) = rect.l_r_b_t();
+        let axis_line = |start: Point, end: Point| {
+            widget::Line::abs(start, end)
+                .color(axis_color)
+                .thickness(axis_thickness)
+                .parent(id)
+                .graphics_for(id)
+        };
+        axis_line([l, b], [r, b]).set(state.ids.x_axis, ui);
+        axis_line([l, b], [l, t]).set(state.ids.y_axis, ui);
+        for (i, &tick_x) in x_ticks.iter().enumerate() {
+            axis_line([tick_x, b], [tick_x, b - tick_length]).set(state.ids.x_ticks[i], ui);
+        }
+        for (i, &tick_y) in y_ticks.iter().enumerate() {
+            axis_line([l, tick_y], [l - tick_length, tick_y]).set(state.ids.y_ticks[i], ui);
+        }
+    }
+
+}
+
+impl<'a, F> Colorable for Chart<'a, F> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Map the given point in data coordinates, lying within the given `x` and `y` ranges, to the
+/// screen coordinates of the given `rect`.
+///
+/// The start of each range maps to the left and bottom edges of the `rect` respectively.
+pub fn map_point(point: Point, x: Range, y: Range, rect: Rect) -> Point {
+    [x.map_value_to(point[0], &rect.x), y.map_value_to(point[1], &rect.y)]
+}
+
+/// Split the given points into runs of consecutive points whose coordinates are finite, skipping
+/// every point with a `NaN` or infinite coordinate.
+///
+/// Runs containing a single point are dropped, as they cannot form a line.
+pub fn finite_runs<I>(points: I) -> Vec<Vec<Point>>
+    where I: IntoIterator<Item=Point>,
+{
+    let mut runs = Vec::new();
+    let mut run = Vec::new();
+    for point in points {
+        if point[0].is_finite() && point[1].is_finite() {
+            run.push(point);
+        } else if !run.is_empty() {
+            runs.push(::std::mem::replace(&mut run, Vec::new()));
+        }
+    }
+    runs.push(run);
+    runs.retain(|run| run.len() > 1);
+    runs
+}
+
+/// The positions of `count` evenly spaced ticks from `start` to `end` inclusive.
+///
+/// A single tick is placed at the `start`.
+pub fn ticks(start: Scalar, end: Scalar, count: usize) -> Vec<Scalar> {
+    match count {
+        0 => vec![],
+        1 => vec![start],
+        _ => {
+            let step = (end - start) / (count - 1) as Scalar;
+            (0..count).map(|i| start + step * i as Scalar).collect()
+        },
+    }
+}
+
+
+#[test]
+fn map_point_should_map_data_coordinates_to_the_rect() {
+    let rect = Rect::from_corners([-50.0, -25.0], [50.0, 25.0]);
+    let (x, y) = (Range::new(0.0, 10.0), Range::new(-1.0, 1.0));
+    assert_eq!([-50.0, -25.0], map_point([0.0, -1.0], x, y, rect));
+    assert_eq!([50.0, 25.0], map_point([10.0, 1.0], x, y, rect));
+    assert_eq!([0.0, 0.0], map_point([5.0, 0.0], x, y, rect));
+    assert_eq!([-40.0, 12.5], map_point([1.0, 0.5], x, y, rect));
+    // Points outside of the ranges map outside of the rect.
+    assert_eq!([60.0, -50.0], map_point([11.0, -2.0], x, y, rect));
+}
+
+#[test]
+fn finite_runs_should_skip_non_finite_points_without_joining_their_neighbours() {
+    use std::f64::{INFINITY, NAN};
+    let points = vec![
+        [0.0, 0.0], [1.0, 1.0], [2.0, NAN], [3.0, 3.0], [4.0, 4.0], [5.0, 5.0],
+        [INFINITY, 6.0], [7.0, 7.0], [8.0, NAN],
+    ];
+    let runs = finite_runs(points);
+    assert_eq!(vec![vec![[0.0, 0.0], [1.0, 1.0]], vec![[3.0, 3.0], [4.0, 4.0], [5.0, 5.0]]], runs);
+    assert!(finite_runs(vec![[NAN, NAN]]).is_empty());
+}
+
+#[test]
+fn ticks_should_be_evenly_spaced_including_both_ends() {
+    assert_eq!(vec![-50.0, -25.0, 0.0, 25.0, 50.0], ticks(-50.0, 50.0, 5));
+    assert_eq!(vec![10.0], ticks(10.0, 20.0, 1));
+    assert!(ticks(10.0, 20.0, 0).is_empty());
+}
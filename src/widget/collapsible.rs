@@ -0,0 +1,272 @@
+//! A container with a header that may be clicked to show or hide its body.
+
+use {
+    Color,
+    Colorable,
+    Dimension,
+    FontSize,
+    Labelable,
+    Positionable,
+    Scalar,
+    Sizeable,
+    Ui,
+    Widget,
+};
+use anim;
+use std;
+use ui::{self, UiCell};
+use widget;
+
+
+/// A container made up of a header and a body, where clicking the header toggles whether or not
+/// the body is visible.
+///
+/// Like the `Toggle`, the `Collapsible` does not store whether or not it is open. Instead, the
+/// user passes the current state and toggles it upon receiving an `Event` with `toggled` set.
+///
+/// The body is revealed and hidden by animating its height over `ANIMATION_DURATION`, cropping its
+/// children as it does so. The height of the `Collapsible` is the height of its header plus the
+/// visible height of its body, so that widgets positioned after it follow the body as it opens
+/// and closes.
+///
+/// As the height of a `Collapsible` is known before its body is instantiated, nested
+/// `Collapsible`s may be sized by passing the sum of the `height`s of the body's children to the
+/// parent `Collapsible`.
+pub struct Collapsible<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    label: &'a str,
+    is_open: bool,
+    body_h: Scalar,
+    /// How far the body is revealed at `Ui::now`, if the `Collapsible` was updated previously.
+    maybe_openness: Option<Scalar>,
+}
+
+/// The duration in seconds over which the body is revealed or hidden.
+pub const ANIMATION_DURATION: Scalar = 0.2;
+
+widget_style! {
+    /// Unique styling for the `Collapsible`.
+    style Style {
+        /// The height of the header.
+        - header_h: Scalar { 30.0 }
+        /// The color of the header.
+        - header_color: Color { theme.shape_color }
+        /// The color of the body.
+        - color: Color { theme.background_color }
+        /// The color of the header's label.
+        - label_color: Color { theme.label_color }
+        /// The font size of the header's label.
+        - label_font_size: FontSize { theme.font_size_medium }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        header,
+        body,
+    }
+}
+
+/// The state of the `Collapsible`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// How far the body is revealed, from `0.0` (hidden) to `1.0` (fully visible).
+    openness: Scalar,
+    /// The moment at which the `openness` was last stepped, while the body is animating.
+    last_update: Option<std::time::Instant>,
+}
+
+/// The event produced by the `Collapsible` each update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Event {
+    /// Whether the header was clicked, in which case the user should toggle the `Collapsible`.
+    pub toggled: bool,
+    /// The id of the body, to be used as the parent of the body's children.
+    ///
+    /// This is `None` while the body is entirely hidden.
+    pub body: Option<widget::Id>,
+}
+
+
+impl<'a> Collapsible<'a> {
+
+    /// Begin building a `Collapsible` with the given header label, whose body has the given
+    /// height when open.
+    pub fn new(label: &'a str, is_open: bool, body_h: Scalar) -> Self {
+        Collapsible {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            label: label,
+            is_open: is_open,
+            body_h: body_h,
+            maybe_openness: None,
+        }
+    }
+
+    builder_methods!{
+        pub header_h { style.header_h = Some(Scalar) }
+        pub header_color { style.header_color = Some(Color) }
+    }
+
+}
+
+
+impl<'a> Widget for Collapsible<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            openness: if self.is_open { 1.0 } else { 0.0 },
+            last_update: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// The height of the header plus the height of the body revealed so far.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        let header_h = self.style.header_h(&ui.theme);
+        let openness = self.maybe_openness.unwrap_or(if self.is_open { 1.0 } else { 0.0 });
+        Dimension::Absolute(animated_height(header_h, self.body_h, openness))
+    }
+
+    /// Steps the animation of the body before the `Collapsible` is sized, so that its height
+    /// follows the body as it is revealed or hidden.
+    fn set<'b, 'c>(mut self, id: widget::Id, ui: &'b mut UiCell<'c>) -> Self::Event {
+        self.maybe_openness = ui.widget_graph().widget(id)
+            .and_then(|container| container.unique_widget_state::<Self>())
+            .map(|unique| openness_at(&unique.state, self.is_open, ui.now()).0);
+        widget::set_widget(self, id, ui)
+    }
+
+    /// Update the state of the Collapsible.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Collapsible { label, is_open, body_h, .. } = self;
+
+        // Step the animation towards the target openness.
+        let target = if is_open { 1.0 } else { 0.0 };
+        if state.openness != target {
+            let (openness, last_update) = openness_at(state, is_open, ui.now());
+            state.update(|state| {
+                state.openness = openness;
+                state.last_update = last_update;
+            });
+            // Keep updating for as long as the animation is running.
+            ui::ref_mut_from_ui_cell(ui).request_redraw();
+        }
+        let openness = state.openness;
+
+        let header_h = style.header_h(&ui.theme);
+        let toggled = widget::Button::new()
+            .w_h(rect.w(), header_h)
+            .mid_top_of(id)
+            .color(style.header_color(&ui.theme))
+            .label(label)
+            .label_color(style.label_color(&ui.theme))
+            .label_font_size(style.label_font_size(&ui.theme))
+            .parent(id)
+            .set(state.ids.header, ui)
+            .count() % 2 == 1;
+
+        // The body hangs from the bottom of the header, revealing its children from the top down.
+        let visible_h = body_h * openness;
+        let body = if visible_h > 0.0 {
+            widget::Rectangle::fill([rect.w(), visible_h])
+                .down_from(state.ids.header, 0.0)
+                .align_middle_x_of(state.ids.header)
+                .color(style.color(&ui.theme))
+                .crop_kids()
+                .parent(id)
+                .set(state.ids.body, ui);
+            Some(state.ids.body)
+        } else {
+            None
+        };
+
+        Event { toggled: toggled, body: body }
+    }
+
+}
+
+
+impl<'a> Colorable for Collapsible<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Labelable<'a> for Collapsible<'a> {
+    builder_methods!{
+        label { label = &'a str }
+        label_color { style.label_color = Some(Color) }
+        label_font_size { style.label_font_size = Some(FontSize) }
+    }
+}
+
+
+/// The height of a `Collapsible` with the given header and body heights.
+pub fn height(header_h: Scalar, body_h: Scalar, is_open: bool) -> Scalar {
+    if is_open { header_h + body_h.max(0.0) } else { header_h }
+}
+
+/// The height of a `Collapsible` with the given header and body heights whose body is revealed by
+/// the given `openness`, from `0.0` (hidden) to `1.0` (fully visible).
+pub fn animated_height(header_h: Scalar, body_h: Scalar, openness: Scalar) -> Scalar {
+    header_h + body_h.max(0.0) * openness
+}
+
+/// The openness of the body of a `Collapsible` with the given `state` once stepped towards the
+/// target until `now`, along with the moment from which to step it next (or `None` once the
+/// target is reached).
+fn openness_at(state: &State, is_open: bool, now: std::time::Instant)
+    -> (Scalar, Option<std::time::Instant>)
+{
+    let target = if is_open { 1.0 } else { 0.0 };
+    if state.openness == target {
+        return (target, None);
+    }
+    let elapsed = now.duration_since(state.last_update.unwrap_or(now));
+    let openness = step_openness(state.openness, is_open, anim::secs(elapsed));
+    let last_update = if openness != target { Some(now) } else { None };
+    (openness, last_update)
+}
+
+/// Step the given `openness` of a body towards fully open or fully closed over the given number
+/// of seconds.
+pub fn step_openness(openness: Scalar, is_open: bool, secs: Scalar) -> Scalar {
+    let step = secs / ANIMATION_DURATION;
+    if is_open {
+        (openness + step).min(1.0)
+    } else {
+        (openness - step).max(0.0)
+    }
+}
+
+
+#[test]
+fn height_should_include_the_body_only_when_open() {
+    assert_eq!(30.0, height(30.0, 100.0, false));
+    assert_eq!(130.0, height(30.0, 100.0, true));
+    assert_eq!(30.0, height(30.0, -10.0, true));
+}
+
+#[test]
+fn step_openness_should_animate_towards_the_target_and_stop() {
+    assert_eq!(0.5, step_openness(0.0, true, ANIMATION_DURATION / 2.0));
+    assert_eq!(1.0, step_openness(0.5, true, ANIMATION_DURATION));
+    assert_eq!(0.5, step_openness(1.0, false, ANIMATION_DURATION / 2.0));
+    assert_eq!(0.0, step_openness(0.5, false, ANIMATION_DURATION));
+}
@@ -0,0 +1,290 @@
+//! A widget for choosing a color via its hue, saturation and value.
+
+use {
+    Color,
+    Colorable,
+    Point,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Widget,
+};
+use std::f32::consts::PI;
+use widget;
+
+
+/// A widget for choosing a color in the [HSV](http://en.wikipedia.org/wiki/HSL_and_HSV) format.
+///
+/// The saturation and value are chosen by dragging within a square, where saturation increases
+/// from left to right and value increases from bottom to top. The hue is chosen by dragging along
+/// a vertical strip to the right of the square, from `0.0` at the bottom to `2.0*PI` at the top.
+///
+/// Dragging beyond the edges of the square or strip clamps to the nearest edge. The region in
+/// which the left mouse button was pressed determines what is being dragged.
+///
+/// Like the `XYPad`, the `ColorPicker` does not store the chosen color. Instead, the user passes
+/// the current hue, saturation and value and updates them upon receiving an `Event`.
+pub struct ColorPicker {
+    common: widget::CommonBuilder,
+    style: Style,
+    hue: f32,
+    saturation: f32,
+    value: f32,
+}
+
+/// The number of cells along each axis of the saturation/value square.
+pub const SQUARE_RESOLUTION: usize = 12;
+
+/// The number of segments making up the hue strip.
+pub const STRIP_RESOLUTION: usize = 24;
+
+widget_style! {
+    /// Unique styling for the `ColorPicker`.
+    style Style {
+        /// The width of the hue strip.
+        - strip_w: Scalar { 20.0 }
+        /// The horizontal space between the saturation/value square and the hue strip.
+        - spacing: Scalar { 5.0 }
+        /// The color of the markers indicating the current saturation/value and hue.
+        - marker_color: Color { theme.label_color }
+        /// The size of the marker indicating the current saturation and value.
+        - marker_size: Scalar { 6.0 }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        cells[],
+        segments[],
+        square_marker,
+        strip_marker,
+    }
+}
+
+/// The state of the `ColorPicker`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+/// The color chosen by dragging within the `ColorPicker`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Event {
+    /// The hue in radians within the range `0.0...2.0*PI`.
+    pub hue: f32,
+    /// The saturation within the range `0.0...1.0`.
+    pub saturation: f32,
+    /// The value within the range `0.0...1.0`.
+    pub value: f32,
+    /// The opaque color described by the `hue`, `saturation` and `value`.
+    pub color: Color,
+}
+
+
+impl ColorPicker {
+
+    /// Begin building a `ColorPicker` showing the given hue (in radians), saturation and value.
+    pub fn new(hue: f32, saturation: f32, value: f32) -> Self {
+        ColorPicker {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            hue: hue,
+            saturation: saturation,
+            value: value,
+        }
+    }
+
+    builder_methods!{
+        pub strip_w { style.strip_w = Some(Scalar) }
+        pub spacing { style.spacing = Some(Scalar) }
+        pub marker_color { style.marker_color = Some(Color) }
+        pub marker_size { style.marker_size = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for ColorPicker {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Event>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the ColorPicker.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let ColorPicker { hue, saturation, value, .. } = self;
+        let (square, strip) = layout(rect, style.strip_w(&ui.theme), style.spacing(&ui.theme));
+
+        // Drag whichever region the left mouse button was pressed within.
+        let (mut new_hue, mut new_saturation, mut new_value) = (hue, saturation, value);
+        if let Some(mouse) = ui.widget_input(id).mouse() {
+            if let Some(press_xy) = mouse.buttons.left().xy_if_down() {
+                let mouse_xy = mouse.abs_xy();
+                if strip.is_over(press_xy) {
+                    new_hue = hue_from_point(strip, mouse_xy);
+                } else {
+                    let (s, v) = saturation_value_from_point(square, mouse_xy);
+                    new_saturation = s;
+                    new_value = v;
+                }
+            }
+        }
+
+        let event = if new_hue != hue || new_saturation != saturation || new_value != value {
+            Some(Event {
+                hue: new_hue,
+                saturation: new_saturation,
+                value: new_value,
+                color: Color::from_hsv(new_hue, new_saturation, new_value),
+            })
+        } else {
+            None
+        };
+
+        let num_cells = SQUARE_RESOLUTION * SQUARE_RESOLUTION;
+        if state.ids.cells.len() < num_cells || state.ids.segments.len() < STRIP_RESOLUTION {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.cells.resize(num_cells, id_gen);
+                state.ids.segments.resize(STRIP_RESOLUTION, id_gen);
+            });
+        }
+
+        // The saturation/value square for the current hue, drawn as a grid of cells.
+        let cell_dim = [square.w() / SQUARE_RESOLUTION as Scalar,
+                        square.h() / SQUARE_RESOLUTION as Scalar];
+        for i in 0..num_cells {
+            let col = i % SQUARE_RESOLUTION;
+            let row = i / SQUARE_RESOLUTION;
+            let s = (col as f32 + 0.5) / SQUARE_RESOLUTION as f32;
+            let v = (row as f32 + 0.5) / SQUARE_RESOLUTION as f32;
+            let x = square.left() + (col as Scalar + 0.5) * cell_dim[0];
+            let y = square.bottom() + (row as Scalar + 0.5) * cell_dim[1];
+            widget::Rectangle::fill(cell_dim)
+                .xy([x, y])
+                .color(Color::from_hsv(new_hue, s, v))
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.cells[i], ui);
+        }
+
+        // The hue strip, drawn as a column of fully saturated segments.
+        let segment_dim = [strip.w(), strip.h() / STRIP_RESOLUTION as Scalar];
+        for i in 0..STRIP_RESOLUTION {
+            let h = (i as f32 + 0.5) / STRIP_RESOLUTION as f32 * 2.0 * PI;
+            let y = strip.bottom() + (i as Scalar + 0.5) * segment_dim[1];
+            widget::Rectangle::fill(segment_dim)
+                .xy([strip.x(), y])
+                .color(Color::from_hsv(h, 1.0, 1.0))
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.segments[i], ui);
+        }
+
+        // Markers indicating the current saturation/value and hue.
+        let marker_color = style.marker_color(&ui.theme);
+        let marker_size = style.marker_size(&ui.theme);
+        let marker_xy = [square.left() + square.w() * new_saturation as Scalar,
+                         square.bottom() + square.h() * new_value as Scalar];
+        widget::Rectangle::outline([marker_size, marker_size])
+            .xy(marker_xy)
+            .color(marker_color)
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.square_marker, ui);
+        let strip_marker_y = strip.bottom() + strip.h() * (new_hue / (2.0 * PI)) as Scalar;
+        widget::Rectangle::outline([strip.w(), 2.0])
+            .xy([strip.x(), strip_marker_y])
+            .color(marker_color)
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.strip_marker, ui);
+
+        event
+    }
+
+}
+
+
+/// Split the given `rect` into the saturation/value square and the hue strip to its right.
+///
+/// The square takes up all of the width not occupied by the strip and the spacing.
+pub fn layout(rect: Rect, strip_w: Scalar, spacing: Scalar) -> (Rect, Rect) {
+    let strip_left = rect.right() - strip_w;
+    let square = Rect { x: Range::new(rect.left(), strip_left - spacing), y: rect.y };
+    let strip = Rect { x: Range::new(strip_left, rect.right()), y: rect.y };
+    (square, strip)
+}
+
+/// The saturation and value at the given point within the saturation/value `square`.
+///
+/// Points beyond the edges of the `square` are clamped to the nearest edge.
+pub fn saturation_value_from_point(square: Rect, xy: Point) -> (f32, f32) {
+    let x = square.x.clamp_value(xy[0]);
+    let y = square.y.clamp_value(xy[1]);
+    let saturation = (x - square.left()) / square.w();
+    let value = (y - square.bottom()) / square.h();
+    (saturation as f32, value as f32)
+}
+
+/// The hue in radians at the given point along the hue `strip`.
+///
+/// Points beyond the ends of the `strip` are clamped to the nearest end.
+pub fn hue_from_point(strip: Rect, xy: Point) -> f32 {
+    let y = strip.y.clamp_value(xy[1]);
+    ((y - strip.bottom()) / strip.h()) as f32 * 2.0 * PI
+}
+
+
+#[test]
+fn layout_should_place_the_strip_to_the_right_of_the_square() {
+    let rect = Rect::from_corners([0.0, 0.0], [125.0, 100.0]);
+    let (square, strip) = layout(rect, 20.0, 5.0);
+    assert_eq!(Rect::from_corners([0.0, 0.0], [100.0, 100.0]), square);
+    assert_eq!(Rect::from_corners([105.0, 0.0], [125.0, 100.0]), strip);
+}
+
+#[test]
+fn saturation_value_from_point_should_map_within_and_clamp_beyond_the_square() {
+    let square = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    assert_eq!((0.25, 0.75), saturation_value_from_point(square, [25.0, 75.0]));
+    assert_eq!((1.0, 0.0), saturation_value_from_point(square, [150.0, -20.0]));
+    assert_eq!((0.0, 1.0), saturation_value_from_point(square, [-10.0, 300.0]));
+
+    // A fully saturated and bright red in the top right corner.
+    let (s, v) = saturation_value_from_point(square, [120.0, 120.0]);
+    assert_eq!(::color::rgb(1.0, 0.0, 0.0), Color::from_hsv(0.0, s, v));
+    // Half saturation and half value.
+    let (s, v) = saturation_value_from_point(square, [50.0, 50.0]);
+    assert_eq!(::color::rgb(0.5, 0.25, 0.25), Color::from_hsv(0.0, s, v));
+}
+
+#[test]
+fn hue_from_point_should_map_along_and_clamp_beyond_the_strip() {
+    let strip = Rect::from_corners([105.0, 0.0], [125.0, 120.0]);
+    assert_eq!(0.0, hue_from_point(strip, [110.0, -50.0]));
+    assert_eq!(2.0 * PI, hue_from_point(strip, [110.0, 500.0]));
+    // A third of the way up the strip is pure green.
+    let hue = hue_from_point(strip, [0.0, 40.0]);
+    assert!((hue - 2.0 * PI / 3.0).abs() < 1e-6);
+    let ::color::Rgba(r, g, b, a) = Color::from_hsv(hue, 1.0, 1.0).to_rgb();
+    assert!(r.abs() < 1e-5 && (g - 1.0).abs() < 1e-5 && b.abs() < 1e-5 && a == 1.0);
+}
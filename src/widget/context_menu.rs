@@ -0,0 +1,196 @@
+//! A pop-up list of items, normally opened upon the `event::ContextMenu` produced by
+//! right-clicking some other widget.
+
+use {
+    Color,
+    Colorable,
+    Dimension,
+    Direction,
+    FontSize,
+    Labelable,
+    Point,
+    Position,
+    Positionable,
+    Scalar,
+    Sizeable,
+    Ui,
+    Widget,
+};
+use event;
+use widget;
+
+
+/// A pop-up list of selectable items whose top left corner is placed at some point, normally the
+/// position of the right-click that opened it.
+///
+/// Each right-click on a widget produces an `event::ContextMenu`, yielded by
+/// `ui.widget_input(id).context_menus()`. As with all widget events, its `xy` is relative to the
+/// centre of the right-clicked widget, so the widget's own position should be added to it to find
+/// the `point` at which to open the menu.
+///
+/// If the menu would extend beyond the right or bottom edge of the window, it is flipped across
+/// the point so that it remains on-screen.
+///
+/// Like the `Modal`, the `ContextMenu` does not store whether or not it is open. The user should
+/// stop instantiating it upon receiving an `Event`.
+pub struct ContextMenu<'a, T: 'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    items: &'a [T],
+    point: Point,
+}
+
+widget_style! {
+    /// Unique styling for the `ContextMenu`.
+    style Style {
+        /// The height of each item.
+        - item_h: Scalar { 30.0 }
+        /// The color of each item.
+        - color: Color { theme.shape_color }
+        /// The color of each item's label.
+        - label_color: Color { theme.label_color }
+        /// The font size of each item's label.
+        - label_font_size: FontSize { theme.font_size_medium }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        items[],
+    }
+}
+
+/// The state of the `ContextMenu`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+/// The events produced by the `ContextMenu`, upon which it should be closed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The item at the given index was selected.
+    Selected(usize),
+    /// A mouse button was pressed outside of the menu.
+    Dismissed,
+}
+
+/// The default width of the `ContextMenu`.
+pub const DEFAULT_WIDTH: Scalar = 150.0;
+
+
+impl<'a, T> ContextMenu<'a, T> {
+
+    /// Begin building a `ContextMenu` for the given items, whose top left corner is placed at the
+    /// given `point`.
+    pub fn new(items: &'a [T], point: Point) -> Self {
+        let mut common = widget::CommonBuilder::new();
+        common.is_floating = true;
+        ContextMenu {
+            common: common,
+            style: Style::new(),
+            items: items,
+            point: point,
+        }
+    }
+
+    builder_methods!{
+        pub item_h { style.item_h = Some(Scalar) }
+    }
+
+}
+
+
+impl<'a, T> Widget for ContextMenu<'a, T>
+    where T: AsRef<str>,
+{
+    type State = State;
+    type Style = Style;
+    type Event = Option<Event>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &Ui) -> Position {
+        Position::Anchor(self.point[0], Direction::Forwards, true)
+    }
+
+    fn default_y_position(&self, _ui: &Ui) -> Position {
+        Position::Anchor(self.point[1], Direction::Backwards, true)
+    }
+
+    fn default_x_dimension(&self, _ui: &Ui) -> Dimension {
+        Dimension::Absolute(DEFAULT_WIDTH)
+    }
+
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::Absolute(self.items.len() as Scalar * self.style.item_h(&ui.theme))
+    }
+
+    /// Update the state of the ContextMenu.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let ContextMenu { items, .. } = self;
+
+        // Presses from the frame in which the menu was opened (such as the right-click that
+        // opened it) should not dismiss it.
+        let was_open = ui.prev_updated_widgets().contains(&id);
+        let was_pressed_outside = was_open && ui.global_input().events().ui().any(|e| match *e {
+            event::Ui::Press(_, event::Press { button: event::Button::Mouse(_, xy), .. }) =>
+                !rect.is_over(xy),
+            _ => false,
+        });
+
+        if state.ids.items.len() < items.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.items.resize(items.len(), id_gen));
+        }
+
+        let item_h = style.item_h(&ui.theme);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let mut maybe_selected = None;
+        for (i, item) in items.iter().enumerate() {
+            let clicked = widget::Button::new()
+                .w_h(rect.w(), item_h)
+                .mid_top_with_margin_on(id, i as Scalar * item_h)
+                .color(color)
+                .label(item.as_ref())
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .parent(id)
+                .set(state.ids.items[i], ui)
+                .was_clicked();
+            if clicked {
+                maybe_selected = Some(i);
+            }
+        }
+
+        match maybe_selected {
+            Some(i) => Some(Event::Selected(i)),
+            None if was_pressed_outside => Some(Event::Dismissed),
+            None => None,
+        }
+    }
+
+}
+
+
+impl<'a, T> Colorable for ContextMenu<'a, T> {
+    builder_method!(color { style.color = Some(Color) });
+}
@@ -11,6 +11,8 @@ use {
     Scalar,
     Sizeable,
 };
+use input;
+use std;
 use text;
 use utils;
 use widget::{self, Widget};
@@ -24,6 +26,10 @@ pub type Len = usize;
 /// Displays a given `Vec<String>` as a selectable drop down menu.
 ///
 /// It's reaction is triggered upon selection of a list item.
+///
+/// While open, the items may also be navigated via the `Up` and `Down` arrow keys. The menu is
+/// scrolled so that the highlighted item is always visible. Pressing `Enter` or `Return` selects
+/// the highlighted item, while `Escape` closes the menu.
 pub struct DropDownList<'a, T: 'a> {
     common: widget::CommonBuilder,
     items: &'a [T],
@@ -49,9 +55,14 @@ widget_style! {
         /// The label's alignment over the *x* axis.
         - label_x_align: Align { Align::Middle }
         /// Maximum height of the Open menu before the scrollbar appears.
+        ///
+        /// The menu is always limited to the space between the top of the widget and the bottom
+        /// of the window.
         - maybe_max_visible_height: Option<MaxHeight> { None }
         /// The position of the scrollbar in the case that the list is scrollable.
-        - scrollbar_position: Option<widget::list::ScrollbarPosition> { None }
+        - scrollbar_position: Option<widget::list::ScrollbarPosition> {
+            Some(widget::list::ScrollbarPosition::NextTo)
+        }
         /// The width of the scrollbar in the case that the list is scrollable.
         - scrollbar_width: Option<Scalar> { None }
         /// The ID of the font used to display the labels.
@@ -69,6 +80,8 @@ widget_ids! {
 /// Represents the state of the DropDownList.
 pub struct State {
     menu_state: MenuState,
+    /// The index of the item highlighted via the keyboard while the menu is open, if any.
+    highlighted: Option<Idx>,
     ids: Ids,
 }
 
@@ -174,6 +187,7 @@ impl<'a, T> Widget for DropDownList<'a, T>
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             menu_state: MenuState::Closed,
+            highlighted: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -195,9 +209,15 @@ impl<'a, T> Widget for DropDownList<'a, T>
         // Track whether or not a list item was clicked.
         let mut clicked_item = None;
 
+        // The item highlighted via the keyboard, beginning at the selected item upon opening.
+        let mut highlighted = state.highlighted;
+
+        // A disabled list is always closed.
+        let menu_state = if self.enabled { state.menu_state } else { MenuState::Closed };
+
         // Act on the current menu state and determine what the next one will be.
         // new_menu_state is what we will be getting passed next frame
-        let new_menu_state = match state.menu_state {
+        let new_menu_state = match menu_state {
 
             // If closed, we only want the button at the selected index to be drawn.
             MenuState::Closed => {
@@ -212,13 +232,19 @@ impl<'a, T> Widget for DropDownList<'a, T>
                         .xy(rect.xy())
                         .wh(rect.dim())
                         .label(label)
+                        .enabled(self.enabled)
                         .parent(id);
                     button.style = style.button_style(false);
                     button.set(state.ids.closed_menu, ui).was_clicked()
                 };
 
                 // If the button was clicked, then open, otherwise stay closed
-                if was_clicked { MenuState::Open } else { MenuState::Closed }
+                if was_clicked {
+                    highlighted = selected;
+                    MenuState::Open
+                } else {
+                    MenuState::Closed
+                }
             },
 
             MenuState::Open => {
@@ -251,6 +277,36 @@ impl<'a, T> Widget for DropDownList<'a, T>
                             .unwrap_or(10.0)
                     });
 
+                // Navigate the items via the keyboard, which remains captured by the closed menu's
+                // button after it is clicked to open the menu.
+                let mut should_close = false;
+                let prev_highlighted = highlighted;
+                for press in ui.widget_input(state.ids.closed_menu).presses().key() {
+                    match press.key {
+                        input::Key::Down if num_items > 0 => highlighted = match highlighted {
+                            None => Some(0),
+                            Some(i) => Some(std::cmp::min(i + 1, num_items - 1)),
+                        },
+                        input::Key::Up if num_items > 0 => highlighted = match highlighted {
+                            None | Some(0) => Some(0),
+                            Some(i) => Some(i - 1),
+                        },
+                        input::Key::Return | input::Key::NumPadEnter =>
+                            if let Some(i) = highlighted {
+                                clicked_item = Some(i);
+                            },
+                        input::Key::Escape => should_close = true,
+                        _ => (),
+                    }
+                }
+
+                // Only scroll on updates where the highlighted item changes so that the menu may
+                // still be scrolled freely via the mouse.
+                let maybe_item_to_scroll_into_view = match highlighted {
+                    Some(i) if highlighted != prev_highlighted => Some(i),
+                    _ => None,
+                };
+
                 let (mut events, scrollbar) = widget::ListSelect::single(num_items, item_h)
                     .w_h(w, list_h)
                     .and(|ls| match scrollbar_position {
@@ -260,6 +316,7 @@ impl<'a, T> Widget for DropDownList<'a, T>
                     })
                     .scrollbar_color(scrollbar_color)
                     .scrollbar_width(scrollbar_width)
+                    .and_then(maybe_item_to_scroll_into_view, |ls, i| ls.scroll_item_into_view(i))
                     .mid_top_of(id)
                     .floating(true)
                     .set(state.ids.list, ui);
@@ -273,7 +330,8 @@ impl<'a, T> Widget for DropDownList<'a, T>
                             let i = item.i;
                             let label = self.items[i].as_ref();
                             let mut button = widget::Button::new().label(label);
-                            button.style = style.button_style(Some(i) == selected);
+                            let is_highlighted = Some(i) == selected || Some(i) == highlighted;
+                            button.style = style.button_style(is_highlighted);
                             item.set(button, ui);
                         },
 
@@ -291,7 +349,7 @@ impl<'a, T> Widget for DropDownList<'a, T>
 
                 // Close the menu if the mouse is pressed and the currently pressed widget is
                 // not any of the drop down list's children.
-                let should_close = clicked_item.is_some() ||
+                let should_close = should_close || clicked_item.is_some() ||
                     clicked_item.is_none()
                     && ui.global_input.current.mouse.buttons.pressed().next().is_some()
                     && match ui.global_input.current.widget_capturing_mouse {
@@ -307,10 +365,18 @@ impl<'a, T> Widget for DropDownList<'a, T>
             }
         };
 
+        if new_menu_state == MenuState::Closed {
+            highlighted = None;
+        }
+
         if state.menu_state != new_menu_state {
             state.update(|state| state.menu_state = new_menu_state);
         }
 
+        if state.highlighted != highlighted {
+            state.update(|state| state.highlighted = highlighted);
+        }
+
         clicked_item
     }
 
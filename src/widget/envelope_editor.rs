@@ -265,9 +265,15 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
             min_x, max_x,
             min_y, max_y,
             maybe_label,
+            enabled,
             ..
         } = self;
 
+        // A disabled editor relinquishes any capture that it held and ignores all events.
+        if !enabled {
+            ui.release_captures(id);
+        }
+
         let mut env = std::borrow::Cow::Borrowed(env);
 
         let point_radius = style.point_radius(ui.theme());
@@ -320,7 +326,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
         };
 
         // Track the currently pressed point if any.
-        let mut pressed_point = state.pressed_point;
+        let mut pressed_point = if enabled { state.pressed_point } else { None };
 
         // Handle all events that have occurred to the EnvelopeEditor since the last update.
         //
@@ -329,7 +335,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
         // - Remove points via right `Click`.
         // - Dragging points via left `Drag`.
         let mut events = Vec::new();
-        'events: for widget_event in ui.widget_input(id).events() {
+        'events: for widget_event in ui.widget_input(id).events().filter(|_| enabled) {
             use event;
             use input::{self, MouseButton};
 
@@ -467,7 +473,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
         let inner_rect = rect.pad(border);
         let dim = rect.dim();
         let border = style.border(ui.theme());
-        let color = style.color(ui.theme());
+        let color = if enabled { style.color(ui.theme()) } else { ui.theme.disabled_color };
         let color = ui.widget_input(id).mouse()
             .and_then(|m| if inner_rect.is_over(m.abs_xy()) { Some(color.highlighted()) }
                           else { None })
@@ -0,0 +1,237 @@
+//! A helper widget for laying out child widgets in rows that wrap at the edge of the container.
+
+use {Dimensions, Point, Positionable, Scalar, Sizeable, UiCell, Widget};
+use std;
+use widget;
+
+
+/// Lays out a sequence of child widgets from left to right, wrapping onto the next row whenever
+/// the next child would exceed the width of the `Flow`.
+///
+/// The dimensions of each child must be given up front so that the wrapping can be determined.
+/// Children that are wider than the `Flow` itself are given a row of their own.
+#[derive(Clone)]
+#[allow(missing_copy_implementations)]
+pub struct Flow<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    child_dims: &'a [Dimensions],
+}
+
+/// The state of the `Flow`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    /// A `widget::Id` for every child of the `Flow`.
+    ids: Vec<widget::Id>,
+}
+
+widget_style!{
+    /// Unique styling for the `Flow`.
+    style Style {
+        /// The distance between neighbouring children along both the *x* and *y* axes.
+        - gap: Scalar { 0.0 }
+    }
+}
+
+/// The event type yielded by the `Flow`.
+///
+/// This can be used to iterate over each `Item` in the `Flow`.
+#[derive(Debug)]
+pub struct Items {
+    flow_id: widget::Id,
+    ids: std::vec::IntoIter<widget::Id>,
+    positions: std::vec::IntoIter<Point>,
+    child_dims: std::vec::IntoIter<Dimensions>,
+    index: usize,
+}
+
+/// Data necessary for instantiating a widget for a single child of the `Flow`.
+#[derive(Copy, Clone, Debug)]
+pub struct Item {
+    /// The index of the child within the `Flow`.
+    pub i: usize,
+    /// The id generated for the widget.
+    pub widget_id: widget::Id,
+    /// The width of the child.
+    pub w: Scalar,
+    /// The height of the child.
+    pub h: Scalar,
+    /// The *x* position of the child relative to the centre of the `Flow`.
+    pub rel_x: Scalar,
+    /// The *y* position of the child relative to the centre of the `Flow`.
+    pub rel_y: Scalar,
+    /// The id of the `Flow`, used for positioning.
+    flow_id: widget::Id,
+}
+
+
+impl<'a> Flow<'a> {
+
+    /// Begin building a `Flow` for children with the given dimensions.
+    pub fn new(child_dims: &'a [Dimensions]) -> Self {
+        Flow {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            child_dims: child_dims,
+        }
+    }
+
+    /// The distance between neighbouring children along both the *x* and *y* axes.
+    pub fn gap(mut self, gap: Scalar) -> Self {
+        self.style.gap = Some(gap);
+        self
+    }
+
+}
+
+
+impl<'a> Widget for Flow<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Items;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State { ids: Vec::new() }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Flow.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Flow { child_dims, .. } = self;
+
+        // Ensure we have a `widget::Id` for every child.
+        let num_ids = state.ids.len();
+        if num_ids < child_dims.len() {
+            let mut id_gen = ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.extend((num_ids..child_dims.len()).map(|_| id_gen.next()));
+            });
+        }
+
+        // Convert the top-left offsets into positions relative to the centre of the `Flow`.
+        let gap = style.gap(&ui.theme);
+        let (half_w, half_h) = (rect.w() / 2.0, rect.h() / 2.0);
+        let positions = layout(rect.w(), child_dims, gap).into_iter()
+            .zip(child_dims.iter())
+            .map(|(offset, dim)| {
+                let rel_x = -half_w + offset[0] + dim[0] / 2.0;
+                let rel_y = half_h - offset[1] - dim[1] / 2.0;
+                [rel_x, rel_y]
+            })
+            .collect::<Vec<_>>();
+
+        Items {
+            flow_id: id,
+            ids: state.ids[..child_dims.len()].to_vec().into_iter(),
+            positions: positions.into_iter(),
+            child_dims: child_dims.to_vec().into_iter(),
+            index: 0,
+        }
+    }
+
+}
+
+
+impl Iterator for Items {
+    type Item = Item;
+    fn next(&mut self) -> Option<Item> {
+        let widget_id = match self.ids.next() {
+            Some(id) => id,
+            None => return None,
+        };
+        match (self.positions.next(), self.child_dims.next()) {
+            (Some(rel_xy), Some(dim)) => {
+                let item = Item {
+                    i: self.index,
+                    widget_id: widget_id,
+                    w: dim[0],
+                    h: dim[1],
+                    rel_x: rel_xy[0],
+                    rel_y: rel_xy[1],
+                    flow_id: self.flow_id,
+                };
+                self.index += 1;
+                Some(item)
+            },
+            _ => None,
+        }
+    }
+}
+
+
+impl Item {
+
+    /// Sets the given widget as the widget to use for the item.
+    ///
+    /// Sets the:
+    /// - position of the widget.
+    /// - dimensions of the widget.
+    /// - parent of the widget.
+    /// - and finally sets the widget within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let Item { widget_id, flow_id, w, h, rel_x, rel_y, .. } = self;
+        widget
+            .w_h(w, h)
+            .x_y_relative_to(flow_id, rel_x, rel_y)
+            .parent(flow_id)
+            .set(widget_id, ui)
+    }
+
+}
+
+
+/// Determine the offset of the top-left corner of each child from the top-left corner of a `Flow`
+/// with the given width.
+///
+/// Children are placed from left to right with `gap` between them. When a child would exceed the
+/// `width`, it begins a new row `gap` below the tallest child of the previous row. A child that is
+/// wider than `width` is placed at the start of a row and the following child always wraps.
+///
+/// The returned offsets are positive to the right and downwards.
+pub fn layout(width: Scalar, child_dims: &[Dimensions], gap: Scalar) -> Vec<Point> {
+    let mut offsets = Vec::with_capacity(child_dims.len());
+    let (mut x, mut y, mut row_h) = (0.0, 0.0, 0.0);
+    let mut row_is_empty = true;
+    for dim in child_dims {
+        let (w, h) = (dim[0], dim[1]);
+        if !row_is_empty && x + w > width {
+            x = 0.0;
+            y += row_h + gap;
+            row_h = 0.0;
+        }
+        offsets.push([x, y]);
+        x += w + gap;
+        row_h = if h > row_h { h } else { row_h };
+        row_is_empty = false;
+    }
+    offsets
+}
+
+
+#[test]
+fn layout_should_wrap_children_exceeding_the_width() {
+    let dims = [[100.0, 50.0], [100.0, 50.0], [100.0, 30.0], [400.0, 20.0], [50.0, 50.0]];
+    let offsets = layout(300.0, &dims, 10.0);
+    assert_eq!(vec![[0.0, 0.0], [110.0, 0.0], [0.0, 60.0], [0.0, 100.0], [0.0, 130.0]], offsets);
+}
+
+#[test]
+fn layout_should_fill_a_row_exactly() {
+    let dims = [[95.0, 10.0], [95.0, 20.0], [95.0, 10.0], [95.0, 10.0]];
+    let offsets = layout(305.0, &dims, 10.0);
+    assert_eq!(vec![[0.0, 0.0], [105.0, 0.0], [210.0, 0.0], [0.0, 30.0]], offsets);
+    assert!(layout(305.0, &[], 10.0).is_empty());
+}
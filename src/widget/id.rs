@@ -4,6 +4,7 @@
 use daggy;
 use graph::Graph;
 use std;
+use std::collections::{HashMap, HashSet};
 
 /// Unique widget identifier.
 ///
@@ -29,6 +30,45 @@ pub struct List(Vec<Id>);
 #[allow(missing_copy_implementations)]
 pub struct ListWalk { i: usize }
 
+/// A registry of `widget::Id`s keyed by user-provided names.
+///
+/// Generated `widget::Id`s depend upon the order in which they are generated. A name on the other
+/// hand always refers to the same widget, and hashes to a key (see `hash_name`) that is the same
+/// across runs. This is useful for recording and replaying input or comparing snapshots of a UI.
+///
+/// The `widget::Id` of each name is derived from its key rather than from the order in which the
+/// names are first used. The first time any name is used, a block of `NAME_BLOCK_LEN` consecutive
+/// `widget::Id`s is reserved, and each name takes the `widget::Id` at the index of its key modulo
+/// `NAME_BLOCK_LEN` within the block. Where the index is taken by a name with a different key, the
+/// following indices are probed in turn, reserving a further block once all are taken. Only such
+/// names depend upon the order in which they are first used.
+///
+/// Distinct names that hash to the same key are reported as a `NameCollision` rather than being
+/// allowed to silently refer to the same widget.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Names {
+    by_key: HashMap<u32, (String, Id)>,
+    /// The first `widget::Id` of each block reserved for names.
+    blocks: Vec<Id>,
+    /// The `widget::Id`s within the `blocks` that are already taken by some name.
+    taken: HashSet<Id>,
+}
+
+/// The number of consecutive `widget::Id`s reserved for names at a time by `Names`.
+pub const NAME_BLOCK_LEN: usize = 64;
+
+/// The error returned when a name hashes to the same key as some other name that is already in
+/// use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NameCollision {
+    /// The name that was requested.
+    pub name: String,
+    /// The name already in use with the same key.
+    pub existing: String,
+    /// The key shared by both names.
+    pub key: u32,
+}
+
 
 impl<'a> Generator<'a> {
 
@@ -103,6 +143,101 @@ impl ListWalk {
 
 }
 
+impl Names {
+
+    /// An empty registry.
+    pub fn new() -> Self {
+        Names {
+            by_key: HashMap::new(),
+            blocks: Vec::new(),
+            taken: HashSet::new(),
+        }
+    }
+
+    /// The `widget::Id` for the given name, deriving it from the name's key if the name has not
+    /// been used before.
+    ///
+    /// Returns a `NameCollision` if some other name with the same key is already in use.
+    pub fn get_or_generate(&mut self, name: &str, id_generator: &mut Generator)
+        -> Result<Id, NameCollision>
+    {
+        let key = hash_name(name);
+        if let Some(&(ref existing, id)) = self.by_key.get(&key) {
+            if existing == name {
+                return Ok(id);
+            }
+            return Err(NameCollision {
+                name: name.to_string(),
+                existing: existing.clone(),
+                key: key,
+            });
+        }
+        let id = self.take_id(key, id_generator);
+        self.by_key.insert(key, (name.to_string(), id));
+        Ok(id)
+    }
+
+    /// Take the first free `widget::Id` within the reserved blocks, probing from the index of the
+    /// given key within each block in turn and reserving a new block if all are taken.
+    fn take_id(&mut self, key: u32, id_generator: &mut Generator) -> Id {
+        let start = key as usize % NAME_BLOCK_LEN;
+        for block_index in 0.. {
+            if block_index == self.blocks.len() {
+                let first = id_generator.next();
+                for i in 1..NAME_BLOCK_LEN {
+                    let id = id_generator.next();
+                    debug_assert_eq!(first.index() + i, id.index());
+                }
+                self.blocks.push(first);
+            }
+            let first = self.blocks[block_index].index();
+            for i in 0..NAME_BLOCK_LEN {
+                let id = Id::new(first + (start + i) % NAME_BLOCK_LEN);
+                if self.taken.insert(id) {
+                    return id;
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    /// The `widget::Id` for the given name if it has been used before.
+    pub fn get(&self, name: &str) -> Option<Id> {
+        match self.by_key.get(&hash_name(name)) {
+            Some(&(ref existing, id)) if existing == name => Some(id),
+            _ => None,
+        }
+    }
+
+    /// The stable key of the name used to produce the given `widget::Id`, if any.
+    pub fn key_of(&self, id: Id) -> Option<u32> {
+        self.by_key.iter().find(|&(_, &(_, named_id))| named_id == id).map(|(&key, _)| key)
+    }
+
+}
+
+impl std::error::Error for NameCollision {}
+
+impl std::fmt::Display for NameCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "The widget name {:?} collides with {:?} (key {:#x}).",
+               self.name, self.existing, self.key)
+    }
+}
+
+
+/// Hash the given widget name to a key that is the same across runs and platforms.
+///
+/// This uses the 32-bit FNV-1a hash.
+pub fn hash_name(name: &str) -> u32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for &byte in name.as_bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
+
 
 /// A macro used to generate a struct with a field for each unique identifier given.
 /// Each field can then be used to generate unique `widget::Id`s.
@@ -413,3 +548,68 @@ fn test_invocation_variations() {
     let g = G::new(ui.widget_id_generator());
     let h = H::new(ui.widget_id_generator());
 }
+
+#[test]
+fn names_should_yield_the_same_id_for_the_same_name() {
+    use ui::UiBuilder;
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let (a, b) = {
+        let mut id_gen = ui.widget_id_generator();
+        let mut names = Names::new();
+        let a = names.get_or_generate("a", &mut id_gen).unwrap();
+        let b = names.get_or_generate("button", &mut id_gen).unwrap();
+        assert_eq!(Ok(a), names.get_or_generate("a", &mut id_gen));
+        assert_eq!(Some(b), names.get("button"));
+        assert_eq!(None, names.get("toggle"));
+        assert_eq!(Some(0x43b27471), names.key_of(b));
+        (a, b)
+    };
+    assert!(a != b);
+    assert_eq!(0xe40c292c, hash_name("a"));
+}
+
+#[test]
+fn names_should_report_hash_collisions() {
+    use ui::UiBuilder;
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    let mut id_gen = ui.widget_id_generator();
+    let mut names = Names::new();
+    assert_eq!(hash_name("costarring"), hash_name("liquid"));
+    let id = names.get_or_generate("costarring", &mut id_gen).unwrap();
+    let collision = NameCollision {
+        name: "liquid".to_string(),
+        existing: "costarring".to_string(),
+        key: 0x5e4daa9d,
+    };
+    assert_eq!(Err(collision), names.get_or_generate("liquid", &mut id_gen));
+    assert_eq!(None, names.get("liquid"));
+    assert_eq!(Some(id), names.get("costarring"));
+    assert_eq!("The widget name \"liquid\" collides with \"costarring\" (key 0x5e4daa9d).",
+               format!("{}", names.get_or_generate("liquid", &mut id_gen).unwrap_err()));
+}
+
+#[test]
+fn names_should_yield_the_same_ids_regardless_of_the_order_in_which_they_are_used() {
+    use ui::UiBuilder;
+    // `item_5` and `item_10` share the same index within a block.
+    let names = ["ok_button", "cancel_button", "title", "volume_slider", "item_5", "item_10"];
+    let ids_in_order = |order: &[usize]| {
+        let mut ui = UiBuilder::new([800.0, 600.0]).build();
+        let mut id_gen = ui.widget_id_generator();
+        let mut registry = Names::new();
+        for &i in order {
+            registry.get_or_generate(names[i], &mut id_gen).unwrap();
+        }
+        names.iter().map(|name| registry.get(name).unwrap()).collect::<Vec<_>>()
+    };
+    let ids = ids_in_order(&[0, 1, 2, 3, 4, 5]);
+    let reversed = ids_in_order(&[5, 4, 3, 2, 1, 0]);
+    assert_eq!(&ids[..4], &reversed[..4]);
+
+    // Names sharing an index are still given distinct ids from the same block.
+    assert!(ids[4] != ids[5]);
+    assert_eq!(ids[4], reversed[5]);
+    assert_eq!(ids[5], reversed[4]);
+    let first = ids.iter().map(|id| id.index()).min().unwrap();
+    assert!(ids.iter().all(|id| id.index() < first + NAME_BLOCK_LEN));
+}
@@ -23,9 +23,13 @@ use widget;
 /// - Simplifying the positioning and sizing of items.
 /// - Optimised widget instantiation by only instantiating visible items. This is very useful for
 ///   lists containing many items, i.e. a `FileNavigator` over a directory with thousands of files.
+///
+/// Items may either share a uniform height (`List::new`) or each have their own height
+/// (`List::with_item_heights`). In both cases the scrollable range spans the total height of all
+/// items, while only the visible items are instantiated.
 #[derive(Clone)]
 #[allow(missing_copy_implementations)]
-pub struct List {
+pub struct List<'a> {
     /// Common widget building params for the `List`.
     pub common: widget::CommonBuilder,
     /// Unique styling for the `List`.
@@ -34,6 +38,8 @@ pub struct List {
     pub item_instantiation: ItemInstantiation,
     item_h: Scalar,
     num_items: usize,
+    maybe_item_heights: Option<&'a ItemHeights>,
+    maybe_item_to_scroll_into_view: Option<usize>,
 }
 
 widget_style! {
@@ -81,6 +87,18 @@ pub struct Item {
     first_item_margin: Scalar,
 }
 
+/// An index of the cumulative heights of a sequence of items with variable heights.
+///
+/// Building the index is `O(n)` in the number of items, after which finding the items visible
+/// within some scrolled range is `O(log n)`. As a result, the index should be built once and only
+/// rebuilt when the items or their heights change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemHeights {
+    /// The distance from the top of the first item to the top of each item, followed by the
+    /// total height of all items.
+    tops: Vec<Scalar>,
+}
+
 /// The way in which a `List` should instantiate its `Item`s.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ItemInstantiation {
@@ -106,7 +124,7 @@ pub struct Scrollbar {
 }
 
 /// An `Iterator` yielding each `Item` in the list.
-pub struct Items {
+pub struct Items<'a> {
     item_indices: std::ops::Range<usize>,
     next_item_indices_index: usize,
     list_id: widget::Id,
@@ -115,10 +133,95 @@ pub struct Items {
     first_item_margin: Scalar,
     item_w: Scalar,
     item_h: Scalar,
+    maybe_item_heights: Option<&'a ItemHeights>,
 }
 
 
-impl List {
+impl ItemHeights {
+
+    /// Index the given heights of each item, in order from the top of the list.
+    ///
+    /// Negative heights are treated as `0.0`.
+    pub fn new<I>(heights: I) -> Self
+        where I: IntoIterator<Item=Scalar>,
+    {
+        let mut total = 0.0;
+        let mut tops = vec![total];
+        for h in heights {
+            total += h.max(0.0);
+            tops.push(total);
+        }
+        ItemHeights { tops: tops }
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.tops.len() - 1
+    }
+
+    /// The total height of all items.
+    pub fn total(&self) -> Scalar {
+        self.tops[self.len()]
+    }
+
+    /// The distance from the top of the first item to the top of the item at the given index.
+    ///
+    /// An index equal to the number of items gives the total height.
+    pub fn top(&self, i: usize) -> Scalar {
+        self.tops[i]
+    }
+
+    /// The height of the item at the given index.
+    pub fn height(&self, i: usize) -> Scalar {
+        self.tops[i + 1] - self.tops[i]
+    }
+
+    /// The range of indices of the items that are at least partially visible when the given
+    /// length of the list is scrolled above a visible area of the given height.
+    pub fn visible_range(&self,
+                         hidden_range_length: Scalar,
+                         visible_h: Scalar) -> std::ops::Range<usize>
+    {
+        let visible_bottom = hidden_range_length + visible_h;
+        // The number of items that end at or above the top of the visible area.
+        let start = partition_point(&self.tops[1..], |&bottom| bottom <= hidden_range_length);
+        // The number of items that begin above the bottom of the visible area.
+        let end = partition_point(&self.tops[..self.len()], |&top| top < visible_bottom);
+        start..std::cmp::max(start, end)
+    }
+
+}
+
+/// The index of the first element of the given sorted slice for which `pred` is `false`.
+fn partition_point<F>(slice: &[Scalar], pred: F) -> usize
+    where F: Fn(&Scalar) -> bool,
+{
+    use std::cmp::Ordering;
+    slice.binary_search_by(|x| if pred(x) { Ordering::Less } else { Ordering::Greater })
+        .unwrap_or_else(|idx| idx)
+}
+
+/// The range of indices of the given number of items of uniform height that are at least
+/// partially visible when the given length of the list is scrolled above a visible area of the
+/// given height.
+pub fn visible_range(num_items: usize,
+                     item_h: Scalar,
+                     hidden_range_length: Scalar,
+                     visible_h: Scalar) -> std::ops::Range<usize>
+{
+    let num_top_hidden_items = hidden_range_length / item_h;
+    let num_visible_items = visible_h / item_h;
+
+    let start = std::cmp::min(num_top_hidden_items.floor() as usize, num_items);
+    let end = std::cmp::min(
+        (num_top_hidden_items + num_visible_items).ceil() as usize,
+        num_items,
+    );
+    start..end
+}
+
+
+impl<'a> List<'a> {
 
     /// Create a List context to be built upon.
     pub fn new(num_items: usize, item_height: Scalar) -> Self {
@@ -128,9 +231,24 @@ impl List {
             item_h: item_height,
             num_items: num_items,
             item_instantiation: ItemInstantiation::OnlyVisible,
+            maybe_item_heights: None,
+            maybe_item_to_scroll_into_view: None,
         }.crop_kids()
     }
 
+    /// Create a List context to be built upon, where each item has its own height.
+    ///
+    /// Only the items visible within the `List` are instantiated, using the given index to find
+    /// them without visiting every item. The index is borrowed so that it may be built once and
+    /// reused across updates.
+    pub fn with_item_heights(item_heights: &'a ItemHeights) -> Self {
+        let num_items = item_heights.len();
+        List {
+            maybe_item_heights: Some(item_heights),
+            ..List::new(num_items, 0.0)
+        }
+    }
+
     /// Specifies that the `List` should be scrollable and should provide a `Scrollbar` to the
     /// right of the items.
     pub fn scrollbar_next_to(mut self) -> Self {
@@ -178,14 +296,26 @@ impl List {
         self
     }
 
+    /// Scroll the `List` by the minimum distance necessary for the item at the given index to be
+    /// fully visible.
+    ///
+    /// This should only be specified on updates where the item should be brought into view (e.g.
+    /// when it is highlighted via the keyboard), otherwise the user will be unable to scroll away
+    /// from it. The scrolling is applied upon the following update and has no effect unless the
+    /// `List` is scrollable.
+    pub fn scroll_item_into_view(mut self, i: usize) -> Self {
+        self.maybe_item_to_scroll_into_view = Some(i);
+        self
+    }
+
 }
 
 
 
-impl Widget for List {
+impl<'a> Widget for List<'a> {
     type State = State;
     type Style = Style;
-    type Event = (Items, Option<Scrollbar>);
+    type Event = (Items<'a>, Option<Scrollbar>);
 
     fn common(&self) -> &widget::CommonBuilder {
         &self.common
@@ -207,10 +337,19 @@ impl Widget for List {
 
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, prev, mut ui, style, .. } = args;
-        let List { item_h, num_items, item_instantiation, .. } = self;
+        let List {
+            item_h,
+            num_items,
+            item_instantiation,
+            maybe_item_heights,
+            maybe_item_to_scroll_into_view,
+            ..
+        } = self;
 
         // We need a positive item height in order to do anything useful.
-        assert!(item_h > 0.0, "the given item height was {:?} however it must be > 0", item_h);
+        if maybe_item_heights.is_none() {
+            assert!(item_h > 0.0, "the given item height was {:?} however it must be > 0", item_h);
+        }
 
         // Determine whther or not the list is scrollable.
         let is_scrollable = prev.maybe_y_scroll_state.as_ref()
@@ -231,7 +370,13 @@ impl Widget for List {
             _ => rect.w(),
         };
 
-        let total_item_h = num_items as Scalar * item_h;
+        // The distance from the top of the first item to the top of the item at the given index.
+        let item_top = |i: usize| match maybe_item_heights {
+            Some(heights) => heights.top(i),
+            None => i as Scalar * item_h,
+        };
+
+        let total_item_h = item_top(num_items);
 
         // The widget used to scroll the `List`'s range.
         //
@@ -243,6 +388,26 @@ impl Widget for List {
             .parent(id)
             .set(state.ids.scroll_trigger, ui);
 
+        // The length of the `scroll_trigger` that is scrolled above the top of the `List`.
+        let hidden_range_length = {
+            let scroll_trigger_rect = ui.rect_of(state.ids.scroll_trigger).unwrap();
+            (scroll_trigger_rect.top() - rect.top()).max(0.0)
+        };
+
+        // Scroll the requested item into view if it lies above or below the visible range.
+        if let Some(i) = maybe_item_to_scroll_into_view {
+            let item_bottom = item_top(i + 1);
+            let item_top = item_top(i);
+            let distance = if item_top < hidden_range_length {
+                item_top - hidden_range_length
+            } else if item_bottom > hidden_range_length + rect.h() {
+                item_bottom - (hidden_range_length + rect.h())
+            } else {
+                0.0
+            };
+            ui.scroll_widget(id, [0.0, distance]);
+        }
+
         // Determine the index range of the items that should be instantiated.
         let (item_idx_range, first_item_margin) = match item_instantiation {
             ItemInstantiation::All => {
@@ -251,19 +416,11 @@ impl Widget for List {
                 (range, margin)
             },
             ItemInstantiation::OnlyVisible => {
-                let scroll_trigger_rect = ui.rect_of(state.ids.scroll_trigger).unwrap();
-                let hidden_range_length = (scroll_trigger_rect.top() - rect.top()).max(0.0);
-                let num_top_hidden_items = hidden_range_length / item_h;
-                let num_visible_items = rect.h() / item_h;
-
-                let first_visible_item_idx = num_top_hidden_items.floor() as usize;
-                let end_visible_item_idx = std::cmp::min(
-                    (num_top_hidden_items + num_visible_items).ceil() as usize,
-                    num_items,
-                );
-
-                let range = first_visible_item_idx..end_visible_item_idx;
-                let margin = first_visible_item_idx as Scalar * item_h;
+                let range = match maybe_item_heights {
+                    Some(heights) => heights.visible_range(hidden_range_length, rect.h()),
+                    None => visible_range(num_items, item_h, hidden_range_length, rect.h()),
+                };
+                let margin = item_top(range.start);
                 (range, margin)
             },
         };
@@ -283,6 +440,7 @@ impl Widget for List {
             first_item_margin: first_item_margin,
             item_w: item_w,
             item_h: item_h,
+            maybe_item_heights: maybe_item_heights,
         };
 
         // Instantiate the `Scrollbar` only if necessary.
@@ -307,7 +465,7 @@ impl Widget for List {
 }
 
 
-impl Items {
+impl<'a> Items<'a> {
 
     /// Yield the next `Item` in the list.
     pub fn next(&mut self, ui: &Ui) -> Option<Item> {
@@ -320,6 +478,7 @@ impl Items {
             first_item_margin,
             item_w,
             item_h,
+            maybe_item_heights,
         } = *self;
 
         // Retrieve the `node_index` that was generated for the next `Item`.
@@ -338,13 +497,14 @@ impl Items {
 
         match (item_indices.next(), node_index) {
             (Some(i), Some(node_index)) => {
+                let h = maybe_item_heights.map(|heights| heights.height(i));
                 let item = Item {
                     i: i,
                     last_id: *last_id,
                     widget_id: node_index,
                     scroll_trigger_id: scroll_trigger_id,
                     w: item_w,
-                    h: item_h,
+                    h: h.unwrap_or(item_h),
                     first_item_margin: first_item_margin,
                 };
                 *last_id = Some(node_index);
@@ -392,3 +552,35 @@ impl Scrollbar {
         widget.set(id, ui);
     }
 }
+
+
+#[test]
+fn visible_range_should_include_partially_visible_items_of_uniform_height() {
+    assert_eq!(0..3, visible_range(100_000, 20.0, 0.0, 60.0));
+    assert_eq!(1..5, visible_range(100_000, 20.0, 30.0, 60.0));
+    assert_eq!(5_000..5_003, visible_range(100_000, 20.0, 100_000.0, 60.0));
+    // The range is clamped to the number of items.
+    assert_eq!(8..10, visible_range(10, 20.0, 170.0, 60.0));
+    assert_eq!(0..0, visible_range(0, 20.0, 0.0, 60.0));
+}
+
+#[test]
+fn item_heights_visible_range_should_search_the_cumulative_heights() {
+    let heights = ItemHeights::new(vec![10.0, 50.0, 20.0, 20.0, 100.0, 10.0]);
+    assert_eq!(6, heights.len());
+    assert_eq!(210.0, heights.total());
+    assert_eq!(60.0, heights.top(2));
+    assert_eq!(100.0, heights.height(4));
+    assert_eq!(0..2, heights.visible_range(0.0, 40.0));
+    // An item ending exactly at the top of the visible area is hidden.
+    assert_eq!(1..4, heights.visible_range(10.0, 80.0));
+    assert_eq!(4..5, heights.visible_range(110.0, 50.0));
+    assert_eq!(4..6, heights.visible_range(150.0, 100.0));
+    assert_eq!(6..6, heights.visible_range(210.0, 100.0));
+
+    // Agrees with the uniform range for items of equal height.
+    let uniform = ItemHeights::new(std::iter::repeat(20.0).take(100_000));
+    for &hidden in &[0.0, 30.0, 100_000.0, 1_999_970.0] {
+        assert_eq!(visible_range(100_000, 20.0, hidden, 60.0), uniform.visible_range(hidden, 60.0));
+    }
+}
@@ -5,6 +5,10 @@ use {event, graph, input, widget};
 use std;
 
 /// A wrapper around the `List` widget that handles single and multiple selection logic.
+///
+/// A disabled `ListSelect` produces no selection or input events for its items and dims its
+/// scrollbar. As the widget for each item is given by the user, items should also be disabled via
+/// their own `enabled` method where they have one.
 #[derive(Clone)]
 #[allow(missing_copy_implementations)]
 pub struct ListSelect<M> {
@@ -14,6 +18,9 @@ pub struct ListSelect<M> {
     mode: M,
     style: widget::list::Style,
     item_instantiation: widget::list::ItemInstantiation,
+    maybe_item_to_scroll_into_view: Option<usize>,
+    /// Whether or not user input is enabled.
+    enabled: bool,
 }
 
 /// Allows the `ListSelect` to be generic over `Single` and `Multiple` selection modes.
@@ -66,9 +73,10 @@ pub struct Events<M>
     where M: Mode,
 {
     id: widget::Id,
-    items: widget::list::Items,
+    items: widget::list::Items<'static>,
     num_items: usize,
     mode: M,
+    enabled: bool,
     pending_events: PendingEvents<M::Selection>,
 }
 
@@ -175,6 +183,8 @@ impl<M> ListSelect<M> {
             num_items: num_items,
             mode: mode,
             item_instantiation: widget::list::ItemInstantiation::OnlyVisible,
+            maybe_item_to_scroll_into_view: None,
+            enabled: true,
         }
     }
 
@@ -225,6 +235,19 @@ impl<M> ListSelect<M> {
         self
     }
 
+    /// Scroll the `List` by the minimum distance necessary for the item at the given index to be
+    /// fully visible.
+    ///
+    /// See `List::scroll_item_into_view` for details.
+    pub fn scroll_item_into_view(mut self, i: usize) -> Self {
+        self.maybe_item_to_scroll_into_view = Some(i);
+        self
+    }
+
+    builder_methods!{
+        pub enabled { enabled = bool }
+    }
+
 }
 
 impl<M> Widget for ListSelect<M>
@@ -256,7 +279,29 @@ impl<M> Widget for ListSelect<M>
     /// Update the state of the ListSelect.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, mut state, style, mut ui, .. } = args;
-        let ListSelect { num_items, item_h, item_instantiation, mode, .. } = self;
+        let ListSelect {
+            num_items,
+            item_h,
+            item_instantiation,
+            maybe_item_to_scroll_into_view,
+            mode,
+            enabled,
+            ..
+        } = self;
+
+        // A disabled `ListSelect` relinquishes any capture held by itself or any of its items.
+        if !enabled {
+            ui.release_captures(id);
+            let capturing = {
+                let input = ui.global_input();
+                [input.current.widget_capturing_mouse, input.current.widget_capturing_keyboard]
+            };
+            for capturing_id in capturing.iter().filter_map(|&capturing| capturing) {
+                if ui.widget_graph().does_recursive_depth_edge_exist(id, capturing_id) {
+                    ui.release_captures(capturing_id);
+                }
+            }
+        }
 
         // Make sure that `last_selected_entry` refers to an actual selected value in the list.
         // If not push first selected item, if any.
@@ -269,9 +314,13 @@ impl<M> Widget for ListSelect<M>
         let scrollbar_position = style.scrollbar_position(&ui.theme);
 
         let mut list = widget::List::new(num_items, item_h)
-            .and_if(scrollbar_position.is_some(), |ls| ls.scroll_kids_vertically());
+            .and_if(scrollbar_position.is_some(), |ls| ls.scroll_kids_vertically())
+            .and_then(maybe_item_to_scroll_into_view, |ls, i| ls.scroll_item_into_view(i));
         list.item_instantiation = item_instantiation;
         list.style = style.clone();
+        if !enabled {
+            list.style.scrollbar_color = Some(ui.theme.disabled_color);
+        }
         let (items, scrollbar) = list.middle_of(id).wh_of(id).set(state.ids.list, ui);
 
         let events = Events {
@@ -279,6 +328,7 @@ impl<M> Widget for ListSelect<M>
             items: items,
             num_items: num_items,
             mode: mode,
+            enabled: enabled,
             pending_events: PendingEvents::new(),
         };
 
@@ -299,6 +349,7 @@ impl<M> Events<M>
             num_items,
             ref mode,
             ref mut items,
+            enabled,
             ref mut pending_events,
         } = *self;
 
@@ -311,6 +362,11 @@ impl<M> Events<M>
             None => return None,
         };
 
+        // The items of a disabled `ListSelect` produce no events.
+        if !enabled {
+            return Some(Event::Item(item));
+        }
+
         // Borrow the `ListSelect::State` from the `Ui`'s widget graph.
         let state = || {
             ui.widget_graph()
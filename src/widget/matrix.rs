@@ -1,7 +1,8 @@
 //! A helper widget for laying out child widgets in the form of a grid.
 
-use {Scalar, Ui, UiCell, Widget};
+use {Rect, Scalar, Ui, UiCell, Widget};
 use graph;
+use std;
 use utils;
 use widget;
 
@@ -68,6 +69,18 @@ pub struct Elements {
     y_min: Scalar, y_max: Scalar,
 }
 
+/// The columns and rows of a `Matrix` whose cells are at least partially visible within some
+/// viewport.
+///
+/// Produced by the `visible_cells` function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisibleCells {
+    /// The range of visible column indices.
+    pub cols: std::ops::Range<ColNum>,
+    /// The range of visible row indices.
+    pub rows: std::ops::Range<RowNum>,
+}
+
 /// Data necessary for instantiating a widget for a single `Matrix` element.
 #[derive(Copy, Clone, Debug)]
 pub struct Element {
@@ -193,9 +206,52 @@ impl Elements {
 
     /// Yield the next `Element`.
     pub fn next(&mut self, ui: &Ui) -> Option<Element> {
+        let (r, c) = (self.row, self.col);
+        let element = match self.element(ui, c, r) {
+            Some(element) => element,
+            None => return None,
+        };
+
+        // Increment the elem indices.
+        self.row += 1;
+        if self.row >= self.num_rows {
+            self.row = 0;
+            self.col += 1;
+        }
+
+        Some(element)
+    }
+
+    /// Call `f` with each `Element` whose cell is at least partially visible within the given
+    /// `viewport`, skipping all others.
+    ///
+    /// The `viewport` should be described in the same coordinates as the `Matrix`'s `Rect` would
+    /// be if it were not scrolled, and `scroll_offset` is the distance by which the view has been
+    /// scrolled to the right and downwards respectively. See `visible_cells` for details.
+    ///
+    /// This is useful for virtualizing very large grids, where instantiating a widget for every
+    /// cell would be wasteful.
+    pub fn visible<F>(self, ui: &mut UiCell, scroll_offset: [Scalar; 2], viewport: Rect, mut f: F)
+        where F: FnMut(&mut UiCell, Element),
+    {
+        let matrix_rect = match ui.rect_of(self.matrix_id) {
+            Some(rect) => rect,
+            None => return,
+        };
+        let (cols, rows) = (self.num_cols, self.num_rows);
+        let visible = visible_cells(cols, rows, matrix_rect, scroll_offset, viewport);
+        for c in visible.cols {
+            for r in visible.rows.clone() {
+                if let Some(element) = self.element(ui, c, r) {
+                    f(ui, element);
+                }
+            }
+        }
+    }
+
+    /// Produce the `Element` for the cell at the given column and row.
+    fn element(&self, ui: &Ui, c: ColNum, r: RowNum) -> Option<Element> {
         let Elements {
-            ref mut row,
-            ref mut col,
             num_rows,
             num_cols,
             matrix_id,
@@ -203,11 +259,10 @@ impl Elements {
             elem_h,
             x_min, x_max,
             y_min, y_max,
+            ..
         } = *self;
 
-        let (r, c) = (*row, *col);
-
-        // Retrieve the `widget::Id` that was generated for the next `Element`.
+        // Retrieve the `widget::Id` that was generated for the `Element`.
         let widget_id = match ui.widget_graph().widget(matrix_id)
             .and_then(|container| container.unique_widget_state::<Matrix>())
             .and_then(|&graph::UniqueWidgetState { ref state, .. }| {
@@ -218,13 +273,6 @@ impl Elements {
             None => return None,
         };
 
-        // Increment the elem indices.
-        *row += 1;
-        if *row >= num_rows {
-            *row = 0;
-            *col += 1;
-        }
-
         let rel_x = utils::map_range(c as Scalar, 0.0, num_cols as Scalar, x_min, x_max);
         let rel_y = utils::map_range(r as Scalar, 0.0, num_rows as Scalar, y_max, y_min);
 
@@ -264,3 +312,83 @@ impl Element {
     }
 
 }
+
+
+/// Determine the columns and rows of a matrix whose cells are at least partially visible within
+/// the given `viewport`.
+///
+/// - `cols` and `rows` describe the size of the matrix.
+/// - `matrix` is the `Rect` occupied by the whole matrix when it is not scrolled.
+/// - `scroll_offset` is the distance by which the view has been scrolled to the right and
+///   downwards respectively, i.e. the matrix itself is shifted left and up by this amount.
+/// - `viewport` is the visible area, described in the same coordinates as the `matrix`.
+///
+/// Cells that are only partially visible at the edges of the viewport are included. Cells that
+/// merely touch the edge of the viewport are not.
+pub fn visible_cells(cols: usize,
+                     rows: usize,
+                     matrix: Rect,
+                     scroll_offset: [Scalar; 2],
+                     viewport: Rect) -> VisibleCells
+{
+    let empty = VisibleCells { cols: 0..0, rows: 0..0 };
+    if cols == 0 || rows == 0 {
+        return empty;
+    }
+
+    let matrix = matrix.shift([-scroll_offset[0], scroll_offset[1]]);
+    let overlap = match matrix.overlap(viewport) {
+        Some(overlap) if overlap.w() > 0.0 && overlap.h() > 0.0 => overlap,
+        _ => return empty,
+    };
+
+    let cell_w = matrix.w() / cols as Scalar;
+    let cell_h = matrix.h() / rows as Scalar;
+
+    // Columns are counted from the left of the matrix and rows from the top.
+    let index_range = |start: Scalar, end: Scalar, cell_len: Scalar, num: usize| {
+        let start = (start / cell_len).floor() as usize;
+        let end = (end / cell_len).ceil() as usize;
+        std::cmp::min(start, num)..std::cmp::min(end, num)
+    };
+    let cols = index_range(overlap.left() - matrix.left(), overlap.right() - matrix.left(),
+                           cell_w, cols);
+    let rows = index_range(matrix.top() - overlap.top(), matrix.top() - overlap.bottom(),
+                           cell_h, rows);
+
+    VisibleCells { cols: cols, rows: rows }
+}
+
+
+#[test]
+fn visible_cells_without_scrolling() {
+    // A 100x100 grid of 10x10 cells with its top-left corner at the origin.
+    let matrix = Rect::from_corners([0.0, 0.0], [1000.0, -1000.0]);
+    let viewport = Rect::from_corners([0.0, 0.0], [50.0, -30.0]);
+    let visible = visible_cells(100, 100, matrix, [0.0, 0.0], viewport);
+    assert_eq!(VisibleCells { cols: 0..5, rows: 0..3 }, visible);
+}
+
+#[test]
+fn visible_cells_should_follow_the_scroll_offset() {
+    let matrix = Rect::from_corners([0.0, 0.0], [1000.0, -1000.0]);
+    let viewport = Rect::from_corners([0.0, 0.0], [50.0, -30.0]);
+    let visible = visible_cells(100, 100, matrix, [200.0, 500.0], viewport);
+    assert_eq!(VisibleCells { cols: 20..25, rows: 50..53 }, visible);
+}
+
+#[test]
+fn visible_cells_should_include_partially_visible_edge_cells() {
+    let matrix = Rect::from_corners([0.0, 0.0], [1000.0, -1000.0]);
+    let viewport = Rect::from_corners([0.0, 0.0], [50.0, -30.0]);
+    let visible = visible_cells(100, 100, matrix, [15.0, 4.0], viewport);
+    assert_eq!(VisibleCells { cols: 1..7, rows: 0..4 }, visible);
+}
+
+#[test]
+fn visible_cells_should_be_empty_when_scrolled_out_of_view() {
+    let matrix = Rect::from_corners([0.0, 0.0], [100.0, -100.0]);
+    let viewport = Rect::from_corners([0.0, 0.0], [50.0, -30.0]);
+    let visible = visible_cells(10, 10, matrix, [100.0, 0.0], viewport);
+    assert_eq!(VisibleCells { cols: 0..0, rows: 0..0 }, visible);
+}
@@ -0,0 +1,459 @@
+//! A horizontal bar of menus, each opening a drop down list of items and nested submenus.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Labelable,
+    Positionable,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use event;
+use input;
+use text;
+use widget;
+
+
+/// A horizontal bar of menus, as found along the top of most desktop applications.
+///
+/// Clicking a menu within the bar opens its drop down list of items. While a menu is open,
+/// hovering over another menu within the bar opens that menu instead, and hovering over an item
+/// with children opens its submenu to the right of the item. If a submenu would extend beyond
+/// the right edge of the window, it is flipped to the left of its parent instead.
+///
+/// While the `MenuBar` (or one of its items) is capturing the keyboard, the open menus may be
+/// navigated via the arrow keys:
+///
+/// - `Up` and `Down` highlight the previous and next items within the innermost menu.
+/// - `Right` opens the highlighted item's submenu, or otherwise opens the next menu in the bar.
+/// - `Left` closes the innermost submenu, or otherwise opens the previous menu in the bar.
+/// - `Enter` or `Return` activates the highlighted item.
+/// - `Escape` closes the innermost submenu, or otherwise closes the menu.
+///
+/// Activating an item without children closes all menus.
+pub struct MenuBar<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    menus: &'a [Item],
+}
+
+/// A single menu or item within a `MenuBar`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item {
+    /// The label displayed for the item.
+    pub label: String,
+    /// The items within the item's submenu, in order of display.
+    pub children: Vec<Item>,
+}
+
+/// The indices leading from the menus within the bar to some item, e.g. `[1, 0]` is the first
+/// item within the second menu.
+pub type Path = Vec<usize>;
+
+widget_style! {
+    /// Unique styling for the `MenuBar`.
+    style Style {
+        /// The width of each menu within the bar.
+        - bar_item_w: Scalar { 80.0 }
+        /// The width of each drop down list of items.
+        - menu_w: Scalar { 150.0 }
+        /// The height of each item within the drop down lists.
+        - item_h: Scalar { 30.0 }
+        /// The color of the bar and of each item.
+        - color: Color { theme.shape_color }
+        /// The color of each item's label.
+        - label_color: Color { theme.label_color }
+        /// The font size of each item's label.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display each item's label.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        bar_items[],
+        menus[],
+        items[],
+    }
+}
+
+/// The state of the `MenuBar`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// The open menu followed by the highlighted item at each level of submenu, if any.
+    path: Path,
+}
+
+
+impl Item {
+
+    /// An item with the given label and no children.
+    pub fn new<S>(label: S) -> Self
+        where S: Into<String>,
+    {
+        Item {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Append the given item to the item's submenu.
+    pub fn child(mut self, child: Item) -> Self {
+        self.children.push(child);
+        self
+    }
+
+}
+
+/// Find the item at the given `path` within the given `menus`.
+pub fn item_at<'a>(menus: &'a [Item], path: &[usize]) -> Option<&'a Item> {
+    let mut items = menus;
+    let mut item = None;
+    for &i in path {
+        let it = match items.get(i) {
+            Some(it) => it,
+            None => return None,
+        };
+        items = &it.children;
+        item = Some(it);
+    }
+    item
+}
+
+/// Navigate the open menus described by the given `path` in response to the given key.
+///
+/// Returns the path to the activated item, if any, in which case the `path` is cleared.
+pub fn navigate(menus: &[Item], path: &mut Path, key: input::Key) -> Option<Path> {
+    let num_menus = menus.len();
+    let has_children = |path: &[usize]| item_at(menus, path).map_or(false, |item| {
+        !item.children.is_empty()
+    });
+    let depth = path.len();
+    if depth == 0 {
+        return None;
+    }
+    match key {
+        input::Key::Down => if depth == 1 {
+            if has_children(&path[..]) {
+                path.push(0);
+            }
+        } else {
+            let num_siblings = item_at(menus, &path[..depth - 1])
+                .map_or(0, |parent| parent.children.len());
+            if path[depth - 1] + 1 < num_siblings {
+                path[depth - 1] += 1;
+            }
+        },
+        input::Key::Up => if depth > 1 && path[depth - 1] > 0 {
+            path[depth - 1] -= 1;
+        },
+        input::Key::Right => if depth > 1 && has_children(&path[..]) {
+            path.push(0);
+        } else {
+            *path = vec![(path[0] + 1) % num_menus];
+        },
+        input::Key::Left => if depth > 2 {
+            path.pop();
+        } else {
+            *path = vec![(path[0] + num_menus - 1) % num_menus];
+        },
+        input::Key::Return | input::Key::NumPadEnter => if depth > 1 {
+            if has_children(&path[..]) {
+                path.push(0);
+            } else {
+                let activated = path.clone();
+                path.clear();
+                return Some(activated);
+            }
+        },
+        input::Key::Escape => if depth > 2 {
+            path.pop();
+        } else {
+            path.clear();
+        },
+        _ => (),
+    }
+    None
+}
+
+/// The left edge of a submenu of the given width, opened beside the given parent menu.
+///
+/// The submenu opens to the right of its parent, unless it would extend beyond the given right
+/// edge of the window, in which case it is flipped to the left of its parent.
+pub fn submenu_left(parent: Rect, menu_w: Scalar, window_right: Scalar) -> Scalar {
+    if parent.right() + menu_w > window_right {
+        parent.left() - menu_w
+    } else {
+        parent.right()
+    }
+}
+
+
+impl<'a> MenuBar<'a> {
+
+    /// Begin building a `MenuBar` displaying the given menus.
+    pub fn new(menus: &'a [Item]) -> Self {
+        MenuBar {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            menus: menus,
+        }
+    }
+
+    /// Specify the font used for displaying the labels.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub bar_item_w { style.bar_item_w = Some(Scalar) }
+        pub menu_w { style.menu_w = Some(Scalar) }
+        pub item_h { style.item_h = Some(Scalar) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a> Widget for MenuBar<'a> {
+    type State = State;
+    type Style = Style;
+    /// The path to the item activated during the update, if any.
+    type Event = Option<Path>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            path: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the MenuBar.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let MenuBar { menus, .. } = self;
+
+        let bar_item_w = style.bar_item_w(&ui.theme);
+        let menu_w = style.menu_w(&ui.theme);
+        let item_h = style.item_h(&ui.theme);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        let window_right = ui.window_dim()[0] / 2.0;
+
+        // The open menu and submenus, described by the first item and the items within each.
+        let open_menus = |path: &[usize]| {
+            let mut open = Vec::new();
+            for depth in 1..path.len() + 1 {
+                match item_at(menus, &path[..depth]) {
+                    Some(item) if !item.children.is_empty() => open.push(depth),
+                    _ => break,
+                }
+            }
+            open
+        };
+
+        if state.ids.bar_items.len() < menus.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.bar_items.resize(menus.len(), id_gen));
+        }
+
+        widget::Rectangle::fill(rect.dim())
+            .xy(rect.xy())
+            .color(color)
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.rectangle, ui);
+
+        let mut path = state.path.clone();
+        let mut activated = None;
+
+        // Open a menu by clicking it, or by hovering over it while another menu is open.
+        for (i, menu) in menus.iter().enumerate() {
+            let bar_item_id = state.ids.bar_items[i];
+            let is_open = state.path.first() == Some(&i);
+            let mut button = widget::Button::new()
+                .label(&menu.label)
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .color(if is_open { color.highlighted() } else { color })
+                .w_h(bar_item_w, rect.h())
+                .top_left_with_margins_on(id, 0.0, i as Scalar * bar_item_w)
+                .parent(id);
+            button.style.label_font_id = Some(font_id);
+            if button.set(bar_item_id, ui).was_clicked() {
+                path = if is_open {
+                    Vec::new()
+                } else if menu.children.is_empty() {
+                    activated = Some(vec![i]);
+                    Vec::new()
+                } else {
+                    vec![i]
+                };
+            } else if !state.path.is_empty() && !is_open
+                && ui.widget_input(bar_item_id).mouse().is_some()
+            {
+                path = vec![i];
+            }
+        }
+
+        // Instantiate the menu and each open submenu, highlighting the item along the path.
+        let open = path.clone();
+        let open_depths = open_menus(&open);
+        let num_open_items = open_depths.iter()
+            .filter_map(|&depth| item_at(menus, &open[..depth]))
+            .fold(0, |total, item| total + item.children.len());
+        if state.ids.menus.len() < open_depths.len() || state.ids.items.len() < num_open_items {
+            let id_gen = &mut ui.widget_id_generator();
+            let num_open_menus = open_depths.len();
+            state.update(|state| {
+                state.ids.menus.resize(num_open_menus, id_gen);
+                state.ids.items.resize(num_open_items, id_gen);
+            });
+        }
+
+        let mut menu_rects: Vec<Rect> = Vec::new();
+        let mut item_idx = 0;
+        for (level, depth) in open_depths.into_iter().enumerate() {
+            let items = match item_at(menus, &open[..depth]) {
+                Some(parent) => &parent.children,
+                None => break,
+            };
+            let menu_h = items.len() as Scalar * item_h;
+            let (left, top) = match menu_rects.last() {
+                // The menu opens beneath its item within the bar.
+                None => {
+                    let left = rect.left() + open[0] as Scalar * bar_item_w;
+                    let left = if left + menu_w > window_right { window_right - menu_w }
+                               else { left };
+                    (left, rect.bottom())
+                },
+                // Submenus open beside their item within the parent menu.
+                Some(parent) => {
+                    let top = parent.top() - open[depth - 1] as Scalar * item_h;
+                    (submenu_left(*parent, menu_w, window_right), top)
+                },
+            };
+            let menu_rect = Rect::from_corners([left, top - menu_h], [left + menu_w, top]);
+            let menu_id = state.ids.menus[level];
+            widget::Rectangle::fill(menu_rect.dim())
+                .xy(menu_rect.xy())
+                .color(color)
+                .floating(true)
+                .parent(id)
+                .set(menu_id, ui);
+            menu_rects.push(menu_rect);
+
+            for (i, item) in items.iter().enumerate() {
+                let item_id = state.ids.items[item_idx];
+                item_idx += 1;
+                let is_highlighted = open.get(depth) == Some(&i);
+                let mut button = widget::Button::new()
+                    .label(&item.label)
+                    .label_color(label_color)
+                    .label_font_size(font_size)
+                    .color(if is_highlighted { color.highlighted() } else { color })
+                    .w_h(menu_w, item_h)
+                    .x_y(menu_rect.x(), top - (i as Scalar + 0.5) * item_h)
+                    .parent(menu_id);
+                button.style.label_font_id = Some(font_id);
+                let mut item_path = open[..depth].to_vec();
+                item_path.push(i);
+                if button.set(item_id, ui).was_clicked() {
+                    if item.children.is_empty() {
+                        activated = Some(item_path);
+                        path = Vec::new();
+                    } else {
+                        path = item_path;
+                    }
+                } else if !is_highlighted && ui.widget_input(item_id).mouse().is_some() {
+                    path = item_path;
+                }
+            }
+        }
+
+        // Navigate the open menus while the bar or one of its items is capturing the keyboard.
+        let num_bar_items = menus.len();
+        let focus_ids = Some(id).into_iter()
+            .chain(state.ids.bar_items.iter().take(num_bar_items).cloned())
+            .chain(state.ids.items.iter().take(item_idx).cloned());
+        for focus_id in focus_ids {
+            for press in ui.widget_input(focus_id).presses().key() {
+                if let Some(path_to_item) = navigate(menus, &mut path, press.key) {
+                    activated = Some(path_to_item);
+                }
+            }
+        }
+
+        // Close the menus if a mouse button is pressed outside of the bar and the open menus.
+        let was_pressed_outside = !state.path.is_empty()
+            && ui.global_input().events().ui().any(|e| match *e {
+                event::Ui::Press(_, event::Press { button: event::Button::Mouse(_, xy), .. }) =>
+                    !rect.is_over(xy) && !menu_rects.iter().any(|r| r.is_over(xy)),
+                _ => false,
+            });
+        if was_pressed_outside && activated.is_none() {
+            path.clear();
+        }
+
+        if path != state.path {
+            state.update(|state| state.path = path);
+        }
+
+        activated
+    }
+
+}
+
+
+impl<'a> Colorable for MenuBar<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+#[test]
+fn navigate_should_move_between_items_menus_and_submenus() {
+    let menus = vec![
+        Item::new("File")
+            .child(Item::new("New"))
+            .child(Item::new("Open Recent").child(Item::new("a.txt")).child(Item::new("b.txt"))),
+        Item::new("Edit").child(Item::new("Undo")),
+    ];
+    let mut path = vec![0];
+    assert_eq!(None, navigate(&menus, &mut path, input::Key::Down));
+    assert_eq!(vec![0, 0], path);
+    navigate(&menus, &mut path, input::Key::Down);
+    navigate(&menus, &mut path, input::Key::Down);
+    assert_eq!(vec![0, 1], path);
+    navigate(&menus, &mut path, input::Key::Right);
+    assert_eq!(vec![0, 1, 0], path);
+    navigate(&menus, &mut path, input::Key::Left);
+    assert_eq!(vec![0, 1], path);
+    navigate(&menus, &mut path, input::Key::Left);
+    assert_eq!(vec![1], path);
+    navigate(&menus, &mut path, input::Key::Right);
+    assert_eq!(vec![0], path);
+    let mut path = vec![0, 1, 1];
+    assert_eq!(Some(vec![0, 1, 1]), navigate(&menus, &mut path, input::Key::Return));
+    assert!(path.is_empty());
+}
@@ -3,8 +3,10 @@
 //! This module contains items related to the implementation of the `Widget` trait. It also
 //! re-exports all widgets (and their modules) that are provided by conrod.
 
+use cursor::MouseCursor;
 use graph;
-use position::{Align, Depth, Dimension, Dimensions, Padding, Position, Positionable, Rect, Sizeable};
+use position::{self, Align, Depth, Dimension, Dimensions, Padding, Position, Positionable, Rect,
+               Scalar, Sizeable};
 use std;
 use text::font;
 use theme::{self, Theme};
@@ -12,6 +14,7 @@ use ui::{self, Ui, UiCell};
 
 
 pub use self::id::Id;
+pub use self::style::resolve;
 //pub use self::index::Index;
 
 pub use self::primitive::line::{self, Line};
@@ -23,25 +26,45 @@ pub use self::primitive::shape::polygon::{self, Polygon};
 pub use self::primitive::shape::rectangle::{self, Rectangle};
 pub use self::primitive::text::{self, Text};
 
+pub use self::autocomplete::Autocomplete;
 pub use self::bordered_rectangle::BorderedRectangle;
+pub use self::breadcrumbs::Breadcrumbs;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
+pub use self::chart::Chart;
+pub use self::collapsible::Collapsible;
+pub use self::color_picker::ColorPicker;
+pub use self::context_menu::ContextMenu;
 pub use self::drop_down_list::DropDownList;
 pub use self::list_select::ListSelect;
 pub use self::envelope_editor::EnvelopeEditor;
 pub use self::file_navigator::FileNavigator;
+pub use self::flow::Flow;
 pub use self::list::List;
 pub use self::matrix::Matrix;
+pub use self::menu_bar::MenuBar;
+pub use self::modal::Modal;
 pub use self::number_dialer::NumberDialer;
+pub use self::numeric_field::NumericField;
 pub use self::plot_path::PlotPath;
+pub use self::progress_bar::ProgressBar;
+pub use self::radio_group::RadioGroup;
 pub use self::range_slider::RangeSlider;
+pub use self::rich_text::RichText;
 pub use self::scrollbar::Scrollbar;
 pub use self::slider::Slider;
+pub use self::spinner::Spinner;
+pub use self::split_pane::SplitPane;
+pub use self::table::Table;
 pub use self::tabs::Tabs;
 pub use self::text_box::TextBox;
 pub use self::text_edit::TextEdit;
 pub use self::title_bar::TitleBar;
+pub use self::toasts::Toasts;
 pub use self::toggle::Toggle;
+pub use self::tooltip::Tooltip;
+pub use self::tree_view::TreeView;
+pub use self::window::Window;
 pub use self::xy_pad::XYPad;
 
 
@@ -57,25 +80,45 @@ pub mod scroll;
 pub mod primitive;
 
 // Widget modules.
+pub mod autocomplete;
+pub mod breadcrumbs;
 pub mod button;
 pub mod canvas;
+pub mod chart;
+pub mod collapsible;
+pub mod color_picker;
+pub mod context_menu;
 pub mod drop_down_list;
 pub mod list_select;
 pub mod envelope_editor;
 pub mod file_navigator;
+pub mod flow;
 pub mod bordered_rectangle;
 pub mod list;
 pub mod matrix;
+pub mod menu_bar;
+pub mod modal;
 pub mod number_dialer;
+pub mod numeric_field;
 pub mod plot_path;
+pub mod progress_bar;
+pub mod radio_group;
 pub mod range_slider;
+pub mod rich_text;
 pub mod scrollbar;
 pub mod slider;
+pub mod spinner;
+pub mod split_pane;
+pub mod table;
 pub mod tabs;
 pub mod text_box;
 pub mod text_edit;
 pub mod title_bar;
+pub mod toasts;
 pub mod toggle;
+pub mod tooltip;
+pub mod tree_view;
+pub mod window;
 pub mod xy_pad;
 
 
@@ -202,6 +245,13 @@ pub struct CommonBuilder {
     /// By default, the kid_area is the size of the entire widget, though it may be specified
     /// otherwise via the `Widget::kid_area` method.
     pub crop_kids: bool,
+    /// The shape that the mouse cursor should take while over or captured by the **Widget**.
+    pub maybe_cursor_hint: Option<MouseCursor>,
+    /// The text that the **Ui** displays within a `Tooltip` after the mouse hovers over the
+    /// **Widget** for the theme's `tooltip_delay`.
+    pub maybe_tooltip: Option<&'static str>,
+    /// Whether or not the **Widget** consumes the mouse events that occur over it.
+    pub consumes_events: bool,
     /// Arguments to the scrolling of the widget's *x* axis.
     pub maybe_x_scroll: Option<scroll::Scroll>,
     /// Arguments to the scrolling of the widget's *y* axis.
@@ -242,6 +292,8 @@ pub struct CommonStyle {
     pub maybe_y_position: Option<Position>,
     /// The rendering Depth of the Widget.
     pub maybe_depth: Option<Depth>,
+    /// The step of the grid to which the Widget's position is snapped.
+    pub maybe_grid_step: Option<Scalar>,
 }
 
 /// A wrapper around a **Widget**'s unique **Widget::State**.
@@ -309,6 +361,12 @@ pub struct PreUpdateCache {
     pub maybe_floating: Option<Floating>,
     /// Whether or not the children of the **Widget** should be cropped to its `kid_area`.
     pub crop_kids: bool,
+    /// The shape that the mouse cursor should take while over or captured by the **Widget**.
+    pub maybe_cursor_hint: Option<MouseCursor>,
+    /// The text displayed within a `Tooltip` while the mouse hovers over the **Widget**.
+    pub maybe_tooltip: Option<&'static str>,
+    /// Whether or not the **Widget** consumes the mouse events that occur over it.
+    pub consumes_events: bool,
     /// Scrolling data for the **Widget**'s *x* axis if there is some.
     pub maybe_x_scroll_state: Option<scroll::StateX>,
     /// Scrolling data for the **Widget**'s *y* axis if there is some.
@@ -612,6 +670,16 @@ pub trait Widget: Sized {
         }
     }
 
+    /// The shape that the mouse cursor should take while the mouse is over the widget or while the
+    /// widget is capturing the mouse.
+    ///
+    /// By default, this returns the hint given via the `mouse_cursor` builder method. Widgets that
+    /// would like a particular cursor shape by default (i.e. a text I-beam for text editing) may
+    /// override this.
+    fn cursor_hint(&self) -> Option<MouseCursor> {
+        self.common().maybe_cursor_hint
+    }
+
 
     // None of the following methods should require overriding. Perhaps they should be split off
     // into a separate trait which is impl'ed for W: Widget to make this clearer?
@@ -682,11 +750,52 @@ pub trait Widget: Sized {
 
     /// Indicates that all widgets who are children of this widget should be cropped to the
     /// `kid_area` of this widget.
+    ///
+    /// The crop applies to all descendants, not only to direct children. When cropping widgets
+    /// are nested, descendants are cropped to the intersection of every cropping ancestor's
+    /// `kid_area`, so a nested crop can only ever shrink the visible area.
     fn crop_kids(mut self) -> Self {
         self.common_mut().crop_kids = true;
         self
     }
 
+    /// Request that the mouse cursor take the given shape while over the widget or while the
+    /// widget is capturing the mouse.
+    ///
+    /// See the `Ui::mouse_cursor` method for how the hints of all widgets are resolved.
+    fn mouse_cursor(mut self, cursor: MouseCursor) -> Self {
+        self.common_mut().maybe_cursor_hint = Some(cursor);
+        self
+    }
+
+    /// Describe the widget with the given text, which the `Ui` displays within a `Tooltip` once
+    /// the mouse has hovered over the widget for the theme's `tooltip_delay`.
+    ///
+    /// The tooltip is shown regardless of whether or not the widget accepts input, so that a
+    /// disabled widget may explain why it is disabled.
+    ///
+    /// Text that is not `'static` (e.g. text formatted from the widget's value) may be given via
+    /// `UiCell::set_widget_tooltip` instead.
+    fn tooltip(mut self, text: &'static str) -> Self {
+        self.common_mut().maybe_tooltip = Some(text);
+        self
+    }
+
+    /// Set whether or not the widget consumes the mouse events that occur over it (the default is
+    /// `true`).
+    ///
+    /// Mouse events are routed to the topmost widget under the mouse that consumes events, so
+    /// widgets beneath it never see them.
+    ///
+    /// A widget that does not consume events is skipped when routing, allowing events to pass
+    /// through to the widgets beneath it. Such a widget becomes an *observer*: its `input::Widget`
+    /// yields every event regardless of which widget it was routed to. This is useful for
+    /// overlays that only need to watch input, such as a logger or a tooltip.
+    fn consume_events(mut self, consume: bool) -> Self {
+        self.common_mut().consumes_events = consume;
+        self
+    }
+
     /// Makes the widget's `KidArea` scrollable.
     ///
     /// If a widget is scrollable and it has children widgets that fall outside of its `KidArea`,
@@ -896,6 +1005,12 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         // A function for generating the xy coords from the given alignment and Position.
         .unwrap_or_else(|| ui.calc_xy(Some(id), x_pos, y_pos, dim, place_on_kid_area));
 
+    // Snap the `xy` to the widget's grid if it has one.
+    let xy = match widget.get_grid_step() {
+        Some(step) => [position::snap_to_grid(xy[0], step), position::snap_to_grid(xy[1], step)],
+        None => xy,
+    };
+
     // Construct the rectangle describing our Widget's area.
     let rect = Rect::from_xy_dim(xy, dim);
 
@@ -997,6 +1112,15 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         // Retrieve whether or not the widget's children should be cropped to it.
         let crop_kids = widget.common().crop_kids;
 
+        // Retrieve the shape that the mouse cursor should take over the widget (if any).
+        let maybe_cursor_hint = widget.cursor_hint();
+
+        // Retrieve the text that describes the widget when hovered (if any).
+        let maybe_tooltip = widget.common().maybe_tooltip;
+
+        // Retrieve whether or not the widget consumes the mouse events that occur over it.
+        let consumes_events = widget.common().consumes_events;
+
         // This will cache the given data into the `ui`'s `widget_graph`.
         let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
         ui::pre_update_cache(ui, PreUpdateCache {
@@ -1010,6 +1134,9 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
             kid_area: kid_area,
             maybe_floating: maybe_floating,
             crop_kids: crop_kids,
+            maybe_cursor_hint: maybe_cursor_hint,
+            maybe_tooltip: maybe_tooltip,
+            consumes_events: consumes_events,
             maybe_y_scroll_state: maybe_y_scroll_state,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_graphics_for: widget.common().maybe_graphics_for,
@@ -1066,8 +1193,10 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         || depth != prev_common.depth
         || is_first_set;
 
-    // Determine whether or not the widget's `Style` has changed.
-    let style_has_changed = maybe_prev_style.map(|style| style != new_style).unwrap_or(false);
+    // Determine whether or not the widget's `Style` has changed. As each `Style` field falls back
+    // to the `Theme`, replacing the `Theme` may also change the widget's appearance.
+    let style_has_changed = maybe_prev_style.map(|style| style != new_style).unwrap_or(false)
+        || ui.theme_has_changed();
 
     // We need to know if the scroll state has changed to see if we need to redraw.
     let scroll_has_changed =
@@ -1084,6 +1213,11 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
         ui.needs_redraw();
     }
 
+    // The widget has had the chance to handle all events routed to it.
+    if ui.global_input.timestamp_events {
+        ui.global_input.handle_events_for(id);
+    }
+
     // Finally, cache the `Widget`'s newly updated `State` and `Style` within the `ui`'s
     // `widget_graph`.
     ui::post_update_cache::<W>(ui, PostUpdateCache {
@@ -1134,6 +1268,9 @@ impl CommonBuilder {
             maybe_x_scroll: None,
             maybe_y_scroll: None,
             crop_kids: false,
+            maybe_cursor_hint: None,
+            maybe_tooltip: None,
+            consumes_events: true,
         }
     }
 }
@@ -1147,6 +1284,7 @@ impl CommonStyle {
             maybe_x_position: None,
             maybe_y_position: None,
             maybe_depth: None,
+            maybe_grid_step: None,
         }
     }
 }
@@ -1185,6 +1323,16 @@ impl<W> Positionable for W
         const DEFAULT_DEPTH: Depth = 0.0;
         self.common().style.maybe_depth.unwrap_or(DEFAULT_DEPTH)
     }
+    fn snap_to_grid(mut self, step: Scalar) -> Self {
+        self.common_mut().style.maybe_grid_step = Some(step);
+        self
+    }
+    fn get_grid_step(&self) -> Option<Scalar> {
+        self.common().style.maybe_grid_step.and_then(|step| match step > 0.0 {
+            true => Some(step),
+            false => None,
+        })
+    }
 }
 
 
@@ -1197,7 +1345,7 @@ fn infer_position_from_other_position(other_pos: Position, dir_align: Align) ->
         Position::Direction(_, _, maybe_id) => Some(Position::Align(dir_align, maybe_id)),
         Position::Place(_, maybe_id) => Some(Position::Align(Align::Middle, maybe_id)),
         Position::Relative(_, maybe_id) => Some(Position::Relative(0.0, maybe_id)),
-        Position::Align(_, _) | Position::Absolute(_) => None,
+        Position::Align(_, _) | Position::Absolute(_) | Position::Anchor(_, _, _) => None,
     }
 }
 
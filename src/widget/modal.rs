@@ -0,0 +1,195 @@
+//! A full-window overlay that dims the background and captures all input while a dialog is open.
+
+use {
+    color,
+    Color,
+    Colorable,
+    Dimension,
+    Place,
+    Position,
+    Positionable,
+    Ui,
+    Widget,
+};
+use event;
+use graph::Graph;
+use input;
+use ui;
+use widget;
+
+
+/// A full-window overlay for displaying dialogs.
+///
+/// The `Modal` covers the window with a translucent *scrim*, rendering above all other widgets as
+/// it is floating. While the `Modal` is instantiated:
+///
+/// - The scrim sits between the background and the dialog, so background widgets cannot be
+/// clicked.
+/// - Widgets outside of the `Modal` may not capture the mouse or keyboard at all, even via
+/// `Ui::focus_widget` (see `Ui::is_blocked_by_modal`).
+/// - The keyboard is captured by the `Modal` unless it is already captured by one of its
+/// descendants.
+///
+/// `Modal`s may be nested, in which case only the top-most one (see `Ui::top_modal`) captures the
+/// keyboard and may be dismissed. Once a `Modal` is no longer instantiated, the keyboard is
+/// returned to the widget that was capturing it when the `Modal` opened.
+///
+/// The dialog's widgets should be instantiated as children of the `Modal` after it is set.
+///
+/// Like the `Collapsible`, the `Modal` does not store whether or not it is open. The user should
+/// stop instantiating it upon receiving some `Dismissal`.
+pub struct Modal {
+    common: widget::CommonBuilder,
+    style: Style,
+    dismiss_on_scrim_click: bool,
+}
+
+widget_style! {
+    /// Unique styling for the `Modal`.
+    style Style {
+        /// The color of the scrim that dims the background.
+        - color: Color { color::BLACK.alpha(0.5) }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        scrim,
+    }
+}
+
+/// The state of the `Modal`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+/// The manner in which a `Modal` was dismissed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dismissal {
+    /// The `Escape` key was pressed while the keyboard was captured by the `Modal` or one of its
+    /// descendants.
+    Escape,
+    /// The scrim surrounding the dialog was clicked.
+    ///
+    /// Only produced if `dismiss_on_scrim_click` is enabled.
+    ScrimClick,
+}
+
+
+impl Modal {
+
+    /// Begin building a `Modal` covering the whole window.
+    pub fn new() -> Self {
+        let mut common = widget::CommonBuilder::new();
+        common.is_floating = true;
+        Modal {
+            common: common,
+            style: Style::new(),
+            dismiss_on_scrim_click: false,
+        }
+    }
+
+    /// Whether or not clicking the scrim surrounding the dialog dismisses the `Modal`.
+    ///
+    /// By default, only the `Escape` key dismisses the `Modal`.
+    pub fn dismiss_on_scrim_click(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_scrim_click = dismiss;
+        self
+    }
+
+}
+
+
+impl Widget for Modal {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Dismissal>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_position(&self, _ui: &Ui) -> Position {
+        Position::Place(Place::Middle, None)
+    }
+
+    fn default_y_position(&self, _ui: &Ui) -> Position {
+        Position::Place(Place::Middle, None)
+    }
+
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::Absolute(ui.win_w)
+    }
+
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::Absolute(ui.win_h)
+    }
+
+    /// Update the state of the Modal.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+
+        // Block input to all widgets outside of the `Modal` for as long as it is instantiated.
+        ui::open_modal(ui::ref_mut_from_ui_cell(ui), id);
+        let is_top_modal = ui.top_modal() == Some(id);
+
+        // The scrim is a graphic of the `Modal`, so that clicking it is a click on the `Modal`.
+        widget::Rectangle::fill(rect.dim())
+            .middle_of(id)
+            .graphics_for(id)
+            .color(style.color(&ui.theme))
+            .set(state.ids.scrim, ui);
+
+        let escape_was_pressed = ui.global_input().events().ui().any(|event| match *event {
+            event::Ui::Press(Some(widget), press) => match press.key() {
+                Some(press) =>
+                    press.key == input::Key::Escape && is_within(ui.widget_graph(), id, widget),
+                None => false,
+            },
+            _ => false,
+        });
+
+        // Take the keyboard from any background widget so that it cannot receive key presses.
+        let is_capturing_keyboard = ui.global_input().current.widget_capturing_keyboard
+            .map_or(false, |widget| is_within(ui.widget_graph(), id, widget));
+        if is_top_modal && !is_capturing_keyboard {
+            ui.focus_widget(id);
+        }
+
+        // Only the top-most of any nested `Modal`s is dismissed by `Escape`.
+        if is_top_modal && escape_was_pressed {
+            Some(Dismissal::Escape)
+        } else if self.dismiss_on_scrim_click
+        && ui.widget_input(id).clicks().left().next().is_some() {
+            Some(Dismissal::ScrimClick)
+        } else {
+            None
+        }
+    }
+
+}
+
+
+impl Colorable for Modal {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Whether the given `widget` is the `modal` or one of its descendants.
+fn is_within(graph: &Graph, modal: widget::Id, widget: widget::Id) -> bool {
+    widget == modal || graph.does_recursive_depth_edge_exist(modal, widget)
+}
@@ -15,6 +15,7 @@ use num::{Float, NumCast};
 use std::cmp::Ordering;
 use std::iter::repeat;
 use text;
+use undo::UndoStack;
 use utils::clamp;
 use widget;
 
@@ -23,6 +24,9 @@ use widget;
 ///
 /// The reaction is triggered when the value is updated or if the mouse button is released while
 /// the cursor is above the widget.
+///
+/// Once pressed, `Ctrl+z` and `Ctrl+y` undo and redo changes to the value, with each drag undone
+/// as a single step.
 pub struct NumberDialer<'a, T> {
     common: widget::CommonBuilder,
     value: T,
@@ -67,6 +71,8 @@ pub struct State {
     pressed_value_idx: Option<usize>,
     ids: Ids,
     glyph_slot_indices: Vec<GlyphSlot>,
+    /// The value prior to each drag, for undoing and redoing via `Ctrl+z`/`Ctrl+y`.
+    undo_stack: UndoStack<f64>,
 }
 
 /// Each digit in the adjustable value has its own **Rectangle** and **Text** widgets.
@@ -173,6 +179,7 @@ impl<'a, T> Widget for NumberDialer<'a, T>
             pressed_value_idx: None,
             ids: Ids::new(id_gen),
             glyph_slot_indices: Vec::new(),
+            undo_stack: UndoStack::new(),
         }
     }
 
@@ -183,7 +190,12 @@ impl<'a, T> Widget for NumberDialer<'a, T>
     /// Update the state of the NumberDialer.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, mut ui, .. } = args;
-        let NumberDialer { value, min, max, precision, maybe_label, .. } = self;
+        let NumberDialer { value, min, max, precision, maybe_label, enabled, .. } = self;
+
+        // A disabled dialer relinquishes any capture that it held and ignores all presses.
+        if !enabled {
+            ui.release_captures(id);
+        }
 
         let rel_rect = rect.relative_to(rect.xy());
         let border = style.border(ui.theme());
@@ -238,7 +250,7 @@ impl<'a, T> Widget for NumberDialer<'a, T>
 
         let value_under_mouse = ui.widget_input(id).mouse()
             .and_then(|m| value_under_rel_xy(m.rel_xy()));
-        let mut pressed_value_idx = state.pressed_value_idx;
+        let mut pressed_value_idx = if enabled { state.pressed_value_idx } else { None };
         let mut new_value = value;
 
         // Check for the following events:
@@ -252,16 +264,41 @@ impl<'a, T> Widget for NumberDialer<'a, T>
             match widget_event {
 
                 // Check to see if a value was pressed in case it is later dragged.
-                event::Widget::Press(press) => {
-                    if let event::Button::Mouse(MouseButton::Left, _) = press.button {
+                event::Widget::Press(press) if enabled => match press.button {
+                    event::Button::Mouse(MouseButton::Left, _) => {
                         pressed_value_idx = value_under_mouse;
-                    }
+                    },
+
+                    // Undo the most recent change on Ctrl+z, or redo the last undone change on
+                    // Ctrl+y.
+                    event::Button::Keyboard(key) => {
+                        if !press.modifiers.contains(input::keyboard::CTRL) {
+                            continue 'events;
+                        }
+                        let current: f64 = NumCast::from(new_value).unwrap();
+                        let mut restored = None;
+                        match key {
+                            input::Key::Z => state.update(|state| {
+                                restored = state.undo_stack.undo(current);
+                            }),
+                            input::Key::Y => state.update(|state| {
+                                restored = state.undo_stack.redo(current);
+                            }),
+                            _ => continue 'events,
+                        }
+                        if let Some(restored) = restored {
+                            new_value = clamp(NumCast::from(restored).unwrap(), min, max);
+                        }
+                    },
+
+                    _ => (),
                 },
 
                 // Check to see if a value was released in case it is later dragged.
                 event::Widget::Release(release) => {
                     if let event::Button::Mouse(MouseButton::Left, _) = release.button {
                         pressed_value_idx = None;
+                        state.update(|state| state.undo_stack.break_coalescing());
                     }
                 },
 
@@ -300,6 +337,9 @@ impl<'a, T> Widget for NumberDialer<'a, T>
                                 }
                             },
                         };
+                        if new_val_f != val_f {
+                            state.update(|state| state.undo_stack.push_coalesced(val_f));
+                        }
                         new_value = NumCast::from(new_val_f).unwrap();
                     }
                 },
@@ -316,7 +356,7 @@ impl<'a, T> Widget for NumberDialer<'a, T>
         }
 
         // The **Rectangle** backdrop widget.
-        let color = style.color(ui.theme());
+        let color = if enabled { style.color(ui.theme()) } else { ui.theme.disabled_color };
         let border = style.border(ui.theme());
         let border_color = style.border_color(ui.theme());
         widget::BorderedRectangle::new(rect.dim())
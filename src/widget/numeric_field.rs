@@ -0,0 +1,381 @@
+//! A one-line field of text for entering a validated number.
+
+use {
+    Align,
+    Color,
+    Colorable,
+    FontSize,
+    Borderable,
+    MouseCursor,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use input;
+use text;
+use undo::UndoStack;
+use widget;
+
+/// A one-line field of text for entering a number, given by the user as an `f64`.
+///
+/// While the field is not being edited, the value is displayed with a fixed number of decimal
+/// places and its integer digits grouped by a thousands separator, e.g. `1,234.50`.
+///
+/// While editing, only keystrokes that keep the text a valid (possibly partial) number are
+/// accepted. Partial input such as `-` or `1.` is allowed so that numbers may be typed one
+/// character at a time. The widget produces the parsed value whenever the text is edited into a
+/// different, complete number.
+///
+/// While editing, `Ctrl+z` and `Ctrl+y` undo and redo changes to the value. All values entered
+/// during a single edit of the field are undone together.
+pub struct NumericField {
+    common: widget::CommonBuilder,
+    value: f64,
+    decimal_places: usize,
+    thousands_separator: Option<char>,
+    style: Style,
+    /// Whether or not user input is enabled.
+    enabled: bool,
+}
+
+widget_style!{
+    /// Unique graphical styling for the NumericField.
+    style Style {
+        /// The length of the gap between the bounding rectangle's border and the edge of the text.
+        - text_padding: Scalar { 5.0 }
+        /// Color of the rectangle behind the text.
+        - color: Color { theme.shape_color }
+        /// The width of the bounding `BorderedRectangle` border.
+        - border: Scalar { theme.border_width }
+        /// The color of the `BorderedRecangle`'s border.
+        - border_color: Color { theme.border_color }
+        /// The color of the text.
+        - text_color: Color { theme.label_color }
+        /// The font size for the text.
+        - font_size: FontSize { theme.font_size_medium }
+        /// The horizontal alignment of the text.
+        - x_align: Align { Align::End }
+        /// The font used for the `Text`.
+        - font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        text_edit,
+        rectangle,
+    }
+}
+
+/// The `State` of the `NumericField` widget that will be cached within the `Ui`.
+pub struct State {
+    ids: Ids,
+    /// The text being edited, while the field is capturing the keyboard.
+    editing: Option<String>,
+    /// The value prior to each edit, for undoing and redoing via `Ctrl+z`/`Ctrl+y`.
+    undo_stack: UndoStack<f64>,
+}
+
+/// The number of decimal places displayed by default.
+pub const DEFAULT_DECIMAL_PLACES: usize = 2;
+
+
+impl NumericField {
+
+    /// Construct a NumericField widget displaying the given value.
+    pub fn new(value: f64) -> Self {
+        NumericField {
+            common: widget::CommonBuilder::new(),
+            value: value,
+            decimal_places: DEFAULT_DECIMAL_PLACES,
+            thousands_separator: Some(','),
+            style: Style::new(),
+            enabled: true,
+        }
+    }
+
+    /// The number of decimal places with which the value is displayed.
+    ///
+    /// Keystrokes that would exceed this number of decimal places are rejected. If this is `0`,
+    /// only integers may be entered.
+    ///
+    /// By default this is `DEFAULT_DECIMAL_PLACES`.
+    pub fn decimal_places(mut self, decimal_places: usize) -> Self {
+        self.decimal_places = decimal_places;
+        self
+    }
+
+    /// The character used to group the integer digits of the displayed value into thousands.
+    ///
+    /// By default this is `Some(',')`.
+    pub fn thousands_separator(mut self, separator: Option<char>) -> Self {
+        self.thousands_separator = separator;
+        self
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub x_align_text { style.x_align = Some(Align) }
+        pub pad_text { style.text_padding = Some(Scalar) }
+        pub enabled { enabled = bool }
+    }
+
+}
+
+impl Widget for NumericField {
+    type State = State;
+    type Style = Style;
+    /// The newly entered value, if the text was edited into a different, complete number.
+    type Event = Option<f64>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            editing: None,
+            undo_stack: UndoStack::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn cursor_hint(&self) -> Option<MouseCursor> {
+        self.common.maybe_cursor_hint.or(Some(MouseCursor::Text))
+    }
+
+    /// Update the state of the NumericField.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let NumericField { value, decimal_places, thousands_separator, enabled, .. } = self;
+
+        // A disabled field ends any edit in progress by relinquishing the captures held by both
+        // the field and its `TextEdit`, which then ignores all input and is dimmed.
+        if !enabled {
+            ui.release_captures(id);
+            ui.release_captures(state.ids.text_edit);
+        }
+
+        let font_size = style.font_size(ui.theme());
+        let border = style.border(ui.theme());
+        let text_padding = style.text_padding(ui.theme());
+        let x_align = style.x_align(ui.theme());
+
+        let text_rect = {
+            let w = rect.x.pad(border + text_padding).len();
+            let h = font_size as Scalar + 1.0;
+            let x = Range::new(0.0, w).align_middle_of(rect.x);
+            let y = Range::new(0.0, h).align_middle_of(rect.y);
+            Rect { x: x, y: y }
+        };
+
+        let color = style.color(ui.theme());
+        let border_color = style.border_color(ui.theme());
+        widget::BorderedRectangle::new(rect.dim())
+            .xy(rect.xy())
+            .graphics_for(id)
+            .parent(id)
+            .border(border)
+            .color(color)
+            .border_color(border_color)
+            .set(state.ids.rectangle, ui);
+
+        // The value is edited without separators from the moment the field captures the keyboard
+        // until it is released.
+        let is_editing = ui.global_input().current.widget_capturing_keyboard
+            == Some(state.ids.text_edit);
+        match (is_editing, state.editing.is_some()) {
+            (true, false) => {
+                let editing = format(value, decimal_places, None);
+                state.update(|state| state.editing = Some(editing));
+            },
+            (false, true) => state.update(|state| {
+                state.editing = None;
+                state.undo_stack.break_coalescing();
+            }),
+            _ => (),
+        }
+
+        // Undo or redo changes to the value on `Ctrl+z`/`Ctrl+y`, in place of the `TextEdit`'s
+        // own history of the text.
+        let mut restored = None;
+        if is_editing {
+            for press in ui.widget_input(state.ids.text_edit).presses().key() {
+                if !press.modifiers.contains(input::keyboard::CTRL) {
+                    continue;
+                }
+                let current = restored.unwrap_or(value);
+                let mut maybe_value = None;
+                match press.key {
+                    input::Key::Z => state.update(|state| {
+                        maybe_value = state.undo_stack.undo(current);
+                    }),
+                    input::Key::Y => state.update(|state| {
+                        maybe_value = state.undo_stack.redo(current);
+                    }),
+                    _ => continue,
+                }
+                restored = maybe_value.or(restored);
+            }
+        }
+        if let Some(restored) = restored {
+            let editing = format(restored, decimal_places, None);
+            state.update(|state| state.editing = Some(editing));
+        }
+
+        let displayed = match state.editing {
+            Some(ref editing) => editing.clone(),
+            None => format(value, decimal_places, thousands_separator),
+        };
+
+        let text_color = style.text_color(ui.theme());
+        let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
+        let maybe_new_text = widget::TextEdit::new(&displayed)
+            .and_then(font_id, widget::TextEdit::font_id)
+            .wh(text_rect.dim())
+            .xy(text_rect.xy())
+            .font_size(font_size)
+            .color(text_color)
+            .x_align_text(x_align)
+            .enabled(enabled)
+            .parent(id)
+            .set(state.ids.text_edit, ui);
+
+        if restored.is_some() {
+            return restored;
+        }
+
+        // Reject any edit that would leave the text an invalid number.
+        let new_text = match maybe_new_text {
+            Some(ref new_text) if is_editing && is_valid_partial(new_text, decimal_places) =>
+                new_text.clone(),
+            _ => return None,
+        };
+        state.update(|state| state.editing = Some(new_text.clone()));
+
+        match parse(&new_text) {
+            Some(new_value) if new_value != value => {
+                state.update(|state| state.undo_stack.push_coalesced(value));
+                Some(new_value)
+            },
+            _ => None,
+        }
+    }
+
+}
+
+impl Borderable for NumericField {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
+
+impl Colorable for NumericField {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Format the given value with the given number of decimal places, grouping the integer digits
+/// into thousands via the given separator (if any).
+pub fn format(value: f64, decimal_places: usize, thousands_separator: Option<char>) -> String {
+    let digits = format!("{:.*}", decimal_places, value.abs());
+    let (integer, fraction) = match digits.find('.') {
+        Some(idx) => digits.split_at(idx),
+        None => (&digits[..], ""),
+    };
+
+    let mut formatted = String::with_capacity(digits.len() * 2);
+    // Avoid displaying a negative sign for values that round to zero.
+    if value < 0.0 && digits.chars().any(|c| c != '0' && c != '.') {
+        formatted.push('-');
+    }
+    let num_integer_digits = integer.len();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (num_integer_digits - i) % 3 == 0 {
+            if let Some(separator) = thousands_separator {
+                formatted.push(separator);
+            }
+        }
+        formatted.push(digit);
+    }
+    formatted.push_str(fraction);
+    formatted
+}
+
+/// Whether or not the given text is a number, or the start of one, with no more than the given
+/// number of decimal places.
+///
+/// This accepts an optional leading `-`, followed by digits and at most one `.` if
+/// `decimal_places` is greater than `0`. Partial input such as `""`, `"-"` and `"1."` is valid.
+pub fn is_valid_partial(text: &str, decimal_places: usize) -> bool {
+    let unsigned = if text.starts_with('-') { &text[1..] } else { text };
+    let mut parts = unsigned.splitn(2, '.');
+    let integer = parts.next().unwrap_or("");
+    let is_digits = |s: &str| s.chars().all(|c| c.is_digit(10));
+    match parts.next() {
+        None => is_digits(integer),
+        Some(fraction) =>
+            decimal_places > 0
+            && is_digits(integer)
+            && is_digits(fraction)
+            && fraction.len() <= decimal_places,
+    }
+}
+
+/// Parse the value of the given text, ignoring any `,` thousands separators.
+///
+/// Returns `None` if the text is not a complete number, e.g. `"-"` or `"."`.
+pub fn parse(text: &str) -> Option<f64> {
+    let digits: String = text.chars().filter(|&c| c != ',').collect();
+    digits.parse().ok()
+}
+
+
+#[test]
+fn format_should_group_thousands_and_round_to_the_decimal_places() {
+    assert_eq!("1,234.50", format(1234.5, 2, Some(',')));
+    assert_eq!("1234.50", format(1234.5, 2, None));
+    assert_eq!("-1,234,568", format(-1234567.8, 0, Some(',')));
+    assert_eq!("999", format(999.0, 0, Some(',')));
+    assert_eq!("0.0", format(-0.01, 1, Some(',')));
+}
+
+#[test]
+fn is_valid_partial_should_allow_incomplete_numbers_only() {
+    for &valid in &["", "-", "1", "-1", "1.", "-.", ".5", "1234.56"] {
+        assert!(is_valid_partial(valid, 2), "{:?} should be valid", valid);
+    }
+    for &invalid in &["a", "1a", "--1", "1-", "1.2.3", "1.234", "1,000", " 1"] {
+        assert!(!is_valid_partial(invalid, 2), "{:?} should be invalid", invalid);
+    }
+    assert!(!is_valid_partial("1.", 0));
+}
+
+#[test]
+fn parse_should_only_produce_complete_numbers() {
+    assert_eq!(Some(1234.5), parse("1,234.5"));
+    assert_eq!(Some(-1.0), parse("-1."));
+    assert_eq!(Some(0.5), parse(".5"));
+    assert_eq!(None, parse("-"));
+    assert_eq!(None, parse("."));
+    assert_eq!(None, parse(""));
+}
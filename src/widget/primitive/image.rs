@@ -1,6 +1,7 @@
 //! A simple, non-interactive widget for drawing an `Image`.
 
-use {Color, Dimension, Rect, Widget, Ui};
+use {Color, Dimension, Rect, Scalar, Widget, Ui};
+use image;
 use widget;
 
 
@@ -9,8 +10,12 @@ use widget;
 pub struct Image {
     /// Data necessary and common for all widget builder types.
     pub common: widget::CommonBuilder,
+    /// The key of the image data within the `image::Map`, if other than the widget's own id.
+    pub image_id: Option<widget::Id>,
     /// The rectangle area of the original source image that should be used.
     pub src_rect: Option<Rect>,
+    /// The insets used to scale the image as a nine-patch.
+    pub nine_patch: Option<NinePatch>,
     /// Unique styling.
     pub style: Style,
 }
@@ -18,10 +23,31 @@ pub struct Image {
 /// Unique `State` to be stored between updates for the `Image`.
 #[derive(Copy, Clone)]
 pub struct State {
+    /// The key of the image data within the `image::Map`, if other than the widget's own id.
+    pub image_id: Option<widget::Id>,
     /// The rectangular area of the image that we wish to display.
     ///
     /// If `None`, the entire image will be used.
     pub src_rect: Option<Rect>,
+    /// The insets used to scale the image as a nine-patch, if any.
+    pub nine_patch: Option<NinePatch>,
+}
+
+/// The distances from each edge of the source rectangle that divide an image into nine patches.
+///
+/// When scaled, the four corner patches keep their size, the top and bottom edges stretch
+/// horizontally, the left and right edges stretch vertically and only the centre patch stretches
+/// along both axes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NinePatch {
+    /// The width of the left column of patches.
+    pub left: Scalar,
+    /// The width of the right column of patches.
+    pub right: Scalar,
+    /// The height of the bottom row of patches.
+    pub bottom: Scalar,
+    /// The height of the top row of patches.
+    pub top: Scalar,
 }
 
 widget_style!{
@@ -62,7 +88,9 @@ impl Image {
     pub fn new() -> Self {
         Image {
             common: widget::CommonBuilder::new(),
+            image_id: None,
             src_rect: None,
+            nine_patch: None,
             style: Style::new(),
         }
     }
@@ -75,6 +103,32 @@ impl Image {
         self
     }
 
+    /// Display the image stored within the `image::Map` at the given key rather than at the
+    /// widget's own id.
+    ///
+    /// `Image`s that share the same key share the same texture, allowing backends to draw
+    /// consecutive `Image`s with a single draw call.
+    pub fn image_id(mut self, id: widget::Id) -> Self {
+        self.image_id = Some(id);
+        self
+    }
+
+    /// Display the given region of an `image::Atlas` page, whose texture is stored within the
+    /// `image::Map` at `page_id`.
+    pub fn atlas_region(self, page_id: widget::Id, region: &image::AtlasRegion) -> Self {
+        self.image_id(page_id).source_rectangle(region.source_rect())
+    }
+
+    /// Scale the image as a nine-patch divided by the given insets, stretching only its centre and
+    /// edges while keeping its corners fixed.
+    ///
+    /// The insets are measured within the `source_rectangle`, which must also be given for the
+    /// nine-patch to take effect as the image's dimensions are otherwise unknown.
+    pub fn nine_patch(mut self, nine_patch: NinePatch) -> Self {
+        self.nine_patch = Some(nine_patch);
+        self
+    }
+
     builder_methods!{
         pub color { style.maybe_color = Some(Option<Color>) }
     }
@@ -97,7 +151,9 @@ impl Widget for Image {
 
     fn init_state(&self, _: widget::id::Generator) -> Self::State {
         State {
+            image_id: None,
             src_rect: None,
+            nine_patch: None,
         }
     }
 
@@ -121,11 +177,111 @@ impl Widget for Image {
 
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { state, .. } = args;
-        let Image { src_rect, .. } = self;
+        let Image { image_id, src_rect, nine_patch, .. } = self;
+
+        if state.image_id != image_id {
+            state.update(|state| state.image_id = image_id);
+        }
 
         if state.src_rect != src_rect {
             state.update(|state| state.src_rect = src_rect);
         }
+
+        if state.nine_patch != nine_patch {
+            state.update(|state| state.nine_patch = nine_patch);
+        }
     }
 
 }
+
+
+impl NinePatch {
+
+    /// The same inset for every edge.
+    pub fn uniform(inset: Scalar) -> Self {
+        NinePatch { left: inset, right: inset, bottom: inset, top: inset }
+    }
+
+    /// The nine `(destination, source)` pairs of `Rect`s required to draw the `src` area of an
+    /// image scaled to the `dest` area, in order from the top-left patch to the bottom-right.
+    ///
+    /// Insets that together exceed the size of the `src` are scaled down to fit within it.
+    /// Likewise, if the `dest` is smaller than the corners, the corners are scaled down to fit.
+    pub fn quads(&self, src: Rect, dest: Rect) -> [(Rect, Rect); 9] {
+        // Clamp a pair of opposing insets so that together they do not exceed `len`.
+        fn clamp(start: Scalar, end: Scalar, len: Scalar) -> (Scalar, Scalar) {
+            let (start, end) = (start.max(0.0), end.max(0.0));
+            if start + end > len {
+                let scale = len / (start + end);
+                (start * scale, end * scale)
+            } else {
+                (start, end)
+            }
+        }
+
+        let NinePatch { left, right, bottom, top } = *self;
+        let (left, right) = clamp(left, right, src.w());
+        let (bottom, top) = clamp(bottom, top, src.h());
+        let (dest_left, dest_right) = clamp(left, right, dest.w());
+        let (dest_bottom, dest_top) = clamp(bottom, top, dest.h());
+
+        // The x and y coordinates of the lines dividing each column and row of patches.
+        let edges = |rect: Rect, l: Scalar, r: Scalar, b: Scalar, t: Scalar| {
+            let (rl, rr, rb, rt) = rect.l_r_b_t();
+            ([rl, rl + l, rr - r, rr], [rb, rb + b, rt - t, rt])
+        };
+        let (src_xs, src_ys) = edges(src, left, right, bottom, top);
+        let (dest_xs, dest_ys) = edges(dest, dest_left, dest_right, dest_bottom, dest_top);
+
+        let mut quads = [(dest, src); 9];
+        for (i, row) in (0..3).rev().enumerate() {
+            for col in 0..3 {
+                let quad = |xs: [Scalar; 4], ys: [Scalar; 4]| {
+                    Rect::from_corners([xs[col], ys[row]], [xs[col + 1], ys[row + 1]])
+                };
+                quads[i * 3 + col] = (quad(dest_xs, dest_ys), quad(src_xs, src_ys));
+            }
+        }
+        quads
+    }
+
+}
+
+
+#[test]
+fn nine_patch_quads_should_keep_corners_fixed_and_stretch_the_rest() {
+    let src = Rect::from_corners([0.0, 0.0], [30.0, 30.0]);
+    let dest = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let quads = NinePatch::uniform(10.0).quads(src, dest);
+    let dest_rects: Vec<_> = quads.iter().map(|&(dest, _)| dest).collect();
+    let expected = vec![
+        Rect::from_corners([0.0, 40.0], [10.0, 50.0]),
+        Rect::from_corners([10.0, 40.0], [90.0, 50.0]),
+        Rect::from_corners([90.0, 40.0], [100.0, 50.0]),
+        Rect::from_corners([0.0, 10.0], [10.0, 40.0]),
+        Rect::from_corners([10.0, 10.0], [90.0, 40.0]),
+        Rect::from_corners([90.0, 10.0], [100.0, 40.0]),
+        Rect::from_corners([0.0, 0.0], [10.0, 10.0]),
+        Rect::from_corners([10.0, 0.0], [90.0, 10.0]),
+        Rect::from_corners([90.0, 0.0], [100.0, 10.0]),
+    ];
+    assert_eq!(expected, dest_rects);
+    assert_eq!(Rect::from_corners([0.0, 20.0], [10.0, 30.0]), quads[0].1);
+    assert_eq!(Rect::from_corners([10.0, 10.0], [20.0, 20.0]), quads[4].1);
+    assert_eq!(Rect::from_corners([20.0, 0.0], [30.0, 10.0]), quads[8].1);
+}
+
+#[test]
+fn nine_patch_insets_larger_than_the_source_should_clamp() {
+    let src = Rect::from_corners([0.0, 0.0], [20.0, 20.0]);
+    let dest = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let nine_patch = NinePatch { left: 30.0, right: 10.0, bottom: 100.0, top: 100.0 };
+    let quads = nine_patch.quads(src, dest);
+    // The insets are scaled down to fit the source, leaving an empty centre column and row.
+    assert_eq!(Rect::from_corners([0.0, 10.0], [15.0, 20.0]), quads[0].1);
+    assert_eq!(0.0, quads[4].1.w());
+    assert_eq!(0.0, quads[4].1.h());
+    // The corners keep their clamped size within the destination.
+    assert_eq!(Rect::from_corners([0.0, 90.0], [15.0, 100.0]), quads[0].0);
+    assert_eq!(Rect::from_corners([95.0, 0.0], [100.0, 10.0]), quads[8].0);
+}
@@ -29,6 +29,8 @@ pub struct Text<'a> {
     pub text: &'a str,
     /// Unique styling for the **Text**.
     pub style: Style,
+    /// The range of characters that should be drawn with a highlight behind them.
+    pub maybe_highlight: Option<std::ops::Range<usize>>,
 }
 
 widget_style!{
@@ -44,6 +46,17 @@ widget_style!{
         - line_spacing: Scalar { 1.0 }
         /// Alignment of the text along the *x* axis.
         - text_align: Align { Align::Start }
+        /// Whether or not wrapped lines should be justified to fill the width.
+        - justify: bool { false }
+        /// If `Some`, every character advances by this fixed cell width (or two cells for wide
+        /// characters) rather than by the natural advance of its glyph.
+        - maybe_monospace: Option<Scalar> { None }
+        /// If `Some`, text exceeding the width is truncated and ends with an ellipsis.
+        - maybe_truncate: Option<Ellipsis> { None }
+        /// The color of the highlight drawn behind highlighted characters.
+        ///
+        /// If `None`, a translucent, highlighted version of the text `color` is used.
+        - highlight_color: Option<Color> { None }
         /// The id of the font to use for rendring and layout.
         - font_id: Option<text::font::Id> { theme.font_id }
         // /// The line styling for the text.
@@ -60,6 +73,15 @@ pub enum Wrap {
     Whitespace,
 }
 
+/// What to display in place of text that is truncated to a width too narrow for the ellipsis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Ellipsis {
+    /// Display nothing at all.
+    Hide,
+    /// Display as many of the periods of a "..." as fit within the width.
+    Partial,
+}
+
 // /// Line styling for the **Text**.
 // pub enum Line {
 //     /// Underline the text.
@@ -77,6 +99,8 @@ pub struct State {
     pub string: String,
     /// The indices and width for each line of text within the `string`.
     pub line_infos: Vec<text::line::Info>,
+    /// The range of highlighted characters within the `string`, if any.
+    pub highlight: Option<std::ops::Range<usize>>,
 }
 
 
@@ -88,6 +112,7 @@ impl<'a> Text<'a> {
             common: widget::CommonBuilder::new(),
             text: text,
             style: Style::new(),
+            maybe_highlight: None,
         }
     }
 
@@ -136,6 +161,49 @@ impl<'a> Text<'a> {
         self.align_text_to(Align::End)
     }
 
+    /// Stretch the spacing between words so that every wrapped line fills the width of the
+    /// bounding **Rect**.
+    ///
+    /// The last line of each paragraph and lines containing only a single word are not stretched
+    /// and are instead aligned via the **Text**'s regular alignment.
+    pub fn justify(mut self) -> Self {
+        self.style.justify = Some(true);
+        self
+    }
+
+    /// Lay out the text in a monospace grid where every character advances by the given
+    /// `cell_width`, i.e. for displaying code or logs.
+    ///
+    /// Wide characters such as CJK ideographs occupy two cells. Monospace text is never justified.
+    pub fn monospace(mut self, cell_width: Scalar) -> Self {
+        self.style.maybe_monospace = Some(Some(cell_width));
+        self
+    }
+
+    /// Truncate text that exceeds the width of the **Text**, ending it with an ellipsis rather
+    /// than allowing it to overflow. Truncated text is never wrapped.
+    ///
+    /// The given `Ellipsis` describes what to display when even the ellipsis doesn't fit.
+    pub fn truncate(mut self, ellipsis: Ellipsis) -> Self {
+        self.style.maybe_truncate = Some(Some(ellipsis));
+        self
+    }
+
+    /// Draw a highlight behind the characters within the given range, i.e. a selection.
+    ///
+    /// A highlight spanning multiple lines is drawn as a separate rectangle on each line.
+    /// Highlights do not account for the extra spacing between words of justified lines.
+    pub fn highlight(mut self, chars: std::ops::Range<usize>) -> Self {
+        self.maybe_highlight = Some(chars);
+        self
+    }
+
+    /// Specify the color of the highlight drawn behind highlighted characters.
+    pub fn highlight_color(mut self, color: Color) -> Self {
+        self.style.highlight_color = Some(Some(color));
+        self
+    }
+
     builder_methods!{
         pub font_size { style.font_size = Some(FontSize) }
         pub align_text_to { style.text_align = Some(Align) }
@@ -162,6 +230,7 @@ impl<'a> Widget for Text<'a> {
         State {
             string: String::new(),
             line_infos: Vec::new(),
+            highlight: None,
         }
     }
 
@@ -183,9 +252,16 @@ impl<'a> Widget for Text<'a> {
         };
 
         let font_size = self.style.font_size(&ui.theme);
+        let maybe_monospace = self.style.maybe_monospace(&ui.theme);
         let mut max_width = 0.0;
         for line in self.text.lines() {
-            let width = text::line::width(line, font, font_size);
+            let width = match maybe_monospace {
+                Some(cell_w) => {
+                    let advance = |ch| text::line::monospace_advance(ch, cell_w);
+                    text::line::width_with(line, advance, |_, _| 0.0)
+                },
+                None => text::line::width(line, font, font_size),
+            };
             max_width = utils::partial_max(max_width, width);
         }
         Dimension::Absolute(max_width)
@@ -198,29 +274,40 @@ impl<'a> Widget for Text<'a> {
     fn default_y_dimension(&self, ui: &Ui) -> Dimension {
         use position::Sizeable;
 
-        let font = match self.style.font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
-        {
+        let font_id = match self.style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+            Some(id) => id,
+            None => return Dimension::Absolute(0.0),
+        };
+        let font = match ui.fonts.get(font_id) {
             Some(font) => font,
             None => return Dimension::Absolute(0.0),
         };
+        let fallbacks = ui.fonts.fallback_fonts(font_id);
 
         let text = &self.text;
         let font_size = self.style.font_size(&ui.theme);
+        let maybe_monospace = self.style.maybe_monospace(&ui.theme);
         let num_lines = match self.style.maybe_wrap(&ui.theme) {
             None => text.lines().count(),
             Some(wrap) => match self.get_w(ui) {
                 None => text.lines().count(),
-                Some(max_w) => match wrap {
+                Some(max_w) => match maybe_monospace {
+                    Some(cell_w) => {
+                        let advance = |ch| text::line::monospace_advance(ch, cell_w);
+                        text::line::wrap_with(text, max_w, wrap, advance).len()
+                    },
+                    None => match wrap {
                     Wrap::Character =>
                         text::line::infos(text, font, font_size)
+                            .fallbacks(&fallbacks)
                             .wrap_by_character(max_w)
                             .count(),
                     Wrap::Whitespace =>
                         text::line::infos(text, font, font_size)
+                            .fallbacks(&fallbacks)
                             .wrap_by_whitespace(max_w)
                             .count(),
+                    },
                 },
             },
         };
@@ -232,31 +319,67 @@ impl<'a> Widget for Text<'a> {
     /// Update the state of the Text.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { rect, state, style, ui, .. } = args;
-        let Text { text, .. } = self;
+        let Text { text, maybe_highlight, .. } = self;
 
-        let maybe_wrap = style.maybe_wrap(ui.theme());
+        let maybe_truncate = style.maybe_truncate(ui.theme());
+        let maybe_wrap = match maybe_truncate {
+            Some(_) => None,
+            None => style.maybe_wrap(ui.theme()),
+        };
         let font_size = style.font_size(ui.theme());
 
-        let font = match style.font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
-        {
+        let font_id = match style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+            Some(id) => id,
+            None => return,
+        };
+        let font = match ui.fonts.get(font_id) {
             Some(font) => font,
             None => return,
         };
 
+        // Characters missing from the `font` are measured using the font that they are drawn with.
+        let fallbacks = ui.fonts.fallback_fonts(font_id);
+
+        // Truncate the text to the width of the `Rect` if necessary.
+        let truncated = match maybe_truncate {
+            Some(ellipsis) => match style.maybe_monospace(ui.theme()) {
+                Some(cell_w) => {
+                    let advance = |ch| text::line::monospace_advance(ch, cell_w);
+                    text::line::truncate_with(text, rect.w(), ellipsis, advance)
+                },
+                None => text::line::truncate(text, rect.w(), ellipsis, font, font_size),
+            },
+            None => std::borrow::Cow::Borrowed(text),
+        };
+        let text: &str = &truncated;
+
         // Produces an iterator yielding info for each line within the `text`.
-        let new_line_infos = || match maybe_wrap {
-            None =>
-                text::line::infos(text, font, font_size),
-            Some(Wrap::Character) =>
-                text::line::infos(text, font, font_size).wrap_by_character(rect.w()),
-            Some(Wrap::Whitespace) =>
-                text::line::infos(text, font, font_size).wrap_by_whitespace(rect.w()),
+        let new_line_infos = || {
+            let infos = text::line::infos(text, font, font_size).fallbacks(&fallbacks);
+            match maybe_wrap {
+                None => infos,
+                Some(Wrap::Character) => infos.wrap_by_character(rect.w()),
+                Some(Wrap::Whitespace) => infos.wrap_by_whitespace(rect.w()),
+            }
         };
 
+        // Monospace text is laid out by its cell width rather than by its `Font`.
+        if let Some(cell_w) = style.maybe_monospace(ui.theme()) {
+            let advance = |ch| text::line::monospace_advance(ch, cell_w);
+            let (max_w, wrap) = match maybe_wrap {
+                Some(wrap) => (rect.w(), wrap),
+                None => (std::f64::MAX, Wrap::Character),
+            };
+            let line_infos = text::line::wrap_with(text, max_w, wrap, advance);
+            if &state.string[..] != text || state.line_infos != line_infos {
+                state.update(|state| {
+                    state.string = text.to_owned();
+                    state.line_infos = line_infos;
+                });
+            }
+
         // If the string is different, we must update both the string and the line breaks.
-        if &state.string[..] != text {
+        } else if &state.string[..] != text {
             state.update(|state| {
                 state.string = text.to_owned();
                 state.line_infos = new_line_infos().collect();
@@ -280,6 +403,12 @@ impl<'a> Widget for Text<'a> {
                 state.update(|state| state.line_infos = new_line_infos);
             }
         }
+
+        // Empty highlights would not be visible, so they are discarded.
+        let highlight = maybe_highlight.and_then(|r| if r.start < r.end { Some(r) } else { None });
+        if state.highlight != highlight {
+            state.update(|state| state.highlight = highlight);
+        }
     }
 
 }
@@ -0,0 +1,243 @@
+//! A widget for displaying the progress of some long-running operation.
+
+use {Borderable, Color, Colorable, Positionable, Range, Rect, Scalar, Widget};
+use anim;
+use std;
+use ui;
+use utils::clamp;
+use widget;
+
+
+/// Displays the progress of some operation as a bar that fills from left to right.
+///
+/// In `Determinate` mode the bar is filled in proportion to the given value. In `Indeterminate`
+/// mode, for operations whose progress is unknown, a segment of the bar continuously slides from
+/// left to right.
+pub struct ProgressBar {
+    common: widget::CommonBuilder,
+    style: Style,
+    mode: Mode,
+}
+
+/// The mode in which a `ProgressBar` displays progress.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// The bar is filled in proportion to the given value from `0.0` (empty) to `1.0` (full).
+    ///
+    /// Values outside of this range are clamped.
+    Determinate(Scalar),
+    /// A segment of the bar slides from left to right, repeating every `INDETERMINATE_PERIOD`.
+    Indeterminate,
+}
+
+/// The width of the sliding segment of an `Indeterminate` `ProgressBar` as a fraction of the
+/// width of the bar.
+pub const INDETERMINATE_WIDTH: Scalar = 0.25;
+
+/// The duration in seconds over which the sliding segment of an `Indeterminate` `ProgressBar`
+/// travels across the bar.
+pub const INDETERMINATE_PERIOD: Scalar = 1.5;
+
+widget_style! {
+    /// Unique styling for the `ProgressBar`.
+    style Style {
+        /// The color of the filled part of the bar.
+        - color: Color { theme.shape_color }
+        /// The color of the unfilled part of the bar.
+        - background_color: Color { theme.background_color }
+        /// The width of the border surrounding the bar.
+        - border: Scalar { theme.border_width }
+        /// The color of the border surrounding the bar.
+        - border_color: Color { theme.border_color }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        background,
+        fill,
+    }
+}
+
+/// The state of the `ProgressBar`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// The moment at which the `Indeterminate` animation began.
+    animation_start: Option<std::time::Instant>,
+}
+
+
+impl ProgressBar {
+
+    /// Begin building a `ProgressBar` in the given `Mode`.
+    pub fn new(mode: Mode) -> Self {
+        ProgressBar {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            mode: mode,
+        }
+    }
+
+    /// A `ProgressBar` filled in proportion to the given `value` between `0.0` and `1.0`.
+    pub fn determinate(value: Scalar) -> Self {
+        ProgressBar::new(Mode::Determinate(value))
+    }
+
+    /// A `ProgressBar` for an operation whose progress is unknown.
+    pub fn indeterminate() -> Self {
+        ProgressBar::new(Mode::Indeterminate)
+    }
+
+    builder_methods!{
+        pub background_color { style.background_color = Some(Color) }
+    }
+
+}
+
+
+impl Widget for ProgressBar {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            animation_start: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the ProgressBar.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+
+        let border = style.border(ui.theme());
+        let inner_rect = rect.pad(border);
+
+        widget::BorderedRectangle::new(rect.dim())
+            .middle_of(id)
+            .graphics_for(id)
+            .color(style.background_color(ui.theme()))
+            .border(border)
+            .border_color(style.border_color(ui.theme()))
+            .set(state.ids.background, ui);
+
+        let fill_rect = match self.mode {
+            Mode::Determinate(value) => {
+                if state.animation_start.is_some() {
+                    state.update(|state| state.animation_start = None);
+                }
+                determinate_rect(inner_rect, value)
+            },
+            Mode::Indeterminate => {
+                let now = ui.now();
+                let start = match state.animation_start {
+                    Some(start) => start,
+                    None => {
+                        state.update(|state| state.animation_start = Some(now));
+                        now
+                    },
+                };
+                let elapsed = now.duration_since(start);
+                let secs = anim::secs(elapsed);
+                // Keep updating for as long as the animation is running.
+                ui::ref_mut_from_ui_cell(ui).request_redraw();
+                indeterminate_rect(inner_rect, secs)
+            },
+        };
+
+        if fill_rect.w() > 0.0 {
+            let fill_xy_offset = [fill_rect.x() - rect.x(), fill_rect.y() - rect.y()];
+            widget::Rectangle::fill(fill_rect.dim())
+                .xy_relative_to(id, fill_xy_offset)
+                .graphics_for(id)
+                .parent(id)
+                .color(style.color(ui.theme()))
+                .set(state.ids.fill, ui);
+        }
+    }
+
+}
+
+
+impl Colorable for ProgressBar {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl Borderable for ProgressBar {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
+
+
+/// The filled area of the given `inner` rect of a `Determinate` `ProgressBar` with the given
+/// `value`, which is clamped between `0.0` and `1.0`.
+pub fn determinate_rect(inner: Rect, value: Scalar) -> Rect {
+    let value = if value.is_nan() { 0.0 } else { clamp(value, 0.0, 1.0) };
+    let left = inner.left();
+    let right = left + inner.w() * value;
+    Rect { x: Range::new(left, right), y: inner.y }
+}
+
+/// The area of the sliding segment within the given `inner` rect of an `Indeterminate`
+/// `ProgressBar` after the animation has run for `secs` seconds.
+///
+/// The segment enters from the left edge and exits from the right edge, and is cropped to the
+/// `inner` rect as it does so.
+pub fn indeterminate_rect(inner: Rect, secs: Scalar) -> Rect {
+    let w = inner.w();
+    let segment_w = w * INDETERMINATE_WIDTH;
+    let phase = (secs / INDETERMINATE_PERIOD).fract();
+    // Travel from just beyond the left edge to just beyond the right edge.
+    let end = inner.left() + phase * (w + segment_w);
+    let start = end - segment_w;
+    let x = Range::new(start.max(inner.left()), end.min(inner.right()));
+    Rect { x: x, y: inner.y }
+}
+
+
+#[test]
+fn determinate_rect_should_fill_in_proportion_to_the_value() {
+    let inner = Rect::from_corners([-50.0, -5.0], [50.0, 5.0]);
+    assert_eq!(0.0, determinate_rect(inner, 0.0).w());
+    assert_eq!(50.0, determinate_rect(inner, 0.5).w());
+    assert_eq!(100.0, determinate_rect(inner, 1.0).w());
+    assert_eq!(-50.0, determinate_rect(inner, 0.5).left());
+    assert_eq!(inner.y, determinate_rect(inner, 0.5).y);
+}
+
+#[test]
+fn determinate_rect_should_clamp_values_outside_of_the_unit_range() {
+    let inner = Rect::from_corners([-50.0, -5.0], [50.0, 5.0]);
+    assert_eq!(0.0, determinate_rect(inner, -0.5).w());
+    assert_eq!(100.0, determinate_rect(inner, 1.5).w());
+    assert_eq!(0.0, determinate_rect(inner, std::f64::NAN).w());
+}
+
+#[test]
+fn indeterminate_rect_should_slide_across_and_stay_within_the_bar() {
+    let inner = Rect::from_corners([0.0, 0.0], [100.0, 10.0]);
+    assert_eq!(0.0, indeterminate_rect(inner, 0.0).w());
+    let halfway = indeterminate_rect(inner, INDETERMINATE_PERIOD / 2.0);
+    assert_eq!(Range::new(37.5, 62.5), halfway.x);
+    // The animation repeats every period.
+    assert_eq!(halfway, indeterminate_rect(inner, INDETERMINATE_PERIOD * 1.5));
+    for i in 0..100 {
+        let rect = indeterminate_rect(inner, i as Scalar * 0.037);
+        assert!(rect.left() >= 0.0 && rect.right() <= 100.0 && rect.w() >= 0.0);
+    }
+}
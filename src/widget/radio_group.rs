@@ -0,0 +1,228 @@
+//! A group of mutually exclusive options of which at most one may be selected.
+
+use {
+    color,
+    Color,
+    Colorable,
+    FontSize,
+    Positionable,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use input;
+use std;
+use text;
+use widget;
+use widget::tabs::{next_tab_idx, previous_tab_idx};
+
+
+/// Displays a vertical list of options, each beside a circular radio button.
+///
+/// Selecting an option, either by clicking it or via the keyboard, deselects all others. While
+/// the `RadioGroup` (or one of its options) is capturing the keyboard, the `Down` and `Right`
+/// arrow keys immediately select the next option and the `Up` and `Left` arrow keys the previous
+/// one, wrapping around at either end.
+///
+/// Like the `Slider`, the `RadioGroup` does not store the selected option. Instead the user
+/// passes the selected index and replaces it upon receiving a newly selected index.
+pub struct RadioGroup<'a, S: 'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    options: &'a [S],
+    selected: Option<usize>,
+}
+
+widget_style! {
+    /// Unique styling for the `RadioGroup`.
+    style Style {
+        /// The color of each radio button.
+        - color: Color { theme.shape_color }
+        /// The color of the dot marking the selected option.
+        - dot_color: Color { theme.label_color }
+        /// The color of the label of each option.
+        - label_color: Color { theme.label_color }
+        /// The font size of the label of each option.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display the label of each option.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        options[],
+        buttons[],
+        labels[],
+        dot,
+    }
+}
+
+/// The state of the `RadioGroup`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+
+impl<'a, S> RadioGroup<'a, S> {
+
+    /// Begin building a `RadioGroup` with the given options, of which the option at the given
+    /// index (if any) is selected.
+    pub fn new(options: &'a [S], selected: Option<usize>) -> Self {
+        RadioGroup {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            options: options,
+            selected: selected,
+        }
+    }
+
+    /// Specify the font used for displaying the label of each option.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub dot_color { style.dot_color = Some(Color) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a, S> Widget for RadioGroup<'a, S>
+    where S: AsRef<str>,
+{
+    type State = State;
+    type Style = Style;
+    /// The index of the newly selected option, if the selection changed during the update.
+    type Event = Option<usize>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the RadioGroup.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let RadioGroup { options, selected, .. } = self;
+        let num_options = options.len();
+
+        if state.ids.options.len() < num_options {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.options.resize(num_options, id_gen);
+                state.ids.buttons.resize(num_options, id_gen);
+                state.ids.labels.resize(num_options, id_gen);
+            });
+        }
+
+        if num_options == 0 {
+            return None;
+        }
+
+        // Check that the selected index, if given, refers to one of the options.
+        let selected = match selected {
+            Some(i) if i < num_options => Some(i),
+            _ => None,
+        };
+        let mut new_selected = selected;
+
+        // While the group or one of its options is capturing the keyboard, the arrow keys select
+        // the next or previous option.
+        let focus_ids = std::iter::once(id).chain(state.ids.options[..num_options].iter().cloned());
+        for focus_id in focus_ids {
+            for press in ui.widget_input(focus_id).presses().key() {
+                new_selected = match press.key {
+                    input::Key::Down | input::Key::Right =>
+                        next_tab_idx(new_selected, num_options),
+                    input::Key::Up | input::Key::Left =>
+                        previous_tab_idx(new_selected, num_options),
+                    _ => continue,
+                };
+            }
+        }
+
+        let option_h = rect.h() / num_options as Scalar;
+        let radius = (option_h / 4.0).max(1.0);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+
+        // Each option is a transparent `Rectangle` spanning the width of the group, upon which
+        // the radio button and label are drawn.
+        for (i, option) in options.iter().enumerate() {
+            let option_id = state.ids.options[i];
+            let button_id = state.ids.buttons[i];
+            let top = i as Scalar * option_h;
+            widget::Rectangle::fill([rect.w(), option_h])
+                .mid_top_with_margin_on(id, top)
+                .color(color::TRANSPARENT)
+                .parent(id)
+                .set(option_id, ui);
+
+            if ui.widget_input(option_id).clicks().left().next().is_some() {
+                new_selected = Some(i);
+            }
+
+            let button_color = match ui.widget_input(option_id).mouse() {
+                Some(mouse) =>
+                    if mouse.buttons.left().is_down() { color.clicked() }
+                    else { color.highlighted() },
+                None => color,
+            };
+            widget::Circle::fill(radius)
+                .mid_left_with_margin_on(option_id, radius)
+                .color(button_color)
+                .graphics_for(option_id)
+                .parent(id)
+                .set(button_id, ui);
+
+            widget::Text::new(option.as_ref())
+                .and_then(font_id, widget::Text::font_id)
+                .right_from(button_id, radius)
+                .align_middle_y_of(option_id)
+                .color(label_color)
+                .font_size(font_size)
+                .no_line_wrap()
+                .graphics_for(option_id)
+                .parent(id)
+                .set(state.ids.labels[i], ui);
+        }
+
+        // Mark the selected option with a dot in the centre of its radio button.
+        if let Some(i) = new_selected {
+            widget::Circle::fill(radius / 2.0)
+                .middle_of(state.ids.buttons[i])
+                .color(style.dot_color(&ui.theme))
+                .graphics_for(state.ids.options[i])
+                .parent(id)
+                .set(state.ids.dot, ui);
+        }
+
+        if new_selected != selected { new_selected } else { None }
+    }
+
+}
+
+
+impl<'a, S> Colorable for RadioGroup<'a, S> {
+    builder_method!(color { style.color = Some(Color) });
+}
@@ -27,6 +27,8 @@ pub struct RangeSlider<'a, T> {
     max: T,
     maybe_label: Option<&'a str>,
     style: Style,
+    /// Whether or not user input is enabled.
+    enabled: bool,
 }
 
 widget_style!{
@@ -115,6 +117,7 @@ impl<'a, T> RangeSlider<'a, T> {
             max: max,
             maybe_label: None,
             style: Style::new(),
+            enabled: true,
         }
     }
 
@@ -124,6 +127,10 @@ impl<'a, T> RangeSlider<'a, T> {
         self
     }
 
+    builder_methods!{
+        pub enabled { enabled = bool }
+    }
+
 }
 
 impl<'a, T> Widget for RangeSlider<'a, T>
@@ -166,7 +173,12 @@ impl<'a, T> Widget for RangeSlider<'a, T>
     /// Update the state of the Slider.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, mut ui, .. } = args;
-        let RangeSlider { start, end, min, max, maybe_label, .. } = self;
+        let RangeSlider { start, end, min, max, maybe_label, enabled, .. } = self;
+
+        // A disabled slider ignores all presses and relinquishes any capture that it held.
+        if !enabled {
+            ui.release_captures(id);
+        }
 
         let border = style.border(ui.theme());
         let inner_rect = rect.pad(border);
@@ -174,7 +186,7 @@ impl<'a, T> Widget for RangeSlider<'a, T>
         let value_to_x = |v| utils::map_range(v, min, max, inner_rect.left(), inner_rect.right());
         let x_to_value = |x| utils::map_range(x, inner_rect.left(), inner_rect.right(), min, max);
 
-        let mut maybe_drag = state.drag;
+        let mut maybe_drag = if enabled { state.drag } else { None };
         let mut new_start = start;
         let mut new_end = utils::clamp(end, start, max);
         for widget_event in ui.widget_input(id).events() {
@@ -187,7 +199,7 @@ impl<'a, T> Widget for RangeSlider<'a, T>
                 /// the cursor.
                 /// - Else if over the range, begin dragging the range.
                 /// - Else if not over the range, snap the end closest to the mouse to the mouse.
-                event::Widget::Press(press) => {
+                event::Widget::Press(press) if enabled => {
                     let press_xy = match press.button {
                         event::Button::Mouse(input::MouseButton::Left, press_xy) => press_xy,
                         _ => continue,
@@ -203,13 +215,17 @@ impl<'a, T> Widget for RangeSlider<'a, T>
                             let distance_from_start = (abs_press_xy[0] - start_x).abs();
                             if distance_from_start < grab_edge_threshold {
                                 maybe_drag = Some(Drag::Edge(Edge::Start));
-                                new_start = x_to_value(abs_press_xy[0]);
+                                let value = x_to_value(abs_press_xy[0]);
+                                new_start =
+                                    clamp_edge(Edge::Start, value, new_start, new_end, min, max);
                                 continue;
                             }
                             let distance_from_end = (end_x - abs_press_xy[0]).abs();
                             if distance_from_end < grab_edge_threshold {
                                 maybe_drag = Some(Drag::Edge(Edge::End));
-                                new_end = x_to_value(abs_press_xy[0]);
+                                let value = x_to_value(abs_press_xy[0]);
+                                new_end =
+                                    clamp_edge(Edge::End, value, new_start, new_end, min, max);
                                 continue;
                             }
                             maybe_drag = Some(Drag::Handle);
@@ -230,13 +246,15 @@ impl<'a, T> Widget for RangeSlider<'a, T>
                 /// Drags either the Start, End or the whole Bar depending on where it was pressed.
                 event::Widget::Drag(drag_event) if drag_event.button == input::MouseButton::Left => {
                     match maybe_drag {
-                        Some(Drag::Edge(Edge::Start)) => {
+                        Some(Drag::Edge(edge)) => {
                             let abs_drag_to = inner_rect.x() + drag_event.to[0];
-                            new_start = utils::clamp(x_to_value(abs_drag_to), min, new_end);
-                        },
-                        Some(Drag::Edge(Edge::End)) => {
-                            let abs_drag_to = inner_rect.x() + drag_event.to[0];
-                            new_end = utils::clamp(x_to_value(abs_drag_to), new_start, max);
+                            let value = x_to_value(abs_drag_to);
+                            match edge {
+                                Edge::Start => new_start =
+                                    clamp_edge(edge, value, new_start, new_end, min, max),
+                                Edge::End => new_end =
+                                    clamp_edge(edge, value, new_start, new_end, min, max),
+                            }
                         },
                         Some(Drag::Handle) => {
                             let drag_amt = drag_event.delta_xy[0];
@@ -287,7 +305,9 @@ impl<'a, T> Widget for RangeSlider<'a, T>
         // The **Rectangle** for the border.
         let interaction_color = |ui: &::ui::UiCell, color: Color|
             ui.widget_input(id).mouse()
-                .map(|mouse| if mouse.buttons.left().is_down() {
+                .map(|mouse| if !enabled {
+                    color
+                } else if mouse.buttons.left().is_down() {
                     color.clicked()
                 } else {
                     color.highlighted()
@@ -305,7 +325,11 @@ impl<'a, T> Widget for RangeSlider<'a, T>
         let start_x = value_to_x(new_start);
         let end_x = value_to_x(new_end);
         let slider_rect = Rect { x: Range::new(start_x, end_x), y: inner_rect.y };
-        let color = interaction_color(&ui, style.color(ui.theme()));
+        let color = if enabled {
+            interaction_color(&ui, style.color(ui.theme()))
+        } else {
+            ui.theme.disabled_color
+        };
         let slider_xy_offset = [slider_rect.x() - rect.x(), slider_rect.y() - rect.y()];
         widget::Rectangle::fill(slider_rect.dim())
             .xy_relative_to(id, slider_xy_offset)
@@ -335,6 +359,21 @@ impl<'a, T> Widget for RangeSlider<'a, T>
 }
 
 
+/// Clamp the given `value` for the given `edge` so that the edges never cross and remain within
+/// the `min` and `max` of the `RangeSlider`.
+///
+/// A `Start` value beyond the `end` clamps to the `end`, and an `End` value before the `start`
+/// clamps to the `start`.
+pub fn clamp_edge<T>(edge: Edge, value: T, start: T, end: T, min: T, max: T) -> T
+    where T: PartialOrd,
+{
+    match edge {
+        Edge::Start => utils::clamp(value, min, end),
+        Edge::End => utils::clamp(value, start, max),
+    }
+}
+
+
 impl<'a, T> Colorable for RangeSlider<'a, T> {
     builder_method!(color { style.color = Some(Color) });
 }
@@ -353,3 +392,13 @@ impl<'a, T> Labelable<'a> for RangeSlider<'a, T> {
         label_font_size { style.label_font_size = Some(FontSize) }
     }
 }
+
+
+#[test]
+fn clamp_edge_should_prevent_the_edges_from_crossing() {
+    assert_eq!(30.0, clamp_edge(Edge::Start, 30.0, 20.0, 80.0, 0.0, 100.0));
+    assert_eq!(80.0, clamp_edge(Edge::Start, 95.0, 20.0, 80.0, 0.0, 100.0));
+    assert_eq!(0.0, clamp_edge(Edge::Start, -5.0, 20.0, 80.0, 0.0, 100.0));
+    assert_eq!(20.0, clamp_edge(Edge::End, 10.0, 20.0, 80.0, 0.0, 100.0));
+    assert_eq!(100.0, clamp_edge(Edge::End, 120.0, 20.0, 80.0, 0.0, 100.0));
+}
@@ -0,0 +1,442 @@
+//! A widget for displaying text made up of spans with differing colors and font sizes.
+
+use {Color, Colorable, FontSize, Positionable, Scalar, Sizeable, Widget};
+use std;
+use text;
+use widget;
+
+
+/// Displays a sequence of styled `Span`s, flowing them together from left to right and wrapping
+/// them by word at the width of the `RichText`.
+///
+/// All spans on a line share a common baseline, so that text of differing font sizes lines up.
+///
+/// Each fragment of a span is instantiated as a `Text` widget.
+#[derive(Clone)]
+#[allow(missing_copy_implementations)]
+pub struct RichText<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    spans: &'a [Span<'a>],
+}
+
+/// A single run of text with its own color and font size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Span<'a> {
+    /// The text within the span.
+    pub text: &'a str,
+    /// The color of the text.
+    pub color: Color,
+    /// The font size of the text.
+    pub font_size: FontSize,
+}
+
+/// A contiguous part of a single `Span` that lies on a single line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fragment {
+    /// The index of the `Span` to which the fragment belongs.
+    pub span: usize,
+    /// The byte range of the fragment within the `Span`'s text.
+    pub byte_range: std::ops::Range<usize>,
+    /// The index of the line on which the fragment lies.
+    pub line: usize,
+    /// The distance from the left edge of the `RichText` to the start of the fragment.
+    pub x: Scalar,
+    /// The distance from the top edge of the `RichText` down to the baseline of the line.
+    pub baseline: Scalar,
+    /// The width of the fragment.
+    pub w: Scalar,
+}
+
+widget_style!{
+    /// Unique styling for the `RichText`.
+    style Style {
+        /// The id of the font to use for every span.
+        - font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        fragments[],
+    }
+}
+
+/// The state of the `RichText`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+
+impl<'a> Span<'a> {
+
+    /// A span of text with the given color and font size.
+    pub fn new(text: &'a str, color: Color, font_size: FontSize) -> Self {
+        Span {
+            text: text,
+            color: color,
+            font_size: font_size,
+        }
+    }
+
+}
+
+impl<'a> RichText<'a> {
+
+    /// Begin building a `RichText` from the given spans.
+    pub fn new(spans: &'a [Span<'a>]) -> Self {
+        RichText {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            spans: spans,
+        }
+    }
+
+    /// Specify the font used for displaying the spans.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+}
+
+
+impl<'a> Widget for RichText<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the RichText.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let RichText { spans, .. } = self;
+
+        let font_id = match style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+            Some(font_id) => font_id,
+            None => return,
+        };
+
+        let fragments = match ui.fonts.get(font_id) {
+            Some(font) => {
+                let advance = |ch, font_size| {
+                    let scale = text::pt_to_scale(font_size);
+                    font.glyph(ch)
+                        .map(|g| g.scaled(scale).h_metrics().advance_width as Scalar)
+                        .unwrap_or(0.0)
+                };
+                let kerning = |a, b, font_size| {
+                    text::kerning(font, text::pt_to_scale(font_size), a, b)
+                };
+                layout(spans, rect.w(), advance, kerning)
+            },
+            None => return,
+        };
+
+        // Ensure there is a `widget::Id` for every fragment.
+        if state.ids.fragments.len() < fragments.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.fragments.resize(fragments.len(), id_gen));
+        }
+
+        // Instantiate a `Text` for each fragment with its baseline at the line's baseline.
+        let (half_w, half_h) = (rect.w() / 2.0, rect.h() / 2.0);
+        for (i, fragment) in fragments.iter().enumerate() {
+            let span = &spans[fragment.span];
+            let size = span.font_size as Scalar;
+            let rel_x = -half_w + fragment.x + fragment.w / 2.0;
+            let rel_y = half_h - fragment.baseline + size / 2.0;
+            widget::Text::new(&span.text[fragment.byte_range.clone()])
+                .no_line_wrap()
+                .font_id(font_id)
+                .font_size(span.font_size)
+                .color(span.color)
+                .w_h(fragment.w, size)
+                .x_y_relative_to(id, rel_x, rel_y)
+                .parent(id)
+                .graphics_for(id)
+                .set(state.ids.fragments[i], ui);
+        }
+    }
+
+}
+
+
+/// A word, whitespace character or partial word that has been placed on a line.
+struct Piece {
+    span: usize,
+    byte_range: std::ops::Range<usize>,
+    x: Scalar,
+    w: Scalar,
+    font_size: FontSize,
+    is_whitespace: bool,
+}
+
+/// The pieces placed on a single line.
+struct Line {
+    pieces: Vec<Piece>,
+    /// The minimum height of the line, given by the span of a newline character that ends it.
+    min_font_size: FontSize,
+}
+
+impl Line {
+    fn new() -> Self {
+        Line { pieces: Vec::new(), min_font_size: 0 }
+    }
+
+    /// The largest font size on the line which determines the height of the line.
+    fn font_size(&self) -> FontSize {
+        self.pieces.iter().fold(self.min_font_size, |max, p| std::cmp::max(max, p.font_size))
+    }
+}
+
+/// Lay out the given `spans` within the given `max_width`, producing a `Fragment` for each part
+/// of each span lying on each line.
+///
+/// Lines are wrapped at the whitespace preceding the first word that exceeds the `max_width`.
+/// Words that are wider than the `max_width` are broken at the first character that exceeds it.
+/// Newline characters always begin a new line.
+///
+/// The height of each line is the largest font size among its spans, and every span on the line
+/// shares the line's baseline. The advance width of each character at some font size is given by
+/// `advance`, while the `kerning` between consecutive characters of the same font size is added
+/// to the advance of the latter. Kerning never applies to the first character of a line.
+pub fn layout<A, K>(spans: &[Span], max_width: Scalar, mut advance: A, mut kerning: K)
+    -> Vec<Fragment>
+    where A: FnMut(char, FontSize) -> Scalar,
+          K: FnMut(char, char, FontSize) -> Scalar,
+{
+    let mut lines = vec![Line::new()];
+    let mut x = 0.0;
+    // The previous character on the current line along with its font size.
+    let mut last: Option<(char, FontSize)> = None;
+
+    for (span_i, span) in spans.iter().enumerate() {
+        let size = span.font_size;
+        let mut chars = span.text.char_indices().peekable();
+        while let Some((start, ch)) = chars.next() {
+
+            // Newlines always begin a new line, which must be at least as tall as the span.
+            if ch == '\n' {
+                {
+                    let line = lines.last_mut().unwrap();
+                    line.min_font_size = std::cmp::max(line.min_font_size, size);
+                }
+                new_line(&mut lines, &mut x, &mut last);
+                continue;
+            }
+
+            // The kerning between the previous character on the line and this one.
+            let lead = match last {
+                Some((last_ch, last_size)) if last_size == size => kerning(last_ch, ch, size),
+                _ => 0.0,
+            };
+
+            // Whitespace never causes a wrap. Any whitespace preceding a wrap is trimmed.
+            if ch.is_whitespace() {
+                let w = advance(ch, size);
+                let end = start + ch.len_utf8();
+                x += lead;
+                push_piece(&mut lines, span_i, start..end, x, w, size, true);
+                x += w;
+                last = Some((ch, size));
+                continue;
+            }
+
+            // Collect the full word.
+            let mut end = start + ch.len_utf8();
+            while let Some(&(i, ch)) = chars.peek() {
+                if ch.is_whitespace() {
+                    break;
+                }
+                end = i + ch.len_utf8();
+                chars.next();
+            }
+            let word = &span.text[start..end];
+            let word_w = text::line::width_with(word, |ch| advance(ch, size), |a, b| {
+                kerning(a, b, size)
+            });
+
+            // Wrap the word onto the next line if it doesn't fit.
+            if x + lead + word_w > max_width && !lines.last().unwrap().pieces.is_empty() {
+                new_line(&mut lines, &mut x, &mut last);
+            } else {
+                x += lead;
+            }
+
+            // If the word still doesn't fit, break it at each character that exceeds the width.
+            // The first character on a line is always placed.
+            let mut piece_start = start;
+            let mut piece_w = 0.0;
+            let mut prev = None;
+            for (i, ch) in word.char_indices() {
+                let i = start + i;
+                let w = advance(ch, size);
+                let kern = prev.map_or(0.0, |prev| kerning(prev, ch, size));
+                prev = Some(ch);
+                let line_is_empty = lines.last().unwrap().pieces.is_empty();
+                if x + piece_w + kern + w > max_width && (piece_w > 0.0 || !line_is_empty) {
+                    push_piece(&mut lines, span_i, piece_start..i, x, piece_w, size, false);
+                    new_line(&mut lines, &mut x, &mut last);
+                    piece_start = i;
+                    piece_w = w;
+                    continue;
+                }
+                piece_w += kern + w;
+            }
+            push_piece(&mut lines, span_i, piece_start..end, x, piece_w, size, false);
+            x += piece_w;
+            last = prev.map(|ch| (ch, size));
+        }
+    }
+    trim_trailing_whitespace(&mut lines);
+
+    // Merge neighbouring pieces of the same span and determine the baseline of each line.
+    let mut fragments: Vec<Fragment> = Vec::new();
+    let mut top = 0.0;
+    for (line_i, line) in lines.iter().enumerate() {
+        let baseline = top + line.font_size() as Scalar;
+        for piece in &line.pieces {
+            if let Some(last) = fragments.last_mut() {
+                if last.line == line_i && last.span == piece.span
+                && last.byte_range.end == piece.byte_range.start {
+                    last.byte_range.end = piece.byte_range.end;
+                    last.w = piece.x + piece.w - last.x;
+                    continue;
+                }
+            }
+            fragments.push(Fragment {
+                span: piece.span,
+                byte_range: piece.byte_range.clone(),
+                line: line_i,
+                x: piece.x,
+                baseline: baseline,
+                w: piece.w,
+            });
+        }
+        top = baseline;
+    }
+    fragments
+}
+
+/// Remove the whitespace from the end of the current line.
+fn trim_trailing_whitespace(lines: &mut Vec<Line>) {
+    if let Some(line) = lines.last_mut() {
+        while line.pieces.last().map(|p| p.is_whitespace).unwrap_or(false) {
+            line.pieces.pop();
+        }
+    }
+}
+
+/// Begin a new line, trimming the trailing whitespace from the current line.
+///
+/// The `last` character is forgotten, so that the first character of the new line is not kerned.
+fn new_line(lines: &mut Vec<Line>, x: &mut Scalar, last: &mut Option<(char, FontSize)>) {
+    trim_trailing_whitespace(lines);
+    lines.push(Line::new());
+    *x = 0.0;
+    *last = None;
+}
+
+/// Push a new `Piece` onto the current line.
+fn push_piece(lines: &mut Vec<Line>,
+              span: usize,
+              byte_range: std::ops::Range<usize>,
+              x: Scalar,
+              w: Scalar,
+              font_size: FontSize,
+              is_whitespace: bool)
+{
+    lines.last_mut().unwrap().pieces.push(Piece {
+        span: span,
+        byte_range: byte_range,
+        x: x,
+        w: w,
+        font_size: font_size,
+        is_whitespace: is_whitespace,
+    });
+}
+
+
+#[test]
+fn spans_of_differing_sizes_should_share_a_baseline() {
+    use color;
+    let spans = [Span::new("small ", color::RED, 10), Span::new("large", color::BLUE, 20)];
+    // Each character is half as wide as the font size.
+    let fragments = layout(&spans, 200.0, |_, size| size as Scalar / 2.0, |_, _, _| 0.0);
+    assert_eq!(2, fragments.len());
+    assert_eq!(0..6, fragments[0].byte_range);
+    assert_eq!(0..5, fragments[1].byte_range);
+    assert_eq!(0.0, fragments[0].x);
+    assert_eq!(30.0, fragments[1].x);
+    assert_eq!(50.0, fragments[1].w);
+    // The baseline lies below the top by the height of the largest span.
+    assert_eq!(20.0, fragments[0].baseline);
+    assert_eq!(fragments[0].baseline, fragments[1].baseline);
+}
+
+#[test]
+fn spans_should_wrap_together_by_word() {
+    use color;
+    let spans = [Span::new("aa bb", color::RED, 10), Span::new(" cc", color::BLUE, 20)];
+    let fragments = layout(&spans, 60.0, |_, size| size as Scalar / 2.0, |_, _, _| 0.0);
+    // Both spans fit on a single line of width 55.
+    let lines: Vec<(usize, usize, std::ops::Range<usize>)> = fragments.iter()
+        .map(|f| (f.line, f.span, f.byte_range.clone()))
+        .collect();
+    assert_eq!(vec![(0, 0, 0..5), (0, 1, 0..3)], lines);
+
+    let fragments = layout(&spans, 40.0, |_, size| size as Scalar / 2.0, |_, _, _| 0.0);
+    let lines: Vec<(usize, usize, std::ops::Range<usize>)> = fragments.iter()
+        .map(|f| (f.line, f.span, f.byte_range.clone()))
+        .collect();
+    assert_eq!(vec![(0, 0, 0..5), (1, 1, 1..3)], lines);
+    // The first line only contains the small span while the second contains the large span.
+    assert_eq!(10.0, fragments[0].baseline);
+    assert_eq!(30.0, fragments[1].baseline);
+}
+
+#[test]
+fn kerning_should_not_apply_to_the_first_character_of_a_line() {
+    use color;
+    // Each character is 10 wide, while every pair of characters is kerned closer by 2.
+    let advance = |_, _| 10.0;
+    let kerning = |_, _, _| -2.0;
+
+    let spans = [Span::new("ab cd", color::RED, 10)];
+    let fragments = layout(&spans, 100.0, advance, kerning);
+    assert_eq!(1, fragments.len());
+    assert_eq!(42.0, fragments[0].w);
+
+    // The kerning between the whitespace and the wrapped word is discarded.
+    let fragments = layout(&spans, 30.0, advance, kerning);
+    assert_eq!(vec![(0, 0..2, 0.0, 18.0), (1, 3..5, 0.0, 18.0)],
+               fragments.iter()
+                   .map(|f| (f.line, f.byte_range.clone(), f.x, f.w))
+                   .collect::<Vec<_>>());
+
+    // As is the kerning before a newline or a break within a word that is too wide.
+    let spans = [Span::new("ab\ncdef", color::RED, 10)];
+    let fragments = layout(&spans, 30.0, advance, kerning);
+    assert_eq!(vec![(0, 0..2, 0.0, 18.0), (1, 3..6, 0.0, 26.0), (2, 6..7, 0.0, 10.0)],
+               fragments.iter()
+                   .map(|f| (f.line, f.byte_range.clone(), f.x, f.w))
+                   .collect::<Vec<_>>());
+}
@@ -145,7 +145,12 @@ impl<'a, T> Widget for Slider<'a, T>
         use utils::{clamp, map_range, value_from_perc};
 
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let Slider { value, min, max, skew, maybe_label, .. } = self;
+        let Slider { value, min, max, skew, maybe_label, enabled, .. } = self;
+
+        // A disabled slider relinquishes any capture that it held, so that it receives no mouse.
+        if !enabled {
+            ui.release_captures(id);
+        }
 
         let is_horizontal = rect.w() > rect.h();
         let border = style.border(ui.theme());
@@ -209,7 +214,11 @@ impl<'a, T> Widget for Slider<'a, T>
             let y = Range::new(bottom, top);
             Rect { x: x, y: y }
         };
-        let color = interaction_color(ui, style.color(ui.theme()));
+        let color = if enabled {
+            interaction_color(ui, style.color(ui.theme()))
+        } else {
+            ui.theme.disabled_color
+        };
         let slider_xy_offset = [slider_rect.x() - rect.x(), slider_rect.y() - rect.y()];
         widget::Rectangle::fill(slider_rect.dim())
             .xy_relative_to(id, slider_xy_offset)
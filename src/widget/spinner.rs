@@ -0,0 +1,170 @@
+//! A widget for indicating that some operation of unknown duration is in progress.
+
+use {Color, Colorable, Point, Positionable, Scalar, Sizeable, Widget};
+use anim;
+use std;
+use std::f64::consts::PI;
+use ui;
+use widget;
+
+
+/// Displays an arc that continuously rotates clockwise about the centre of the widget, completing
+/// a revolution every `PERIOD` seconds.
+///
+/// The rotation is driven by the `Ui`'s animation clock (see `Ui::now`), so a `Ui` driven via
+/// `Ui::step` rotates the arc by exactly the stepped durations. The `Spinner` requests a redraw
+/// during every update in which it is instantiated, so the animation keeps running for as long
+/// as the `Spinner` is visible.
+#[derive(Clone, Debug)]
+pub struct Spinner {
+    common: widget::CommonBuilder,
+    style: Style,
+}
+
+/// The duration in seconds of a single revolution of the arc.
+pub const PERIOD: Scalar = 1.0;
+
+/// The angle in radians spanned by the arc.
+pub const ARC_LENGTH: Scalar = PI * 1.5;
+
+/// The number of line segments used to approximate the arc.
+pub const ARC_RESOLUTION: usize = 32;
+
+widget_style! {
+    /// Unique styling for the `Spinner`.
+    style Style {
+        /// The color of the arc.
+        - color: Color { theme.shape_color }
+        /// The thickness of the arc.
+        - thickness: Scalar { 4.0 }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        arc,
+    }
+}
+
+/// The state of the `Spinner`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// The moment at which the animation began.
+    animation_start: Option<std::time::Instant>,
+}
+
+
+impl Spinner {
+
+    /// Begin building a `Spinner`.
+    pub fn new() -> Self {
+        Spinner {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+        }
+    }
+
+    builder_methods!{
+        pub thickness { style.thickness = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for Spinner {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            animation_start: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Spinner.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+
+        let now = ui.now();
+        let start = match state.animation_start {
+            Some(start) => start,
+            None => {
+                state.update(|state| state.animation_start = Some(now));
+                now
+            },
+        };
+        let secs = anim::secs(now.duration_since(start));
+        // Keep updating for as long as the spinner is visible.
+        ui::ref_mut_from_ui_cell(ui).request_redraw();
+
+        let thickness = style.thickness(&ui.theme);
+        let radius = (rect.w().min(rect.h()) - thickness).max(0.0) / 2.0;
+        let points = arc_points(rect.xy(), radius, angle(secs));
+        widget::PointPath::new(points)
+            .wh(rect.dim())
+            .xy(rect.xy())
+            .color(style.color(&ui.theme))
+            .thickness(thickness)
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.arc, ui);
+    }
+
+}
+
+
+impl Colorable for Spinner {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// The angle in radians by which the arc has rotated clockwise after the animation has run for
+/// `secs` seconds.
+///
+/// The angle lies within `0.0..2π`, returning to `0.0` at the end of each `PERIOD`.
+pub fn angle(secs: Scalar) -> Scalar {
+    (secs / PERIOD).fract() * PI * 2.0
+}
+
+/// The points of an arc about the given `centre` whose leading end has rotated clockwise from
+/// the top of the circle by the given `angle`.
+///
+/// The points are ordered from the trailing end of the arc to the leading end.
+pub fn arc_points(centre: Point, radius: Scalar, angle: Scalar) -> Vec<Point> {
+    let head = PI / 2.0 - angle;
+    (0..ARC_RESOLUTION + 1)
+        .map(|i| {
+            let theta = head + ARC_LENGTH * (1.0 - i as Scalar / ARC_RESOLUTION as Scalar);
+            [centre[0] + radius * theta.cos(), centre[1] + radius * theta.sin()]
+        })
+        .collect()
+}
+
+
+#[test]
+fn angle_should_advance_with_elapsed_time_and_wrap_each_period() {
+    assert_eq!(0.0, angle(0.0));
+    assert_eq!(PI / 2.0, angle(PERIOD / 4.0));
+    assert_eq!(PI, angle(PERIOD / 2.0));
+    assert_eq!(PI, angle(PERIOD * 2.5));
+    let mut prev = angle(0.0);
+    for i in 1..10 {
+        let next = angle(i as Scalar * PERIOD / 10.0);
+        assert!(next > prev);
+        prev = next;
+    }
+}
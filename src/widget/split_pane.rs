@@ -0,0 +1,252 @@
+//! A container divided into two panes by a divider that may be dragged to resize them.
+
+use {
+    Axis,
+    Color,
+    Colorable,
+    MouseCursor,
+    Positionable,
+    Rect,
+    Scalar,
+    Widget,
+};
+use widget;
+
+
+/// A container divided into two panes along some `Axis` by a draggable divider.
+///
+/// Along `Axis::X` the first pane is on the left and the second on the right, while along
+/// `Axis::Y` the first pane is on top and the second beneath it.
+///
+/// The position of the divider is given as a *ratio*: the fraction of the length available to the
+/// panes (the length of the `SplitPane` less the thickness of the divider) that is occupied by
+/// the first pane. Like the `Slider`, the `SplitPane` does not store its ratio. Instead the user
+/// passes the current ratio and replaces it upon receiving an `Event` with a new `ratio`. As a
+/// result the ratio may be persisted and later restored simply by passing it back.
+///
+/// Each pane may be given a minimum length via `min_lengths`, which is enforced by clamping the
+/// ratio. If the given ratio must be clamped (e.g. a ratio restored from a larger window), the
+/// clamped ratio is produced as though the divider were dragged there.
+pub struct SplitPane {
+    common: widget::CommonBuilder,
+    style: Style,
+    axis: Axis,
+    ratio: Scalar,
+    min_first_len: Scalar,
+    min_second_len: Scalar,
+}
+
+widget_style! {
+    /// Unique styling for the `SplitPane`.
+    style Style {
+        /// The color of each pane.
+        - color: Color { theme.background_color }
+        /// The color of the divider.
+        - divider_color: Color { theme.border_color }
+        /// The thickness of the divider.
+        - divider_thickness: Scalar { 6.0 }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        first,
+        divider,
+        second,
+    }
+}
+
+/// The state of the `SplitPane`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+/// The event produced by the `SplitPane` each update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Event {
+    /// The new ratio, if the divider was dragged or the given ratio had to be clamped.
+    pub ratio: Option<Scalar>,
+    /// The id of the first pane, to be used as the parent of the pane's children.
+    pub first: widget::Id,
+    /// The id of the second pane, to be used as the parent of the pane's children.
+    pub second: widget::Id,
+}
+
+
+impl SplitPane {
+
+    /// Begin building a `SplitPane` divided along the given axis, where the first pane occupies
+    /// the given fraction of the available length.
+    pub fn new(axis: Axis, ratio: Scalar) -> Self {
+        SplitPane {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            axis: axis,
+            ratio: ratio,
+            min_first_len: 0.0,
+            min_second_len: 0.0,
+        }
+    }
+
+    /// The minimum length of each pane along the axis of the split.
+    pub fn min_lengths(mut self, first: Scalar, second: Scalar) -> Self {
+        self.min_first_len = first;
+        self.min_second_len = second;
+        self
+    }
+
+    builder_methods!{
+        pub divider_color { style.divider_color = Some(Color) }
+        pub divider_thickness { style.divider_thickness = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for SplitPane {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the SplitPane.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let SplitPane { axis, ratio, min_first_len, min_second_len, .. } = self;
+
+        let thickness = style.divider_thickness(&ui.theme);
+        let len = match axis {
+            Axis::X => rect.w(),
+            Axis::Y => rect.h(),
+        };
+        let available = (len - thickness).max(0.0);
+
+        // Move the divider by the distance that it was dragged, towards the second pane.
+        let mut new_ratio = clamp_ratio(ratio, available, min_first_len, min_second_len);
+        for drag in ui.widget_input(state.ids.divider).drags().left() {
+            let distance = match axis {
+                Axis::X => drag.delta_xy[0],
+                Axis::Y => -drag.delta_xy[1],
+            };
+            if available > 0.0 {
+                let first_len = new_ratio * available + distance;
+                new_ratio = clamp_ratio(first_len / available,
+                                        available,
+                                        min_first_len,
+                                        min_second_len);
+            }
+        }
+        let first_len = new_ratio * available;
+
+        let (first, divider, second) = match axis {
+            Axis::X => {
+                let first = Rect::from_corners(rect.bottom_left(),
+                                               [rect.left() + first_len, rect.top()]);
+                let divider = Rect::from_corners(first.bottom_right(),
+                                                 [first.right() + thickness, rect.top()]);
+                let second = Rect::from_corners(divider.bottom_right(), rect.top_right());
+                (first, divider, second)
+            },
+            Axis::Y => {
+                let first = Rect::from_corners([rect.left(), rect.top() - first_len],
+                                               rect.top_right());
+                let divider = Rect::from_corners([rect.left(), first.bottom() - thickness],
+                                                 first.bottom_right());
+                let second = Rect::from_corners(rect.bottom_left(), divider.bottom_right());
+                (first, divider, second)
+            },
+        };
+
+        let color = style.color(&ui.theme);
+        let cursor = match axis {
+            Axis::X => MouseCursor::ResizeHorizontal,
+            Axis::Y => MouseCursor::ResizeVertical,
+        };
+        widget::Rectangle::fill(first.dim())
+            .xy(first.xy())
+            .color(color)
+            .crop_kids()
+            .parent(id)
+            .set(state.ids.first, ui);
+        widget::Rectangle::fill(divider.dim())
+            .xy(divider.xy())
+            .color(style.divider_color(&ui.theme))
+            .mouse_cursor(cursor)
+            .parent(id)
+            .set(state.ids.divider, ui);
+        widget::Rectangle::fill(second.dim())
+            .xy(second.xy())
+            .color(color)
+            .crop_kids()
+            .parent(id)
+            .set(state.ids.second, ui);
+
+        Event {
+            ratio: if new_ratio != ratio { Some(new_ratio) } else { None },
+            first: state.ids.first,
+            second: state.ids.second,
+        }
+    }
+
+}
+
+
+impl Colorable for SplitPane {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Clamp the given ratio so that, of the given available length, the first pane is at least
+/// `min_first_len` long and the second pane is at least `min_second_len` long.
+///
+/// Ratios that already satisfy both minimums are returned unchanged. If the available length is
+/// too short for both minimums, the first pane's minimum is preferred.
+pub fn clamp_ratio(ratio: Scalar,
+                   available: Scalar,
+                   min_first_len: Scalar,
+                   min_second_len: Scalar) -> Scalar
+{
+    if available <= 0.0 {
+        return ratio.max(0.0).min(1.0);
+    }
+    let min_first_len = min_first_len.max(0.0).min(available);
+    let max_first_len = (available - min_second_len).max(min_first_len);
+    let first_len = ratio * available;
+    if first_len < min_first_len {
+        min_first_len / available
+    } else if first_len > max_first_len {
+        max_first_len / available
+    } else {
+        ratio
+    }
+}
+
+
+#[test]
+fn clamp_ratio_should_keep_both_panes_at_least_their_minimum_lengths() {
+    assert_eq!(0.5, clamp_ratio(0.5, 100.0, 10.0, 10.0));
+    assert_eq!(0.3, clamp_ratio(0.3, 77.0, 10.0, 10.0));
+    assert_eq!(0.2, clamp_ratio(0.1, 100.0, 20.0, 10.0));
+    assert_eq!(0.7, clamp_ratio(0.9, 100.0, 20.0, 30.0));
+    assert_eq!(0.0, clamp_ratio(-1.0, 100.0, 0.0, 0.0));
+    assert_eq!(1.0, clamp_ratio(2.0, 100.0, 0.0, 0.0));
+    // The first pane's minimum wins when both cannot fit.
+    assert_eq!(0.8, clamp_ratio(0.5, 100.0, 80.0, 80.0));
+}
@@ -1,3 +1,16 @@
+/// Resolve a single style value, where an explicitly set `style_field` always takes precedence
+/// over the given `theme_field`.
+///
+/// A field counts as explicitly set if it is `Some`, even if the value is equal to that of the
+/// `theme_field`.
+pub fn resolve<T>(style_field: Option<T>, theme_field: T) -> T {
+    match style_field {
+        Some(value) => value,
+        None => theme_field,
+    }
+}
+
+
 /// Defines a struct called `$Style`.
 ///
 /// Each given `$field_name` `$FieldType` pair will be defined as `Option` fields.
@@ -81,6 +94,34 @@ macro_rules! default_widget_style_struct {
 
 }
 
+/// Implements the `merge` method for the `$Style` type.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_widget_style_merge {
+    (
+        $Style:ident {
+            $(
+                $(#[$field_attr:meta])*
+                - $field_name:ident: $FieldType:ty { $($default:tt)* }
+             )*
+        }
+    ) => {
+        impl $Style {
+            /// Layer `self` over the given `fallback` style.
+            ///
+            /// Every field that is explicitly set (`Some`) within `self` is retained, while every
+            /// unset field (`None`) is taken from the `fallback`.
+            pub fn merge(self, fallback: Self) -> Self {
+                $Style {
+                    $(
+                        $field_name: self.$field_name.or(fallback.$field_name),
+                    )*
+                }
+            }
+        }
+    };
+}
+
 /// Implements the static method `new` for the `$Style` type.
 #[doc(hidden)]
 #[macro_export]
@@ -104,11 +145,11 @@ macro_rules! impl_widget_style_retrieval_method {
         ///
         /// If the `Style`'s field is `None`, falls back to default specified within the `Theme`.
         pub fn $field_name(&self, theme: &$crate::Theme) -> $FieldType {
-            self.$field_name
+            let style_field = self.$field_name
                 .or_else(|| theme.widget_style::<Self>().and_then(|default| {
                     default.style.$field_name
-                }))
-                .unwrap_or_else(|| theme.$($theme_field).+)
+                }));
+            $crate::widget::resolve(style_field, theme.$($theme_field).+)
         }
     };
     ($field_name:ident: $FieldType:ty { $default:expr }) => {
@@ -182,8 +223,9 @@ macro_rules! impl_widget_style_retrieval_methods {
 /// An invocation of the macro expands into two things:
 ///
 /// 1. A struct definition with the given name following the `style` token.
-/// 2. An `impl Style` block with a `new` constructor as well as a style retrieval method for each
-///    given field. These retrieval methods do the following:
+/// 2. An `impl Style` block with a `new` constructor, a `merge` method for layering one `Style`
+///    over another as well as a style retrieval method for each given field. These retrieval
+///    methods do the following:
 ///
 ///    1. Attempt to use the value at the field.
 ///    2. If the field is `None`, attempts to retreive a default from the `widget_styling` map in
@@ -262,16 +304,27 @@ macro_rules! impl_widget_style_retrieval_methods {
 ///             label_color: None,
 ///         }
 ///     }
+///
+///     /// Layer `self` over the given `fallback` style.
+///     ///
+///     /// Every field that is explicitly set (`Some`) within `self` is retained, while every
+///     /// unset field (`None`) is taken from the `fallback`.
+///     pub fn merge(self, fallback: Self) -> Self {
+///         Style {
+///             color: self.color.or(fallback.color),
+///             label_color: self.label_color.or(fallback.label_color),
+///         }
+///     }
 /// 
 ///     /// Retrieves the value from the `Style`.
 ///     ///
 ///     /// If the `Style`'s field is `None`, falls back to default specified within the `Theme`.
 ///     pub fn color(&self, theme: &conrod::Theme) -> conrod::Color {
-///         self.color
+///         let style_field = self.color
 ///             .or_else(|| theme.widget_style::<Self>().and_then(|default| {
 ///                 default.style.color
-///             }))
-///             .unwrap_or_else(|| theme.shape_color)
+///             }));
+///         conrod::widget::resolve(style_field, theme.shape_color)
 ///     }
 ///
 ///     /// Retrieves the value from the `Style`.
@@ -313,9 +366,53 @@ macro_rules! widget_style {
         // The `new` method for the `Style` struct.
         impl_widget_style_new!($Style { $($fields)* });
 
+        // The `merge` method for layering one `Style` over another.
+        impl_widget_style_merge!($Style { $($fields)* });
+
         // The "field, theme or default" retrieval methods.
         impl $Style {
             impl_widget_style_retrieval_methods!($($fields)*);
         }
     };
 }
+
+
+#[test]
+fn resolve_should_prefer_explicitly_set_style_fields() {
+    assert_eq!(5, resolve(Some(5), 10));
+    assert_eq!(10, resolve(None, 10));
+    // A value equal to the theme's still counts as explicitly set.
+    assert_eq!(10, resolve(Some(10), 10));
+}
+
+#[test]
+fn style_overrides_should_win_and_unset_fields_should_fall_back() {
+    use color;
+    use theme::Theme;
+    use widget::toggle::Style;
+
+    let theme = Theme::builder()
+        .shape_color(color::RED)
+        .border_width(3.0)
+        .build();
+
+    let mut style = Style::new();
+    style.color = Some(color::BLUE);
+    style.border = Some(theme.border_width);
+
+    assert_eq!(color::BLUE, style.color(&theme));
+    assert_eq!(3.0, style.border(&theme));
+    assert_eq!(theme.border_color, style.border_color(&theme));
+
+    let mut fallback = Style::new();
+    fallback.color = Some(color::GREEN);
+    fallback.border = Some(1.0);
+    fallback.label_color = Some(color::PURPLE);
+
+    // Explicitly set fields should be retained even if they are equal to the theme's value.
+    let merged = style.merge(fallback);
+    assert_eq!(Some(color::BLUE), merged.color);
+    assert_eq!(Some(3.0), merged.border);
+    assert_eq!(Some(color::PURPLE), merged.label_color);
+    assert_eq!(None, merged.border_color);
+}
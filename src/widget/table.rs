@@ -0,0 +1,379 @@
+//! A widget for displaying rows of data beneath a header of sortable columns.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Labelable,
+    Positionable,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use std::cmp::Ordering;
+use text;
+use widget;
+
+
+/// Displays a grid of cells, where each row holds one cell for each of the given columns.
+///
+/// Clicking the header of a column sorts the rows by the cells within that column, first in
+/// ascending order and then, upon subsequent clicks, toggling between descending and ascending
+/// order. Cells that parse as numbers are compared numerically and precede all other cells, which
+/// are compared as text.
+///
+/// Sorting is stable, so rows with equal cells in the sorted column retain their given order.
+pub struct Table<'a, S: 'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    columns: &'a [S],
+    rows: &'a [Vec<S>],
+}
+
+/// The order in which the rows of a `Table` are sorted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// From the least cell to the greatest.
+    Ascending,
+    /// From the greatest cell to the least.
+    Descending,
+}
+
+/// The column by which the rows of a `Table` are sorted, along with the order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sort {
+    /// The index of the column.
+    pub column: usize,
+    /// The order in which the cells of the column are sorted.
+    pub order: Order,
+}
+
+widget_style! {
+    /// Unique styling for the `Table`.
+    style Style {
+        /// The height of the header and of each row.
+        - row_h: Scalar { 24.0 }
+        /// The color behind the rows.
+        - color: Color { theme.background_color }
+        /// The color of the column headers.
+        - header_color: Color { theme.shape_color }
+        /// The color of the cell text and of the sort indicator.
+        - label_color: Color { theme.label_color }
+        /// The font size of the cell text and headers.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display the cell text and headers.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        headers[],
+        sort_indicator,
+        cells[],
+    }
+}
+
+/// The state of the `Table`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    sort: Option<Sort>,
+}
+
+
+impl Order {
+
+    /// The opposite order.
+    pub fn toggled(self) -> Self {
+        match self {
+            Order::Ascending => Order::Descending,
+            Order::Descending => Order::Ascending,
+        }
+    }
+
+}
+
+
+impl Sort {
+
+    /// The sort that results from clicking the header of the given column.
+    ///
+    /// Clicking the sorted column toggles the order, while clicking any other column sorts by that
+    /// column in ascending order.
+    pub fn clicked(sort: Option<Sort>, column: usize) -> Sort {
+        let order = match sort {
+            Some(sort) if sort.column == column => sort.order.toggled(),
+            _ => Order::Ascending,
+        };
+        Sort { column: column, order: order }
+    }
+
+}
+
+
+/// Compare two cells, numerically if both parse as numbers or otherwise as text.
+///
+/// This is a total order: numeric cells precede text cells, and numbers are ordered as per
+/// `total_cmp`, so that `NaN`s are ordered consistently rather than comparing equal to everything.
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => total_cmp(a, b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// A total order over `f64`s, equivalent to the IEEE 754 `totalOrder` predicate.
+///
+/// Negative `NaN`s precede `-inf`, positive `NaN`s follow `inf` and `-0.0` precedes `0.0`.
+pub fn total_cmp(a: f64, b: f64) -> Ordering {
+    // Flipping all but the sign bit of negative numbers orders the bits as signed integers.
+    let key = |f: f64| {
+        let bits = f.to_bits() as i64;
+        bits ^ (((bits >> 63) as u64) >> 1) as i64
+    };
+    key(a).cmp(&key(b))
+}
+
+/// The indices of the given rows in the order in which they should be displayed for the given
+/// sort.
+///
+/// The sort is stable in both orders, so rows with equal cells retain their relative order. Rows
+/// that have no cell in the sorted column are treated as though the cell were empty.
+pub fn sorted_rows<S>(rows: &[Vec<S>], sort: Option<Sort>) -> Vec<usize>
+    where S: AsRef<str>,
+{
+    let mut indices: Vec<usize> = (0..rows.len()).collect();
+    if let Some(Sort { column, order }) = sort {
+        let cell = |row: usize| rows[row].get(column).map(|cell| cell.as_ref()).unwrap_or("");
+        // `sort_by` is stable, so equal rows are never swapped regardless of the order.
+        indices.sort_by(|&a, &b| match order {
+            Order::Ascending => compare_cells(cell(a), cell(b)),
+            Order::Descending => compare_cells(cell(b), cell(a)),
+        });
+    }
+    indices
+}
+
+
+impl<'a, S> Table<'a, S> {
+
+    /// Begin building a `Table` with the given column headers and rows of cells.
+    pub fn new(columns: &'a [S], rows: &'a [Vec<S>]) -> Self {
+        Table {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            columns: columns,
+            rows: rows,
+        }
+    }
+
+    /// Specify the font used for displaying the cell text and headers.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub row_h { style.row_h = Some(Scalar) }
+        pub header_color { style.header_color = Some(Color) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a, S> Widget for Table<'a, S>
+    where S: AsRef<str>,
+{
+    type State = State;
+    type Style = Style;
+    /// The new sort, if a column header was clicked during the update.
+    type Event = Option<Sort>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            sort: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Table.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Table { columns, rows, .. } = self;
+        let num_columns = columns.len();
+        let num_cells = num_columns * rows.len();
+
+        if state.ids.headers.len() < num_columns || state.ids.cells.len() < num_cells {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.headers.resize(num_columns, id_gen);
+                state.ids.cells.resize(num_cells, id_gen);
+            });
+        }
+
+        let color = style.color(&ui.theme);
+        widget::Rectangle::fill(rect.dim())
+            .xy(rect.xy())
+            .color(color)
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.rectangle, ui);
+
+        if num_columns == 0 {
+            return None;
+        }
+
+        let row_h = style.row_h(&ui.theme);
+        let column_w = rect.w() / num_columns as Scalar;
+        let header_color = style.header_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+
+        // Instantiate a header `Button` for each column, sorting by the column when clicked.
+        let mut sort = state.sort;
+        for (i, column) in columns.iter().enumerate() {
+            let mut button = widget::Button::new()
+                .label(column.as_ref())
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .color(header_color)
+                .w_h(column_w, row_h)
+                .top_left_with_margins_on(id, 0.0, i as Scalar * column_w)
+                .parent(id);
+            button.style.label_font_id = Some(font_id);
+            for _click in button.set(state.ids.headers[i], ui) {
+                sort = Some(Sort::clicked(sort, i));
+            }
+        }
+
+        let event = if sort != state.sort {
+            state.update(|state| state.sort = sort);
+            sort
+        } else {
+            None
+        };
+
+        // Indicate the sorted column with a triangle pointing up for ascending order or down for
+        // descending order.
+        match sort {
+            Some(Sort { column, order }) if column < num_columns => {
+                let r = (row_h / 6.0).max(1.0);
+                let points = match order {
+                    Order::Ascending => vec![[-r, -r], [r, -r], [0.0, r]],
+                    Order::Descending => vec![[-r, r], [r, r], [0.0, -r]],
+                };
+                let x = column_w / 2.0 - row_h / 2.0;
+                widget::Polygon::centred_fill(points)
+                    .x_y_relative_to(state.ids.headers[column], x, 0.0)
+                    .color(label_color)
+                    .graphics_for(state.ids.headers[column])
+                    .parent(id)
+                    .set(state.ids.sort_indicator, ui);
+            },
+            _ => (),
+        }
+
+        // Instantiate the text of each cell in order of display.
+        let text_padding = 4.0;
+        for (row_idx, &i) in sorted_rows(rows, sort).iter().enumerate() {
+            let top = (row_idx + 1) as Scalar * row_h;
+            for (column, cell) in rows[i].iter().take(num_columns).enumerate() {
+                let cell_id = state.ids.cells[row_idx * num_columns + column];
+                widget::Text::new(cell.as_ref())
+                    .and_then(font_id, widget::Text::font_id)
+                    .w(column_w - text_padding * 2.0)
+                    .top_left_with_margins_on(id, top, column as Scalar * column_w + text_padding)
+                    .color(label_color)
+                    .font_size(font_size)
+                    .no_line_wrap()
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(cell_id, ui);
+            }
+        }
+
+        event
+    }
+
+}
+
+
+impl<'a, S> Colorable for Table<'a, S> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+#[test]
+fn sorted_rows_should_sort_stably_in_ascending_then_descending_order() {
+    let rows = vec![
+        vec!["Carol", "30"],
+        vec!["Alice", "25"],
+        vec!["Bob", "30"],
+        vec!["Dave", "9"],
+    ];
+    let ascending = Some(Sort::clicked(None, 1));
+    assert_eq!(Some(Order::Ascending), ascending.map(|s| s.order));
+    // Numeric cells are compared as numbers and equal cells retain their given order.
+    assert_eq!(vec![3, 1, 0, 2], sorted_rows(&rows, ascending));
+    let descending = Some(Sort::clicked(ascending, 1));
+    assert_eq!(Some(Order::Descending), descending.map(|s| s.order));
+    assert_eq!(vec![0, 2, 1, 3], sorted_rows(&rows, descending));
+    // Sorting by another column begins in ascending order.
+    let by_name = Some(Sort::clicked(descending, 0));
+    assert_eq!(vec![1, 2, 0, 3], sorted_rows(&rows, by_name));
+    assert_eq!(vec![0, 1, 2, 3], sorted_rows(&rows, None));
+}
+
+#[test]
+fn sorted_rows_should_order_numbers_before_text_in_mixed_columns() {
+    let rows = vec![
+        vec!["n/a"],
+        vec!["10"],
+        vec!["NaN"],
+        vec!["-inf"],
+        vec!["abc"],
+        vec!["9"],
+        vec![""],
+    ];
+    let ascending = Some(Sort::clicked(None, 0));
+    assert_eq!(vec![3, 5, 1, 2, 6, 4, 0], sorted_rows(&rows, ascending));
+    let descending = Some(Sort::clicked(ascending, 0));
+    assert_eq!(vec![0, 4, 6, 2, 1, 5, 3], sorted_rows(&rows, descending));
+}
+
+#[test]
+fn compare_cells_should_be_a_total_order() {
+    let cells = ["-1", "NaN", "0", "-0", "1e3", "x", "", "inf"];
+    for &a in &cells {
+        assert_eq!(Ordering::Equal, compare_cells(a, a));
+        for &b in &cells {
+            assert_eq!(compare_cells(a, b), compare_cells(b, a).reverse(), "{:?} {:?}", a, b);
+            for &c in &cells {
+                if compare_cells(a, b) != Ordering::Greater
+                && compare_cells(b, c) != Ordering::Greater {
+                    assert!(compare_cells(a, c) != Ordering::Greater, "{:?} {:?} {:?}", a, b, c);
+                }
+            }
+        }
+    }
+    assert_eq!(Ordering::Greater, compare_cells("NaN", "inf"));
+    assert_eq!(Ordering::Less, compare_cells("NaN", "abc"));
+}
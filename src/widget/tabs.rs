@@ -9,6 +9,7 @@ use {
     Scalar,
     Widget,
 };
+use input;
 use std;
 use text;
 use utils;
@@ -16,11 +17,19 @@ use widget;
 
 
 /// A wrapper around a list of `Canvas`ses that displays them as a list of selectable tabs.
+///
+/// A tab is selected by clicking it. While the `Tabs` (or one of its tabs) is capturing the
+/// keyboard, `Ctrl+Tab` selects the next tab and `Ctrl+Shift+Tab` the previous one, wrapping
+/// around at either end.
+///
+/// While disabled, the selected tab may not be changed and the tab buttons are dimmed.
 pub struct Tabs<'a> {
     tabs: &'a [(widget::Id, &'a str)],
     style: Style,
     common: widget::CommonBuilder,
     maybe_starting_tab_idx: Option<usize>,
+    /// Whether or not user input is enabled.
+    enabled: bool,
 }
 
 /// The state to be cached within the Canvas.
@@ -80,6 +89,7 @@ impl<'a> Tabs<'a> {
             tabs: tabs,
             style: Style::new(),
             maybe_starting_tab_idx: None,
+            enabled: true,
         }
     }
 
@@ -156,6 +166,7 @@ impl<'a> Tabs<'a> {
         pub starting_tab_idx { maybe_starting_tab_idx = Some(usize) }
         pub label_color { style.label_color = Some(Color) }
         pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub enabled { enabled = bool }
     }
 
 }
@@ -217,7 +228,13 @@ impl<'a> Widget for Tabs<'a> {
     /// Update the state of the Tabs.
     fn update(self, args: widget::UpdateArgs<Self>) {
         let widget::UpdateArgs { id, state, rect, style, mut ui, .. } = args;
-        let Tabs { tabs, maybe_starting_tab_idx, .. } = self;
+        let Tabs { tabs, maybe_starting_tab_idx, enabled, .. } = self;
+
+        // Disabled `Tabs` ignore all input and relinquish any capture that they held.
+        if !enabled {
+            ui.release_captures(id);
+        }
+
         let layout = style.layout(&ui.theme);
         let font_size = style.label_font_size(&ui.theme);
         let canvas_style = style.canvas(&ui.theme);
@@ -270,6 +287,24 @@ impl<'a> Widget for Tabs<'a> {
             let mut maybe_selected_tab_idx = state.maybe_selected_tab_idx
                 .or(maybe_starting_tab_idx)
                 .or_else(|| if tabs.len() > 0 { Some(0) } else { None });
+
+            // While the `Tabs` or one of its tab buttons is capturing the keyboard, `Ctrl+Tab`
+            // selects the next tab and `Ctrl+Shift+Tab` selects the previous tab.
+            let focus_ids = std::iter::once(id).chain(state.tabs.iter().map(|tab| tab.button_id));
+            for focus_id in focus_ids.filter(|_| enabled) {
+                for press in ui.widget_input(focus_id).presses().key() {
+                    if press.key != input::Key::Tab
+                    || !press.modifiers.contains(input::keyboard::CTRL) {
+                        continue;
+                    }
+                    maybe_selected_tab_idx = if press.modifiers.contains(input::keyboard::SHIFT) {
+                        previous_tab_idx(maybe_selected_tab_idx, tabs.len())
+                    } else {
+                        next_tab_idx(maybe_selected_tab_idx, tabs.len())
+                    };
+                }
+            }
+
             let mut tab_rects = TabRects::new(tabs, layout, rel_tab_bar_rect);
             let mut i = 0;
             while let Some((tab_rect, _, label)) = tab_rects.next_with_id_and_label() {
@@ -286,6 +321,7 @@ impl<'a> Widget for Tabs<'a> {
                     .border_color(border_color)
                     .label(label)
                     .label_color(label_color)
+                    .enabled(enabled)
                     .parent(id)
                     .set(tab.button_id, &mut ui)
                     .was_clicked()
@@ -320,6 +356,33 @@ impl<'a> Widget for Tabs<'a> {
 }
 
 
+/// The index of the tab following the selected tab, wrapping from the last tab to the first.
+///
+/// Returns `None` if there are no tabs.
+pub fn next_tab_idx(maybe_selected_idx: Option<usize>, num_tabs: usize) -> Option<usize> {
+    if num_tabs == 0 {
+        return None;
+    }
+    match maybe_selected_idx {
+        Some(idx) => Some((idx + 1) % num_tabs),
+        None => Some(0),
+    }
+}
+
+/// The index of the tab preceding the selected tab, wrapping from the first tab to the last.
+///
+/// Returns `None` if there are no tabs.
+pub fn previous_tab_idx(maybe_selected_idx: Option<usize>, num_tabs: usize) -> Option<usize> {
+    if num_tabs == 0 {
+        return None;
+    }
+    match maybe_selected_idx {
+        Some(idx) if idx > 0 && idx < num_tabs => Some(idx - 1),
+        _ => Some(num_tabs - 1),
+    }
+}
+
+
 /// Calculate the max text width yielded by a string in the tabs slice.
 fn max_text_width<'a, I>(tabs: I, font_size: FontSize, font: &text::Font) -> Scalar
     where I: Iterator<Item=&'a (widget::Id, &'a str)>,
@@ -446,3 +509,21 @@ impl<'a> TabRects<'a> {
     }
 
 }
+
+
+#[test]
+fn next_tab_idx_should_wrap_from_the_last_tab_to_the_first() {
+    assert_eq!(Some(1), next_tab_idx(Some(0), 3));
+    assert_eq!(Some(2), next_tab_idx(Some(1), 3));
+    assert_eq!(Some(0), next_tab_idx(Some(2), 3));
+    assert_eq!(Some(0), next_tab_idx(None, 3));
+    assert_eq!(None, next_tab_idx(Some(0), 0));
+}
+
+#[test]
+fn previous_tab_idx_should_wrap_from_the_first_tab_to_the_last() {
+    assert_eq!(Some(1), previous_tab_idx(Some(2), 3));
+    assert_eq!(Some(2), previous_tab_idx(Some(0), 3));
+    assert_eq!(Some(2), previous_tab_idx(None, 3));
+    assert_eq!(None, previous_tab_idx(None, 0));
+}
@@ -6,6 +6,7 @@ use {
     Colorable,
     FontSize,
     Borderable,
+    MouseCursor,
     Positionable,
     Range,
     Rect,
@@ -25,7 +26,11 @@ use widget;
 pub struct TextBox<'a> {
     common: widget::CommonBuilder,
     text: &'a str,
+    is_password: bool,
+    is_revealed: bool,
     style: Style,
+    /// Whether or not user input is enabled.
+    enabled: bool,
 }
 
 widget_style!{
@@ -72,10 +77,29 @@ impl<'a> TextBox<'a> {
         TextBox {
             common: widget::CommonBuilder::new(),
             text: text,
+            is_password: false,
+            is_revealed: false,
             style: Style::new(),
+            enabled: true,
         }
     }
 
+    /// Whether or not the text is a password, in which case each character is masked.
+    ///
+    /// See `TextEdit::password` for details.
+    pub fn password(mut self, is_password: bool) -> Self {
+        self.is_password = is_password;
+        self
+    }
+
+    /// Whether or not to reveal the real characters of a password.
+    ///
+    /// See `TextEdit::reveal` for details.
+    pub fn reveal(mut self, is_revealed: bool) -> Self {
+        self.is_revealed = is_revealed;
+        self
+    }
+
     /// Align the text to the left of its bounding **Rect**'s *x* axis range.
     pub fn align_text_left(self) -> Self {
         self.x_align_text(Align::Start)
@@ -102,6 +126,7 @@ impl<'a> TextBox<'a> {
         pub font_size { style.font_size = Some(FontSize) }
         pub x_align_text { style.x_align = Some(Align) }
         pub pad_text { style.text_padding = Some(Scalar) }
+        pub enabled { enabled = bool }
     }
 
 }
@@ -138,10 +163,20 @@ impl<'a> Widget for TextBox<'a> {
         self.style.clone()
     }
 
+    fn cursor_hint(&self) -> Option<MouseCursor> {
+        self.common.maybe_cursor_hint.or(Some(MouseCursor::Text))
+    }
+
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, mut ui, .. } = args;
-        let TextBox { text, .. } = self;
+        let TextBox { text, is_password, is_revealed, enabled, .. } = self;
+
+        // A disabled text box relinquishes any capture that it held, while its `TextEdit` ignores
+        // all input and is dimmed.
+        if !enabled {
+            ui.release_captures(id);
+        }
 
         let font_size = style.font_size(ui.theme());
         let border = style.border(ui.theme());
@@ -178,6 +213,9 @@ impl<'a> Widget for TextBox<'a> {
             .font_size(font_size)
             .color(text_color)
             .x_align_text(x_align)
+            .password(is_password)
+            .reveal(is_revealed)
+            .enabled(enabled)
             .parent(id)
             .set(state.ids.text_edit, ui)
         {
@@ -189,7 +227,7 @@ impl<'a> Widget for TextBox<'a> {
         // TODO: We should probably be doing this via the `TextEdit` widget.
         for widget_event in ui.widget_input(state.ids.text_edit).events() {
             match widget_event {
-                event::Widget::Press(press) => match press.button {
+                event::Widget::Press(press) if enabled => match press.button {
                     event::Button::Keyboard(key) => match key {
                         input::Key::Return => events.push(Event::Enter),
                         _ => (),
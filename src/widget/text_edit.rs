@@ -6,12 +6,14 @@ use {
     Colorable,
     Dimension,
     FontSize,
+    MouseCursor,
     Point,
     Positionable,
     Range,
     Rect,
     Scalar,
     Sizeable,
+    Theme,
     Widget,
     Ui,
 };
@@ -19,6 +21,7 @@ use event;
 use input;
 use std;
 use text;
+use undo::UndoStack;
 use utils;
 use widget;
 use widget::primitive::text::Wrap;
@@ -31,7 +34,11 @@ use widget::primitive::text::Wrap;
 pub struct TextEdit<'a> {
     common: widget::CommonBuilder,
     text: &'a str,
+    is_password: bool,
+    is_revealed: bool,
     style: Style,
+    /// Whether or not user input is enabled.
+    enabled: bool,
 }
 
 widget_style!{
@@ -53,17 +60,23 @@ widget_style!{
         - restrict_to_height: bool { true }
         /// The font used for the `Text`.
         - font_id: Option<text::font::Id> { theme.font_id }
+        /// The character displayed in place of each character of a password.
+        - mask_char: char { '\u{2022}' }
     }
 }
 
 widget_ids! {
     struct Ids {
         selected_rectangles[],
+        composition_underlines[],
         text,
         cursor,
     }
 }
 
+/// The thickness of the line drawn beneath text that is being composed via an input method editor.
+pub const COMPOSITION_UNDERLINE_THICKNESS: Scalar = 1.0;
+
 /// The State of the TextEdit widget that will be cached within the Ui.
 pub struct State {
     cursor: Cursor,
@@ -71,6 +84,11 @@ pub struct State {
     drag: Option<Drag>,
     /// Information about each line of text.
     line_infos: Vec<text::line::Info>,
+    /// The text and cursor prior to each edit, for undoing and redoing via `Ctrl+z`/`Ctrl+y`.
+    undo_stack: UndoStack<(String, Cursor)>,
+    /// The text being composed via an input method editor, displayed at the cursor until it is
+    /// committed.
+    composition: Option<String>,
     ids: Ids,
 }
 
@@ -109,10 +127,32 @@ impl<'a> TextEdit<'a> {
         TextEdit {
             common: widget::CommonBuilder::new(),
             text: text,
+            is_password: false,
+            is_revealed: false,
             style: Style::new(),
+            enabled: true,
         }
     }
 
+    /// Whether or not the text is a password, in which case each character is displayed as the
+    /// `mask_char` rather than its real glyph.
+    ///
+    /// The cursor and selection still refer to the real characters behind the mask. Password text
+    /// may not be copied or cut to the clipboard.
+    pub fn password(mut self, is_password: bool) -> Self {
+        self.is_password = is_password;
+        self
+    }
+
+    /// Whether or not to reveal the real characters of a password, e.g. while the user holds down
+    /// a "show password" toggle.
+    ///
+    /// This has no effect unless the `TextEdit` is a `password`.
+    pub fn reveal(mut self, is_revealed: bool) -> Self {
+        self.is_revealed = is_revealed;
+        self
+    }
+
     /// The `TextEdit` will wrap text via the whitespace that precedes the first width-exceeding
     /// character.
     ///
@@ -170,6 +210,15 @@ impl<'a> TextEdit<'a> {
         self
     }
 
+    /// The char displayed in place of each char of the text, if the text is a hidden password.
+    fn mask(&self, theme: &Theme) -> Option<char> {
+        if self.is_password && !self.is_revealed {
+            Some(self.style.mask_char(theme))
+        } else {
+            None
+        }
+    }
+
     builder_methods!{
         pub font_size { style.font_size = Some(FontSize) }
         pub x_align_text { style.x_align = Some(Align) }
@@ -177,6 +226,8 @@ impl<'a> TextEdit<'a> {
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub mask_char { style.mask_char = Some(char) }
+        pub enabled { enabled = bool }
     }
 
 }
@@ -203,6 +254,8 @@ impl<'a> Widget for TextEdit<'a> {
             cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
             drag: None,
             line_infos: Vec::new(),
+            undo_stack: UndoStack::new(),
+            composition: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -211,6 +264,10 @@ impl<'a> Widget for TextEdit<'a> {
         self.style.clone()
     }
 
+    fn cursor_hint(&self) -> Option<MouseCursor> {
+        self.common.maybe_cursor_hint.or(Some(MouseCursor::Text))
+    }
+
     fn default_y_dimension(&self, ui: &Ui) -> Dimension {
         // If the user has specified `restrict_to_height = true`, then we should infer the height
         // using the previous widget as is the default case.
@@ -228,7 +285,8 @@ impl<'a> Widget for TextEdit<'a> {
             None => return Dimension::Absolute(0.0),
         };
 
-        let text = &self.text;
+        let mask = self.mask(&ui.theme);
+        let text = &masked(self.text, mask);
         let font_size = self.style.font_size(&ui.theme);
         let num_lines = match self.get_w(ui) {
             None => text.lines().count(),
@@ -251,9 +309,19 @@ impl<'a> Widget for TextEdit<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, mut ui, .. } = args;
-        let TextEdit { text, .. } = self;
+        let mask = self.mask(ui.theme());
+        let TextEdit { text, is_password, enabled, .. } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
+        // A disabled `TextEdit` ignores all input, relinquishes any capture that it held and
+        // abandons any composition in progress.
+        if !enabled {
+            ui.release_captures(id);
+            if state.composition.is_some() {
+                state.update(|state| state.composition = None);
+            }
+        }
+
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
         //
         // If we've no font to use for text logic, bail out without updating.
@@ -293,7 +361,8 @@ impl<'a> Widget for TextEdit<'a> {
             let maybe_new_line_infos = {
                 let line_info_slice = &state.line_infos[..];
                 let font = ui.fonts.get(font_id).unwrap();
-                let new_line_infos = line_infos(&text, font, font_size, line_wrap, rect.w());
+                let layout_text = masked(&text, mask);
+                let new_line_infos = line_infos(&layout_text, font, font_size, line_wrap, rect.w());
                 match utils::write_if_different(line_info_slice, new_line_infos) {
                     std::borrow::Cow::Owned(new) => Some(new),
                     _ => None,
@@ -349,8 +418,27 @@ impl<'a> Widget for TextEdit<'a> {
             })
         };
 
-        let mut cursor = state.cursor;
-        let mut drag = state.drag;
+        // The text may have shrunk since the cursor was placed (e.g. if the last edit was rejected
+        // by the user), in which case the cursor is clamped to the end of the text.
+        let mut cursor = {
+            let line_infos = &state.line_infos;
+            let clamp = |idx: text::cursor::Index| {
+                match text::glyph::index_after_cursor(line_infos.iter().cloned(), idx) {
+                    Some(_) => idx,
+                    None => {
+                        let num_chars = text.chars().count();
+                        text::cursor::index_before_char(line_infos.iter().cloned(), num_chars)
+                            .unwrap_or(text::cursor::Index { line: 0, char: 0 })
+                    },
+                }
+            };
+            match state.cursor {
+                Cursor::Idx(idx) => Cursor::Idx(clamp(idx)),
+                Cursor::Selection { start, end } =>
+                    Cursor::Selection { start: clamp(start), end: clamp(end) },
+            }
+        };
+        let mut drag = if enabled { state.drag } else { None };
 
         // Insert the given `string` at the given `cursor` position within the given `text`.
         //
@@ -393,7 +481,7 @@ impl<'a> Widget for TextEdit<'a> {
 
             // Calculate the new `line_infos` for the `new_text`.
             let new_line_infos: Vec<_> = {
-                line_infos(&new_text, font, font_size, line_wrap, rect.w()).collect()
+                line_infos(&masked(&new_text, mask), font, font_size, line_wrap, rect.w()).collect()
             };
 
             // Check that the new text would not exceed the `inner_rect` bounds.
@@ -424,7 +512,19 @@ impl<'a> Widget for TextEdit<'a> {
         //     - begin dragging selected text.
         // - Left mouse `Drag` for extending the end of the selection, or for dragging selected text.
         // - Key presses for cursor movement.
-        'events: for widget_event in ui.widget_input(id).events() {
+        // - `Ctrl+c`, `Ctrl+x` and `Ctrl+v` for copying, cutting and pasting via the clipboard.
+        //
+        // The events are collected so that the clipboard may be borrowed while handling them.
+        let widget_events: Vec<_> = if enabled {
+            ui.widget_input(id).events().collect()
+        } else {
+            Vec::new()
+        };
+        'events: for widget_event in widget_events {
+            let layout_text = masked(&text, mask);
+            let is_text = match widget_event { event::Widget::Text(_) => true, _ => false };
+            let prev_cursor = cursor;
+
             match widget_event {
 
                 event::Widget::Press(press) => match press.button {
@@ -435,7 +535,8 @@ impl<'a> Widget for TextEdit<'a> {
                         let abs_xy = utils::vec2_add(rel_xy, rect.xy());
                         let infos = &state.line_infos;
                         let font = ui.fonts.get(font_id).unwrap();
-                        let closest = closest_cursor_index_and_xy(abs_xy, &text, infos, font);
+                        let closest =
+                            closest_cursor_index_and_xy(abs_xy, &layout_text, infos, font);
                         if let Some((closest_cursor, _)) = closest {
                             cursor = Cursor::Idx(closest_cursor);
                         }
@@ -459,16 +560,16 @@ impl<'a> Widget for TextEdit<'a> {
 
                                     let end = match (key, delete_word) {
                                         (input::Key::Backspace, false) => {
-                                            cursor_idx.previous(line_infos)
+                                            cursor_idx.previous_grapheme(&layout_text, line_infos)
                                         }
                                         (input::Key::Backspace, true) => {
-                                            cursor_idx.previous_word_start(&text, line_infos)
+                                            cursor_idx.previous_word_start(&layout_text, line_infos)
                                         }
                                         (input::Key::Delete, false) => {
-                                            cursor_idx.next(line_infos)
+                                            cursor_idx.next_grapheme(&layout_text, line_infos)
                                         }
                                         (input::Key::Delete, true) => {
-                                            cursor_idx.next_word_end(&text, line_infos)
+                                            cursor_idx.next_word_end(&layout_text, line_infos)
                                         }
                                         _ => unreachable!(),
                                     }.unwrap_or(cursor_idx);
@@ -496,6 +597,10 @@ impl<'a> Widget for TextEdit<'a> {
                                                                     new_cursor_char_idx)
                                         .expect("char index was out of range")
                                 };
+                                if start_idx != end_idx {
+                                    let snapshot = (text.to_string(), cursor);
+                                    state.update(|state| state.undo_stack.push(snapshot));
+                                }
                                 cursor = Cursor::Idx(new_cursor_idx);
                                 *text.to_mut() = text.chars().take(start_idx)
                                     .chain(text.chars().skip(end_idx))
@@ -503,6 +608,7 @@ impl<'a> Widget for TextEdit<'a> {
                                 state.update(|state| {
                                     let font = ui.fonts.get(font_id).unwrap();
                                     let w = rect.w();
+                                    let text = masked(&text, mask);
                                     state.line_infos =
                                         line_infos(&text, font, font_size, line_wrap, w)
                                             .collect();
@@ -524,16 +630,17 @@ impl<'a> Widget for TextEdit<'a> {
                                 let line_infos = state.line_infos.iter().cloned();
                                 match (key, move_word) {
                                     (input::Key::Left, true) => cursor_idx
-                                        .previous_word_start(&text, line_infos),
+                                        .previous_word_start(&layout_text, line_infos),
                                     (input::Key::Right, true) => cursor_idx
-                                        .next_word_end(&text, line_infos),
+                                        .next_word_end(&layout_text, line_infos),
                                     (input::Key::Left, false) => cursor_idx
-                                        .previous(line_infos),
+                                        .left(&layout_text, line_infos),
                                     (input::Key::Right, false) => cursor_idx
-                                        .next(line_infos),
+                                        .right(&layout_text, line_infos),
 
                                     // Up/Down movement
-                                    _ => cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
+                                    _ => cursor_xy_at(cursor_idx, &layout_text,
+                                                      &state.line_infos, font)
                                         .and_then(|(x_pos, _)| {
                                             let text::cursor::Index { line, .. } = cursor_idx;
                                             let next_line = match key {
@@ -541,7 +648,7 @@ impl<'a> Widget for TextEdit<'a> {
                                                 input::Key::Down => line + 1,
                                                 _ => unreachable!(),
                                             };
-                                            closest_cursor_index_on_line(x_pos, next_line, &text, &state.line_infos, font)
+                                            closest_cursor_index_on_line(x_pos, next_line, &layout_text, &state.line_infos, font)
                                         })
                                 }.unwrap_or(cursor_idx)
                             };
@@ -575,10 +682,10 @@ impl<'a> Widget for TextEdit<'a> {
                                                 let line_infos = state.line_infos.iter().cloned();
                                                 match key {
                                                     input::Key::Left | input::Key::Up => {
-                                                        cursor_idx.previous_word_start(&text, line_infos)
+                                                        cursor_idx.previous_word_start(&layout_text, line_infos)
                                                     },
                                                     input::Key::Right | input::Key::Down => {
-                                                        cursor_idx.next_word_end(&text, line_infos)
+                                                        cursor_idx.next_word_end(&layout_text, line_infos)
                                                     }
                                                     _ => unreachable!(),
                                                 }.unwrap_or(cursor_idx)
@@ -645,13 +752,104 @@ impl<'a> Widget for TextEdit<'a> {
                             }
                         },
 
+                        // Copy the selected text on Ctrl+c, also removing it on Ctrl+x.
+                        input::Key::C | input::Key::X => {
+                            if !press.modifiers.contains(input::keyboard::CTRL) || is_password {
+                                continue 'events;
+                            }
+                            let (start, end) = match cursor {
+                                Cursor::Selection { start, end } => (start, end),
+                                Cursor::Idx(_) => continue 'events,
+                            };
+                            let (start_idx, end_idx) = {
+                                let line_infos = state.line_infos.iter().cloned();
+                                (text::glyph::index_after_cursor(line_infos.clone(), start),
+                                 text::glyph::index_after_cursor(line_infos, end))
+                            };
+                            if let (Some(start_idx), Some(end_idx)) = (start_idx, end_idx) {
+                                let (start_idx, end_idx) = (std::cmp::min(start_idx, end_idx),
+                                                            std::cmp::max(start_idx, end_idx));
+                                let selected = text.chars()
+                                    .skip(start_idx)
+                                    .take(end_idx - start_idx)
+                                    .collect();
+                                ui.clipboard().set_contents(selected);
+                                if key == input::Key::X {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    match insert_text("", cursor, &text, &state.line_infos, font) {
+                                        Some((new_text, new_cursor, new_line_infos)) => {
+                                            let snapshot = (text.to_string(), cursor);
+                                            *text.to_mut() = new_text;
+                                            cursor = new_cursor;
+                                            state.update(|state| {
+                                                state.undo_stack.push(snapshot);
+                                                state.line_infos = new_line_infos;
+                                            });
+                                        }, _ => ()
+                                    }
+                                }
+                            }
+                        },
+
+                        // Paste at the cursor on Ctrl+v, replacing the selected text (if any).
+                        input::Key::V => {
+                            if !press.modifiers.contains(input::keyboard::CTRL) {
+                                continue 'events;
+                            }
+                            if let Some(string) = ui.clipboard().contents() {
+                                let font = ui.fonts.get(font_id).unwrap();
+                                match insert_text(&string, cursor, &text, &state.line_infos, font) {
+                                    Some((new_text, new_cursor, new_line_infos)) => {
+                                        let snapshot = (text.to_string(), cursor);
+                                        *text.to_mut() = new_text;
+                                        cursor = new_cursor;
+                                        state.update(|state| {
+                                            state.undo_stack.push(snapshot);
+                                            state.line_infos = new_line_infos;
+                                        });
+                                    }, _ => ()
+                                }
+                            }
+                        },
+
+                        // Undo the most recent edit on Ctrl+z, or redo the last undone edit on Ctrl+y.
+                        input::Key::Z | input::Key::Y => {
+                            if !press.modifiers.contains(input::keyboard::CTRL) {
+                                continue 'events;
+                            }
+                            let current = (text.to_string(), cursor);
+                            let mut restored = None;
+                            state.update(|state| {
+                                restored = match key {
+                                    input::Key::Z => state.undo_stack.undo(current),
+                                    _ => state.undo_stack.redo(current),
+                                };
+                            });
+                            if let Some((restored_text, restored_cursor)) = restored {
+                                *text.to_mut() = restored_text;
+                                cursor = restored_cursor;
+                                state.update(|state| {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    let w = rect.w();
+                                    let text = masked(&text, mask);
+                                    state.line_infos =
+                                        line_infos(&text, font, font_size, line_wrap, w)
+                                            .collect();
+                                });
+                            }
+                        },
+
                         input::Key::Return => {
                             let font = ui.fonts.get(font_id).unwrap();
                             match insert_text("\n", cursor, &text, &state.line_infos, font) {
                                 Some((new_text, new_cursor, new_line_infos)) => {
+                                    let snapshot = (text.to_string(), cursor);
                                     *text.to_mut() = new_text;
                                     cursor = new_cursor;
-                                    state.update(|state| state.line_infos = new_line_infos);
+                                    state.update(|state| {
+                                        state.undo_stack.push(snapshot);
+                                        state.line_infos = new_line_infos;
+                                    });
                                 }, _ => ()
                             }
                         },
@@ -670,6 +868,46 @@ impl<'a> Widget for TextEdit<'a> {
                     }
                 },
 
+                // Select the word or whitespace run under a double click, or the line under a
+                // triple click.
+                event::Widget::Click(click)
+                    if click.button == input::MouseButton::Left && click.count >= 2 =>
+                {
+                    let abs_xy = utils::vec2_add(click.xy, rect.xy());
+                    let infos = &state.line_infos;
+                    let font = ui.fonts.get(font_id).unwrap();
+                    let closest = closest_cursor_index_and_xy(abs_xy, &layout_text, infos, font);
+                    let clicked_idx = match closest {
+                        Some((clicked_idx, _)) => clicked_idx,
+                        None => continue 'events,
+                    };
+                    let line_info = match infos.get(clicked_idx.line) {
+                        Some(line_info) => *line_info,
+                        None => continue 'events,
+                    };
+                    cursor = if click.count == 2 {
+                        let char_idx = line_info.start_char + clicked_idx.char;
+                        let range = word_char_range(&layout_text, char_idx);
+                        let char_to_idx = |char_idx: usize| text::cursor::Index {
+                            line: clicked_idx.line,
+                            char: char_idx - line_info.start_char,
+                        };
+                        Cursor::Selection {
+                            start: char_to_idx(std::cmp::max(range.start, line_info.start_char)),
+                            end: char_to_idx(std::cmp::min(range.end, line_info.end_char())),
+                        }
+                    } else {
+                        Cursor::Selection {
+                            start: text::cursor::Index { line: clicked_idx.line, char: 0 },
+                            end: text::cursor::Index {
+                                line: clicked_idx.line,
+                                char: line_info.end_char() - line_info.start_char,
+                            },
+                        }
+                    };
+                    drag = None;
+                },
+
                 event::Widget::Text(event::Text { string, modifiers }) => {
                     if modifiers.contains(input::keyboard::CTRL)
                     || string.chars().count() == 0
@@ -688,12 +926,22 @@ impl<'a> Widget for TextEdit<'a> {
                         _ => ()
                     }
 
+                    // Consecutively typed characters are undone together.
+                    let is_single_char = string.chars().count() == 1;
                     let font = ui.fonts.get(font_id).unwrap();
                     match insert_text(&string, cursor, &text, &state.line_infos, font) {
                         Some((new_text, new_cursor, new_line_infos)) => {
+                            let snapshot = (text.to_string(), cursor);
                             *text.to_mut() = new_text;
                             cursor = new_cursor;
-                            state.update(|state| state.line_infos = new_line_infos);
+                            state.update(|state| {
+                                if is_single_char {
+                                    state.undo_stack.push_coalesced(snapshot);
+                                } else {
+                                    state.undo_stack.push(snapshot);
+                                }
+                                state.line_infos = new_line_infos;
+                            });
                         }, _ => ()
                     }
                 },
@@ -710,7 +958,7 @@ impl<'a> Widget for TextEdit<'a> {
                             let abs_xy = utils::vec2_add(drag_event.to, rect.xy());
                             let infos = &state.line_infos;
                             let font = ui.fonts.get(font_id).unwrap();
-                            match closest_cursor_index_and_xy(abs_xy, &text, infos, font) {
+                            match closest_cursor_index_and_xy(abs_xy, &layout_text, infos, font) {
                                 Some((end_cursor_idx, _)) =>
                                     cursor = Cursor::Selection {
                                         start: start_cursor_idx,
@@ -729,8 +977,56 @@ impl<'a> Widget for TextEdit<'a> {
                     }
                 },
 
+                // Display the in-progress IME composition, or replace it with the committed text.
+                event::Widget::Composition(composition) => match composition {
+
+                    event::Composition::PreEdit(string) => {
+                        let composition = if string.is_empty() { None } else { Some(string) };
+                        if state.composition != composition {
+                            state.update(|state| state.composition = composition);
+                        }
+                    },
+
+                    event::Composition::Commit(string) => {
+                        if state.composition.is_some() {
+                            state.update(|state| state.composition = None);
+                        }
+
+                        // An empty commit cancels the composition.
+                        if string.is_empty() {
+                            continue 'events;
+                        }
+
+                        let font = ui.fonts.get(font_id).unwrap();
+                        match insert_text(&string, cursor, &text, &state.line_infos, font) {
+                            Some((new_text, new_cursor, new_line_infos)) => {
+                                let snapshot = (text.to_string(), cursor);
+                                *text.to_mut() = new_text;
+                                cursor = new_cursor;
+                                state.update(|state| {
+                                    state.undo_stack.push(snapshot);
+                                    state.line_infos = new_line_infos;
+                                });
+                            }, _ => ()
+                        }
+                    },
+
+                },
+
+                // Abandon any composition when the keyboard is lost.
+                event::Widget::UncapturesKeyboard => {
+                    if state.composition.is_some() {
+                        state.update(|state| state.composition = None);
+                    }
+                },
+
                 _ => (),
             }
+
+            // Moving the cursor other than by typing ends the current run of typed characters.
+            if !is_text && cursor != prev_cursor {
+                state.update(|state| state.undo_stack.break_coalescing());
+            }
         }
 
         let cursor_has_changed = state.cursor != cursor;
@@ -750,16 +1046,35 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
-        let color = style.color(ui.theme());
+        // Display any in-progress IME composition at the cursor in place of the selected text,
+        // along with the cursor at the end of the composition and the number of composed chars.
+        let composed = match state.composition {
+            Some(ref composition) => {
+                let font = ui.fonts.get(font_id).unwrap();
+                let num_chars = composition.chars().count();
+                insert_text(composition, cursor, &text, &state.line_infos, font)
+                    .map(|(composed_text, composed_cursor, composed_infos)| {
+                        (composed_text, composed_infos, composed_cursor, num_chars)
+                    })
+            },
+            None => None,
+        };
+        let (display_text, display_infos, display_cursor) = match composed {
+            Some((ref composed_text, ref composed_infos, composed_cursor, _)) =>
+                (masked(composed_text, mask), &composed_infos[..], composed_cursor),
+            None => (masked(&text, mask), &state.line_infos[..], cursor),
+        };
+
+        let color = if enabled { style.color(ui.theme()) } else { ui.theme.disabled_color };
         let font_size = style.font_size(ui.theme());
-        let num_lines = state.line_infos.iter().count();
+        let num_lines = display_infos.iter().count();
         let text_height = text::height(num_lines, font_size, line_spacing);
         let text_y_range = Range::new(0.0, text_height).align_to(y_align, rect.y);
         let text_rect = Rect { x: rect.x, y: text_y_range };
 
         match line_wrap {
-            Wrap::Whitespace => widget::Text::new(&text).wrap_by_word(),
-            Wrap::Character => widget::Text::new(&text).wrap_by_character(),
+            Wrap::Whitespace => widget::Text::new(&display_text).wrap_by_word(),
+            Wrap::Character => widget::Text::new(&display_text).wrap_by_character(),
         }
             .font_id(font_id)
             .wh(text_rect.dim())
@@ -773,7 +1088,7 @@ impl<'a> Widget for TextEdit<'a> {
             .set(state.ids.text, ui);
 
         // Draw the line for the cursor.
-        let cursor_idx = match cursor {
+        let cursor_idx = match display_cursor {
             Cursor::Idx(idx) => idx,
             Cursor::Selection { end, .. } => end,
         };
@@ -785,7 +1100,7 @@ impl<'a> Widget for TextEdit<'a> {
 
         let (cursor_x, cursor_y_range) = {
             let font = ui.fonts.get(font_id).unwrap();
-            cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
+            cursor_xy_at(cursor_idx, &display_text, display_infos, font)
                 .unwrap_or_else(|| {
                     let x = rect.left();
                     let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
@@ -828,37 +1143,72 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
-        if let Cursor::Selection { start, end } = cursor {
-            let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+        // The rects spanning the displayed text between the given cursor indices on each line.
+        let range_rects = |start: text::cursor::Index, end: text::cursor::Index| -> Vec<Rect> {
+            let line_infos = display_infos.iter().cloned();
+            let lines = line_infos.clone().map(|info| &display_text[info.byte_range()]);
+            let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
+                                               x_align, y_align, line_spacing);
+            let lines_with_rects = lines.zip(line_rects.clone());
+            let font = ui.fonts.get(font_id).unwrap();
+            text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
+        };
 
-            let selected_rects: Vec<Rect> = {
-                let line_infos = state.line_infos.iter().cloned();
-                let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
-                let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
-                                                   x_align, y_align, line_spacing);
-                let lines_with_rects = lines.zip(line_rects.clone());
-                let font = ui.fonts.get(font_id).unwrap();
-                text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
-            };
+        let selected_rects = match display_cursor {
+            Cursor::Selection { start, end } =>
+                range_rects(std::cmp::min(start, end), std::cmp::max(start, end)),
+            Cursor::Idx(_) => Vec::new(),
+        };
 
-            // Ensure we have at least as many widgets as selected_rectangles.
-            if state.ids.selected_rectangles.len() < selected_rects.len() {
-                let num_rects = selected_rects.len();
-                let id_gen = &mut ui.widget_id_generator();
-                state.update(|state| state.ids.selected_rectangles.resize(num_rects, id_gen));
-            }
+        // The composition ends at the displayed cursor.
+        let underlined_rects = match composed {
+            Some((_, _, Cursor::Idx(end), num_chars)) => {
+                let line_infos = display_infos.iter().cloned();
+                let end_char = text::glyph::index_after_cursor(line_infos.clone(), end)
+                    .unwrap_or(0);
+                let start = text::cursor::index_before_char(line_infos,
+                                                            end_char.saturating_sub(num_chars))
+                    .unwrap_or(end);
+                range_rects(start, end)
+            },
+            _ => Vec::new(),
+        };
 
-            // Draw a semi-transparent `Rectangle` for the selected range across each line.
-            let selected_rect_color = color.highlighted().alpha(0.25);
-            let iter = state.ids.selected_rectangles.iter().zip(&selected_rects);
-            for (&selected_rectangle_id, selected_rect) in iter {
-                widget::Rectangle::fill(selected_rect.dim())
-                    .xy(selected_rect.xy())
-                    .color(selected_rect_color)
-                    .graphics_for(id)
-                    .parent(id)
-                    .set(selected_rectangle_id, ui);
-            }
+        // Ensure we have at least as many widgets as selected_rectangles.
+        if state.ids.selected_rectangles.len() < selected_rects.len() {
+            let num_rects = selected_rects.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.selected_rectangles.resize(num_rects, id_gen));
+        }
+
+        // Draw a semi-transparent `Rectangle` for the selected range across each line.
+        let selected_rect_color = color.highlighted().alpha(0.25);
+        let iter = state.ids.selected_rectangles.iter().zip(&selected_rects);
+        for (&selected_rectangle_id, selected_rect) in iter {
+            widget::Rectangle::fill(selected_rect.dim())
+                .xy(selected_rect.xy())
+                .color(selected_rect_color)
+                .graphics_for(id)
+                .parent(id)
+                .set(selected_rectangle_id, ui);
+        }
+
+        // Ensure we have at least as many widgets as composition_underlines.
+        if state.ids.composition_underlines.len() < underlined_rects.len() {
+            let num_rects = underlined_rects.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.composition_underlines.resize(num_rects, id_gen));
+        }
+
+        // Underline the composed text along the bottom of each line.
+        let iter = state.ids.composition_underlines.iter().zip(&underlined_rects);
+        for (&underline_id, underlined_rect) in iter {
+            widget::Rectangle::fill([underlined_rect.w(), COMPOSITION_UNDERLINE_THICKNESS])
+                .x_y(underlined_rect.x(), underlined_rect.bottom())
+                .color(color)
+                .graphics_for(id)
+                .parent(id)
+                .set(underline_id, ui);
         }
 
         take_if_owned(text)
@@ -870,3 +1220,58 @@ impl<'a> Widget for TextEdit<'a> {
 impl<'a> Colorable for TextEdit<'a> {
     builder_method!(color { style.color = Some(Color) });
 }
+
+
+/// The text to lay out and display in place of the given text.
+///
+/// If there is a `mask`, every char other than line breaks is replaced by it. As each char is
+/// replaced by exactly one char, cursor indices into the masked text refer to the same chars within
+/// the real text.
+fn masked(text: &str, mask: Option<char>) -> std::borrow::Cow<str> {
+    match mask {
+        None => std::borrow::Cow::Borrowed(text),
+        Some(mask) => {
+            let masked = text.chars().map(|c| if c == '\n' { c } else { mask }).collect();
+            std::borrow::Cow::Owned(masked)
+        },
+    }
+}
+
+/// The range of chars forming the run of either whitespace or non-whitespace chars at the given
+/// char index, never extending past a line break.
+///
+/// The char following the index is used to determine the kind of run, unless the index is at the
+/// end of a line, in which case the preceding char is used. If neither exists, the range is empty.
+fn word_char_range(text: &str, char_idx: usize) -> std::ops::Range<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_in_line = |idx: usize| chars.get(idx).map_or(false, |&c| c != '\n');
+    let idx = if is_in_line(char_idx) {
+        char_idx
+    } else if char_idx > 0 && is_in_line(char_idx - 1) {
+        char_idx - 1
+    } else {
+        return char_idx..char_idx;
+    };
+    let is_whitespace = chars[idx].is_whitespace();
+    let is_in_run = |c: char| c != '\n' && c.is_whitespace() == is_whitespace;
+    let start = chars[..idx].iter().rposition(|&c| !is_in_run(c)).map_or(0, |i| i + 1);
+    let end = chars[idx..].iter().position(|&c| !is_in_run(c)).map_or(chars.len(), |i| idx + i);
+    start..end
+}
+
+
+#[test]
+fn word_char_range_should_select_the_run_of_words_or_whitespace_at_the_index() {
+    let text = "foo  bar\nbaz";
+    // Within and at the edges of a word.
+    assert_eq!(0..3, word_char_range(text, 0));
+    assert_eq!(0..3, word_char_range(text, 2));
+    assert_eq!(5..8, word_char_range(text, 5));
+    // Within a run of whitespace.
+    assert_eq!(3..5, word_char_range(text, 3));
+    assert_eq!(3..5, word_char_range(text, 4));
+    // At the end of a line, the preceding word is selected without the line break.
+    assert_eq!(5..8, word_char_range(text, 8));
+    assert_eq!(9..12, word_char_range(text, 12));
+    assert_eq!(0..0, word_char_range("", 0));
+}
@@ -0,0 +1,308 @@
+//! A widget for displaying a queue of transient notifications.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Positionable,
+    Scalar,
+    Widget,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use text;
+use ui;
+use widget;
+
+
+/// Displays the messages of a `Queue` as a stack of toasts from the top of the widget downwards.
+///
+/// Each toast is dismissed automatically once it has been shown for its lifetime. Only as many
+/// toasts as the `Queue`'s capacity are shown at once. The rest wait in the `Queue` until a slot
+/// is freed.
+///
+/// The `Queue` is owned by the user so that messages may be pushed from anywhere in the
+/// application.
+pub struct Toasts<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    queue: &'a mut Queue,
+}
+
+/// A queue of messages, of which up to `capacity` are shown at a time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Queue {
+    capacity: usize,
+    shown: Vec<Toast>,
+    pending: VecDeque<Toast>,
+}
+
+/// A single message within a `Queue`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    /// The message to display.
+    pub message: String,
+    /// How long the message is shown before it is dismissed.
+    pub lifetime: Duration,
+    /// How long the message has been shown so far.
+    pub elapsed: Duration,
+}
+
+widget_style! {
+    /// Unique styling for the `Toasts` widget.
+    style Style {
+        /// The height of each toast.
+        - toast_h: Scalar { 40.0 }
+        /// The vertical space between each toast.
+        - spacing: Scalar { 5.0 }
+        /// The color of each toast.
+        - color: Color { theme.shape_color }
+        /// The color of each toast's message.
+        - label_color: Color { theme.label_color }
+        /// The font size of each toast's message.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display each toast's message.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        rectangles[],
+        labels[],
+    }
+}
+
+/// The state of the `Toasts` widget, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    /// The moment at which the `Queue` was last advanced, while it contains messages.
+    last_update: Option<Instant>,
+}
+
+
+impl Queue {
+
+    /// An empty `Queue` that shows up to `capacity` messages at once.
+    pub fn new(capacity: usize) -> Self {
+        Queue {
+            capacity: capacity,
+            shown: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Push a message that is to be shown for the given `lifetime`.
+    ///
+    /// If there is a free slot, the message is shown immediately. Otherwise, it is shown once all
+    /// messages pushed before it have been shown and a slot is freed.
+    pub fn push<S>(&mut self, message: S, lifetime: Duration)
+        where S: Into<String>,
+    {
+        let toast = Toast {
+            message: message.into(),
+            lifetime: lifetime,
+            elapsed: Duration::from_secs(0),
+        };
+        self.pending.push_back(toast);
+        self.fill_free_slots();
+    }
+
+    /// Advance the lifetime of all shown messages by `dt`, dismissing those that have expired and
+    /// showing pending messages in their place.
+    ///
+    /// Newly shown messages begin their lifetime from zero.
+    pub fn advance(&mut self, dt: Duration) {
+        for toast in &mut self.shown {
+            toast.elapsed += dt;
+        }
+        self.shown.retain(|toast| toast.elapsed < toast.lifetime);
+        self.fill_free_slots();
+    }
+
+    /// The messages that are currently shown, from oldest to newest.
+    pub fn shown(&self) -> &[Toast] {
+        &self.shown
+    }
+
+    /// The number of messages waiting for a free slot.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether there are no shown or pending messages.
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty() && self.pending.is_empty()
+    }
+
+    fn fill_free_slots(&mut self) {
+        while self.shown.len() < self.capacity {
+            match self.pending.pop_front() {
+                Some(toast) => self.shown.push(toast),
+                None => break,
+            }
+        }
+    }
+
+}
+
+
+impl<'a> Toasts<'a> {
+
+    /// Begin building a `Toasts` widget displaying the given `Queue`.
+    pub fn new(queue: &'a mut Queue) -> Self {
+        Toasts {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            queue: queue,
+        }
+    }
+
+    builder_methods!{
+        pub toast_h { style.toast_h = Some(Scalar) }
+        pub spacing { style.spacing = Some(Scalar) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a> Widget for Toasts<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            last_update: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Toasts.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Toasts { queue, .. } = self;
+
+        // Age the queue by the time passed since the last update.
+        if queue.is_empty() {
+            if state.last_update.is_some() {
+                state.update(|state| state.last_update = None);
+            }
+        } else {
+            let now = ui.now();
+            if let Some(last_update) = state.last_update {
+                queue.advance(now.duration_since(last_update));
+            }
+            state.update(|state| state.last_update = Some(now));
+            // Keep updating while there are messages to dismiss.
+            ui::ref_mut_from_ui_cell(ui).request_redraw();
+        }
+
+        let num_shown = queue.shown().len();
+        if state.ids.rectangles.len() < num_shown {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.rectangles.resize(num_shown, id_gen);
+                state.ids.labels.resize(num_shown, id_gen);
+            });
+        }
+
+        let toast_h = style.toast_h(&ui.theme);
+        let spacing = style.spacing(&ui.theme);
+        let color = style.color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        for (i, toast) in queue.shown().iter().enumerate() {
+            let rectangle_id = state.ids.rectangles[i];
+            widget::Rectangle::fill([rect.w(), toast_h])
+                .mid_top_with_margin_on(id, toast_top_offset(i, toast_h, spacing))
+                .color(color)
+                .graphics_for(id)
+                .parent(id)
+                .set(rectangle_id, ui);
+            widget::Text::new(&toast.message)
+                .and_then(font_id, widget::Text::font_id)
+                .middle_of(rectangle_id)
+                .color(label_color)
+                .font_size(font_size)
+                .graphics_for(id)
+                .parent(id)
+                .set(state.ids.labels[i], ui);
+        }
+    }
+
+}
+
+
+impl<'a> Colorable for Toasts<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// The distance from the top of the `Toasts` widget to the top of the toast at the given index.
+pub fn toast_top_offset(index: usize, toast_h: Scalar, spacing: Scalar) -> Scalar {
+    index as Scalar * (toast_h + spacing)
+}
+
+
+#[test]
+fn queue_should_dismiss_toasts_once_their_lifetime_has_elapsed() {
+    let mut queue = Queue::new(3);
+    queue.push("short", Duration::from_secs(1));
+    queue.push("long", Duration::from_secs(3));
+    assert_eq!(2, queue.shown().len());
+
+    queue.advance(Duration::from_millis(900));
+    assert_eq!(2, queue.shown().len());
+    queue.advance(Duration::from_millis(100));
+    assert_eq!(vec!["long"], queue.shown().iter().map(|t| &t.message[..]).collect::<Vec<_>>());
+    assert_eq!(Duration::from_secs(1), queue.shown()[0].elapsed);
+
+    queue.advance(Duration::from_secs(2));
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn queue_should_hold_overflowing_toasts_until_a_slot_is_freed() {
+    let mut queue = Queue::new(2);
+    queue.push("a", Duration::from_secs(1));
+    queue.push("b", Duration::from_secs(2));
+    queue.push("c", Duration::from_secs(1));
+    queue.push("d", Duration::from_secs(1));
+    let messages = |queue: &Queue| -> Vec<String> {
+        queue.shown().iter().map(|t| t.message.clone()).collect()
+    };
+    assert_eq!(vec!["a", "b"], messages(&queue));
+    assert_eq!(2, queue.pending_len());
+
+    // Pending toasts do not age while waiting.
+    queue.advance(Duration::from_secs(1));
+    assert_eq!(vec!["b", "c"], messages(&queue));
+    assert_eq!(Duration::from_secs(0), queue.shown()[1].elapsed);
+    assert_eq!(1, queue.pending_len());
+
+    queue.advance(Duration::from_secs(1));
+    assert_eq!(vec!["d"], messages(&queue));
+    assert_eq!(0, queue.pending_len());
+}
+
+#[test]
+fn toasts_should_be_stacked_from_the_top() {
+    assert_eq!(0.0, toast_top_offset(0, 40.0, 5.0));
+    assert_eq!(90.0, toast_top_offset(2, 40.0, 5.0));
+}
@@ -140,6 +140,11 @@ impl<'a> Widget for Toggle<'a> {
         let widget::UpdateArgs { id, state, style, rect, mut ui, .. } = args;
         let Toggle { value, enabled, maybe_label, .. } = self;
 
+        // A disabled toggle ignores all input and relinquishes any capture that it held.
+        if !enabled {
+            ui.release_captures(id);
+        }
+
         let times_clicked = TimesClicked {
             state: value,
             count: if enabled { ui.widget_input(id).clicks().left().count() as u16 } else { 0 },
@@ -149,10 +154,11 @@ impl<'a> Widget for Toggle<'a> {
         let dim = rect.dim();
         let border = style.border(ui.theme());
         let color = {
-            let color = style.color(ui.theme());
+            let color = if enabled { style.color(ui.theme()) } else { ui.theme.disabled_color };
             let new_value = times_clicked.clone().last().unwrap_or(value);
             let color = if new_value { color } else { color.with_luminance(0.1) };
             match ui.widget_input(id).mouse() {
+                _ if !enabled => color,
                 Some(mouse) =>
                     if mouse.buttons.left().is_down() { color.clicked() }
                     else { color.highlighted() },
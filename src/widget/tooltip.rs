@@ -0,0 +1,197 @@
+//! A small box of text describing the widget beneath the mouse.
+
+use {
+    Color,
+    Colorable,
+    Dimension,
+    Dimensions,
+    FontSize,
+    Point,
+    Positionable,
+    Scalar,
+    Ui,
+    Widget,
+};
+use std;
+use text;
+use widget;
+
+
+/// Displays some short text within a box, typically beside the mouse in order to describe the
+/// widget that it is hovering over.
+///
+/// By default, the box is fit to the text along with the style's `padding`. As the `Tooltip` is
+/// purely descriptive, it does not consume the mouse events that occur over it.
+///
+/// The `Ui` instantiates a `Tooltip` automatically for any widget given some text via the
+/// `Widget::tooltip` builder method, so the `Tooltip` rarely needs to be instantiated directly.
+pub struct Tooltip {
+    common: widget::CommonBuilder,
+    style: Style,
+    text: std::borrow::Cow<'static, str>,
+}
+
+/// The distance beneath the mouse at which the top of a tooltip is placed, so that the tooltip
+/// is not obscured by the cursor.
+pub const CURSOR_OFFSET: Scalar = 20.0;
+
+widget_style! {
+    /// Unique styling for the `Tooltip`.
+    style Style {
+        /// The color of the box behind the text.
+        - color: Color { theme.shape_color }
+        /// The color of the text.
+        - label_color: Color { theme.label_color }
+        /// The font size of the text.
+        - label_font_size: FontSize { theme.font_size_small }
+        /// The ID of the font used to display the text.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+        /// The distance between the text and each edge of the box.
+        - padding: Scalar { 6.0 }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        text,
+    }
+}
+
+/// The state of the `Tooltip`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+
+impl Tooltip {
+
+    /// Begin building a `Tooltip` displaying the given text, either a `&'static str` or an owned
+    /// `String`.
+    pub fn new<T>(text: T) -> Self
+        where T: Into<std::borrow::Cow<'static, str>>,
+    {
+        let mut common = widget::CommonBuilder::new();
+        common.consumes_events = false;
+        Tooltip {
+            common: common,
+            style: Style::new(),
+            text: text.into(),
+        }
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    /// The dimensions of the text when displayed with the given style, or `None` if no font is
+    /// available.
+    fn text_dim(&self, ui: &Ui) -> Option<Dimensions> {
+        let font_size = self.style.label_font_size(&ui.theme);
+        self.style.label_font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id))
+            .map(|font| text::dimensions(&self.text, font_size, font))
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for Tooltip {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// If no specific width was given, the width of the text and its padding is used.
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        let text_w = self.text_dim(ui).map_or(0.0, |dim| dim[0]);
+        Dimension::Absolute(text_w + self.style.padding(&ui.theme) * 2.0)
+    }
+
+    /// If no specific height was given, the height of the text and its padding is used.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        let text_h = self.text_dim(ui).map_or(0.0, |dim| dim[1]);
+        Dimension::Absolute(text_h + self.style.padding(&ui.theme) * 2.0)
+    }
+
+    /// Update the state of the Tooltip.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Tooltip { text, .. } = self;
+
+        widget::Rectangle::fill(rect.dim())
+            .xy(rect.xy())
+            .color(style.color(&ui.theme))
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.rectangle, ui);
+
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        widget::Text::new(&text)
+            .and_then(font_id, widget::Text::font_id)
+            .middle_of(state.ids.rectangle)
+            .color(style.label_color(&ui.theme))
+            .font_size(style.label_font_size(&ui.theme))
+            .no_line_wrap()
+            .graphics_for(id)
+            .parent(id)
+            .set(state.ids.text, ui);
+    }
+
+}
+
+
+impl Colorable for Tooltip {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// The position of the centre of a tooltip with the given dimensions, such that its top left
+/// corner lies `CURSOR_OFFSET` beneath the mouse at `mouse_xy`.
+///
+/// Where the tooltip would otherwise extend beyond the edges of a window with the given
+/// dimensions (centred at the origin), it is shifted back within them. If it is taller or wider
+/// than the window, its top left corner is kept within the window.
+pub fn position(mouse_xy: Point, dim: Dimensions, window_dim: Dimensions) -> Point {
+    let (half_win_w, half_win_h) = (window_dim[0] / 2.0, window_dim[1] / 2.0);
+    let left = mouse_xy[0].min(half_win_w - dim[0]).max(-half_win_w);
+    let top = (mouse_xy[1] - CURSOR_OFFSET).max(dim[1] - half_win_h).min(half_win_h);
+    [left + dim[0] / 2.0, top - dim[1] / 2.0]
+}
+
+
+#[test]
+fn position_should_place_the_tooltip_beneath_the_mouse_and_keep_it_within_the_window() {
+    let window_dim = [800.0, 600.0];
+    assert_eq!([50.0, -35.0], position([0.0, 0.0], [100.0, 30.0], window_dim));
+    // Near the right and bottom edges, the tooltip is shifted back within the window.
+    assert_eq!([350.0, -285.0], position([390.0, -290.0], [100.0, 30.0], window_dim));
+    // A tooltip wider than the window begins at the window's left edge.
+    assert_eq!([100.0, -35.0], position([0.0, 0.0], [1000.0, 30.0], window_dim));
+}
@@ -0,0 +1,494 @@
+//! A widget for displaying and navigating a hierarchy of nodes, such as a file tree.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Positionable,
+    Scalar,
+    Widget,
+};
+use input;
+use std::collections::HashSet;
+use text;
+use widget;
+
+
+/// Displays a hierarchy of `Node`s as indented rows, where each node with children may be
+/// expanded or collapsed by clicking its arrow.
+///
+/// Clicking a row selects it. While the `TreeView` (or one of its rows) is capturing the
+/// keyboard, the `Up` and `Down` keys move the selection between visible rows, the `Right` key
+/// expands the selected node and the `Left` key collapses it (or, if it is already collapsed,
+/// selects its parent).
+///
+/// Collapsing a node that contains the selected node moves the selection to the collapsed node.
+pub struct TreeView<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    nodes: &'a [Node],
+}
+
+/// A single node within the hierarchy displayed by a `TreeView`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// The label displayed for the node.
+    pub label: String,
+    /// The children of the node, in order of display.
+    pub children: Vec<Node>,
+}
+
+/// The indices leading from the root nodes to some node, e.g. `[2, 0]` is the first child of the
+/// third root node.
+pub type Path = Vec<usize>;
+
+/// A single visible row of a `TreeView`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    /// The path to the node displayed by the row.
+    pub path: Path,
+    /// The depth of the node, where root nodes have a depth of `0`.
+    pub depth: usize,
+    /// Whether the node has any children.
+    pub has_children: bool,
+    /// Whether the node's children are visible.
+    pub is_expanded: bool,
+}
+
+/// The expanded nodes and the selected node of a `TreeView`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Outline {
+    expanded: HashSet<Path>,
+    selected: Option<Path>,
+}
+
+widget_style! {
+    /// Unique styling for the `TreeView`.
+    style Style {
+        /// The height of each row.
+        - row_h: Scalar { 24.0 }
+        /// The width by which each level of depth is indented, also used as the width of the
+        /// expand/collapse arrows.
+        - indent: Scalar { 16.0 }
+        /// The color of unselected rows.
+        - color: Color { theme.background_color }
+        /// The color of the selected row.
+        - selected_color: Color { theme.shape_color }
+        /// The color of the expand/collapse arrows and labels.
+        - label_color: Color { theme.label_color }
+        /// The font size of each label.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display each label.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        rows[],
+        arrows[],
+        labels[],
+    }
+}
+
+/// The state of the `TreeView`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+    outline: Outline,
+}
+
+
+impl Node {
+
+    /// A node with the given label and no children.
+    pub fn new<S>(label: S) -> Self
+        where S: Into<String>,
+    {
+        Node {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Append the given child to the node's children.
+    pub fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+
+}
+
+/// Find the node at the given `path` within the given `nodes`.
+pub fn node_at<'a>(nodes: &'a [Node], path: &[usize]) -> Option<&'a Node> {
+    let mut nodes = nodes;
+    let mut node = None;
+    for &i in path {
+        let n = match nodes.get(i) {
+            Some(n) => n,
+            None => return None,
+        };
+        nodes = &n.children;
+        node = Some(n);
+    }
+    node
+}
+
+
+impl Outline {
+
+    /// An `Outline` with all nodes collapsed and no selection.
+    pub fn new() -> Self {
+        Outline {
+            expanded: HashSet::new(),
+            selected: None,
+        }
+    }
+
+    /// The path to the selected node, if any.
+    pub fn selected(&self) -> Option<&[usize]> {
+        self.selected.as_ref().map(|path| &path[..])
+    }
+
+    /// Select the node at the given path.
+    pub fn select(&mut self, path: Option<Path>) {
+        self.selected = path;
+    }
+
+    /// Whether the node at the given path is expanded.
+    pub fn is_expanded(&self, path: &[usize]) -> bool {
+        self.expanded.contains(path)
+    }
+
+    /// Expand the node at the given path, revealing its children.
+    pub fn expand(&mut self, path: Path) {
+        self.expanded.insert(path);
+    }
+
+    /// Collapse the node at the given path, hiding its descendants.
+    ///
+    /// If one of the node's descendants is selected, the selection moves to the node itself.
+    pub fn collapse(&mut self, path: Path) {
+        let selection_is_hidden = self.selected.as_ref()
+            .map_or(false, |s| s.len() > path.len() && s.starts_with(&path));
+        if selection_is_hidden {
+            self.selected = Some(path.clone());
+        }
+        self.expanded.remove(&path);
+    }
+
+    /// Expand the node at the given path if it is collapsed, or collapse it if it is expanded.
+    pub fn toggle(&mut self, path: Path) {
+        if self.is_expanded(&path) {
+            self.collapse(path);
+        } else {
+            self.expand(path);
+        }
+    }
+
+    /// The visible rows of the given `nodes`, in order of display from the top.
+    ///
+    /// The descendants of collapsed nodes are not visible.
+    pub fn rows(&self, nodes: &[Node]) -> Vec<Row> {
+        let mut rows = Vec::new();
+        self.push_rows(nodes, &mut Vec::new(), &mut rows);
+        rows
+    }
+
+    fn push_rows(&self, nodes: &[Node], path: &mut Path, rows: &mut Vec<Row>) {
+        for (i, node) in nodes.iter().enumerate() {
+            path.push(i);
+            let is_expanded = self.is_expanded(path);
+            rows.push(Row {
+                path: path.clone(),
+                depth: path.len() - 1,
+                has_children: !node.children.is_empty(),
+                is_expanded: is_expanded,
+            });
+            if is_expanded {
+                self.push_rows(&node.children, path, rows);
+            }
+            path.pop();
+        }
+    }
+
+    /// Navigate the given `nodes` in response to the given key.
+    ///
+    /// - `Up` and `Down` select the previous and next visible rows.
+    /// - `Right` expands the selected node.
+    /// - `Left` collapses the selected node, or selects its parent if it is already collapsed.
+    ///
+    /// If nothing is selected, any of these keys selects the first row.
+    pub fn navigate(&mut self, nodes: &[Node], key: input::Key) {
+        let rows = self.rows(nodes);
+        let selected_idx = self.selected.as_ref()
+            .and_then(|selected| rows.iter().position(|row| &row.path == selected));
+        let idx = match selected_idx {
+            Some(idx) => idx,
+            None => {
+                match key {
+                    input::Key::Up | input::Key::Down | input::Key::Left | input::Key::Right =>
+                        self.selected = rows.first().map(|row| row.path.clone()),
+                    _ => (),
+                }
+                return;
+            },
+        };
+        let row = &rows[idx];
+        match key {
+            input::Key::Up => if idx > 0 {
+                self.selected = Some(rows[idx - 1].path.clone());
+            },
+            input::Key::Down => if idx + 1 < rows.len() {
+                self.selected = Some(rows[idx + 1].path.clone());
+            },
+            input::Key::Right => if row.has_children && !row.is_expanded {
+                self.expand(row.path.clone());
+            },
+            input::Key::Left => if row.is_expanded {
+                self.collapse(row.path.clone());
+            } else if row.depth > 0 {
+                self.selected = Some(row.path[..row.depth].to_vec());
+            },
+            _ => (),
+        }
+    }
+
+}
+
+
+impl<'a> TreeView<'a> {
+
+    /// Begin building a `TreeView` displaying the given root nodes.
+    pub fn new(nodes: &'a [Node]) -> Self {
+        TreeView {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            nodes: nodes,
+        }
+    }
+
+    builder_methods!{
+        pub row_h { style.row_h = Some(Scalar) }
+        pub indent { style.indent = Some(Scalar) }
+        pub selected_color { style.selected_color = Some(Color) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a> Widget for TreeView<'a> {
+    type State = State;
+    type Style = Style;
+    /// The path to the newly selected node, if the selection changed during the update.
+    type Event = Option<Path>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            outline: Outline::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the TreeView.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let TreeView { nodes, .. } = self;
+        let row_h = style.row_h(&ui.theme);
+        let indent = style.indent(&ui.theme);
+
+        // Handle clicks on the rows as they were displayed during the last update.
+        let mut outline = state.outline.clone();
+        let prev_rows = outline.rows(nodes);
+        for (row, &row_id) in prev_rows.iter().zip(state.ids.rows.iter()) {
+            for click in ui.widget_input(row_id).clicks().left() {
+                // The click position relative to the left edge of the row.
+                let x = click.xy[0] + rect.w() / 2.0;
+                let arrow_left = row.depth as Scalar * indent;
+                if row.has_children && x >= arrow_left && x < arrow_left + indent {
+                    outline.toggle(row.path.clone());
+                } else {
+                    outline.select(Some(row.path.clone()));
+                }
+            }
+        }
+
+        // Navigate with the arrow keys while the tree or one of its rows captures the keyboard.
+        let focus_ids = Some(id).into_iter().chain(state.ids.rows.iter().cloned());
+        for focus_id in focus_ids {
+            for press in ui.widget_input(focus_id).presses().key() {
+                outline.navigate(nodes, press.key);
+            }
+        }
+
+        let event = if outline.selected != state.outline.selected {
+            outline.selected.clone()
+        } else {
+            None
+        };
+        if outline != state.outline {
+            state.update(|state| state.outline = outline);
+        }
+
+        let rows = state.outline.rows(nodes);
+        if state.ids.rows.len() < rows.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            let num_rows = rows.len();
+            state.update(|state| {
+                state.ids.rows.resize(num_rows, id_gen);
+                state.ids.arrows.resize(num_rows, id_gen);
+                state.ids.labels.resize(num_rows, id_gen);
+            });
+        }
+
+        let color = style.color(&ui.theme);
+        let selected_color = style.selected_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        for (i, row) in rows.iter().enumerate() {
+            let row_id = state.ids.rows[i];
+            let is_selected = state.outline.selected.as_ref() == Some(&row.path);
+            widget::Rectangle::fill([rect.w(), row_h])
+                .mid_top_with_margin_on(id, i as Scalar * row_h)
+                .color(if is_selected { selected_color } else { color })
+                .parent(id)
+                .set(row_id, ui);
+
+            let arrow_x = -rect.w() / 2.0 + (row.depth as Scalar + 0.5) * indent;
+            if row.has_children {
+                let r = (indent.min(row_h) / 4.0).max(1.0);
+                let points = if row.is_expanded {
+                    vec![[-r, r], [r, r], [0.0, -r]]
+                } else {
+                    vec![[-r, r], [r, 0.0], [-r, -r]]
+                };
+                widget::Polygon::centred_fill(points)
+                    .x_y_relative_to(row_id, arrow_x, 0.0)
+                    .color(label_color)
+                    .graphics_for(row_id)
+                    .parent(id)
+                    .set(state.ids.arrows[i], ui);
+            }
+
+            let label = match node_at(nodes, &row.path) {
+                Some(node) => &node.label[..],
+                None => continue,
+            };
+            widget::Text::new(label)
+                .and_then(font_id, widget::Text::font_id)
+                .mid_left_with_margin_on(row_id, (row.depth as Scalar + 1.0) * indent)
+                .color(label_color)
+                .font_size(font_size)
+                .graphics_for(row_id)
+                .parent(id)
+                .set(state.ids.labels[i], ui);
+        }
+
+        event
+    }
+
+}
+
+
+impl<'a> Colorable for TreeView<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+#[cfg(test)]
+fn test_nodes() -> Vec<Node> {
+    vec![
+        Node::new("src")
+            .child(Node::new("widget").child(Node::new("button.rs")).child(Node::new("list.rs")))
+            .child(Node::new("lib.rs")),
+        Node::new("Cargo.toml"),
+    ]
+}
+
+#[test]
+fn rows_should_only_include_the_children_of_expanded_nodes() {
+    let nodes = test_nodes();
+    let mut outline = Outline::new();
+    let paths = |outline: &Outline| -> Vec<Path> {
+        outline.rows(&nodes).into_iter().map(|row| row.path).collect()
+    };
+    assert_eq!(vec![vec![0], vec![1]], paths(&outline));
+
+    outline.expand(vec![0]);
+    assert_eq!(vec![vec![0], vec![0, 0], vec![0, 1], vec![1]], paths(&outline));
+    let rows = outline.rows(&nodes);
+    assert_eq!(Row { path: vec![0, 0], depth: 1, has_children: true, is_expanded: false }, rows[1]);
+
+    outline.toggle(vec![0, 0]);
+    assert_eq!(vec![vec![0], vec![0, 0], vec![0, 0, 0], vec![0, 0, 1], vec![0, 1], vec![1]],
+               paths(&outline));
+
+    // Collapsing an ancestor hides all of its descendants, but remembers their expansion.
+    outline.toggle(vec![0]);
+    assert_eq!(vec![vec![0], vec![1]], paths(&outline));
+    outline.expand(vec![0]);
+    assert_eq!(6, paths(&outline).len());
+    assert_eq!("list.rs", node_at(&nodes, &[0, 0, 1]).unwrap().label);
+}
+
+#[test]
+fn collapsing_should_move_the_selection_from_a_descendant_to_the_collapsed_node() {
+    let mut outline = Outline::new();
+    outline.expand(vec![0]);
+    outline.expand(vec![0, 0]);
+    outline.select(Some(vec![0, 0, 1]));
+
+    // Collapsing an unrelated node leaves the selection.
+    outline.collapse(vec![1]);
+    assert_eq!(Some(&[0, 0, 1][..]), outline.selected());
+
+    outline.collapse(vec![0]);
+    assert_eq!(Some(&[0][..]), outline.selected());
+
+    // Collapsing the selected node itself leaves the selection.
+    outline.collapse(vec![0]);
+    assert_eq!(Some(&[0][..]), outline.selected());
+}
+
+#[test]
+fn arrow_keys_should_navigate_expand_and_collapse() {
+    use input::Key;
+    let nodes = test_nodes();
+    let mut outline = Outline::new();
+
+    outline.navigate(&nodes, Key::Down);
+    assert_eq!(Some(&[0][..]), outline.selected());
+    outline.navigate(&nodes, Key::Right);
+    assert!(outline.is_expanded(&[0]));
+    outline.navigate(&nodes, Key::Down);
+    outline.navigate(&nodes, Key::Right);
+    outline.navigate(&nodes, Key::Down);
+    outline.navigate(&nodes, Key::Down);
+    assert_eq!(Some(&[0, 0, 1][..]), outline.selected());
+
+    // `Left` on a collapsed node selects its parent, and on an expanded node collapses it.
+    outline.navigate(&nodes, Key::Left);
+    assert_eq!(Some(&[0, 0][..]), outline.selected());
+    outline.navigate(&nodes, Key::Left);
+    assert!(!outline.is_expanded(&[0, 0]));
+    assert_eq!(Some(&[0, 0][..]), outline.selected());
+
+    // `Up` and `Down` stop at either end.
+    outline.navigate(&nodes, Key::Up);
+    outline.navigate(&nodes, Key::Up);
+    assert_eq!(Some(&[0][..]), outline.selected());
+}
@@ -0,0 +1,330 @@
+//! A window within the `Ui` that may be moved by dragging its title bar and resized by dragging
+//! its edges.
+
+use {
+    color,
+    Color,
+    Colorable,
+    Dimensions,
+    FontSize,
+    MouseCursor,
+    Point,
+    Positionable,
+    Rect,
+    Scalar,
+    Widget,
+};
+use text;
+use widget;
+
+
+/// A window within the `Ui`, as found in MDI-style applications, consisting of a title bar above
+/// a body within which the user may instantiate the window's children.
+///
+/// Dragging the title bar moves the window, while dragging any of its edges resizes it. The
+/// window is kept within the kid area of its parent: a window dragged past the edge of its
+/// parent stops at the edge, and an edge cannot be dragged beyond the parent's. Each edge is also
+/// kept far enough from the opposite edge that the window is at least `min_dim` in size.
+///
+/// Like the `SplitPane`, the `Window` does not store its own position or dimensions. Instead the
+/// user positions and sizes it as usual and, upon receiving an `Event` with a new `rect`,
+/// positions and sizes it with the new rect during the following update, e.g. via
+/// `.xy(rect.xy()).wh(rect.dim())`. If the given rect must be clamped (e.g. a window restored
+/// within a smaller parent), the clamped rect is produced as though the window were dragged
+/// there.
+///
+/// Overlapping windows may be brought to the front when clicked by making them `floating`.
+pub struct Window<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    title: &'a str,
+    min_dim: Dimensions,
+}
+
+/// An edge of a `Window` that may be dragged to resize it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// The left edge.
+    Left,
+    /// The right edge.
+    Right,
+    /// The top edge, along the top of the title bar.
+    Top,
+    /// The bottom edge.
+    Bottom,
+}
+
+widget_style! {
+    /// Unique styling for the `Window`.
+    style Style {
+        /// The color of the window's body.
+        - color: Color { theme.background_color }
+        /// The color of the title bar.
+        - title_bar_color: Color { theme.shape_color }
+        /// The height of the title bar.
+        - title_bar_h: Scalar { 30.0 }
+        /// The thickness of the area along each edge that may be dragged to resize the window.
+        - edge_thickness: Scalar { 6.0 }
+        /// The color of the title.
+        - label_color: Color { theme.label_color }
+        /// The font size of the title.
+        - label_font_size: FontSize { theme.font_size_medium }
+        /// The ID of the font used to display the title.
+        - label_font_id: Option<text::font::Id> { theme.font_id }
+    }
+}
+
+widget_ids! {
+    struct Ids {
+        title_bar,
+        title,
+        body,
+        left_edge,
+        right_edge,
+        top_edge,
+        bottom_edge,
+    }
+}
+
+/// The state of the `Window`, to be cached within the `Ui`'s widget `Graph`.
+pub struct State {
+    ids: Ids,
+}
+
+/// The event produced by the `Window` each update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Event {
+    /// The new rect of the window, if it was moved, resized or had to be clamped.
+    pub rect: Option<Rect>,
+    /// The id of the window's body, to be used as the parent of the window's children.
+    pub body: widget::Id,
+}
+
+/// The minimum dimensions of a `Window` by default.
+pub const DEFAULT_MIN_DIM: Dimensions = [60.0, 60.0];
+
+
+impl<'a> Window<'a> {
+
+    /// Begin building a `Window` with the given title.
+    pub fn new(title: &'a str) -> Self {
+        Window {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            title: title,
+            min_dim: DEFAULT_MIN_DIM,
+        }
+    }
+
+    /// The minimum dimensions to which the window may be resized.
+    ///
+    /// The minimum height is never less than the height of the title bar. By default this is
+    /// `DEFAULT_MIN_DIM`.
+    pub fn min_dim(mut self, min_dim: Dimensions) -> Self {
+        self.min_dim = min_dim;
+        self
+    }
+
+    /// Specify the font used for displaying the title.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub title_bar_color { style.title_bar_color = Some(Color) }
+        pub title_bar_h { style.title_bar_h = Some(Scalar) }
+        pub edge_thickness { style.edge_thickness = Some(Scalar) }
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+    }
+
+}
+
+
+impl<'a> Widget for Window<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Window.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, maybe_parent_id, state, rect, style, ui, .. } = args;
+        let Window { title, min_dim, .. } = self;
+
+        let title_bar_h = style.title_bar_h(&ui.theme);
+        let edge_thickness = style.edge_thickness(&ui.theme);
+        let min_dim = [min_dim[0].max(0.0), min_dim[1].max(title_bar_h)];
+        let bounds = maybe_parent_id.and_then(|parent| ui.kid_area_of(parent))
+            .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], ui.window_dim()));
+
+        // Resize the window by the distance each edge was dragged, then move it by the distance
+        // that the title bar was dragged.
+        let mut new_rect = keep_within(rect, bounds);
+        let edges = [
+            (Edge::Left, state.ids.left_edge),
+            (Edge::Right, state.ids.right_edge),
+            (Edge::Top, state.ids.top_edge),
+            (Edge::Bottom, state.ids.bottom_edge),
+        ];
+        for &(edge, edge_id) in edges.iter() {
+            for drag in ui.widget_input(edge_id).drags().left() {
+                new_rect = resized(new_rect, edge, drag.delta_xy, min_dim, bounds);
+            }
+        }
+        for drag in ui.widget_input(state.ids.title_bar).drags().left() {
+            new_rect = moved(new_rect, drag.delta_xy, bounds);
+        }
+
+        // Instantiate the widgets at the window's new position so that it follows the mouse.
+        let title_bar = Rect::from_corners([new_rect.left(), new_rect.top() - title_bar_h],
+                                           new_rect.top_right());
+        let body = Rect::from_corners(new_rect.bottom_left(), title_bar.bottom_right());
+
+        widget::Rectangle::fill(title_bar.dim())
+            .xy(title_bar.xy())
+            .color(style.title_bar_color(&ui.theme))
+            .mouse_cursor(MouseCursor::Move)
+            .parent(id)
+            .set(state.ids.title_bar, ui);
+
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        widget::Text::new(title)
+            .and_then(font_id, widget::Text::font_id)
+            .middle_of(state.ids.title_bar)
+            .color(style.label_color(&ui.theme))
+            .font_size(style.label_font_size(&ui.theme))
+            .no_line_wrap()
+            .graphics_for(state.ids.title_bar)
+            .parent(id)
+            .set(state.ids.title, ui);
+
+        widget::Rectangle::fill(body.dim())
+            .xy(body.xy())
+            .color(style.color(&ui.theme))
+            .crop_kids()
+            .parent(id)
+            .set(state.ids.body, ui);
+
+        // Instantiate the edges last so that they lie above the title bar and body.
+        for &(edge, edge_id) in edges.iter() {
+            let (edge_rect, cursor) = match edge {
+                Edge::Left => (new_rect.pad_right(new_rect.w() - edge_thickness),
+                               MouseCursor::ResizeHorizontal),
+                Edge::Right => (new_rect.pad_left(new_rect.w() - edge_thickness),
+                                MouseCursor::ResizeHorizontal),
+                Edge::Top => (new_rect.pad_bottom(new_rect.h() - edge_thickness),
+                              MouseCursor::ResizeVertical),
+                Edge::Bottom => (new_rect.pad_top(new_rect.h() - edge_thickness),
+                                 MouseCursor::ResizeVertical),
+            };
+            widget::Rectangle::fill(edge_rect.dim())
+                .xy(edge_rect.xy())
+                .color(color::TRANSPARENT)
+                .mouse_cursor(cursor)
+                .parent(id)
+                .set(edge_id, ui);
+        }
+
+        Event {
+            rect: if new_rect != rect { Some(new_rect) } else { None },
+            body: state.ids.body,
+        }
+    }
+
+}
+
+
+impl<'a> Colorable for Window<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// Shift the given rect by the smallest distance that places it within the given bounds.
+///
+/// Rects that are larger than the bounds are aligned with the left and top of the bounds, so that
+/// the title bar of a window remains reachable.
+pub fn keep_within(rect: Rect, bounds: Rect) -> Rect {
+    let x = if rect.left() < bounds.left() {
+        bounds.left() - rect.left()
+    } else if rect.right() > bounds.right() {
+        (bounds.right() - rect.right()).max(bounds.left() - rect.left())
+    } else {
+        0.0
+    };
+    let y = if rect.top() > bounds.top() {
+        bounds.top() - rect.top()
+    } else if rect.bottom() < bounds.bottom() {
+        (bounds.bottom() - rect.bottom()).min(bounds.top() - rect.top())
+    } else {
+        0.0
+    };
+    rect.shift([x, y])
+}
+
+/// The rect of a window with the given rect after its title bar was dragged by the given
+/// distance, kept within the given bounds.
+pub fn moved(rect: Rect, delta: Point, bounds: Rect) -> Rect {
+    keep_within(rect.shift(delta), bounds)
+}
+
+/// The rect of a window with the given rect after the given edge was dragged by the given
+/// distance.
+///
+/// The dragged edge is kept within the given bounds and at least `min_dim` from the opposite
+/// edge, while all other edges remain in place.
+pub fn resized(rect: Rect, edge: Edge, delta: Point, min_dim: Dimensions, bounds: Rect) -> Rect {
+    let (mut l, mut r, mut b, mut t) = rect.l_r_b_t();
+    match edge {
+        Edge::Left => l = (l + delta[0]).min(r - min_dim[0]).max(bounds.left()),
+        Edge::Right => r = (r + delta[0]).max(l + min_dim[0]).min(bounds.right()),
+        Edge::Top => t = (t + delta[1]).max(b + min_dim[1]).min(bounds.top()),
+        Edge::Bottom => b = (b + delta[1]).min(t - min_dim[1]).max(bounds.bottom()),
+    }
+    Rect::from_corners([l, b], [r, t])
+}
+
+
+#[test]
+fn resized_should_keep_the_dragged_edge_within_the_bounds_and_minimum_dimensions() {
+    let bounds = Rect::from_corners([-100.0, -100.0], [100.0, 100.0]);
+    let rect = Rect::from_corners([-50.0, -50.0], [50.0, 50.0]);
+    let min_dim = [40.0, 30.0];
+    let resize = |edge, delta| resized(rect, edge, delta, min_dim, bounds).l_r_b_t();
+    assert_eq!((-70.0, 50.0, -50.0, 50.0), resize(Edge::Left, [-20.0, 5.0]));
+    assert_eq!((-100.0, 50.0, -50.0, 50.0), resize(Edge::Left, [-80.0, 0.0]));
+    assert_eq!((10.0, 50.0, -50.0, 50.0), resize(Edge::Left, [90.0, 0.0]));
+    assert_eq!((-50.0, 100.0, -50.0, 50.0), resize(Edge::Right, [70.0, 0.0]));
+    assert_eq!((-50.0, -10.0, -50.0, 50.0), resize(Edge::Right, [-90.0, 0.0]));
+    assert_eq!((-50.0, 50.0, -50.0, 100.0), resize(Edge::Top, [0.0, 60.0]));
+    assert_eq!((-50.0, 50.0, -50.0, -20.0), resize(Edge::Top, [0.0, -90.0]));
+    assert_eq!((-50.0, 50.0, -100.0, 50.0), resize(Edge::Bottom, [0.0, -60.0]));
+    assert_eq!((-50.0, 50.0, 20.0, 50.0), resize(Edge::Bottom, [0.0, 90.0]));
+}
+
+#[test]
+fn keep_within_should_prefer_the_top_left_of_bounds_smaller_than_the_rect() {
+    let bounds = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let rect = Rect::from_corners([50.0, -80.0], [250.0, 70.0]);
+    assert_eq!((0.0, 200.0, -50.0, 100.0), keep_within(rect, bounds).l_r_b_t());
+    let rect = Rect::from_corners([-250.0, 120.0], [-50.0, 270.0]);
+    assert_eq!((0.0, 200.0, -50.0, 100.0), keep_within(rect, bounds).l_r_b_t());
+}
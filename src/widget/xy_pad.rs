@@ -131,9 +131,15 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
             x, min_x, max_x,
             y, min_y, max_y,
             maybe_label,
+            enabled,
             ..
         } = self;
 
+        // A disabled pad relinquishes any capture that it held, so that it receives no mouse.
+        if !enabled {
+            ui.release_captures(id);
+        }
+
         let border = style.border(ui.theme());
         let inner_rect = rect.pad(border);
 
@@ -168,7 +174,11 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
 
         // The backdrop **BorderedRectangle** widget.
         let dim = rect.dim();
-        let color = interaction_color(&ui, style.color(ui.theme()));
+        let color = if enabled {
+            interaction_color(&ui, style.color(ui.theme()))
+        } else {
+            ui.theme.disabled_color
+        };
         let border = style.border(ui.theme());
         let border_color = style.border_color(ui.theme());
         widget::BorderedRectangle::new(dim)